@@ -0,0 +1,54 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tobelog::handlers::api::{generate_excerpt, generate_slug};
+use tobelog::services::MarkdownService;
+
+/// A long post with frontmatter, code, tables and lists
+fn sample_post() -> String {
+    let mut post = String::from(
+        "---\ntitle: \"Benchmarking the blog\"\ncategory: tech\ntags: [rust, performance]\n\
+         published: true\nauthor: junichiro\n---\n\n# Benchmarking the blog\n\n",
+    );
+    for i in 0..30 {
+        post.push_str(&format!(
+            "## Section {i}\n\nRust keeps **rendering** fast, with [links](https://example.com/{i}) \
+             and `inline code`. 日本語の段落も含めて計測します。\n\n\
+             ```rust\nfn section_{i}() -> usize {{ {i} }}\n```\n\n\
+             | Name | Value |\n|------|-------|\n| item | {i} |\n\n- one\n- two\n- three\n\n"
+        ));
+    }
+    post
+}
+
+fn markdown_benchmarks(c: &mut Criterion) {
+    let markdown = MarkdownService::new();
+    let post = sample_post();
+    let body = markdown.parse_markdown(&post).unwrap().content;
+
+    c.bench_function("parse_markdown", |b| {
+        b.iter(|| markdown.parse_markdown(black_box(&post)).unwrap())
+    });
+    c.bench_function("markdown_to_html", |b| {
+        b.iter(|| markdown.markdown_to_html(black_box(&body)).unwrap())
+    });
+    c.bench_function("first_paragraph_excerpt", |b| {
+        b.iter(|| markdown.first_paragraph_excerpt(black_box(&body), 200))
+    });
+}
+
+fn utility_benchmarks(c: &mut Criterion) {
+    let ascii_body = "Rust keeps rendering fast.\n\n".repeat(200);
+
+    c.bench_function("generate_slug", |b| {
+        b.iter(|| {
+            generate_slug(black_box(
+                "Benchmarking the Blog: Markdown, Slugs & Diffs 2024",
+            ))
+        })
+    });
+    c.bench_function("generate_excerpt", |b| {
+        b.iter(|| generate_excerpt(black_box(&ascii_body), 200))
+    });
+}
+
+criterion_group!(benches, markdown_benchmarks, utility_benchmarks);
+criterion_main!(benches);
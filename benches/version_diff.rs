@@ -0,0 +1,66 @@
+use chrono::Utc;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use tobelog::models::{CreatePost, Post, PostVersion};
+use tobelog::services::{DatabaseService, MarkdownService, VersionService};
+
+/// Body of `lines` lines where every tenth line differs between `revision`s
+fn body(lines: usize, revision: usize) -> String {
+    (0..lines)
+        .map(|i| {
+            if i % 10 == 0 {
+                format!("Line {} of revision {}", i, revision)
+            } else {
+                format!("Line {} stays the same in every revision", i)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn version_diff_benchmarks(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let database = runtime
+        .block_on(DatabaseService::new("sqlite::memory:"))
+        .unwrap();
+    let versions = VersionService::new(database, MarkdownService::new());
+
+    let mut group = c.benchmark_group("diff_against_post");
+    for lines in [50, 500, 2000] {
+        let post = Post::new(CreatePost {
+            slug: "diff".to_string(),
+            title: "Current title".to_string(),
+            content: body(lines, 2),
+            html_content: String::new(),
+            category: None,
+            tags: vec![],
+            published: true,
+            featured: false,
+            author: None,
+            excerpt: None,
+            dropbox_path: "/posts/diff.md".to_string(),
+        });
+        let version = PostVersion {
+            id: 1,
+            post_id: post.id,
+            version: 1,
+            title: "Old title".to_string(),
+            content: body(lines, 1),
+            html_content: String::new(),
+            excerpt: None,
+            category: None,
+            tags: vec![],
+            metadata: None,
+            change_summary: None,
+            created_at: Utc::now(),
+            created_by: None,
+        };
+
+        group.bench_with_input(BenchmarkId::from_parameter(lines), &lines, |b, _| {
+            b.iter(|| versions.diff_against_post(black_box(&version), black_box(&post)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, version_diff_benchmarks);
+criterion_main!(benches);
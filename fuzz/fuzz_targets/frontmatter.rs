@@ -0,0 +1,46 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::sync::{Arc, OnceLock};
+use tobelog::services::dropbox::FileMetadata;
+use tobelog::services::storage::LocalStorage;
+use tobelog::services::{BlogStorageService, MarkdownService};
+
+struct Parsers {
+    markdown: MarkdownService,
+    blog_storage: BlogStorageService,
+    file_metadata: FileMetadata,
+}
+
+fn parsers() -> &'static Parsers {
+    static PARSERS: OnceLock<Parsers> = OnceLock::new();
+    PARSERS.get_or_init(|| Parsers {
+        markdown: MarkdownService::new(),
+        // Parsing never touches storage
+        blog_storage: BlogStorageService::new(Arc::new(LocalStorage::new(
+            std::env::temp_dir().join("tobelog-fuzz"),
+        ))),
+        file_metadata: FileMetadata {
+            name: "fuzz.md".to_string(),
+            path_lower: "/blogstorage/posts/fuzz.md".to_string(),
+            path_display: "/BlogStorage/posts/fuzz.md".to_string(),
+            size: None,
+            content_hash: None,
+            client_modified: None,
+            server_modified: None,
+        },
+    })
+}
+
+// Markdown files come from Dropbox, so any text must parse or fail without panicking
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let parsers = parsers();
+
+    let _ = parsers.markdown.extract_frontmatter(content);
+    let _ = parsers
+        .blog_storage
+        .parse_blog_post(content, &parsers.file_metadata);
+});
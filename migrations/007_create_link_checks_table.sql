@@ -0,0 +1,23 @@
+-- Migration 007: Create link checks table for the broken link checker
+-- Stores the latest check result for every link found in a published post
+
+CREATE TABLE IF NOT EXISTS link_checks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    link_type TEXT NOT NULL, -- 'internal' or 'external'
+    status_code INTEGER, -- NULL when the request itself failed
+    is_broken BOOLEAN NOT NULL DEFAULT FALSE,
+    error TEXT,
+    checked_at TEXT NOT NULL,
+
+    -- Foreign key constraint
+    FOREIGN KEY (post_id) REFERENCES posts (id) ON DELETE CASCADE,
+
+    -- One result per link per post
+    UNIQUE (post_id, url)
+);
+
+-- Create indexes for efficient querying
+CREATE INDEX IF NOT EXISTS idx_link_checks_post_id ON link_checks (post_id);
+CREATE INDEX IF NOT EXISTS idx_link_checks_broken ON link_checks (is_broken);
@@ -0,0 +1,5 @@
+-- Migration 008: Scheduled publishing support
+-- The posts.publish_at column (TEXT, RFC3339) is added by the migration runner
+-- when missing, since SQLite has no ADD COLUMN IF NOT EXISTS.
+
+CREATE INDEX IF NOT EXISTS idx_posts_publish_at ON posts (publish_at);
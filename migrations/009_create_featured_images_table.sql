@@ -0,0 +1,20 @@
+-- Migration 009: Featured images
+-- The posts.featured_media_id column (TEXT, media_files.id) is added by the
+-- migration runner when missing, since SQLite has no ADD COLUMN IF NOT EXISTS.
+
+CREATE INDEX IF NOT EXISTS idx_posts_featured_media_id ON posts (featured_media_id);
+
+-- Cropped renditions of each post's featured image
+CREATE TABLE IF NOT EXISTS featured_images (
+    post_id TEXT PRIMARY KEY,
+    media_id TEXT NOT NULL,
+    focal_x REAL NOT NULL DEFAULT 50.0,
+    focal_y REAL NOT NULL DEFAULT 50.0,
+    card_url TEXT NOT NULL,
+    og_url TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (media_id) REFERENCES media_files(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_featured_images_media_id ON featured_images (media_id);
@@ -0,0 +1,5 @@
+-- Migration 010: Content-addressed media deduplication
+-- The media_files.content_hash column (TEXT, hex SHA-256 of the uploaded bytes) is
+-- added by the migration runner when missing, since SQLite has no ADD COLUMN IF NOT EXISTS.
+
+CREATE INDEX IF NOT EXISTS idx_media_files_content_hash ON media_files (content_hash);
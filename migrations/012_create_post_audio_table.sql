@@ -0,0 +1,12 @@
+-- Migration 012: Audio enclosures for podcast episodes
+CREATE TABLE IF NOT EXISTS post_audio (
+    post_id TEXT PRIMARY KEY,
+    media_id TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    duration_seconds REAL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (media_id) REFERENCES media_files(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_audio_media_id ON post_audio (media_id);
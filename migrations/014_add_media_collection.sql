@@ -0,0 +1,5 @@
+-- Migration 014: Media collections
+-- The media_files.collection column (TEXT, free-form grouping such as "screenshots")
+-- is added by the migration runner when missing, since SQLite has no ADD COLUMN IF NOT EXISTS.
+
+CREATE INDEX IF NOT EXISTS idx_media_files_collection ON media_files (collection);
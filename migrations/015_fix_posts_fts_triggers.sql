@@ -0,0 +1,18 @@
+-- Migration 015: Fix FTS sync triggers
+-- posts_fts is an external-content table, so rows must be removed with the 'delete'
+-- command and the old column values. A plain DELETE re-reads the already updated
+-- posts row and fails with "database disk image is malformed".
+
+DROP TRIGGER IF EXISTS posts_fts_delete;
+CREATE TRIGGER posts_fts_delete AFTER DELETE ON posts BEGIN
+    INSERT INTO posts_fts(posts_fts, rowid, title, content, excerpt)
+    VALUES ('delete', old.rowid, old.title, old.content, COALESCE(old.excerpt, ''));
+END;
+
+DROP TRIGGER IF EXISTS posts_fts_update;
+CREATE TRIGGER posts_fts_update AFTER UPDATE ON posts BEGIN
+    INSERT INTO posts_fts(posts_fts, rowid, title, content, excerpt)
+    VALUES ('delete', old.rowid, old.title, old.content, COALESCE(old.excerpt, ''));
+    INSERT INTO posts_fts(rowid, title, content, excerpt)
+    VALUES (new.rowid, new.title, new.content, COALESCE(new.excerpt, ''));
+END;
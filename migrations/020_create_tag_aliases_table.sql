@@ -0,0 +1,6 @@
+-- Migration 020: Aliases mapping variant tag and category spellings to a canonical name
+CREATE TABLE IF NOT EXISTS tag_aliases (
+    alias TEXT PRIMARY KEY,
+    canonical TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
@@ -0,0 +1,18 @@
+-- Migration 024: Persistent background job queue
+CREATE TABLE IF NOT EXISTS jobs (
+    id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL, -- JSON handed to the job handler
+    status TEXT NOT NULL DEFAULT 'queued', -- 'queued', 'running', 'succeeded' or 'dead'
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    last_error TEXT,
+    result TEXT, -- JSON output of a succeeded job
+    run_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    started_at TEXT,
+    finished_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_status_run_at ON jobs (status, run_at);
+CREATE INDEX IF NOT EXISTS idx_jobs_created_at ON jobs (created_at);
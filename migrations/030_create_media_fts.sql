@@ -0,0 +1,30 @@
+-- Migration 030: Full-text search index for media
+-- media_fts is an external-content table over media_files, like posts_fts. Old rows
+-- are removed with the 'delete' command and the values that were indexed, so the
+-- triggers index NULL alt text and captions as empty strings on both sides.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS media_fts USING fts5(
+    filename,
+    original_filename,
+    alt_text,
+    caption,
+    content='media_files',
+    content_rowid='rowid'
+);
+
+CREATE TRIGGER IF NOT EXISTS media_fts_insert AFTER INSERT ON media_files BEGIN
+    INSERT INTO media_fts(rowid, filename, original_filename, alt_text, caption)
+    VALUES (new.rowid, new.filename, new.original_filename, COALESCE(new.alt_text, ''), COALESCE(new.caption, ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS media_fts_delete AFTER DELETE ON media_files BEGIN
+    INSERT INTO media_fts(media_fts, rowid, filename, original_filename, alt_text, caption)
+    VALUES ('delete', old.rowid, old.filename, old.original_filename, COALESCE(old.alt_text, ''), COALESCE(old.caption, ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS media_fts_update AFTER UPDATE OF filename, original_filename, alt_text, caption ON media_files BEGIN
+    INSERT INTO media_fts(media_fts, rowid, filename, original_filename, alt_text, caption)
+    VALUES ('delete', old.rowid, old.filename, old.original_filename, COALESCE(old.alt_text, ''), COALESCE(old.caption, ''));
+    INSERT INTO media_fts(rowid, filename, original_filename, alt_text, caption)
+    VALUES (new.rowid, new.filename, new.original_filename, COALESCE(new.alt_text, ''), COALESCE(new.caption, ''));
+END;
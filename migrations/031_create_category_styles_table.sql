@@ -0,0 +1,11 @@
+-- Migration 031: Per-category accents of a theme
+-- Each theme can give a category an accent color and a hero image, emitted as CSS
+-- variables for elements marked with data-category.
+
+CREATE TABLE IF NOT EXISTS category_styles (
+    theme_name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    accent_color TEXT NOT NULL,
+    hero_image TEXT,
+    PRIMARY KEY (theme_name, category)
+);
@@ -0,0 +1,16 @@
+-- Migration 032: Site-wide announcement banners
+-- Announcements without a start or end are shown from creation or until deleted.
+
+CREATE TABLE IF NOT EXISTS announcements (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    message TEXT NOT NULL,
+    link TEXT,
+    style TEXT NOT NULL DEFAULT 'info',
+    starts_at TEXT,
+    ends_at TEXT,
+    dismissible BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_announcements_window ON announcements(starts_at, ends_at);
@@ -0,0 +1,6 @@
+-- Migration 033: Scheduled content expiry
+-- The posts.expires_at (TEXT, RFC3339), posts.expiry_action and
+-- posts.expiry_notice_days columns are added by the migration runner when
+-- missing, since SQLite has no ADD COLUMN IF NOT EXISTS.
+
+CREATE INDEX IF NOT EXISTS idx_posts_expires_at ON posts (expires_at);
@@ -0,0 +1,13 @@
+-- Migration 035: Downloadable files (PDFs, archives, datasets) attached to posts
+CREATE TABLE IF NOT EXISTS post_attachments (
+    id TEXT PRIMARY KEY,
+    post_id TEXT NOT NULL,
+    media_id TEXT NOT NULL,
+    download_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (media_id) REFERENCES media_files(id) ON DELETE CASCADE,
+    UNIQUE (post_id, media_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_attachments_media_id ON post_attachments (media_id);
@@ -0,0 +1,16 @@
+-- Migration 037: TOTP two-factor authentication for the admin login
+CREATE TABLE IF NOT EXISTS admin_totp (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    secret TEXT NOT NULL,                -- Base32, as shown to authenticator apps
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    last_used_step INTEGER,              -- Refuses replaying an accepted code
+    created_at TEXT NOT NULL,
+    enabled_at TEXT
+);
+
+-- Recovery codes are only stored as SHA-256 hashes
+CREATE TABLE IF NOT EXISTS admin_recovery_codes (
+    code_hash TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL,
+    used_at TEXT
+);
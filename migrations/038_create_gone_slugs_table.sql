@@ -0,0 +1,7 @@
+-- Migration 038: Slugs of deleted posts, answered with 410 Gone instead of 404
+CREATE TABLE IF NOT EXISTS gone_slugs (
+    slug TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    created_at TEXT NOT NULL,            -- Of the deleted post, for year-based URLs
+    deleted_at TEXT NOT NULL
+);
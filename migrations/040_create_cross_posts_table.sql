@@ -0,0 +1,12 @@
+-- Migration 040: Copies of posts on external platforms (Zenn, Qiita, dev.to)
+CREATE TABLE IF NOT EXISTS cross_posts (
+    post_id TEXT NOT NULL,
+    provider TEXT NOT NULL,              -- zenn, qiita or dev_to
+    remote_id TEXT,                      -- Article ID on the platform
+    remote_url TEXT,
+    status TEXT NOT NULL,                -- published or failed
+    error TEXT,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (post_id, provider),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
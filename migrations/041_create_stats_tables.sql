@@ -0,0 +1,50 @@
+-- Migration 041: Post counts kept current by triggers, so stats reads don't scan posts
+CREATE TABLE IF NOT EXISTS stats (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    total_posts INTEGER NOT NULL DEFAULT 0,
+    published_posts INTEGER NOT NULL DEFAULT 0,
+    featured_posts INTEGER NOT NULL DEFAULT 0
+);
+
+-- Published posts per category
+CREATE TABLE IF NOT EXISTS category_stats (
+    category TEXT PRIMARY KEY,
+    count INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TRIGGER IF NOT EXISTS stats_posts_insert AFTER INSERT ON posts BEGIN
+    UPDATE stats SET
+        total_posts = total_posts + 1,
+        published_posts = published_posts + NEW.published,
+        featured_posts = featured_posts + NEW.featured
+    WHERE id = 1;
+    INSERT OR IGNORE INTO category_stats (category, count)
+        SELECT NEW.category, 0 WHERE NEW.category IS NOT NULL AND NEW.published;
+    UPDATE category_stats SET count = count + 1
+        WHERE category = NEW.category AND NEW.published;
+END;
+
+CREATE TRIGGER IF NOT EXISTS stats_posts_delete AFTER DELETE ON posts BEGIN
+    UPDATE stats SET
+        total_posts = total_posts - 1,
+        published_posts = published_posts - OLD.published,
+        featured_posts = featured_posts - OLD.featured
+    WHERE id = 1;
+    UPDATE category_stats SET count = count - 1
+        WHERE category = OLD.category AND OLD.published;
+    DELETE FROM category_stats WHERE count <= 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS stats_posts_update AFTER UPDATE OF published, featured, category ON posts BEGIN
+    UPDATE stats SET
+        published_posts = published_posts - OLD.published + NEW.published,
+        featured_posts = featured_posts - OLD.featured + NEW.featured
+    WHERE id = 1;
+    UPDATE category_stats SET count = count - 1
+        WHERE category = OLD.category AND OLD.published;
+    INSERT OR IGNORE INTO category_stats (category, count)
+        SELECT NEW.category, 0 WHERE NEW.category IS NOT NULL AND NEW.published;
+    UPDATE category_stats SET count = count + 1
+        WHERE category = NEW.category AND NEW.published;
+    DELETE FROM category_stats WHERE count <= 0;
+END;
@@ -0,0 +1,6 @@
+-- Migration 042: Descriptions shown for each category on the category index
+CREATE TABLE IF NOT EXISTS category_descriptions (
+    category TEXT PRIMARY KEY,
+    description TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -35,6 +35,7 @@ async fn main() -> Result<()> {
             "tobelogでの初めての投稿です。Rustで作ったブログシステムの動作テストを行います。"
                 .to_string(),
         ),
+        extra: Default::default(),
     };
 
     // Create test post content
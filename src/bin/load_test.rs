@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tobelog::handlers::api::{self, ApiState};
+use tobelog::models::{CreatePost, EndpointLoadStats, LoadTestReport, DEFAULT_LOAD_TEST_REPORT};
+use tobelog::services::i18n::SiteTimezone;
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::{
+    BlogStorageService, CacheService, CalendarService, DatabaseService, EventBus, ImportJobService,
+    JobQueue, JobQueueConfig, LLMImportService, MarkdownService, MediaService, StorageBackend,
+    SyncConflictService,
+};
+use tower::ServiceExt;
+use tracing::{info, Level};
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: cargo run --release --bin load_test [--posts <n>] [--requests <n>] [--concurrency <n>] [--output <path>]"
+    );
+    eprintln!("Runs the list, post and search API handlers against an in-memory fixture");
+    eprintln!(
+        "and writes the latencies to LOAD_TEST_REPORT (default {}), where",
+        DEFAULT_LOAD_TEST_REPORT
+    );
+    eprintln!("GET /api/performance/metrics picks them up.");
+    std::process::exit(1);
+}
+
+struct Options {
+    posts: usize,
+    requests: usize,
+    concurrency: usize,
+    output: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt().with_max_level(Level::WARN).init();
+
+    dotenv::dotenv().ok();
+
+    let mut options = Options {
+        posts: 200,
+        requests: 1000,
+        concurrency: 16,
+        output: env::var("LOAD_TEST_REPORT")
+            .unwrap_or_else(|_| DEFAULT_LOAD_TEST_REPORT.to_string()),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--posts" => options.posts = value.parse().unwrap_or_else(|_| usage()),
+            "--requests" => options.requests = value.parse().unwrap_or_else(|_| usage()),
+            "--concurrency" => options.concurrency = value.parse().unwrap_or_else(|_| usage()),
+            "--output" => options.output = value,
+            _ => usage(),
+        }
+    }
+    if options.posts == 0 || options.concurrency == 0 {
+        usage();
+    }
+
+    let app = fixture_app(options.posts).await?;
+    println!(
+        "🏋️ {} requests per endpoint, {} concurrent, {} posts",
+        options.requests, options.concurrency, options.posts
+    );
+
+    let mut endpoints = Vec::new();
+    for (name, path) in [
+        ("list_posts", "/api/posts?page=2&per_page=20"),
+        ("get_post", "/api/posts/{slug}"),
+        ("search_posts", "/api/search?q=rust&per_page=20"),
+    ] {
+        let stats = run_endpoint(&app, name, path, &options).await;
+        println!(
+            "{:<14} {:>8.1} req/s  p50 {:>7.2}ms  p95 {:>7.2}ms  p99 {:>7.2}ms  errors {}",
+            stats.name,
+            stats.requests_per_sec,
+            stats.p50_ms,
+            stats.p95_ms,
+            stats.p99_ms,
+            stats.errors
+        );
+        endpoints.push(stats);
+    }
+
+    let report = LoadTestReport {
+        finished_at: Utc::now(),
+        concurrency: options.concurrency,
+        posts: options.posts,
+        endpoints,
+    };
+    if let Some(parent) = std::path::Path::new(&options.output).parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    std::fs::write(&options.output, serde_json::to_vec_pretty(&report)?)
+        .with_context(|| format!("Failed to write {}", options.output))?;
+    info!("Report written to {}", options.output);
+    println!("📄 Report written to {}", options.output);
+
+    Ok(())
+}
+
+/// API router over an in-memory database holding `posts` published posts
+async fn fixture_app(posts: usize) -> Result<Router> {
+    let database = DatabaseService::in_memory().await?;
+    let markdown = MarkdownService::new();
+
+    for i in 0..posts {
+        let content = format!(
+            "# Post {i}\n\nNotes on Rust, async code and SQLite, part {i}.\n\n\
+             ```rust\nfn main() {{ println!(\"{i}\"); }}\n```\n\n{}",
+            "Benchmarks keep performance regressions measurable. ".repeat(40)
+        );
+        database
+            .create_post(CreatePost {
+                slug: format!("post-{}", i),
+                title: format!("Rust notes {}", i),
+                html_content: markdown.markdown_to_html(&content)?,
+                content,
+                category: Some(["tech", "life", "notes"][i % 3].to_string()),
+                tags: vec!["rust".to_string(), format!("topic-{}", i % 10)],
+                published: true,
+                featured: i % 25 == 0,
+                author: Some("load-test".to_string()),
+                excerpt: None,
+                dropbox_path: format!("/BlogStorage/posts/post-{}.md", i),
+            })
+            .await?;
+    }
+
+    let storage: Arc<dyn StorageBackend> = Arc::new(MemoryStorage::new());
+    let blog_storage = Arc::new(BlogStorageService::new(storage.clone()));
+    let llm_import = LLMImportService::new(markdown.clone(), database.clone());
+    let jobs = JobQueue::new(database.clone(), JobQueueConfig::default());
+    let state = ApiState {
+        database: database.clone(),
+        markdown: markdown.clone(),
+        blog_storage: blog_storage.clone(),
+        llm_import: llm_import.clone(),
+        media: MediaService::new(storage, blog_storage.clone(), database.clone()),
+        calendar: CalendarService::new(database.clone()),
+        cache: CacheService::new(),
+        api_key: None,
+        dropbox_root: "/BlogStorage".to_string(),
+        timezone: SiteTimezone::new(chrono_tz::UTC),
+        sync_conflicts: SyncConflictService::new(database.clone(), blog_storage, markdown),
+        max_pinned_posts: 3,
+        summarizer: None,
+        import_jobs: ImportJobService::new(llm_import, jobs.clone()),
+        jobs,
+        events: EventBus::new(),
+    };
+
+    Ok(Router::new()
+        .route("/api/posts", get(api::list_posts_api))
+        .route("/api/posts/:slug", get(api::get_post_api))
+        .route("/api/search", get(api::search_posts_api))
+        .with_state(state))
+}
+
+/// Send `options.requests` requests with `options.concurrency` in flight
+async fn run_endpoint(
+    app: &Router,
+    name: &str,
+    path: &str,
+    options: &Options,
+) -> EndpointLoadStats {
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+
+    let workers = (0..options.concurrency).map(|_| {
+        let app = app.clone();
+        let next = next.clone();
+        let path = path.to_string();
+        let (requests, posts) = (options.requests, options.posts);
+        tokio::spawn(async move {
+            let mut latencies = Vec::new();
+            let mut errors = 0;
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= requests {
+                    break;
+                }
+                let uri = path.replace("{slug}", &format!("post-{}", i % posts));
+                let request = Request::get(uri)
+                    .body(Body::empty())
+                    .expect("valid request");
+
+                let sent = Instant::now();
+                let status = match app.clone().oneshot(request).await {
+                    Ok(response) => response.status(),
+                    Err(never) => match never {},
+                };
+                latencies.push(sent.elapsed().as_secs_f64() * 1000.0);
+                if status != StatusCode::OK {
+                    errors += 1;
+                }
+            }
+            (latencies, errors)
+        })
+    });
+
+    let mut latencies = Vec::new();
+    let mut errors = 0;
+    for worker in futures_util::future::join_all(workers).await {
+        let (worker_latencies, worker_errors) = worker.expect("load test worker panicked");
+        latencies.extend(worker_latencies);
+        errors += worker_errors;
+    }
+    let elapsed: Duration = started.elapsed();
+
+    EndpointLoadStats::from_latencies(name, path, latencies, errors, elapsed)
+}
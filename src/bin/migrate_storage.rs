@@ -0,0 +1,62 @@
+use anyhow::Result;
+use std::env;
+use tobelog::models::StorageMigrationRequest;
+use tobelog::services::{DatabaseService, StorageMigrationService};
+use tobelog::Config;
+use tracing::{error, info, Level};
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: cargo run --bin migrate_storage <target> [--from <backend>] [--source-root <folder>] [--target-root <folder>]"
+    );
+    eprintln!("Backends: dropbox, local, s3 (--from defaults to STORAGE_BACKEND)");
+    eprintln!("Example: cargo run --bin migrate_storage s3 --from dropbox");
+    std::process::exit(1);
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    dotenv::dotenv().ok();
+
+    let mut args = env::args().skip(1);
+    let Some(target) = args.next() else { usage() };
+    let mut request = StorageMigrationRequest {
+        source: None,
+        target,
+        source_root: None,
+        target_root: None,
+    };
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--from" => request.source = Some(value),
+            "--source-root" => request.source_root = Some(value),
+            "--target-root" => request.target_root = Some(value),
+            _ => usage(),
+        }
+    }
+
+    let config = Config::from_env()?;
+    let database = DatabaseService::new(&config.database_url).await?;
+    let migrator = StorageMigrationService::new(database, config);
+
+    info!("📦 Migrating storage to {}...", request.target);
+    match migrator.migrate(&request).await {
+        Ok(report) => {
+            info!(
+                "✅ {} files copied ({} bytes), {} already present, {} database paths rewritten",
+                report.progress.copied_files,
+                report.progress.copied_bytes,
+                report.progress.skipped_files,
+                report.rewritten_paths
+            );
+            Ok(())
+        }
+        Err(e) => {
+            error!("❌ Storage migration failed: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+}
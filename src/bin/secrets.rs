@@ -0,0 +1,81 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::io::Read;
+use tobelog::services::secrets::{reencrypt_env_file, SecretBox};
+
+fn usage() -> ! {
+    eprintln!("Usage: cargo run --bin secrets <command>");
+    eprintln!("Commands:");
+    eprintln!("  keygen <key-file>                   Write a new random key file");
+    eprintln!(
+        "  encrypt <NAME>                      Encrypt the value read from stdin for variable NAME"
+    );
+    eprintln!("  rotate <env-file> [--new-key <key-file>]");
+    eprintln!("                                      Re-encrypt the credentials in env-file, with a new key if given");
+    eprintln!("The current key is read from SECRETS_KEY_FILE.");
+    std::process::exit(1);
+}
+
+fn current_key() -> Result<Option<SecretBox>> {
+    env::var("SECRETS_KEY_FILE")
+        .ok()
+        .map(SecretBox::from_key_file)
+        .transpose()
+}
+
+fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["keygen", path] => {
+            SecretBox::generate_key_file(path)?;
+            println!("🔑 Wrote {}; set SECRETS_KEY_FILE={} to use it", path, path);
+        }
+        ["encrypt", name] => {
+            let secrets = current_key()?.context("SECRETS_KEY_FILE is not set")?;
+            let mut value = String::new();
+            std::io::stdin().read_to_string(&mut value)?;
+            println!(
+                "{}",
+                secrets.encrypt(name, value.trim_end_matches(['\r', '\n']))?
+            );
+        }
+        ["rotate", path, rest @ ..] => {
+            let old = current_key()?;
+            let new = match rest {
+                [] => None,
+                ["--new-key", key_file] => Some(SecretBox::from_key_file(key_file)?),
+                _ => usage(),
+            };
+            let Some(new) = new.as_ref().or(old.as_ref()) else {
+                anyhow::bail!("Set SECRETS_KEY_FILE or pass --new-key");
+            };
+
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path))?;
+            let (rotated, count) = reencrypt_env_file(&contents, old.as_ref(), new)?;
+
+            // Write next to the file and rename, so a failure never leaves it half-written
+            let temp_path = format!("{}.tmp", path);
+            std::fs::write(&temp_path, rotated)
+                .with_context(|| format!("Failed to write {}", temp_path))?;
+            std::fs::set_permissions(&temp_path, std::fs::metadata(path)?.permissions())?;
+            std::fs::rename(&temp_path, path)
+                .with_context(|| format!("Failed to replace {}", path))?;
+
+            println!("✅ Encrypted {} credentials in {}", count, path);
+            if !rest.is_empty() {
+                println!("Point SECRETS_KEY_FILE at the new key before restarting the server");
+            }
+        }
+        _ => usage(),
+    }
+
+    Ok(())
+}
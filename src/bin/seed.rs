@@ -0,0 +1,50 @@
+use anyhow::Result;
+use std::sync::Arc;
+use tobelog::services::seed::SeedService;
+use tobelog::services::{
+    open_storage, BlogStorageService, DatabaseService, MarkdownService, MediaService, ThemeService,
+};
+use tobelog::Config;
+use tracing::{error, Level};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
+
+    dotenv::dotenv().ok();
+
+    if std::env::args().len() > 1 {
+        eprintln!("Usage: cargo run --bin seed");
+        eprintln!("Adds sample posts, images and themes to DATABASE_URL and STORAGE_BACKEND;");
+        eprintln!("existing posts with the same slugs are left alone.");
+        std::process::exit(1);
+    }
+
+    let config = Config::from_env()?;
+    let storage = open_storage(config.storage_backend.parse()?, &config)?;
+    let database = DatabaseService::new(&config.database_url).await?;
+    let blog_storage = Arc::new(BlogStorageService::new(storage.clone()));
+    let markdown = MarkdownService::new();
+
+    let seed = SeedService::new(
+        database.clone(),
+        markdown,
+        MediaService::new(storage.clone(), blog_storage, database.clone()),
+        ThemeService::new(database, storage),
+    );
+
+    println!("🌱 Seeding sample data...");
+    match seed.seed().await {
+        Ok(summary) => {
+            println!(
+                "✅ {} posts, {} images and {} themes added ({} posts already present)",
+                summary.posts, summary.media, summary.themes, summary.skipped_posts
+            );
+            Ok(())
+        }
+        Err(e) => {
+            error!("❌ Seeding failed: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -69,6 +69,7 @@ async fn main() -> Result<()> {
             excerpt: Some(
                 "This is a test blog post to verify the blog storage service.".to_string(),
             ),
+            extra: Default::default(),
         },
         content: r#"# Test Blog Post
 
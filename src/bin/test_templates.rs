@@ -3,6 +3,7 @@ use tokio;
 use tracing::{info, Level};
 use tracing_subscriber;
 
+use tobelog::models::PostVisibility;
 use tobelog::services::template::{
     HomePageContext, PostData, PostPageContext, PostSummary, TemplateService,
 };
@@ -66,6 +67,8 @@ async fn test_template_rendering() -> Result<()> {
             author: Some("Test Author".to_string()),
             published: true,
             featured: true,
+            pinned: false,
+            visibility: PostVisibility::Public,
             created_at: chrono::Utc::now(),
             published_at: Some(chrono::Utc::now()),
         },
@@ -79,6 +82,8 @@ async fn test_template_rendering() -> Result<()> {
             author: Some("Test Author".to_string()),
             published: true,
             featured: false,
+            pinned: false,
+            visibility: PostVisibility::Public,
             created_at: chrono::Utc::now(),
             published_at: Some(chrono::Utc::now()),
         },
@@ -129,14 +134,27 @@ async fn test_template_rendering() -> Result<()> {
         author: Some("Test Author".to_string()),
         published: true,
         featured: false,
+        visibility: PostVisibility::Public,
         created_at: chrono::Utc::now(),
         published_at: Some(chrono::Utc::now()),
+        expires_at: None,
+        outdated_notice: false,
+        archived: false,
+        syndication_links: vec!["https://dev.to/test/test-post".to_string()],
     };
 
     let post_context = PostPageContext {
         site_title: "Test Blog".to_string(),
         site_description: "A test blog".to_string(),
         post: sample_post,
+        og_image_url: None,
+        canonical_url: None,
+        audio: None,
+        changelog: None,
+        attachments: vec![],
+        pdf_url: None,
+        outline: vec![],
+        reading_progress: Default::default(),
     };
 
     let post_html = template_service.render("post.html", &post_context)?;
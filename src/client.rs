@@ -0,0 +1,250 @@
+//! Typed client for the HTTP API
+//!
+//! Requests and responses use the same models as the server handlers, so
+//! scripts talking to a running blog stay in step with the API.
+//!
+//! ```no_run
+//! # async fn run() -> anyhow::Result<()> {
+//! use tobelog::client::TobelogClient;
+//! use tobelog::handlers::api::ApiPostQuery;
+//!
+//! let client = TobelogClient::new("http://localhost:3000").with_api_key("secret");
+//! let posts = client.list_posts(&ApiPostQuery::default()).await?;
+//! println!("{} posts", posts.total);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use reqwest::{multipart, Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+use crate::handlers::api::{
+    ApiPostQuery, CreatePostRequest, PostOperationResponse, SyncDropboxRequest,
+};
+use crate::models::{ErrorResponse, JobEnqueuedResponse, MediaUploadResponse, PostListResponse};
+
+/// Optional form fields of a media upload
+#[derive(Debug, Clone, Default)]
+pub struct UploadMediaOptions {
+    /// Guessed from the filename when not set
+    pub content_type: Option<String>,
+    pub alt_text: Option<String>,
+    pub caption: Option<String>,
+    /// Store a new copy even when identical content was uploaded before
+    pub force: bool,
+    /// Attach the file to `post_slug` as a downloadable attachment
+    pub attachment: bool,
+    pub post_slug: Option<String>,
+}
+
+/// Client for a running Tobelog server
+#[derive(Debug, Clone)]
+pub struct TobelogClient {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl TobelogClient {
+    /// Create a client for the server at `base_url` (e.g. "http://localhost:3000")
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+        }
+    }
+
+    /// Send the API key with every request, as required by mutating endpoints
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// List posts; unpublished posts are only included with an API key
+    pub async fn list_posts(&self, query: &ApiPostQuery) -> Result<PostListResponse> {
+        let request = self.client.get(self.url("/api/posts")).query(query);
+        self.send(request, "list posts").await
+    }
+
+    /// Create a post
+    pub async fn create_post(&self, request: &CreatePostRequest) -> Result<PostOperationResponse> {
+        let request = self.client.post(self.url("/api/posts")).json(request);
+        self.send(request, "create post").await
+    }
+
+    /// Upload a media file under `filename`
+    pub async fn upload_media(
+        &self,
+        filename: &str,
+        bytes: Vec<u8>,
+        options: &UploadMediaOptions,
+    ) -> Result<MediaUploadResponse> {
+        let content_type = options.content_type.clone().unwrap_or_else(|| {
+            mime_guess::from_path(filename)
+                .first_or_octet_stream()
+                .to_string()
+        });
+        let file = multipart::Part::bytes(bytes)
+            .file_name(filename.to_string())
+            .mime_str(&content_type)
+            .context("Invalid media content type")?;
+
+        let mut form = multipart::Form::new().part("file", file);
+        if let Some(alt_text) = &options.alt_text {
+            form = form.text("alt_text", alt_text.clone());
+        }
+        if let Some(caption) = &options.caption {
+            form = form.text("caption", caption.clone());
+        }
+        if options.force {
+            form = form.text("force", "true");
+        }
+        if options.attachment {
+            form = form.text("attachment", "true");
+        }
+        if let Some(post_slug) = &options.post_slug {
+            form = form.text("post_slug", post_slug.clone());
+        }
+
+        let request = self
+            .client
+            .post(self.url("/api/media/upload"))
+            .multipart(form);
+        self.send(request, "upload media").await
+    }
+
+    /// Queue a Dropbox sync; follow `status_url` of the response for the result
+    pub async fn run_sync(&self, request: &SyncDropboxRequest) -> Result<JobEnqueuedResponse> {
+        let request = self
+            .client
+            .post(self.url("/api/sync/dropbox"))
+            .json(request);
+        self.send(request, "queue Dropbox sync").await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        mut request: RequestBuilder,
+        action: &str,
+    ) -> Result<T> {
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach the server to {}", action))?;
+        let response = check_status(response, action).await?;
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Invalid response to {}", action))
+    }
+}
+
+/// Turn an error status into an error carrying the server's message
+async fn check_status(response: Response, action: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<ErrorResponse>(&body)
+        .map(|error| error.message)
+        .unwrap_or(body);
+    Err(anyhow!("Failed to {} ({}): {}", action, status, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        extract::RawQuery,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+
+    async fn serve(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/", addr)
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_sends_query_and_api_key() {
+        let handler = |headers: HeaderMap, RawQuery(query): RawQuery| async move {
+            assert_eq!(headers["authorization"], "Bearer secret");
+            assert_eq!(query.as_deref(), Some("page=2&tag=rust"));
+            Json(serde_json::json!({
+                "posts": [],
+                "total": 0,
+                "page": 2,
+                "per_page": 10,
+                "total_pages": 0
+            }))
+        };
+        let app = Router::new().route("/api/posts", get(handler));
+        let client = TobelogClient::new(serve(app).await).with_api_key("secret");
+
+        let query = ApiPostQuery {
+            page: Some(2),
+            tag: Some("rust".to_string()),
+            ..Default::default()
+        };
+        let posts = client.list_posts(&query).await.unwrap();
+
+        assert_eq!(posts.page, 2);
+        assert!(posts.posts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_error_response_message_is_surfaced() {
+        let app = Router::new().route(
+            "/api/posts",
+            post(|| async {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    Json(ErrorResponse::new(
+                        "unauthorized".to_string(),
+                        "Invalid API key".to_string(),
+                        401,
+                    )),
+                )
+            }),
+        );
+        let client = TobelogClient::new(serve(app).await);
+
+        let request = CreatePostRequest {
+            title: "Hello".to_string(),
+            content: "Body".to_string(),
+            category: None,
+            tags: None,
+            published: None,
+            featured: None,
+            author: None,
+            canonical_url: None,
+            syndication_links: None,
+        };
+        let error = client.create_post(&request).await.unwrap_err();
+
+        assert!(error.to_string().contains("Invalid API key"));
+        assert!(error.to_string().contains("401"));
+    }
+}
@@ -1,30 +1,390 @@
 use anyhow::Result;
 use std::env;
 
+use crate::models::DEFAULT_LOAD_TEST_REPORT;
+use crate::services::secrets::{self, SecretBox};
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub host: String,
     pub port: u16,
     pub database_url: String,
+    pub database_max_connections: u32,
+    pub database_busy_timeout_ms: u64,
+    pub database_journal_mode: String,
+    pub database_synchronous: String,
+    pub database_read_pool_size: u32,
+    pub database_read_url: Option<String>,
+    /// Where posts, media and theme files are kept: "dropbox", "local" or "s3"
+    pub storage_backend: String,
+    /// Directory of the local storage backend
+    pub storage_local_path: String,
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: String,
+    pub s3_access_key_id: Option<String>,
+    pub s3_secret_access_key: Option<String>,
+    /// Key prefix of the blog's objects in the bucket
+    pub s3_prefix: String,
+    /// Required by the Dropbox storage backend
     pub dropbox_access_token: String,
+    /// Dropbox folder holding the blog; "edit in Dropbox" links are limited to it
+    pub dropbox_blog_root: String,
+    /// Consecutive Dropbox API failures that open the circuit breaker
+    pub dropbox_breaker_failures: u32,
+    /// Seconds Dropbox calls are short-circuited once the breaker opens
+    pub dropbox_breaker_cooldown_secs: u64,
+    /// Percentage of the Dropbox quota in use at which admins are warned
+    pub dropbox_quota_warning_percent: f64,
     pub api_key: Option<String>,
+    /// Token accepted by `POST /api/cache/purge` only, for external automation
+    pub cache_purge_token: Option<String>,
+    /// Networks (CIDR) allowed to reach `/admin` and mutating API routes; empty allows all
+    pub admin_ip_allow: Vec<String>,
+    /// Networks (CIDR) refused on the same routes, even when also allowed
+    pub admin_ip_deny: Vec<String>,
+    /// Reverse proxies whose `X-Forwarded-For`/`-Proto`/`-Host` headers are trusted
+    pub trusted_proxies: Vec<String>,
+    /// Lifetime of an admin login session
+    pub admin_session_ttl_hours: u64,
+    /// Age after which an admin session's token is replaced on the next request
+    pub admin_session_rotate_minutes: u64,
+    /// Key signing the cookies that unlock password-protected posts
+    pub post_password_secret: Option<String>,
+    pub post_password_cookie_ttl_secs: u64,
     pub template_theme: String,
+    pub site_url: String,
+    /// Deployment environment ("production", "staging", ...)
+    pub environment: String,
+    /// Honor `?theme=` and theme preview cookies (defaults to on outside production)
+    pub theme_preview: bool,
+    /// Serve Swagger UI at `/api/docs` (defaults to on outside production)
+    pub api_docs_ui: bool,
+    /// Read-only demo: refuse every request that would change data
+    pub demo_mode: bool,
+    /// Start in maintenance mode: public pages answer 503 until turned off
+    pub maintenance_mode: bool,
+    /// `Retry-After` seconds sent while in maintenance mode
+    pub maintenance_retry_after_secs: u64,
+    pub robots_disallow: Vec<String>,
+    pub security_contact: Option<String>,
+    pub compression_gzip: bool,
+    pub compression_brotli: bool,
+    /// Seconds rendered pages are cached for anonymous visitors; 0 disables it
+    pub response_cache_page_ttl_secs: u64,
+    /// Seconds feeds are cached for anonymous clients; 0 disables it
+    pub response_cache_feed_ttl_secs: u64,
+    pub tag_lowercase: bool,
+    pub category_lowercase: bool,
+    pub max_pinned_posts: i64,
+    pub link_check_interval_hours: u64,
+    pub job_workers: usize,
+    pub job_max_attempts: i64,
+    pub job_retry_delay_secs: u64,
+    pub scheduled_publish_interval_secs: u64,
+    /// Newest versions of each post kept by the version cleanup
+    pub version_retention_keep: Option<i32>,
+    /// Versions saved within this many days are kept by the version cleanup
+    pub version_retention_days: Option<i64>,
+    pub version_cleanup_interval_hours: u64,
+    pub proofread_enabled: bool,
+    pub textlint_url: Option<String>,
+    pub import_min_word_count: i64,
+    pub import_max_similarity: Option<f64>,
+    pub import_required_metadata: Vec<String>,
+    pub og_font_path: Option<String>,
+    pub og_cache_dir: String,
+    /// Headless Chromium binary used for PDF export (`pdf` feature)
+    pub pdf_chromium_path: String,
+    pub pdf_cache_dir: String,
+    /// Report of the last `load_test` run, shown with the performance metrics
+    pub load_test_report: String,
+    /// OTLP/HTTP collector receiving request traces; export is off when unset
+    pub otlp_endpoint: Option<String>,
+    pub otlp_service_name: String,
+    /// Queries and handlers slower than these are logged and kept in the slow log
+    pub slow_query_threshold_ms: u64,
+    pub slow_request_threshold_ms: u64,
+    pub slow_log_size: usize,
+    pub media_allow_svg: bool,
+    /// Largest accepted request body outside the media upload routes
+    pub api_body_limit_bytes: usize,
+    /// Largest media file accepted by the upload routes
+    pub media_upload_limit_bytes: usize,
+    pub podcast_category: String,
+    pub podcast_language: String,
+    pub podcast_explicit: bool,
+    pub alt_text_api_url: Option<String>,
+    pub alt_text_api_key: Option<String>,
+    pub alt_text_model: String,
+    pub alt_text_language: String,
+    pub summary_api_url: Option<String>,
+    pub summary_api_key: Option<String>,
+    pub summary_model: String,
+    pub summary_language: String,
+    /// CAPTCHA checked on public forms: turnstile, hcaptcha or recaptcha
+    pub captcha_provider: Option<String>,
+    pub captcha_site_key: Option<String>,
+    pub captcha_secret_key: Option<String>,
+    pub crosspost_qiita_token: Option<String>,
+    pub crosspost_devto_api_key: Option<String>,
+    pub crosspost_zenn_github_token: Option<String>,
+    /// `owner/repo` of the GitHub repository linked to Zenn
+    pub crosspost_zenn_repo: Option<String>,
+    pub crosspost_zenn_branch: String,
+    pub crosspost_zenn_username: Option<String>,
+    pub github_mirror_token: Option<String>,
+    /// `owner/repo` the published posts are committed to
+    pub github_mirror_repo: Option<String>,
+    pub github_mirror_branch: String,
+    pub github_mirror_folder: String,
+    /// Slack incoming webhook receiving the monthly digest
+    pub digest_slack_webhook_url: Option<String>,
+    pub notify_slack_webhook_url: Option<String>,
+    pub notify_discord_webhook_url: Option<String>,
+    /// Announce posts published through the API or the admin UI
+    pub notify_on_publish: bool,
+    /// Announce scheduled posts when they go live
+    pub notify_on_scheduled_publish: bool,
     // pub blog_title: String, // TODO: Use when implementing blog title feature
 }
 
 impl Config {
     pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Config from the variables returned by `var`, e.g. the environment
+    /// overlaid with reloaded `.env` entries
+    pub fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let port: u16 = var("SERVER_PORT")
+            .unwrap_or_else(|| "3000".to_string())
+            .parse()?;
+        let environment = var("APP_ENV").unwrap_or_else(|| "production".to_string());
+        // Credentials may be stored encrypted (`enc:v1:...`) with this key
+        let secrets = var("SECRETS_KEY_FILE")
+            .map(SecretBox::from_key_file)
+            .transpose()?;
+        let secret = |name: &str| {
+            var(name)
+                .map(|value| secrets::reveal(name, value, secrets.as_ref()))
+                .transpose()
+        };
+
         Ok(Config {
-            host: env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
-            port: env::var("SERVER_PORT")
-                .unwrap_or_else(|_| "3000".to_string())
-                .parse()?,
-            database_url: env::var("DATABASE_URL")
-                .unwrap_or_else(|_| "sqlite://blog.db".to_string()),
-            dropbox_access_token: env::var("DROPBOX_ACCESS_TOKEN")?,
-            api_key: env::var("API_KEY").ok(),
-            template_theme: env::var("BLOG_TEMPLATE").unwrap_or_else(|_| "default".to_string()),
-            // blog_title: env::var("BLOG_TITLE").unwrap_or_else(|_| "My Personal Blog".to_string()),
+            host: var("SERVER_HOST").unwrap_or_else(|| "0.0.0.0".to_string()),
+            port,
+            database_url: var("DATABASE_URL").unwrap_or_else(|| "sqlite://blog.db".to_string()),
+            database_max_connections: var("DATABASE_MAX_CONNECTIONS")
+                .unwrap_or_else(|| "10".to_string())
+                .parse()?,
+            database_busy_timeout_ms: var("DATABASE_BUSY_TIMEOUT_MS")
+                .unwrap_or_else(|| "5000".to_string())
+                .parse()?,
+            database_journal_mode: var("DATABASE_JOURNAL_MODE")
+                .unwrap_or_else(|| "wal".to_string()),
+            database_synchronous: var("DATABASE_SYNCHRONOUS")
+                .unwrap_or_else(|| "normal".to_string()),
+            database_read_pool_size: var("DATABASE_READ_POOL_SIZE")
+                .unwrap_or_else(|| "0".to_string())
+                .parse()?,
+            database_read_url: var("DATABASE_READ_URL"),
+            storage_backend: var("STORAGE_BACKEND").unwrap_or_else(|| "dropbox".to_string()),
+            storage_local_path: var("STORAGE_LOCAL_PATH").unwrap_or_else(|| "storage".to_string()),
+            s3_endpoint: var("S3_ENDPOINT"),
+            s3_bucket: var("S3_BUCKET"),
+            s3_region: var("S3_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+            s3_access_key_id: secret("S3_ACCESS_KEY_ID")?,
+            s3_secret_access_key: secret("S3_SECRET_ACCESS_KEY")?,
+            s3_prefix: var("S3_PREFIX").unwrap_or_default(),
+            dropbox_access_token: secret("DROPBOX_ACCESS_TOKEN")?.unwrap_or_default(),
+            dropbox_blog_root: var("DROPBOX_BLOG_ROOT")
+                .unwrap_or_else(|| "/BlogStorage".to_string()),
+            dropbox_breaker_failures: var("DROPBOX_BREAKER_FAILURES")
+                .unwrap_or_else(|| "5".to_string())
+                .parse()?,
+            dropbox_breaker_cooldown_secs: var("DROPBOX_BREAKER_COOLDOWN_SECS")
+                .unwrap_or_else(|| "60".to_string())
+                .parse()?,
+            dropbox_quota_warning_percent: var("DROPBOX_QUOTA_WARNING_PERCENT")
+                .unwrap_or_else(|| "90".to_string())
+                .parse()?,
+            api_key: secret("API_KEY")?,
+            cache_purge_token: secret("CACHE_PURGE_TOKEN")?,
+            admin_ip_allow: comma_list(var("ADMIN_IP_ALLOW")),
+            admin_ip_deny: comma_list(var("ADMIN_IP_DENY")),
+            trusted_proxies: comma_list(var("TRUSTED_PROXIES")),
+            admin_session_ttl_hours: var("ADMIN_SESSION_TTL_HOURS")
+                .unwrap_or_else(|| "12".to_string())
+                .parse()?,
+            admin_session_rotate_minutes: var("ADMIN_SESSION_ROTATE_MINUTES")
+                .unwrap_or_else(|| "30".to_string())
+                .parse()?,
+            post_password_secret: secret("POST_PASSWORD_SECRET")?,
+            post_password_cookie_ttl_secs: var("POST_PASSWORD_COOKIE_TTL_SECS")
+                .unwrap_or_else(|| "3600".to_string())
+                .parse()?,
+            template_theme: var("BLOG_TEMPLATE").unwrap_or_else(|| "default".to_string()),
+            site_url: var("SITE_URL").unwrap_or_else(|| format!("http://localhost:{}", port)),
+            environment: environment.clone(),
+            theme_preview: var("THEME_PREVIEW")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(environment != "production"),
+            api_docs_ui: var("API_DOCS_UI")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(environment != "production"),
+            demo_mode: var("DEMO_MODE")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            maintenance_mode: var("MAINTENANCE_MODE")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            maintenance_retry_after_secs: var("MAINTENANCE_RETRY_AFTER_SECS")
+                .unwrap_or_else(|| "300".to_string())
+                .parse()?,
+            robots_disallow: var("ROBOTS_DISALLOW")
+                .unwrap_or_else(|| "/admin,/api/,/staging".to_string())
+                .split(',')
+                .map(|path| path.trim().to_string())
+                .filter(|path| !path.is_empty())
+                .collect(),
+            security_contact: var("SECURITY_CONTACT"),
+            compression_gzip: var("COMPRESSION_GZIP")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            compression_brotli: var("COMPRESSION_BROTLI")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            response_cache_page_ttl_secs: var("RESPONSE_CACHE_PAGE_TTL_SECS")
+                .unwrap_or_else(|| "60".to_string())
+                .parse()?,
+            response_cache_feed_ttl_secs: var("RESPONSE_CACHE_FEED_TTL_SECS")
+                .unwrap_or_else(|| "300".to_string())
+                .parse()?,
+            tag_lowercase: var("TAG_LOWERCASE")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            category_lowercase: var("CATEGORY_LOWERCASE")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            max_pinned_posts: var("MAX_PINNED_POSTS")
+                .unwrap_or_else(|| "3".to_string())
+                .parse()?,
+            link_check_interval_hours: var("LINK_CHECK_INTERVAL_HOURS")
+                .unwrap_or_else(|| "24".to_string())
+                .parse()?,
+            job_workers: var("JOB_WORKERS")
+                .unwrap_or_else(|| "2".to_string())
+                .parse()?,
+            job_max_attempts: var("JOB_MAX_ATTEMPTS")
+                .unwrap_or_else(|| "3".to_string())
+                .parse()?,
+            job_retry_delay_secs: var("JOB_RETRY_DELAY_SECS")
+                .unwrap_or_else(|| "30".to_string())
+                .parse()?,
+            scheduled_publish_interval_secs: var("SCHEDULED_PUBLISH_INTERVAL_SECS")
+                .unwrap_or_else(|| "60".to_string())
+                .parse()?,
+            version_retention_keep: var("VERSION_RETENTION_KEEP")
+                .map(|v| v.parse())
+                .transpose()?,
+            version_retention_days: var("VERSION_RETENTION_DAYS")
+                .map(|v| v.parse())
+                .transpose()?,
+            version_cleanup_interval_hours: var("VERSION_CLEANUP_INTERVAL_HOURS")
+                .unwrap_or_else(|| "24".to_string())
+                .parse()?,
+            proofread_enabled: var("PROOFREAD_ENABLED")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            textlint_url: var("TEXTLINT_URL"),
+            import_min_word_count: var("IMPORT_MIN_WORD_COUNT")
+                .unwrap_or_else(|| "0".to_string())
+                .parse()?,
+            import_max_similarity: var("IMPORT_MAX_SIMILARITY")
+                .map(|v| v.parse())
+                .transpose()?,
+            import_required_metadata: var("IMPORT_REQUIRED_METADATA")
+                .unwrap_or_default()
+                .split(',')
+                .map(|field| field.trim().to_lowercase())
+                .filter(|field| !field.is_empty())
+                .collect(),
+            og_font_path: var("OG_FONT_PATH"),
+            og_cache_dir: var("OG_CACHE_DIR").unwrap_or_else(|| "cache/og".to_string()),
+            pdf_chromium_path: var("PDF_CHROMIUM_PATH").unwrap_or_else(|| "chromium".to_string()),
+            pdf_cache_dir: var("PDF_CACHE_DIR").unwrap_or_else(|| "cache/pdf".to_string()),
+            load_test_report: var("LOAD_TEST_REPORT")
+                .unwrap_or_else(|| DEFAULT_LOAD_TEST_REPORT.to_string()),
+            otlp_endpoint: var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                .filter(|endpoint| !endpoint.is_empty()),
+            otlp_service_name: var("OTEL_SERVICE_NAME").unwrap_or_else(|| "tobelog".to_string()),
+            slow_query_threshold_ms: var("SLOW_QUERY_THRESHOLD_MS")
+                .unwrap_or_else(|| "250".to_string())
+                .parse()?,
+            slow_request_threshold_ms: var("SLOW_REQUEST_THRESHOLD_MS")
+                .unwrap_or_else(|| "1000".to_string())
+                .parse()?,
+            slow_log_size: var("SLOW_LOG_SIZE")
+                .unwrap_or_else(|| "200".to_string())
+                .parse()?,
+            media_allow_svg: var("MEDIA_ALLOW_SVG")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            api_body_limit_bytes: var("API_BODY_LIMIT_BYTES")
+                .unwrap_or_else(|| (1024 * 1024).to_string())
+                .parse()?,
+            media_upload_limit_bytes: var("MEDIA_UPLOAD_LIMIT_BYTES")
+                .unwrap_or_else(|| (10 * 1024 * 1024).to_string())
+                .parse()?,
+            podcast_category: var("PODCAST_CATEGORY").unwrap_or_else(|| "Technology".to_string()),
+            podcast_language: var("PODCAST_LANGUAGE").unwrap_or_else(|| "ja".to_string()),
+            podcast_explicit: var("PODCAST_EXPLICIT")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            alt_text_api_url: var("ALT_TEXT_API_URL"),
+            alt_text_api_key: secret("ALT_TEXT_API_KEY")?,
+            alt_text_model: var("ALT_TEXT_MODEL").unwrap_or_else(|| "gpt-4o-mini".to_string()),
+            alt_text_language: var("ALT_TEXT_LANGUAGE").unwrap_or_else(|| "Japanese".to_string()),
+            summary_api_url: var("SUMMARY_API_URL"),
+            summary_api_key: secret("SUMMARY_API_KEY")?,
+            summary_model: var("SUMMARY_MODEL").unwrap_or_else(|| "gpt-4o-mini".to_string()),
+            summary_language: var("SUMMARY_LANGUAGE").unwrap_or_else(|| "Japanese".to_string()),
+            captcha_provider: var("CAPTCHA_PROVIDER"),
+            captcha_site_key: var("CAPTCHA_SITE_KEY"),
+            captcha_secret_key: secret("CAPTCHA_SECRET_KEY")?,
+            crosspost_qiita_token: secret("CROSSPOST_QIITA_TOKEN")?,
+            crosspost_devto_api_key: secret("CROSSPOST_DEVTO_API_KEY")?,
+            crosspost_zenn_github_token: secret("CROSSPOST_ZENN_GITHUB_TOKEN")?,
+            crosspost_zenn_repo: var("CROSSPOST_ZENN_REPO"),
+            crosspost_zenn_branch: var("CROSSPOST_ZENN_BRANCH")
+                .unwrap_or_else(|| "main".to_string()),
+            crosspost_zenn_username: var("CROSSPOST_ZENN_USERNAME"),
+            github_mirror_token: secret("GITHUB_MIRROR_TOKEN")?,
+            github_mirror_repo: var("GITHUB_MIRROR_REPO"),
+            github_mirror_branch: var("GITHUB_MIRROR_BRANCH").unwrap_or_else(|| "main".to_string()),
+            github_mirror_folder: var("GITHUB_MIRROR_FOLDER")
+                .unwrap_or_else(|| "posts".to_string()),
+            digest_slack_webhook_url: secret("DIGEST_SLACK_WEBHOOK_URL")?,
+            notify_slack_webhook_url: secret("NOTIFY_SLACK_WEBHOOK_URL")?,
+            notify_discord_webhook_url: secret("NOTIFY_DISCORD_WEBHOOK_URL")?,
+            notify_on_publish: var("NOTIFY_ON_PUBLISH")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            notify_on_scheduled_publish: var("NOTIFY_ON_SCHEDULED_PUBLISH")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            // blog_title: var("BLOG_TITLE").unwrap_or_else(|| "My Personal Blog".to_string()),
         })
     }
 }
+
+/// Comma-separated values of a variable, blanks skipped
+fn comma_list(value: Option<String>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
@@ -1,17 +1,29 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Html,
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Redirect, Response,
+    },
     Form,
 };
+use futures_util::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use tracing::{debug, error};
+use subtle::ConstantTimeEq;
+use tracing::{debug, error, info, warn};
 
 use crate::models::{
     response::{PostResponse, PostSummary},
-    LLMArticleImportRequest, PostFilters,
+    ActivityEntry, CalendarEntry, CalendarMonth, LLMArticleImportRequest, MediaFile, MediaFilters,
+    MediaType, Post, PostFilters, PostVersion, QualityCheckResults, TotpStatus, VersionDiff,
+    VersionHistory,
 };
-use crate::services::{DatabaseService, LLMImportService, MarkdownService, TemplateService};
+use crate::services::{
+    ActivityService, AdminSessionService, CalendarService, ClientInfo, DatabaseService, EventBus,
+    LLMImportService, MarkdownService, QualityGateError, StorageQuotaService, TemplateService,
+    TotpService, VersionService,
+};
+use crate::services::storage_quota::StorageQuota;
 
 /// Application state for admin handlers
 #[derive(Clone)]
@@ -21,6 +33,18 @@ pub struct AdminState {
     pub markdown: MarkdownService,
     pub templates: TemplateService,
     pub llm_import: LLMImportService,
+    pub calendar: CalendarService,
+    pub activity: ActivityService,
+    pub events: EventBus,
+    /// Dropbox folder that "edit in Dropbox" links must stay within
+    pub dropbox_root: String,
+    /// Set when Dropbox is the storage backend
+    pub storage_quota: Option<StorageQuotaService>,
+    pub versions: VersionService,
+    /// Key logging in to the admin pages; without one they stay open
+    pub api_key: Option<String>,
+    pub sessions: AdminSessionService,
+    pub totp: TotpService,
 }
 
 /// Form data for post creation/editing
@@ -35,6 +59,9 @@ pub struct PostFormData {
     pub featured: Option<bool>,
 }
 
+/// Number of entries in the dashboard's recent activity feed
+const DASHBOARD_ACTIVITY_LIMIT: usize = 15;
+
 /// Dashboard statistics
 #[derive(Debug, Serialize)]
 struct DashboardStats {
@@ -53,6 +80,8 @@ struct DashboardContext {
     draft_posts: Vec<crate::models::Post>,
     categories: Vec<crate::models::CategoryStat>,
     tags: Vec<crate::models::TagStat>,
+    activity: Vec<ActivityEntry>,
+    storage_quota: Option<StorageQuota>,
 }
 
 /// Post list context for template rendering
@@ -81,6 +110,168 @@ struct PostFormPost {
     tags: Vec<String>,
     published: bool,
     featured: bool,
+    /// Source file in the Dropbox web UI
+    dropbox_url: Option<String>,
+    /// Scheduled publication time in the site timezone, as a `datetime-local` value
+    publish_at_local: Option<String>,
+    /// Original URL of a cross-post
+    canonical_url: String,
+    /// URLs where the post is also published
+    syndication_links: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminLoginContext {
+    page_title: String,
+    /// Admin page to return to after logging in
+    next: String,
+    error: Option<String>,
+    /// Ask for an authenticator or recovery code as well
+    two_factor: bool,
+}
+
+/// Admin page to continue to after logging in; anything else falls back to the dashboard
+fn login_redirect_target(next: Option<&str>) -> String {
+    match next {
+        Some(next)
+            if (next == "/admin"
+                || next.starts_with("/admin/")
+                || next.starts_with("/admin?"))
+                && !next.contains("//")
+                && !next.contains('\\') =>
+        {
+            next.to_string()
+        }
+        _ => "/admin".to_string(),
+    }
+}
+
+async fn render_login(
+    state: &AdminState,
+    next: String,
+    error: Option<String>,
+    status: StatusCode,
+) -> Result<Response, (StatusCode, Html<String>)> {
+    let context = AdminLoginContext {
+        page_title: state.templates.translate("admin.login.title", &[]),
+        next,
+        error,
+        two_factor: two_factor_enabled(state).await?,
+    };
+
+    let html = state
+        .templates
+        .render("admin/login.html", &context)
+        .map_err(|e| {
+            error!("Template error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Template error".to_string()),
+            )
+        })?;
+
+    Ok((
+        status,
+        [(header::CACHE_CONTROL, "private, no-store")],
+        Html(html),
+    )
+        .into_response())
+}
+
+/// GET /admin/login - Login form for the admin pages
+pub async fn admin_login_page(
+    State(state): State<AdminState>,
+    Query(query): Query<AdminLoginQuery>,
+) -> Result<Response, (StatusCode, Html<String>)> {
+    if state.api_key.is_none() {
+        return Ok(Redirect::to("/admin").into_response());
+    }
+
+    let next = login_redirect_target(query.next.as_deref());
+    render_login(&state, next, None, StatusCode::OK).await
+}
+
+/// POST /admin/login - Check the API key and start a session
+pub async fn admin_login(
+    State(state): State<AdminState>,
+    client: ClientInfo,
+    Form(form): Form<AdminLoginForm>,
+) -> Result<Response, (StatusCode, Html<String>)> {
+    let Some(api_key) = &state.api_key else {
+        return Ok(Redirect::to("/admin").into_response());
+    };
+
+    let next = login_redirect_target(form.next.as_deref());
+    let two_factor = two_factor_enabled(&state).await?;
+    // Constant-time, so response timing doesn't reveal how much of the key matched
+    let key_valid = bool::from(form.api_key.as_bytes().ct_eq(api_key.as_bytes()));
+    let code_valid = match (two_factor, key_valid) {
+        (false, key_valid) => key_valid,
+        (true, false) => false,
+        (true, true) => {
+            let code = form.code.as_deref().unwrap_or_default();
+            state.totp.verify(code).await.map_err(|e| {
+                error!("Failed to verify two-factor code: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Html("ログインに失敗しました".to_string()),
+                )
+            })?
+        }
+    };
+    if !code_valid {
+        warn!("Failed admin login attempt from {}", client.ip);
+        // Don't reveal which of the two factors was wrong
+        let key = if two_factor {
+            "admin.login.invalid_two_factor"
+        } else {
+            "admin.login.invalid"
+        };
+        let error = state.templates.translate(key, &[]);
+        return render_login(&state, next, Some(error), StatusCode::UNAUTHORIZED).await;
+    }
+
+    let token = state.sessions.create().await.map_err(|e| {
+        error!("Failed to create admin session: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html("ログインに失敗しました".to_string()),
+        )
+    })?;
+
+    info!("Admin logged in from {}", client.ip);
+    Ok((
+        [(header::SET_COOKIE, state.sessions.cookie(&token, &client))],
+        Redirect::to(&next),
+    )
+        .into_response())
+}
+
+async fn two_factor_enabled(state: &AdminState) -> Result<bool, (StatusCode, Html<String>)> {
+    state.totp.is_enabled().await.map_err(|e| {
+        error!("Failed to load two-factor settings: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html("ログインに失敗しました".to_string()),
+        )
+    })
+}
+
+/// POST /admin/logout - End the admin session
+pub async fn admin_logout(
+    State(state): State<AdminState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(e) = state.sessions.revoke(&headers).await {
+        warn!("Failed to delete admin session: {}", e);
+    }
+
+    (
+        [(header::SET_COOKIE, state.sessions.clear_cookie(&client))],
+        Redirect::to("/admin/login"),
+    )
+        .into_response()
 }
 
 /// GET /admin - Admin dashboard
@@ -124,6 +315,17 @@ pub async fn dashboard(State(state): State<AdminState>) -> Result<Html<String>,
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
+    let activity = state.activity.recent(DASHBOARD_ACTIVITY_LIMIT).await;
+
+    let storage_quota = match &state.storage_quota {
+        Some(quota) => quota
+            .usage()
+            .await
+            .map_err(|e| warn!("Failed to get Dropbox storage usage: {:#}", e))
+            .ok(),
+        None => None,
+    };
+
     let dashboard_stats = DashboardStats {
         total_posts: stats.total_posts,
         published_posts: stats.published_posts,
@@ -132,12 +334,14 @@ pub async fn dashboard(State(state): State<AdminState>) -> Result<Html<String>,
     };
 
     let context = DashboardContext {
-        page_title: "Admin Dashboard".to_string(),
+        page_title: state.templates.translate("admin.dashboard.page_title", &[]),
         stats: dashboard_stats,
         recent_posts,
         draft_posts,
         categories: stats.categories,
         tags: stats.tags,
+        activity,
+        storage_quota,
     };
 
     let html = state
@@ -166,7 +370,7 @@ pub async fn posts_list(State(state): State<AdminState>) -> Result<Html<String>,
     })?;
 
     let context = PostListContext {
-        page_title: "Manage Posts".to_string(),
+        page_title: state.templates.translate("admin.posts.page_title", &[]),
         posts,
     };
 
@@ -186,7 +390,7 @@ pub async fn new_post_form(State(state): State<AdminState>) -> Result<Html<Strin
     debug!("Rendering new post form");
 
     let context = PostFormContext {
-        page_title: "Create New Post".to_string(),
+        page_title: state.templates.translate("admin.form.create_title", &[]),
         is_new: true,
         post: PostFormPost {
             id: None,
@@ -197,6 +401,10 @@ pub async fn new_post_form(State(state): State<AdminState>) -> Result<Html<Strin
             tags: Vec::new(),
             published: false,
             featured: false,
+            dropbox_url: None,
+            publish_at_local: None,
+            canonical_url: String::new(),
+            syndication_links: Vec::new(),
         },
     };
 
@@ -233,9 +441,21 @@ pub async fn edit_post_form(
 
     // Parse tags from JSON string to array
     let tags: Vec<String> = serde_json::from_str(&post.tags).unwrap_or_default();
+    let syndication_links = post.get_syndication_links();
+    let dropbox_url = post.dropbox_web_url(&state.dropbox_root);
+    let publish_at_local = post.publish_at.map(|publish_at| {
+        state
+            .templates
+            .site_timezone()
+            .to_local(&publish_at)
+            .format("%Y-%m-%dT%H:%M")
+            .to_string()
+    });
 
     let context = PostFormContext {
-        page_title: format!("Edit: {}", post.title),
+        page_title: state
+            .templates
+            .translate("admin.form.page_edit_title", &[("title", &post.title)]),
         is_new: false,
         post: PostFormPost {
             id: Some(post.id),
@@ -246,6 +466,10 @@ pub async fn edit_post_form(
             tags,
             published: post.published,
             featured: post.featured,
+            dropbox_url,
+            publish_at_local,
+            canonical_url: post.canonical_url.unwrap_or_default(),
+            syndication_links,
         },
     };
 
@@ -295,7 +519,7 @@ pub async fn admin_import_page(
     debug!("Admin: Loading import page");
 
     let context = AdminImportContext {
-        page_title: "LLM記事インポート".to_string(),
+        page_title: state.templates.translate("admin.import.page_title", &[]),
     };
 
     let html = state
@@ -322,7 +546,7 @@ pub async fn admin_process_import(
     if form_data.content.trim().is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
-            Html("コンテンツが空です".to_string()),
+            Html(state.templates.translate("admin.import.empty_content", &[])),
         ));
     }
 
@@ -353,6 +577,7 @@ pub async fn admin_process_import(
         source: form_data.source.clone(),
         published: Some(form_data.published),
         featured: Some(form_data.featured),
+        override_quality_gates: form_data.override_quality_gates,
     };
 
     // Process the import
@@ -364,7 +589,11 @@ pub async fn admin_process_import(
             error!("LLM import error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Html(format!("インポートエラー: {}", e)),
+                Html(
+                    state
+                        .templates
+                        .translate("admin.import.import_error", &[("error", &e.to_string())]),
+                ),
             )
         })?;
 
@@ -372,13 +601,24 @@ pub async fn admin_process_import(
     if form_data.published {
         if let Err(e) = state
             .llm_import
-            .save_imported_article(import_response.clone(), true)
+            .save_imported_article(
+                import_response.clone(),
+                true,
+                form_data.override_quality_gates,
+            )
             .await
         {
+            if e.downcast_ref::<QualityGateError>().is_some() {
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, Html(e.to_string())));
+            }
             error!("Failed to save imported article: {}", e);
             return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Html(format!("保存エラー: {}", e)),
+                Html(
+                    state
+                        .templates
+                        .translate("admin.import.save_error", &[("error", &e.to_string())]),
+                ),
             ));
         }
     }
@@ -395,6 +635,7 @@ pub async fn admin_process_import(
             .unwrap_or_default(),
         suggested_tags: import_response.suggested_metadata.tags.join(", "),
         saved_to_db: form_data.published,
+        quality: import_response.quality,
     };
 
     let html = state
@@ -437,7 +678,7 @@ pub async fn admin_edit_post_page(
     };
 
     let context = AdminEditPostContext {
-        post: PostResponse::from(post),
+        post: PostResponse::with_dropbox_link(post, &state.dropbox_root),
     };
 
     let html = state
@@ -454,6 +695,152 @@ pub async fn admin_edit_post_page(
     Ok(Html(html))
 }
 
+/// GET /admin/posts/{slug}/versions - Version history of a post
+pub async fn admin_post_versions_page(
+    Path(slug): Path<String>,
+    State(state): State<AdminState>,
+) -> Result<Html<String>, (StatusCode, Html<String>)> {
+    debug!("Admin: Loading version history for post: {}", slug);
+
+    let post = admin_find_post(&state, &slug).await?;
+    let history = state
+        .versions
+        .get_version_history(post.id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get version history for post {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Database error".to_string()),
+            )
+        })?;
+
+    let context = AdminVersionHistoryContext {
+        page_title: state
+            .templates
+            .translate("admin.versions.page_title", &[("title", &post.title)]),
+        current_version: post.version,
+        history,
+    };
+
+    let html = state
+        .templates
+        .render("admin/versions.html", &context)
+        .map_err(|e| {
+            error!("Template error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Template error".to_string()),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// GET /admin/posts/{slug}/versions/{version} - One stored version with its diff against the current post
+pub async fn admin_post_version_page(
+    Path((slug, version)): Path<(String, i32)>,
+    State(state): State<AdminState>,
+) -> Result<Html<String>, (StatusCode, Html<String>)> {
+    debug!("Admin: Loading version {} of post: {}", version, slug);
+
+    let post = admin_find_post(&state, &slug).await?;
+    let stored = state
+        .versions
+        .get_version(post.id, version)
+        .await
+        .map_err(|e| {
+            error!("Failed to get version {} of post {}: {}", version, slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Database error".to_string()),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Html(format!(
+                    "記事 '{}' のバージョン {} が見つかりません",
+                    slug, version
+                )),
+            )
+        })?;
+
+    let diff = state.versions.diff_against_post(&stored, &post);
+    // Identical content comes back as a "No changes" placeholder, not a diff
+    let content_lines = if stored.content == post.content {
+        Vec::new()
+    } else {
+        diff_lines(&diff.content_diff)
+    };
+    let context = AdminVersionContext {
+        page_title: state.templates.translate(
+            "admin.versions.version_title",
+            &[("version", &version.to_string()), ("title", &stored.title)],
+        ),
+        post_slug: post.slug,
+        current_version: post.version,
+        content_lines,
+        title_lines: diff.title_diff.as_deref().map(diff_lines),
+        version: stored,
+        diff,
+    };
+
+    let html = state
+        .templates
+        .render("admin/version.html", &context)
+        .map_err(|e| {
+            error!("Template error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Template error".to_string()),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+async fn admin_find_post(
+    state: &AdminState,
+    slug: &str,
+) -> Result<Post, (StatusCode, Html<String>)> {
+    state
+        .database
+        .get_post_by_slug(slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Database error".to_string()),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Html(format!("記事 '{}' が見つかりません", slug)),
+            )
+        })
+}
+
+/// Split the line diff from `VersionService` into lines the template can colour
+fn diff_lines(diff: &str) -> Vec<DiffLine> {
+    diff.lines()
+        .map(|line| {
+            let (kind, text) = match line.split_at_checked(2) {
+                Some(("+ ", text)) => ("added", text),
+                Some(("- ", text)) => ("removed", text),
+                Some(("  ", text)) => ("unchanged", text),
+                _ => ("unchanged", line),
+            };
+            DiffLine {
+                kind,
+                text: text.to_string(),
+            }
+        })
+        .collect()
+}
+
 /// GET /admin/posts - Post management page with enhanced features
 #[allow(dead_code)]
 pub async fn admin_posts_page(
@@ -535,7 +922,244 @@ pub async fn admin_posts_page(
     Ok(Html(html))
 }
 
+/// GET /admin/media - Media library with filters, usage counts and Markdown snippets
+pub async fn admin_media_page(
+    Query(query): Query<AdminMediaQuery>,
+    State(state): State<AdminState>,
+) -> Result<Html<String>, (StatusCode, Html<String>)> {
+    debug!("Admin: Loading media library with {:?}", query);
+
+    let db_error = |e: anyhow::Error| {
+        error!("Database error loading media library: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html("Database error".to_string()),
+        )
+    };
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = 24;
+    let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+
+    let filters = MediaFilters {
+        folder: non_empty(&query.folder),
+        mime_type: non_empty(&query.mime_type),
+        search: non_empty(&query.search),
+        collection: non_empty(&query.collection),
+        limit: None,
+        offset: None,
+    };
+
+    let total = state
+        .database
+        .count_media_files(filters.clone())
+        .await
+        .map_err(db_error)?;
+    let media_files = state
+        .database
+        .list_media_files(MediaFilters {
+            limit: Some(per_page as i64),
+            offset: Some(((page - 1) * per_page) as i64),
+            ..filters.clone()
+        })
+        .await
+        .map_err(db_error)?;
+
+    let media_ids: Vec<uuid::Uuid> = media_files.iter().map(|media| media.id).collect();
+    let usage_counts = state
+        .database
+        .get_media_usage_counts(&media_ids)
+        .await
+        .map_err(db_error)?;
+    let collections = state
+        .database
+        .list_media_collections()
+        .await
+        .map_err(db_error)?;
+
+    let items = media_files
+        .into_iter()
+        .map(|media| AdminMediaItem {
+            usage_count: usage_counts.get(&media.id).copied().unwrap_or(0),
+            is_image: MediaType::from_mime_type(&media.mime_type) == MediaType::Image,
+            markdown: markdown_snippet(&media),
+            media,
+        })
+        .collect();
+
+    let context = AdminMediaContext {
+        page_title: state.templates.translate("admin.media.page_title", &[]),
+        media: items,
+        current_page: page,
+        total_pages: total.div_ceil(per_page),
+        total_media: total,
+        filter_folder: filters.folder.unwrap_or_default(),
+        filter_mime_type: filters.mime_type.unwrap_or_default(),
+        filter_collection: filters.collection.unwrap_or_default(),
+        search_query: filters.search.unwrap_or_default(),
+        folders: [
+            MediaType::Image,
+            MediaType::Video,
+            MediaType::Audio,
+            MediaType::Document,
+            MediaType::Other,
+        ]
+        .iter()
+        .map(|media_type| media_type.folder_name())
+        .collect(),
+        collections,
+    };
+
+    let html = state
+        .templates
+        .render("admin/media.html", &context)
+        .map_err(|e| {
+            error!("Template error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Template error".to_string()),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// Markdown that embeds images and links to other files
+fn markdown_snippet(media: &MediaFile) -> String {
+    if MediaType::from_mime_type(&media.mime_type) == MediaType::Image {
+        format!(
+            "![{}]({})",
+            media.alt_text.as_deref().unwrap_or(""),
+            media.url
+        )
+    } else {
+        format!("[{}]({})", media.original_filename, media.url)
+    }
+}
+
 // Context structures for LLM templates
+/// GET /admin/calendar - Content calendar for planning the posting schedule
+pub async fn admin_calendar_page(
+    Query(query): Query<AdminCalendarQuery>,
+    State(state): State<AdminState>,
+) -> Result<Html<String>, (StatusCode, Html<String>)> {
+    debug!("Admin: Loading calendar for {:?}", query.month);
+
+    let calendar = state
+        .calendar
+        .get_month(query.month.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to build content calendar: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Html(format!("カレンダーを表示できません: {}", e)),
+            )
+        })?;
+
+    let context = AdminCalendarContext {
+        page_title: state
+            .templates
+            .translate("admin.calendar.page_title", &[("month", &calendar.month)]),
+        weeks: calendar_weeks(&calendar),
+        calendar,
+    };
+
+    let html = state
+        .templates
+        .render("admin/calendar.html", &context)
+        .map_err(|e| {
+            error!("Template error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Template error".to_string()),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// GET /admin/security - Two-factor authentication settings
+pub async fn admin_security_page(
+    State(state): State<AdminState>,
+) -> Result<Html<String>, (StatusCode, Html<String>)> {
+    debug!("Admin: Loading security settings");
+
+    let status = state.totp.status().await.map_err(|e| {
+        error!("Failed to load two-factor settings: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html("セキュリティ設定を読み込めません".to_string()),
+        )
+    })?;
+
+    let context = AdminSecurityContext {
+        page_title: state.templates.translate("admin.security.title", &[]),
+        status,
+    };
+
+    let html = state
+        .templates
+        .render("admin/security.html", &context)
+        .map_err(|e| {
+            error!("Template error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("Template error".to_string()),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// Lay the month out as Sunday-first weeks; `None` pads days outside the month
+fn calendar_weeks(calendar: &CalendarMonth) -> Vec<Vec<Option<CalendarCell>>> {
+    use chrono::{Datelike, NaiveDate};
+
+    let Ok(first_day) = NaiveDate::parse_from_str(&format!("{}-01", calendar.month), "%Y-%m-%d")
+    else {
+        return Vec::new();
+    };
+    let today = chrono::Utc::now().date_naive();
+
+    let mut cells: Vec<Option<CalendarCell>> =
+        vec![None; first_day.weekday().num_days_from_sunday() as usize];
+    for date in first_day
+        .iter_days()
+        .take_while(|d| d.month() == first_day.month())
+    {
+        let entries = calendar
+            .days
+            .iter()
+            .find(|day| day.date == date)
+            .map(|day| day.entries.clone())
+            .unwrap_or_default();
+        cells.push(Some(CalendarCell {
+            day: date.day(),
+            is_today: date == today,
+            entries,
+        }));
+    }
+    while !cells.len().is_multiple_of(7) {
+        cells.push(None);
+    }
+
+    cells.chunks(7).map(|week| week.to_vec()).collect()
+}
+
+/// GET /admin/events - Live notifications for open admin pages (Server-Sent Events)
+pub async fn admin_events(
+    State(state): State<AdminState>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    debug!("Admin: Event stream subscribed");
+
+    let stream = state
+        .events
+        .subscribe()
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 #[derive(Serialize)]
 struct AdminImportContext {
     page_title: String,
@@ -551,6 +1175,7 @@ struct ImportResultContext {
     suggested_category: String,
     suggested_tags: String,
     saved_to_db: bool,
+    quality: Option<QualityCheckResults>,
 }
 
 #[derive(Serialize)]
@@ -570,6 +1195,105 @@ struct AdminEditPostContext {
     post: PostResponse,
 }
 
+#[derive(Serialize)]
+struct AdminVersionHistoryContext {
+    page_title: String,
+    current_version: i32,
+    history: VersionHistory,
+}
+
+#[derive(Serialize)]
+struct AdminVersionContext {
+    page_title: String,
+    post_slug: String,
+    current_version: i32,
+    version: PostVersion,
+    diff: VersionDiff,
+    /// `None` when the title is unchanged
+    title_lines: Option<Vec<DiffLine>>,
+    /// Empty when the content is unchanged
+    content_lines: Vec<DiffLine>,
+}
+
+#[derive(Serialize)]
+struct DiffLine {
+    /// "added", "removed" or "unchanged"
+    kind: &'static str,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct AdminCalendarContext {
+    page_title: String,
+    calendar: CalendarMonth,
+    weeks: Vec<Vec<Option<CalendarCell>>>,
+}
+
+#[derive(Serialize)]
+struct AdminSecurityContext {
+    page_title: String,
+    status: TotpStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CalendarCell {
+    day: u32,
+    is_today: bool,
+    entries: Vec<CalendarEntry>,
+}
+
+#[derive(Serialize)]
+struct AdminMediaContext {
+    page_title: String,
+    media: Vec<AdminMediaItem>,
+    current_page: usize,
+    total_pages: usize,
+    total_media: usize,
+    filter_folder: String,
+    filter_mime_type: String,
+    filter_collection: String,
+    search_query: String,
+    folders: Vec<&'static str>,
+    collections: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct AdminMediaItem {
+    media: MediaFile,
+    usage_count: i64,
+    is_image: bool,
+    markdown: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminMediaQuery {
+    pub page: Option<usize>,
+    pub folder: Option<String>,
+    #[serde(rename = "type")]
+    pub mime_type: Option<String>,
+    pub search: Option<String>,
+    pub collection: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLoginQuery {
+    pub next: Option<String>,
+}
+
+/// Form posted from the admin login page
+#[derive(Debug, Deserialize)]
+pub struct AdminLoginForm {
+    pub api_key: String,
+    /// Authenticator or recovery code, required once two-factor login is enabled
+    pub code: Option<String>,
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCalendarQuery {
+    pub month: Option<String>,
+}
+
 // Form data structures for LLM import
 #[derive(Debug, Deserialize)]
 pub struct ImportFormData {
@@ -580,6 +1304,9 @@ pub struct ImportFormData {
     pub source: String,
     pub published: bool,
     pub featured: bool,
+    /// Save despite failed import quality gates
+    #[serde(default)]
+    pub override_quality_gates: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{debug, error, info};
+
+use crate::models::{
+    response::ErrorResponse, Announcement, AnnouncementListResponse, AnnouncementRequest,
+};
+use crate::services::{DatabaseService, TemplateService};
+
+/// App state for announcement handlers
+#[derive(Clone)]
+pub struct AnnouncementState {
+    pub database: DatabaseService,
+    /// Receives the announcements after every change
+    pub templates: TemplateService,
+}
+
+impl AnnouncementState {
+    /// Hand the stored announcements to the templates
+    pub async fn refresh_templates(&self) -> anyhow::Result<()> {
+        let announcements = self.database.list_announcements().await?;
+        self.templates.set_announcements(announcements);
+        Ok(())
+    }
+
+    async fn refresh_after_change(&self) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+        self.refresh_templates().await.map_err(|e| {
+            error!("Failed to reload announcements: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Announcement saved but pages were not updated",
+                )),
+            )
+        })
+    }
+}
+
+/// GET /api/admin/announcements - List all announcements, including inactive ones
+pub async fn list_announcements(
+    State(state): State<AnnouncementState>,
+) -> Result<Json<AnnouncementListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Listing announcements");
+
+    let announcements = state.database.list_announcements().await.map_err(|e| {
+        error!("Database error listing announcements: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to load announcements",
+            )),
+        )
+    })?;
+
+    Ok(Json(AnnouncementListResponse {
+        total: announcements.len(),
+        announcements,
+    }))
+}
+
+/// POST /api/admin/announcements - Create an announcement banner
+pub async fn create_announcement(
+    State(state): State<AnnouncementState>,
+    Json(request): Json<AnnouncementRequest>,
+) -> Result<(StatusCode, Json<Announcement>), (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Creating announcement");
+
+    let request = validate_announcement(request)?;
+    let announcement = state
+        .database
+        .create_announcement(&request)
+        .await
+        .map_err(|e| {
+            error!("Database error creating announcement: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to create announcement",
+                )),
+            )
+        })?;
+    state.refresh_after_change().await?;
+
+    Ok((StatusCode::CREATED, Json(announcement)))
+}
+
+/// PUT /api/admin/announcements/{id} - Replace an announcement
+pub async fn update_announcement(
+    Path(id): Path<i64>,
+    State(state): State<AnnouncementState>,
+    Json(request): Json<AnnouncementRequest>,
+) -> Result<Json<Announcement>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Updating announcement {}", id);
+
+    let request = validate_announcement(request)?;
+    let announcement = state
+        .database
+        .update_announcement(id, &request)
+        .await
+        .map_err(|e| {
+            error!("Database error updating announcement {}: {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to update announcement",
+                )),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found("Announcement not found")),
+            )
+        })?;
+    state.refresh_after_change().await?;
+
+    Ok(Json(announcement))
+}
+
+/// DELETE /api/admin/announcements/{id} - Remove an announcement
+pub async fn delete_announcement(
+    Path(id): Path<i64>,
+    State(state): State<AnnouncementState>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Deleting announcement {}", id);
+
+    let deleted = state.database.delete_announcement(id).await.map_err(|e| {
+        error!("Database error deleting announcement {}: {}", id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to delete announcement",
+            )),
+        )
+    })?;
+
+    if !deleted {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found("Announcement not found")),
+        ));
+    }
+    state.refresh_after_change().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn validate_announcement(
+    mut request: AnnouncementRequest,
+) -> Result<AnnouncementRequest, (StatusCode, Json<ErrorResponse>)> {
+    request.message = request.message.trim().to_string();
+    request.link = request
+        .link
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty());
+
+    if request.message.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Message must not be empty")),
+        ));
+    }
+    if let Some(link) = &request.link {
+        // Rendered as a link on every page, so no `javascript:` or other schemes
+        if !(link.starts_with('/') || link.starts_with("https://") || link.starts_with("http://")) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(
+                    "Link must be a site path or an http(s) URL",
+                )),
+            ));
+        }
+    }
+    if let (Some(starts_at), Some(ends_at)) = (request.starts_at, request.ends_at) {
+        if ends_at <= starts_at {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(
+                    "ends_at must be after starts_at",
+                )),
+            ));
+        }
+    }
+
+    Ok(request)
+}
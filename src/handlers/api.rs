@@ -1,29 +1,63 @@
+use crate::handlers::posts::{
+    has_api_key, is_authorized, pinned_source_response, post_source_response, private_post_error,
+};
 use crate::models::{
+    count_words, is_absolute_http_url, reading_time_minutes,
     response::{
-        BlogStatsResponse, CategoryInfo, ErrorResponse, PostListResponse, PostResponse,
-        PostSummary, TagInfo,
+        BlogStatsResponse, CategoryInfo, ErrorResponse, PostListResponse, PostOutlineResponse,
+        PostResponse, PostSummary, TagInfo,
     },
-    BatchImportRequest, BatchImportResponse, CreatePost, LLMArticleImportRequest,
-    LLMArticleImportResponse, MediaFilters, MediaListResponse, MediaQuery, MediaUploadResponse,
-    PostFilters, UpdatePost,
+    AdminEvent, AttachAudioRequest, AudioEnclosureResponse, BatchImportRequest,
+    BulkRegenerateExcerptRequest, BulkRegenerateExcerptResponse, CalendarResponse,
+    CategoryDescription, CategoryDescriptionListResponse, CommandPaletteQuery,
+    CommandPaletteResponse, ConflictResolution, CreatePost, CreateTagAliasRequest,
+    FeaturedImageResponse, FocalPoint, ImportJob, ImportJobCreatedResponse, Job,
+    JobEnqueuedResponse, JobFilters, JobListResponse, LLMArticleImportRequest,
+    LLMArticleImportResponse, MediaFile, MediaFilters, MediaListResponse, MediaPasteQuery,
+    MediaPasteRequest, MediaPasteResponse, MediaQuery, MediaResponse, MediaUploadResponse,
+    MergeTagsRequest, PinPostRequest, Post, PostAttachmentListResponse, PostFilters,
+    PostVisibility, RegenerateExcerptQuery, RegeneratedExcerpt, ReindexQuery, ReindexResponse,
+    RenameTagRequest, ResolveConflictRequest, SchedulePostRequest, SearchFilters,
+    SetCategoryDescriptionRequest, SetPostExpiryRequest, SetPostPasswordRequest,
+    SetVisibilityRequest, StatsRebuildResponse, SyncConflictsResponse, SyncState, TagAlias,
+    TagAliasListResponse, TagNormalization, TagOperationResponse, UpdateMediaRequest, UpdatePost,
+    UpdatePostMetadata,
 };
+use crate::services::blog_storage::BlogPost;
+use crate::services::command_palette;
+use crate::services::i18n::SiteTimezone;
+use crate::services::media::{decode_pasted_image, image_markdown, pasted_image_filename};
+use crate::services::outline::add_heading_anchors;
+use crate::services::svg_sanitize::SVG_CONTENT_SECURITY_POLICY;
+use crate::services::sync_conflicts::content_hash;
 use crate::services::{
-    BlogStorageService, DatabaseService, LLMImportService, MarkdownService, MediaService,
+    hash_post_password, BlogStorageService, CacheService, CalendarService, CircuitOpen,
+    ContentTypeMismatch, DatabaseService, EventBus, ImportJobService, InvalidMediaPath, JobQueue,
+    LLMImportService, MarkdownService, MediaService, QualityGateError, SummaryService,
+    SyncConflictService, UploadedFile,
 };
+use anyhow::Context;
 use axum::{
-    body::Body,
+    body::{Body, Bytes},
     extract::{Path, Query, State},
-    http::{header, StatusCode},
-    response::{Json, Response},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json, Response,
+    },
 };
-use axum_extra::extract::{multipart::Field, Multipart};
+use axum_extra::extract::{multipart::MultipartError, Multipart};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 /// Query parameters for post listing API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
 pub struct ApiPostQuery {
     pub page: Option<usize>,
     pub per_page: Option<usize>,
@@ -41,18 +75,120 @@ pub struct ApiState {
     pub blog_storage: Arc<BlogStorageService>,
     pub llm_import: LLMImportService,
     pub media: MediaService,
+    pub calendar: CalendarService,
+    pub cache: CacheService,
+    /// Key required to read the source of unpublished posts
+    pub api_key: Option<String>,
+    /// Dropbox folder that "edit in Dropbox" links must stay within
+    pub dropbox_root: String,
+    /// Site timezone for `published_at_local` and wall-clock schedule times
+    pub timezone: SiteTimezone,
+    pub sync_conflicts: SyncConflictService,
+    /// Cap on posts pinned at the same time
+    pub max_pinned_posts: i64,
+    /// Language model for post summaries, when configured
+    pub summarizer: Option<SummaryService>,
+    pub import_jobs: ImportJobService,
+    pub jobs: JobQueue,
+    pub events: EventBus,
+}
+
+impl ApiState {
+    /// Whether the request carries the API key needed for private posts
+    fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        is_authorized(headers, self.api_key.as_deref())
+    }
+
+    /// Convert a post for API output, including its Dropbox web link and
+    /// publication time in the site timezone
+    fn post_response(&self, post: Post) -> PostResponse {
+        PostResponse::with_dropbox_link(post, &self.dropbox_root)
+            .with_local_times(self.timezone.get())
+    }
+
+    /// Remember `content` as the version both the database and Dropbox hold
+    async fn record_sync(&self, post_id: Uuid, content: &str) {
+        if let Err(e) = self
+            .database
+            .set_post_sync_hash(post_id, &content_hash(content))
+            .await
+        {
+            warn!("Failed to record sync of post {}: {}", post_id, e);
+        }
+    }
+
+    /// Keep the frontmatter keys of a synced file that don't map to post fields
+    async fn record_custom_frontmatter(&self, post_id: Uuid, custom_frontmatter: Option<&str>) {
+        if let Err(e) = self
+            .database
+            .set_post_custom_frontmatter(post_id, custom_frontmatter)
+            .await
+        {
+            warn!(
+                "Failed to store custom frontmatter of post {}: {}",
+                post_id, e
+            );
+        }
+    }
+
+    /// Store the cross-posting fields of a post, logging failures
+    async fn record_syndication(
+        &self,
+        post_id: Uuid,
+        canonical_url: Option<&str>,
+        syndication_links: &[String],
+    ) {
+        if let Err(e) = self
+            .database
+            .set_post_syndication(post_id, canonical_url, syndication_links)
+            .await
+        {
+            warn!(
+                "Failed to store cross-posting metadata of post {}: {}",
+                post_id, e
+            );
+        }
+    }
+
+    /// Register the handlers of the jobs queued by API endpoints
+    pub fn register_jobs(&self) {
+        let state = self.clone();
+        self.jobs.register(DROPBOX_SYNC_JOB, move |payload| {
+            let state = state.clone();
+            async move { run_dropbox_sync(&state, serde_json::from_value(payload)?).await }
+        });
+        self.import_jobs.register_jobs();
+    }
+}
+
+/// Query parameters for the content calendar
+#[derive(Debug, Deserialize)]
+pub struct CalendarQuery {
+    /// Month in `YYYY-MM` format (defaults to the current month)
+    pub month: Option<String>,
 }
 
 /// GET /api/posts - List posts with pagination and filtering
+#[utoipa::path(
+    get,
+    path = "/api/posts",
+    tag = "posts",
+    params(ApiPostQuery),
+    responses(
+        (status = 200, description = "Page of posts", body = PostListResponse)
+    )
+)]
 pub async fn list_posts_api(
     Query(query): Query<ApiPostQuery>,
     State(state): State<ApiState>,
+    headers: HeaderMap,
 ) -> Result<Json<PostListResponse>, (StatusCode, Json<ErrorResponse>)> {
     debug!("API: Listing posts with query: {:?}", query);
 
     let page = query.page.unwrap_or(1);
     let per_page = query.per_page.unwrap_or(10).min(100); // Limit to 100 per page
     let offset = (page.saturating_sub(1)) * per_page;
+    let visibility = PostVisibility::listed(state.is_authorized(&headers));
 
     // Build filters
     let filters = PostFilters {
@@ -62,11 +198,13 @@ pub async fn list_posts_api(
         featured: query.featured,
         limit: Some(per_page as i64),
         offset: Some(offset as i64),
+        pinned_first: true,
+        visibility: Some(visibility.clone()),
         ..Default::default()
     };
 
     // Get posts from database
-    let posts = state
+    let mut posts = state
         .database
         .list_posts(filters.clone())
         .await
@@ -77,6 +215,7 @@ pub async fn list_posts_api(
                 Json(ErrorResponse::internal_error("Failed to load posts")),
             )
         })?;
+    show_published_versions(&state, &headers, &mut posts).await?;
 
     // Get total count for pagination using efficient count method
     let count_filters = PostFilters {
@@ -84,6 +223,7 @@ pub async fn list_posts_api(
         category: query.category.clone(),
         tag: query.tag.clone(),
         featured: query.featured,
+        visibility: Some(visibility),
         ..Default::default()
     };
 
@@ -117,12 +257,37 @@ pub async fn list_posts_api(
 }
 
 /// GET /api/posts/{slug} - Get individual post by slug
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "The post", body = PostResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 410, description = "Post was deleted", body = ErrorResponse)
+    )
+)]
 pub async fn get_post_api(
     Path(slug): Path<String>,
     State(state): State<ApiState>,
+    headers: HeaderMap,
 ) -> Result<Json<PostResponse>, (StatusCode, Json<ErrorResponse>)> {
     debug!("API: Getting post by slug: {}", slug);
 
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        )
+    };
+    if state.cache.is_missing_post(&slug).await {
+        return Err(not_found());
+    }
+
     let post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
         error!("Database error getting post {}: {}", slug, e);
         (
@@ -131,23 +296,165 @@ pub async fn get_post_api(
         )
     })?;
 
-    let post = match post {
+    let mut post = match post {
         Some(post) => post,
         None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::not_found(format!(
-                    "Post '{}' not found",
-                    slug
-                ))),
-            ));
+            let gone = state.database.get_gone_post(&slug).await.map_err(|e| {
+                error!("Database error getting deleted post {}: {}", slug, e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Database error")),
+                )
+            })?;
+            if gone.is_some() {
+                return Err((
+                    StatusCode::GONE,
+                    Json(ErrorResponse::gone(format!("Post '{}' was deleted", slug))),
+                ));
+            }
+            state.cache.remember_missing_post(&slug).await;
+            return Err(not_found());
         }
     };
 
-    let response = PostResponse::from(post);
+    if post.visibility == PostVisibility::Private && !state.is_authorized(&headers) {
+        return Err(private_post_error(&slug));
+    }
+
+    show_published_versions(&state, &headers, std::slice::from_mut(&mut post)).await?;
+
+    let mut response = state.post_response(post);
+    if !has_api_key(&headers, state.api_key.as_deref()) {
+        response = response.without_protected_content();
+    }
     Ok(Json(response))
 }
 
+/// GET /api/posts/{slug}/raw - Markdown source with frontmatter as stored in Dropbox
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}/raw",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "Markdown source with frontmatter", body = String, content_type = "text/markdown"),
+        (status = 404, description = "Post not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_post_source_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Getting markdown source for: {}", slug);
+
+    let post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
+        error!("Database error getting post {}: {}", slug, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let Some(mut post) = post else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        ));
+    };
+
+    // The Dropbox source is the latest draft, so readers get a pinned post's own markdown
+    if !has_api_key(&headers, state.api_key.as_deref()) {
+        let pinned = state
+            .database
+            .show_published_version(&mut post)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Database error loading published version of {}: {}",
+                    slug, e
+                );
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Database error")),
+                )
+            })?;
+        if pinned {
+            return pinned_source_response(state.api_key.as_deref(), &headers, &post);
+        }
+    }
+
+    post_source_response(
+        &state.blog_storage,
+        state.api_key.as_deref(),
+        &headers,
+        &post,
+    )
+    .await
+}
+
+/// GET /api/posts/{slug}/outline - Heading tree with anchors, for progress bars and deep links
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}/outline",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "Headings of the post", body = PostOutlineResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_post_outline_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<PostOutlineResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Getting outline of post: {}", slug);
+
+    let post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
+        error!("Database error getting post {}: {}", slug, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let Some(mut post) = post else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        ));
+    };
+
+    if post.visibility == PostVisibility::Private && !state.is_authorized(&headers) {
+        return Err(private_post_error(&slug));
+    }
+
+    show_published_versions(&state, &headers, std::slice::from_mut(&mut post)).await?;
+
+    // Headings are content, so they stay hidden like the body of a locked post
+    let headings =
+        if post.is_password_protected() && !has_api_key(&headers, state.api_key.as_deref()) {
+            Vec::new()
+        } else {
+            add_heading_anchors(&post.html_content).1
+        };
+
+    Ok(Json(PostOutlineResponse {
+        reading_time: post.reading_time(),
+        word_count: post.word_count,
+        slug: post.slug,
+        title: post.title,
+        headings,
+    }))
+}
+
 /// GET /api/blog/stats - Get blog statistics
 pub async fn blog_stats_api(
     State(state): State<ApiState>,
@@ -166,6 +473,7 @@ pub async fn blog_stats_api(
     let recent_filters = PostFilters {
         published: Some(true),
         limit: Some(5),
+        visibility: Some(vec![PostVisibility::Public]),
         ..Default::default()
     };
 
@@ -269,922 +577,3430 @@ pub async fn list_tags_api(
     Ok(Json(tags))
 }
 
-/// GET /api/search - Search posts
-pub async fn search_posts_api(
-    Query(query): Query<SearchQuery>,
+/// POST /api/tags/rename - Rename a tag on every post
+pub async fn rename_tag_api(
     State(state): State<ApiState>,
-) -> Result<Json<PostListResponse>, (StatusCode, Json<ErrorResponse>)> {
-    debug!("API: Searching posts with query: {:?}", query);
+    Json(request): Json<RenameTagRequest>,
+) -> Result<Json<TagOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Renaming tag '{}' to '{}'", request.from, request.to);
 
-    let search_query = query.q.unwrap_or_default();
-    if search_query.trim().is_empty() {
+    let to = validate_tag_name(&request.to)?;
+    let affected = rewrite_tags(&state, vec![request.from.trim().to_string()], Some(to)).await?;
+
+    Ok(Json(TagOperationResponse {
+        success: true,
+        message: format!(
+            "Tag '{}' renamed to '{}' on {} posts",
+            request.from,
+            to,
+            affected.len()
+        ),
+        affected_posts: affected,
+    }))
+}
+
+/// POST /api/tags/merge - Merge several tags into one
+pub async fn merge_tags_api(
+    State(state): State<ApiState>,
+    Json(request): Json<MergeTagsRequest>,
+) -> Result<Json<TagOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Merging tags {:?} into '{}'",
+        request.sources, request.target
+    );
+
+    let target = validate_tag_name(&request.target)?;
+    let sources: Vec<String> = request
+        .sources
+        .iter()
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty())
+        .collect();
+    if sources.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse::bad_request(
-                "Search query 'q' parameter is required",
+                "At least one source tag is required",
             )),
         ));
     }
 
-    let limit = query.limit.unwrap_or(20).min(100);
-
-    let posts = state
-        .database
-        .search_posts(&search_query, Some(limit as i64))
-        .await
-        .map_err(|e| {
-            error!("Database error searching posts: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Search failed")),
-            )
-        })?;
-
-    let post_summaries: Vec<PostSummary> = posts.into_iter().map(PostSummary::from).collect();
-
-    let total = post_summaries.len();
-
-    let response = PostListResponse {
-        posts: post_summaries,
-        total,
-        page: 1,
-        per_page: limit,
-        total_pages: 1, // Search results are not paginated
-    };
-
-    Ok(Json(response))
-}
-
-/// Query parameters for search
-#[derive(Debug, Deserialize)]
-pub struct SearchQuery {
-    pub q: Option<String>,
-    pub limit: Option<usize>,
-}
-
-/// Request body for creating a new post
-#[derive(Debug, Deserialize)]
-pub struct CreatePostRequest {
-    pub title: String,
-    pub content: String,
-    pub category: Option<String>,
-    pub tags: Option<Vec<String>>,
-    pub published: Option<bool>,
-    pub featured: Option<bool>,
-    pub author: Option<String>,
-}
+    let affected = rewrite_tags(&state, sources, Some(target)).await?;
 
-/// Request body for updating a post
-#[derive(Debug, Deserialize)]
-pub struct UpdatePostRequest {
-    pub title: Option<String>,
-    pub content: Option<String>,
-    pub category: Option<String>,
-    pub tags: Option<Vec<String>>,
-    pub published: Option<bool>,
-    pub featured: Option<bool>,
-    pub author: Option<String>,
+    Ok(Json(TagOperationResponse {
+        success: true,
+        message: format!(
+            "Merged {} tags into '{}' on {} posts",
+            request.sources.len(),
+            target,
+            affected.len()
+        ),
+        affected_posts: affected,
+    }))
 }
 
-/// Response for post operations (create, update, delete)
-#[derive(Debug, Serialize)]
-pub struct PostOperationResponse {
-    pub success: bool,
-    pub slug: String,
-    pub message: String,
-    pub post: Option<PostResponse>,
-}
+/// DELETE /api/tags/{name} - Remove a tag from every post
+pub async fn delete_tag_api(
+    Path(name): Path<String>,
+    State(state): State<ApiState>,
+) -> Result<Json<TagOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Deleting tag '{}'", name);
 
-/// Request body for Dropbox sync
-#[derive(Debug, Deserialize)]
-pub struct SyncDropboxRequest {
-    pub force: Option<bool>,
-}
+    let affected = rewrite_tags(&state, vec![name.clone()], None).await?;
 
-/// Response for sync operations
-#[derive(Debug, Serialize)]
-pub struct SyncResponse {
-    pub success: bool,
-    pub message: String,
-    pub synced_count: Option<usize>,
-    pub errors: Option<Vec<String>>,
+    Ok(Json(TagOperationResponse {
+        success: true,
+        message: format!("Tag '{}' removed from {} posts", name, affected.len()),
+        affected_posts: affected,
+    }))
 }
 
-/// Request body for markdown import
-#[derive(Debug, Deserialize)]
-pub struct ImportMarkdownRequest {
-    pub files: Vec<MarkdownFileImport>,
-    pub overwrite: Option<bool>,
-}
+/// GET /api/admin/tag-aliases - List tag and category aliases
+pub async fn list_tag_aliases_api(
+    State(state): State<ApiState>,
+) -> Result<Json<TagAliasListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Listing tag aliases");
 
-#[derive(Debug, Deserialize)]
-pub struct MarkdownFileImport {
-    pub path: String,
-    pub content: String,
-    pub metadata: Option<PostMetadata>,
-}
+    let aliases = state.database.list_tag_aliases().await.map_err(|e| {
+        error!("Database error listing tag aliases: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to load tag aliases")),
+        )
+    })?;
 
-#[derive(Debug, Deserialize)]
-pub struct PostMetadata {
-    pub title: Option<String>,
-    pub category: Option<String>,
-    pub tags: Option<Vec<String>>,
-    pub published: Option<bool>,
-    pub author: Option<String>,
+    Ok(Json(TagAliasListResponse {
+        total: aliases.len(),
+        aliases,
+    }))
 }
 
-/// POST /api/posts - Create a new post
-pub async fn create_post_api(
+/// POST /api/admin/tag-aliases - Map an alias to a canonical tag
+///
+/// Existing posts tagged with any spelling of the alias are retagged right away;
+/// categories pick the alias up the next time a post is saved.
+pub async fn create_tag_alias_api(
     State(state): State<ApiState>,
-    Json(request): Json<CreatePostRequest>,
-) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
-    info!("API: Creating new post with title: {}", request.title);
+    Json(request): Json<CreateTagAliasRequest>,
+) -> Result<(StatusCode, Json<TagAlias>), (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Creating tag alias '{}' -> '{}'",
+        request.alias, request.canonical
+    );
 
-    // Validate request
-    if request.title.trim().is_empty() {
+    let alias_key = TagNormalization::alias_key(&request.alias);
+    let canonical = validate_tag_name(&request.canonical)?;
+    if alias_key.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::bad_request("Title cannot be empty")),
+            Json(ErrorResponse::bad_request("Alias must not be empty")),
         ));
     }
-
-    if request.content.trim().is_empty() {
+    if alias_key == TagNormalization::alias_key(canonical) {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::bad_request("Content cannot be empty")),
-        ));
-    }
-
-    // Generate slug from title
-    let slug = generate_slug(&request.title);
-
-    // Check if slug already exists
-    if let Ok(Some(_)) = state.database.get_post_by_slug(&slug).await {
-        return Err((
-            StatusCode::CONFLICT,
-            Json(ErrorResponse::new(
-                "conflict",
-                format!("Post with slug '{}' already exists", slug),
-                409,
+            Json(ErrorResponse::bad_request(
+                "Alias must differ from the canonical name",
             )),
         ));
     }
 
-    // Parse markdown content to HTML
-    let parsed = state
-        .markdown
-        .parse_markdown(&request.content)
+    let alias = state
+        .database
+        .upsert_tag_alias(&alias_key, canonical)
+        .await
         .map_err(|e| {
-            error!("Failed to parse markdown: {}", e);
+            error!("Database error saving tag alias: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to parse markdown")),
+                Json(ErrorResponse::internal_error("Failed to save tag alias")),
             )
         })?;
-    let html_content = parsed.html;
 
-    // Generate excerpt if not provided
-    let excerpt = generate_excerpt(&request.content, 200);
-
-    // Prepare the year-based path
-    let now = chrono::Utc::now();
-    let year = now.format("%Y");
-    let filename = format!("{}.md", slug);
-    let dropbox_path = format!("/posts/{}/{}", year, filename);
-
-    // Create post data
-    let create_data = CreatePost {
-        slug: slug.clone(),
-        title: request.title.clone(),
-        content: request.content.clone(),
-        html_content,
-        excerpt: Some(excerpt),
-        category: request.category,
-        tags: request.tags.unwrap_or_default(),
-        published: request.published.unwrap_or(false),
-        featured: request.featured.unwrap_or(false),
-        author: request.author,
-        dropbox_path: dropbox_path.clone(),
-    };
-
-    // Save to database first
-    let post = state.database.create_post(create_data).await.map_err(|e| {
-        error!("Database error creating post: {}", e);
+    let tag_names = state.database.list_tag_names().await.map_err(|e| {
+        error!("Database error listing tags: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::internal_error("Failed to create post")),
+            Json(ErrorResponse::internal_error("Failed to load tags")),
         )
     })?;
+    let sources: Vec<String> = tag_names
+        .into_iter()
+        .filter(|name| TagNormalization::alias_key(name) == alias_key)
+        .collect();
+    rewrite_tags(&state, sources, Some(&alias.canonical)).await?;
 
-    // Save to Dropbox using blog storage service
-    let blog_post = crate::services::blog_storage::BlogPost {
-        metadata: crate::services::blog_storage::BlogPostMetadata {
-            title: post.title.clone(),
-            slug: post.slug.clone(),
-            created_at: post.created_at,
-            updated_at: post.updated_at,
-            category: post.category.clone(),
-            tags: parse_tags_from_json(&post.tags),
-            published: post.published,
-            author: post.author.clone(),
-            excerpt: post.excerpt.clone(),
-        },
-        content: post.content.clone(),
-        dropbox_path: post.dropbox_path.clone(),
-        file_metadata: None,
-    };
-
-    match state.blog_storage.save_post(&blog_post, false).await {
-        Ok(_) => {
-            info!("Post saved to Dropbox: {}", dropbox_path);
-        }
-        Err(e) => {
-            error!("Failed to save post to Dropbox: {}", e);
-            // Don't fail the request, but log the error
-            // The post is already saved in the database
-        }
-    }
-
-    let response = PostOperationResponse {
-        success: true,
-        slug: post.slug.clone(),
-        message: format!("Post '{}' created successfully", request.title),
-        post: Some(PostResponse::from(post)),
-    };
-
-    Ok(Json(response))
+    Ok((StatusCode::CREATED, Json(alias)))
 }
 
-/// PUT /api/posts/{slug} - Update an existing post
-pub async fn update_post_api(
-    Path(slug): Path<String>,
+/// DELETE /api/admin/tag-aliases/{alias} - Remove a tag alias
+pub async fn delete_tag_alias_api(
+    Path(alias): Path<String>,
     State(state): State<ApiState>,
-    Json(request): Json<UpdatePostRequest>,
-) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
-    info!("API: Updating post with slug: {}", slug);
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Deleting tag alias '{}'", alias);
 
-    // Get existing post
-    let existing_post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
-        error!("Database error getting post: {}", e);
+    let deleted = state.database.delete_tag_alias(&alias).await.map_err(|e| {
+        error!("Database error deleting tag alias: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::internal_error("Database error")),
+            Json(ErrorResponse::internal_error("Failed to delete tag alias")),
         )
     })?;
 
-    let existing_post = match existing_post {
-        Some(post) => post,
-        None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::not_found(format!(
-                    "Post '{}' not found",
-                    slug
-                ))),
-            ));
-        }
-    };
-
-    // Update HTML content if content is being updated
-    let html_content = if let Some(ref content) = request.content {
-        let parsed = state.markdown.parse_markdown(content).map_err(|e| {
-            error!("Failed to parse markdown: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to parse markdown")),
-            )
-        })?;
-        Some(parsed.html)
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
     } else {
-        None
-    };
+        Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found("Tag alias not found")),
+        ))
+    }
+}
 
-    // Create update data
-    let update_data = UpdatePost {
-        title: request.title.clone(),
-        content: request.content.clone(),
-        html_content,
-        excerpt: None, // Keep existing excerpt unless content changes
-        category: request.category,
-        tags: request.tags,
-        published: request.published,
-        featured: request.featured,
-        author: request.author,
-        dropbox_path: None, // Keep existing path
-    };
+/// GET /api/admin/category-descriptions - List category descriptions
+pub async fn list_category_descriptions_api(
+    State(state): State<ApiState>,
+) -> Result<Json<CategoryDescriptionListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Listing category descriptions");
 
-    // Update in database
-    let updated_post = state
+    let descriptions = state
         .database
-        .update_post(existing_post.id, update_data)
+        .list_category_descriptions()
         .await
         .map_err(|e| {
-            error!("Database error updating post: {}", e);
+            error!("Database error listing category descriptions: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to update post")),
+                Json(ErrorResponse::internal_error(
+                    "Failed to load category descriptions",
+                )),
             )
         })?;
 
-    // Update in Dropbox if content changed
-    if let Some(ref updated_post) = updated_post {
-        let blog_post = crate::services::blog_storage::BlogPost {
-            metadata: crate::services::blog_storage::BlogPostMetadata {
-                title: updated_post.title.clone(),
-                slug: updated_post.slug.clone(),
-                created_at: updated_post.created_at,
-                updated_at: updated_post.updated_at,
-                category: updated_post.category.clone(),
-                tags: parse_tags_from_json(&updated_post.tags),
-                published: updated_post.published,
-                author: updated_post.author.clone(),
-                excerpt: updated_post.excerpt.clone(),
-            },
-            content: updated_post.content.clone(),
-            dropbox_path: updated_post.dropbox_path.clone(),
-            file_metadata: None,
-        };
+    Ok(Json(CategoryDescriptionListResponse {
+        total: descriptions.len(),
+        descriptions,
+    }))
+}
 
-        match state.blog_storage.save_post(&blog_post, false).await {
-            Ok(_) => {
-                info!("Post updated in Dropbox: {}", existing_post.dropbox_path);
-            }
-            Err(e) => {
-                error!("Failed to update post in Dropbox: {}", e);
-                // Don't fail the request, but log the error
-            }
-        }
+/// PUT /api/admin/category-descriptions/{category} - Set the description
+/// shown on the category index
+pub async fn set_category_description_api(
+    Path(category): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<SetCategoryDescriptionRequest>,
+) -> Result<Json<CategoryDescription>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Setting description of category '{}'", category);
+
+    let category = validate_tag_name(&category)?;
+    let description = request.description.trim();
+    if description.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Description must not be empty; delete it instead",
+            )),
+        ));
     }
 
-    let response = PostOperationResponse {
-        success: true,
-        slug: updated_post
-            .as_ref()
-            .map(|p| p.slug.clone())
-            .unwrap_or_else(|| slug.clone()),
-        message: format!(
-            "Post '{}' updated successfully",
-            updated_post
-                .as_ref()
-                .map(|p| p.title.as_str())
-                .unwrap_or(&slug)
-        ),
-        post: updated_post.map(PostResponse::from),
-    };
+    let entry = state
+        .database
+        .upsert_category_description(category, description)
+        .await
+        .map_err(|e| {
+            error!("Database error saving category description: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to save category description",
+                )),
+            )
+        })?;
+    state.cache.invalidate_responses().await;
 
-    Ok(Json(response))
+    Ok(Json(entry))
 }
 
-/// DELETE /api/posts/{slug} - Delete a post
-pub async fn delete_post_api(
-    Path(slug): Path<String>,
+/// DELETE /api/admin/category-descriptions/{category} - Remove a category description
+pub async fn delete_category_description_api(
+    Path(category): Path<String>,
     State(state): State<ApiState>,
-) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
-    info!("API: Deleting post with slug: {}", slug);
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Deleting description of category '{}'", category);
 
-    // Get existing post
-    let existing_post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
-        error!("Database error getting post: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::internal_error("Database error")),
-        )
-    })?;
+    let deleted = state
+        .database
+        .delete_category_description(&category)
+        .await
+        .map_err(|e| {
+            error!("Database error deleting category description: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to delete category description",
+                )),
+            )
+        })?;
 
-    let existing_post = match existing_post {
-        Some(post) => post,
-        None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::not_found(format!(
-                    "Post '{}' not found",
-                    slug
-                ))),
-            ));
-        }
-    };
+    if deleted {
+        state.cache.invalidate_responses().await;
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found("Category description not found")),
+        ))
+    }
+}
 
-    // Delete from database (soft delete by marking as unpublished)
-    state
+fn validate_tag_name(name: &str) -> Result<&str, (StatusCode, Json<ErrorResponse>)> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Tag name must not be empty")),
+        ));
+    }
+    Ok(name)
+}
+
+/// Rewrite tags in the database and drop cached pages for the affected posts
+async fn rewrite_tags(
+    state: &ApiState,
+    sources: Vec<String>,
+    target: Option<&str>,
+) -> Result<Vec<String>, (StatusCode, Json<ErrorResponse>)> {
+    let affected = state
         .database
-        .delete_post(existing_post.id)
+        .rewrite_tags(&sources, target)
         .await
         .map_err(|e| {
-            error!("Database error deleting post: {}", e);
+            error!("Database error rewriting tags: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to delete post")),
+                Json(ErrorResponse::internal_error("Failed to update tags")),
             )
         })?;
 
-    // Delete from Dropbox (or move to archive folder)
-    match state.blog_storage.delete_post(&slug).await {
-        Ok(true) => {
-            info!("Post deleted from Dropbox: {}", existing_post.dropbox_path);
-        }
-        Ok(false) => {
-            warn!("Post not found in Dropbox: {}", slug);
-        }
-        Err(e) => {
-            error!("Failed to delete post from Dropbox: {}", e);
-            // Don't fail the request, but log the error
+    for slug in &affected {
+        if let Err(e) = state.cache.invalidate_post(slug).await {
+            warn!("Failed to invalidate cache for {}: {}", slug, e);
         }
     }
 
-    let response = PostOperationResponse {
-        success: true,
-        slug: slug.clone(),
-        message: format!("Post '{}' deleted successfully", existing_post.title),
-        post: None,
-    };
-
-    Ok(Json(response))
+    Ok(affected)
 }
 
-/// POST /api/sync/dropbox - Sync posts from Dropbox
-pub async fn sync_dropbox_api(
+/// GET /api/search - Search posts with optional filters and pagination
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching posts", body = PostListResponse),
+        (status = 400, description = "Invalid filter", body = ErrorResponse)
+    )
+)]
+pub async fn search_posts_api(
+    Query(query): Query<SearchQuery>,
     State(state): State<ApiState>,
-    Json(request): Json<SyncDropboxRequest>,
-) -> Result<Json<SyncResponse>, (StatusCode, Json<ErrorResponse>)> {
-    info!(
-        "API: Syncing posts from Dropbox (force: {:?})",
-        request.force
-    );
-
-    let mut synced = 0;
-    let mut errors = Vec::new();
+    headers: HeaderMap,
+) -> Result<Json<PostListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Searching posts with query: {:?}", query);
 
-    // Get all published posts from Dropbox
-    match state.blog_storage.list_published_posts().await {
-        Ok(dropbox_posts) => {
-            for dropbox_post in dropbox_posts {
-                // Check if post exists in database
-                match state
-                    .database
-                    .get_post_by_slug(&dropbox_post.metadata.slug)
-                    .await
-                {
-                    Ok(Some(db_post)) => {
-                        // Post exists, check if we should update
-                        if request.force.unwrap_or(false)
-                            || dropbox_post.metadata.updated_at > db_post.updated_at
-                        {
-                            // Update existing post
-                            let update_data = crate::models::UpdatePost {
-                                title: Some(dropbox_post.metadata.title.clone()),
-                                content: Some(dropbox_post.content.clone()),
-                                html_content: None, // Will be generated from content
-                                excerpt: dropbox_post.metadata.excerpt.clone(),
-                                category: dropbox_post.metadata.category.clone(),
-                                tags: Some(dropbox_post.metadata.tags.clone()),
-                                published: Some(dropbox_post.metadata.published),
-                                featured: None,
-                                author: dropbox_post.metadata.author.clone(),
-                                dropbox_path: Some(dropbox_post.dropbox_path.clone()),
-                            };
-
-                            match state.database.update_post(db_post.id, update_data).await {
-                                Ok(_) => {
-                                    synced += 1;
-                                    info!("Updated existing post: {}", dropbox_post.metadata.slug);
-                                }
-                                Err(e) => {
-                                    errors.push(format!(
-                                        "Failed to update post '{}': {}",
-                                        dropbox_post.metadata.slug, e
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                    Ok(None) => {
-                        // New post, create it
-                        let create_data = crate::models::CreatePost {
-                            slug: dropbox_post.metadata.slug.clone(),
-                            title: dropbox_post.metadata.title.clone(),
-                            content: dropbox_post.content.clone(),
-                            html_content: String::new(), // Will be generated
-                            excerpt: dropbox_post.metadata.excerpt,
-                            category: dropbox_post.metadata.category,
-                            tags: dropbox_post.metadata.tags,
-                            published: dropbox_post.metadata.published,
-                            featured: false,
-                            author: dropbox_post.metadata.author,
-                            dropbox_path: dropbox_post.dropbox_path,
-                        };
-
-                        match state.database.create_post(create_data).await {
-                            Ok(_) => {
-                                synced += 1;
-                                info!("Created new post: {}", dropbox_post.metadata.slug);
-                            }
-                            Err(e) => {
-                                errors.push(format!(
-                                    "Failed to create post '{}': {}",
-                                    dropbox_post.metadata.slug, e
-                                ));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        errors.push(format!(
-                            "Database error checking post '{}': {}",
-                            dropbox_post.metadata.slug, e
-                        ));
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            errors.push(format!("Failed to list Dropbox posts: {}", e));
-        }
+    let search_query = query.q.clone().unwrap_or_default();
+    if search_query.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Search query 'q' parameter is required",
+            )),
+        ));
     }
 
-    let response = SyncResponse {
-        success: errors.is_empty(),
-        message: format!("Synced {} posts from Dropbox", synced),
-        synced_count: Some(synced),
-        errors: if errors.is_empty() {
-            None
-        } else {
-            Some(errors)
-        },
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.or(query.limit).unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * per_page;
+
+    let from = parse_search_date(query.from.as_deref(), false)?;
+    let to = parse_search_date(query.to.as_deref(), true)?;
+
+    let filters = SearchFilters {
+        published: query.published,
+        category: query.category.clone(),
+        tag: query.tag.clone(),
+        author: query.author.clone(),
+        from,
+        to,
+        limit: Some(per_page as i64),
+        offset: Some(offset as i64),
+        visibility: Some(PostVisibility::listed(state.is_authorized(&headers))),
+    };
+
+    let mut posts = state
+        .database
+        .search_posts(&search_query, &filters)
+        .await
+        .map_err(|e| {
+            error!("Database error searching posts: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Search failed")),
+            )
+        })?;
+    show_published_versions(&state, &headers, &mut posts).await?;
+
+    let total_count = state
+        .database
+        .count_search_results(&search_query, &filters)
+        .await
+        .map_err(|e| {
+            error!("Database error counting search results: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Search failed")),
+            )
+        })?;
+
+    let total = total_count as usize;
+    let total_pages = total.div_ceil(per_page);
+
+    let post_summaries: Vec<PostSummary> = posts.into_iter().map(PostSummary::from).collect();
+
+    let response = PostListResponse {
+        posts: post_summaries,
+        total,
+        page,
+        per_page,
+        total_pages,
     };
 
     Ok(Json(response))
 }
 
-/// POST /api/import/markdown - Import markdown files in bulk
-pub async fn import_markdown_api(
+/// POST /api/posts/{slug}/regenerate-excerpt - Rebuild the excerpt and reading time of a post
+///
+/// With `?summary=true` a summary is also requested from the configured language model.
+pub async fn regenerate_excerpt_api(
+    Path(slug): Path<String>,
+    Query(query): Query<RegenerateExcerptQuery>,
     State(state): State<ApiState>,
-    Json(request): Json<ImportMarkdownRequest>,
-) -> Result<Json<SyncResponse>, (StatusCode, Json<ErrorResponse>)> {
-    info!("API: Importing {} markdown files", request.files.len());
-
-    let mut imported = 0;
-    let mut errors = Vec::new();
+) -> Result<Json<RegeneratedExcerpt>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Regenerating excerpt of {}", slug);
 
-    for file in request.files {
-        // Extract title from metadata or content
-        let title = file
-            .metadata
-            .as_ref()
-            .and_then(|m| m.title.clone())
-            .unwrap_or_else(|| extract_title_from_markdown(&file.content));
+    ensure_summarizer(&state, query.summary)?;
 
-        let slug = generate_slug(&title);
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
 
-        // Check if should overwrite
-        if !request.overwrite.unwrap_or(false) {
-            if let Ok(Some(_)) = state.database.get_post_by_slug(&slug).await {
-                errors.push(format!("Post '{}' already exists", slug));
-                continue;
-            }
-        }
+    let regenerated = regenerate_excerpt(&state, &post, query.summary)
+        .await
+        .map_err(|e| {
+            error!("Failed to regenerate excerpt of {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to regenerate excerpt",
+                )),
+            )
+        })?;
 
-        // Parse markdown
-        let html_content = match state.markdown.parse_markdown(&file.content) {
-            Ok(parsed) => parsed.html,
-            Err(e) => {
-                errors.push(format!("Failed to parse markdown for '{}': {}", slug, e));
-                continue;
-            }
-        };
-        let excerpt = generate_excerpt(&file.content, 200);
+    Ok(Json(regenerated))
+}
 
-        // Create post
-        let create_data = CreatePost {
-            slug: slug.clone(),
-            title,
-            content: file.content.clone(),
-            html_content,
-            excerpt: Some(excerpt),
-            category: file.metadata.as_ref().and_then(|m| m.category.clone()),
-            tags: file
-                .metadata
-                .as_ref()
-                .and_then(|m| m.tags.clone())
-                .unwrap_or_default(),
-            published: file
-                .metadata
-                .as_ref()
-                .and_then(|m| m.published)
-                .unwrap_or(false),
-            featured: false,
-            author: file.metadata.as_ref().and_then(|m| m.author.clone()),
-            dropbox_path: file.path.clone(),
-        };
+/// POST /api/admin/regenerate-excerpts - Rebuild excerpts of many posts
+///
+/// By default only posts without an excerpt are processed.
+pub async fn bulk_regenerate_excerpts_api(
+    State(state): State<ApiState>,
+    Json(request): Json<BulkRegenerateExcerptRequest>,
+) -> Result<Json<BulkRegenerateExcerptResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Regenerating excerpts (missing only: {}, summary: {})",
+        request.missing_only, request.summary
+    );
 
-        match state.database.create_post(create_data).await {
-            Ok(post) => {
-                imported += 1;
+    ensure_summarizer(&state, request.summary)?;
 
-                // Save to Dropbox as well
-                let blog_post = crate::services::blog_storage::BlogPost {
-                    metadata: crate::services::blog_storage::BlogPostMetadata {
-                        title: post.title.clone(),
-                        slug: post.slug.clone(),
-                        created_at: post.created_at,
-                        updated_at: post.updated_at,
-                        category: post.category.clone(),
-                        tags: parse_tags_from_json(&post.tags),
-                        published: post.published,
-                        author: post.author.clone(),
-                        excerpt: post.excerpt.clone(),
-                    },
-                    content: post.content.clone(),
-                    dropbox_path: post.dropbox_path.clone(),
-                    file_metadata: None,
-                };
+    let db_error = |e: anyhow::Error| {
+        error!("Database error loading posts: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to load posts")),
+        )
+    };
 
-                if let Err(e) = state.blog_storage.save_post(&blog_post, false).await {
-                    errors.push(format!("Failed to save '{}' to Dropbox: {}", slug, e));
+    let mut errors = Vec::new();
+    let posts = match &request.slugs {
+        Some(slugs) => {
+            let mut posts = Vec::with_capacity(slugs.len());
+            for slug in slugs {
+                match state
+                    .database
+                    .get_post_by_slug(slug)
+                    .await
+                    .map_err(db_error)?
+                {
+                    Some(post) => posts.push(post),
+                    None => errors.push(format!("Post '{}' not found", slug)),
                 }
             }
-            Err(e) => {
-                errors.push(format!("Failed to import '{}': {}", slug, e));
-            }
+            posts
         }
-    }
-
-    let response = SyncResponse {
-        success: errors.is_empty(),
-        message: format!("Imported {} posts", imported),
-        synced_count: Some(imported),
-        errors: if errors.is_empty() {
-            None
-        } else {
-            Some(errors)
-        },
+        None => state
+            .database
+            .list_posts(PostFilters::default())
+            .await
+            .map_err(db_error)?,
     };
 
-    Ok(Json(response))
-}
+    let mut regenerated = Vec::new();
+    let mut skipped = 0;
+    for post in &posts {
+        let has_excerpt = post
+            .excerpt
+            .as_deref()
+            .is_some_and(|excerpt| !excerpt.trim().is_empty());
+        if request.missing_only && has_excerpt {
+            skipped += 1;
+            continue;
+        }
 
-// Helper functions
+        match regenerate_excerpt(&state, post, request.summary).await {
+            Ok(result) => regenerated.push(result),
+            Err(e) => errors.push(format!("Failed to regenerate '{}': {}", post.slug, e)),
+        }
+    }
 
-fn parse_tags_from_json(tags_json: &str) -> Vec<String> {
-    serde_json::from_str(tags_json).unwrap_or_default()
+    Ok(Json(BulkRegenerateExcerptResponse {
+        success: errors.is_empty(),
+        regenerated,
+        skipped,
+        errors,
+    }))
 }
 
-fn generate_slug(title: &str) -> String {
-    title
-        .to_lowercase()
-        .chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == '-' {
-                c
-            } else {
-                '-'
-            }
-        })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
+fn ensure_summarizer(
+    state: &ApiState,
+    summary: bool,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if summary && state.summarizer.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Summary generation is not configured (set SUMMARY_API_URL)",
+            )),
+        ));
+    }
+    Ok(())
 }
 
-fn generate_excerpt(content: &str, max_length: usize) -> String {
-    let text = content
-        .lines()
-        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
-        .collect::<Vec<_>>()
-        .join(" ");
+/// Recompute a post's excerpt and word count, and its summary when `summarize` is set
+async fn regenerate_excerpt(
+    state: &ApiState,
+    post: &Post,
+    summarize: bool,
+) -> anyhow::Result<RegeneratedExcerpt> {
+    let excerpt = state
+        .markdown
+        .first_paragraph_excerpt(&post.content, EXCERPT_LENGTH);
+    let word_count = count_words(&post.content);
+    state
+        .database
+        .set_post_excerpt(post.id, excerpt.as_deref(), word_count)
+        .await?;
+
+    let summary = match (&state.summarizer, summarize) {
+        (Some(summarizer), true) => {
+            let summary = summarizer.summarize(&post.title, &post.content).await?;
+            state
+                .database
+                .set_post_summary(post.id, summary.as_deref())
+                .await?;
+            summary
+        }
+        _ => post.summary.clone(),
+    };
 
-    if text.len() <= max_length {
-        text
-    } else {
-        format!("{}...", &text[..max_length])
+    if let Err(e) = state.cache.invalidate_post(&post.slug).await {
+        warn!("Failed to invalidate cache for {}: {}", post.slug, e);
     }
+
+    Ok(RegeneratedExcerpt {
+        slug: post.slug.clone(),
+        excerpt,
+        word_count,
+        reading_time: reading_time_minutes(word_count),
+        summary,
+    })
 }
 
-fn extract_title_from_markdown(content: &str) -> String {
-    content
-        .lines()
-        .find(|line| line.starts_with("# "))
-        .map(|line| line.trim_start_matches("# ").to_string())
-        .unwrap_or_else(|| "Untitled".to_string())
+/// GET /api/admin/jobs - Inspect the background job queue, newest first
+pub async fn list_jobs_api(
+    Query(filters): Query<JobFilters>,
+    State(state): State<ApiState>,
+) -> Result<Json<JobListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Listing jobs with filters: {:?}", filters);
+
+    let filters = JobFilters {
+        limit: Some(filters.limit.unwrap_or(50).clamp(1, 500)),
+        ..filters
+    };
+    let jobs = state.database.list_jobs(&filters).await.map_err(|e| {
+        error!("Database error listing jobs: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to load jobs")),
+        )
+    })?;
+
+    Ok(Json(JobListResponse {
+        total: jobs.len(),
+        jobs,
+    }))
 }
 
-/// POST /api/import/llm-article - Import a single LLM-generated article
-pub async fn import_llm_article_api(
+/// Most recent posts searched by the command palette
+const COMMAND_PALETTE_POSTS: i64 = 200;
+
+/// GET /api/admin/command-palette - Fuzzy search over admin actions, pages and recent posts
+pub async fn command_palette_api(
+    Query(query): Query<CommandPaletteQuery>,
     State(state): State<ApiState>,
-    Json(request): Json<LLMArticleImportRequest>,
-) -> Result<Json<LLMArticleImportResponse>, (StatusCode, Json<ErrorResponse>)> {
-    debug!("API: Importing LLM article from source: {}", request.source);
+) -> Result<Json<CommandPaletteResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let text = query.q.unwrap_or_default().trim().to_string();
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    debug!("API: Command palette search: {:?}", text);
 
-    if request.content.trim().is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::bad_request("Content cannot be empty")),
-        ));
-    }
+    let locale = state
+        .database
+        .get_site_config()
+        .await
+        .map(|config| config.unwrap_or_default().locale)
+        .unwrap_or_else(|e| {
+            warn!("Failed to load site locale: {}", e);
+            Default::default()
+        });
 
-    let import_response = state
-        .llm_import
-        .process_single_article(request.clone())
+    let posts = state
+        .database
+        .list_posts(PostFilters {
+            limit: Some(COMMAND_PALETTE_POSTS),
+            ..Default::default()
+        })
         .await
         .map_err(|e| {
-            error!("LLM import error: {}", e);
+            error!("Database error listing posts for command palette: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to process article")),
+                Json(ErrorResponse::internal_error("Failed to load posts")),
             )
         })?;
 
-    // Optionally save to database if requested
-    if request.published.unwrap_or(false) {
-        if let Err(e) = state
-            .llm_import
-            .save_imported_article(import_response.clone(), true)
-            .await
-        {
-            error!("Failed to save imported article: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to save article")),
-            ));
-        }
-    }
+    let entries = command_palette::static_entries(locale)
+        .into_iter()
+        .chain(
+            posts
+                .iter()
+                .map(|post| command_palette::post_entry(post, locale)),
+        )
+        .collect();
+    let mut entries = command_palette::search(entries, &text);
+    let total = entries.len();
+    entries.truncate(limit);
 
-    Ok(Json(import_response))
+    Ok(Json(CommandPaletteResponse {
+        query: text,
+        entries,
+        total,
+    }))
 }
 
-/// POST /api/import/batch - Import multiple articles in batch
-pub async fn batch_import_api(
+/// GET /api/admin/jobs/{id} - Status, attempts and result of a background job
+pub async fn get_job_api(
+    Path(id): Path<Uuid>,
+    State(state): State<ApiState>,
+) -> Result<Json<Job>, (StatusCode, Json<ErrorResponse>)> {
+    match state.database.get_job(id).await {
+        Ok(Some(job)) => Ok(Json(job)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!("Job '{}' not found", id))),
+        )),
+        Err(e) => {
+            error!("Database error loading job {}: {}", id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load job")),
+            ))
+        }
+    }
+}
+
+/// POST /api/admin/stats/rebuild - Recount the materialized post stats
+pub async fn rebuild_stats_api(
+    State(state): State<ApiState>,
+) -> Result<Json<StatsRebuildResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Stats rebuild requested");
+
+    let stats_error = |e: anyhow::Error| {
+        error!("Failed to rebuild stats: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to rebuild statistics",
+            )),
+        )
+    };
+
+    let before = state.database.get_post_stats().await.map_err(stats_error)?;
+    let stats = state.database.rebuild_stats().await.map_err(stats_error)?;
+    if before != stats {
+        warn!("Materialized stats had drifted and were recounted");
+    }
+
+    Ok(Json(StatsRebuildResponse {
+        success: true,
+        drifted: before != stats,
+        stats,
+    }))
+}
+
+/// POST /api/admin/reindex - Rebuild the full-text search index and report its consistency
+pub async fn reindex_search_api(
+    Query(query): Query<ReindexQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<ReindexResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Search index reindex requested ({:?})", query);
+
+    let index_error = |e: anyhow::Error| {
+        error!("Search index error: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Search index operation failed",
+            )),
+        )
+    };
+
+    let before = state
+        .database
+        .check_search_index()
+        .await
+        .map_err(index_error)?;
+
+    if query.check_only {
+        return Ok(Json(ReindexResponse {
+            success: true,
+            message: if before.consistent {
+                "Search index is consistent".to_string()
+            } else {
+                format!(
+                    "Search index is inconsistent: {} posts, {} indexed",
+                    before.post_count, before.indexed_count
+                )
+            },
+            rebuilt: false,
+            indexed_posts: before.indexed_count,
+            before,
+            after: None,
+        }));
+    }
+
+    let batch_size = query.batch_size.unwrap_or(200).clamp(1, 5000);
+    let indexed_posts = state
+        .database
+        .rebuild_search_index(batch_size)
+        .await
+        .map_err(index_error)?;
+    let after = state
+        .database
+        .check_search_index()
+        .await
+        .map_err(index_error)?;
+
+    Ok(Json(ReindexResponse {
+        success: after.consistent,
+        message: format!("Rebuilt search index with {} posts", indexed_posts),
+        rebuilt: true,
+        indexed_posts,
+        before,
+        after: Some(after),
+    }))
+}
+
+/// Parse a `from`/`to` search bound given as `YYYY-MM-DD` or RFC3339
+///
+/// A bare date used as upper bound covers that whole day.
+fn parse_search_date(
+    value: Option<&str>,
+    end_of_day: bool,
+) -> Result<Option<DateTime<Utc>>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(datetime.with_timezone(&Utc)));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(format!(
+                "Invalid date '{}', expected YYYY-MM-DD or RFC3339",
+                value
+            ))),
+        )
+    })?;
+    let date = if end_of_day {
+        date.succ_opt().unwrap_or(date)
+    } else {
+        date
+    };
+
+    Ok(date.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc()))
+}
+
+/// Query parameters for search
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    /// Alias of `per_page`, kept for backwards compatibility
+    pub limit: Option<usize>,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub author: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub published: Option<bool>,
+}
+
+/// Request body for creating a new post
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreatePostRequest {
+    pub title: String,
+    pub content: String,
+    pub category: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub published: Option<bool>,
+    pub featured: Option<bool>,
+    pub author: Option<String>,
+    /// Original URL when the post is a cross-post, used as `rel=canonical`
+    pub canonical_url: Option<String>,
+    /// URLs where the post is also published
+    pub syndication_links: Option<Vec<String>>,
+}
+
+/// Request body for updating a post
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePostRequest {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub category: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub published: Option<bool>,
+    pub featured: Option<bool>,
+    pub author: Option<String>,
+    /// Original URL of a cross-post; an empty string clears it
+    pub canonical_url: Option<String>,
+    /// URLs where the post is also published; replaces the stored list
+    pub syndication_links: Option<Vec<String>>,
+}
+
+/// Response for post operations (create, update, delete)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PostOperationResponse {
+    pub success: bool,
+    pub slug: String,
+    pub message: String,
+    pub post: Option<PostResponse>,
+}
+
+/// Cross-posting fields of a create or update request; `None` leaves a field alone
+struct SyndicationUpdate {
+    /// `Some(None)` clears the canonical URL
+    canonical_url: Option<Option<String>>,
+    syndication_links: Option<Vec<String>>,
+}
+
+/// Trim the cross-posting fields of a request, turning an empty canonical URL
+/// into `Some(None)`; 400 unless all are absolute http(s) URLs
+fn normalize_syndication(
+    canonical_url: Option<String>,
+    syndication_links: Option<Vec<String>>,
+) -> Result<SyndicationUpdate, (StatusCode, Json<ErrorResponse>)> {
+    let canonical_url = canonical_url.map(|url| {
+        let url = url.trim();
+        (!url.is_empty()).then(|| url.to_string())
+    });
+    let syndication_links = syndication_links.map(|links| {
+        links
+            .iter()
+            .map(|link| link.trim().to_string())
+            .filter(|link| !link.is_empty())
+            .collect::<Vec<_>>()
+    });
+
+    let urls = canonical_url
+        .iter()
+        .flatten()
+        .chain(syndication_links.iter().flatten());
+    for url in urls {
+        if !is_absolute_http_url(url) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "'{}' is not an absolute http(s) URL",
+                    url
+                ))),
+            ));
+        }
+    }
+
+    Ok(SyndicationUpdate {
+        canonical_url,
+        syndication_links,
+    })
+}
+
+/// Characters of the first paragraph used as generated excerpt
+const EXCERPT_LENGTH: usize = 200;
+
+/// Job kind of queued Dropbox syncs
+pub const DROPBOX_SYNC_JOB: &str = "dropbox_sync";
+
+/// Request body for Dropbox sync
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncDropboxRequest {
+    pub force: Option<bool>,
+    /// Only sync these files, as paths or names in the posts folder
+    pub paths: Option<Vec<String>>,
+    /// Only sync the files of these posts
+    pub slugs: Option<Vec<String>>,
+}
+
+impl SyncDropboxRequest {
+    fn is_selective(&self) -> bool {
+        self.paths.is_some() || self.slugs.is_some()
+    }
+}
+
+/// Response for sync operations
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncResponse {
+    pub success: bool,
+    pub message: String,
+    pub synced_count: Option<usize>,
+    pub errors: Option<Vec<String>>,
+    /// Slugs skipped because both copies changed; see `GET /api/sync/conflicts`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<Vec<String>>,
+}
+
+/// Request body for markdown import
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportMarkdownRequest {
+    pub files: Vec<MarkdownFileImport>,
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkdownFileImport {
+    pub path: String,
+    pub content: String,
+    pub metadata: Option<PostMetadata>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PostMetadata {
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub published: Option<bool>,
+    pub author: Option<String>,
+}
+
+/// POST /api/posts - Create a new post
+#[utoipa::path(
+    post,
+    path = "/api/posts",
+    tag = "posts",
+    request_body = CreatePostRequest,
+    responses(
+        (status = 200, description = "Post created", body = PostOperationResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn create_post_api(
+    State(state): State<ApiState>,
+    Json(request): Json<CreatePostRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Creating new post with title: {}", request.title);
+
+    // Validate request
+    if request.title.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Title cannot be empty")),
+        ));
+    }
+
+    if request.content.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Content cannot be empty")),
+        ));
+    }
+
+    let syndication = normalize_syndication(request.canonical_url, request.syndication_links)?;
+    let canonical_url = syndication.canonical_url.flatten();
+    let syndication_links = syndication.syndication_links.unwrap_or_default();
+
+    // Generate slug from title
+    let slug = generate_slug(&request.title);
+
+    // Check if slug already exists
+    if let Ok(Some(_)) = state.database.get_post_by_slug(&slug).await {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse::new(
+                "conflict",
+                format!("Post with slug '{}' already exists", slug),
+                409,
+            )),
+        ));
+    }
+
+    // Parse markdown content to HTML
+    let parsed = state
+        .markdown
+        .parse_markdown(&request.content)
+        .map_err(|e| {
+            error!("Failed to parse markdown: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to parse markdown")),
+            )
+        })?;
+    let html_content = parsed.html;
+
+    // Generate excerpt if not provided
+    let excerpt = generate_excerpt(&request.content, 200);
+
+    // Prepare the year-based path
+    let now = chrono::Utc::now();
+    let year = now.format("%Y");
+    let filename = format!("{}.md", slug);
+    let dropbox_path = format!("/posts/{}/{}", year, filename);
+
+    // Create post data
+    let create_data = CreatePost {
+        slug: slug.clone(),
+        title: request.title.clone(),
+        content: request.content.clone(),
+        html_content,
+        excerpt: Some(excerpt),
+        category: request.category,
+        tags: request.tags.unwrap_or_default(),
+        published: request.published.unwrap_or(false),
+        featured: request.featured.unwrap_or(false),
+        author: request.author,
+        dropbox_path: dropbox_path.clone(),
+    };
+
+    // Save to database first
+    let mut post = state.database.create_post(create_data).await.map_err(|e| {
+        error!("Database error creating post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to create post")),
+        )
+    })?;
+
+    // The slug may have been requested before it existed
+    if let Err(e) = state.cache.invalidate_post(&post.slug).await {
+        warn!("Failed to invalidate cache for {}: {}", post.slug, e);
+    }
+
+    if canonical_url.is_some() || !syndication_links.is_empty() {
+        state
+            .record_syndication(post.id, canonical_url.as_deref(), &syndication_links)
+            .await;
+        post.canonical_url = canonical_url;
+        post.set_syndication_links(syndication_links);
+    }
+
+    // Save to Dropbox using blog storage service
+    let blog_post = crate::services::blog_storage::BlogPost::from_post(&post);
+
+    match state.blog_storage.save_post(&blog_post, false).await {
+        Ok(_) => {
+            info!("Post saved to Dropbox: {}", dropbox_path);
+            state.record_sync(post.id, &post.content).await;
+        }
+        Err(e) => {
+            error!("Failed to save post to Dropbox: {}", e);
+            // Don't fail the request, but log the error
+            // The post is already saved in the database
+        }
+    }
+
+    if post.published {
+        state.events.publish(AdminEvent::PostPublished {
+            slug: post.slug.clone(),
+            title: post.title.clone(),
+            newly_published: true,
+        });
+    }
+
+    let response = PostOperationResponse {
+        success: true,
+        slug: post.slug.clone(),
+        message: format!("Post '{}' created successfully", request.title),
+        post: Some(state.post_response(post)),
+    };
+
+    Ok(Json(response))
+}
+
+/// PUT /api/posts/{slug} - Update an existing post
+#[utoipa::path(
+    put,
+    path = "/api/posts/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    request_body = UpdatePostRequest,
+    responses(
+        (status = 200, description = "Post updated", body = PostOperationResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_post_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<UpdatePostRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Updating post with slug: {}", slug);
+
+    // Get existing post
+    let existing_post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let existing_post = match existing_post {
+        Some(post) => post,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            ));
+        }
+    };
+
+    let syndication = normalize_syndication(request.canonical_url, request.syndication_links)?;
+
+    // Update HTML content if content is being updated
+    let html_content = if let Some(ref content) = request.content {
+        let parsed = state.markdown.parse_markdown(content).map_err(|e| {
+            error!("Failed to parse markdown: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to parse markdown")),
+            )
+        })?;
+        Some(parsed.html)
+    } else {
+        None
+    };
+
+    // Create update data
+    let update_data = UpdatePost {
+        title: request.title.clone(),
+        content: request.content.clone(),
+        html_content,
+        excerpt: None, // Keep existing excerpt unless content changes
+        category: request.category,
+        tags: request.tags,
+        published: request.published,
+        featured: request.featured,
+        author: request.author,
+        dropbox_path: None, // Keep existing path
+    };
+
+    // Update in database
+    let mut updated_post = state
+        .database
+        .update_post(existing_post.id, update_data)
+        .await
+        .map_err(|e| {
+            error!("Database error updating post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to update post")),
+            )
+        })?;
+
+    if syndication.canonical_url.is_some() || syndication.syndication_links.is_some() {
+        if let Some(post) = updated_post.as_mut() {
+            let canonical_url = syndication
+                .canonical_url
+                .unwrap_or_else(|| post.canonical_url.clone());
+            let syndication_links = syndication
+                .syndication_links
+                .unwrap_or_else(|| post.get_syndication_links());
+            state
+                .record_syndication(post.id, canonical_url.as_deref(), &syndication_links)
+                .await;
+            post.canonical_url = canonical_url;
+            post.set_syndication_links(syndication_links);
+        }
+    }
+
+    // Update in Dropbox if content changed
+    if let Some(ref updated_post) = updated_post {
+        let blog_post = crate::services::blog_storage::BlogPost::from_post(updated_post);
+
+        match state.blog_storage.save_post(&blog_post, false).await {
+            Ok(_) => {
+                info!("Post updated in Dropbox: {}", existing_post.dropbox_path);
+                state
+                    .record_sync(updated_post.id, &updated_post.content)
+                    .await;
+            }
+            Err(e) => {
+                error!("Failed to update post in Dropbox: {}", e);
+                // Don't fail the request, but log the error
+            }
+        }
+
+        if let Err(e) = state.cache.invalidate_post(&slug).await {
+            warn!("Failed to invalidate cache for {}: {}", slug, e);
+        }
+
+        if updated_post.published {
+            state.events.publish(AdminEvent::PostPublished {
+                slug: updated_post.slug.clone(),
+                title: updated_post.title.clone(),
+                newly_published: !existing_post.published,
+            });
+        }
+    }
+
+    let response = PostOperationResponse {
+        success: true,
+        slug: updated_post
+            .as_ref()
+            .map(|p| p.slug.clone())
+            .unwrap_or_else(|| slug.clone()),
+        message: format!(
+            "Post '{}' updated successfully",
+            updated_post
+                .as_ref()
+                .map(|p| p.title.as_str())
+                .unwrap_or(&slug)
+        ),
+        post: updated_post.map(|post| state.post_response(post)),
+    };
+
+    Ok(Json(response))
+}
+
+/// PATCH /api/posts/{slug}/metadata - Change category, tags and flags of a post
+///
+/// The markdown is not re-rendered, and `updated_at`, the version number and
+/// the content hash stay as they are; `metadata_updated_at` records the edit.
+#[utoipa::path(
+    patch,
+    path = "/api/posts/{slug}/metadata",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    request_body = UpdatePostMetadata,
+    responses(
+        (status = 200, description = "Metadata updated", body = PostOperationResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_post_metadata_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<UpdatePostMetadata>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Updating metadata of post with slug: {}", slug);
+
+    let existing_post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    let post = state
+        .database
+        .update_post_metadata(existing_post.id, request)
+        .await
+        .map_err(|e| {
+            error!("Database error updating post metadata: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to update post metadata",
+                )),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    // The frontmatter of the Dropbox file carries the metadata too
+    let blog_post = BlogPost::from_post(&post);
+    match state.blog_storage.save_post(&blog_post, false).await {
+        Ok(_) => state.record_sync(post.id, &post.content).await,
+        Err(e) => error!("Failed to update post in Dropbox: {}", e),
+    }
+
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    if post.published {
+        state.events.publish(AdminEvent::PostPublished {
+            slug: post.slug.clone(),
+            title: post.title.clone(),
+            newly_published: !existing_post.published,
+        });
+    }
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug,
+        message: format!("Metadata of post '{}' updated", post.title),
+        post: Some(state.post_response(post)),
+    }))
+}
+
+/// DELETE /api/posts/{slug} - Delete a post
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "Post deleted", body = PostOperationResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn delete_post_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Deleting post with slug: {}", slug);
+
+    // Get existing post
+    let existing_post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let existing_post = match existing_post {
+        Some(post) => post,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            ));
+        }
+    };
+
+    // Delete from database, leaving a tombstone so the URL answers 410 Gone
+    state
+        .database
+        .delete_post(existing_post.id)
+        .await
+        .map_err(|e| {
+            error!("Database error deleting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to delete post")),
+            )
+        })?;
+
+    // Cached pages, the sitemap and feeds may still list the post
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    // Delete from Dropbox (or move to archive folder)
+    match state.blog_storage.delete_post(&slug).await {
+        Ok(true) => {
+            info!("Post deleted from Dropbox: {}", existing_post.dropbox_path);
+        }
+        Ok(false) => {
+            warn!("Post not found in Dropbox: {}", slug);
+        }
+        Err(e) => {
+            error!("Failed to delete post from Dropbox: {}", e);
+            // Don't fail the request, but log the error
+        }
+    }
+
+    let response = PostOperationResponse {
+        success: true,
+        slug: slug.clone(),
+        message: format!("Post '{}' deleted successfully", existing_post.title),
+        post: None,
+    };
+
+    Ok(Json(response))
+}
+
+/// PUT /api/posts/{slug}/schedule - Set or clear the scheduled publication time
+pub async fn schedule_post_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<SchedulePostRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let publish_at = match (request.publish_at, request.publish_at_local) {
+        (Some(publish_at), _) => Some(publish_at),
+        (None, Some(local)) => Some(state.timezone.resolve_local(local).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "{} does not exist in the site timezone {}",
+                    local,
+                    state.timezone.get().name()
+                ))),
+            )
+        })?),
+        (None, None) => None,
+    };
+    info!("API: Scheduling post {} for {:?}", slug, publish_at);
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    if post.published && publish_at.is_some() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Published posts cannot be scheduled",
+            )),
+        ));
+    }
+
+    state
+        .database
+        .set_post_publish_at(post.id, publish_at)
+        .await
+        .map_err(|e| {
+            error!("Database error scheduling post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to schedule post")),
+            )
+        })?;
+
+    let updated_post = state.database.get_post_by_id(post.id).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let message = match publish_at {
+        Some(publish_at) => format!("Post '{}' scheduled for {}", post.title, publish_at),
+        None => format!("Schedule of post '{}' cleared", post.title),
+    };
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug,
+        message,
+        post: updated_post.map(|post| state.post_response(post)),
+    }))
+}
+
+/// PUT /api/posts/{slug}/expiry - Unpublish or archive a post automatically
+/// after a date, or clear its expiry with `expires_at: null`
+pub async fn set_post_expiry_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<SetPostExpiryRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let expires_at = match (request.expires_at, request.expires_at_local) {
+        (Some(expires_at), _) => Some(expires_at),
+        (None, Some(local)) => Some(state.timezone.resolve_local(local).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "{} does not exist in the site timezone {}",
+                    local,
+                    state.timezone.get().name()
+                ))),
+            )
+        })?),
+        (None, None) => None,
+    };
+    info!(
+        "API: Setting expiry of post {} to {:?} ({})",
+        slug,
+        expires_at,
+        request.action.as_str()
+    );
+
+    if request.notice_days.is_some_and(|days| days < 0) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "notice_days must not be negative",
+            )),
+        ));
+    }
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    state
+        .database
+        .set_post_expiry(
+            post.id,
+            expires_at,
+            request.action,
+            expires_at.and(request.notice_days),
+        )
+        .await
+        .map_err(|e| {
+            error!("Database error setting post expiry: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to set post expiry")),
+            )
+        })?;
+
+    let updated_post = state.database.get_post_by_id(post.id).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let message = match expires_at {
+        Some(expires_at) => format!(
+            "Post '{}' will {} at {}",
+            post.title,
+            request.action.as_str(),
+            expires_at
+        ),
+        None => format!("Expiry of post '{}' cleared", post.title),
+    };
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug,
+        message,
+        post: updated_post.map(|post| state.post_response(post)),
+    }))
+}
+
+/// PUT /api/posts/{slug}/pin - Pin a post above the chronological stream, or unpin it
+pub async fn pin_post_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<PinPostRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Setting pin of post {} to {} ({:?})",
+        slug, request.pinned, request.pin_order
+    );
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    if request.pin_order.is_some_and(|order| order < 0) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("pin_order must not be negative")),
+        ));
+    }
+
+    if request.pinned && !post.pinned {
+        let pinned_count = state
+            .database
+            .count_posts(PostFilters {
+                pinned: Some(true),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| {
+                error!("Database error counting pinned posts: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Database error")),
+                )
+            })?;
+
+        if pinned_count >= state.max_pinned_posts {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "At most {} posts can be pinned; unpin another post first",
+                    state.max_pinned_posts
+                ))),
+            ));
+        }
+    }
+
+    state
+        .database
+        .set_post_pin(post.id, request.pinned, request.pin_order)
+        .await
+        .map_err(|e| {
+            error!("Database error pinning post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to pin post")),
+            )
+        })?;
+
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    let updated_post = state.database.get_post_by_id(post.id).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let message = if request.pinned {
+        format!("Post '{}' pinned", post.title)
+    } else {
+        format!("Post '{}' unpinned", post.title)
+    };
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug,
+        message,
+        post: updated_post.map(|post| state.post_response(post)),
+    }))
+}
+
+/// PUT /api/posts/{slug}/visibility - Make a post public, unlisted or private
+pub async fn set_post_visibility_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<SetVisibilityRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Setting visibility of post {} to {}",
+        slug,
+        request.visibility.as_str()
+    );
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    state
+        .database
+        .set_post_visibility(post.id, request.visibility)
+        .await
+        .map_err(|e| {
+            error!("Database error setting post visibility: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to update visibility")),
+            )
+        })?;
+
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    let updated_post = state.database.get_post_by_id(post.id).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug,
+        message: format!(
+            "Post '{}' is now {}",
+            post.title,
+            request.visibility.as_str()
+        ),
+        post: updated_post.map(|post| state.post_response(post)),
+    }))
+}
+
+/// PUT /api/posts/{slug}/password - Protect a post with a password, or remove it
+pub async fn set_post_password_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<SetPostPasswordRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: {} password protection of post {}",
+        if request.password.is_some() {
+            "Setting"
+        } else {
+            "Removing"
+        },
+        slug
+    );
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    let password_hash = match request.password.as_deref() {
+        Some("") => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request("Password must not be empty")),
+            ));
+        }
+        Some(password) => Some(hash_post_password(password).map_err(|e| {
+            error!("Failed to hash password of post {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to set password")),
+            )
+        })?),
+        None => None,
+    };
+
+    state
+        .database
+        .set_post_password(post.id, password_hash.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Database error setting post password: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to set password")),
+            )
+        })?;
+
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    let updated_post = state.database.get_post_by_id(post.id).await.map_err(|e| {
+        error!("Database error getting post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let message = if password_hash.is_some() {
+        format!("Post '{}' is now password-protected", post.title)
+    } else {
+        format!("Password protection removed from post '{}'", post.title)
+    };
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug,
+        message,
+        post: updated_post.map(|post| state.post_response(post)),
+    }))
+}
+
+/// GET /api/calendar - Posts of a month grouped by day
+pub async fn get_calendar_api(
+    Query(query): Query<CalendarQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<CalendarResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Getting content calendar for {:?}", query.month);
+
+    if let Some(month) = &query.month {
+        crate::services::calendar::parse_month(month).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(e.to_string())),
+            )
+        })?;
+    }
+
+    let calendar = state
+        .calendar
+        .get_month(query.month.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to build content calendar: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load calendar")),
+            )
+        })?;
+
+    Ok(Json(CalendarResponse {
+        success: true,
+        data: calendar,
+    }))
+}
+
+/// POST /api/sync/dropbox - Queue a sync of posts from Dropbox
+pub async fn sync_dropbox_api(
+    State(state): State<ApiState>,
+    Json(request): Json<SyncDropboxRequest>,
+) -> Result<(StatusCode, Json<JobEnqueuedResponse>), (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Queueing Dropbox sync (force: {:?})", request.force);
+
+    let job = state
+        .jobs
+        .enqueue(DROPBOX_SYNC_JOB, &request)
+        .await
+        .map_err(|e| {
+            error!("Failed to queue Dropbox sync: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to queue sync")),
+            )
+        })?;
+
+    Ok((StatusCode::ACCEPTED, Json(JobEnqueuedResponse::from(&job))))
+}
+
+/// Sync posts from Dropbox; fails only when the Dropbox listing itself fails,
+/// per-post problems are reported in the response
+pub async fn run_dropbox_sync(
+    state: &ApiState,
+    request: SyncDropboxRequest,
+) -> anyhow::Result<SyncResponse> {
+    info!("Syncing posts from Dropbox (force: {:?})", request.force);
+
+    let mut synced = 0;
+    let mut errors = Vec::new();
+    let mut conflicts = Vec::new();
+
+    // Get the requested or all published posts from Dropbox
+    let dropbox_posts = if request.is_selective() {
+        load_selected_posts(state, &request, &mut errors).await
+    } else {
+        state
+            .blog_storage
+            .list_published_posts()
+            .await
+            .context("Failed to list Dropbox posts")?
+    };
+
+    for dropbox_post in dropbox_posts {
+        let (html_content, excerpt) = match render_synced_post(&state.markdown, &dropbox_post) {
+            Ok(rendered) => rendered,
+            Err(e) => {
+                errors.push(format!(
+                    "Failed to render post '{}': {}",
+                    dropbox_post.metadata.slug, e
+                ));
+                continue;
+            }
+        };
+        let custom_frontmatter = dropbox_post.metadata.extra_yaml();
+        let canonical_url = dropbox_post.metadata.canonical_url();
+        let syndication_links = dropbox_post.metadata.syndication_links();
+
+        // Check if post exists in database
+        match state
+            .database
+            .get_post_by_slug(&dropbox_post.metadata.slug)
+            .await
+        {
+            Ok(Some(db_post)) => {
+                // Post exists, only take Dropbox changes that don't discard database edits
+                let sync_state = if request.force.unwrap_or(false) {
+                    SyncState::DropboxAhead
+                } else {
+                    match state.sync_conflicts.inspect(&db_post, &dropbox_post).await {
+                        Ok(status) => status.state,
+                        Err(e) => {
+                            errors.push(format!(
+                                "Failed to compare post '{}': {}",
+                                dropbox_post.metadata.slug, e
+                            ));
+                            continue;
+                        }
+                    }
+                };
+
+                let should_update = match sync_state {
+                    SyncState::DropboxAhead => true,
+                    // Same content; still pick up newer frontmatter
+                    SyncState::InSync => dropbox_post.metadata.updated_at > db_post.updated_at,
+                    SyncState::DatabaseAhead => {
+                        debug!(
+                            "Database copy of '{}' is newer, not overwriting",
+                            dropbox_post.metadata.slug
+                        );
+                        false
+                    }
+                    SyncState::Conflict => {
+                        warn!(
+                            "Sync conflict on '{}', both copies changed",
+                            dropbox_post.metadata.slug
+                        );
+                        conflicts.push(dropbox_post.metadata.slug.clone());
+                        false
+                    }
+                };
+
+                if should_update {
+                    // Update existing post
+                    let update_data = crate::models::UpdatePost {
+                        title: Some(dropbox_post.metadata.title.clone()),
+                        content: Some(dropbox_post.content.clone()),
+                        html_content: Some(html_content),
+                        excerpt,
+                        category: dropbox_post.metadata.category.clone(),
+                        tags: Some(dropbox_post.metadata.tags.clone()),
+                        published: Some(dropbox_post.metadata.published),
+                        featured: None,
+                        author: dropbox_post.metadata.author.clone(),
+                        dropbox_path: Some(dropbox_post.dropbox_path.clone()),
+                    };
+
+                    match state.database.update_post(db_post.id, update_data).await {
+                        Ok(_) => {
+                            synced += 1;
+                            info!("Updated existing post: {}", dropbox_post.metadata.slug);
+                            state.record_sync(db_post.id, &dropbox_post.content).await;
+                            state
+                                .record_custom_frontmatter(
+                                    db_post.id,
+                                    custom_frontmatter.as_deref(),
+                                )
+                                .await;
+                            state
+                                .record_syndication(
+                                    db_post.id,
+                                    canonical_url.as_deref(),
+                                    &syndication_links,
+                                )
+                                .await;
+                        }
+                        Err(e) => {
+                            errors.push(format!(
+                                "Failed to update post '{}': {}",
+                                dropbox_post.metadata.slug, e
+                            ));
+                        }
+                    }
+                }
+            }
+            Ok(None) => {
+                // New post, create it
+                let create_data = crate::models::CreatePost {
+                    slug: dropbox_post.metadata.slug.clone(),
+                    title: dropbox_post.metadata.title.clone(),
+                    content: dropbox_post.content.clone(),
+                    html_content,
+                    excerpt,
+                    category: dropbox_post.metadata.category,
+                    tags: dropbox_post.metadata.tags,
+                    published: dropbox_post.metadata.published,
+                    featured: false,
+                    author: dropbox_post.metadata.author,
+                    dropbox_path: dropbox_post.dropbox_path,
+                };
+
+                match state.database.create_post(create_data).await {
+                    Ok(post) => {
+                        synced += 1;
+                        info!("Created new post: {}", dropbox_post.metadata.slug);
+                        state.record_sync(post.id, &post.content).await;
+                        state
+                            .record_custom_frontmatter(post.id, custom_frontmatter.as_deref())
+                            .await;
+                        state
+                            .record_syndication(
+                                post.id,
+                                canonical_url.as_deref(),
+                                &syndication_links,
+                            )
+                            .await;
+                    }
+                    Err(e) => {
+                        errors.push(format!(
+                            "Failed to create post '{}': {}",
+                            dropbox_post.metadata.slug, e
+                        ));
+                    }
+                }
+            }
+            Err(e) => {
+                errors.push(format!(
+                    "Database error checking post '{}': {}",
+                    dropbox_post.metadata.slug, e
+                ));
+            }
+        }
+    }
+
+    state.events.publish(AdminEvent::SyncCompleted {
+        synced,
+        errors: errors.len(),
+        conflicts: conflicts.len(),
+    });
+
+    Ok(SyncResponse {
+        success: errors.is_empty(),
+        message: if conflicts.is_empty() {
+            format!("Synced {} posts from Dropbox", synced)
+        } else {
+            format!(
+                "Synced {} posts from Dropbox, {} conflicts need resolution",
+                synced,
+                conflicts.len()
+            )
+        },
+        synced_count: Some(synced),
+        errors: if errors.is_empty() {
+            None
+        } else {
+            Some(errors)
+        },
+        conflicts: if conflicts.is_empty() {
+            None
+        } else {
+            Some(conflicts)
+        },
+    })
+}
+
+/// HTML and excerpt of a Dropbox post, preferring the frontmatter excerpt
+fn render_synced_post(
+    markdown: &MarkdownService,
+    post: &BlogPost,
+) -> anyhow::Result<(String, Option<String>)> {
+    let html = markdown.parse_markdown(&post.content)?.html;
+    let excerpt = post
+        .metadata
+        .excerpt
+        .clone()
+        .or_else(|| markdown.first_paragraph_excerpt(&post.content, EXCERPT_LENGTH));
+    Ok((html, excerpt))
+}
+
+/// Fetch only the files named by `paths` and `slugs`, recording failures in `errors`
+async fn load_selected_posts(
+    state: &ApiState,
+    request: &SyncDropboxRequest,
+    errors: &mut Vec<String>,
+) -> Vec<BlogPost> {
+    let mut paths = request.paths.clone().unwrap_or_default();
+    for slug in request.slugs.iter().flatten() {
+        // Posts keep their file name when synced, so prefer the recorded path
+        let known_path = match state.database.get_post_by_slug(slug).await {
+            Ok(Some(post)) => Some(post.dropbox_path)
+                .filter(|path| state.blog_storage.resolve_post_path(path).is_ok()),
+            Ok(None) => None,
+            Err(e) => {
+                errors.push(format!("Database error checking post '{}': {}", slug, e));
+                continue;
+            }
+        };
+        paths.push(known_path.unwrap_or_else(|| format!("{}.md", slug)));
+    }
+    paths.sort();
+    paths.dedup();
+
+    let mut posts = Vec::new();
+    for path in paths {
+        match state.blog_storage.load_post(&path).await {
+            Ok(Some(post)) if post.metadata.published => posts.push(post),
+            Ok(Some(post)) => {
+                debug!("Skipping unpublished post: {}", post.dropbox_path);
+            }
+            Ok(None) => errors.push(format!("{} has no frontmatter", path)),
+            Err(e) => errors.push(format!("Failed to load '{}': {}", path, e)),
+        }
+    }
+
+    info!("Loaded {} selected posts from Dropbox", posts.len());
+    posts
+}
+
+/// GET /api/sync/conflicts - Posts whose database and Dropbox content diverged
+pub async fn list_sync_conflicts_api(
+    State(state): State<ApiState>,
+) -> Result<Json<SyncConflictsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Checking posts for sync conflicts");
+
+    let conflicts = state.sync_conflicts.find_diverged().await.map_err(|e| {
+        error!("Failed to check sync conflicts: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to check sync conflicts",
+            )),
+        )
+    })?;
+
+    Ok(Json(SyncConflictsResponse {
+        total: conflicts.len(),
+        conflicts,
+    }))
+}
+
+/// POST /api/sync/conflicts/{slug}/resolve - Settle a diverged post
+pub async fn resolve_sync_conflict_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<ResolveConflictRequest>,
+) -> Result<Json<PostOperationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Resolving sync conflict of {} ({:?})",
+        slug, request.resolution
+    );
+
+    if request.resolution == ConflictResolution::ManualMerge && request.content.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "`content` is required for manual-merge".to_string(),
+            )),
+        ));
+    }
+
+    let post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
+        error!("Database error getting post {}: {}", slug, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    })?;
+
+    let Some(post) = post else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        ));
+    };
+
+    let post = state
+        .sync_conflicts
+        .resolve(post, request.resolution, request.content)
+        .await
+        .map_err(|e| {
+            error!("Failed to resolve sync conflict of {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(format!(
+                    "Failed to resolve conflict: {}",
+                    e
+                ))),
+            )
+        })?;
+
+    Ok(Json(PostOperationResponse {
+        success: true,
+        slug: post.slug.clone(),
+        message: format!("Conflict of '{}' resolved", post.title),
+        post: Some(state.post_response(post)),
+    }))
+}
+
+/// POST /api/import/markdown - Import markdown files in bulk
+#[utoipa::path(
+    post,
+    path = "/api/import/markdown",
+    tag = "import",
+    request_body = ImportMarkdownRequest,
+    responses(
+        (status = 200, description = "Import result", body = SyncResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn import_markdown_api(
+    State(state): State<ApiState>,
+    Json(request): Json<ImportMarkdownRequest>,
+) -> Result<Json<SyncResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Importing {} markdown files", request.files.len());
+
+    let mut imported = 0;
+    let mut errors = Vec::new();
+
+    for file in request.files {
+        // Extract title from metadata or content
+        let title = file
+            .metadata
+            .as_ref()
+            .and_then(|m| m.title.clone())
+            .unwrap_or_else(|| extract_title_from_markdown(&file.content));
+
+        let slug = generate_slug(&title);
+
+        // Check if should overwrite
+        if !request.overwrite.unwrap_or(false) {
+            if let Ok(Some(_)) = state.database.get_post_by_slug(&slug).await {
+                errors.push(format!("Post '{}' already exists", slug));
+                continue;
+            }
+        }
+
+        // Parse markdown
+        let html_content = match state.markdown.parse_markdown(&file.content) {
+            Ok(parsed) => parsed.html,
+            Err(e) => {
+                errors.push(format!("Failed to parse markdown for '{}': {}", slug, e));
+                continue;
+            }
+        };
+        let excerpt = generate_excerpt(&file.content, 200);
+
+        // Create post
+        let create_data = CreatePost {
+            slug: slug.clone(),
+            title,
+            content: file.content.clone(),
+            html_content,
+            excerpt: Some(excerpt),
+            category: file.metadata.as_ref().and_then(|m| m.category.clone()),
+            tags: file
+                .metadata
+                .as_ref()
+                .and_then(|m| m.tags.clone())
+                .unwrap_or_default(),
+            published: file
+                .metadata
+                .as_ref()
+                .and_then(|m| m.published)
+                .unwrap_or(false),
+            featured: false,
+            author: file.metadata.as_ref().and_then(|m| m.author.clone()),
+            dropbox_path: file.path.clone(),
+        };
+
+        match state.database.create_post(create_data).await {
+            Ok(post) => {
+                imported += 1;
+                if let Err(e) = state.cache.invalidate_post(&post.slug).await {
+                    warn!("Failed to invalidate cache for {}: {}", post.slug, e);
+                }
+
+                // Save to Dropbox as well
+                let blog_post = crate::services::blog_storage::BlogPost::from_post(&post);
+
+                if let Err(e) = state.blog_storage.save_post(&blog_post, false).await {
+                    errors.push(format!("Failed to save '{}' to Dropbox: {}", slug, e));
+                }
+            }
+            Err(e) => {
+                errors.push(format!("Failed to import '{}': {}", slug, e));
+            }
+        }
+    }
+
+    let response = SyncResponse {
+        success: errors.is_empty(),
+        message: format!("Imported {} posts", imported),
+        synced_count: Some(imported),
+        errors: if errors.is_empty() {
+            None
+        } else {
+            Some(errors)
+        },
+        conflicts: None,
+    };
+
+    Ok(Json(response))
+}
+
+// Helper functions
+
+/// URL slug of a title: lowercase alphanumerics separated by single hyphens
+pub fn generate_slug(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Plain excerpt of a post body without headings, cut at `max_length` characters
+pub fn generate_excerpt(content: &str, max_length: usize) -> String {
+    let text = content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if text.chars().count() <= max_length {
+        text
+    } else {
+        format!("{}...", text.chars().take(max_length).collect::<String>())
+    }
+}
+
+fn extract_title_from_markdown(content: &str) -> String {
+    content
+        .lines()
+        .find(|line| line.starts_with("# "))
+        .map(|line| line.trim_start_matches("# ").to_string())
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+/// POST /api/import/llm-article - Import a single LLM-generated article
+#[utoipa::path(
+    post,
+    path = "/api/import/llm-article",
+    tag = "import",
+    request_body = LLMArticleImportRequest,
+    responses(
+        (status = 200, description = "Article imported", body = LLMArticleImportResponse),
+        (status = 400, description = "Invalid article", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn import_llm_article_api(
+    State(state): State<ApiState>,
+    Json(request): Json<LLMArticleImportRequest>,
+) -> Result<Json<LLMArticleImportResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Importing LLM article from source: {}", request.source);
+
+    if request.content.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Content cannot be empty")),
+        ));
+    }
+
+    let import_response = state
+        .llm_import
+        .process_single_article(request.clone())
+        .await
+        .map_err(|e| {
+            error!("LLM import error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to process article")),
+            )
+        })?;
+
+    // Optionally save to database if requested
+    if request.published.unwrap_or(false) {
+        if let Err(e) = state
+            .llm_import
+            .save_imported_article(
+                import_response.clone(),
+                true,
+                request.override_quality_gates,
+            )
+            .await
+        {
+            if let Some(gate_error) = e.downcast_ref::<QualityGateError>() {
+                return Err((
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(ErrorResponse::new(
+                        "quality_gate_failed",
+                        gate_error.to_string(),
+                        422,
+                    )),
+                ));
+            }
+            error!("Failed to save imported article: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to save article")),
+            ));
+        }
+    }
+
+    Ok(Json(import_response))
+}
+
+/// POST /api/import/batch - Start a background job importing multiple articles
+#[utoipa::path(
+    post,
+    path = "/api/import/batch",
+    tag = "import",
+    request_body = BatchImportRequest,
+    responses(
+        (status = 202, description = "Import job started", body = ImportJobCreatedResponse),
+        (status = 400, description = "No articles", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn batch_import_api(
     State(state): State<ApiState>,
     Json(request): Json<BatchImportRequest>,
-) -> Result<Json<BatchImportResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<ImportJobCreatedResponse>), (StatusCode, Json<ErrorResponse>)> {
     debug!("API: Batch importing {} articles", request.articles.len());
 
-    if request.articles.is_empty() {
+    if request.articles.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "No articles provided for import",
+            )),
+        ));
+    }
+
+    if request.articles.len() > 50 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Too many articles (max 50 per batch)",
+            )),
+        ));
+    }
+
+    let job = state.import_jobs.start(request).await.map_err(|e| {
+        error!("Failed to queue batch import: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to queue batch import",
+            )),
+        )
+    })?;
+    info!(
+        "API: Started batch import job {} ({} articles)",
+        job.id, job.total
+    );
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ImportJobCreatedResponse {
+            status_url: format!("/api/import/jobs/{}", job.id),
+            events_url: format!("/api/import/jobs/{}/events", job.id),
+            job_id: job.id,
+            status: job.status,
+            total: job.total,
+        }),
+    ))
+}
+
+/// GET /api/import/jobs/{id} - Current progress and results of a batch import job
+#[utoipa::path(
+    get,
+    path = "/api/import/jobs/{id}",
+    tag = "import",
+    params(("id" = String, Path, description = "Import job ID")),
+    responses(
+        (status = 200, description = "Import job progress", body = ImportJob),
+        (status = 404, description = "Job not found", body = ErrorResponse)
+    )
+)]
+pub async fn import_job_api(
+    Path(id): Path<String>,
+    State(state): State<ApiState>,
+) -> Result<Json<ImportJob>, (StatusCode, Json<ErrorResponse>)> {
+    match state.import_jobs.get(&id).await {
+        Ok(Some(job)) => Ok(Json(job)),
+        Ok(None) => Err(import_job_not_found(&id)),
+        Err(e) => {
+            error!("Database error loading import job {}: {}", id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load import job")),
+            ))
+        }
+    }
+}
+
+fn import_job_not_found(id: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::not_found(format!(
+            "Import job '{}' not found",
+            id
+        ))),
+    )
+}
+
+/// GET /api/import/jobs/{id}/events - Stream a batch import job's progress as Server-Sent Events
+pub async fn import_job_events_api(
+    Path(id): Path<String>,
+    State(state): State<ApiState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    let events = match state.import_jobs.subscribe(&id).await {
+        Ok(Some(events)) => events,
+        Ok(None) => return Err(import_job_not_found(&id)),
+        Err(e) => {
+            error!("Database error loading import job {}: {}", id, e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load import job")),
+            ));
+        }
+    };
+
+    let stream = events.map(|event| Event::default().event(event.name()).json_data(&event));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// POST /api/posts/{slug}/save - Save a processed LLM article to database
+pub async fn save_llm_article_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(save_request): Json<SaveLLMArticleRequest>,
+) -> Result<Json<PostResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Saving LLM article with slug: {}", slug);
+
+    // Check if article already exists
+    if state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error checking slug {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .is_some()
+    {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse::bad_request(format!(
+                "Article with slug '{}' already exists",
+                slug
+            ))),
+        ));
+    }
+
+    let create_post = CreatePost {
+        slug: slug.clone(),
+        title: save_request.title,
+        content: save_request.content,
+        html_content: save_request.html_content,
+        excerpt: save_request.excerpt,
+        category: save_request.category,
+        tags: save_request.tags,
+        published: save_request.published,
+        featured: save_request.featured,
+        author: save_request.author,
+        dropbox_path: save_request.dropbox_path,
+    };
+
+    let post = state.database.create_post(create_post).await.map_err(|e| {
+        error!("Database error creating post: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to save article")),
+        )
+    })?;
+
+    if let Err(e) = state.cache.invalidate_post(&post.slug).await {
+        warn!("Failed to invalidate cache for {}: {}", post.slug, e);
+    }
+
+    let response = state.post_response(post);
+    Ok(Json(response))
+}
+
+/// Request for saving LLM article
+#[derive(Debug, Deserialize)]
+pub struct SaveLLMArticleRequest {
+    pub title: String,
+    pub content: String,
+    pub html_content: String,
+    pub excerpt: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub published: bool,
+    pub featured: bool,
+    pub author: Option<String>,
+    pub dropbox_path: String,
+}
+
+// Media API endpoints
+
+/// Error response for a multipart body that could not be read
+///
+/// Bodies cut off by the route's size limit answer 413 rather than 400.
+fn multipart_error(status: StatusCode) -> (StatusCode, Json<ErrorResponse>) {
+    if status == StatusCode::PAYLOAD_TOO_LARGE {
+        (
+            status,
+            Json(ErrorResponse::payload_too_large(
+                "Uploaded file is too large",
+            )),
+        )
+    } else {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Invalid multipart data")),
+        )
+    }
+}
+
+/// Error response for a failed media upload
+///
+/// Files whose content contradicts their declared type answer 415.
+fn upload_error(e: &anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    match ContentTypeMismatch::find(e) {
+        Some(mismatch) => (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(ErrorResponse::new(
+                "unsupported_media_type",
+                mismatch.to_string(),
+                415,
+            )),
+        ),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(format!(
+                "Upload failed: {}",
+                e
+            ))),
+        ),
+    }
+}
+
+/// Multipart form read by [`upload_media_api`], described for the OpenAPI spec
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct MediaUploadForm {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    pub alt_text: Option<String>,
+    pub caption: Option<String>,
+    /// Store a new copy even if an identical file exists
+    pub force: Option<bool>,
+    /// Also list the file as a download at the end of `post_slug`
+    pub attachment: Option<bool>,
+    pub post_slug: Option<String>,
+}
+
+/// POST /api/media/upload - Upload media file
+///
+/// Identical files are stored once; send `force=true` to store a new copy anyway.
+/// With `attachment=true` and `post_slug` the file is also offered as a download
+/// at the end of that post.
+#[utoipa::path(
+    post,
+    path = "/api/media/upload",
+    tag = "media",
+    request_body(content = MediaUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Upload result", body = MediaUploadResponse),
+        (status = 400, description = "Invalid upload", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 413, description = "Upload larger than `MEDIA_UPLOAD_LIMIT_BYTES`", body = ErrorResponse),
+        (status = 415, description = "Content does not match the declared type or extension", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+pub async fn upload_media_api(
+    State(state): State<ApiState>,
+    mut multipart: Multipart,
+) -> Result<Json<MediaUploadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Uploading media file");
+
+    let mut alt_text: Option<String> = None;
+    let mut caption: Option<String> = None;
+    let mut force_new = false;
+    let mut attachment = false;
+    let mut post_slug: Option<String> = None;
+    let mut file: Option<UploadedFile> = None;
+
+    // Process multipart form data
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        error!("Failed to read multipart field: {}", e);
+        multipart_error(e.status())
+    })? {
+        match field.name() {
+            Some("file") => {
+                // Buffer the file so that the fields after it can be read
+                file = Some(UploadedFile::from_field(field).await.map_err(|e| {
+                    error!("Failed to read uploaded file: {}", e);
+                    multipart_error(
+                        e.downcast_ref::<MultipartError>()
+                            .map_or(StatusCode::BAD_REQUEST, MultipartError::status),
+                    )
+                })?);
+            }
+            Some("alt_text") => {
+                alt_text = field.text().await.ok();
+            }
+            Some("caption") => {
+                caption = field.text().await.ok();
+            }
+            Some("force") => {
+                force_new = field
+                    .text()
+                    .await
+                    .is_ok_and(|value| matches!(value.trim(), "true" | "1"));
+            }
+            Some("attachment") => {
+                attachment = field
+                    .text()
+                    .await
+                    .is_ok_and(|value| matches!(value.trim(), "true" | "1"));
+            }
+            Some("post_slug") => {
+                post_slug = field.text().await.ok().map(|slug| slug.trim().to_string());
+            }
+            _ => {
+                // Skip unknown fields
+                let _ = field.bytes().await;
+            }
+        }
+    }
+
+    let file = file.ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("No file provided")),
+        )
+    })?;
+
+    // Resolve the post before storing anything, so a bad slug uploads nothing
+    let attach_to = if attachment {
+        let slug = post_slug.filter(|slug| !slug.is_empty()).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(
+                    "post_slug is required for attachments",
+                )),
+            )
+        })?;
+        Some(find_post_for_api(&state, &slug).await?)
+    } else {
+        None
+    };
+
+    // Upload file using media service
+    let (media_file, deduplicated) = state
+        .media
+        .upload_data(file, alt_text, caption, force_new)
+        .await
+        .map_err(|e| {
+            error!("Media upload error: {}", e);
+            upload_error(&e)
+        })?;
+    state.cache.forget_missing_media().await;
+
+    let attachment = match attach_to {
+        Some(post) => {
+            let attachment = state
+                .database
+                .add_post_attachment(post.id, media_file.id)
+                .await
+                .map_err(|e| {
+                    error!("Failed to attach {} to {}: {}", media_file.id, post.slug, e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse::internal_error(
+                            "File uploaded but could not be attached to the post",
+                        )),
+                    )
+                })?;
+            if let Err(e) = state.cache.invalidate_post(&post.slug).await {
+                warn!("Failed to invalidate cache for {}: {}", post.slug, e);
+            }
+            Some(attachment)
+        }
+        None => None,
+    };
+
+    let response = MediaUploadResponse {
+        success: true,
+        message: if deduplicated {
+            format!(
+                "Identical file already uploaded as '{}'",
+                media_file.filename
+            )
+        } else {
+            format!("File '{}' uploaded successfully", media_file.filename)
+        },
+        media: Some(media_file),
+        errors: None,
+        attachment,
+    };
+
+    Ok(Json(response))
+}
+
+/// POST /api/media/paste - Upload a pasted image and get Markdown to insert
+///
+/// Send the image as the raw request body with its `Content-Type` (`filename` and
+/// `alt` as query parameters), or as JSON with base64 `data`. Identical images
+/// are stored once.
+#[utoipa::path(
+    post,
+    path = "/api/media/paste",
+    tag = "media",
+    params(MediaPasteQuery),
+    request_body(content = MediaPasteRequest, description = "Raw image body (`image/*`) or base64 JSON"),
+    responses(
+        (status = 200, description = "Uploaded image and its Markdown", body = MediaPasteResponse),
+        (status = 400, description = "Empty or undecodable image", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 413, description = "Image larger than `MEDIA_UPLOAD_LIMIT_BYTES`", body = ErrorResponse),
+        (status = 415, description = "Not an image", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+pub async fn paste_media_api(
+    State(state): State<ApiState>,
+    Query(query): Query<MediaPasteQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<MediaPasteResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let bad_request = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(message)),
+        )
+    };
+
+    let request_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_lowercase())
+        .unwrap_or_default();
+
+    let (content_type, data, filename, alt) = if request_type == "application/json" {
+        let request: MediaPasteRequest = serde_json::from_slice(&body)
+            .map_err(|e| bad_request(format!("Invalid JSON body: {}", e)))?;
+        let (data_url_type, data) =
+            decode_pasted_image(&request.data).map_err(|e| bad_request(e.to_string()))?;
+        let content_type = data_url_type
+            .or(request.content_type)
+            .unwrap_or_default()
+            .to_lowercase();
+        (content_type, data, request.filename, request.alt)
+    } else {
+        (request_type, body.to_vec(), query.filename, query.alt)
+    };
+    debug!("API: Pasting {} byte {} image", data.len(), content_type);
+
+    if !content_type.starts_with("image/") {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(ErrorResponse::new(
+                "unsupported_media_type",
+                format!("Expected an image, got '{}'", content_type),
+                415,
+            )),
+        ));
+    }
+    if data.is_empty() {
+        return Err(bad_request("No image data provided".to_string()));
+    }
+
+    let file = UploadedFile {
+        filename: filename
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| pasted_image_filename(&content_type)),
+        content_type,
+        data,
+    };
+    let alt = alt.filter(|alt| !alt.trim().is_empty());
+
+    let (media_file, deduplicated) = state
+        .media
+        .upload_data(file, alt.clone(), None, false)
+        .await
+        .map_err(|e| {
+            error!("Media paste error: {}", e);
+            upload_error(&e)
+        })?;
+    state.cache.forget_missing_media().await;
+
+    let alt = alt
+        .or_else(|| media_file.alt_text.clone())
+        .unwrap_or_default();
+    Ok(Json(MediaPasteResponse {
+        markdown: image_markdown(&alt, &media_file.url),
+        media: media_file,
+        deduplicated,
+    }))
+}
+
+/// POST /api/posts/{slug}/featured-image - Set a post's featured image
+///
+/// Accepts a multipart form with either `media_id` (an existing upload) or `file` (a new
+/// upload with optional `alt_text` and `caption`), plus optional `focal_x`/`focal_y`
+/// percentages that keep the subject inside the card and OG crops. Coordinates left out
+/// come from the media file's own focal point, or the image center.
+pub async fn set_featured_image_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    mut multipart: Multipart,
+) -> Result<Json<FeaturedImageResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Setting featured image for post: {}", slug);
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    let mut media_id: Option<String> = None;
+    let mut uploaded: Option<MediaFile> = None;
+    let mut alt_text: Option<String> = None;
+    let mut caption: Option<String> = None;
+    let mut focal_x: Option<f64> = None;
+    let mut focal_y: Option<f64> = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        error!("Failed to read multipart field: {}", e);
+        multipart_error(e.status())
+    })? {
+        match field.name() {
+            Some("file") => {
+                // Upload right away so the stream is consumed before the next field
+                let (media_file, _) = state
+                    .media
+                    .upload_file(field, alt_text.take(), caption.take(), false)
+                    .await
+                    .map_err(|e| {
+                        error!("Media upload error: {}", e);
+                        upload_error(&e)
+                    })?;
+                uploaded = Some(media_file);
+            }
+            Some("media_id") => media_id = field.text().await.ok(),
+            Some("alt_text") => alt_text = field.text().await.ok(),
+            Some("caption") => caption = field.text().await.ok(),
+            Some("focal_x") => focal_x = Some(parse_focal_coordinate(field.text().await.ok())?),
+            Some("focal_y") => focal_y = Some(parse_focal_coordinate(field.text().await.ok())?),
+            _ => {
+                // Skip unknown fields
+                let _ = field.bytes().await;
+            }
+        }
+    }
+
+    let media_file = match (uploaded, media_id) {
+        (Some(media_file), _) => media_file,
+        (None, Some(media_id)) => {
+            let id = Uuid::parse_str(media_id.trim()).map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::bad_request("Invalid media ID format")),
+                )
+            })?;
+            state
+                .media
+                .get_media_file(id)
+                .await
+                .map_err(|e| {
+                    error!("Database error getting media file: {}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse::internal_error("Failed to get media file")),
+                    )
+                })?
+                .ok_or_else(|| {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(ErrorResponse::not_found("Media file not found")),
+                    )
+                })?
+        }
+        (None, None) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(
+                    "Either media_id or file is required",
+                )),
+            ));
+        }
+    };
+
+    if !media_file.mime_type.starts_with("image/") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(format!(
+                "Media '{}' is not an image",
+                media_file.filename
+            ))),
+        ));
+    }
+
+    let default_point = media_file.focal_point.unwrap_or_default();
+    let focal_point = FocalPoint {
+        x: focal_x.unwrap_or(default_point.x),
+        y: focal_y.unwrap_or(default_point.y),
+    };
+    let featured_image = state
+        .media
+        .set_featured_image(post.id, &media_file, focal_point)
+        .await
+        .map_err(|e| {
+            error!("Failed to set featured image: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(format!(
+                    "Failed to set featured image: {}",
+                    e
+                ))),
+            )
+        })?;
+
+    // The card and OG crops are new files, or replace ones requested too early
+    state.cache.forget_missing_media().await;
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    Ok(Json(FeaturedImageResponse {
+        success: true,
+        message: format!("Featured image set for post '{}'", slug),
+        featured_image,
+    }))
+}
+
+fn parse_focal_coordinate(value: Option<String>) -> Result<f64, (StatusCode, Json<ErrorResponse>)> {
+    value
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| (0.0..=100.0).contains(value))
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(
+                    "Focal point coordinates must be percentages between 0 and 100",
+                )),
+            )
+        })
+}
+
+/// PUT /api/posts/{slug}/audio - Attach an uploaded audio file as the post's podcast enclosure
+pub async fn attach_audio_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<AttachAudioRequest>,
+) -> Result<Json<AudioEnclosureResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Attaching audio {} to post: {}",
+        request.media_id, slug
+    );
+
+    let post = find_post_for_api(&state, &slug).await?;
+
+    let media_file = state
+        .media
+        .get_media_file(request.media_id)
+        .await
+        .map_err(|e| {
+            error!("Database error getting media file: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to get media file")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found("Media file not found")),
+            )
+        })?;
+
+    let mime_type = request
+        .mime_type
+        .map(|mime_type| mime_type.trim().to_string())
+        .filter(|mime_type| !mime_type.is_empty())
+        .unwrap_or_else(|| media_file.mime_type.clone());
+    if !mime_type.starts_with("audio/") {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::bad_request(
-                "No articles provided for import",
-            )),
+            Json(ErrorResponse::bad_request(format!(
+                "Media '{}' is not an audio file",
+                media_file.filename
+            ))),
         ));
     }
 
-    if request.articles.len() > 50 {
+    let duration_seconds = request.duration_seconds.or(media_file.duration_seconds);
+    if duration_seconds.is_some_and(|duration| !duration.is_finite() || duration < 0.0) {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse::bad_request(
-                "Too many articles (max 50 per batch)",
+                "Duration must be a non-negative number of seconds",
             )),
         ));
     }
 
-    let batch_response = state.llm_import.process_batch_import(request).await;
+    let db_error = |e: anyhow::Error| {
+        error!("Failed to attach audio: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to attach audio")),
+        )
+    };
+    state
+        .database
+        .set_post_audio(post.id, media_file.id, &mime_type, duration_seconds)
+        .await
+        .map_err(db_error)?;
+    let audio = state
+        .database
+        .get_post_audio(post.id)
+        .await
+        .map_err(db_error)?;
+
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
 
-    Ok(Json(batch_response))
+    Ok(Json(AudioEnclosureResponse {
+        success: true,
+        message: format!("Audio attached to post '{}'", slug),
+        audio,
+    }))
 }
 
-/// POST /api/posts/{slug}/save - Save a processed LLM article to database
-pub async fn save_llm_article_api(
+/// DELETE /api/posts/{slug}/audio - Detach the post's podcast enclosure
+pub async fn remove_audio_api(
     Path(slug): Path<String>,
     State(state): State<ApiState>,
-    Json(save_request): Json<SaveLLMArticleRequest>,
-) -> Result<Json<PostResponse>, (StatusCode, Json<ErrorResponse>)> {
-    debug!("API: Saving LLM article with slug: {}", slug);
+) -> Result<Json<AudioEnclosureResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Removing audio from post: {}", slug);
 
-    // Check if article already exists
-    if state
+    let post = find_post_for_api(&state, &slug).await?;
+
+    let removed = state
         .database
-        .get_post_by_slug(&slug)
+        .remove_post_audio(post.id)
         .await
         .map_err(|e| {
-            error!("Database error checking slug {}: {}", slug, e);
+            error!("Failed to remove audio: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Database error")),
+                Json(ErrorResponse::internal_error("Failed to remove audio")),
             )
-        })?
-        .is_some()
-    {
+        })?;
+    if !removed {
         return Err((
-            StatusCode::CONFLICT,
-            Json(ErrorResponse::bad_request(format!(
-                "Article with slug '{}' already exists",
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' has no audio attached",
                 slug
             ))),
         ));
     }
 
-    let create_post = CreatePost {
-        slug: slug.clone(),
-        title: save_request.title,
-        content: save_request.content,
-        html_content: save_request.html_content,
-        excerpt: save_request.excerpt,
-        category: save_request.category,
-        tags: save_request.tags,
-        published: save_request.published,
-        featured: save_request.featured,
-        author: save_request.author,
-        dropbox_path: save_request.dropbox_path,
-    };
-
-    let post = state.database.create_post(create_post).await.map_err(|e| {
-        error!("Database error creating post: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::internal_error("Failed to save article")),
-        )
-    })?;
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
 
-    let response = PostResponse::from(post);
-    Ok(Json(response))
+    Ok(Json(AudioEnclosureResponse {
+        success: true,
+        message: format!("Audio removed from post '{}'", slug),
+        audio: None,
+    }))
 }
 
-/// Request for saving LLM article
-#[derive(Debug, Deserialize)]
-pub struct SaveLLMArticleRequest {
-    pub title: String,
-    pub content: String,
-    pub html_content: String,
-    pub excerpt: Option<String>,
-    pub category: Option<String>,
-    pub tags: Vec<String>,
-    pub published: bool,
-    pub featured: bool,
-    pub author: Option<String>,
-    pub dropbox_path: String,
-}
+/// GET /api/posts/{slug}/attachments - List the downloads attached to a post
+pub async fn list_attachments_api(
+    Path(slug): Path<String>,
+    State(state): State<ApiState>,
+) -> Result<Json<PostAttachmentListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Listing attachments of post: {}", slug);
 
-// Media API endpoints
+    let post = find_post_for_api(&state, &slug).await?;
+    let attachments = state
+        .database
+        .list_post_attachments(post.id)
+        .await
+        .map_err(|e| {
+            error!("Failed to list attachments: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to list attachments")),
+            )
+        })?;
 
-/// POST /api/media/upload - Upload media file
-pub async fn upload_media_api(
+    Ok(Json(PostAttachmentListResponse {
+        total: attachments.len(),
+        attachments,
+    }))
+}
+
+/// DELETE /api/posts/{slug}/attachments/{id} - Detach a download from a post
+///
+/// The media file stays in the library.
+pub async fn remove_attachment_api(
+    Path((slug, id)): Path<(String, Uuid)>,
     State(state): State<ApiState>,
-    mut multipart: Multipart,
-) -> Result<Json<MediaUploadResponse>, (StatusCode, Json<ErrorResponse>)> {
-    debug!("API: Uploading media file");
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Removing attachment {} from post: {}", id, slug);
 
-    let mut alt_text: Option<String> = None;
-    let mut caption: Option<String> = None;
-    let mut file_field: Option<Field> = None;
+    let post = find_post_for_api(&state, &slug).await?;
+    let removed = state
+        .database
+        .remove_post_attachment(post.id, id)
+        .await
+        .map_err(|e| {
+            error!("Failed to remove attachment: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to remove attachment")),
+            )
+        })?;
+    if !removed {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found("Attachment not found")),
+        ));
+    }
 
-    // Process multipart form data
-    while let Some(field) = multipart.next_field().await.map_err(|e| {
-        error!("Failed to read multipart field: {}", e);
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::bad_request("Invalid multipart data")),
-        )
-    })? {
-        match field.name() {
-            Some("file") => {
-                file_field = Some(field);
-            }
-            Some("alt_text") => {
-                alt_text = field.text().await.ok();
-            }
-            Some("caption") => {
-                caption = field.text().await.ok();
-            }
-            _ => {
-                // Skip unknown fields
-                let _ = field.bytes().await;
-            }
-        }
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
     }
 
-    let file_field = file_field.ok_or_else(|| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::bad_request("No file provided")),
-        )
-    })?;
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    // Upload file using media service
-    let media_file = state
-        .media
-        .upload_file(file_field, alt_text, caption)
+/// Swap in the versions readers see; API key holders get the latest drafts
+async fn show_published_versions(
+    state: &ApiState,
+    headers: &HeaderMap,
+    posts: &mut [Post],
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if has_api_key(headers, state.api_key.as_deref()) {
+        return Ok(());
+    }
+    state
+        .database
+        .show_published_versions(posts)
         .await
         .map_err(|e| {
-            error!("Media upload error: {}", e);
+            error!("Database error loading published versions: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error(format!(
-                    "Upload failed: {}",
-                    e
-                ))),
+                Json(ErrorResponse::internal_error("Database error")),
             )
-        })?;
-
-    let response = MediaUploadResponse {
-        success: true,
-        message: format!("File '{}' uploaded successfully", media_file.filename),
-        media: Some(media_file),
-        errors: None,
-    };
+        })
+}
 
-    Ok(Json(response))
+async fn find_post_for_api(
+    state: &ApiState,
+    slug: &str,
+) -> Result<Post, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .database
+        .get_post_by_slug(slug)
+        .await
+        .map_err(|e| {
+            error!("Database error getting post: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Database error")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })
 }
 
 /// GET /api/media - List media files
+#[utoipa::path(
+    get,
+    path = "/api/media",
+    tag = "media",
+    params(MediaQuery),
+    responses(
+        (status = 200, description = "Page of media files", body = MediaListResponse)
+    )
+)]
 pub async fn list_media_api(
     Query(query): Query<MediaQuery>,
     State(state): State<ApiState>,
@@ -1199,6 +4015,7 @@ pub async fn list_media_api(
         folder: query.folder.clone(),
         mime_type: query.mime_type.clone(),
         search: query.search.clone(),
+        collection: query.collection.clone(),
         limit: Some(per_page as i64),
         offset: Some(offset as i64),
     };
@@ -1235,8 +4052,19 @@ pub async fn list_media_api(
 
     let total_pages = total_count.div_ceil(per_page);
 
+    let media_ids: Vec<Uuid> = media_files.iter().map(|media| media.id).collect();
+    let usage_counts = state
+        .database
+        .get_media_usage_counts(&media_ids)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to count media usage: {}", e);
+            HashMap::new()
+        });
+
     let response = MediaListResponse {
         media: media_files,
+        usage_counts,
         total: total_count,
         page,
         per_page,
@@ -1246,9 +4074,36 @@ pub async fn list_media_api(
     Ok(Json(response))
 }
 
+/// Query parameters for media deletion
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DeleteMediaQuery {
+    /// Delete even when posts still use the file
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// DELETE /api/media/{id} - Delete media file
+///
+/// Files still used by posts are kept (409) unless `?force=true` is given.
+#[utoipa::path(
+    delete,
+    path = "/api/media/{id}",
+    tag = "media",
+    params(
+        ("id" = String, Path, description = "Media file ID"),
+        DeleteMediaQuery
+    ),
+    responses(
+        (status = 200, description = "Media file deleted", body = MediaUploadResponse),
+        (status = 404, description = "Media file not found", body = ErrorResponse),
+        (status = 409, description = "Still used by posts", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
 pub async fn delete_media_api(
     Path(id): Path<String>,
+    Query(query): Query<DeleteMediaQuery>,
     State(state): State<ApiState>,
 ) -> Result<Json<MediaUploadResponse>, (StatusCode, Json<ErrorResponse>)> {
     debug!("API: Deleting media file with ID: {}", id);
@@ -1260,6 +4115,34 @@ pub async fn delete_media_api(
         )
     })?;
 
+    if !query.force {
+        let slugs = state
+            .database
+            .get_post_slugs_using_media(media_id)
+            .await
+            .map_err(|e| {
+                error!("Database error checking media usage: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Failed to check media usage")),
+                )
+            })?;
+        if !slugs.is_empty() {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(ErrorResponse::new(
+                    "conflict",
+                    format!(
+                        "Media file is used by {} post(s): {}",
+                        slugs.len(),
+                        slugs.join(", ")
+                    ),
+                    409,
+                )),
+            ));
+        }
+    }
+
     let deleted = state.media.delete_media_file(media_id).await.map_err(|e| {
         error!("Media deletion error: {}", e);
         (
@@ -1280,11 +4163,113 @@ pub async fn delete_media_api(
         message: "Media file deleted successfully".to_string(),
         media: None,
         errors: None,
+        attachment: None,
     };
 
     Ok(Json(response))
 }
 
+/// PATCH /api/media/{id} - Update media metadata
+///
+/// Accepts `alt_text`, `caption`, `filename` (display name), `collection`,
+/// `alt_text_suggestion` (`accept`/`reject`) and `focal_point` (`{"x": .., "y": ..}` in
+/// percent). Empty strings clear optional fields. Featured images cropped from the
+/// file are regenerated around a new focal point.
+#[utoipa::path(
+    patch,
+    path = "/api/media/{id}",
+    tag = "media",
+    params(("id" = String, Path, description = "Media file ID")),
+    request_body = UpdateMediaRequest,
+    responses(
+        (status = 200, description = "Media file updated", body = MediaResponse),
+        (status = 400, description = "Invalid metadata", body = ErrorResponse),
+        (status = 404, description = "Media file not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn update_media_api(
+    Path(id): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<UpdateMediaRequest>,
+) -> Result<Json<MediaResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Updating media file with ID: {}", id);
+
+    let media_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request("Invalid media ID format")),
+        )
+    })?;
+
+    let mut media_file = state
+        .media
+        .get_media_file(media_id)
+        .await
+        .map_err(|e| {
+            error!("Database error getting media file: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to get media file")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found("Media file not found")),
+            )
+        })?;
+
+    let focal_point = media_file.focal_point;
+    media_file.apply_update(request).map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(message)),
+        )
+    })?;
+
+    state
+        .media
+        .update_media_metadata(&media_file)
+        .await
+        .map_err(|e| {
+            error!("Media update error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to update media file")),
+            )
+        })?;
+
+    if media_file.focal_point != focal_point {
+        match state.media.recrop_featured_images(&media_file).await {
+            Ok(count) if count > 0 => {
+                info!("Re-cropped {} featured images of media {}", count, media_id)
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to re-crop featured images of {}: {}", media_id, e),
+        }
+    }
+
+    // Pages showing this file render its alt text, caption and featured crops
+    match state.database.get_post_slugs_using_media(media_id).await {
+        Ok(slugs) => {
+            for slug in slugs {
+                if let Err(e) = state.cache.invalidate_post(&slug).await {
+                    warn!("Failed to invalidate cache for {}: {}", slug, e);
+                }
+            }
+        }
+        Err(e) => warn!("Failed to find posts using media {}: {}", media_id, e),
+    }
+
+    Ok(Json(MediaResponse {
+        success: true,
+        message: "Media file updated successfully".to_string(),
+        media: media_file,
+    }))
+}
+
 /// GET /media/{path} - Serve media file
 pub async fn serve_media_file(
     Path(path): Path<String>,
@@ -1292,18 +4277,52 @@ pub async fn serve_media_file(
 ) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
     debug!("API: Serving media file: {}", path);
 
-    let (data, mime_type) = state.media.serve_media_file(&path).await.map_err(|e| {
-        error!("Media serving error: {}", e);
+    let not_found = || {
         (
             StatusCode::NOT_FOUND,
             Json(ErrorResponse::not_found("Media file not found")),
         )
-    })?;
+    };
+    if state.cache.is_missing_media(&path).await {
+        return Err(not_found());
+    }
+
+    let (data, mime_type) = match state.media.serve_media_file(&path).await {
+        Ok(served) => served,
+        Err(e) => {
+            if let Some(invalid) = InvalidMediaPath::find(&e) {
+                warn!("Refused media request: {}", invalid);
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::bad_request("Invalid media path")),
+                ));
+            }
+            error!("Media serving error: {:#}", e);
+            if CircuitOpen::find(&e).is_some() {
+                return Err((
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(ErrorResponse::new(
+                        "media_unavailable",
+                        "Media storage is temporarily unavailable",
+                        503,
+                    )),
+                ));
+            }
+            state.cache.remember_missing_media(&path).await;
+            return Err(not_found());
+        }
+    };
 
-    let response = Response::builder()
+    let mut response = Response::builder()
         .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, mime_type)
         .header(header::CACHE_CONTROL, "public, max-age=31536000") // Cache for 1 year
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    // Media share the site's origin; an SVG must not be able to run scripts there
+    if mime_type == "image/svg+xml" {
+        response = response.header(header::CONTENT_SECURITY_POLICY, SVG_CONTENT_SECURITY_POLICY);
+    }
+    let response = response
+        .header(header::CONTENT_TYPE, mime_type)
         .body(Body::from(data))
         .map_err(|e| {
             error!("Failed to build response: {}", e);
@@ -0,0 +1,45 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Json, Response},
+};
+use tracing::{debug, error};
+
+use crate::models::response::ErrorResponse;
+use crate::services::AssetService;
+
+/// Cache policy for fingerprinted assets: the URL changes whenever the content does
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// GET /assets/{path} - Serve a minified, fingerprinted asset
+pub async fn serve_asset(
+    Path(path): Path<String>,
+    State(assets): State<AssetService>,
+) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Serving asset: {}", path);
+
+    let asset = assets.get(&path).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Asset '{}' not found",
+                path
+            ))),
+        )
+    })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, asset.content_type)
+        .header(header::CACHE_CONTROL, IMMUTABLE_CACHE_CONTROL)
+        .header(header::ETAG, format!("\"{}\"", asset.hash))
+        .body(Body::from(asset.content.clone()))
+        .map_err(|e| {
+            error!("Failed to build asset response: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to serve asset")),
+            )
+        })
+}
@@ -0,0 +1,35 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{debug, error};
+
+use crate::models::{response::ErrorResponse, ConfigReloadResponse};
+use crate::services::ConfigReloader;
+
+/// POST /api/admin/reload - Reload `.env` and apply runtime settings without a restart
+pub async fn reload_config(
+    State(reloader): State<ConfigReloader>,
+) -> Result<Json<ConfigReloadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Reloading configuration");
+
+    match tokio::task::spawn_blocking(move || reloader.reload()).await {
+        Ok(Ok(response)) => Ok(Json(response)),
+        Ok(Err(e)) => {
+            error!("Failed to reload configuration: {:#}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "Configuration not reloaded: {:#}",
+                    e
+                ))),
+            ))
+        }
+        Err(e) => {
+            error!("Configuration reload task failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to reload configuration",
+                )),
+            ))
+        }
+    }
+}
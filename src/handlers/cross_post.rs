@@ -0,0 +1,97 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{debug, error};
+
+use crate::models::{response::ErrorResponse, CrossPostListResponse, Post};
+use crate::services::{CrossPostService, DatabaseService};
+
+/// App state for cross-posting handlers
+#[derive(Clone)]
+pub struct CrossPostState {
+    pub cross_posts: CrossPostService,
+    pub database: DatabaseService,
+}
+
+impl CrossPostState {
+    async fn find_post(&self, slug: &str) -> Result<Post, (StatusCode, Json<ErrorResponse>)> {
+        match self.database.get_post_by_slug(slug).await {
+            Ok(Some(post)) => Ok(post),
+            Ok(None) => Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )),
+            Err(e) => {
+                error!("Database error getting post: {}", e);
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Database error")),
+                ))
+            }
+        }
+    }
+}
+
+/// GET /api/posts/{slug}/cross-posts - Where the post is cross-posted and how it went
+pub async fn list_cross_posts(
+    Path(slug): Path<String>,
+    State(state): State<CrossPostState>,
+) -> Result<Json<CrossPostListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Listing cross-posts of {}", slug);
+
+    let post = state.find_post(&slug).await?;
+    let data = state
+        .database
+        .list_cross_posts(post.id)
+        .await
+        .map_err(|e| {
+            error!("Failed to list cross-posts: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to list cross-posts")),
+            )
+        })?;
+
+    Ok(Json(CrossPostListResponse {
+        success: true,
+        slug: post.slug,
+        data,
+    }))
+}
+
+/// POST /api/posts/{slug}/cross-posts - Publish or update the copies now
+pub async fn run_cross_post(
+    Path(slug): Path<String>,
+    State(state): State<CrossPostState>,
+) -> Result<Json<CrossPostListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Cross-posting {}", slug);
+
+    let post = state.find_post(&slug).await?;
+    if !post.published {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Only published posts can be cross-posted",
+            )),
+        ));
+    }
+
+    let data = state.cross_posts.cross_post(&post).await.map_err(|e| {
+        error!("Failed to cross-post {}: {}", slug, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to cross-post")),
+        )
+    })?;
+
+    Ok(Json(CrossPostListResponse {
+        success: true,
+        slug: post.slug,
+        data,
+    }))
+}
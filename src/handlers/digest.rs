@@ -0,0 +1,74 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{debug, error};
+
+use crate::models::{response::ErrorResponse, DigestQuery, JobEnqueuedResponse, MonthlyDigest};
+use crate::services::digest::DIGEST_JOB;
+use crate::services::{DigestService, JobQueue};
+
+/// App state for monthly digest handlers
+#[derive(Clone)]
+pub struct DigestState {
+    pub digest: DigestService,
+    pub jobs: JobQueue,
+}
+
+impl DigestState {
+    /// Register the handler of queued digest deliveries
+    pub fn register_jobs(&self) {
+        let digest = self.digest.clone();
+        self.jobs.register(DIGEST_JOB, move |payload| {
+            let digest = digest.clone();
+            async move {
+                let query: DigestQuery = serde_json::from_value(payload)?;
+                digest.send(query.month.as_deref()).await
+            }
+        });
+    }
+}
+
+/// GET /api/admin/digest - Preview the digest of a month without sending it
+pub async fn preview_digest(
+    Query(query): Query<DigestQuery>,
+    State(state): State<DigestState>,
+) -> Result<Json<MonthlyDigest>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Previewing digest for {:?}", query.month);
+
+    let digest = state
+        .digest
+        .compile(query.month.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to compile digest: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "Failed to compile digest: {}",
+                    e
+                ))),
+            )
+        })?;
+
+    Ok(Json(digest))
+}
+
+/// POST /api/admin/digest/send - Queue delivery of the digest of a month
+pub async fn send_digest(
+    Query(query): Query<DigestQuery>,
+    State(state): State<DigestState>,
+) -> Result<(StatusCode, Json<JobEnqueuedResponse>), (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Queueing digest for {:?}", query.month);
+
+    let job = state.jobs.enqueue(DIGEST_JOB, &query).await.map_err(|e| {
+        error!("Failed to queue digest: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to queue digest")),
+        )
+    })?;
+
+    Ok((StatusCode::ACCEPTED, Json(JobEnqueuedResponse::from(&job))))
+}
@@ -0,0 +1,61 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Json, Response},
+};
+use tracing::error;
+
+use crate::models::response::ErrorResponse;
+use crate::services::FeedService;
+
+type FeedResult = Result<Response<Body>, (StatusCode, Json<ErrorResponse>)>;
+
+/// GET /tag/{tag}/feed.xml - RSS feed of the posts with one tag
+pub async fn serve_tag_feed(
+    Path(tag): Path<String>,
+    State(feeds): State<FeedService>,
+) -> FeedResult {
+    let feed = feeds.tag_feed(&tag).await.map_err(|e| {
+        error!("Failed to generate feed for tag {}: {}", tag, e);
+        internal_error("Failed to generate feed")
+    })?;
+    feed_response(feed, &format!("No posts tagged '{}'", tag))
+}
+
+/// GET /series/{slug}/feed.xml - RSS feed of the posts in one series
+pub async fn serve_series_feed(
+    Path(series): Path<String>,
+    State(feeds): State<FeedService>,
+) -> FeedResult {
+    let feed = feeds.series_feed(&series).await.map_err(|e| {
+        error!("Failed to generate feed for series {}: {}", series, e);
+        internal_error("Failed to generate feed")
+    })?;
+    feed_response(feed, &format!("Series '{}' not found", series))
+}
+
+fn feed_response(feed: Option<String>, not_found: &str) -> FeedResult {
+    let feed = feed.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(not_found)),
+        )
+    })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")
+        .body(Body::from(feed))
+        .map_err(|e| {
+            error!("Failed to build feed response: {}", e);
+            internal_error("Failed to serve feed")
+        })
+}
+
+fn internal_error(message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse::internal_error(message)),
+    )
+}
@@ -0,0 +1,72 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{debug, error};
+
+use crate::models::{response::ErrorResponse, BrokenLinkReportResponse, JobEnqueuedResponse};
+use crate::services::{JobQueue, LinkCheckService};
+
+/// Job kind of queued link check runs
+pub const LINK_CHECK_JOB: &str = "link_check";
+
+/// App state for link check handlers
+#[derive(Clone)]
+pub struct LinkCheckState {
+    pub link_checker: LinkCheckService,
+    pub jobs: JobQueue,
+}
+
+impl LinkCheckState {
+    /// Register the handler of queued link check runs
+    pub fn register_jobs(&self) {
+        let link_checker = self.link_checker.clone();
+        self.jobs.register(LINK_CHECK_JOB, move |_payload| {
+            let link_checker = link_checker.clone();
+            async move { link_checker.check_all_posts().await }
+        });
+    }
+}
+
+/// GET /api/admin/links/broken - List broken links per post from the last check
+pub async fn broken_links_report(
+    State(state): State<LinkCheckState>,
+) -> Result<Json<BrokenLinkReportResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Getting broken link report");
+
+    let reports = state.link_checker.get_report().await.map_err(|e| {
+        error!("Failed to build broken link report: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to get broken link report",
+            )),
+        )
+    })?;
+
+    let total_broken = reports.iter().map(|r| r.broken_links.len()).sum();
+
+    Ok(Json(BrokenLinkReportResponse {
+        success: true,
+        data: reports,
+        total_broken,
+    }))
+}
+
+/// POST /api/admin/links/check - Queue a check of all links of published posts
+pub async fn run_link_check(
+    State(state): State<LinkCheckState>,
+) -> Result<(StatusCode, Json<JobEnqueuedResponse>), (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Queueing link check");
+
+    let job = state
+        .jobs
+        .enqueue(LINK_CHECK_JOB, &serde_json::Value::Null)
+        .await
+        .map_err(|e| {
+            error!("Failed to queue link check: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to queue link check")),
+            )
+        })?;
+
+    Ok((StatusCode::ACCEPTED, Json(JobEnqueuedResponse::from(&job))))
+}
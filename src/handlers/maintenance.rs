@@ -0,0 +1,21 @@
+use axum::{extract::State, response::Json};
+use tracing::debug;
+
+use crate::models::{MaintenanceStatus, UpdateMaintenance};
+use crate::services::MaintenanceService;
+
+/// GET /api/admin/maintenance - Current maintenance mode setting
+pub async fn get_maintenance(
+    State(maintenance): State<MaintenanceService>,
+) -> Json<MaintenanceStatus> {
+    Json(maintenance.status())
+}
+
+/// PUT /api/admin/maintenance - Turn maintenance mode on or off
+pub async fn update_maintenance(
+    State(maintenance): State<MaintenanceService>,
+    Json(request): Json<UpdateMaintenance>,
+) -> Json<MaintenanceStatus> {
+    debug!("API: Setting maintenance mode: {}", request.enabled);
+    Json(maintenance.update(request))
+}
@@ -1,10 +1,26 @@
 // Handlers module for HTTP request processing
 
 pub mod admin;
+pub mod announcement;
 pub mod api;
+pub mod assets;
+pub mod config_reload;
+pub mod cross_post;
+pub mod digest;
+pub mod feed;
+pub mod link_check;
+pub mod maintenance;
+pub mod notifications;
+pub mod og_image;
+pub mod openapi;
+pub mod pdf;
 pub mod performance;
+pub mod podcast;
 pub mod posts;
+pub mod site_files;
+pub mod storage_migration;
 pub mod theme;
+pub mod totp;
 pub mod version;
 
 // Re-export specific items as needed
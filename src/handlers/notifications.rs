@@ -0,0 +1,33 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::debug;
+
+use crate::models::{response::ErrorResponse, NotificationTestResponse};
+use crate::services::NotificationService;
+
+/// App state for notification handlers
+#[derive(Clone)]
+pub struct NotificationState {
+    pub notifications: NotificationService,
+}
+
+/// POST /api/admin/notifications/test - Send a test message to every configured webhook
+pub async fn send_test_notification(
+    State(state): State<NotificationState>,
+) -> Result<Json<NotificationTestResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Sending test notification");
+
+    let results = state.notifications.send_test().await;
+    if results.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "No notification webhook is configured",
+            )),
+        ));
+    }
+
+    Ok(Json(NotificationTestResponse {
+        success: results.iter().all(|result| result.delivered),
+        results,
+    }))
+}
@@ -0,0 +1,57 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Json, Response},
+};
+use tracing::{debug, error};
+
+use crate::models::response::ErrorResponse;
+use crate::services::OgImageService;
+
+/// Share images are keyed by content, so a short cache is enough to absorb crawler bursts
+const OG_IMAGE_CACHE_CONTROL: &str = "public, max-age=3600";
+
+/// GET /og/{slug}.png - Serve the generated social share image of a post
+pub async fn serve_og_image(
+    Path(file): Path<String>,
+    State(og_images): State<OgImageService>,
+) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "OG image '{}' not found",
+                file
+            ))),
+        )
+    };
+
+    let slug = file.strip_suffix(".png").ok_or_else(not_found)?;
+    debug!("Serving OG image for post: {}", slug);
+
+    let png = og_images
+        .get_post_image(slug)
+        .await
+        .map_err(|e| {
+            error!("Failed to generate OG image for {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to generate OG image")),
+            )
+        })?
+        .ok_or_else(not_found)?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "image/png")
+        .header(header::CACHE_CONTROL, OG_IMAGE_CACHE_CONTROL)
+        .body(Body::from(png))
+        .map_err(|e| {
+            error!("Failed to build OG image response: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to serve OG image")),
+            )
+        })
+}
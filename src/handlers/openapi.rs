@@ -0,0 +1,182 @@
+use axum::response::{Html, Json};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::handlers::{api, theme, version};
+use crate::models::{
+    response::{
+        ErrorResponse, OutlineHeading, PostListResponse, PostOutlineResponse, PostResponse,
+        PostSummary,
+    },
+    AltTextSuggestionAction, BatchImportRequest, CategoryStyle, CreateThemeRequest, DiffChangeKind,
+    DiffStats, ExpiryAction, FocalPoint, FooterStyle, HeaderStyle, ImportError, ImportJob,
+    ImportJobCreatedResponse, ImportJobStatus, LLMArticleImportRequest, LLMArticleImportResponse,
+    LLMSuggestedMetadata, MediaFile, MediaListResponse, MediaPasteRequest, MediaPasteResponse,
+    MediaResponse, MediaUploadResponse, ParagraphDiff, PostAttachment, PostVersion, PostVisibility,
+    PublishVersionResponse, QualityCheckResults, QualitySeverity, QualityWarning,
+    RestoreVersionRequest, RestoreVersionResponse, ThemeLayout, ThemeListResponse, ThemeResponse,
+    ThemeSettings, UpdateMediaRequest, UpdatePostMetadata, UpdateThemeRequest, VersionDiff,
+    VersionDiffResponse, VersionHistory, VersionHistoryResponse, VersionResponse, VersionSummary,
+    WordDiffSegment,
+};
+
+/// OpenAPI 3 description of the public and admin JSON API
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Tobelog API",
+        description = "Posts, media, search, themes, versions and imports of a Tobelog blog. \
+                       Requests that change data need the API key."
+    ),
+    paths(
+        api::list_posts_api,
+        api::get_post_api,
+        api::get_post_source_api,
+        api::get_post_outline_api,
+        api::create_post_api,
+        api::update_post_api,
+        api::update_post_metadata_api,
+        api::delete_post_api,
+        api::search_posts_api,
+        api::upload_media_api,
+        api::paste_media_api,
+        api::list_media_api,
+        api::update_media_api,
+        api::delete_media_api,
+        api::import_llm_article_api,
+        api::batch_import_api,
+        api::import_job_api,
+        api::import_markdown_api,
+        theme::list_themes,
+        theme::get_active_theme,
+        theme::get_theme,
+        theme::create_theme,
+        theme::update_theme,
+        theme::delete_theme,
+        theme::activate_theme,
+        version::get_version_history,
+        version::get_post_version,
+        version::compare_versions,
+        version::restore_version,
+        version::publish_version,
+    ),
+    components(schemas(
+        ErrorResponse,
+        PostResponse,
+        PostSummary,
+        PostListResponse,
+        PostOutlineResponse,
+        OutlineHeading,
+        ExpiryAction,
+        PostVisibility,
+        api::CreatePostRequest,
+        api::UpdatePostRequest,
+        UpdatePostMetadata,
+        api::PostOperationResponse,
+        api::SyncResponse,
+        api::ImportMarkdownRequest,
+        api::MarkdownFileImport,
+        api::PostMetadata,
+        api::MediaUploadForm,
+        MediaFile,
+        MediaUploadResponse,
+        MediaPasteRequest,
+        MediaPasteResponse,
+        MediaResponse,
+        MediaListResponse,
+        UpdateMediaRequest,
+        AltTextSuggestionAction,
+        FocalPoint,
+        PostAttachment,
+        LLMArticleImportRequest,
+        LLMArticleImportResponse,
+        LLMSuggestedMetadata,
+        QualityCheckResults,
+        QualityWarning,
+        QualitySeverity,
+        BatchImportRequest,
+        ImportError,
+        ImportJob,
+        ImportJobStatus,
+        ImportJobCreatedResponse,
+        ThemeSettings,
+        ThemeLayout,
+        HeaderStyle,
+        FooterStyle,
+        CategoryStyle,
+        CreateThemeRequest,
+        UpdateThemeRequest,
+        ThemeResponse,
+        ThemeListResponse,
+        PostVersion,
+        VersionSummary,
+        VersionHistory,
+        VersionDiff,
+        DiffStats,
+        ParagraphDiff,
+        WordDiffSegment,
+        DiffChangeKind,
+        RestoreVersionRequest,
+        VersionHistoryResponse,
+        VersionResponse,
+        VersionDiffResponse,
+        RestoreVersionResponse,
+        PublishVersionResponse,
+    )),
+    modifiers(&ApiKeySecurity),
+    tags(
+        (name = "posts", description = "Blog posts"),
+        (name = "search", description = "Full-text search"),
+        (name = "media", description = "Media library"),
+        (name = "import", description = "Markdown and LLM article imports"),
+        (name = "themes", description = "Site themes"),
+        (name = "versions", description = "Post version history"),
+    )
+)]
+pub struct ApiDoc;
+
+/// The API key is accepted as `X-API-Key` or as a bearer token
+struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// GET /api/openapi.json - OpenAPI specification of the API
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// GET /api/docs - Swagger UI for the OpenAPI specification
+pub async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_PAGE)
+}
+
+const SWAGGER_UI_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <meta name="robots" content="noindex">
+    <title>Tobelog API</title>
+    <link rel="stylesheet" href="https://cdnjs.cloudflare.com/ajax/libs/swagger-ui/5.17.14/swagger-ui.min.css">
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://cdnjs.cloudflare.com/ajax/libs/swagger-ui/5.17.14/swagger-ui-bundle.min.js"></script>
+    <script>
+        window.ui = SwaggerUIBundle({ url: '/api/openapi.json', dom_id: '#swagger-ui' });
+    </script>
+</body>
+</html>
+"#;
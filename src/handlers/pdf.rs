@@ -0,0 +1,69 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Json, Response},
+};
+use tracing::{debug, error};
+
+use crate::models::response::ErrorResponse;
+use crate::services::pdf::{self, PdfService};
+
+/// PDFs are keyed by content, so browsers may keep them for a while
+const PDF_CACHE_CONTROL: &str = "public, max-age=3600";
+
+/// GET /api/posts/{slug}/pdf - Download a post as PDF for offline reading
+pub async fn get_post_pdf_api(
+    Path(slug): Path<String>,
+    State(pdfs): State<PdfService>,
+) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Exporting post to PDF: {}", slug);
+
+    if !pdf::is_supported() {
+        return Err((
+            StatusCode::NOT_IMPLEMENTED,
+            Json(ErrorResponse::new(
+                "not_implemented",
+                "PDF export requires the `pdf` feature",
+                501,
+            )),
+        ));
+    }
+
+    let pdf = pdfs
+        .get_post_pdf(&slug)
+        .await
+        .map_err(|e| {
+            error!("Failed to export PDF for {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to export PDF")),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("inline; filename=\"{}.pdf\"", slug),
+        )
+        .header(header::CACHE_CONTROL, PDF_CACHE_CONTROL)
+        .body(Body::from(pdf))
+        .map_err(|e| {
+            error!("Failed to build PDF response: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to serve PDF")),
+            )
+        })
+}
@@ -1,14 +1,41 @@
-use axum::{extract::State, http::StatusCode, response::Json};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
 use serde_json::Value;
-use tracing::{debug, error};
+use std::path::PathBuf;
+use tracing::{debug, error, info, warn};
 
-use crate::models::response::ErrorResponse;
-use crate::services::CacheService;
+use crate::handlers::posts::request_api_key;
+use crate::models::{response::ErrorResponse, LoadTestReport};
+use crate::services::{CacheService, SlowLog};
 
 /// Performance monitoring handler state
 #[derive(Clone)]
 pub struct PerformanceState {
     pub cache: CacheService,
+    /// Report written by the `load_test` binary
+    pub load_test_report: PathBuf,
+    /// Queries and requests over the slow thresholds
+    pub slow_log: SlowLog,
+}
+
+impl PerformanceState {
+    /// Results of the last load test run, if one was recorded
+    async fn load_test_report(&self) -> Option<LoadTestReport> {
+        let contents = tokio::fs::read(&self.load_test_report).await.ok()?;
+        serde_json::from_slice(&contents)
+            .map_err(|e| {
+                warn!(
+                    "Ignoring invalid load test report {}: {}",
+                    self.load_test_report.display(),
+                    e
+                )
+            })
+            .ok()
+    }
 }
 
 /// GET /api/performance/metrics - Get current performance metrics
@@ -19,12 +46,19 @@ pub async fn get_performance_metrics(
 
     let metrics = state.cache.get_metrics().await;
     let cache_stats = state.cache.get_cache_stats().await;
+    let load_test = state.load_test_report().await;
+    let (slow_queries, slow_requests) = state.slow_log.counts();
 
     let response = serde_json::json!({
         "success": true,
         "data": {
             "performance": metrics,
             "cache": cache_stats,
+            "load_test": load_test,
+            "slow_log": {
+                "slow_queries": slow_queries,
+                "slow_requests": slow_requests
+            },
             "targets": {
                 "page_load_time_target": 2000.0, // 2 seconds
                 "cache_hit_rate_target": 80.0,   // 80%
@@ -41,6 +75,16 @@ pub async fn get_performance_metrics(
     Ok(Json(response))
 }
 
+/// GET /api/performance/slowlog - Recent queries and requests over the slow thresholds
+pub async fn get_slow_log(State(state): State<PerformanceState>) -> Json<Value> {
+    debug!("API: Getting slow log");
+
+    Json(serde_json::json!({
+        "success": true,
+        "data": state.slow_log.report()
+    }))
+}
+
 /// POST /api/performance/cache/clear - Clear all caches
 pub async fn clear_cache(
     State(state): State<PerformanceState>,
@@ -63,6 +107,107 @@ pub async fn clear_cache(
     Ok(Json(response))
 }
 
+/// State of the cache purge endpoint, which has its own token
+#[derive(Clone)]
+pub struct CachePurgeState {
+    pub cache: CacheService,
+    pub api_key: Option<String>,
+    /// Token that can purge the cache and nothing else, for external automation
+    pub purge_token: Option<String>,
+}
+
+/// Request body for cache purges
+#[derive(Debug, Default, Deserialize)]
+pub struct CachePurgeRequest {
+    #[serde(default)]
+    pub slugs: Vec<String>,
+    /// Purge every post whose slug starts with one of these
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+    /// Purge everything
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// POST /api/cache/purge - Purge cached posts for webhooks and automation
+///
+/// Needs `CACHE_PURGE_TOKEN` or the API key; without either configured the
+/// endpoint is disabled.
+pub async fn purge_cache(
+    State(state): State<CachePurgeState>,
+    headers: HeaderMap,
+    Json(request): Json<CachePurgeRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<ErrorResponse>)> {
+    if state.purge_token.is_none() && state.api_key.is_none() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden",
+                "Cache purge is disabled; set CACHE_PURGE_TOKEN to enable it",
+                403,
+            )),
+        ));
+    }
+
+    let token = request_api_key(&headers);
+    let authorized = token.is_some() && (token == state.purge_token || token == state.api_key);
+    if !authorized {
+        warn!("Unauthorized cache purge request");
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse::new(
+                "unauthorized",
+                "Valid cache purge token required",
+                401,
+            )),
+        ));
+    }
+
+    if !request.all && request.slugs.is_empty() && request.prefixes.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Specify slugs, prefixes or all=true",
+            )),
+        ));
+    }
+    if request.prefixes.iter().any(|prefix| prefix.is_empty()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "Prefixes must not be empty; use all=true to purge everything",
+            )),
+        ));
+    }
+
+    let result = if request.all {
+        state.cache.invalidate_all().await.map(|_| None)
+    } else {
+        state
+            .cache
+            .purge_posts(&request.slugs, &request.prefixes)
+            .await
+            .map(Some)
+    };
+    let purged = result.map_err(|e| {
+        error!("Failed to purge cache: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to purge cache")),
+        )
+    })?;
+    info!(
+        "Cache purged (all: {}, slugs: {:?}, prefixes: {:?})",
+        request.all, request.slugs, request.prefixes
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "all": request.all,
+        "purged_posts": purged
+    })))
+}
+
 /// GET /api/performance/health - Performance health check
 pub async fn performance_health_check(
     State(state): State<PerformanceState>,
@@ -120,12 +265,16 @@ pub async fn performance_health_check(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::services::CacheService;
+    use crate::services::{CacheService, SlowLogConfig};
 
     #[tokio::test]
     async fn test_performance_health_check_healthy() {
         let cache = CacheService::new();
-        let state = PerformanceState { cache };
+        let state = PerformanceState {
+            cache,
+            load_test_report: PathBuf::from("missing-load-test.json"),
+            slow_log: SlowLog::new(SlowLogConfig::default()),
+        };
 
         let result = performance_health_check(State(state)).await;
         assert!(result.is_ok());
@@ -134,7 +283,11 @@ mod tests {
     #[tokio::test]
     async fn test_clear_cache() {
         let cache = CacheService::new();
-        let state = PerformanceState { cache };
+        let state = PerformanceState {
+            cache,
+            load_test_report: PathBuf::from("missing-load-test.json"),
+            slow_log: SlowLog::new(SlowLogConfig::default()),
+        };
 
         let result = clear_cache(State(state)).await;
         assert!(result.is_ok());
@@ -143,9 +296,69 @@ mod tests {
     #[tokio::test]
     async fn test_get_performance_metrics() {
         let cache = CacheService::new();
-        let state = PerformanceState { cache };
+        let state = PerformanceState {
+            cache,
+            load_test_report: PathBuf::from("missing-load-test.json"),
+            slow_log: SlowLog::new(SlowLogConfig::default()),
+        };
 
         let result = get_performance_metrics(State(state)).await;
         assert!(result.is_ok());
     }
+
+    fn purge_state(purge_token: Option<&str>) -> CachePurgeState {
+        CachePurgeState {
+            cache: CacheService::new(),
+            api_key: Some("admin-key".to_string()),
+            purge_token: purge_token.map(str::to_string),
+        }
+    }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_purge_cache_accepts_scoped_token() {
+        let request = CachePurgeRequest {
+            slugs: vec!["hello".to_string()],
+            ..Default::default()
+        };
+
+        let result = purge_cache(
+            State(purge_state(Some("purge-token"))),
+            bearer("purge-token"),
+            Json(request),
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_purge_cache_rejects_wrong_token_and_empty_request() {
+        let request = CachePurgeRequest {
+            all: true,
+            ..Default::default()
+        };
+        let result = purge_cache(
+            State(purge_state(Some("purge-token"))),
+            bearer("other"),
+            Json(request),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+        let result = purge_cache(
+            State(purge_state(None)),
+            bearer("admin-key"),
+            Json(CachePurgeRequest::default()),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
 }
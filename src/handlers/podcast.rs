@@ -0,0 +1,36 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, StatusCode},
+    response::{Json, Response},
+};
+use tracing::error;
+
+use crate::models::response::ErrorResponse;
+use crate::services::PodcastService;
+
+/// GET /podcast.xml - iTunes-compatible RSS feed of posts with audio attached
+pub async fn serve_podcast_feed(
+    State(podcast): State<PodcastService>,
+) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
+    let internal_error = |message: &str| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(message)),
+        )
+    };
+
+    let feed = podcast.generate_feed().await.map_err(|e| {
+        error!("Failed to generate podcast feed: {}", e);
+        internal_error("Failed to generate podcast feed")
+    })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")
+        .body(Body::from(feed))
+        .map_err(|e| {
+            error!("Failed to build podcast feed response: {}", e);
+            internal_error("Failed to serve podcast feed")
+        })
+}
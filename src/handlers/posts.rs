@@ -1,17 +1,32 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::{Html, Json},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{Html, IntoResponse, Json, Redirect, Response},
+    Extension, Form,
 };
-use serde::Deserialize;
-use tracing::{debug, error};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, error, warn};
 
-use crate::models::response::ErrorResponse;
+use crate::handlers::theme::ThemePreview;
+use crate::models::response::{ErrorResponse, PostResponse};
+use crate::models::{Post, PostVisibility};
+use crate::services::outline::{add_heading_anchors, heading_count};
+use crate::services::pdf;
+use crate::services::reader_mode::simplify_html;
 use crate::services::template::{
-    BlogStats, CategoryPageContext, HomePageContext, PostData, PostPageContext, PostSummary,
-    TagPageContext,
+    tag_cloud, BlogStats, CategoryIndexContext, CategoryPageContext, HomePageContext,
+    LitePageContext, PostData, PostPageContext, PostPasswordContext, PostSummary, ReadingProgress,
+    SearchPageContext, SearchResultSummary, TagIndexContext, TagPageContext,
+};
+use crate::services::{
+    BlogStorageService, Breadcrumb, CacheService, CaptchaService, CircuitOpen, ClientInfo,
+    DatabaseService, MarkdownService, MediaService, PostPasswordService, StructuredDataService,
+    TemplateService, VersionService,
 };
-use crate::services::{DatabaseService, MarkdownService, TemplateService};
 
 /// Query parameters for post listing
 #[derive(Debug, Deserialize)]
@@ -31,29 +46,296 @@ pub struct AppState {
     #[allow(dead_code)] // Will be used for markdown processing in the future
     pub markdown: MarkdownService,
     pub templates: TemplateService,
+    /// Public base URL used for absolute links such as share images
+    pub site_url: String,
+    /// Source of the raw markdown served at `/posts/{year}/{slug}.md`
+    pub blog_storage: Arc<BlogStorageService>,
+    /// Key required to read the source of unpublished posts
+    pub api_key: Option<String>,
+    pub post_passwords: PostPasswordService,
+    /// Source of the changelog shown on posts that opt in
+    pub versions: VersionService,
+    /// Storage the files attached to posts are downloaded from
+    pub media: MediaService,
+    /// CAPTCHA required on the password form, when configured
+    pub captcha: Option<CaptchaService>,
+    /// JSON-LD embedded in the pages for search engines
+    pub structured_data: StructuredDataService,
+    /// Remembers slugs that were not found, so probes skip the database
+    pub cache: CacheService,
+}
+
+/// Form posted from the password page of a protected post
+#[derive(Debug, Deserialize)]
+pub struct PostPasswordForm {
+    pub password: String,
+    /// Remaining fields, such as the token of the CAPTCHA widget
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+
+/// Request extension marking a page as rendered in staging view, where
+/// drafts and scheduled posts are visible
+#[derive(Debug, Clone, Copy)]
+pub struct StagingView;
+
+/// Path prefix of the staging view routes
+pub const STAGING_PREFIX: &str = "/staging";
+
+impl AppState {
+    /// Render a public page, adding the staging banner and link prefix in staging view
+    /// and the previewed theme when one was requested
+    fn render_page<T: Serialize>(
+        &self,
+        staging: bool,
+        theme_preview: Option<&ThemePreview>,
+        template_name: &str,
+        context: &T,
+        structured_data: Option<String>,
+    ) -> anyhow::Result<String> {
+        if !staging && theme_preview.is_none() && structured_data.is_none() {
+            return self.templates.render(template_name, context);
+        }
+
+        let mut additional_context = HashMap::new();
+        if let Some(structured_data) = structured_data {
+            additional_context.insert(
+                "structured_data".to_string(),
+                tera::Value::String(structured_data),
+            );
+        }
+        if staging {
+            additional_context.insert("staging".to_string(), tera::Value::Bool(true));
+            additional_context.insert(
+                "url_prefix".to_string(),
+                tera::Value::String(STAGING_PREFIX.to_string()),
+            );
+        }
+        if let Some(ThemePreview(theme)) = theme_preview {
+            additional_context.insert(
+                "theme_preview".to_string(),
+                tera::Value::String(theme.clone()),
+            );
+        }
+        self.templates
+            .render_with_context(template_name, context, additional_context)
+    }
+}
+
+/// Key from `X-API-Key`, a bearer token or the password of HTTP Basic auth
+pub fn request_api_key(headers: &HeaderMap) -> Option<String> {
+    if let Some(key) = headers.get("X-API-Key").and_then(|h| h.to_str().ok()) {
+        return Some(key.to_string());
+    }
+
+    let authorization = headers.get("Authorization")?.to_str().ok()?;
+    if let Some(token) = authorization.strip_prefix("Bearer ") {
+        return Some(token.to_string());
+    }
+
+    let decoded = STANDARD
+        .decode(authorization.strip_prefix("Basic ")?.trim())
+        .ok()?;
+    let credentials = String::from_utf8(decoded).ok()?;
+    credentials
+        .split_once(':')
+        .map(|(_, password)| password.to_string())
+}
+
+/// Whether the request carries the configured API key (always true when none is set)
+pub fn is_authorized(headers: &HeaderMap, api_key: Option<&str>) -> bool {
+    match api_key {
+        Some(expected) => request_api_key(headers).as_deref() == Some(expected),
+        None => true,
+    }
+}
+
+/// Whether the request carries the configured API key; false when none is configured
+///
+/// Unlike [`is_authorized`] this never lets anonymous requests through, for
+/// content that stays protected on installations without an API key.
+pub fn has_api_key(headers: &HeaderMap, api_key: Option<&str>) -> bool {
+    api_key.is_some() && is_authorized(headers, api_key)
+}
+
+/// Error for a private post requested without the API key
+pub fn private_post_error(slug: &str) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Unauthorized request for private post: {}", slug);
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse::new(
+            "unauthorized",
+            "API key required for private posts",
+            401,
+        )),
+    )
+}
+
+/// Markdown file of a post exactly as stored in Dropbox; unpublished posts need the API key
+pub async fn post_source_response(
+    blog_storage: &BlogStorageService,
+    api_key: Option<&str>,
+    headers: &HeaderMap,
+    post: &Post,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    authorize_post_source(api_key, headers, post)?;
+
+    let source = blog_storage
+        .download_post_source(&post.dropbox_path)
+        .await
+        .map_err(|e| {
+            error!("Failed to load source of {}: {:#}", post.slug, e);
+            if CircuitOpen::find(&e).is_some() {
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(ErrorResponse::new(
+                        "dropbox_unavailable",
+                        "Dropbox is temporarily unavailable, please try again later",
+                        503,
+                    )),
+                );
+            }
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(ErrorResponse::new(
+                    "dropbox_error",
+                    "Failed to load post source from Dropbox",
+                    502,
+                )),
+            )
+        })?;
+
+    Ok(markdown_source_response(post, source))
+}
+
+/// Markdown of a pinned version, which has no file of its own in Dropbox
+pub fn pinned_source_response(
+    api_key: Option<&str>,
+    headers: &HeaderMap,
+    post: &Post,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    authorize_post_source(api_key, headers, post)?;
+    Ok(markdown_source_response(post, post.content.clone()))
+}
+
+fn authorize_post_source(
+    api_key: Option<&str>,
+    headers: &HeaderMap,
+    post: &Post,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if post.visibility == PostVisibility::Private && !is_authorized(headers, api_key) {
+        return Err(private_post_error(&post.slug));
+    }
+
+    if post.is_password_protected() && !has_api_key(headers, api_key) {
+        warn!(
+            "Unauthorized source request for password-protected post: {}",
+            post.slug
+        );
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse::new(
+                "unauthorized",
+                "API key required for password-protected posts",
+                401,
+            )),
+        ));
+    }
+
+    if !post.published && !is_authorized(headers, api_key) {
+        warn!(
+            "Unauthorized source request for unpublished post: {}",
+            post.slug
+        );
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse::new(
+                "unauthorized",
+                "API key required for unpublished posts",
+                401,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+fn markdown_source_response(post: &Post, source: String) -> Response {
+    let cache_control = if post.published
+        && post.visibility != PostVisibility::Private
+        && !post.is_password_protected()
+    {
+        "public, max-age=300"
+    } else {
+        "private, no-store"
+    };
+    (
+        [
+            (header::CONTENT_TYPE, "text/markdown; charset=utf-8"),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        source,
+    )
+        .into_response()
+}
+
+/// Drafts and scheduled posts are only listed in staging view
+/// JSON-LD for a page; failing to build it only leaves it out of the page
+fn structured_data_or_none(result: anyhow::Result<String>) -> Option<String> {
+    result
+        .map_err(|e| warn!("Failed to build structured data: {}", e))
+        .ok()
+}
+
+fn published_filter(staging: bool) -> Option<bool> {
+    if staging {
+        None
+    } else {
+        Some(true)
+    }
+}
+
+impl AppState {
+    /// Visibilities listed for this request: unlisted posts only in staging
+    /// view, private ones only with the API key
+    fn listed_visibility(&self, staging: bool, headers: &HeaderMap) -> Option<Vec<PostVisibility>> {
+        if staging {
+            None
+        } else {
+            Some(PostVisibility::listed(is_authorized(
+                headers,
+                self.api_key.as_deref(),
+            )))
+        }
+    }
 }
 
 /// GET / - Home page showing recent and featured posts
 pub async fn home_page(
     Query(query): Query<PostQuery>,
     State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
 ) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
     debug!("Loading home page with query: {:?}", query);
 
     // Get recent posts
     let filters = crate::models::PostFilters {
-        published: Some(true),
+        published: published_filter(staging.is_some()),
         limit: Some(10),
+        pinned_first: true,
+        visibility: state.listed_visibility(staging.is_some(), &headers),
         ..Default::default()
     };
 
-    let posts = state.database.list_posts(filters).await.map_err(|e| {
+    let mut posts = state.database.list_posts(filters).await.map_err(|e| {
         error!("Database error loading posts: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse::internal_error("Failed to load posts")),
         )
     })?;
+    show_published_versions(&state, staging.is_some(), &mut posts).await?;
 
     // Get blog stats
     let blog_stats = state.database.get_post_stats().await.map_err(|e| {
@@ -75,10 +357,17 @@ pub async fn home_page(
         blog_stats: Some(template_stats),
     };
 
+    let structured_data = structured_data_or_none(state.structured_data.home().await);
+
     // Render template
     let html = state
-        .templates
-        .render("index.html", &context)
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "index.html",
+            &context,
+            structured_data,
+        )
         .map_err(|e| {
             error!("Template rendering error: {}", e);
             (
@@ -90,15 +379,64 @@ pub async fn home_page(
     Ok(Html(html))
 }
 
-/// GET /posts/{year}/{slug} - Individual post page
-pub async fn post_page(
-    Path((year, slug)): Path<(String, String)>,
-    State(state): State<AppState>,
-) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
-    debug!("Loading post page for {}/{}", year, slug);
+/// Representation of a post chosen from the `Accept` header
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PostFormat {
+    Html,
+    Markdown,
+    Json,
+}
+
+/// Pick the representation with the highest quality value; ties keep header order
+pub(crate) fn preferred_post_format(headers: &HeaderMap) -> PostFormat {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+    else {
+        return PostFormat::Html;
+    };
+
+    let mut best = (PostFormat::Html, 0.0_f32);
+    for entry in accept.split(',') {
+        let mut parts = entry.split(';').map(str::trim);
+        let format = match parts.next().unwrap_or_default() {
+            "text/html" | "application/xhtml+xml" | "text/*" | "*/*" => PostFormat::Html,
+            "text/markdown" | "text/x-markdown" => PostFormat::Markdown,
+            "application/json" => PostFormat::Json,
+            _ => continue,
+        };
+        let quality = parts
+            .find_map(|param| param.strip_prefix("q="))
+            .and_then(|q| q.parse().ok())
+            .unwrap_or(1.0);
+        if quality > best.1 {
+            best = (format, quality);
+        }
+    }
+    best.0
+}
+
+/// Post with `slug` whose URL year is `year`
+async fn find_post(
+    state: &AppState,
+    year: &str,
+    slug: &str,
+) -> Result<Post, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        )
+    };
+    if state.cache.is_missing_post(slug).await {
+        return Err(not_found());
+    }
 
     // Get post by slug
-    let post = state.database.get_post_by_slug(&slug).await.map_err(|e| {
+    let post = state.database.get_post_by_slug(slug).await.map_err(|e| {
         error!("Database error getting post {}: {}", slug, e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -106,17 +444,20 @@ pub async fn post_page(
         )
     })?;
 
-    let post = match post {
-        Some(post) => post,
-        None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::not_found(format!(
-                    "Post '{}' not found",
-                    slug
-                ))),
-            ));
+    let Some(post) = post else {
+        match state.database.get_gone_post(slug).await {
+            Ok(Some(gone)) if gone.created_at.format("%Y").to_string() == year => {
+                return Err((
+                    StatusCode::GONE,
+                    Json(ErrorResponse::gone(format!("Post '{}' was deleted", slug))),
+                ));
+            }
+            Ok(Some(_)) => {}
+            // Never created nor deleted: skip the lookups until the slug is created
+            Ok(None) => state.cache.remember_missing_post(slug).await,
+            Err(e) => warn!("Failed to look up deleted post {}: {}", slug, e),
         }
+        return Err(not_found());
     };
 
     // Check if the year in URL matches the post's year
@@ -131,8 +472,126 @@ pub async fn post_page(
         ));
     }
 
-    // Only show published posts
-    if !post.published {
+    Ok(post)
+}
+
+/// Swap in the post's pinned version, if it has one other than the latest;
+/// returns whether it did
+async fn show_published_version(state: &AppState, post: &mut Post) -> bool {
+    state
+        .database
+        .show_published_version(post)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load published version of {}: {}", post.slug, e);
+            false
+        })
+}
+
+/// Swap in the pinned versions of listed posts; staging lists show the latest drafts
+async fn show_published_versions(
+    state: &AppState,
+    staging: bool,
+    posts: &mut [Post],
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if staging {
+        return Ok(());
+    }
+    state
+        .database
+        .show_published_versions(posts)
+        .await
+        .map_err(|e| {
+            error!("Database error loading published versions: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load posts")),
+            )
+        })
+}
+
+/// Password form shown in place of a protected post
+async fn password_page(
+    state: &AppState,
+    post: &Post,
+    year: &str,
+    error: Option<String>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let site_config = state.database.get_site_config().await.map_err(|e| {
+        error!("Database error loading site config: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to load site config")),
+        )
+    })?;
+    let (site_title, site_description) = match site_config {
+        Some(config) => (config.site_title, config.site_description),
+        None => (
+            "Tobelog".to_string(),
+            "Personal Blog System built with Rust".to_string(),
+        ),
+    };
+    let context = PostPasswordContext {
+        site_title,
+        site_description,
+        post_title: post.title.clone(),
+        action: format!("/posts/{}/{}", year, post.slug),
+        error,
+        captcha: state.captcha.as_ref().map(CaptchaService::widget),
+    };
+
+    let html = state
+        .templates
+        .render("post_password.html", &context)
+        .map_err(|e| {
+            error!("Template rendering error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to render page")),
+            )
+        })?;
+
+    Ok((
+        StatusCode::UNAUTHORIZED,
+        [(header::CACHE_CONTROL, "private, no-store")],
+        Html(html),
+    )
+        .into_response())
+}
+
+/// GET /posts/{year}/{slug} - Individual post page, also served as Markdown or JSON
+pub async fn post_page(
+    Path((year, slug)): Path<(String, String)>,
+    State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Loading post page for {}/{}", year, slug);
+
+    // `/posts/{year}/{slug}.md` serves the markdown source instead of the page
+    let (slug, source_requested) = match slug.strip_suffix(".md") {
+        Some(slug) => (slug.to_string(), true),
+        None => (slug, false),
+    };
+
+    let mut post = find_post(&state, &year, &slug).await?;
+    // Readers see the pinned version; staging shows the latest draft
+    let pinned = staging.is_none() && show_published_version(&state, &mut post).await;
+
+    // The Dropbox source is the latest draft, so a pinned post serves its own markdown
+    if source_requested && !pinned {
+        return post_source_response(
+            &state.blog_storage,
+            state.api_key.as_deref(),
+            &headers,
+            &post,
+        )
+        .await;
+    }
+
+    // Only show published posts outside staging view
+    if !post.published && staging.is_none() {
         return Err((
             StatusCode::NOT_FOUND,
             Json(ErrorResponse::not_found(format!(
@@ -142,25 +601,463 @@ pub async fn post_page(
         ));
     }
 
+    // Private posts ask the browser for credentials (the API key as Basic auth password)
+    if post.visibility == PostVisibility::Private
+        && !is_authorized(&headers, state.api_key.as_deref())
+    {
+        let (status, body) = private_post_error(&slug);
+        return Ok((
+            status,
+            [(header::WWW_AUTHENTICATE, "Basic realm=\"tobelog\"")],
+            body,
+        )
+            .into_response());
+    }
+
+    let format = if source_requested {
+        PostFormat::Markdown
+    } else {
+        preferred_post_format(&headers)
+    };
+
+    // Password-protected posts show a password form until the reader unlocks them
+    if post.is_password_protected()
+        && staging.is_none()
+        && !state.post_passwords.is_unlocked(&headers, &post)
+    {
+        if format != PostFormat::Html {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse::new(
+                    "password_required",
+                    "This post is password-protected",
+                    401,
+                )),
+            ));
+        }
+        return password_page(&state, &post, &year, None).await;
+    }
+
+    // Staging and theme previews render differently from what the ETag describes
+    let revalidate = staging.is_none() && theme_preview.is_none() && !post.is_password_protected();
+
+    match format {
+        PostFormat::Html => {}
+        PostFormat::Markdown => {
+            let etag = post.etag("markdown");
+            if revalidate && if_none_match(&headers, &etag) {
+                return Ok(not_modified(&etag));
+            }
+            return Ok((
+                [
+                    (header::CONTENT_TYPE, "text/markdown; charset=utf-8"),
+                    (header::VARY, "Accept"),
+                    (header::ETAG, etag.as_str()),
+                ],
+                post.content,
+            )
+                .into_response());
+        }
+        PostFormat::Json => {
+            return Ok((
+                [(header::VARY, "Accept")],
+                Json(
+                    PostResponse::from(post)
+                        .with_local_times(state.templates.site_timezone().get()),
+                ),
+            )
+                .into_response());
+        }
+    }
+
+    // Prefer the featured image's OG crop over the generated share image
+    let og_image_path = match state.database.get_featured_image(post.id).await {
+        Ok(Some(featured_image)) => featured_image.og_url,
+        Ok(None) => format!("/og/{}.png", post.slug),
+        Err(e) => {
+            warn!("Failed to load featured image for {}: {}", slug, e);
+            format!("/og/{}.png", post.slug)
+        }
+    };
+
+    let audio = state
+        .database
+        .get_post_audio(post.id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load audio for {}: {}", slug, e);
+            None
+        });
+
+    let changelog = if post.show_changelog {
+        state
+            .versions
+            .changelog(&post)
+            .await
+            .map(Some)
+            .unwrap_or_else(|e| {
+                warn!("Failed to load changelog for {}: {}", slug, e);
+                None
+            })
+    } else {
+        None
+    };
+
+    let attachments = state
+        .database
+        .list_post_attachments(post.id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load attachments for {}: {}", slug, e);
+            Vec::new()
+        });
+
+    // A cross-post points search engines at its original
+    let canonical_url = post.canonical_url.clone().unwrap_or_else(|| {
+        format!(
+            "{}{}",
+            state.site_url.trim_end_matches('/'),
+            post.get_url_path()
+        )
+    });
+
+    let og_image_url = format!("{}{}", state.site_url.trim_end_matches('/'), og_image_path);
+    let structured_data = structured_data_or_none(
+        state
+            .structured_data
+            .post(&post, &canonical_url, Some(&og_image_url))
+            .await,
+    );
+
+    let pdf_url = (pdf::is_supported() && pdf::is_exportable(&post))
+        .then(|| format!("/api/posts/{}/pdf", post.slug));
+
+    // Anchor every heading for deep links and the reading progress bar
+    let (html_content, outline) = add_heading_anchors(&post.html_content);
+    let reading_progress = ReadingProgress {
+        word_count: post.word_count,
+        reading_time: post.reading_time(),
+        sections: heading_count(&outline),
+        outline_url: format!("/api/posts/{}/outline", post.slug),
+    };
+
     // Convert to template data
-    let post_data = PostData::from(post);
+    let post_data = PostData {
+        html_content,
+        ..PostData::from(post)
+    };
 
     let context = PostPageContext {
         site_title: "Tobelog".to_string(),
         site_description: "Personal Blog System built with Rust".to_string(),
         post: post_data,
+        og_image_url: Some(og_image_url),
+        canonical_url: Some(canonical_url),
+        audio,
+        changelog,
+        attachments,
+        pdf_url,
+        outline,
+        reading_progress,
     };
 
     // Render template
-    let html = state.templates.render("post.html", &context).map_err(|e| {
-        error!("Template rendering error: {}", e);
+    let html = state
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "post.html",
+            &context,
+            structured_data,
+        )
+        .map_err(|e| {
+            error!("Template rendering error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to render post")),
+            )
+        })?;
+
+    // Tag the page itself, so the site title, announcements, audio,
+    // attachments and expiry notice all invalidate cached copies
+    let etag = revalidate.then(|| rendered_etag(&html));
+    if let Some(etag) = etag.as_deref().filter(|etag| if_none_match(&headers, etag)) {
+        return Ok(not_modified(etag));
+    }
+
+    let mut response = ([(header::VARY, "Accept")], Html(html)).into_response();
+    if let Some(etag) = etag.and_then(|etag| HeaderValue::from_str(&etag).ok()) {
+        response.headers_mut().insert(header::ETAG, etag);
+    }
+    Ok(response)
+}
+
+/// Whether `If-None-Match` lists the ETag, compared weakly as for GET requests
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == etag || tag == "*")
+        })
+}
+
+/// Weak ETag of a rendered page
+fn rendered_etag(body: &str) -> String {
+    format!("W/\"{}\"", &format!("{:x}", Sha256::digest(body))[..16])
+}
+
+/// 304 answer to a post request revalidated with its ETag
+fn not_modified(etag: &str) -> Response {
+    (
+        StatusCode::NOT_MODIFIED,
+        [(header::ETAG, etag), (header::VARY, "Accept")],
+    )
+        .into_response()
+}
+
+/// GET /posts/{year}/{slug}/lite - Script-free version of a post with inline
+/// styles and thumbnail-sized images, for slow connections and reader apps
+pub async fn lite_post_page(
+    Path((year, slug)): Path<(String, String)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Loading lite post page for {}/{}", year, slug);
+
+    let mut post = find_post(&state, &year, &slug).await?;
+    if !post.published {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        ));
+    }
+
+    if post.visibility == PostVisibility::Private
+        && !is_authorized(&headers, state.api_key.as_deref())
+    {
+        let (status, body) = private_post_error(&slug);
+        return Ok((
+            status,
+            [(header::WWW_AUTHENTICATE, "Basic realm=\"tobelog\"")],
+            body,
+        )
+            .into_response());
+    }
+
+    // The password form lives on the full page
+    if post.is_password_protected() && !state.post_passwords.is_unlocked(&headers, &post) {
+        return Ok(Redirect::to(&post.get_url_path()).into_response());
+    }
+    show_published_version(&state, &mut post).await;
+
+    let thumbnails = state
+        .database
+        .post_media_thumbnails(post.id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load media thumbnails for {}: {}", slug, e);
+            HashMap::new()
+        });
+
+    let full_url = post.get_url_path();
+    let canonical_url = post
+        .canonical_url
+        .clone()
+        .unwrap_or_else(|| format!("{}{}", state.site_url.trim_end_matches('/'), full_url));
+
+    let mut post_data = PostData::from(post);
+    post_data.html_content = simplify_html(&post_data.html_content, &thumbnails);
+
+    let context = LitePageContext {
+        site_title: "Tobelog".to_string(),
+        post: post_data,
+        canonical_url,
+        full_url,
+    };
+
+    let html = state.templates.render("lite.html", &context).map_err(|e| {
+        error!("Template rendering error for lite page of {}: {}", slug, e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse::internal_error("Failed to render post")),
         )
     })?;
 
-    Ok(Html(html))
+    Ok(Html(html).into_response())
+}
+
+/// POST /posts/{year}/{slug} - Unlock a password-protected post with a cookie
+pub async fn unlock_post(
+    Path((year, slug)): Path<(String, String)>,
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Form(form): Form<PostPasswordForm>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let post = find_post(&state, &year, &slug).await?;
+    if !post.published {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found(format!(
+                "Post '{}' not found",
+                slug
+            ))),
+        ));
+    }
+
+    let post_url = format!("/posts/{}/{}", year, slug);
+    if !post.is_password_protected() {
+        return Ok(Redirect::to(&post_url).into_response());
+    }
+
+    // Checked first so that the CAPTCHA also throttles password guessing
+    if let Some(captcha) = &state.captcha {
+        let token = form.extra.get(captcha.response_field()).map(String::as_str);
+        let solved = captcha.verify(token, client.ip).await.unwrap_or_else(|e| {
+            error!("CAPTCHA verification failed: {:#}", e);
+            false
+        });
+        if !solved {
+            warn!("CAPTCHA not solved on password form of post: {}", slug);
+            return password_page(
+                &state,
+                &post,
+                &year,
+                Some(state.templates.translate("password.captcha_failed", &[])),
+            )
+            .await;
+        }
+    }
+
+    if !state.post_passwords.verify_password(&post, &form.password) {
+        warn!("Wrong password entered for post: {}", slug);
+        return password_page(
+            &state,
+            &post,
+            &year,
+            Some(state.templates.translate("password.wrong", &[])),
+        )
+        .await;
+    }
+
+    debug!("Unlocked password-protected post: {}", slug);
+    Ok((
+        [(
+            header::SET_COOKIE,
+            state.post_passwords.unlock_cookie(&post),
+        )],
+        Redirect::to(&post_url),
+    )
+        .into_response())
+}
+
+/// GET /attachments/{id} - Download a file attached to a post and count it
+///
+/// Readers get the same access as to the post itself: drafts and private
+/// posts need the API key, protected posts an unlock cookie or the API key.
+pub async fn download_attachment(
+    Path(id): Path<uuid::Uuid>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Downloading attachment: {}", id);
+
+    let db_error = |e: anyhow::Error| {
+        error!("Database error loading attachment {}: {}", id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Database error")),
+        )
+    };
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::not_found("Attachment not found")),
+        )
+    };
+
+    let attachment = state
+        .database
+        .get_post_attachment(id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+    let post = state
+        .database
+        .get_post_by_id(attachment.post_id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+
+    let api_key = state.api_key.as_deref();
+    if !post.published && !is_authorized(&headers, api_key) {
+        return Err(not_found());
+    }
+    if post.visibility == PostVisibility::Private && !is_authorized(&headers, api_key) {
+        return Err(private_post_error(&post.slug));
+    }
+    if post.is_password_protected()
+        && !state.post_passwords.is_unlocked(&headers, &post)
+        && !has_api_key(&headers, api_key)
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse::new(
+                "unauthorized",
+                "Unlock the post to download its attachments",
+                401,
+            )),
+        ));
+    }
+
+    let media_file = state
+        .database
+        .get_media_file(attachment.media_id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+    let path = media_file
+        .url
+        .strip_prefix("/media")
+        .unwrap_or(&media_file.url);
+    let (data, _) = state.media.serve_media_file(path).await.map_err(|e| {
+        error!("Failed to load attachment {}: {:#}", id, e);
+        if CircuitOpen::find(&e).is_some() {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ErrorResponse::new(
+                    "media_unavailable",
+                    "Media storage is temporarily unavailable",
+                    503,
+                )),
+            );
+        }
+        not_found()
+    })?;
+
+    if let Err(e) = state.database.record_attachment_download(id).await {
+        warn!("Failed to count download of attachment {}: {}", id, e);
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.mime_type.clone()),
+            (
+                header::CONTENT_DISPOSITION,
+                attachment.content_disposition(),
+            ),
+            (header::CACHE_CONTROL, "private, no-cache".to_string()),
+        ],
+        data,
+    )
+        .into_response())
 }
 
 /// GET /category/{category} - Category page showing posts in a specific category
@@ -168,23 +1065,28 @@ pub async fn category_page(
     Path(category): Path<String>,
     Query(query): Query<PostQuery>,
     State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
 ) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
     debug!("Loading category page for category: {}", category);
 
     let page = query.page.unwrap_or(1);
     let per_page = query.per_page.unwrap_or(10);
     let offset = (page.saturating_sub(1)) * per_page;
+    let visibility = state.listed_visibility(staging.is_some(), &headers);
 
     // Get posts in this category
     let filters = crate::models::PostFilters {
-        published: Some(true),
+        published: published_filter(staging.is_some()),
         category: Some(category.clone()),
         limit: Some(per_page as i64),
         offset: Some(offset as i64),
+        visibility: visibility.clone(),
         ..Default::default()
     };
 
-    let posts = state
+    let mut posts = state
         .database
         .list_posts(filters.clone())
         .await
@@ -198,11 +1100,13 @@ pub async fn category_page(
                 Json(ErrorResponse::internal_error("Failed to load posts")),
             )
         })?;
+    show_published_versions(&state, staging.is_some(), &mut posts).await?;
 
     // Get total count for pagination
     let count_filters = crate::models::PostFilters {
-        published: Some(true),
+        published: published_filter(staging.is_some()),
         category: Some(category.clone()),
+        visibility,
         ..Default::default()
     };
 
@@ -237,10 +1141,25 @@ pub async fn category_page(
         total_pages,
     };
 
+    let structured_data = structured_data_or_none(
+        state
+            .structured_data
+            .listing(&[
+                Breadcrumb::translated("common.categories", "/categories"),
+                Breadcrumb::new(&category, format!("/category/{}", category)),
+            ])
+            .await,
+    );
+
     // Render template
     let html = state
-        .templates
-        .render("category.html", &context)
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "category.html",
+            &context,
+            structured_data,
+        )
         .map_err(|e| {
             error!("Template rendering error for category {}: {}", category, e);
             (
@@ -257,23 +1176,28 @@ pub async fn tag_page(
     Path(tag): Path<String>,
     Query(query): Query<PostQuery>,
     State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
 ) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
     debug!("Loading tag page for tag: {}", tag);
 
     let page = query.page.unwrap_or(1);
     let per_page = query.per_page.unwrap_or(10);
     let offset = (page.saturating_sub(1)) * per_page;
+    let visibility = state.listed_visibility(staging.is_some(), &headers);
 
     // Get posts with this tag
     let filters = crate::models::PostFilters {
-        published: Some(true),
+        published: published_filter(staging.is_some()),
         tag: Some(tag.clone()),
         limit: Some(per_page as i64),
         offset: Some(offset as i64),
+        visibility: visibility.clone(),
         ..Default::default()
     };
 
-    let posts = state
+    let mut posts = state
         .database
         .list_posts(filters.clone())
         .await
@@ -284,11 +1208,13 @@ pub async fn tag_page(
                 Json(ErrorResponse::internal_error("Failed to load posts")),
             )
         })?;
+    show_published_versions(&state, staging.is_some(), &mut posts).await?;
 
     // Get total count for pagination
     let count_filters = crate::models::PostFilters {
-        published: Some(true),
+        published: published_filter(staging.is_some()),
         tag: Some(tag.clone()),
+        visibility,
         ..Default::default()
     };
 
@@ -320,14 +1246,356 @@ pub async fn tag_page(
         total_pages,
     };
 
+    let structured_data = structured_data_or_none(
+        state
+            .structured_data
+            .listing(&[
+                Breadcrumb::translated("common.tags", "/tags"),
+                Breadcrumb::new(format!("#{}", tag), format!("/tag/{}", tag)),
+            ])
+            .await,
+    );
+
     // Render template
-    let html = state.templates.render("tag.html", &context).map_err(|e| {
-        error!("Template rendering error for tag {}: {}", tag, e);
+    let html = state
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "tag.html",
+            &context,
+            structured_data,
+        )
+        .map_err(|e| {
+            error!("Template rendering error for tag {}: {}", tag, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to render page")),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// Query parameters for the search page
+#[derive(Debug, Deserialize)]
+pub struct SearchPageQuery {
+    pub q: Option<String>,
+    pub category: Option<String>,
+    pub page: Option<usize>,
+}
+
+/// GET /search?q= - Search results page with category facets
+pub async fn search_page(
+    Query(query): Query<SearchPageQuery>,
+    State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
+) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
+    let search_query = query.q.unwrap_or_default().trim().to_string();
+    let category = query.category.filter(|c| !c.is_empty());
+    debug!("Loading search page for query: {}", search_query);
+
+    let per_page = 10;
+    let page = query.page.unwrap_or(1).max(1);
+    let visibility = state.listed_visibility(staging.is_some(), &headers);
+
+    let filters = crate::models::SearchFilters {
+        published: published_filter(staging.is_some()),
+        category: category.clone(),
+        limit: Some(per_page as i64),
+        offset: Some(((page - 1) * per_page) as i64),
+        visibility: visibility.clone(),
+        ..Default::default()
+    };
+
+    let search_error = |e: anyhow::Error| {
+        error!("Database error searching posts: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::internal_error("Failed to render page")),
+            Json(ErrorResponse::internal_error("Search failed")),
         )
-    })?;
+    };
+
+    let match_query = DatabaseService::fts_match_query(&search_query);
+    let (hits, total_count, facets) = if match_query.is_empty() {
+        (Vec::new(), 0, Vec::new())
+    } else {
+        let mut hits = state
+            .database
+            .search_posts_with_snippets(&match_query, &filters)
+            .await
+            .map_err(search_error)?;
+        // Snippets quote the latest draft, so pinned posts show their excerpt
+        if staging.is_none() {
+            for hit in &mut hits {
+                if show_published_version(&state, &mut hit.post).await {
+                    hit.snippet_html =
+                        html_escape::encode_text(hit.post.excerpt.as_deref().unwrap_or_default())
+                            .to_string();
+                }
+            }
+        }
+        let total_count = state
+            .database
+            .count_search_results(&match_query, &filters)
+            .await
+            .map_err(search_error)?;
+        let facets = state
+            .database
+            .search_category_facets(&match_query, &filters)
+            .await
+            .map_err(search_error)?;
+        (hits, total_count, facets)
+    };
+
+    // Point visitors somewhere useful when nothing matched
+    let (suggested_posts, suggested_categories) = if hits.is_empty() {
+        let mut recent = state
+            .database
+            .list_posts(crate::models::PostFilters {
+                published: published_filter(staging.is_some()),
+                limit: Some(5),
+                visibility,
+                ..Default::default()
+            })
+            .await
+            .map_err(search_error)?;
+        show_published_versions(&state, staging.is_some(), &mut recent).await?;
+        let mut categories = state
+            .database
+            .get_post_stats()
+            .await
+            .map_err(search_error)?
+            .categories;
+        categories.truncate(8);
+        (
+            recent.into_iter().map(PostSummary::from).collect(),
+            categories,
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let total_results = total_count as usize;
+    let context = SearchPageContext {
+        site_title: "Tobelog".to_string(),
+        site_description: "Personal Blog System built with Rust".to_string(),
+        query: search_query,
+        category,
+        results: hits.into_iter().map(SearchResultSummary::from).collect(),
+        facets,
+        total_results,
+        page,
+        total_pages: total_results.div_ceil(per_page),
+        suggested_posts,
+        suggested_categories,
+    };
+
+    let structured_data = structured_data_or_none(
+        state
+            .structured_data
+            .listing(&[Breadcrumb::translated("common.search", "/search")])
+            .await,
+    );
+    let html = state
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "search.html",
+            &context,
+            structured_data,
+        )
+        .map_err(|e| {
+            error!("Template rendering error for search page: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to render page")),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// GET /tags - Tag cloud weighted by post count
+pub async fn tag_index_page(
+    State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
+) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Loading tag index page");
+
+    let visibility = state.listed_visibility(staging.is_some(), &headers);
+    let tags = state
+        .database
+        .list_tag_counts(published_filter(staging.is_some()), visibility.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Database error loading tags: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load tags")),
+            )
+        })?;
+
+    let context = TagIndexContext {
+        site_title: "Tobelog".to_string(),
+        site_description: "Personal Blog System built with Rust".to_string(),
+        tags: tag_cloud(tags),
+    };
+
+    let structured_data = structured_data_or_none(
+        state
+            .structured_data
+            .listing(&[Breadcrumb::translated("common.tags", "/tags")])
+            .await,
+    );
+    let html = state
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "tags.html",
+            &context,
+            structured_data,
+        )
+        .map_err(|e| {
+            error!("Template rendering error for tag index: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to render page")),
+            )
+        })?;
+
+    Ok(Html(html))
+}
+
+/// GET /categories - Category index with post counts and descriptions
+pub async fn category_index_page(
+    State(state): State<AppState>,
+    staging: Option<Extension<StagingView>>,
+    theme_preview: Option<Extension<ThemePreview>>,
+    headers: HeaderMap,
+) -> Result<Html<String>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Loading category index page");
+
+    let visibility = state.listed_visibility(staging.is_some(), &headers);
+    let categories = state
+        .database
+        .list_category_index(published_filter(staging.is_some()), visibility.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Database error loading categories: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to load categories")),
+            )
+        })?;
+
+    let context = CategoryIndexContext {
+        site_title: "Tobelog".to_string(),
+        site_description: "Personal Blog System built with Rust".to_string(),
+        categories,
+    };
+
+    let structured_data = structured_data_or_none(
+        state
+            .structured_data
+            .listing(&[Breadcrumb::translated("common.categories", "/categories")])
+            .await,
+    );
+    let html = state
+        .render_page(
+            staging.is_some(),
+            theme_preview.as_deref(),
+            "categories.html",
+            &context,
+            structured_data,
+        )
+        .map_err(|e| {
+            error!("Template rendering error for category index: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to render page")),
+            )
+        })?;
 
     Ok(Html(html))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_preferred_post_format_honours_quality_values() {
+        assert_eq!(preferred_post_format(&HeaderMap::new()), PostFormat::Html);
+        assert_eq!(
+            preferred_post_format(&accept("text/html,application/xhtml+xml,*/*;q=0.8")),
+            PostFormat::Html
+        );
+        assert_eq!(
+            preferred_post_format(&accept("text/markdown")),
+            PostFormat::Markdown
+        );
+        assert_eq!(
+            preferred_post_format(&accept("text/html;q=0.5, application/json")),
+            PostFormat::Json
+        );
+        assert_eq!(
+            preferred_post_format(&accept("image/png")),
+            PostFormat::Html
+        );
+    }
+
+    #[test]
+    fn test_request_api_key_accepts_basic_auth_password() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Basic {}", STANDARD.encode("admin:secret"))).unwrap(),
+        );
+        assert_eq!(request_api_key(&headers), Some("secret".to_string()));
+        assert!(is_authorized(&headers, Some("secret")));
+        assert!(!is_authorized(&HeaderMap::new(), Some("secret")));
+        assert!(is_authorized(&HeaderMap::new(), None));
+
+        headers.insert("Authorization", HeaderValue::from_static("Bearer token"));
+        assert_eq!(request_api_key(&headers), Some("token".to_string()));
+
+        assert_eq!(request_api_key(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_if_none_match_compares_weakly() {
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, "W/\"abc\""));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"other\", \"abc\""),
+        );
+        assert!(if_none_match(&headers, "W/\"abc\""));
+        assert!(!if_none_match(&headers, "W/\"abd\""));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, "\"anything\""));
+    }
+
+    #[test]
+    fn test_rendered_etag_follows_the_whole_page() {
+        let etag = rendered_etag("<main>Body</main><aside>Announcement</aside>");
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(
+            rendered_etag("<main>Body</main><aside>Announcement</aside>"),
+            etag
+        );
+        assert_ne!(rendered_etag("<main>Body</main>"), etag);
+    }
+}
@@ -0,0 +1,40 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+use crate::services::SiteFilesService;
+
+fn text_response(body: String) -> Response {
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body).into_response()
+}
+
+/// GET /robots.txt - Crawler rules; non-production environments disallow everything
+pub async fn serve_robots_txt(State(site_files): State<SiteFilesService>) -> Response {
+    text_response(site_files.robots_txt())
+}
+
+/// GET /.well-known/security.txt - Security contact (RFC 9116)
+pub async fn serve_security_txt(State(site_files): State<SiteFilesService>) -> Response {
+    match site_files.security_txt().await {
+        Ok(Some(body)) => text_response(body),
+        Ok(None) => (StatusCode::NOT_FOUND, "No security contact configured").into_response(),
+        Err(e) => {
+            error!("Failed to generate security.txt: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// GET /humans.txt - Credits generated from the site configuration
+pub async fn serve_humans_txt(State(site_files): State<SiteFilesService>) -> Response {
+    match site_files.humans_txt().await {
+        Ok(body) => text_response(body),
+        Err(e) => {
+            error!("Failed to generate humans.txt: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{debug, error};
+
+use crate::models::{response::ErrorResponse, JobEnqueuedResponse, StorageMigrationRequest};
+use crate::services::{JobQueue, StorageKind, StorageMigrationService};
+
+/// Job kind of queued storage migrations
+pub const STORAGE_MIGRATION_JOB: &str = "storage_migration";
+
+/// App state for storage migration handlers
+#[derive(Clone)]
+pub struct StorageMigrationState {
+    pub migrator: StorageMigrationService,
+    pub jobs: JobQueue,
+}
+
+impl StorageMigrationState {
+    /// Register the handler of queued storage migrations
+    pub fn register_jobs(&self) {
+        let migrator = self.migrator.clone();
+        self.jobs.register(STORAGE_MIGRATION_JOB, move |payload| {
+            let migrator = migrator.clone();
+            async move { migrator.migrate(&serde_json::from_value(payload)?).await }
+        });
+    }
+}
+
+/// POST /api/admin/storage/migrate - Queue a copy of all files to another
+/// storage backend; progress and the report are on the job status
+pub async fn start_storage_migration(
+    State(state): State<StorageMigrationState>,
+    Json(request): Json<StorageMigrationRequest>,
+) -> Result<(StatusCode, Json<JobEnqueuedResponse>), (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Queueing storage migration to {}", request.target);
+
+    for backend in request.source.iter().chain([&request.target]) {
+        if let Err(e) = backend.parse::<StorageKind>() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(e.to_string())),
+            ));
+        }
+    }
+
+    let job = state
+        .jobs
+        .enqueue(STORAGE_MIGRATION_JOB, &request)
+        .await
+        .map_err(|e| {
+            error!("Failed to queue storage migration: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to queue storage migration",
+                )),
+            )
+        })?;
+
+    Ok((StatusCode::ACCEPTED, Json(JobEnqueuedResponse::from(&job))))
+}
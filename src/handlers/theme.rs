@@ -1,16 +1,21 @@
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{Json, Response},
+    Extension,
 };
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use tracing::{debug, error};
+use utoipa::IntoParams;
 
 use crate::models::{
-    response::ErrorResponse, CreateThemeRequest, SiteConfig, SiteConfigResponse, ThemeFilters,
-    ThemeListResponse, ThemePreviewResponse, ThemeResponse, UpdateThemeRequest,
+    response::ErrorResponse, CreateThemeRequest, SettingsDocument, SettingsImportResponse,
+    SiteConfig, SiteConfigResponse, ThemeFilters, ThemeListResponse, ThemePreviewResponse,
+    ThemeResponse, UpdateThemeRequest,
 };
-use crate::services::{DatabaseService, ThemeService};
+use crate::services::{DatabaseService, TemplateService, ThemePreviewService, ThemeService};
 
 /// App state for theme handlers
 #[derive(Clone)]
@@ -18,10 +23,29 @@ pub struct ThemeState {
     pub theme_service: ThemeService,
     #[allow(dead_code)]
     pub database: DatabaseService,
+    /// Follows locale and timezone changes in the site configuration
+    pub templates: TemplateService,
+    pub preview: ThemePreviewService,
 }
 
-/// Query parameters for theme listing
+/// Request extension marking a page or stylesheet rendered with a previewed theme
+#[derive(Debug, Clone)]
+pub struct ThemePreview(pub String);
+
+/// Query parameter overriding the active theme for one request
+#[derive(Debug, Deserialize)]
+pub struct ThemePreviewQuery {
+    pub theme: Option<String>,
+}
+
+/// Request body for the preview cookie endpoint; `null` ends the preview
 #[derive(Debug, Deserialize)]
+pub struct ThemePreviewRequest {
+    pub theme: Option<String>,
+}
+
+/// Query parameters for theme listing
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct ThemeQuery {
     pub is_active: Option<bool>,
     pub layout: Option<String>,
@@ -38,6 +62,15 @@ pub struct SyncQuery {
 }
 
 /// GET /api/themes - List all themes
+#[utoipa::path(
+    get,
+    path = "/api/themes",
+    tag = "themes",
+    params(ThemeQuery),
+    responses(
+        (status = 200, description = "Themes", body = ThemeListResponse)
+    )
+)]
 pub async fn list_themes(
     Query(query): Query<ThemeQuery>,
     State(state): State<ThemeState>,
@@ -81,6 +114,16 @@ pub async fn list_themes(
 }
 
 /// GET /api/themes/{name} - Get a specific theme
+#[utoipa::path(
+    get,
+    path = "/api/themes/{name}",
+    tag = "themes",
+    params(("name" = String, Path, description = "Theme name")),
+    responses(
+        (status = 200, description = "The theme", body = ThemeResponse),
+        (status = 404, description = "Theme not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_theme(
     Path(name): Path<String>,
     State(state): State<ThemeState>,
@@ -114,6 +157,14 @@ pub async fn get_theme(
 }
 
 /// GET /api/themes/active - Get the currently active theme
+#[utoipa::path(
+    get,
+    path = "/api/themes/active",
+    tag = "themes",
+    responses(
+        (status = 200, description = "Active theme", body = ThemeResponse)
+    )
+)]
 pub async fn get_active_theme(
     State(state): State<ThemeState>,
 ) -> Result<Json<ThemeResponse>, (StatusCode, Json<ErrorResponse>)> {
@@ -136,6 +187,18 @@ pub async fn get_active_theme(
 }
 
 /// POST /api/themes - Create a new theme
+#[utoipa::path(
+    post,
+    path = "/api/themes",
+    tag = "themes",
+    request_body = CreateThemeRequest,
+    responses(
+        (status = 200, description = "Theme created", body = ThemeResponse),
+        (status = 400, description = "Invalid theme", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
 pub async fn create_theme(
     State(state): State<ThemeState>,
     Json(request): Json<CreateThemeRequest>,
@@ -150,6 +213,8 @@ pub async fn create_theme(
             error!("Failed to create theme: {}", e);
             let status = if e.to_string().contains("already exists") {
                 StatusCode::CONFLICT
+            } else if e.to_string().contains("Invalid category style") {
+                StatusCode::BAD_REQUEST
             } else {
                 StatusCode::INTERNAL_SERVER_ERROR
             };
@@ -172,6 +237,19 @@ pub async fn create_theme(
 }
 
 /// PUT /api/themes/{name} - Update a theme
+#[utoipa::path(
+    put,
+    path = "/api/themes/{name}",
+    tag = "themes",
+    params(("name" = String, Path, description = "Theme name")),
+    request_body = UpdateThemeRequest,
+    responses(
+        (status = 200, description = "Theme updated", body = ThemeResponse),
+        (status = 404, description = "Theme not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
 pub async fn update_theme(
     Path(name): Path<String>,
     State(state): State<ThemeState>,
@@ -185,10 +263,17 @@ pub async fn update_theme(
         .await
         .map_err(|e| {
             error!("Failed to update theme {}: {}", name, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::internal_error("Failed to update theme")),
-            )
+            if e.to_string().contains("Invalid category style") {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::bad_request(e.to_string())),
+                )
+            } else {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Failed to update theme")),
+                )
+            }
         })?;
 
     let response = ThemeResponse {
@@ -200,6 +285,18 @@ pub async fn update_theme(
 }
 
 /// DELETE /api/themes/{name} - Delete a theme
+#[utoipa::path(
+    delete,
+    path = "/api/themes/{name}",
+    tag = "themes",
+    params(("name" = String, Path, description = "Theme name")),
+    responses(
+        (status = 200, description = "Theme deleted"),
+        (status = 404, description = "Theme not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
 pub async fn delete_theme(
     Path(name): Path<String>,
     State(state): State<ThemeState>,
@@ -241,6 +338,18 @@ pub async fn delete_theme(
 }
 
 /// POST /api/themes/{name}/activate - Set a theme as active
+#[utoipa::path(
+    post,
+    path = "/api/themes/{name}/activate",
+    tag = "themes",
+    params(("name" = String, Path, description = "Theme name")),
+    responses(
+        (status = 200, description = "Theme activated", body = ThemeResponse),
+        (status = 404, description = "Theme not found", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
 pub async fn activate_theme(
     Path(name): Path<String>,
     State(state): State<ThemeState>,
@@ -319,6 +428,118 @@ pub async fn get_theme_css(
     Ok(css)
 }
 
+/// GET /theme.css - Serve the active (or previewed) theme's compiled CSS with ETag revalidation
+pub async fn serve_active_theme_css(
+    headers: HeaderMap,
+    State(state): State<ThemeState>,
+    theme_preview: Option<Extension<ThemePreview>>,
+) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Serving active theme CSS");
+
+    let css = match theme_preview.as_deref() {
+        Some(ThemePreview(theme)) => state.theme_service.generate_theme_css(theme).await,
+        None => state.theme_service.generate_active_theme_css().await,
+    }
+    .map_err(|e| {
+        error!("Failed to generate active theme CSS: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error(
+                "Failed to generate theme CSS",
+            )),
+        )
+    })?;
+
+    let etag = format!("\"{:x}\"", Sha256::digest(css.as_bytes()));
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| {
+            v.split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        })
+        .unwrap_or(false);
+
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, "text/css; charset=utf-8")
+        .header(header::CACHE_CONTROL, "public, no-cache")
+        .header(header::ETAG, &etag);
+
+    let response = if not_modified {
+        builder.status(StatusCode::NOT_MODIFIED).body(Body::empty())
+    } else {
+        builder.status(StatusCode::OK).body(Body::from(css))
+    };
+
+    response.map_err(|e| {
+        error!("Failed to build theme CSS response: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to serve theme CSS")),
+        )
+    })
+}
+
+/// POST /api/admin/theme-preview - Preview a theme in this browser without activating it
+pub async fn set_theme_preview(
+    State(state): State<ThemeState>,
+    Json(request): Json<ThemePreviewRequest>,
+) -> Result<Response<Body>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Setting theme preview: {:?}", request.theme);
+
+    if !state.preview.is_enabled() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden",
+                "Theme preview is disabled (set THEME_PREVIEW=true)",
+                403,
+            )),
+        ));
+    }
+
+    let cookie = match &request.theme {
+        Some(name) => {
+            let theme = state.theme_service.get_theme(name).await.map_err(|e| {
+                error!("Failed to get theme {}: {}", name, e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::internal_error("Failed to get theme")),
+                )
+            })?;
+            if theme.is_none() {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse::not_found(format!(
+                        "Theme '{}' not found",
+                        name
+                    ))),
+                ));
+            }
+            state.preview.preview_cookie(name)
+        }
+        None => state.preview.clear_cookie(),
+    };
+
+    let body = serde_json::json!({
+        "success": true,
+        "theme": request.theme,
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::SET_COOKIE, cookie)
+        .body(Body::from(body.to_string()))
+        .map_err(|e| {
+            error!("Failed to build theme preview response: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to set theme preview")),
+            )
+        })
+}
+
 /// POST /api/themes/sync - Sync themes from Dropbox
 pub async fn sync_dropbox_themes(
     Query(_query): Query<SyncQuery>,
@@ -424,6 +645,8 @@ pub async fn update_site_config(
                 )),
             )
         })?;
+    state.templates.set_locale(updated_config.locale);
+    state.templates.set_timezone(updated_config.timezone);
 
     let response = SiteConfigResponse {
         success: true,
@@ -432,3 +655,51 @@ pub async fn update_site_config(
 
     Ok(Json(response))
 }
+
+// Settings transfer endpoints
+
+/// GET /api/admin/settings/export - Export site configuration and themes as JSON
+pub async fn export_settings(
+    State(state): State<ThemeState>,
+) -> Result<Json<SettingsDocument>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Exporting site settings");
+
+    let document = state.theme_service.export_settings().await.map_err(|e| {
+        error!("Failed to export settings: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to export settings")),
+        )
+    })?;
+
+    Ok(Json(document))
+}
+
+/// POST /api/admin/settings/import - Restore an exported settings document
+pub async fn import_settings(
+    State(state): State<ThemeState>,
+    Json(document): Json<SettingsDocument>,
+) -> Result<Json<SettingsImportResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Importing site settings");
+
+    let locale = document.site_config.locale;
+    let timezone = document.site_config.timezone;
+    let response = state
+        .theme_service
+        .import_settings(document)
+        .await
+        .map_err(|e| {
+            error!("Failed to import settings: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request(format!(
+                    "Failed to import settings: {}",
+                    e
+                ))),
+            )
+        })?;
+    state.templates.set_locale(locale);
+    state.templates.set_timezone(timezone);
+
+    Ok(Json(response))
+}
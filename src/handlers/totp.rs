@@ -0,0 +1,111 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{error, info, warn};
+
+use crate::models::{
+    response::ErrorResponse, TotpCodeRequest, TotpEnrollment, TotpRecoveryCodesResponse,
+};
+use crate::services::{TotpError, TotpService};
+
+/// App state for two-factor authentication handlers
+#[derive(Clone)]
+pub struct TotpState {
+    pub totp: TotpService,
+}
+
+type TotpResult<T> = Result<T, (StatusCode, Json<ErrorResponse>)>;
+
+fn internal_error(action: &str, e: anyhow::Error) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Failed to {}: {}", action, e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse::internal_error(format!(
+            "Failed to {}",
+            action
+        ))),
+    )
+}
+
+fn totp_error(e: TotpError) -> (StatusCode, Json<ErrorResponse>) {
+    match e {
+        TotpError::InvalidState => (
+            StatusCode::CONFLICT,
+            Json(ErrorResponse::new(
+                "conflict",
+                "Two-factor authentication is not in the required state for this action",
+                409,
+            )),
+        ),
+        TotpError::InvalidCode => {
+            warn!("Invalid two-factor code");
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::bad_request("Invalid authentication code")),
+            )
+        }
+    }
+}
+
+/// POST /api/admin/totp/enroll - Generate a secret and provisioning URI for an authenticator app
+pub async fn begin_enrollment(State(state): State<TotpState>) -> TotpResult<Json<TotpEnrollment>> {
+    info!("API: Starting two-factor enrollment");
+
+    let enrollment = state
+        .totp
+        .begin_enrollment()
+        .await
+        .map_err(|e| internal_error("start two-factor enrollment", e))?
+        .map_err(totp_error)?;
+
+    Ok(Json(enrollment))
+}
+
+/// POST /api/admin/totp/confirm - Enable two-factor login with a first code
+pub async fn confirm_enrollment(
+    State(state): State<TotpState>,
+    Json(request): Json<TotpCodeRequest>,
+) -> TotpResult<Json<TotpRecoveryCodesResponse>> {
+    info!("API: Confirming two-factor enrollment");
+
+    let recovery_codes = state
+        .totp
+        .confirm_enrollment(&request.code)
+        .await
+        .map_err(|e| internal_error("confirm two-factor enrollment", e))?
+        .map_err(totp_error)?;
+
+    Ok(Json(TotpRecoveryCodesResponse { recovery_codes }))
+}
+
+/// POST /api/admin/totp/recovery-codes - Replace the recovery codes
+pub async fn regenerate_recovery_codes(
+    State(state): State<TotpState>,
+    Json(request): Json<TotpCodeRequest>,
+) -> TotpResult<Json<TotpRecoveryCodesResponse>> {
+    info!("API: Regenerating recovery codes");
+
+    let recovery_codes = state
+        .totp
+        .regenerate_recovery_codes(&request.code)
+        .await
+        .map_err(|e| internal_error("regenerate recovery codes", e))?
+        .map_err(totp_error)?;
+
+    Ok(Json(TotpRecoveryCodesResponse { recovery_codes }))
+}
+
+/// POST /api/admin/totp/disable - Turn two-factor login off
+pub async fn disable(
+    State(state): State<TotpState>,
+    Json(request): Json<TotpCodeRequest>,
+) -> TotpResult<StatusCode> {
+    info!("API: Disabling two-factor authentication");
+
+    state
+        .totp
+        .disable(&request.code)
+        .await
+        .map_err(|e| internal_error("disable two-factor authentication", e))?
+        .map_err(totp_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
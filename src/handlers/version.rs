@@ -1,23 +1,29 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
 use serde::Deserialize;
-use tracing::{debug, error};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::models::{
-    response::ErrorResponse, RestoreVersionRequest, RestoreVersionResponse, VersionDiffResponse,
-    VersionHistoryResponse, VersionResponse,
+    response::ErrorResponse, PostChangelogResponse, PostVisibility, PublishVersionResponse,
+    RestoreVersionRequest, RestoreVersionResponse, SetChangelogRequest, VersionCleanupResponse,
+    VersionDiffResponse, VersionHistoryResponse, VersionImportResponse, VersionResponse,
+    VersionRetentionPolicy,
 };
-use crate::services::{DatabaseService, VersionService};
+use crate::services::version::parse_versions_jsonl;
+use crate::services::{CacheService, DatabaseService, VersionService};
 
 /// App state for version handlers
 #[derive(Clone)]
 pub struct VersionState {
     pub version_service: VersionService,
     pub database: DatabaseService,
+    pub cache: CacheService,
+    /// Configured policy of the site-wide cleanup
+    pub retention: VersionRetentionPolicy,
 }
 
 /// Query parameters for version listing
@@ -34,6 +40,13 @@ pub struct CleanupQuery {
     pub keep_versions: Option<i32>,
 }
 
+/// Query parameters for the site-wide cleanup; either one replaces the configured policy
+#[derive(Debug, Deserialize)]
+pub struct RetentionQuery {
+    pub keep_versions: Option<i32>,
+    pub max_age_days: Option<i64>,
+}
+
 /// Helper function to get post ID by slug
 async fn get_post_id_by_slug(
     database: &DatabaseService,
@@ -63,6 +76,16 @@ async fn get_post_id_by_slug(
 }
 
 /// GET /api/posts/{slug}/versions - Get version history for a post
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}/versions",
+    tag = "versions",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "Version history", body = VersionHistoryResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_version_history(
     Path(slug): Path<String>,
     Query(_query): Query<VersionQuery>,
@@ -96,6 +119,19 @@ pub async fn get_version_history(
 }
 
 /// GET /api/posts/{slug}/versions/{version} - Get a specific version of a post
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}/versions/{version}",
+    tag = "versions",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        ("version" = i32, Path, description = "Version number")
+    ),
+    responses(
+        (status = 200, description = "The version", body = VersionResponse),
+        (status = 404, description = "Version not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_post_version(
     Path((slug, version)): Path<(String, i32)>,
     State(state): State<VersionState>,
@@ -135,6 +171,20 @@ pub async fn get_post_version(
 }
 
 /// GET /api/posts/{slug}/diff/{version_from}/{version_to} - Compare two versions
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}/diff/{version_from}/{version_to}",
+    tag = "versions",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        ("version_from" = i32, Path, description = "Older version"),
+        ("version_to" = i32, Path, description = "Newer version")
+    ),
+    responses(
+        (status = 200, description = "Differences between the versions", body = VersionDiffResponse),
+        (status = 404, description = "Version not found", body = ErrorResponse)
+    )
+)]
 pub async fn compare_versions(
     Path((slug, version_from, version_to)): Path<(String, i32, i32)>,
     State(state): State<VersionState>,
@@ -170,6 +220,22 @@ pub async fn compare_versions(
 }
 
 /// POST /api/posts/{slug}/restore/{version} - Restore a post to a previous version
+#[utoipa::path(
+    post,
+    path = "/api/posts/{slug}/restore/{version}",
+    tag = "versions",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        ("version" = i32, Path, description = "Version number")
+    ),
+    request_body = RestoreVersionRequest,
+    responses(
+        (status = 200, description = "Post restored as a new version", body = RestoreVersionResponse),
+        (status = 400, description = "Invalid version", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key")
+    ),
+    security(("api_key" = []))
+)]
 pub async fn restore_version(
     Path((slug, target_version)): Path<(String, i32)>,
     State(state): State<VersionState>,
@@ -193,6 +259,9 @@ pub async fn restore_version(
                 Json(ErrorResponse::internal_error("Failed to restore version")),
             )
         })?;
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
 
     let response = RestoreVersionResponse {
         success: true,
@@ -203,6 +272,170 @@ pub async fn restore_version(
     Ok(Json(response))
 }
 
+/// POST /api/posts/{slug}/publish-version/{version} - Pin the version readers see
+///
+/// Later edits keep accumulating as drafts until the pin is advanced again.
+/// Pinning the latest version saves a snapshot of it first.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{slug}/publish-version/{version}",
+    tag = "versions",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        ("version" = i32, Path, description = "Version number")
+    ),
+    responses(
+        (status = 200, description = "Version pinned as the published page", body = PublishVersionResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 404, description = "Post or version not found", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+pub async fn publish_version(
+    Path((slug, version)): Path<(String, i32)>,
+    State(state): State<VersionState>,
+) -> Result<Json<PublishVersionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Publishing version {} of post {}", version, slug);
+
+    let internal_error = |e: anyhow::Error| {
+        error!("Failed to publish version {} of {}: {}", version, slug, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to publish version")),
+        )
+    };
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Post with slug '{}' not found",
+                    slug
+                ))),
+            )
+        })?;
+
+    let stored = state
+        .version_service
+        .get_version(post.id, version)
+        .await
+        .map_err(internal_error)?;
+    if stored.is_none() {
+        if version != post.version {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "Version {} of post '{}' not found",
+                    version, slug
+                ))),
+            ));
+        }
+        // The page keeps rendering the snapshot after the post moves on
+        state
+            .version_service
+            .create_version(&post, Some(format!("Published version {}", version)))
+            .await
+            .map_err(internal_error)?;
+    }
+
+    state
+        .database
+        .set_post_published_version(post.id, Some(version))
+        .await
+        .map_err(internal_error)?;
+    if let Err(e) = state.cache.invalidate_post(&slug).await {
+        warn!("Failed to invalidate cache for {}: {}", slug, e);
+    }
+
+    Ok(Json(PublishVersionResponse {
+        success: true,
+        message: format!("Version {} is now published", version),
+        published_version: version,
+        latest_version: post.version,
+    }))
+}
+
+/// GET /api/posts/{slug}/versions/export - Version history as JSON Lines, oldest first
+pub async fn export_versions(
+    Path(slug): Path<String>,
+    State(state): State<VersionState>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Exporting version history of post: {}", slug);
+
+    let post_id = get_post_id_by_slug(&state.database, &slug).await?;
+
+    let jsonl = state
+        .version_service
+        .export_versions(post_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to export version history of post {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to export version history",
+                )),
+            )
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.versions.jsonl\"", slug),
+            ),
+        ],
+        jsonl,
+    )
+        .into_response())
+}
+
+/// POST /api/posts/{slug}/versions/import - Restore exported version history
+///
+/// Takes the JSON Lines written by the export (or the GitHub mirror) as the
+/// request body; versions the post already has are left alone.
+pub async fn import_versions(
+    Path(slug): Path<String>,
+    State(state): State<VersionState>,
+    body: String,
+) -> Result<Json<VersionImportResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!("API: Importing version history into post: {}", slug);
+
+    let post_id = get_post_id_by_slug(&state.database, &slug).await?;
+
+    let versions = parse_versions_jsonl(&body).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(format!("{:#}", e))),
+        )
+    })?;
+
+    let report = state
+        .version_service
+        .import_versions(post_id, &versions)
+        .await
+        .map_err(|e| {
+            error!("Failed to import version history of post {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to import version history",
+                )),
+            )
+        })?;
+
+    Ok(Json(VersionImportResponse {
+        success: true,
+        data: report,
+    }))
+}
+
 /// POST /api/posts/{slug}/versions/cleanup - Clean up old versions
 pub async fn cleanup_old_versions(
     Path(slug): Path<String>,
@@ -249,3 +482,130 @@ pub async fn cleanup_old_versions(
 
     Ok(Json(response))
 }
+
+/// POST /api/admin/versions/cleanup - Apply the retention policy to every post
+pub async fn cleanup_all_versions(
+    Query(query): Query<RetentionQuery>,
+    State(state): State<VersionState>,
+) -> Result<Json<VersionCleanupResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let policy = if query.keep_versions.is_some() || query.max_age_days.is_some() {
+        VersionRetentionPolicy {
+            keep_versions: query.keep_versions,
+            max_age_days: query.max_age_days,
+        }
+    } else {
+        state.retention
+    };
+    info!("API: Cleaning up versions of all posts ({:?})", policy);
+
+    if !policy.is_enabled() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(
+                "No retention policy configured; pass keep_versions or max_age_days",
+            )),
+        ));
+    }
+    policy.validate().map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::bad_request(message)),
+        )
+    })?;
+
+    let report = state
+        .version_service
+        .apply_retention(&policy)
+        .await
+        .map_err(|e| {
+            error!("Failed to clean up versions: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to clean up versions")),
+            )
+        })?;
+
+    Ok(Json(VersionCleanupResponse {
+        success: true,
+        data: report,
+    }))
+}
+
+/// GET /api/posts/{slug}/changelog - Public changelog of a post that opted in
+pub async fn get_post_changelog(
+    Path(slug): Path<String>,
+    State(state): State<VersionState>,
+) -> Result<Json<PostChangelogResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("API: Getting changelog for post: {}", slug);
+
+    let post = state
+        .database
+        .get_post_by_slug(&slug)
+        .await
+        .map_err(|e| {
+            error!("Database error when getting post by slug {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error("Failed to get post")),
+            )
+        })?
+        // Readers only see the changelog where the post page would show it
+        .filter(|post| {
+            post.published
+                && post.show_changelog
+                && post.visibility != PostVisibility::Private
+                && !post.is_password_protected()
+        })
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::not_found(format!(
+                    "No changelog for post '{}'",
+                    slug
+                ))),
+            )
+        })?;
+
+    let changelog = state.version_service.changelog(&post).await.map_err(|e| {
+        error!("Failed to build changelog for post {}: {}", slug, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal_error("Failed to get changelog")),
+        )
+    })?;
+
+    Ok(Json(PostChangelogResponse {
+        success: true,
+        data: changelog,
+    }))
+}
+
+/// PUT /api/posts/{slug}/changelog - Show or hide the changelog on the post page
+pub async fn set_post_changelog(
+    Path(slug): Path<String>,
+    State(state): State<VersionState>,
+    Json(request): Json<SetChangelogRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        "API: Setting changelog of post {} to {}",
+        slug, request.show_changelog
+    );
+
+    let post_id = get_post_id_by_slug(&state.database, &slug).await?;
+
+    state
+        .database
+        .set_post_show_changelog(post_id, request.show_changelog)
+        .await
+        .map_err(|e| {
+            error!("Failed to update changelog flag of post {}: {}", slug, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::internal_error(
+                    "Failed to update changelog setting",
+                )),
+            )
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
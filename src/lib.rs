@@ -1,5 +1,7 @@
 // Tobelog library crate - Personal blog system with Dropbox integration
 
+#[cfg(feature = "client")]
+pub mod client;
 pub mod config;
 pub mod handlers;
 pub mod models;
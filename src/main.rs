@@ -1,15 +1,25 @@
+use anyhow::Context;
 use axum::{
-    extract::{Path, State},
+    extract::{DefaultBodyLimit, Path, State},
     http::StatusCode,
     middleware::{from_fn, from_fn_with_state},
     response::Json,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use tower::ServiceBuilder;
-use tower_http::{cors::CorsLayer, services::ServeDir};
+use tower_http::{
+    compression::{
+        predicate::{DefaultPredicate, NotForContentType, Predicate},
+        CompressionLayer,
+    },
+    cors::CorsLayer,
+    services::ServeDir,
+};
 use tracing::{info, warn, Level};
 use tracing_subscriber;
 
@@ -19,70 +29,246 @@ mod middleware;
 mod models;
 mod services;
 
-use handlers::{admin, api, performance, posts, theme, version};
+use handlers::{
+    admin, announcement, api, assets, config_reload, cross_post, digest, feed, link_check,
+    maintenance, notifications, og_image, openapi, pdf, performance, podcast, posts, site_files,
+    storage_migration, theme, totp, version,
+};
+use middleware::error_pages::ErrorPageState;
+use middleware::ip_filter::IpFilter;
+use middleware::maintenance::MaintenanceState;
+use middleware::normalize::{NormalizeOptions, UrlNormalization};
+use middleware::response_cache::ResponseCache;
+use models::{
+    ImportQualityGates, MediaConstraints, Post, PostFilters, PostVisibility, TagNormalization,
+    VersionRetentionPolicy,
+};
 use services::{
-    BlogStorageService, CacheService, DatabaseService, DropboxClient, LLMImportService,
-    MarkdownService, MediaService, TemplateService, ThemeService, VersionService,
+    dropbox_client, open_storage, ActivityService, AdminSessionService, AltTextConfig,
+    AltTextService, AssetService, BlogStorageService, CacheService, CalendarService, CaptchaConfig,
+    CaptchaService, CircuitOpen, ConfigReloader, CrossPostConfig, CrossPostService,
+    DatabasePoolConfig, DatabaseService, DigestService, DropboxClient, EventBus, FeedService,
+    GitHubMirrorConfig, GitHubMirrorService, ImportJobService, JobQueue, JobQueueConfig,
+    LLMImportService, LinkCheckService, MaintenanceService, MarkdownService, MediaService,
+    NotificationConfig, NotificationService, OgImageConfig, OgImageService, OtlpConfig, OtlpLayer,
+    PdfConfig, PdfService, PodcastConfig, PodcastService, PostPasswordService, ProofreadConfig,
+    ProofreadService, ScheduledPublishService, SiteFilesConfig, SiteFilesService, SlowLog,
+    SlowLogConfig, SlowQueryLayer, StorageBackend, StorageKind, StorageMigrationService,
+    StorageQuotaService, StructuredDataService, SummaryConfig, SummaryService, SyncConflictService,
+    TemplateService, ThemePreviewService, ThemeService, TotpService, TrustedProxies,
+    VersionService,
 };
 
 #[derive(Clone)]
 struct AppState {
-    dropbox_client: Arc<DropboxClient>,
+    /// Set when Dropbox is the storage backend
+    dropbox_client: Option<Arc<DropboxClient>>,
+    storage_quota: Option<StorageQuotaService>,
     blog_storage: Arc<BlogStorageService>,
-    #[allow(dead_code)]
     database: Arc<DatabaseService>,
     #[allow(dead_code)]
     markdown: Arc<MarkdownService>,
-    #[allow(dead_code)]
     config: Arc<config::Config>,
     #[allow(dead_code)]
     cache: Arc<CacheService>,
 }
 
+/// Log to stdout and, when an OTLP endpoint is configured, export traces
+fn init_tracing(config: &config::Config, slow_log: &SlowLog) {
+    use tracing_subscriber::filter::{LevelFilter, Targets};
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::Layer;
+
+    let otlp = config.otlp_endpoint.clone().map(|endpoint| {
+        OtlpLayer::new(OtlpConfig {
+            endpoint,
+            service_name: config.otlp_service_name.clone(),
+        })
+        // sqlx logs every statement at debug level; those events become DB spans
+        .with_filter(
+            Targets::new()
+                .with_default(Level::INFO)
+                .with_target("sqlx::query", Level::DEBUG),
+        )
+    });
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO))
+        .with(otlp)
+        // Sees the slow statements sqlx reports at warn level
+        .with(SlowQueryLayer::new(slow_log.clone()).with_filter(LevelFilter::INFO))
+        .init();
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt().with_max_level(Level::INFO).init();
-
-    dotenv::dotenv().ok();
+    // Variables set before `.env` is read keep precedence over it on reload
+    let process_env: HashSet<String> = std::env::vars_os()
+        .filter_map(|(name, _)| name.into_string().ok())
+        .collect();
+    let env_file = dotenv::dotenv().ok();
 
     let config = config::Config::from_env()?;
+    let slow_log = SlowLog::new(SlowLogConfig {
+        query_threshold: std::time::Duration::from_millis(config.slow_query_threshold_ms),
+        request_threshold: std::time::Duration::from_millis(config.slow_request_threshold_ms),
+        capacity: config.slow_log_size,
+    });
+    init_tracing(&config, &slow_log);
     info!("Configuration loaded successfully");
 
-    // Initialize Dropbox client
-    let dropbox_client = Arc::new(DropboxClient::new(config.dropbox_access_token.clone()));
-    info!("Dropbox client initialized");
+    // Initialize the storage backend holding posts, media and theme files
+    let (storage, dropbox_client) = build_storage(&config)?;
+    info!("Storage backend initialized: {}", storage.name());
 
     // Initialize blog storage service
-    let blog_storage = Arc::new(BlogStorageService::new(dropbox_client.clone()));
+    let blog_storage = Arc::new(BlogStorageService::new(storage.clone()));
     info!("Blog storage service initialized");
 
     // Initialize database service
-    let database = Arc::new(DatabaseService::new(&config.database_url).await?);
+    let pool_config = DatabasePoolConfig {
+        max_connections: config.database_max_connections,
+        busy_timeout: std::time::Duration::from_millis(config.database_busy_timeout_ms),
+        journal_mode: config.database_journal_mode.clone(),
+        synchronous: config.database_synchronous.clone(),
+        read_max_connections: config.database_read_pool_size,
+        read_url: config.database_read_url.clone(),
+        slow_statement_threshold: slow_log.query_threshold(),
+    };
+    let tag_normalization = TagNormalization {
+        lowercase_tags: config.tag_lowercase,
+        lowercase_categories: config.category_lowercase,
+    };
+    let database = Arc::new(
+        DatabaseService::with_pool_config(&config.database_url, &pool_config)
+            .await?
+            .with_tag_normalization(tag_normalization),
+    );
     info!("Database service initialized");
 
     // Initialize markdown service
     let markdown = Arc::new(MarkdownService::new());
     info!("Markdown service initialized");
 
+    // Build fingerprinted CSS/JS assets
+    let asset_service = AssetService::build("static")?;
+    info!("Asset pipeline initialized");
+
     // Initialize template service with theme from config
-    let templates = Arc::new(TemplateService::new_with_theme(&config.template_theme)?);
-    info!("Template service initialized with theme: {}", config.template_theme);
+    let templates = Arc::new(
+        TemplateService::new_with_theme(&config.template_theme)?.with_assets(asset_service.clone()),
+    );
+    info!(
+        "Template service initialized with theme: {}",
+        config.template_theme
+    );
 
-    // Initialize LLM import service
-    let llm_import = Arc::new(LLMImportService::new(
-        (*markdown).clone(),
-        (*database).clone(),
-    ));
+    // Initialize LLM import service (with optional proofreading step)
+    let mut llm_import_service = LLMImportService::new((*markdown).clone(), (*database).clone());
+    if config.proofread_enabled {
+        llm_import_service =
+            llm_import_service.with_proofreader(ProofreadService::new(ProofreadConfig {
+                textlint_url: config.textlint_url.clone(),
+                ..Default::default()
+            }));
+    }
+    let llm_import = Arc::new(llm_import_service.with_quality_gates(ImportQualityGates {
+        min_word_count: config.import_min_word_count,
+        max_similarity: config.import_max_similarity,
+        required_metadata: config.import_required_metadata.clone(),
+    }));
     info!("LLM import service initialized");
 
-    // Initialize media service
-    let media = Arc::new(MediaService::new(
-        dropbox_client.clone(),
-        blog_storage.clone(),
-        (*database).clone(),
-    ));
+    // Initialize admin live event channel
+    let events = EventBus::new();
+
+    // Watch the Dropbox quota when Dropbox holds the media
+    let storage_quota = dropbox_client.as_ref().map(|client| {
+        StorageQuotaService::new(
+            client.clone(),
+            events.clone(),
+            config.dropbox_quota_warning_percent,
+        )
+    });
+
+    // Initialize media service (with optional alt text suggestions and quota checks)
+    let mut media_service =
+        MediaService::new(storage.clone(), blog_storage.clone(), (*database).clone())
+            .with_constraints(MediaConstraints {
+                max_file_size: config.media_upload_limit_bytes as u64,
+                allow_svg: config.media_allow_svg,
+                ..Default::default()
+            });
+    if let Some(quota) = &storage_quota {
+        media_service = media_service.with_storage_quota(quota.clone());
+    }
+    if let Some(api_url) = &config.alt_text_api_url {
+        media_service =
+            media_service.with_alt_text_suggestions(AltTextService::new(AltTextConfig {
+                api_url: api_url.clone(),
+                api_key: config.alt_text_api_key.clone(),
+                model: config.alt_text_model.clone(),
+                language: config.alt_text_language.clone(),
+            }));
+    }
+    let media = Arc::new(media_service);
     info!("Media service initialized");
 
+    // Initialize OG image service
+    let og_images = OgImageService::new(
+        (*database).clone(),
+        OgImageConfig {
+            font_path: config.og_font_path.clone(),
+            cache_dir: config.og_cache_dir.clone().into(),
+        },
+    );
+    info!("OG image service initialized");
+
+    // Initialize PDF export service
+    let pdfs = PdfService::new(
+        (*database).clone(),
+        (*templates).clone(),
+        config.site_url.clone(),
+        PdfConfig {
+            chromium_path: config.pdf_chromium_path.clone(),
+            cache_dir: config.pdf_cache_dir.clone().into(),
+        },
+    );
+    if services::pdf::is_supported() {
+        info!("PDF export enabled ({})", config.pdf_chromium_path);
+    }
+
+    // Initialize podcast feed service
+    let podcast_service = PodcastService::new(
+        (*database).clone(),
+        PodcastConfig {
+            site_url: config.site_url.clone(),
+            category: config.podcast_category.clone(),
+            language: config.podcast_language.clone(),
+            explicit: config.podcast_explicit,
+        },
+    );
+    info!("Podcast service initialized");
+
+    // Initialize tag and series feed service
+    let feed_service = FeedService::new((*database).clone(), config.site_url.clone());
+
+    // Initialize robots.txt / security.txt / humans.txt generation
+    let site_files_config = SiteFilesConfig {
+        site_url: config.site_url.clone(),
+        environment: config.environment.clone(),
+        robots_disallow: config.robots_disallow.clone(),
+        security_contact: config.security_contact.clone(),
+    };
+    if !site_files_config.is_production() {
+        warn!(
+            "Running in {} environment: robots.txt disallows all crawlers",
+            config.environment
+        );
+    }
+    let site_files = SiteFilesService::new((*database).clone(), site_files_config);
+
     // Initialize version service
     let version_service = Arc::new(VersionService::new(
         (*database).clone(),
@@ -90,25 +276,114 @@ async fn main() -> anyhow::Result<()> {
     ));
     info!("Version service initialized");
 
+    // Prune old post versions according to the retention policy
+    let version_retention = VersionRetentionPolicy {
+        keep_versions: config.version_retention_keep,
+        max_age_days: config.version_retention_days,
+    };
+    if let Err(message) = version_retention.validate() {
+        anyhow::bail!("Invalid version retention policy: {}", message);
+    }
+    if version_retention.is_enabled() && config.version_cleanup_interval_hours > 0 {
+        (*version_service).clone().spawn_periodic(
+            version_retention,
+            std::time::Duration::from_secs(config.version_cleanup_interval_hours * 3600),
+        );
+    }
+
     // Initialize theme service
-    let theme_service = Arc::new(ThemeService::new(
-        (*database).clone(),
-        dropbox_client.clone(),
-    ));
+    let theme_service = Arc::new(ThemeService::new((*database).clone(), storage.clone()));
     info!("Theme service initialized");
 
+    // Render UI strings and dates in the configured site locale and timezone
+    match theme_service.get_site_config().await {
+        Ok(site_config) => {
+            templates.set_locale(site_config.locale);
+            templates.set_timezone(site_config.timezone);
+        }
+        Err(e) => warn!("Failed to load site locale, using the default: {}", e),
+    }
+
+    // Initialize link checker and schedule periodic runs
+    let link_checker = LinkCheckService::new((*database).clone(), config.site_url.clone())?;
+    if config.link_check_interval_hours > 0 {
+        let interval = std::time::Duration::from_secs(config.link_check_interval_hours * 3600);
+        link_checker.clone().spawn_periodic(interval);
+    }
+    info!("Link check service initialized");
+
+    // Copy published posts to the configured external platforms
+    let cross_post_service = CrossPostService::new(
+        (*database).clone(),
+        CrossPostConfig {
+            site_url: config.site_url.clone(),
+            qiita_token: config.crosspost_qiita_token.clone(),
+            devto_api_key: config.crosspost_devto_api_key.clone(),
+            zenn_github_token: config.crosspost_zenn_github_token.clone(),
+            zenn_repo: config.crosspost_zenn_repo.clone(),
+            zenn_branch: config.crosspost_zenn_branch.clone(),
+            zenn_username: config.crosspost_zenn_username.clone(),
+        },
+    );
+    cross_post_service.spawn_listener(&events);
+
+    // Commit published posts to a GitHub repository as a second backup
+    if let (Some(token), Some(repo)) = (&config.github_mirror_token, &config.github_mirror_repo) {
+        GitHubMirrorService::new(
+            (*database).clone(),
+            blog_storage.clone(),
+            GitHubMirrorConfig {
+                token: token.clone(),
+                repo: repo.clone(),
+                branch: config.github_mirror_branch.clone(),
+                folder: config.github_mirror_folder.clone(),
+            },
+        )
+        .spawn_listener(&events);
+        info!("GitHub mirror enabled for {}", repo);
+    }
+
+    // Announce published posts on Slack and Discord
+    let notification_service = NotificationService::new(
+        (*database).clone(),
+        NotificationConfig {
+            site_url: config.site_url.clone(),
+            slack_webhook_url: config.notify_slack_webhook_url.clone(),
+            discord_webhook_url: config.notify_discord_webhook_url.clone(),
+            on_publish: config.notify_on_publish,
+            on_scheduled_publish: config.notify_on_scheduled_publish,
+        },
+    );
+    notification_service.spawn_listener(&events);
+
     // Initialize cache service
     let cache_service = Arc::new(CacheService::new());
+    cache_service.invalidate_responses_on_events(&events);
     info!("Cache service initialized");
 
-    // Test Dropbox connection on startup (with warning if it fails)
-    match dropbox_client.test_connection().await {
-        Ok(account_info) => {
-            if let Some(name) = account_info.get("name") {
-                if let Some(display_name) = name.get("display_name") {
-                    info!("✅ Connected to Dropbox account: {}", display_name);
-                }
-            }
+    // Initialize background job queue (workers start once handlers are registered)
+    let job_queue = JobQueue::new(
+        (*database).clone(),
+        JobQueueConfig {
+            workers: config.job_workers,
+            max_attempts: config.job_max_attempts,
+            retry_base_delay: std::time::Duration::from_secs(config.job_retry_delay_secs),
+            ..Default::default()
+        },
+    )
+    .with_events(events.clone());
+
+    // Publish scheduled posts once they are due and expire posts past their expiry
+    if config.scheduled_publish_interval_secs > 0 {
+        ScheduledPublishService::new((*database).clone(), events.clone()).spawn_periodic(
+            std::time::Duration::from_secs(config.scheduled_publish_interval_secs),
+        );
+    }
+
+    // Test the storage connection on startup (with warning if it fails)
+    match storage.check_connection().await {
+        Ok(description) => {
+            info!("✅ Connected to {}", description);
 
             // Initialize blog folder structure
             if let Err(e) = blog_storage.initialize_blog_structure().await {
@@ -116,12 +391,13 @@ async fn main() -> anyhow::Result<()> {
             }
         }
         Err(e) => {
-            warn!("⚠️  Dropbox connection test failed: {}. Server will start but Dropbox features may not work.", e);
+            warn!("⚠️  {} storage connection test failed: {}. Server will start but storage features may not work.", storage.name(), e);
         }
     }
 
     let app_state = AppState {
         dropbox_client,
+        storage_quota: storage_quota.clone(),
         blog_storage: blog_storage.clone(),
         database: database.clone(),
         markdown: markdown.clone(),
@@ -129,69 +405,358 @@ async fn main() -> anyhow::Result<()> {
         cache: cache_service.clone(),
     };
 
+    // CAPTCHA checked on public forms, when a provider is configured
+    let captcha = match &config.captcha_provider {
+        Some(provider) => {
+            let (Some(site_key), Some(secret_key)) =
+                (&config.captcha_site_key, &config.captcha_secret_key)
+            else {
+                anyhow::bail!(
+                    "CAPTCHA_SITE_KEY and CAPTCHA_SECRET_KEY are required with CAPTCHA_PROVIDER"
+                );
+            };
+            info!("CAPTCHA enabled on public forms ({})", provider);
+            Some(CaptchaService::new(CaptchaConfig {
+                provider: provider.parse()?,
+                site_key: site_key.clone(),
+                secret_key: secret_key.clone(),
+            }))
+        }
+        None => None,
+    };
+
     // Create handler states
     let posts_state = posts::AppState {
         database: (*database).clone(),
         markdown: (*markdown).clone(),
         templates: (*templates).clone(),
+        site_url: config.site_url.clone(),
+        blog_storage: blog_storage.clone(),
+        api_key: config.api_key.clone(),
+        post_passwords: PostPasswordService::new(
+            config.post_password_secret.as_deref(),
+            std::time::Duration::from_secs(config.post_password_cookie_ttl_secs),
+        ),
+        versions: (*version_service).clone(),
+        media: (*media).clone(),
+        captcha,
+        structured_data: StructuredDataService::new((*database).clone(), config.site_url.clone()),
+        cache: (*cache_service).clone(),
     };
 
+    let calendar = CalendarService::new((*database).clone());
+
+    let sync_conflicts = SyncConflictService::new(
+        (*database).clone(),
+        blog_storage.clone(),
+        (*markdown).clone(),
+    );
+
     let api_state = api::ApiState {
         database: (*database).clone(),
         llm_import: (*llm_import).clone(),
         markdown: (*markdown).clone(),
         blog_storage: blog_storage,
+        api_key: config.api_key.clone(),
+        dropbox_root: config.dropbox_blog_root.clone(),
+        timezone: templates.site_timezone().clone(),
+        sync_conflicts,
+        max_pinned_posts: config.max_pinned_posts,
+        import_jobs: ImportJobService::new((*llm_import).clone(), job_queue.clone())
+            .with_events(events.clone()),
+        jobs: job_queue.clone(),
+        events: events.clone(),
+        summarizer: config.summary_api_url.as_ref().map(|api_url| {
+            SummaryService::new(SummaryConfig {
+                api_url: api_url.clone(),
+                api_key: config.summary_api_key.clone(),
+                model: config.summary_model.clone(),
+                language: config.summary_language.clone(),
+            })
+        }),
         media: (*media).clone(),
+        calendar: calendar.clone(),
+        cache: (*cache_service).clone(),
     };
 
+    // Authenticator apps list the site by its host name
+    let totp_service = TotpService::new(
+        (*database).clone(),
+        config
+            .site_url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_end_matches('/'),
+    );
+
     let admin_state = admin::AdminState {
         database: (*database).clone(),
         markdown: (*markdown).clone(),
         templates: (*templates).clone(),
         llm_import: (*llm_import).clone(),
+        calendar,
+        activity: ActivityService::new((*database).clone()),
+        events,
+        dropbox_root: config.dropbox_blog_root.clone(),
+        storage_quota,
+        versions: (*version_service).clone(),
+        api_key: config.api_key.clone(),
+        sessions: AdminSessionService::new(
+            (*database).clone(),
+            std::time::Duration::from_secs(config.admin_session_ttl_hours * 3600),
+            std::time::Duration::from_secs(config.admin_session_rotate_minutes * 60),
+        ),
+        totp: totp_service.clone(),
     };
+    // Admin-only data behind GET routes, which `auth_middleware` lets through
+    let admin_api_layer = from_fn_with_state(
+        crate::middleware::AdminApiAuth {
+            api_key: config.api_key.clone(),
+            sessions: admin_state.sessions.clone(),
+        },
+        crate::middleware::admin_api_middleware,
+    );
 
     let version_state = version::VersionState {
         version_service: (*version_service).clone(),
         database: (*database).clone(),
+        cache: (*cache_service).clone(),
+        retention: version_retention,
+    };
+
+    let link_check_state = link_check::LinkCheckState {
+        link_checker,
+        jobs: job_queue.clone(),
+    };
+
+    let cross_post_state = cross_post::CrossPostState {
+        cross_posts: cross_post_service,
+        database: (*database).clone(),
     };
 
+    let digest_state = digest::DigestState {
+        digest: DigestService::new(
+            (*database).clone(),
+            config.site_url.clone(),
+            config.digest_slack_webhook_url.clone(),
+        ),
+        jobs: job_queue.clone(),
+    };
+
+    let storage_migration_state = storage_migration::StorageMigrationState {
+        migrator: StorageMigrationService::new((*database).clone(), config.clone())
+            .with_jobs(job_queue.clone()),
+        jobs: job_queue.clone(),
+    };
+
+    // Register job handlers, then start the queue workers
+    api_state.register_jobs();
+    link_check_state.register_jobs();
+    digest_state.register_jobs();
+    digest_state.digest.spawn_monthly(job_queue.clone());
+    storage_migration_state.register_jobs();
+    job_queue.start().await?;
+    info!("Job queue initialized");
+
     let theme_state = theme::ThemeState {
         theme_service: (*theme_service).clone(),
         database: (*database).clone(),
+        templates: (*templates).clone(),
+        preview: ThemePreviewService::new(config.theme_preview),
     };
+    // Response cache lifetimes, replaced when the config is reloaded
+    let page_cache_ttl = Arc::new(AtomicU64::new(config.response_cache_page_ttl_secs));
+    let feed_cache_ttl = Arc::new(AtomicU64::new(config.response_cache_feed_ttl_secs));
+    let theme_preview = theme_state.preview.clone();
+    if config.theme_preview {
+        info!("Theme preview enabled (?theme=<name> overrides the active theme)");
+    }
+
+    // Show active announcement banners on every page
+    let announcement_state = announcement::AnnouncementState {
+        database: (*database).clone(),
+        templates: (*templates).clone(),
+    };
+    if let Err(e) = announcement_state.refresh_templates().await {
+        warn!("Failed to load announcements: {}", e);
+    }
 
     // Create separate routers for each state type
     let web_pages_router = Router::new()
         .route("/", get(posts::home_page))
-        .route("/posts/:year/:slug", get(posts::post_page))
+        .route(
+            "/posts/:year/:slug",
+            get(posts::post_page).post(posts::unlock_post),
+        )
+        .route("/posts/:year/:slug/lite", get(posts::lite_post_page))
         .route("/category/:category", get(posts::category_page))
         .route("/tag/:tag", get(posts::tag_page))
-        .with_state(posts_state.clone());
+        .route("/search", get(posts::search_page))
+        .route("/tags", get(posts::tag_index_page))
+        .route("/categories", get(posts::category_index_page))
+        .route("/attachments/:id", get(posts::download_attachment))
+        .with_state(posts_state.clone())
+        .layer(from_fn_with_state(
+            theme_state.clone(),
+            crate::middleware::theme_preview_middleware,
+        ))
+        .layer(from_fn_with_state(
+            ResponseCache {
+                cache: (*cache_service).clone(),
+                theme_service: (*theme_service).clone(),
+                ttl_secs: page_cache_ttl.clone(),
+            },
+            crate::middleware::response_cache::response_cache_middleware,
+        ));
 
+    // Drafts and scheduled posts rendered on the public templates (auth required)
+    let staging_router = Router::new()
+        .route("/staging", get(posts::home_page))
+        .route("/staging/posts/:year/:slug", get(posts::post_page))
+        .route("/staging/category/:category", get(posts::category_page))
+        .route("/staging/tag/:tag", get(posts::tag_page))
+        .route("/staging/search", get(posts::search_page))
+        .route("/staging/tags", get(posts::tag_index_page))
+        .route("/staging/categories", get(posts::category_index_page))
+        .with_state(posts_state.clone())
+        .layer(from_fn_with_state(
+            theme_state.clone(),
+            crate::middleware::theme_preview_middleware,
+        ))
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::staging_middleware,
+        ));
+
+    // Media uploads and imports may be larger than the JSON API bodies
+    let upload_limit = DefaultBodyLimit::max(crate::middleware::body_limit::upload_body_limit(
+        config.media_upload_limit_bytes,
+    ));
     let api_router = Router::new()
         // Read operations (no auth required)
         .route("/api/posts", get(api::list_posts_api))
         .route("/api/posts/:slug", get(api::get_post_api))
+        .route("/api/posts/:slug/raw", get(api::get_post_source_api))
+        .route("/api/posts/:slug/outline", get(api::get_post_outline_api))
         .route("/api/blog/stats", get(api::blog_stats_api))
         .route("/api/categories", get(api::list_categories_api))
         .route("/api/tags", get(api::list_tags_api))
         .route("/api/search", get(api::search_posts_api))
+        .route("/api/calendar", get(api::get_calendar_api))
         // CRUD operations (auth required)
         .route("/api/posts", post(api::create_post_api))
         .route("/api/posts/:slug", put(api::update_post_api))
+        .route(
+            "/api/posts/:slug/metadata",
+            patch(api::update_post_metadata_api),
+        )
         .route("/api/posts/:slug", delete(api::delete_post_api))
+        .route("/api/posts/:slug/schedule", put(api::schedule_post_api))
+        .route("/api/posts/:slug/expiry", put(api::set_post_expiry_api))
+        .route("/api/posts/:slug/pin", put(api::pin_post_api))
+        .route(
+            "/api/posts/:slug/visibility",
+            put(api::set_post_visibility_api),
+        )
+        .route("/api/posts/:slug/password", put(api::set_post_password_api))
+        .route(
+            "/api/posts/:slug/regenerate-excerpt",
+            post(api::regenerate_excerpt_api),
+        )
+        // Tag management (auth required)
+        .route("/api/tags/rename", post(api::rename_tag_api))
+        .route("/api/tags/merge", post(api::merge_tags_api))
+        .route("/api/tags/:name", delete(api::delete_tag_api))
+        .route(
+            "/api/admin/tag-aliases",
+            get(api::list_tag_aliases_api).post(api::create_tag_alias_api),
+        )
+        .route(
+            "/api/admin/tag-aliases/:alias",
+            delete(api::delete_tag_alias_api),
+        )
+        .route(
+            "/api/admin/category-descriptions",
+            get(api::list_category_descriptions_api),
+        )
+        .route(
+            "/api/admin/category-descriptions/:category",
+            put(api::set_category_description_api).delete(api::delete_category_description_api),
+        )
         // LLM import operations (auth required)
         .route("/api/import/llm-article", post(api::import_llm_article_api))
-        .route("/api/import/batch", post(api::batch_import_api))
+        .route(
+            "/api/import/batch",
+            post(api::batch_import_api).layer(upload_limit),
+        )
+        .route("/api/import/jobs/:id", get(api::import_job_api))
+        .route(
+            "/api/import/jobs/:id/events",
+            get(api::import_job_events_api),
+        )
         .route("/api/posts/:slug/save", post(api::save_llm_article_api))
         // Media operations (auth required)
-        .route("/api/media/upload", post(api::upload_media_api))
+        .route(
+            "/api/media/upload",
+            post(api::upload_media_api).layer(upload_limit),
+        )
+        .route(
+            "/api/media/paste",
+            post(api::paste_media_api).layer(upload_limit),
+        )
         .route("/api/media", get(api::list_media_api))
         .route("/api/media/:id", delete(api::delete_media_api))
+        .route("/api/media/:id", patch(api::update_media_api))
+        .route(
+            "/api/posts/:slug/featured-image",
+            post(api::set_featured_image_api).layer(upload_limit),
+        )
+        .route("/api/posts/:slug/audio", put(api::attach_audio_api))
+        .route("/api/posts/:slug/audio", delete(api::remove_audio_api))
+        .route(
+            "/api/posts/:slug/attachments",
+            get(api::list_attachments_api),
+        )
+        .route(
+            "/api/posts/:slug/attachments/:id",
+            delete(api::remove_attachment_api),
+        )
         // Sync operations (auth required)
         .route("/api/sync/dropbox", post(api::sync_dropbox_api))
-        .route("/api/import/markdown", post(api::import_markdown_api))
+        .route(
+            "/api/import/markdown",
+            post(api::import_markdown_api).layer(upload_limit),
+        )
+        // Diverged posts include drafts, so GET needs the login as well
+        .route(
+            "/api/sync/conflicts",
+            get(api::list_sync_conflicts_api).layer(admin_api_layer.clone()),
+        )
+        .route(
+            "/api/sync/conflicts/:slug/resolve",
+            post(api::resolve_sync_conflict_api),
+        )
+        // Search index maintenance (auth required)
+        .route("/api/admin/reindex", post(api::reindex_search_api))
+        .route("/api/admin/stats/rebuild", post(api::rebuild_stats_api))
+        // Job payloads carry whole articles, so GET needs the login as well
+        .route(
+            "/api/admin/jobs",
+            get(api::list_jobs_api).layer(admin_api_layer.clone()),
+        )
+        // Lists draft and private titles, so GET needs the login as well
+        .route(
+            "/api/admin/command-palette",
+            get(api::command_palette_api).layer(admin_api_layer.clone()),
+        )
+        .route(
+            "/api/admin/jobs/:id",
+            get(api::get_job_api).layer(admin_api_layer.clone()),
+        )
+        .route(
+            "/api/admin/regenerate-excerpts",
+            post(api::bulk_regenerate_excerpts_api),
+        )
         .with_state(api_state.clone())
         .layer(from_fn_with_state(
             config.clone(),
@@ -203,13 +768,35 @@ async fn main() -> anyhow::Result<()> {
         .route("/admin/posts", get(admin::posts_list))
         .route("/admin/new", get(admin::new_post_form))
         .route("/admin/edit/:slug", get(admin::edit_post_form))
+        .route("/admin/calendar", get(admin::admin_calendar_page))
+        .route("/admin/events", get(admin::admin_events))
+        .route("/admin/media", get(admin::admin_media_page))
+        .route("/admin/security", get(admin::admin_security_page))
         // LLM import admin routes
         .route(
             "/admin/import",
             get(admin::admin_import_page).post(admin::admin_process_import),
         )
         .route("/admin/posts/:slug/edit", get(admin::admin_edit_post_page))
-        .with_state(admin_state);
+        .route(
+            "/admin/posts/:slug/versions",
+            get(admin::admin_post_versions_page),
+        )
+        .route(
+            "/admin/posts/:slug/versions/:version",
+            get(admin::admin_post_version_page),
+        )
+        .route(
+            "/admin/login",
+            get(admin::admin_login_page).post(admin::admin_login),
+        )
+        .route("/admin/logout", post(admin::admin_logout))
+        .with_state(admin_state.clone())
+        // Admin pages need a login session (or the API key)
+        .layer(from_fn_with_state(
+            admin_state,
+            crate::middleware::admin_session_middleware,
+        ));
 
     let version_router = Router::new()
         // Version management API endpoints (auth required)
@@ -229,10 +816,31 @@ async fn main() -> anyhow::Result<()> {
             "/api/posts/:slug/restore/:version",
             post(version::restore_version),
         )
+        .route(
+            "/api/posts/:slug/publish-version/:version",
+            post(version::publish_version),
+        )
+        .route(
+            "/api/posts/:slug/versions/export",
+            get(version::export_versions),
+        )
+        .route(
+            "/api/posts/:slug/versions/import",
+            post(version::import_versions).layer(upload_limit),
+        )
         .route(
             "/api/posts/:slug/versions/cleanup",
             post(version::cleanup_old_versions),
         )
+        .route(
+            "/api/admin/versions/cleanup",
+            post(version::cleanup_all_versions),
+        )
+        // Reader-facing changelog (public GET, auth required to toggle)
+        .route(
+            "/api/posts/:slug/changelog",
+            get(version::get_post_changelog).put(version::set_post_changelog),
+        )
         .with_state(version_state)
         .layer(from_fn_with_state(
             config.clone(),
@@ -240,6 +848,14 @@ async fn main() -> anyhow::Result<()> {
         ));
 
     let theme_router = Router::new()
+        // Compiled stylesheet of the active theme (public, GET bypasses auth)
+        .route(
+            "/theme.css",
+            get(theme::serve_active_theme_css).layer(from_fn_with_state(
+                theme_state.clone(),
+                crate::middleware::theme_preview_middleware,
+            )),
+        )
         // Theme management API endpoints (auth required)
         .route("/api/themes", get(theme::list_themes))
         .route("/api/themes", post(theme::create_theme))
@@ -255,15 +871,133 @@ async fn main() -> anyhow::Result<()> {
         // Site configuration endpoints (auth required)
         .route("/api/site/config", get(theme::get_site_config))
         .route("/api/site/config", put(theme::update_site_config))
+        // Settings transfer between environments (auth required)
+        .route(
+            "/api/admin/settings/export",
+            get(theme::export_settings).layer(admin_api_layer.clone()),
+        )
+        .route(
+            "/api/admin/settings/import",
+            post(theme::import_settings).layer(upload_limit),
+        )
+        // Per-browser theme preview cookie (auth required)
+        .route("/api/admin/theme-preview", post(theme::set_theme_preview))
         .with_state(theme_state)
         .layer(from_fn_with_state(
             config.clone(),
             crate::middleware::auth_middleware,
         ));
 
+    let announcement_router = Router::new()
+        // Site-wide announcement banners (auth required)
+        .route(
+            "/api/admin/announcements",
+            get(announcement::list_announcements).post(announcement::create_announcement),
+        )
+        .route(
+            "/api/admin/announcements/:id",
+            put(announcement::update_announcement).delete(announcement::delete_announcement),
+        )
+        .with_state(announcement_state)
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let totp_router = Router::new()
+        // Two-factor enrollment for the admin login (auth required)
+        .route("/api/admin/totp/enroll", post(totp::begin_enrollment))
+        .route("/api/admin/totp/confirm", post(totp::confirm_enrollment))
+        .route(
+            "/api/admin/totp/recovery-codes",
+            post(totp::regenerate_recovery_codes),
+        )
+        .route("/api/admin/totp/disable", post(totp::disable))
+        .with_state(totp::TotpState { totp: totp_service })
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let link_check_router = Router::new()
+        // Broken link report (auth required for triggering a run)
+        .route(
+            "/api/admin/links/broken",
+            get(link_check::broken_links_report),
+        )
+        .route("/api/admin/links/check", post(link_check::run_link_check))
+        .with_state(link_check_state)
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let cross_post_router = Router::new()
+        // Cross-post status and manual runs (auth required)
+        .route(
+            "/api/posts/:slug/cross-posts",
+            get(cross_post::list_cross_posts).post(cross_post::run_cross_post),
+        )
+        .with_state(cross_post_state)
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let maintenance_service =
+        MaintenanceService::new(config.maintenance_mode, config.maintenance_retry_after_secs);
+    let maintenance_router = Router::new()
+        // Maintenance mode switch (auth required)
+        .route(
+            "/api/admin/maintenance",
+            get(maintenance::get_maintenance).put(maintenance::update_maintenance),
+        )
+        .with_state(maintenance_service.clone())
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let notifications_router = Router::new()
+        // Test message to the notification webhooks (auth required)
+        .route(
+            "/api/admin/notifications/test",
+            post(notifications::send_test_notification),
+        )
+        .with_state(notifications::NotificationState {
+            notifications: notification_service,
+        })
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let digest_router = Router::new()
+        // Monthly digest preview and delivery (auth required)
+        .route("/api/admin/digest", get(digest::preview_digest))
+        .route("/api/admin/digest/send", post(digest::send_digest))
+        .with_state(digest_state)
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
+    let storage_migration_router = Router::new()
+        .route(
+            "/api/admin/storage/migrate",
+            post(storage_migration::start_storage_migration),
+        )
+        .with_state(storage_migration_state)
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
     // Performance monitoring router
     let performance_state = performance::PerformanceState {
         cache: (*cache_service).clone(),
+        load_test_report: config.load_test_report.clone().into(),
+        slow_log: slow_log.clone(),
     };
 
     let performance_router = Router::new()
@@ -280,12 +1014,22 @@ async fn main() -> anyhow::Result<()> {
             "/api/performance/health",
             get(performance::performance_health_check),
         )
+        .route("/api/performance/slowlog", get(performance::get_slow_log))
         .with_state(performance_state)
         .layer(from_fn_with_state(
             config.clone(),
             crate::middleware::auth_middleware,
         ));
 
+    // Cache purges check their own scoped token instead of the API key
+    let cache_purge_router = Router::new()
+        .route("/api/cache/purge", post(performance::purge_cache))
+        .with_state(performance::CachePurgeState {
+            cache: (*cache_service).clone(),
+            api_key: config.api_key.clone(),
+            purge_token: config.cache_purge_token.clone(),
+        });
+
     let legacy_router = Router::new()
         .route("/health", get(health_handler))
         .route("/api/dropbox/status", get(dropbox_status_handler))
@@ -298,15 +1042,162 @@ async fn main() -> anyhow::Result<()> {
         .route("/media/*path", get(api::serve_media_file))
         .with_state(api_state);
 
+    let assets_router = Router::new()
+        .route("/assets/*path", get(assets::serve_asset))
+        .with_state(asset_service);
+
+    let og_image_router = Router::new()
+        .route("/og/:file", get(og_image::serve_og_image))
+        .with_state(og_images);
+
+    let pdf_router = Router::new()
+        .route("/api/posts/:slug/pdf", get(pdf::get_post_pdf_api))
+        .with_state(pdfs);
+
+    let podcast_router = Router::new()
+        .route("/podcast.xml", get(podcast::serve_podcast_feed))
+        .with_state(podcast_service)
+        .layer(from_fn_with_state(
+            ResponseCache {
+                cache: (*cache_service).clone(),
+                theme_service: (*theme_service).clone(),
+                ttl_secs: feed_cache_ttl.clone(),
+            },
+            crate::middleware::response_cache::response_cache_middleware,
+        ));
+
+    let feed_router = Router::new()
+        .route("/tag/:tag/feed.xml", get(feed::serve_tag_feed))
+        .route("/series/:slug/feed.xml", get(feed::serve_series_feed))
+        .with_state(feed_service)
+        .layer(from_fn_with_state(
+            ResponseCache {
+                cache: (*cache_service).clone(),
+                theme_service: (*theme_service).clone(),
+                ttl_secs: feed_cache_ttl.clone(),
+            },
+            crate::middleware::response_cache::response_cache_middleware,
+        ));
+
+    let site_files_router = Router::new()
+        .route("/robots.txt", get(site_files::serve_robots_txt))
+        .route("/humans.txt", get(site_files::serve_humans_txt))
+        .route(
+            "/.well-known/security.txt",
+            get(site_files::serve_security_txt),
+        )
+        .with_state(site_files);
+
+    let mut openapi_router = Router::new().route("/api/openapi.json", get(openapi::openapi_json));
+    if config.api_docs_ui {
+        openapi_router = openapi_router.route("/api/docs", get(openapi::swagger_ui));
+    }
+
+    let url_normalization = UrlNormalization::new()
+        .group("/", NormalizeOptions::slashes())
+        // Frontmatter slugs may be uppercase and are looked up exactly, so no
+        // group lowercases its paths
+        .group("/posts", NormalizeOptions::slashes())
+        .group("/category", NormalizeOptions::slashes())
+        .group("/tag", NormalizeOptions::slashes())
+        .group("/staging", NormalizeOptions::slashes())
+        .group("/admin", NormalizeOptions::slashes());
+
+    let trusted_proxies =
+        TrustedProxies::new(&config.trusted_proxies).context("Invalid TRUSTED_PROXIES")?;
+    let ip_filter = IpFilter::new(&config.admin_ip_allow, &config.admin_ip_deny)
+        .context("Invalid ADMIN_IP_ALLOW or ADMIN_IP_DENY")?;
+    if ip_filter.is_enabled() {
+        info!("Admin IP filter enabled");
+    }
+    if config.demo_mode {
+        info!("Demo mode enabled, changes are refused");
+    }
+    let trusted_proxies = Arc::new(RwLock::new(trusted_proxies));
+    let ip_filter = Arc::new(RwLock::new(ip_filter));
+    let demo_mode = Arc::new(AtomicBool::new(config.demo_mode));
+    if config.maintenance_mode {
+        info!("Maintenance mode enabled, public pages answer 503");
+    }
+    if let Some(endpoint) = &config.otlp_endpoint {
+        info!("Exporting request traces to {}", endpoint);
+    }
+
+    // Apply runtime settings from a reloaded config (SIGHUP or POST /api/admin/reload)
+    let reloader = ConfigReloader::new(env_file, process_env);
+    reloader.on_reload({
+        let trusted_proxies = trusted_proxies.clone();
+        let ip_filter = ip_filter.clone();
+        move |config| {
+            let proxies =
+                TrustedProxies::new(&config.trusted_proxies).context("Invalid TRUSTED_PROXIES")?;
+            let filter = IpFilter::new(&config.admin_ip_allow, &config.admin_ip_deny)
+                .context("Invalid ADMIN_IP_ALLOW or ADMIN_IP_DENY")?;
+            *trusted_proxies
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = proxies;
+            *ip_filter
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = filter;
+            Ok(())
+        }
+    });
+    reloader.on_reload({
+        let demo_mode = demo_mode.clone();
+        let maintenance = maintenance_service.clone();
+        move |config| {
+            demo_mode.store(config.demo_mode, Ordering::Relaxed);
+            theme_preview.set_enabled(config.theme_preview);
+            page_cache_ttl.store(config.response_cache_page_ttl_secs, Ordering::Relaxed);
+            feed_cache_ttl.store(config.response_cache_feed_ttl_secs, Ordering::Relaxed);
+            maintenance.apply_config(config.maintenance_mode, config.maintenance_retry_after_secs);
+            Ok(())
+        }
+    });
+    #[cfg(unix)]
+    reloader.clone().spawn_sighup_listener();
+
+    let reload_router = Router::new()
+        // Reload `.env` without a restart (auth required)
+        .route("/api/admin/reload", post(config_reload::reload_config))
+        .with_state(reloader)
+        .layer(from_fn_with_state(
+            config.clone(),
+            crate::middleware::auth_middleware,
+        ));
+
     let app = Router::new()
         .merge(web_pages_router)
+        .merge(staging_router)
         .merge(api_router)
         .merge(admin_router)
         .merge(version_router)
         .merge(theme_router)
+        .merge(announcement_router)
+        .merge(totp_router)
+        .merge(link_check_router)
+        .merge(cross_post_router)
+        .merge(digest_router)
+        .merge(notifications_router)
+        .merge(maintenance_router)
+        .merge(reload_router)
+        .merge(storage_migration_router)
         .merge(performance_router)
+        .merge(cache_purge_router)
         .merge(legacy_router)
         .merge(media_router)
+        .merge(assets_router)
+        .merge(og_image_router)
+        .merge(pdf_router)
+        .merge(podcast_router)
+        .merge(feed_router)
+        .merge(site_files_router)
+        .merge(openapi_router)
+        // Handlers slower than the threshold go to the slow log, grouped by route
+        .route_layer(from_fn_with_state(
+            slow_log.clone(),
+            crate::middleware::slow_log::slow_request_middleware,
+        ))
         // Static file serving
         .nest_service("/static", ServeDir::new("static"))
         // Performance and caching middleware
@@ -315,14 +1206,81 @@ async fn main() -> anyhow::Result<()> {
         .layer(from_fn(
             crate::middleware::performance::cache_headers_middleware,
         ))
+        // Themed 404 and error pages for browsers (API routes keep JSON errors)
+        .layer(from_fn_with_state(
+            ErrorPageState {
+                templates: (*templates).clone(),
+                database: (*database).clone(),
+            },
+            crate::middleware::error_pages::error_page_middleware,
+        ))
+        // Cap request bodies; the media upload routes set a larger limit of their own
+        .layer(DefaultBodyLimit::max(config.api_body_limit_bytes))
+        .layer(from_fn(
+            crate::middleware::body_limit::payload_too_large_middleware,
+        ))
+        // Refuse changes on read-only demo instances
+        .layer(from_fn_with_state(
+            demo_mode.clone(),
+            crate::middleware::demo_mode::demo_mode_middleware,
+        ))
+        // Answer public requests with 503 during maintenance; outside the error
+        // pages so the maintenance page is not replaced by the generic error page
+        .layer(from_fn_with_state(
+            MaintenanceState {
+                maintenance: maintenance_service.clone(),
+                templates: (*templates).clone(),
+                database: (*database).clone(),
+            },
+            crate::middleware::maintenance::maintenance_middleware,
+        ))
+        // Keep the admin panel and mutating API calls to the configured networks
+        .layer(from_fn_with_state(
+            ip_filter.clone(),
+            crate::middleware::ip_filter::ip_filter_middleware,
+        ))
+        // Redirect duplicate-content URLs of the HTML pages to their canonical form
+        .layer(from_fn_with_state(
+            Arc::new(url_normalization),
+            crate::middleware::normalize::normalize_url_middleware,
+        ))
+        // Compress text responses; media types are already compressed
+        .layer(
+            CompressionLayer::new()
+                .gzip(config.compression_gzip)
+                .br(config.compression_brotli)
+                .compress_when(
+                    DefaultPredicate::new()
+                        .and(NotForContentType::const_new("audio/"))
+                        .and(NotForContentType::const_new("video/"))
+                        .and(NotForContentType::const_new("font/woff"))
+                        .and(NotForContentType::const_new("application/zip"))
+                        .and(NotForContentType::const_new("application/gzip"))
+                        .and(NotForContentType::const_new("application/pdf")),
+                ),
+        )
         // CORS middleware
-        .layer(ServiceBuilder::new().layer(CorsLayer::permissive())); // TODO: Configure restrictive CORS policy for production
+        .layer(ServiceBuilder::new().layer(CorsLayer::permissive())) // TODO: Configure restrictive CORS policy for production
+        // Resolve the real client address, scheme and host behind trusted proxies
+        .layer(from_fn_with_state(
+            trusted_proxies,
+            crate::middleware::client_info::client_info_middleware,
+        ))
+        // Request ID and tracing span around everything else
+        .layer(from_fn(
+            crate::middleware::request_id::request_id_middleware,
+        ));
 
     let addr = format!("{}:{}", config.host, config.port);
     info!("Starting server on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    // The peer address is needed to resolve the client behind trusted proxies
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -333,8 +1291,35 @@ async fn health_handler() -> &'static str {
     "OK"
 }
 
+/// The storage backend, plus the Dropbox client when Dropbox is that backend
+type StorageSetup = (Arc<dyn StorageBackend>, Option<Arc<DropboxClient>>);
+
+/// Build the storage backend selected by `STORAGE_BACKEND`
+fn build_storage(config: &config::Config) -> anyhow::Result<StorageSetup> {
+    match config.storage_backend.parse()? {
+        StorageKind::Dropbox => {
+            let client = Arc::new(dropbox_client(config)?);
+            Ok((client.clone(), Some(client)))
+        }
+        kind => Ok((open_storage(kind, config)?, None)),
+    }
+}
+
 async fn dropbox_status_handler(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
-    match state.dropbox_client.test_connection().await {
+    let Some(dropbox_client) = &state.dropbox_client else {
+        return Ok(Json(json!({
+            "status": "disabled",
+            "message": format!("Dropbox is not the storage backend ({})", state.config.storage_backend)
+        })));
+    };
+
+    let connection = dropbox_client.test_connection().await;
+    let circuit_breaker = dropbox_client.circuit_breaker_status();
+    let quota = match &state.storage_quota {
+        Some(quota) => quota.usage().await.ok(),
+        None => None,
+    };
+    match connection {
         Ok(account_info) => {
             let response = json!({
                 "status": "connected",
@@ -343,20 +1328,49 @@ async fn dropbox_status_handler(State(state): State<AppState>) -> Result<Json<Va
                     "email": account_info.get("email"),
                     "account_id": account_info.get("account_id")
                 },
+                "circuit_breaker": circuit_breaker,
+                "quota": quota,
                 "message": "Dropbox API connection successful"
             });
             Ok(Json(response))
         }
         Err(e) => {
+            let status = if CircuitOpen::find(&e).is_some() {
+                "circuit_open"
+            } else {
+                "error"
+            };
             let response = json!({
-                "status": "error",
-                "message": format!("Dropbox API connection failed: {}", e)
+                "status": status,
+                "circuit_breaker": circuit_breaker,
+                "quota": quota,
+                "message": format!("Dropbox API connection failed: {:#}", e)
             });
             Ok(Json(response))
         }
     }
 }
 
+/// Posts synced to the database, served while Dropbox calls are short-circuited
+///
+/// The rows are serialized whole, so password-protected posts are left out.
+async fn database_posts(state: &AppState, published: bool) -> Vec<Post> {
+    let filters = PostFilters {
+        published: Some(published),
+        visibility: Some(PostVisibility::listed(false)),
+        password_protected: Some(false),
+        ..Default::default()
+    };
+    state
+        .database
+        .list_posts(filters)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load posts from the database: {}", e);
+            Vec::new()
+        })
+}
+
 async fn list_posts_handler(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
     match state.blog_storage.list_published_posts().await {
         Ok(posts) => {
@@ -366,6 +1380,15 @@ async fn list_posts_handler(State(state): State<AppState>) -> Result<Json<Value>
             });
             Ok(Json(response))
         }
+        Err(e) if CircuitOpen::find(&e).is_some() => {
+            let posts = database_posts(&state, true).await;
+            let response = json!({
+                "posts": posts,
+                "count": posts.len(),
+                "source": "database"
+            });
+            Ok(Json(response))
+        }
         Err(e) => {
             let response = json!({
                 "error": format!("Failed to list posts: {}", e)
@@ -387,6 +1410,20 @@ async fn get_post_handler(
             });
             Ok(Json(response))
         }
+        Err(e) if CircuitOpen::find(&e).is_some() => {
+            match state.database.get_post_by_slug(&slug).await {
+                Ok(Some(post))
+                    if post.published
+                        && post.visibility != PostVisibility::Private
+                        && !post.is_password_protected() =>
+                {
+                    Ok(Json(serde_json::to_value(post).unwrap()))
+                }
+                _ => Ok(Json(json!({
+                    "error": format!("Post '{}' is unavailable while Dropbox is unreachable", slug)
+                }))),
+            }
+        }
         Err(e) => {
             let response = json!({
                 "error": format!("Failed to get post: {}", e)
@@ -405,6 +1442,15 @@ async fn list_drafts_handler(State(state): State<AppState>) -> Result<Json<Value
             });
             Ok(Json(response))
         }
+        Err(e) if CircuitOpen::find(&e).is_some() => {
+            let drafts = database_posts(&state, false).await;
+            let response = json!({
+                "drafts": drafts,
+                "count": drafts.len(),
+                "source": "database"
+            });
+            Ok(Json(response))
+        }
         Err(e) => {
             let response = json!({
                 "error": format!("Failed to list drafts: {}", e)
@@ -1,15 +1,30 @@
 use axum::{
-    extract::{Request, State},
-    http::{HeaderMap, StatusCode},
+    extract::{Query, Request, State},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
-    response::{Json, Response},
+    response::{IntoResponse, Json, Redirect, Response},
 };
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde_json::json;
-use tracing::{debug, warn};
+use tracing::{debug, error, warn};
 
 use crate::config::Config;
+use crate::handlers::admin::AdminState;
+use crate::handlers::posts::{is_authorized, StagingView};
+use crate::handlers::theme::{ThemePreview, ThemePreviewQuery, ThemeState};
+use crate::services::{AdminSessionService, ClientInfo, SessionCheck};
 
+pub mod body_limit;
+pub mod client_info;
+pub mod demo_mode;
+pub mod error_pages;
+pub mod ip_filter;
+pub mod maintenance;
+pub mod normalize;
 pub mod performance;
+pub mod request_id;
+pub mod response_cache;
+pub mod slow_log;
 
 /// Authentication middleware for API endpoints
 pub async fn auth_middleware(
@@ -79,6 +94,189 @@ pub async fn auth_middleware(
     }
 }
 
+/// Authentication middleware for `/staging/...` preview pages
+///
+/// Unlike the API, GET requests are protected as well. Browsers can log in with
+/// HTTP Basic auth using the API key as the password.
+pub async fn staging_middleware(
+    State(config): State<Config>,
+    headers: HeaderMap,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+
+    if config.api_key.is_none() {
+        debug!("No API key configured, allowing staging view: {}", path);
+    } else if !is_authorized(&headers, config.api_key.as_deref()) {
+        warn!("Unauthorized staging view request: {}", path);
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, r#"Basic realm="staging""#)],
+            "Authentication required for staging view",
+        )
+            .into_response();
+    }
+
+    request.extensions_mut().insert(StagingView);
+    let mut response = next.run(request).await;
+    // Drafts must never end up in shared caches
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_static("private, no-store"),
+    );
+    response
+}
+
+/// Login check for the admin pages
+///
+/// Requests need the session cookie issued by `/admin/login`, which also
+/// checks the TOTP code when two-factor login is on; everything else is
+/// redirected to the login page. The API key alone is only accepted by `/api`.
+/// Without a configured API key the pages stay open.
+pub async fn admin_session_middleware(
+    State(state): State<AdminState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    if state.api_key.is_none() || path == "/admin/login" {
+        return next.run(request).await;
+    }
+
+    match state.sessions.check(&headers).await {
+        Ok(SessionCheck::Valid) => next.run(request).await,
+        Ok(SessionCheck::Rotated(token)) => {
+            let response = next.run(request).await;
+            with_session_cookie(response, &state.sessions, &token, &client)
+        }
+        Ok(SessionCheck::Invalid) => {
+            debug!("No admin session, redirecting to login: {}", path);
+            // Keep the query so the page reopens as it was, e.g. with its filters
+            let target = request
+                .uri()
+                .path_and_query()
+                .map_or(path.as_str(), |target| target.as_str());
+            let next = utf8_percent_encode(target, NON_ALPHANUMERIC);
+            Redirect::to(&format!("/admin/login?next={}", next)).into_response()
+        }
+        Err(e) => {
+            error!("Failed to check admin session: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Credentials accepted by [`admin_api_middleware`]
+#[derive(Clone)]
+pub struct AdminApiAuth {
+    pub api_key: Option<String>,
+    pub sessions: AdminSessionService,
+}
+
+/// Authentication for admin API routes that only read
+///
+/// [`auth_middleware`] lets every GET through, so routes exposing drafts or
+/// other admin-only data are wrapped in this check as well. It accepts the API
+/// key or the admin login session, which the admin pages' scripts send.
+/// Without a configured API key the routes stay open.
+pub async fn admin_api_middleware(
+    State(auth): State<AdminApiAuth>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_authorized(&headers, auth.api_key.as_deref()) {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    match auth.sessions.check(&headers).await {
+        Ok(SessionCheck::Valid) => next.run(request).await,
+        Ok(SessionCheck::Rotated(token)) => {
+            let response = next.run(request).await;
+            with_session_cookie(response, &auth.sessions, &token, &client)
+        }
+        Ok(SessionCheck::Invalid) => {
+            warn!("Unauthorized admin API request: {}", path);
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "unauthorized",
+                    "message": "API key or admin login required for this endpoint"
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("Failed to check admin session: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Hand a rotated session token back to the browser
+fn with_session_cookie(
+    mut response: Response,
+    sessions: &AdminSessionService,
+    token: &str,
+    client: &ClientInfo,
+) -> Response {
+    if let Ok(cookie) = header::HeaderValue::from_str(&sessions.cookie(token, client)) {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+    response
+}
+
+/// Theme preview middleware for pages and `/theme.css`
+///
+/// When preview is enabled, `?theme=` or the admin-set preview cookie renders
+/// the request with that theme instead of the active one. Unknown themes are
+/// ignored.
+pub async fn theme_preview_middleware(
+    State(state): State<ThemeState>,
+    headers: HeaderMap,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if !state.preview.is_enabled() {
+        return next.run(request).await;
+    }
+
+    let query = Query::<ThemePreviewQuery>::try_from_uri(request.uri()).ok();
+    let requested = state
+        .preview
+        .requested_theme(query.as_ref().and_then(|q| q.theme.as_deref()), &headers);
+    let Some(theme) = requested else {
+        return next.run(request).await;
+    };
+
+    match state.theme_service.get_theme(&theme).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            debug!("Ignoring preview of unknown theme: {}", theme);
+            return next.run(request).await;
+        }
+        Err(e) => {
+            warn!("Failed to look up previewed theme {}: {}", theme, e);
+            return next.run(request).await;
+        }
+    }
+
+    debug!("Previewing theme {} for {}", theme, request.uri().path());
+    request.extensions_mut().insert(ThemePreview(theme));
+    let mut response = next.run(request).await;
+    // Previews depend on the query or cookie and must not replace the shared page
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_static("private, no-store"),
+    );
+    response
+}
+
 /// Check if the endpoint is read-only (doesn't require authentication)
 fn is_read_only_endpoint(path: &str, method: &str) -> bool {
     // Always allow GET requests
@@ -112,3 +310,64 @@ pub async fn csrf_middleware(
     // For now, just pass through
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::admin_session::SESSION_COOKIE;
+    use crate::services::DatabaseService;
+    use axum::{body::Body, middleware::from_fn_with_state, routing::get, Router};
+    use std::time::Duration;
+    use tower::util::ServiceExt;
+
+    #[tokio::test]
+    async fn test_admin_api_requires_key_or_session_for_get() {
+        let database = DatabaseService::in_memory().await.unwrap();
+        let hour = Duration::from_secs(3600);
+        let sessions = AdminSessionService::new(database, hour, hour);
+        let token = sessions.create().await.unwrap();
+        let auth = AdminApiAuth {
+            api_key: Some("secret".to_string()),
+            sessions,
+        };
+        let app = Router::new().route(
+            "/api/admin/command-palette",
+            get(|| async { "entries" }).layer(from_fn_with_state(auth, admin_api_middleware)),
+        );
+        let status = |header: Option<(header::HeaderName, String)>| {
+            let app = app.clone();
+            async move {
+                let mut request = Request::get("/api/admin/command-palette");
+                if let Some((name, value)) = header {
+                    request = request.header(name, value);
+                }
+                app.oneshot(request.body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+
+        assert_eq!(status(None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(Some((header::COOKIE, format!("{}=stale", SESSION_COOKIE)))).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(Some((
+                header::HeaderName::from_static("x-api-key"),
+                "secret".to_string()
+            )))
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(Some((
+                header::COOKIE,
+                format!("{}={}", SESSION_COOKIE, token)
+            )))
+            .await,
+            StatusCode::OK
+        );
+    }
+}
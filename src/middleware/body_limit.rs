@@ -0,0 +1,108 @@
+use axum::{
+    extract::Request,
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use tracing::warn;
+
+use crate::models::response::ErrorResponse;
+
+/// Room for multipart boundaries, part headers and the other form fields
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+/// Body limit of the media upload routes for files of up to `max_file_size` bytes
+///
+/// Pasted images may arrive base64-encoded in JSON, which is a third larger
+/// than the file itself.
+pub fn upload_body_limit(max_file_size: usize) -> usize {
+    max_file_size
+        .saturating_add(max_file_size / 3)
+        .saturating_add(MULTIPART_OVERHEAD_BYTES)
+}
+
+/// Answer oversized request bodies with a JSON error
+///
+/// The limits themselves are `DefaultBodyLimit` layers on the routers, which
+/// make the body extractors stop reading and reject with a plain-text 413.
+pub async fn payload_too_large_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || is_json(&response) {
+        return response;
+    }
+
+    warn!("Refused oversized request body for {} {}", method, path);
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(ErrorResponse::payload_too_large(
+            "Request body is too large",
+        )),
+    )
+        .into_response()
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, extract::DefaultBodyLimit, routing::post, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_oversized_bodies_get_a_json_413() {
+        let app = Router::new()
+            .route(
+                "/api/posts",
+                post(|body: Bytes| async move { body.len().to_string() }),
+            )
+            .route(
+                "/api/media/upload",
+                post(|body: Bytes| async move { body.len().to_string() })
+                    .layer(DefaultBodyLimit::max(64)),
+            )
+            .layer(DefaultBodyLimit::max(16))
+            .layer(axum::middleware::from_fn(payload_too_large_middleware));
+        let send = |path: &str, size: usize| {
+            let request = Request::post(path)
+                .body(axum::body::Body::from(vec![b'a'; size]))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        let response = send("/api/posts", 17).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(is_json(&response));
+
+        // The upload route's own limit wins over the router-wide one
+        assert_eq!(
+            send("/api/media/upload", 64).await.unwrap().status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send("/api/media/upload", 65).await.unwrap().status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(
+            send("/api/posts", 16).await.unwrap().status(),
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn test_upload_limit_leaves_room_for_base64_and_multipart() {
+        assert_eq!(
+            upload_body_limit(3 * 1024),
+            4 * 1024 + MULTIPART_OVERHEAD_BYTES
+        );
+        assert_eq!(upload_body_limit(usize::MAX), usize::MAX);
+    }
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use tracing::Span;
+
+use crate::services::TrustedProxies;
+
+/// Trusted proxies shared with the config reload, which replaces them in place
+pub type SharedTrustedProxies = Arc<RwLock<TrustedProxies>>;
+
+/// Resolve the real client of each request and attach it to the request
+///
+/// The address, scheme and host are also recorded on the request span, so
+/// every log line of the request names the client rather than the proxy.
+pub async fn client_info_middleware(
+    State(proxies): State<SharedTrustedProxies>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let client = proxies
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .resolve(peer.ip(), request.headers(), request.uri().scheme_str());
+    let span = Span::current();
+    span.record("client.address", client.ip.to_string());
+    span.record("url.scheme", client.scheme.as_str());
+    if let Some(host) = &client.host {
+        span.record("server.address", host.as_str());
+    }
+    request.extensions_mut().insert(client);
+    next.run(request).await
+}
@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Whether `DEMO_MODE` lets a request through
+///
+/// Reads are always allowed, as are logging in and out of the admin panel and
+/// unlocking password-protected posts, which only set cookies.
+fn is_allowed(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    matches!(path, "/admin/login" | "/admin/logout")
+        || (*method == Method::POST && path.starts_with("/posts/"))
+}
+
+/// Refuse every request that would change data while `DEMO_MODE` is on
+pub async fn demo_mode_middleware(
+    State(enabled): State<Arc<AtomicBool>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !enabled.load(Ordering::Relaxed) || is_allowed(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    debug!(
+        "Demo mode: refused {} {}",
+        request.method(),
+        request.uri().path()
+    );
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "demo_mode",
+            "message": "This is a read-only demo; changes are disabled"
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_reads_and_sessions_are_allowed() {
+        assert!(is_allowed(&Method::GET, "/admin/posts"));
+        assert!(is_allowed(&Method::HEAD, "/"));
+        assert!(is_allowed(&Method::POST, "/admin/login"));
+        assert!(is_allowed(&Method::POST, "/posts/2024/secret"));
+
+        assert!(!is_allowed(&Method::POST, "/api/posts"));
+        assert!(!is_allowed(&Method::DELETE, "/api/posts/hello"));
+        assert!(!is_allowed(&Method::PUT, "/posts/2024/secret"));
+        assert!(!is_allowed(&Method::POST, "/admin/import"));
+    }
+}
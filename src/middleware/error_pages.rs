@@ -0,0 +1,202 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::models::{PostFilters, PostVisibility};
+use crate::services::template::{ErrorPageContext, PostSummary};
+use crate::services::{DatabaseService, TemplateService};
+
+/// Number of recent posts suggested on the 404 page
+const RECENT_POST_LIMIT: i64 = 5;
+
+/// Largest error body read back for logging alongside the incident ID
+const ERROR_BODY_LOG_LIMIT: usize = 16 * 1024;
+
+/// State for rendering themed error pages
+#[derive(Clone)]
+pub struct ErrorPageState {
+    pub templates: TemplateService,
+    pub database: DatabaseService,
+}
+
+/// Replace 404, 410 and 5xx responses with themed HTML pages for browsers
+///
+/// `/api/*` routes and clients that don't accept HTML keep the original
+/// (usually JSON) error response.
+pub async fn error_page_middleware(
+    State(state): State<ErrorPageState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let wants_html = !path.starts_with("/api/")
+        && request
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .is_some_and(|accept| accept.contains("text/html"));
+
+    let response = next.run(request).await;
+    let status = response.status();
+    let missing = status == StatusCode::NOT_FOUND || status == StatusCode::GONE;
+    if !wants_html || !(missing || status.is_server_error()) {
+        return response;
+    }
+
+    let context = if missing {
+        not_found_context(&state, path, status).await
+    } else {
+        let incident_id = Uuid::new_v4().simple().to_string()[..12].to_string();
+        let (_, body) = response.into_parts();
+        let detail = to_bytes(body, ERROR_BODY_LOG_LIMIT)
+            .await
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        error!(
+            "Incident {}: {} on {}: {}",
+            incident_id, status, path, detail
+        );
+
+        ErrorPageContext {
+            site_title: "Tobelog".to_string(),
+            site_description: "Personal Blog System built with Rust".to_string(),
+            status: status.as_u16(),
+            message: state.templates.translate("errors.server_error", &[]),
+            path,
+            recent_posts: vec![],
+            incident_id: Some(incident_id),
+        }
+    };
+
+    let template = if missing { "404.html" } else { "error.html" };
+    match state.templates.render(template, &context) {
+        Ok(html) => {
+            let mut page = (status, html).into_response();
+            page.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/html; charset=utf-8"),
+            );
+            page
+        }
+        Err(e) => {
+            error!("Failed to render error page {}: {}", template, e);
+            (status, Body::from(status.to_string())).into_response()
+        }
+    }
+}
+
+/// Context for the 404 page, also used for deleted posts (410)
+async fn not_found_context(
+    state: &ErrorPageState,
+    path: String,
+    status: StatusCode,
+) -> ErrorPageContext {
+    let filters = PostFilters {
+        published: Some(true),
+        limit: Some(RECENT_POST_LIMIT),
+        visibility: Some(vec![PostVisibility::Public]),
+        ..Default::default()
+    };
+    let recent_posts = state
+        .database
+        .list_posts(filters)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load recent posts for 404 page: {}", e);
+            vec![]
+        })
+        .into_iter()
+        .map(PostSummary::from)
+        .collect();
+
+    ErrorPageContext {
+        site_title: "Tobelog".to_string(),
+        site_description: "Personal Blog System built with Rust".to_string(),
+        status: status.as_u16(),
+        message: if status == StatusCode::GONE {
+            state.templates.translate("errors.gone", &[])
+        } else {
+            state.templates.translate("errors.not_found", &[])
+        },
+        path,
+        recent_posts,
+        incident_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Router};
+    use tower::util::ServiceExt;
+
+    async fn failing_handler() -> (StatusCode, &'static str) {
+        (StatusCode::INTERNAL_SERVER_ERROR, "boom")
+    }
+
+    async fn gone_handler() -> StatusCode {
+        StatusCode::GONE
+    }
+
+    async fn app() -> Router {
+        let state = ErrorPageState {
+            templates: TemplateService::new().unwrap(),
+            database: DatabaseService::new("sqlite::memory:").await.unwrap(),
+        };
+        Router::new()
+            .route("/broken", get(failing_handler))
+            .route("/deleted", get(gone_handler))
+            .route("/api/broken", get(failing_handler))
+            .layer(middleware::from_fn_with_state(state, error_page_middleware))
+    }
+
+    fn html_request(uri: &str) -> Request {
+        Request::builder()
+            .uri(uri)
+            .header(header::ACCEPT, "text/html,application/xhtml+xml")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_error_page_middleware_renders_html_for_browsers() {
+        let response = app().await.oneshot(html_request("/broken")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("<html"));
+        assert!(!body.contains("boom"));
+
+        let response = app().await.oneshot(html_request("/missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("&#x2F;missing"));
+    }
+
+    #[tokio::test]
+    async fn test_error_page_middleware_renders_gone_for_deleted_posts() {
+        let response = app().await.oneshot(html_request("/deleted")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GONE);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("410"));
+        assert!(body.contains("&#x2F;deleted"));
+    }
+
+    #[tokio::test]
+    async fn test_error_page_middleware_keeps_api_errors() {
+        let response = app()
+            .await
+            .oneshot(html_request("/api/broken"))
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"boom");
+    }
+}
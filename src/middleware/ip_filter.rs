@@ -0,0 +1,141 @@
+use anyhow::Result;
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use ipnet::IpNet;
+use serde_json::json;
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+use tracing::{debug, warn};
+
+use crate::services::{client_info::parse_networks, ClientInfo};
+
+/// Network allow/deny lists for the admin panel and mutating API routes
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    allow: Vec<IpNet>,
+    deny: Vec<IpNet>,
+}
+
+impl IpFilter {
+    /// Parse CIDR lists; plain addresses are accepted as single-host networks
+    pub fn new(allow: &[String], deny: &[String]) -> Result<Self> {
+        Ok(Self {
+            allow: parse_networks(allow)?,
+            deny: parse_networks(deny)?,
+        })
+    }
+
+    /// Whether any rule is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.allow.is_empty() || !self.deny.is_empty()
+    }
+
+    /// Deny wins over allow; an empty allow list allows every address not denied
+    pub fn is_allowed(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        if self.deny.iter().any(|net| net.contains(&ip)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|net| net.contains(&ip))
+    }
+}
+
+/// Admin pages and API calls that change data; public pages and reads stay open
+fn is_restricted(method: &Method, path: &str) -> bool {
+    if path == "/admin" || path.starts_with("/admin/") {
+        return true;
+    }
+    path.starts_with("/api/") && !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// IP filter shared with the config reload, which replaces it in place
+pub type SharedIpFilter = Arc<RwLock<IpFilter>>;
+
+/// Refuse restricted routes to clients outside the configured networks
+pub async fn ip_filter_middleware(
+    State(filter): State<SharedIpFilter>,
+    client: ClientInfo,
+    request: Request,
+    next: Next,
+) -> Response {
+    let verdict = {
+        let filter = filter
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (filter.is_enabled() && is_restricted(request.method(), request.uri().path()))
+            .then(|| (client.ip, filter.is_allowed(client.ip)))
+    };
+    let client = match verdict {
+        None => return next.run(request).await,
+        Some((client, true)) => {
+            debug!("IP {} allowed for {}", client, request.uri().path());
+            return next.run(request).await;
+        }
+        Some((client, false)) => client,
+    };
+
+    warn!(
+        "Blocked {} {} from {}",
+        request.method(),
+        request.uri().path(),
+        client
+    );
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "forbidden",
+            "message": "Access from this network is not allowed"
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let filter = IpFilter::new(
+            &list(&["192.168.1.0/24", "10.8.0.0/16"]),
+            &list(&["192.168.1.13"]),
+        )
+        .unwrap();
+
+        assert!(filter.is_allowed(ip("192.168.1.20")));
+        assert!(filter.is_allowed(ip("10.8.3.4")));
+        assert!(!filter.is_allowed(ip("192.168.1.13")));
+        assert!(!filter.is_allowed(ip("203.0.113.5")));
+        // IPv4-mapped IPv6 peers match IPv4 rules
+        assert!(filter.is_allowed(ip("::ffff:192.168.1.20")));
+
+        let deny_only = IpFilter::new(&[], &list(&["203.0.113.0/24"])).unwrap();
+        assert!(deny_only.is_allowed(ip("198.51.100.1")));
+        assert!(!deny_only.is_allowed(ip("203.0.113.9")));
+
+        assert!(IpFilter::new(&list(&["not-a-network"]), &[]).is_err());
+    }
+
+    #[test]
+    fn test_only_admin_and_mutating_api_routes_are_restricted() {
+        assert!(is_restricted(&Method::GET, "/admin"));
+        assert!(is_restricted(&Method::GET, "/admin/posts"));
+        assert!(is_restricted(&Method::DELETE, "/api/posts/hello"));
+        assert!(is_restricted(&Method::POST, "/api/media/upload"));
+        assert!(!is_restricted(&Method::GET, "/api/posts"));
+        assert!(!is_restricted(&Method::POST, "/posts/2024/hello"));
+        assert!(!is_restricted(&Method::GET, "/administration"));
+    }
+}
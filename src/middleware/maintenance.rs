@@ -0,0 +1,156 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+use tracing::{debug, error, warn};
+
+use crate::services::admin_session::session_token;
+use crate::services::template::MaintenancePageContext;
+use crate::services::{DatabaseService, MaintenanceService, TemplateService};
+
+/// State for the maintenance mode middleware
+#[derive(Clone)]
+pub struct MaintenanceState {
+    pub maintenance: MaintenanceService,
+    pub templates: TemplateService,
+    pub database: DatabaseService,
+}
+
+/// Whether a request is still served during maintenance
+///
+/// The health check, the admin panel with its assets and the admin API stay
+/// up. Other API calls pass when they carry an API key or admin session, since
+/// the admin panel and deploy scripts rely on them.
+fn is_exempt(path: &str, headers: &HeaderMap) -> bool {
+    if path == "/health"
+        || path == "/admin"
+        || path == "/theme.css"
+        || ["/admin/", "/api/admin/", "/static/", "/assets/", "/media/"]
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+    {
+        return true;
+    }
+
+    path.starts_with("/api/")
+        && (headers.contains_key(header::AUTHORIZATION)
+            || headers.contains_key("X-API-Key")
+            || session_token(headers).is_some())
+}
+
+/// Answer public requests with 503 and `Retry-After` while maintenance mode is on
+///
+/// Browsers get the themed maintenance page, other clients a JSON error.
+pub async fn maintenance_middleware(
+    State(state): State<MaintenanceState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.maintenance.is_enabled() || is_exempt(request.uri().path(), request.headers()) {
+        return next.run(request).await;
+    }
+
+    let status = state.maintenance.status();
+    debug!("Maintenance mode: refused {}", request.uri().path());
+
+    let wants_html = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"));
+
+    let mut response = if wants_html {
+        // The page still renders when the database is unavailable mid-deploy
+        let site_config = state.database.get_site_config().await.unwrap_or_else(|e| {
+            warn!("Failed to load site config for maintenance page: {}", e);
+            None
+        });
+        let (site_title, site_description) = match site_config {
+            Some(config) => (config.site_title, config.site_description),
+            None => (
+                "Tobelog".to_string(),
+                "Personal Blog System built with Rust".to_string(),
+            ),
+        };
+        let context = MaintenancePageContext {
+            site_title,
+            site_description,
+            message: status.message.clone(),
+            retry_after_minutes: status.retry_after_secs.div_ceil(60).max(1),
+        };
+        match state.templates.render("maintenance.html", &context) {
+            Ok(html) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+                html,
+            )
+                .into_response(),
+            Err(e) => {
+                error!("Failed to render maintenance page: {}", e);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Body::from(StatusCode::SERVICE_UNAVAILABLE.to_string()),
+                )
+                    .into_response()
+            }
+        }
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": "maintenance",
+                "message": status
+                    .message
+                    .unwrap_or_else(|| state.templates.translate("maintenance.message", &[])),
+                "retry_after": status.retry_after_secs
+            })),
+        )
+            .into_response()
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(status.retry_after_secs),
+    );
+    // The maintenance page must not replace cached pages in browsers or proxies
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_admin_and_authenticated_api_are_exempt() {
+        let anonymous = HeaderMap::new();
+        assert!(is_exempt("/health", &anonymous));
+        assert!(is_exempt("/admin", &anonymous));
+        assert!(is_exempt("/admin/posts", &anonymous));
+        assert!(is_exempt("/api/admin/maintenance", &anonymous));
+        assert!(is_exempt("/static/css/main.css", &anonymous));
+
+        assert!(!is_exempt("/", &anonymous));
+        assert!(!is_exempt("/posts/2024/hello", &anonymous));
+        assert!(!is_exempt("/feed.xml", &anonymous));
+        assert!(!is_exempt("/api/posts", &anonymous));
+        assert!(!is_exempt("/administrator", &anonymous));
+
+        let mut api_key = HeaderMap::new();
+        api_key.insert("X-API-Key", HeaderValue::from_static("secret"));
+        assert!(is_exempt("/api/posts", &api_key));
+        assert!(!is_exempt("/", &api_key));
+
+        let mut session = HeaderMap::new();
+        session.insert(
+            header::COOKIE,
+            HeaderValue::from_static("tobelog_admin_session=abc"),
+        );
+        assert!(is_exempt("/api/posts", &session));
+    }
+}
@@ -0,0 +1,194 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Normalizations applied to a group of routes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizeOptions {
+    /// Collapse `//` into `/`
+    pub merge_slashes: bool,
+    /// Redirect `/path/` to `/path`
+    pub trim_trailing_slash: bool,
+    /// Lowercase ASCII letters (percent-encoded bytes are left alone)
+    pub lowercase: bool,
+}
+
+impl NormalizeOptions {
+    /// Merge duplicate slashes and trim trailing slashes, keeping the case
+    pub fn slashes() -> Self {
+        Self {
+            merge_slashes: true,
+            trim_trailing_slash: true,
+            lowercase: false,
+        }
+    }
+}
+
+/// Canonical URL rules per route group, keyed by path prefix
+#[derive(Debug, Clone, Default)]
+pub struct UrlNormalization {
+    groups: Vec<(String, NormalizeOptions)>,
+}
+
+impl UrlNormalization {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply `options` to `prefix` and everything below it; the longest prefix wins
+    pub fn group(mut self, prefix: &str, options: NormalizeOptions) -> Self {
+        let prefix = prefix.trim_end_matches('/');
+        self.groups
+            .push((format!("/{}", prefix.trim_start_matches('/')), options));
+        self
+    }
+
+    fn options_for(&self, path: &str) -> Option<NormalizeOptions> {
+        self.groups
+            .iter()
+            .filter(|(prefix, _)| {
+                if prefix == "/" {
+                    path == "/"
+                } else {
+                    path == prefix
+                        || path
+                            .strip_prefix(prefix.as_str())
+                            .is_some_and(|rest| rest.starts_with('/'))
+                }
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, options)| *options)
+    }
+
+    /// Canonical form of `path`, or `None` when it is already canonical or unmanaged
+    pub fn canonical_path(&self, path: &str) -> Option<String> {
+        // Match groups on the merged path so `//posts/x` still finds `/posts`
+        let merged = merge_slashes(path);
+        let options = self.options_for(&merged)?;
+
+        let mut canonical = if options.merge_slashes {
+            merged
+        } else {
+            path.to_string()
+        };
+        if options.trim_trailing_slash {
+            while canonical.len() > 1 && canonical.ends_with('/') {
+                canonical.pop();
+            }
+        }
+        if options.lowercase {
+            canonical = lowercase_path(&canonical);
+        }
+
+        (canonical != path).then_some(canonical)
+    }
+}
+
+fn merge_slashes(path: &str) -> String {
+    let mut merged = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c == '/' && merged.ends_with('/') {
+            continue;
+        }
+        merged.push(c);
+    }
+    merged
+}
+
+/// Lowercase ASCII letters but keep `%XX` escapes as they are
+fn lowercase_path(path: &str) -> String {
+    let mut lowered = String::with_capacity(path.len());
+    let mut escape_digits = 0;
+    for c in path.chars() {
+        if c == '%' {
+            escape_digits = 2;
+            lowered.push(c);
+        } else if escape_digits > 0 {
+            escape_digits -= 1;
+            lowered.push(c);
+        } else {
+            lowered.push(c.to_ascii_lowercase());
+        }
+    }
+    lowered
+}
+
+/// Redirect GET and HEAD requests to the canonical URL with 301 Moved Permanently
+pub async fn normalize_url_middleware(
+    State(rules): State<Arc<UrlNormalization>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+
+    let Some(canonical) = rules.canonical_path(request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let location = match request.uri().query() {
+        Some(query) => format!("{}?{}", canonical, query),
+        None => canonical,
+    };
+    debug!("Redirecting {} to {}", request.uri(), location);
+
+    (
+        StatusCode::MOVED_PERMANENTLY,
+        [(header::LOCATION, location)],
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> UrlNormalization {
+        UrlNormalization::new()
+            .group("/", NormalizeOptions::slashes())
+            .group(
+                "/posts",
+                NormalizeOptions {
+                    lowercase: true,
+                    ..NormalizeOptions::slashes()
+                },
+            )
+            .group("/tag", NormalizeOptions::slashes())
+    }
+
+    #[test]
+    fn test_canonical_path_per_group() {
+        let rules = rules();
+
+        assert_eq!(
+            rules.canonical_path("//posts//2024/Hello-World/"),
+            Some("/posts/2024/hello-world".to_string())
+        );
+        assert_eq!(
+            rules.canonical_path("/tag/Rust/"),
+            Some("/tag/Rust".to_string())
+        );
+        assert_eq!(rules.canonical_path("//"), Some("/".to_string()));
+        assert_eq!(rules.canonical_path("/posts/2024/hello"), None);
+        assert_eq!(rules.canonical_path("/"), None);
+        // Paths outside every group are left alone
+        assert_eq!(rules.canonical_path("/api/posts/"), None);
+        assert_eq!(rules.canonical_path("/postscript/"), None);
+    }
+
+    #[test]
+    fn test_lowercase_keeps_percent_escapes() {
+        let rules = rules();
+
+        assert_eq!(
+            rules.canonical_path("/posts/2024/%E3%81%82-Note"),
+            Some("/posts/2024/%E3%81%82-note".to_string())
+        );
+    }
+}
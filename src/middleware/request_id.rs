@@ -0,0 +1,83 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{field, info_span, Instrument};
+use uuid::Uuid;
+
+/// Header carrying the request ID in both directions
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest request ID accepted from clients or proxies
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Request ID sent by a client or proxy, if it is safe to log
+fn incoming_request_id(value: Option<&HeaderValue>) -> Option<String> {
+    let id = value?.to_str().ok()?.trim();
+    let valid = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid.then(|| id.to_string())
+}
+
+/// Run each request in a span tagged with its request ID
+///
+/// The ID comes from `X-Request-Id` when a proxy already assigned one, or is
+/// generated, and is echoed in the response. Logs written while handling the
+/// request carry it, and with OTLP export enabled it is the trace ID.
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = incoming_request_id(request.headers().get(&REQUEST_ID_HEADER))
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        request
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER.clone(), value);
+    }
+
+    let span = info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", request.method(), request.uri().path()),
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        http.method = %request.method(),
+        http.target = %request.uri(),
+        http.status_code = field::Empty,
+        request_id = %request_id,
+        client.address = field::Empty,
+        url.scheme = field::Empty,
+        server.address = field::Empty,
+    );
+
+    let mut response = next.run(request).instrument(span.clone()).await;
+    span.record("http.status_code", response.status().as_u16());
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incoming_request_ids_are_validated() {
+        let id = |value: &str| incoming_request_id(Some(&HeaderValue::from_str(value).unwrap()));
+        assert_eq!(id(" abc-123_x.y ").as_deref(), Some("abc-123_x.y"));
+        assert_eq!(id(""), None);
+        assert_eq!(id("has space"), None);
+        assert_eq!(id("<script>"), None);
+        assert_eq!(id(&"a".repeat(129)), None);
+        assert_eq!(incoming_request_id(None), None);
+    }
+}
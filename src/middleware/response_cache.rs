@@ -0,0 +1,267 @@
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::handlers::posts::{if_none_match, preferred_post_format};
+use crate::services::{CacheService, CachedResponse, ThemeService};
+
+/// Largest response body kept in the cache
+const MAX_CACHED_BODY: usize = 2 * 1024 * 1024;
+
+/// HTTP response cache for pages and feeds rendered the same for every visitor
+///
+/// Only anonymous GETs are served from the cache; requests with cookies or
+/// credentials may see drafts, unlocked posts or theme previews. Entries are
+/// keyed by path, query, active theme and the format negotiated from `Accept`
+/// and are dropped together with the data cache.
+#[derive(Clone)]
+pub struct ResponseCache {
+    pub cache: CacheService,
+    pub theme_service: ThemeService,
+    /// Seconds entries are kept; shared so a config reload can change it
+    pub ttl_secs: Arc<AtomicU64>,
+}
+
+impl ResponseCache {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs.load(Ordering::Relaxed))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl().is_zero()
+    }
+}
+
+/// Serve anonymous GETs from the response cache and store cacheable misses
+pub async fn response_cache_middleware(
+    State(state): State<ResponseCache>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.is_enabled() || !is_anonymous_get(&request) {
+        return next.run(request).await;
+    }
+
+    let theme = match state.theme_service.get_active_theme().await {
+        Ok(theme) => theme.name,
+        Err(e) => {
+            warn!("Skipping response cache, active theme unavailable: {}", e);
+            return next.run(request).await;
+        }
+    };
+    // Post pages answer with HTML, markdown or JSON depending on `Accept`
+    let key = format!(
+        "{}|{:?}|{}",
+        theme,
+        preferred_post_format(request.headers()),
+        request.uri()
+    );
+
+    if let Some(cached) = state.cache.get_response(&key).await {
+        debug!("Response cache hit: {}", key);
+        let etag = cached
+            .headers
+            .get(header::ETAG)
+            .and_then(|etag| etag.to_str().ok());
+        if etag.is_some_and(|etag| if_none_match(request.headers(), etag)) {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            for name in [header::ETAG, header::VARY, header::CACHE_CONTROL] {
+                if let Some(value) = cached.headers.get(&name) {
+                    response.headers_mut().insert(name, value.clone());
+                }
+            }
+            return response;
+        }
+        let mut response = Response::new(Body::from(cached.body));
+        *response.status_mut() = cached.status;
+        *response.headers_mut() = cached.headers;
+        response
+            .headers_mut()
+            .insert("X-Cache", HeaderValue::from_static("HIT"));
+        return response;
+    }
+
+    let response = next.run(request).await;
+    if !is_cacheable(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_CACHED_BODY).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to buffer response for {}: {}", key, e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    state
+        .cache
+        .set_response(
+            &key,
+            CachedResponse::new(
+                parts.status,
+                parts.headers.clone(),
+                body.clone(),
+                state.ttl(),
+            ),
+        )
+        .await;
+    parts
+        .headers
+        .insert("X-Cache", HeaderValue::from_static("MISS"));
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// GET without cookies or credentials
+fn is_anonymous_get(request: &Request) -> bool {
+    let headers = request.headers();
+    request.method() == Method::GET
+        && !headers.contains_key(header::COOKIE)
+        && !headers.contains_key(header::AUTHORIZATION)
+        && !headers.contains_key("X-API-Key")
+}
+
+/// Successful, shared and of a known size small enough to keep
+fn is_cacheable(response: &Response) -> bool {
+    let headers = response.headers();
+    let private = headers
+        .get(header::CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("private") || value.contains("no-store"));
+    // Streamed bodies have no upper bound and are passed through
+    let fits = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|length| length <= MAX_CACHED_BODY as u64);
+
+    response.status() == StatusCode::OK
+        && !private
+        && fits
+        && !headers.contains_key(header::SET_COOKIE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::storage::MemoryStorage;
+    use crate::services::DatabaseService;
+    use axum::body::Bytes;
+    use axum::http::HeaderMap;
+    use axum::{middleware, routing::get, Router};
+    use tower::util::ServiceExt;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        let mut builder = Request::builder().method(Method::GET).uri("/tag/rust");
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    fn response(headers: &[(&str, &str)]) -> Response {
+        let mut builder = Response::builder().status(StatusCode::OK);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(Body::from("<html></html>")).unwrap()
+    }
+
+    #[test]
+    fn test_only_anonymous_requests_use_the_cache() {
+        assert!(is_anonymous_get(&request(&[])));
+        assert!(!is_anonymous_get(&request(&[(
+            "Cookie",
+            "theme_preview=x"
+        )])));
+        assert!(!is_anonymous_get(&request(&[("X-API-Key", "secret")])));
+        assert!(!is_anonymous_get(&request(&[(
+            "Authorization",
+            "Bearer secret"
+        )])));
+    }
+
+    async fn negotiated_handler(headers: HeaderMap) -> Response {
+        let body = match preferred_post_format(&headers) {
+            crate::handlers::posts::PostFormat::Json => r#"{"title":"Post"}"#,
+            _ => "<html>Post</html>",
+        };
+        Response::builder()
+            .header(header::VARY, "Accept")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_negotiated_formats_are_cached_separately() {
+        let database = DatabaseService::in_memory().await.unwrap();
+        let state = ResponseCache {
+            cache: CacheService::new(),
+            theme_service: ThemeService::new(database, Arc::new(MemoryStorage::new())),
+            ttl_secs: Arc::new(AtomicU64::new(60)),
+        };
+        let app = Router::new()
+            .route("/posts/2024/post", get(negotiated_handler))
+            .layer(middleware::from_fn_with_state(
+                state,
+                response_cache_middleware,
+            ));
+        let fetch = |accept: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::get("/posts/2024/post")
+                            .header(header::ACCEPT, accept)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                let cache = response.headers()["X-Cache"].clone();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (cache, String::from_utf8_lossy(&body).to_string())
+            }
+        };
+
+        let (cache, body) = fetch("application/json").await;
+        assert_eq!(cache, "MISS");
+        assert_eq!(body, r#"{"title":"Post"}"#);
+
+        let (cache, body) = fetch("text/html,application/xhtml+xml").await;
+        assert_eq!(cache, "MISS");
+        assert_eq!(body, "<html>Post</html>");
+
+        let (cache, body) = fetch("text/html").await;
+        assert_eq!(cache, "HIT");
+        assert_eq!(body, "<html>Post</html>");
+    }
+
+    #[test]
+    fn test_private_responses_are_not_cached() {
+        assert!(is_cacheable(&response(&[(
+            "Content-Type",
+            "text/html; charset=utf-8"
+        )])));
+        assert!(!is_cacheable(&response(&[(
+            "Cache-Control",
+            "private, no-store"
+        )])));
+        assert!(!is_cacheable(&response(&[("Set-Cookie", "a=b")])));
+
+        let streamed = Response::new(Body::from_stream(futures_util::stream::empty::<
+            Result<Bytes, std::io::Error>,
+        >()));
+        assert!(!is_cacheable(&streamed));
+    }
+}
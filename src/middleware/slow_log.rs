@@ -0,0 +1,39 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+
+use super::request_id::REQUEST_ID_HEADER;
+use crate::services::SlowLog;
+
+/// Record handlers that take longer than the slow request threshold
+///
+/// Added with `route_layer` so the matched route pattern is known; requests
+/// are grouped by route rather than by their full URL.
+pub async fn slow_request_middleware(
+    State(slow_log): State<SlowLog>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(path) => format!("{} {}", request.method(), path.as_str()),
+        None => format!("{} {}", request.method(), request.uri().path()),
+    };
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    slow_log.record_request(
+        &route,
+        response.status().as_u16(),
+        start.elapsed(),
+        request_id,
+    );
+    response
+}
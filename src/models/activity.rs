@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Source of an entry in the admin activity feed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    /// A saved version in a post's history
+    PostRevision,
+    /// A post brought in line with its Dropbox file
+    DropboxSync,
+    /// A file added to the media library
+    MediaUpload,
+}
+
+/// One event in the admin dashboard's recent activity stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub kind: ActivityKind,
+    pub title: String,
+    pub detail: Option<String>,
+    /// Admin page or file the entry refers to
+    pub link: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Last time a post's database and Dropbox copies were recorded as matching
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub slug: String,
+    pub title: String,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// Merge entries from several sources into a newest-first feed of at most `limit` entries
+pub fn merge_activity(
+    sources: impl IntoIterator<Item = Vec<ActivityEntry>>,
+    limit: usize,
+) -> Vec<ActivityEntry> {
+    let mut entries: Vec<ActivityEntry> = sources.into_iter().flatten().collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.occurred_at));
+    entries.truncate(limit);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(kind: ActivityKind, hour: u32) -> ActivityEntry {
+        ActivityEntry {
+            kind,
+            title: format!("{:?} {}", kind, hour),
+            detail: None,
+            link: None,
+            occurred_at: Utc.with_ymd_and_hms(2024, 7, 1, hour, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_merge_activity_orders_newest_first() {
+        let feed = merge_activity(
+            [
+                vec![
+                    entry(ActivityKind::PostRevision, 9),
+                    entry(ActivityKind::PostRevision, 3),
+                ],
+                vec![entry(ActivityKind::MediaUpload, 12)],
+                vec![entry(ActivityKind::DropboxSync, 6)],
+            ],
+            3,
+        );
+
+        let hours: Vec<String> = feed.iter().map(|e| e.title.clone()).collect();
+        assert_eq!(
+            hours,
+            vec!["MediaUpload 12", "PostRevision 9", "DropboxSync 6"]
+        );
+    }
+}
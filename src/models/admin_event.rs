@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::ExpiryAction;
+
+/// Notification pushed to open admin pages over `GET /admin/events`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdminEvent {
+    /// A Dropbox sync job finished
+    SyncCompleted {
+        synced: usize,
+        errors: usize,
+        conflicts: usize,
+    },
+    /// A scheduled post reached its `publish_at` time and went live
+    ScheduledPostPublished { slug: String, title: String },
+    /// The API saved a published post, newly or as an edit
+    PostPublished {
+        slug: String,
+        title: String,
+        /// Whether the post went from draft (or nothing) to published
+        newly_published: bool,
+    },
+    /// A post reached its `expires_at` time and was unpublished or archived
+    PostExpired {
+        slug: String,
+        title: String,
+        action: ExpiryAction,
+    },
+    /// A background batch import finished processing every article
+    ImportJobFinished {
+        job_id: String,
+        successful: usize,
+        failed: usize,
+    },
+    /// A queued job failed on its last attempt
+    JobDead {
+        job_id: Uuid,
+        kind: String,
+        error: String,
+    },
+    /// A media upload pushed Dropbox usage past the warning threshold
+    StorageQuotaWarning {
+        used: u64,
+        allocated: u64,
+        percent: f64,
+    },
+}
+
+impl AdminEvent {
+    /// SSE event name, matching the serialized `type`
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdminEvent::SyncCompleted { .. } => "sync_completed",
+            AdminEvent::ScheduledPostPublished { .. } => "scheduled_post_published",
+            AdminEvent::PostPublished { .. } => "post_published",
+            AdminEvent::PostExpired { .. } => "post_expired",
+            AdminEvent::ImportJobFinished { .. } => "import_job_finished",
+            AdminEvent::JobDead { .. } => "job_dead",
+            AdminEvent::StorageQuotaWarning { .. } => "storage_quota_warning",
+        }
+    }
+
+    /// Whether the event changed what public pages show
+    pub fn changes_content(&self) -> bool {
+        matches!(
+            self,
+            AdminEvent::SyncCompleted { .. }
+                | AdminEvent::ScheduledPostPublished { .. }
+                | AdminEvent::PostPublished { .. }
+                | AdminEvent::PostExpired { .. }
+                | AdminEvent::ImportJobFinished { .. }
+        )
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Visual style of an announcement banner
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnouncementStyle {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl AnnouncementStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementStyle::Info => "info",
+            AnnouncementStyle::Success => "success",
+            AnnouncementStyle::Warning => "warning",
+            AnnouncementStyle::Danger => "danger",
+        }
+    }
+}
+
+impl std::str::FromStr for AnnouncementStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(AnnouncementStyle::Info),
+            "success" => Ok(AnnouncementStyle::Success),
+            "warning" => Ok(AnnouncementStyle::Warning),
+            "danger" => Ok(AnnouncementStyle::Danger),
+            other => Err(anyhow::anyhow!("Unknown announcement style: {}", other)),
+        }
+    }
+}
+
+/// Site-wide banner shown above every public page while it is active
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Announcement {
+    pub id: i64,
+    pub message: String,
+    pub link: Option<String>,
+    pub style: AnnouncementStyle,
+    /// Shown from this time on; `None` shows it right away
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Hidden from this time on; `None` keeps it until deleted
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Whether readers can close the banner (remembered in their browser)
+    pub dismissible: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Announcement {
+    /// Whether the banner is shown at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at.is_none_or(|starts_at| starts_at <= now)
+            && self.ends_at.is_none_or(|ends_at| now < ends_at)
+    }
+}
+
+/// Request body for creating an announcement or replacing all of its fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnouncementRequest {
+    pub message: String,
+    pub link: Option<String>,
+    #[serde(default)]
+    pub style: AnnouncementStyle,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    #[serde(default = "default_dismissible")]
+    pub dismissible: bool,
+}
+
+fn default_dismissible() -> bool {
+    true
+}
+
+/// Response for `GET /api/admin/announcements`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnouncementListResponse {
+    pub announcements: Vec<Announcement>,
+    pub total: usize,
+}
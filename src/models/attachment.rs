@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Downloadable file (PDF, archive, dataset, ...) listed at the end of a post
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostAttachment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub media_id: Uuid,
+    /// Name the file is downloaded as
+    pub filename: String,
+    pub mime_type: String,
+    pub file_size: u64,
+    /// Counted download link, see [`PostAttachment::download_path`]
+    pub download_url: String,
+    pub download_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PostAttachment {
+    /// Path of the download endpoint, which counts each download
+    pub fn download_path(id: Uuid) -> String {
+        format!("/attachments/{}", id)
+    }
+
+    /// `Content-Disposition` value that saves the file under its original name
+    pub fn content_disposition(&self) -> String {
+        // Plain ASCII fallback for old clients, RFC 5987 encoding for the rest
+        let fallback: String = self
+            .filename
+            .chars()
+            .map(|c| {
+                if (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let encoded: String = self
+            .filename
+            .bytes()
+            .map(|b| {
+                if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                    (b as char).to_string()
+                } else {
+                    format!("%{:02X}", b)
+                }
+            })
+            .collect();
+
+        format!(
+            "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+            fallback, encoded
+        )
+    }
+}
+
+/// Response listing a post's attachments
+#[derive(Debug, Serialize)]
+pub struct PostAttachmentListResponse {
+    pub attachments: Vec<PostAttachment>,
+    pub total: usize,
+}
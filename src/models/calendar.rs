@@ -0,0 +1,61 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Publication state of a post on the content calendar
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarEntryStatus {
+    Published,
+    Scheduled,
+    Draft,
+}
+
+/// A post placed on the content calendar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarEntry {
+    pub slug: String,
+    pub title: String,
+    pub category: Option<String>,
+    pub status: CalendarEntryStatus,
+    /// The timestamp that placed the post on this day
+    pub date: DateTime<Utc>,
+}
+
+/// All entries of a single day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    pub entries: Vec<CalendarEntry>,
+}
+
+/// Content calendar for one month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarMonth {
+    /// Month in `YYYY-MM` format
+    pub month: String,
+    pub previous_month: String,
+    pub next_month: String,
+    /// Days that have at least one entry, in ascending order
+    pub days: Vec<CalendarDay>,
+    pub published_count: usize,
+    pub scheduled_count: usize,
+    pub draft_count: usize,
+}
+
+/// Request body for scheduling a post
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchedulePostRequest {
+    /// Publication time; `null` clears the schedule
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Wall-clock publication time in the site timezone, as entered in the
+    /// admin form; used when `publish_at` is not given
+    #[serde(default)]
+    pub publish_at_local: Option<NaiveDateTime>,
+}
+
+/// Response types for calendar APIs
+#[derive(Debug, Serialize)]
+pub struct CalendarResponse {
+    pub success: bool,
+    pub data: CalendarMonth,
+}
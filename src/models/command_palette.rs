@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// What selecting a command palette entry does
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteEntryKind {
+    /// Open the editor of a post
+    Post,
+    /// Run an operation by sending `method` to `url`
+    Action,
+    /// Navigate to an admin or settings page
+    Page,
+}
+
+/// One searchable command palette entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaletteEntry {
+    /// Stable identifier, such as `action:reindex` or `post:<slug>`
+    pub id: String,
+    pub kind: PaletteEntryKind,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    pub url: String,
+    /// HTTP method for actions; pages and posts are opened with GET
+    pub method: String,
+    /// Extra words the entry can be found by, e.g. English names in a Japanese UI
+    #[serde(default, skip_serializing)]
+    pub keywords: Vec<String>,
+    /// Fuzzy match score against the query, higher is better
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<i64>,
+}
+
+/// Query parameters for the command palette
+#[derive(Debug, Default, Deserialize)]
+pub struct CommandPaletteQuery {
+    #[serde(default)]
+    pub q: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Command palette entries matching a query, best match first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommandPaletteResponse {
+    pub query: String,
+    pub entries: Vec<PaletteEntry>,
+    /// Entries that matched before `limit` was applied
+    pub total: usize,
+}
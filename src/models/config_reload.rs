@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of reloading the configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigReloadResponse {
+    /// Changed variables that took effect
+    pub applied: Vec<String>,
+    /// Changed variables that only take effect after a restart
+    pub restart_required: Vec<String>,
+    pub reloaded_at: DateTime<Utc>,
+}
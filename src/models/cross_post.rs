@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// External platform a post is cross-posted to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossPostProvider {
+    /// Committed as a Markdown file to the GitHub repository linked to Zenn
+    Zenn,
+    Qiita,
+    DevTo,
+}
+
+impl CrossPostProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossPostProvider::Zenn => "zenn",
+            CrossPostProvider::Qiita => "qiita",
+            CrossPostProvider::DevTo => "dev_to",
+        }
+    }
+}
+
+impl std::str::FromStr for CrossPostProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zenn" => Ok(CrossPostProvider::Zenn),
+            "qiita" => Ok(CrossPostProvider::Qiita),
+            "dev_to" => Ok(CrossPostProvider::DevTo),
+            other => Err(anyhow::anyhow!("Unknown cross-post provider: {}", other)),
+        }
+    }
+}
+
+/// Outcome of the last cross-post of a post to one provider
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CrossPostStatus {
+    Published,
+    Failed,
+}
+
+impl CrossPostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossPostStatus::Published => "published",
+            CrossPostStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for CrossPostStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "published" => Ok(CrossPostStatus::Published),
+            "failed" => Ok(CrossPostStatus::Failed),
+            other => Err(anyhow::anyhow!("Unknown cross-post status: {}", other)),
+        }
+    }
+}
+
+/// Copy of a post on an external platform
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CrossPost {
+    pub post_id: Uuid,
+    pub provider: CrossPostProvider,
+    /// Article ID on the platform, used to update the copy; kept when a later
+    /// update fails
+    pub remote_id: Option<String>,
+    pub remote_url: Option<String>,
+    pub status: CrossPostStatus,
+    /// Error of the last attempt when it failed
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response listing the cross-posts of a post
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CrossPostListResponse {
+    pub success: bool,
+    pub slug: String,
+    pub data: Vec<CrossPost>,
+}
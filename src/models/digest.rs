@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// A post published during the digest month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestPost {
+    pub title: String,
+    pub url: String,
+}
+
+/// Blog activity over one month, sent as the monthly digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyDigest {
+    /// Month in `YYYY-MM` format, in the site timezone
+    pub month: String,
+    pub site_title: String,
+    pub new_posts: Vec<DigestPost>,
+    /// Published posts edited during the month that were published before it
+    pub updated_posts: i64,
+    pub media_uploads: i64,
+    /// Broken links found by the last link check
+    pub broken_links: usize,
+    pub total_published: i64,
+}
+
+/// Query selecting the digest month
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DigestQuery {
+    /// `YYYY-MM`; the previous month when missing
+    pub month: Option<String>,
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::post::{ImportError, ImportSummary, LLMArticleImportResponse};
+
+/// Lifecycle of a background batch import
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Queued,
+    Running,
+    Completed,
+    /// The job queue gave up on the job; the results so far are kept
+    Failed,
+}
+
+/// Progress event of a batch import job, streamed over SSE in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImportJobEvent {
+    Started {
+        total: usize,
+    },
+    ArticleImported {
+        index: usize,
+        total: usize,
+        result: Box<LLMArticleImportResponse>,
+    },
+    ArticleFailed {
+        index: usize,
+        total: usize,
+        duplicate: bool,
+        error: ImportError,
+    },
+    Completed {
+        summary: ImportSummary,
+    },
+}
+
+impl ImportJobEvent {
+    /// SSE event name, matching the serialized `type`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImportJobEvent::Started { .. } => "started",
+            ImportJobEvent::ArticleImported { .. } => "article_imported",
+            ImportJobEvent::ArticleFailed { .. } => "article_failed",
+            ImportJobEvent::Completed { .. } => "completed",
+        }
+    }
+}
+
+/// Snapshot of a batch import job, returned by the polling endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportJob {
+    pub id: String,
+    pub status: ImportJobStatus,
+    pub total: usize,
+    pub processed: usize,
+    pub successful: Vec<LLMArticleImportResponse>,
+    pub failed: Vec<ImportError>,
+    pub duplicates_detected: usize,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ImportJob {
+    pub fn new(id: String, total: usize) -> Self {
+        Self {
+            id,
+            status: ImportJobStatus::Queued,
+            total,
+            processed: 0,
+            successful: Vec::new(),
+            failed: Vec::new(),
+            duplicates_detected: 0,
+            created_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+
+    /// Fold a progress event into the snapshot
+    pub fn apply(&mut self, event: &ImportJobEvent) {
+        match event {
+            ImportJobEvent::Started { .. } => self.status = ImportJobStatus::Running,
+            ImportJobEvent::ArticleImported { result, .. } => {
+                self.processed += 1;
+                self.successful.push(result.as_ref().clone());
+            }
+            ImportJobEvent::ArticleFailed {
+                duplicate, error, ..
+            } => {
+                self.processed += 1;
+                if *duplicate {
+                    self.duplicates_detected += 1;
+                }
+                self.failed.push(error.clone());
+            }
+            ImportJobEvent::Completed { .. } => {
+                self.status = ImportJobStatus::Completed;
+                self.finished_at = Some(Utc::now());
+            }
+        }
+    }
+
+    pub fn summary(&self) -> ImportSummary {
+        ImportSummary {
+            total_attempted: self.total,
+            successful: self.successful.len(),
+            failed: self.failed.len(),
+            duplicates_detected: self.duplicates_detected,
+        }
+    }
+}
+
+/// Response to starting a batch import job
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportJobCreatedResponse {
+    pub job_id: String,
+    pub status: ImportJobStatus,
+    pub total: usize,
+    /// Polling endpoint with the job's current snapshot
+    pub status_url: String,
+    /// Server-Sent Events stream of [`ImportJobEvent`]s
+    pub events_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_job_tracks_progress_events() {
+        let mut job = ImportJob::new("job".to_string(), 2);
+        job.apply(&ImportJobEvent::Started { total: 2 });
+        assert_eq!(job.status, ImportJobStatus::Running);
+
+        job.apply(&ImportJobEvent::ArticleFailed {
+            index: 0,
+            total: 2,
+            duplicate: true,
+            error: ImportError {
+                content_preview: "dup".to_string(),
+                error_message: "重複するコンテンツが検出されました".to_string(),
+                source: "custom".to_string(),
+            },
+        });
+        let summary = job.summary();
+        job.apply(&ImportJobEvent::Completed { summary });
+
+        assert_eq!(job.status, ImportJobStatus::Completed);
+        assert_eq!(job.processed, 1);
+        assert_eq!(job.summary().duplicates_detected, 1);
+        assert!(job.finished_at.is_some());
+    }
+}
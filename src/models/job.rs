@@ -0,0 +1,118 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// State of a job in the background queue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for a worker, possibly until `run_at` after a failed attempt
+    Queued,
+    Running,
+    Succeeded,
+    /// Failed on every attempt; kept for inspection and never retried
+    Dead,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Dead => "dead",
+        }
+    }
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(JobStatus::Queued),
+            "running" => Ok(JobStatus::Running),
+            "succeeded" => Ok(JobStatus::Succeeded),
+            "dead" => Ok(JobStatus::Dead),
+            other => Err(anyhow::anyhow!("Unknown job status: {}", other)),
+        }
+    }
+}
+
+/// A unit of background work persisted in the `jobs` table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Uuid,
+    /// Handler that runs the job, e.g. "dropbox_sync"
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    pub attempts: i64,
+    pub max_attempts: i64,
+    pub last_error: Option<String>,
+    /// Handler output of a succeeded job
+    pub result: Option<serde_json::Value>,
+    /// Latest progress reported by the handler, kept after the job finishes
+    pub progress: Option<serde_json::Value>,
+    /// Earliest time a worker may pick the job up
+    pub run_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Delay before retrying a job that has failed `attempts` times: doubling
+/// from `base`, capped at one hour
+pub fn retry_backoff(base: Duration, attempts: i64) -> Duration {
+    let exponent = (attempts.clamp(1, 20) - 1) as u32;
+    (base * 2i32.pow(exponent)).min(Duration::hours(1))
+}
+
+/// Query parameters for `GET /api/admin/jobs`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobFilters {
+    pub status: Option<JobStatus>,
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Response for `GET /api/admin/jobs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobListResponse {
+    pub jobs: Vec<Job>,
+    pub total: usize,
+}
+
+/// Response of endpoints that hand their work to the job queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEnqueuedResponse {
+    pub job_id: Uuid,
+    pub kind: String,
+    pub status: JobStatus,
+    /// Where to follow the job's progress and result
+    pub status_url: String,
+}
+
+impl From<&Job> for JobEnqueuedResponse {
+    fn from(job: &Job) -> Self {
+        Self {
+            job_id: job.id,
+            kind: job.kind.clone(),
+            status: job.status,
+            status_url: format!("/api/admin/jobs/{}", job.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff_doubles_up_to_an_hour() {
+        let base = Duration::seconds(30);
+        assert_eq!(retry_backoff(base, 1), Duration::seconds(30));
+        assert_eq!(retry_backoff(base, 3), Duration::seconds(120));
+        assert_eq!(retry_backoff(base, 50), Duration::hours(1));
+    }
+}
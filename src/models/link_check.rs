@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Whether a link points back into the blog or to another site
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkType {
+    Internal,
+    External,
+}
+
+impl LinkType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkType::Internal => "internal",
+            LinkType::External => "external",
+        }
+    }
+}
+
+impl std::str::FromStr for LinkType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "internal" => Ok(LinkType::Internal),
+            "external" => Ok(LinkType::External),
+            other => Err(anyhow::anyhow!("Unknown link type: {}", other)),
+        }
+    }
+}
+
+/// Result of checking a single link found in a post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCheck {
+    pub id: i64,
+    pub post_id: Uuid,
+    pub url: String,
+    pub link_type: LinkType,
+    pub status_code: Option<u16>,
+    pub is_broken: bool,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Link check result to be recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLinkCheck {
+    pub url: String,
+    pub link_type: LinkType,
+    pub status_code: Option<u16>,
+    pub is_broken: bool,
+    pub error: Option<String>,
+}
+
+/// Broken links grouped by post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLinkReport {
+    pub post_id: Uuid,
+    pub post_slug: String,
+    pub post_title: String,
+    pub broken_links: Vec<LinkCheck>,
+}
+
+/// Summary of a link check run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCheckSummary {
+    pub posts_checked: usize,
+    pub links_checked: usize,
+    pub broken_links: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Response types for link check APIs
+#[derive(Debug, Serialize)]
+pub struct BrokenLinkReportResponse {
+    pub success: bool,
+    pub data: Vec<BrokenLinkReport>,
+    pub total_broken: usize,
+}
+
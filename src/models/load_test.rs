@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Where the `load_test` binary writes its report unless `LOAD_TEST_REPORT` is set
+pub const DEFAULT_LOAD_TEST_REPORT: &str = "cache/load-test.json";
+
+/// Results of a `load_test` run, shown by `GET /api/performance/metrics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadTestReport {
+    pub finished_at: DateTime<Utc>,
+    /// Requests in flight at the same time per endpoint
+    pub concurrency: usize,
+    /// Posts in the fixture database
+    pub posts: usize,
+    pub endpoints: Vec<EndpointLoadStats>,
+}
+
+/// Latencies of one endpoint under load, in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointLoadStats {
+    pub name: String,
+    pub path: String,
+    pub requests: usize,
+    /// Requests answered with anything but 200
+    pub errors: usize,
+    pub requests_per_sec: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl EndpointLoadStats {
+    /// Summarize the request latencies (ms) measured over `elapsed`
+    #[allow(dead_code)] // Used by the load_test binary
+    pub fn from_latencies(
+        name: &str,
+        path: &str,
+        mut latencies: Vec<f64>,
+        errors: usize,
+        elapsed: Duration,
+    ) -> Self {
+        latencies.sort_by(|a, b| a.total_cmp(b));
+        let requests = latencies.len();
+        let mean_ms = if requests > 0 {
+            latencies.iter().sum::<f64>() / requests as f64
+        } else {
+            0.0
+        };
+        let seconds = elapsed.as_secs_f64();
+
+        Self {
+            name: name.to_string(),
+            path: path.to_string(),
+            requests,
+            errors,
+            requests_per_sec: if seconds > 0.0 {
+                requests as f64 / seconds
+            } else {
+                0.0
+            },
+            mean_ms,
+            p50_ms: percentile(&latencies, 50.0),
+            p95_ms: percentile(&latencies, 95.0),
+            p99_ms: percentile(&latencies, 99.0),
+            max_ms: latencies.last().copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentiles_use_nearest_rank() {
+        let latencies = (1..=100).rev().map(f64::from).collect();
+        let stats = EndpointLoadStats::from_latencies(
+            "list",
+            "/api/posts",
+            latencies,
+            2,
+            Duration::from_secs(2),
+        );
+
+        assert_eq!(stats.requests, 100);
+        assert_eq!(stats.requests_per_sec, 50.0);
+        assert_eq!(stats.p50_ms, 50.0);
+        assert_eq!(stats.p95_ms, 95.0);
+        assert_eq!(stats.p99_ms, 99.0);
+        assert_eq!(stats.max_ms, 100.0);
+        assert_eq!(stats.mean_ms, 50.5);
+    }
+}
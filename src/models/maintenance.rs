@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Current maintenance mode setting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    /// Sent as `Retry-After` with the 503 responses
+    pub retry_after_secs: u64,
+    /// Shown on the maintenance page instead of the default text
+    pub message: Option<String>,
+    /// When maintenance mode was last turned on
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Request to turn maintenance mode on or off
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateMaintenance {
+    pub enabled: bool,
+    /// Keeps the current value when omitted
+    pub retry_after_secs: Option<u64>,
+    /// Replaces the current message; omit or send an empty string for the default text
+    pub message: Option<String>,
+}
@@ -1,9 +1,19 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use super::attachment::PostAttachment;
+
+/// Limits on editable media metadata
+const MAX_ALT_TEXT_LENGTH: usize = 500;
+const MAX_CAPTION_LENGTH: usize = 1000;
+const MAX_FILENAME_LENGTH: usize = 255;
+const MAX_COLLECTION_LENGTH: usize = 100;
+
 /// Media file information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MediaFile {
     pub id: Uuid,
     pub filename: String,
@@ -18,21 +28,194 @@ pub struct MediaFile {
     pub thumbnail_url: Option<String>,
     pub alt_text: Option<String>,
     pub caption: Option<String>,
+    /// Hex SHA-256 of the uploaded bytes, used to detect duplicate uploads
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Playback length of videos, when extracted
+    #[serde(default)]
+    pub duration_seconds: Option<f64>,
+    /// Machine-suggested alt text awaiting review
+    #[serde(default)]
+    pub alt_text_suggestion: Option<String>,
+    /// Collection the file is filed under in the media library
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// Subject of the image that card and Open Graph crops keep in frame
+    #[serde(default)]
+    pub focal_point: Option<FocalPoint>,
+}
+
+impl MediaFile {
+    /// Apply a metadata update; empty strings clear a field
+    ///
+    /// Setting alt text explicitly or acting on the suggestion resolves any pending
+    /// suggestion.
+    pub fn apply_update(&mut self, update: UpdateMediaRequest) -> Result<(), String> {
+        let non_empty = |value: String| {
+            let value = value.trim().to_string();
+            (!value.is_empty()).then_some(value)
+        };
+
+        match update.alt_text_suggestion {
+            Some(AltTextSuggestionAction::Accept) => {
+                if update.alt_text.is_some() {
+                    return Err("Cannot both accept the suggestion and set alt_text".to_string());
+                }
+                let suggestion = self
+                    .alt_text_suggestion
+                    .take()
+                    .ok_or_else(|| "No alt text suggestion is pending".to_string())?;
+                self.alt_text = Some(suggestion);
+            }
+            Some(AltTextSuggestionAction::Reject) => self.alt_text_suggestion = None,
+            None => {}
+        }
+
+        if let Some(alt_text) = update.alt_text {
+            self.alt_text = non_empty(alt_text);
+            self.alt_text_suggestion = None;
+        }
+        if let Some(caption) = update.caption {
+            if caption.chars().count() > MAX_CAPTION_LENGTH {
+                return Err(format!(
+                    "Caption must be at most {} characters",
+                    MAX_CAPTION_LENGTH
+                ));
+            }
+            self.caption = non_empty(caption);
+        }
+        if let Some(filename) = update.filename {
+            let filename = filename.trim();
+            if filename.is_empty()
+                || filename.chars().count() > MAX_FILENAME_LENGTH
+                || filename.starts_with('.')
+                || filename.contains(['/', '\\'])
+                || filename.chars().any(char::is_control)
+            {
+                return Err(format!("Invalid filename '{}'", filename));
+            }
+            self.original_filename = filename.to_string();
+        }
+        if let Some(collection) = update.collection {
+            let collection = non_empty(collection);
+            if let Some(name) = &collection {
+                let valid = name.chars().count() <= MAX_COLLECTION_LENGTH
+                    && name
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' '));
+                if !valid {
+                    return Err(format!(
+                        "Collection names may only contain letters, digits, spaces, '-' and '_' (max {} characters)",
+                        MAX_COLLECTION_LENGTH
+                    ));
+                }
+            }
+            self.collection = collection;
+        }
+        if let Some(focal_point) = update.focal_point {
+            if !focal_point.is_valid() {
+                return Err(
+                    "Focal point coordinates must be percentages between 0 and 100".to_string(),
+                );
+            }
+            self.focal_point = Some(focal_point);
+        }
+        if self
+            .alt_text
+            .as_ref()
+            .is_some_and(|alt_text| alt_text.chars().count() > MAX_ALT_TEXT_LENGTH)
+        {
+            return Err(format!(
+                "Alt text must be at most {} characters",
+                MAX_ALT_TEXT_LENGTH
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// JSON body for pasting an image encoded as base64
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct MediaPasteRequest {
+    /// Base64 image data, or a `data:image/...;base64,` URL
+    pub data: String,
+    /// MIME type, required unless `data` is a data URL
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub alt: Option<String>,
+}
+
+/// Query parameters for pasting a raw image body
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct MediaPasteQuery {
+    pub filename: Option<String>,
+    pub alt: Option<String>,
+}
+
+/// Response for a pasted image, with Markdown ready to insert into the editor
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MediaPasteResponse {
+    /// `![alt](/media/...)`
+    pub markdown: String,
+    pub media: MediaFile,
+    /// An identical image was already uploaded and is reused
+    pub deduplicated: bool,
 }
 
 /// Response for media upload
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct MediaUploadResponse {
     pub success: bool,
     pub message: String,
     pub media: Option<MediaFile>,
     pub errors: Option<Vec<String>>,
+    /// Set when the upload was attached to a post with `attachment=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<PostAttachment>,
+}
+
+/// What to do with a pending alt text suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AltTextSuggestionAction {
+    /// Use the suggestion as the alt text
+    Accept,
+    /// Discard the suggestion
+    Reject,
+}
+
+/// Request body for updating media metadata
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMediaRequest {
+    /// New alt text; an edit also resolves any pending suggestion
+    pub alt_text: Option<String>,
+    pub caption: Option<String>,
+    /// Display filename; the stored file and its URL keep their name so links stay valid
+    pub filename: Option<String>,
+    pub collection: Option<String>,
+    pub alt_text_suggestion: Option<AltTextSuggestionAction>,
+    /// Point kept inside card and Open Graph crops, in percent of width and height
+    pub focal_point: Option<FocalPoint>,
+}
+
+/// Response for a single media file operation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MediaResponse {
+    pub success: bool,
+    pub message: String,
+    pub media: MediaFile,
 }
 
 /// Response for media list
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MediaListResponse {
     pub media: Vec<MediaFile>,
+    /// Number of posts using each listed file; unused files are omitted
+    pub usage_counts: HashMap<Uuid, i64>,
     pub total: usize,
     pub page: usize,
     pub per_page: usize,
@@ -40,13 +223,14 @@ pub struct MediaListResponse {
 }
 
 /// Query parameters for media listing
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct MediaQuery {
     pub page: Option<usize>,
     pub per_page: Option<usize>,
     pub folder: Option<String>,
     pub mime_type: Option<String>,
     pub search: Option<String>,
+    pub collection: Option<String>,
 }
 
 /// Media file filters for database queries
@@ -55,6 +239,7 @@ pub struct MediaFilters {
     pub folder: Option<String>,
     pub mime_type: Option<String>,
     pub search: Option<String>,
+    pub collection: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
@@ -73,6 +258,8 @@ pub struct CreateMediaFile {
     pub thumbnail_url: Option<String>,
     pub alt_text: Option<String>,
     pub caption: Option<String>,
+    pub content_hash: Option<String>,
+    pub duration_seconds: Option<f64>,
 }
 
 /// Supported media file types
@@ -126,6 +313,77 @@ impl Default for ThumbnailConfig {
     }
 }
 
+/// Renditions generated for featured images
+#[derive(Debug, Clone)]
+pub struct FeaturedImageConfig {
+    pub card_width: u32,
+    pub card_height: u32,
+    pub og_width: u32,
+    pub og_height: u32,
+}
+
+impl Default for FeaturedImageConfig {
+    fn default() -> Self {
+        Self {
+            card_width: 600,
+            card_height: 400,
+            og_width: 1200,
+            og_height: 630,
+        }
+    }
+}
+
+/// Point of interest kept inside crops, as percentages of width and height
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FocalPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Default for FocalPoint {
+    fn default() -> Self {
+        Self { x: 50.0, y: 50.0 }
+    }
+}
+
+impl FocalPoint {
+    /// Clamp both coordinates into the 0-100 range
+    pub fn clamped(self) -> Self {
+        Self {
+            x: self.x.clamp(0.0, 100.0),
+            y: self.y.clamp(0.0, 100.0),
+        }
+    }
+
+    /// Whether both coordinates are percentages within 0-100
+    pub fn is_valid(&self) -> bool {
+        [self.x, self.y]
+            .iter()
+            .all(|value| (0.0..=100.0).contains(value))
+    }
+}
+
+/// Featured image of a post with its cropped renditions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeaturedImage {
+    pub post_id: Uuid,
+    pub media_id: Uuid,
+    pub focal_point: FocalPoint,
+    /// Crop sized for post cards in listings
+    pub card_url: String,
+    /// Crop sized for Open Graph / social share images
+    pub og_url: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response for featured image assignment
+#[derive(Debug, Serialize)]
+pub struct FeaturedImageResponse {
+    pub success: bool,
+    pub message: String,
+    pub featured_image: FeaturedImage,
+}
+
 /// Image processing configuration
 #[derive(Debug, Clone)]
 pub struct ImageProcessingConfig {
@@ -135,6 +393,7 @@ pub struct ImageProcessingConfig {
     pub quality: u8,
     pub generate_thumbnail: bool,
     pub thumbnail_config: ThumbnailConfig,
+    pub featured_config: FeaturedImageConfig,
 }
 
 impl Default for ImageProcessingConfig {
@@ -145,6 +404,7 @@ impl Default for ImageProcessingConfig {
             quality: 85,
             generate_thumbnail: true,
             thumbnail_config: ThumbnailConfig::default(),
+            featured_config: FeaturedImageConfig::default(),
         }
     }
 }
@@ -156,6 +416,8 @@ pub struct MediaConstraints {
     pub allowed_mime_types: Vec<String>,
     pub max_width: Option<u32>,
     pub max_height: Option<u32>,
+    /// Accept SVG uploads (after sanitization); when false they are rejected outright
+    pub allow_svg: bool,
 }
 
 impl Default for MediaConstraints {
@@ -181,9 +443,126 @@ impl Default for MediaConstraints {
                 "application/pdf".to_string(),
                 "text/plain".to_string(),
                 "text/markdown".to_string(),
+                // Archives and datasets offered as post attachments
+                "application/zip".to_string(),
+                "application/gzip".to_string(),
+                "text/csv".to_string(),
+                "application/json".to_string(),
             ],
             max_width: Some(3840),  // 4K width
             max_height: Some(2160), // 4K height
+            allow_svg: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn media_with_suggestion() -> MediaFile {
+        MediaFile {
+            id: Uuid::new_v4(),
+            filename: "photo.jpg".to_string(),
+            original_filename: "photo.jpg".to_string(),
+            dropbox_path: "/BlogStorage/media/images/2024/07/photo.jpg".to_string(),
+            url: "/media/images/2024/07/photo.jpg".to_string(),
+            file_size: 1024,
+            mime_type: "image/jpeg".to_string(),
+            width: None,
+            height: None,
+            uploaded_at: Utc::now(),
+            thumbnail_url: None,
+            alt_text: None,
+            caption: Some("Old caption".to_string()),
+            content_hash: None,
+            duration_seconds: None,
+            alt_text_suggestion: Some("A red bicycle".to_string()),
+            collection: None,
+            focal_point: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_update_accepts_and_edits_suggestion() {
+        let mut media = media_with_suggestion();
+        media
+            .apply_update(UpdateMediaRequest {
+                alt_text: None,
+                caption: Some("  ".to_string()),
+                filename: Some("bicycle.jpg".to_string()),
+                collection: Some("travel".to_string()),
+                alt_text_suggestion: Some(AltTextSuggestionAction::Accept),
+                focal_point: Some(FocalPoint { x: 30.0, y: 25.0 }),
+            })
+            .unwrap();
+        assert_eq!(media.alt_text.as_deref(), Some("A red bicycle"));
+        assert_eq!(media.alt_text_suggestion, None);
+        assert_eq!(media.caption, None);
+        assert_eq!(media.original_filename, "bicycle.jpg");
+        assert_eq!(media.collection.as_deref(), Some("travel"));
+        assert_eq!(media.focal_point, Some(FocalPoint { x: 30.0, y: 25.0 }));
+
+        let mut media = media_with_suggestion();
+        media
+            .apply_update(UpdateMediaRequest {
+                alt_text: Some("A red bicycle by the river".to_string()),
+                caption: None,
+                filename: None,
+                collection: None,
+                alt_text_suggestion: None,
+                focal_point: None,
+            })
+            .unwrap();
+        assert_eq!(
+            media.alt_text.as_deref(),
+            Some("A red bicycle by the river")
+        );
+        assert_eq!(media.alt_text_suggestion, None);
+        assert_eq!(media.caption.as_deref(), Some("Old caption"));
+    }
+
+    #[test]
+    fn test_apply_update_rejects_invalid_values() {
+        let update =
+            |filename: Option<&str>,
+             collection: Option<&str>,
+             action: Option<AltTextSuggestionAction>| UpdateMediaRequest {
+                alt_text: None,
+                caption: None,
+                filename: filename.map(str::to_string),
+                collection: collection.map(str::to_string),
+                alt_text_suggestion: action,
+                focal_point: None,
+            };
+
+        let mut media = media_with_suggestion();
+        media.alt_text_suggestion = None;
+        assert!(media
+            .apply_update(update(None, None, Some(AltTextSuggestionAction::Accept)))
+            .is_err());
+
+        for filename in ["", "../etc/passwd", ".hidden", "a\\b.jpg"] {
+            assert!(media
+                .apply_update(update(Some(filename), None, None))
+                .is_err());
+        }
+        assert!(media
+            .apply_update(update(None, Some("weird/collection"), None))
+            .is_err());
+        for focal_point in [
+            FocalPoint { x: -1.0, y: 50.0 },
+            FocalPoint { x: 50.0, y: 100.5 },
+            FocalPoint {
+                x: f64::NAN,
+                y: 50.0,
+            },
+        ] {
+            let mut invalid = update(None, None, None);
+            invalid.focal_point = Some(focal_point);
+            assert!(media.apply_update(invalid).is_err());
         }
+        assert_eq!(media.focal_point, None);
+        assert_eq!(media.original_filename, "photo.jpg");
     }
 }
@@ -1,16 +1,60 @@
 // Models module for data structures
 
+pub mod activity;
+pub mod admin_event;
+pub mod announcement;
+pub mod attachment;
+pub mod calendar;
+pub mod command_palette;
+pub mod config_reload;
+pub mod cross_post;
+pub mod digest;
+pub mod import_job;
+pub mod job;
+pub mod link_check;
+pub mod load_test;
+pub mod maintenance;
 pub mod media;
 pub mod metadata;
+pub mod notification;
+pub mod podcast;
 pub mod post;
+pub mod quality;
 pub mod response;
+pub mod session;
+pub mod slow_log;
+pub mod storage_migration;
+pub mod sync;
 pub mod theme;
+pub mod totp;
 pub mod version;
 
+pub use activity::*;
+pub use admin_event::*;
+pub use announcement::*;
+pub use attachment::*;
+pub use calendar::*;
+pub use command_palette::*;
+pub use config_reload::*;
+pub use cross_post::*;
+pub use digest::*;
+pub use import_job::*;
+pub use job::*;
+pub use link_check::*;
+pub use load_test::*;
+pub use maintenance::*;
 pub use media::*;
 #[cfg(feature = "metadata")]
 pub use metadata::{BlogConfig, PostMetadata};
+pub use notification::*;
+pub use podcast::*;
 pub use post::*;
+pub use quality::*;
 pub use response::*;
+pub use session::*;
+pub use slow_log::*;
+pub use storage_migration::*;
+pub use sync::*;
 pub use theme::*;
+pub use totp::*;
 pub use version::*;
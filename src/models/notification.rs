@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Chat service a publishing notification is delivered to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationTarget {
+    Slack,
+    Discord,
+}
+
+/// Outcome of a notification for one target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationResult {
+    pub target: NotificationTarget,
+    pub delivered: bool,
+    pub error: Option<String>,
+}
+
+/// Response of the test notification endpoint
+#[derive(Debug, Serialize)]
+pub struct NotificationTestResponse {
+    pub success: bool,
+    pub results: Vec<NotificationResult>,
+}
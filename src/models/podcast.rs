@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Audio file attached to a post as a podcast episode enclosure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioEnclosure {
+    pub post_id: Uuid,
+    pub media_id: Uuid,
+    /// Public URL of the audio file
+    pub url: String,
+    pub file_size: u64,
+    pub mime_type: String,
+    pub duration_seconds: Option<f64>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AudioEnclosure {
+    /// Duration formatted as `HH:MM:SS` for `itunes:duration`
+    pub fn formatted_duration(&self) -> Option<String> {
+        self.duration_seconds.map(|seconds| {
+            let total = seconds.round().max(0.0) as u64;
+            format!(
+                "{:02}:{:02}:{:02}",
+                total / 3600,
+                (total % 3600) / 60,
+                total % 60
+            )
+        })
+    }
+}
+
+/// Request body for attaching audio to a post
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttachAudioRequest {
+    pub media_id: Uuid,
+    /// Overrides the duration extracted at upload time
+    pub duration_seconds: Option<f64>,
+    /// Overrides the media file's MIME type
+    pub mime_type: Option<String>,
+}
+
+/// Response for audio attachment operations
+#[derive(Debug, Serialize)]
+pub struct AudioEnclosureResponse {
+    pub success: bool,
+    pub message: String,
+    pub audio: Option<AudioEnclosure>,
+}
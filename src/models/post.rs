@@ -1,8 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::FromRow;
+use std::collections::HashMap;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::models::{PostVersion, QualityCheckResults};
+
 /// Blog post entity for database storage
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Post {
@@ -22,6 +27,158 @@ pub struct Post {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
+    /// Scheduled publication time for unpublished posts
+    #[serde(default)]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Media file used as the post's featured image
+    #[serde(default)]
+    pub featured_media_id: Option<Uuid>,
+    /// Number of words in the markdown body, see [`count_words`]
+    #[serde(default)]
+    pub word_count: i64,
+    /// YAML mapping of source frontmatter keys the blog doesn't use
+    #[serde(default)]
+    pub custom_frontmatter: Option<String>,
+    /// Shown above the chronological stream on the home page and in listings
+    #[serde(default)]
+    pub pinned: bool,
+    /// Position among pinned posts, lowest first; unordered pins follow
+    #[serde(default)]
+    pub pin_order: Option<i64>,
+    /// Short generated summary for post lists
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Who can see the post once it is published
+    #[serde(default)]
+    pub visibility: PostVisibility,
+    /// Argon2 hash of the password readers must enter; never serialized
+    #[serde(skip_serializing, default)]
+    pub password_hash: Option<String>,
+    /// Time after which the scheduler applies `expiry_action`
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub expiry_action: ExpiryAction,
+    /// Days before `expires_at` from which the post page warns that the
+    /// content may be outdated; `None` shows no warning
+    #[serde(default)]
+    pub expiry_notice_days: Option<i64>,
+    /// Render the public changelog built from version summaries on the post page
+    #[serde(default)]
+    pub show_changelog: bool,
+    /// URL of the original when the post is a cross-post; replaces the post's
+    /// own URL as `rel=canonical`
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// URLs where the post is also published, JSON array stored as string
+    #[serde(default)]
+    pub syndication_links: Option<String>,
+    /// [`content_hash`] of the title and body, refreshed whenever they are written
+    #[serde(default)]
+    pub content_hash: String,
+    /// Last change made through [`Post::update_metadata`], which leaves
+    /// `updated_at` and the version alone
+    #[serde(default)]
+    pub metadata_updated_at: Option<DateTime<Utc>>,
+    /// Version readers see on the post page while newer drafts accumulate;
+    /// `None` shows the latest version
+    #[serde(default)]
+    pub published_version: Option<i32>,
+}
+
+/// What happens to a published post once its `expires_at` time has passed
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum ExpiryAction {
+    /// Take the post offline
+    #[default]
+    Unpublish,
+    /// Keep the post at its URL, unlisted and marked as outdated
+    Archive,
+}
+
+impl ExpiryAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpiryAction::Unpublish => "unpublish",
+            ExpiryAction::Archive => "archive",
+        }
+    }
+}
+
+impl std::str::FromStr for ExpiryAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unpublish" => Ok(ExpiryAction::Unpublish),
+            "archive" => Ok(ExpiryAction::Archive),
+            other => Err(anyhow::anyhow!("Unknown expiry action: {}", other)),
+        }
+    }
+}
+
+/// Audience of a published post
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum PostVisibility {
+    #[default]
+    Public,
+    /// Reachable by its URL but left out of lists, search and feeds
+    Unlisted,
+    /// Only readers with the API key can see it
+    Private,
+}
+
+impl PostVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostVisibility::Public => "public",
+            PostVisibility::Unlisted => "unlisted",
+            PostVisibility::Private => "private",
+        }
+    }
+
+    /// Visibilities that appear in lists, search and feeds for a reader
+    pub fn listed(authorized: bool) -> Vec<PostVisibility> {
+        if authorized {
+            vec![PostVisibility::Public, PostVisibility::Private]
+        } else {
+            vec![PostVisibility::Public]
+        }
+    }
+}
+
+impl std::str::FromStr for PostVisibility {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "public" => Ok(PostVisibility::Public),
+            "unlisted" => Ok(PostVisibility::Unlisted),
+            "private" => Ok(PostVisibility::Private),
+            other => Err(anyhow::anyhow!("Unknown post visibility: {}", other)),
+        }
+    }
+}
+
+/// Frontmatter key of [`Post::canonical_url`]
+pub const CANONICAL_URL_KEY: &str = "canonical_url";
+
+/// Frontmatter key of [`Post::syndication_links`]
+pub const SYNDICATION_LINKS_KEY: &str = "syndication_links";
+
+/// Whether `url` is an absolute http(s) URL, as canonical and syndication
+/// links must be
+pub fn is_absolute_http_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://"))
+        && !url.contains(char::is_whitespace)
 }
 
 /// Post creation data
@@ -40,6 +197,15 @@ pub struct CreatePost {
     pub dropbox_path: String,
 }
 
+/// Tombstone of a deleted post, whose URL answers 410 Gone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GonePost {
+    pub slug: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: DateTime<Utc>,
+}
+
 /// Post update data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdatePost {
@@ -55,6 +221,16 @@ pub struct UpdatePost {
     pub dropbox_path: Option<String>,
 }
 
+/// Changes to the category, tags and flags of a post that leave its content alone
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdatePostMetadata {
+    /// An empty string clears the category
+    pub category: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub featured: Option<bool>,
+    pub published: Option<bool>,
+}
+
 /// Post query filters
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PostFilters {
@@ -63,13 +239,50 @@ pub struct PostFilters {
     pub tag: Option<String>,
     pub author: Option<String>,
     pub featured: Option<bool>,
+    #[serde(default)]
+    pub pinned: Option<bool>,
     pub search: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Order pinned posts first, by `pin_order`, before the chronological stream
+    #[serde(default)]
+    pub pinned_first: bool,
+    /// Only posts with one of these visibilities; all posts when unset
+    #[serde(default)]
+    pub visibility: Option<Vec<PostVisibility>>,
+    /// Only posts with (`true`) or without (`false`) a password
+    #[serde(default)]
+    pub password_protected: Option<bool>,
+}
+
+/// Filters combined with a full-text search query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    pub published: Option<bool>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub author: Option<String>,
+    /// Inclusive lower bound on the publication date (creation date for drafts)
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on the publication date (creation date for drafts)
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Only posts with one of these visibilities; all posts when unset
+    #[serde(default)]
+    pub visibility: Option<Vec<PostVisibility>>,
+}
+
+/// A full-text search match with a highlighted excerpt of the matching text
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub post: Post,
+    /// HTML-escaped excerpt with the matched terms wrapped in `<mark>`
+    pub snippet_html: String,
 }
 
 /// Post statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PostStats {
     pub total_posts: i64,
     pub published_posts: i64,
@@ -80,21 +293,268 @@ pub struct PostStats {
 }
 
 /// Category statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CategoryStat {
     pub name: String,
     pub count: i64,
 }
 
 /// Tag statistics  
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TagStat {
     pub name: String,
     pub count: i64,
 }
 
-/// LLM記事インポートリクエスト
+/// Request body for pinning or unpinning a post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinPostRequest {
+    pub pinned: bool,
+    /// Position among pinned posts, lowest first
+    pub pin_order: Option<i64>,
+}
+
+/// Request body for changing who can see a post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetVisibilityRequest {
+    pub visibility: PostVisibility,
+}
+
+/// Request body for setting or clearing the expiry of a post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetPostExpiryRequest {
+    /// Expiry time; `null` clears the expiry
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Wall-clock expiry time in the site timezone; used when `expires_at`
+    /// is not given
+    #[serde(default)]
+    pub expires_at_local: Option<chrono::NaiveDateTime>,
+    #[serde(default)]
+    pub action: ExpiryAction,
+    /// Days before expiry to show the outdated content banner
+    #[serde(default)]
+    pub notice_days: Option<i64>,
+}
+
+/// Request body for setting or removing a post's password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetPostPasswordRequest {
+    /// New password; `null` removes the protection
+    pub password: Option<String>,
+}
+
+/// Query parameters for regenerating a post's excerpt
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegenerateExcerptQuery {
+    /// Also ask the configured language model for a summary
+    #[serde(default)]
+    pub summary: bool,
+}
+
+/// Request body for regenerating excerpts of many posts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkRegenerateExcerptRequest {
+    /// Posts to process; all posts when omitted
+    pub slugs: Option<Vec<String>>,
+    /// Skip posts that already have an excerpt
+    #[serde(default = "default_true")]
+    pub missing_only: bool,
+    #[serde(default)]
+    pub summary: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Regenerated derived fields of a post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegeneratedExcerpt {
+    pub slug: String,
+    pub excerpt: Option<String>,
+    pub word_count: i64,
+    pub reading_time: i64,
+    pub summary: Option<String>,
+}
+
+/// Result of a bulk excerpt regeneration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkRegenerateExcerptResponse {
+    pub success: bool,
+    pub regenerated: Vec<RegeneratedExcerpt>,
+    pub skipped: usize,
+    pub errors: Vec<String>,
+}
+
+/// Request body for renaming a tag across all posts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameTagRequest {
+    pub from: String,
+    pub to: String,
+}
+
+/// Request body for merging several tags into one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeTagsRequest {
+    pub sources: Vec<String>,
+    pub target: String,
+}
+
+/// Result of a tag rename, merge or delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagOperationResponse {
+    pub success: bool,
+    pub message: String,
+    pub affected_posts: Vec<String>,
+}
+
+/// Alternative spelling of a tag or category, e.g. "JS" for "javascript"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagAlias {
+    /// Lookup key, stored in its normalized lowercase form
+    pub alias: String,
+    pub canonical: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for creating or replacing a tag alias
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTagAliasRequest {
+    pub alias: String,
+    pub canonical: String,
+}
+
+/// Response for `GET /api/admin/tag-aliases`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagAliasListResponse {
+    pub aliases: Vec<TagAlias>,
+    pub total: usize,
+}
+
+/// Description shown for a category on the category index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryDescription {
+    pub category: String,
+    pub description: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for setting the description of a category
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetCategoryDescriptionRequest {
+    pub description: String,
+}
+
+/// Response for `GET /api/admin/category-descriptions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryDescriptionListResponse {
+    pub descriptions: Vec<CategoryDescription>,
+    pub total: usize,
+}
+
+/// Category with its post count and description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryIndexEntry {
+    pub name: String,
+    pub count: i64,
+    pub description: Option<String>,
+}
+
+/// Rules applied to tag and category names on every post write
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TagNormalization {
+    pub lowercase_tags: bool,
+    pub lowercase_categories: bool,
+}
+
+impl TagNormalization {
+    /// Key under which an alias is stored and looked up
+    pub fn alias_key(name: &str) -> String {
+        collapse_whitespace(name).to_lowercase()
+    }
+
+    /// Normalize a tag: trim, collapse inner whitespace, resolve aliases, then lowercase
+    pub fn tag(&self, name: &str, aliases: &HashMap<String, String>) -> String {
+        Self::apply(name, aliases, self.lowercase_tags)
+    }
+
+    /// Normalize a category with the same steps as tags
+    pub fn category(&self, name: &str, aliases: &HashMap<String, String>) -> String {
+        Self::apply(name, aliases, self.lowercase_categories)
+    }
+
+    /// Normalize a tag list, dropping empty and duplicate tags
+    pub fn tags(&self, tags: &[String], aliases: &HashMap<String, String>) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = self.tag(tag, aliases);
+            if !tag.is_empty() && !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        normalized
+    }
+
+    fn apply(name: &str, aliases: &HashMap<String, String>, lowercase: bool) -> String {
+        let name = collapse_whitespace(name);
+        let name = aliases.get(&name.to_lowercase()).cloned().unwrap_or(name);
+        if lowercase {
+            name.to_lowercase()
+        } else {
+            name
+        }
+    }
+}
+
+fn collapse_whitespace(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Consistency of the `posts_fts` search index against the posts table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexStatus {
+    pub post_count: i64,
+    pub indexed_count: i64,
+    /// Posts that are missing from the index
+    pub missing_slugs: Vec<String>,
+    /// Index entries whose post no longer exists
+    pub orphaned_entries: i64,
+    /// Result of FTS5's own integrity check against the content table
+    pub integrity_ok: bool,
+    pub consistent: bool,
+}
+
+/// Query parameters for the search index rebuild endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReindexQuery {
+    /// Only report the consistency check without rebuilding
+    #[serde(default)]
+    pub check_only: bool,
+    pub batch_size: Option<i64>,
+}
+
+/// Result of a search index rebuild or consistency check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexResponse {
+    pub success: bool,
+    pub message: String,
+    pub rebuilt: bool,
+    pub indexed_posts: i64,
+    pub before: SearchIndexStatus,
+    pub after: Option<SearchIndexStatus>,
+}
+
+/// Result of recounting the materialized post stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsRebuildResponse {
+    pub success: bool,
+    /// Whether the stored counts differed from the recount
+    pub drifted: bool,
+    pub stats: PostStats,
+}
+
+/// LLM記事インポートリクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LLMArticleImportRequest {
     pub content: String,
     pub suggested_title: Option<String>,
@@ -103,10 +563,13 @@ pub struct LLMArticleImportRequest {
     pub source: String, // "chatgpt", "claude", "custom"
     pub published: Option<bool>,
     pub featured: Option<bool>,
+    /// Save even if the import quality gates fail (requires the admin API key)
+    #[serde(default)]
+    pub override_quality_gates: bool,
 }
 
 /// LLM記事インポートレスポンス
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LLMArticleImportResponse {
     pub slug: String,
     pub suggested_metadata: LLMSuggestedMetadata,
@@ -114,10 +577,13 @@ pub struct LLMArticleImportResponse {
     pub html_content: String,
     pub preview_url: String,
     pub dropbox_path: String,
+    /// Proofreading results, present when the proofreading step is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<QualityCheckResults>,
 }
 
 /// LLM記事の提案メタデータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LLMSuggestedMetadata {
     pub title: String,
     pub excerpt: Option<String>,
@@ -128,23 +594,15 @@ pub struct LLMSuggestedMetadata {
 }
 
 /// バッチインポート用のリクエスト
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct BatchImportRequest {
     pub articles: Vec<LLMArticleImportRequest>,
     pub default_category: Option<String>,
     pub default_published: Option<bool>,
 }
 
-/// バッチインポート用のレスポンス
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BatchImportResponse {
-    pub successful: Vec<LLMArticleImportResponse>,
-    pub failed: Vec<ImportError>,
-    pub summary: ImportSummary,
-}
-
 /// インポートエラー
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ImportError {
     pub content_preview: String, // 最初の100文字
     pub error_message: String,
@@ -166,6 +624,8 @@ impl Post {
     pub fn new(data: CreatePost) -> Self {
         let now = Utc::now();
         let published_at = if data.published { Some(now) } else { None };
+        let word_count = count_words(&data.content);
+        let content_hash = content_hash(&data.title, &data.content);
 
         Self {
             id: Uuid::new_v4(),
@@ -184,6 +644,134 @@ impl Post {
             created_at: now,
             updated_at: now,
             published_at,
+            publish_at: None,
+            featured_media_id: None,
+            word_count,
+            custom_frontmatter: None,
+            pinned: false,
+            pin_order: None,
+            summary: None,
+            visibility: PostVisibility::Public,
+            password_hash: None,
+            expires_at: None,
+            expiry_action: ExpiryAction::Unpublish,
+            expiry_notice_days: None,
+            show_changelog: false,
+            canonical_url: None,
+            syndication_links: None,
+            content_hash,
+            metadata_updated_at: None,
+            published_version: None,
+        }
+    }
+
+    /// Whether readers must enter a password to see the post body
+    pub fn is_password_protected(&self) -> bool {
+        self.password_hash.is_some()
+    }
+
+    /// Weak ETag for a rendering made from the post alone, e.g. its markdown
+    /// source or PDF; full pages hash their rendered HTML instead
+    ///
+    /// Built from the content hash and the metadata shown next to the body,
+    /// so pinning, scheduling or re-syncing an unchanged file keeps cached
+    /// copies valid.
+    pub fn etag(&self, variant: &str) -> String {
+        let featured_media_id = self
+            .featured_media_id
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        let published_at = self
+            .published_at
+            .map(|published_at| published_at.to_rfc3339())
+            .unwrap_or_default();
+        let mut hasher = Sha256::new();
+        for part in [
+            self.content_hash.as_str(),
+            variant,
+            self.excerpt.as_deref().unwrap_or_default(),
+            self.category.as_deref().unwrap_or_default(),
+            &self.tags,
+            self.author.as_deref().unwrap_or_default(),
+            &featured_media_id,
+            &published_at,
+            self.canonical_url.as_deref().unwrap_or_default(),
+            if self.show_changelog { "changelog" } else { "" },
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("W/\"{}\"", &format!("{:x}", hasher.finalize())[..16])
+    }
+
+    /// Whether the post page warns readers that the content may be outdated:
+    /// inside the notice window before expiry, or after being archived
+    pub fn shows_outdated_notice(&self, now: DateTime<Utc>) -> bool {
+        let Some(expires_at) = self.expires_at else {
+            return false;
+        };
+        if now >= expires_at {
+            return self.expiry_action == ExpiryAction::Archive;
+        }
+        self.expiry_notice_days
+            .is_some_and(|days| now >= expires_at - chrono::Duration::days(days))
+    }
+
+    /// Estimated reading time in minutes
+    pub fn reading_time(&self) -> i64 {
+        reading_time_minutes(self.word_count)
+    }
+
+    /// Custom frontmatter to write back to the source file, with the
+    /// cross-posting fields as currently stored
+    pub fn custom_frontmatter_map(&self) -> serde_yaml::Mapping {
+        let mut map: serde_yaml::Mapping = self
+            .custom_frontmatter
+            .as_deref()
+            .and_then(|yaml| serde_yaml::from_str(yaml).ok())
+            .unwrap_or_default();
+
+        map.remove(CANONICAL_URL_KEY);
+        map.remove(SYNDICATION_LINKS_KEY);
+        if let Some(canonical_url) = &self.canonical_url {
+            map.insert(CANONICAL_URL_KEY.into(), canonical_url.as_str().into());
+        }
+        let links = self.get_syndication_links();
+        if !links.is_empty() {
+            map.insert(
+                SYNDICATION_LINKS_KEY.into(),
+                serde_yaml::Value::Sequence(links.into_iter().map(Into::into).collect()),
+            );
+        }
+        map
+    }
+
+    /// Get the URLs where the post is also published
+    pub fn get_syndication_links(&self) -> Vec<String> {
+        self.syndication_links
+            .as_deref()
+            .and_then(|links| serde_json::from_str(links).ok())
+            .unwrap_or_default()
+    }
+
+    /// Set the URLs where the post is also published
+    pub fn set_syndication_links(&mut self, links: Vec<String>) {
+        self.syndication_links = if links.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&links).ok()
+        };
+    }
+
+    /// Series the post belongs to, from the `series` key of its frontmatter
+    pub fn series(&self) -> Option<String> {
+        let map: serde_yaml::Mapping =
+            serde_yaml::from_str(self.custom_frontmatter.as_deref()?).ok()?;
+        match map.get("series")? {
+            serde_yaml::Value::String(series) if !series.trim().is_empty() => {
+                Some(series.trim().to_string())
+            }
+            _ => None,
         }
     }
 
@@ -198,6 +786,34 @@ impl Post {
         self.tags = serde_json::to_string(&tags).unwrap_or_default();
     }
 
+    /// Replace every tag in `sources` with `target`, or drop them when `target` is `None`
+    ///
+    /// Duplicates introduced by the replacement are removed. Returns whether the tags changed.
+    pub fn replace_tags(&mut self, sources: &[String], target: Option<&str>) -> bool {
+        let tags = self.get_tags();
+        if !tags.iter().any(|tag| sources.contains(tag)) {
+            return false;
+        }
+
+        let mut rewritten: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = if sources.contains(&tag) {
+                match target {
+                    Some(target) => target.to_string(),
+                    None => continue,
+                }
+            } else {
+                tag
+            };
+            if !rewritten.contains(&tag) {
+                rewritten.push(tag);
+            }
+        }
+
+        self.set_tags(rewritten);
+        true
+    }
+
     /// Update post data
     #[allow(dead_code)]
     pub fn update(&mut self, data: UpdatePost) {
@@ -205,6 +821,7 @@ impl Post {
             self.title = title;
         }
         if let Some(content) = data.content {
+            self.word_count = count_words(&content);
             self.content = content;
         }
         if let Some(html_content) = data.html_content {
@@ -237,10 +854,49 @@ impl Post {
             self.dropbox_path = dropbox_path;
         }
 
+        self.content_hash = content_hash(&self.title, &self.content);
         self.updated_at = Utc::now();
         self.version += 1;
     }
 
+    /// Change category, tags and flags without touching the content,
+    /// `updated_at` or the version number
+    pub fn update_metadata(&mut self, data: UpdatePostMetadata) {
+        if let Some(category) = data.category {
+            self.category = Some(category).filter(|category| !category.is_empty());
+        }
+        if let Some(tags) = data.tags {
+            self.set_tags(tags);
+        }
+        if let Some(published) = data.published {
+            if published && !self.published {
+                self.published_at = Some(Utc::now());
+            } else if !published {
+                self.published_at = None;
+            }
+            self.published = published;
+        }
+        if let Some(featured) = data.featured {
+            self.featured = featured;
+        }
+
+        self.metadata_updated_at = Some(Utc::now());
+    }
+
+    /// Replace the title, body, excerpt, category and tags with those saved
+    /// in `version`, as the page shows them while a version is pinned
+    pub fn show_version(&mut self, version: &PostVersion) {
+        self.title = version.title.clone();
+        self.content = version.content.clone();
+        self.html_content = version.html_content.clone();
+        self.excerpt = version.excerpt.clone();
+        self.category = version.category.clone();
+        self.set_tags(version.tags.clone());
+        self.word_count = count_words(&self.content);
+        self.content_hash = content_hash(&self.title, &self.content);
+        self.version = version.version;
+    }
+
     /// Check if post is published
     #[allow(dead_code)]
     pub fn is_published(&self) -> bool {
@@ -258,12 +914,162 @@ impl Post {
         let year = self.created_at.format("%Y");
         format!("/posts/{}/{}", year, self.slug)
     }
+
+    /// Link to the post's file in the Dropbox web UI, if it lives under `blog_root`
+    pub fn dropbox_web_url(&self, blog_root: &str) -> Option<String> {
+        dropbox_web_url(&self.dropbox_path, blog_root)
+    }
+}
+
+/// Reading speed for reading time estimates; [`count_words`] counts each CJK
+/// character as a word, so this matches Japanese text read at a relaxed pace
+pub const WORDS_PER_MINUTE: i64 = 400;
+
+/// Estimated minutes to read `word_count` words, at least one
+pub fn reading_time_minutes(word_count: i64) -> i64 {
+    ((word_count + WORDS_PER_MINUTE - 1) / WORDS_PER_MINUTE).max(1)
+}
+
+/// SHA-256 of a post's title and markdown body, hex encoded
+///
+/// Unlike `updated_at`, which also moves on metadata edits and on re-syncs
+/// of unchanged files, it only changes when the text readers see changes.
+pub fn content_hash(title: &str, content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(title.as_bytes());
+    hasher.update([0]);
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Count words in markdown, taking each CJK character as one word since
+/// Japanese and Chinese text isn't separated by spaces
+pub fn count_words(text: &str) -> i64 {
+    let mut count = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            count += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else if c.is_whitespace() {
+            in_word = false;
+        }
+    }
+    count
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // Hiragana and Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    )
+}
+
+/// Build `https://www.dropbox.com/home{path}` for a file inside `blog_root`.
+///
+/// Relative paths, `.`/`..` segments and paths outside the root yield `None`.
+/// Dropbox paths are case-insensitive, so the root is compared ignoring case.
+pub fn dropbox_web_url(dropbox_path: &str, blog_root: &str) -> Option<String> {
+    let root = blog_root.trim_end_matches('/');
+    let segments: Vec<&str> = dropbox_path.strip_prefix('/')?.split('/').collect();
+    if segments
+        .iter()
+        .any(|s| s.is_empty() || *s == "." || *s == ".." || s.chars().any(char::is_control))
+    {
+        return None;
+    }
+
+    let within_root = root.is_empty()
+        || dropbox_path
+            .get(..root.len() + 1)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{}/", root)));
+    if !within_root {
+        return None;
+    }
+
+    let encoded: String = segments
+        .iter()
+        .map(|segment| format!("/{}", encode_path_segment(segment)))
+        .collect();
+    Some(format!("https://www.dropbox.com/home{}", encoded))
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_count_words_handles_japanese() {
+        assert_eq!(count_words("# Hello, world!\n\nRust's `async` code"), 5);
+        assert_eq!(count_words("日本語の文章"), 6);
+        assert_eq!(count_words("Rustで書く"), 4);
+        assert_eq!(count_words(""), 0);
+        assert_eq!(reading_time_minutes(0), 1);
+        assert_eq!(reading_time_minutes(401), 2);
+    }
+
+    #[test]
+    fn test_tag_normalization_resolves_aliases() {
+        let aliases = HashMap::from([("js".to_string(), "javascript".to_string())]);
+        let rules = TagNormalization {
+            lowercase_tags: true,
+            lowercase_categories: false,
+        };
+
+        assert_eq!(
+            rules.tags(
+                &[
+                    " JS ".to_string(),
+                    "JavaScript".to_string(),
+                    "Web  Dev".to_string(),
+                    "  ".to_string(),
+                ],
+                &aliases
+            ),
+            vec!["javascript", "web dev"]
+        );
+        assert_eq!(rules.category(" js", &aliases), "javascript");
+        assert_eq!(rules.category("Tech ", &aliases), "Tech");
+    }
+
+    #[test]
+    fn test_dropbox_web_url_validates_blog_root() {
+        assert_eq!(
+            dropbox_web_url("/BlogStorage/posts/2024/日記 1.md", "/BlogStorage"),
+            Some(
+                "https://www.dropbox.com/home/BlogStorage/posts/2024/%E6%97%A5%E8%A8%98%201.md"
+                    .to_string()
+            )
+        );
+        assert!(dropbox_web_url("/blogstorage/posts/a.md", "/BlogStorage/").is_some());
+        assert!(dropbox_web_url("/Other/posts/a.md", "/BlogStorage").is_none());
+        assert!(dropbox_web_url("/BlogStorageX/a.md", "/BlogStorage").is_none());
+        assert!(dropbox_web_url("/BlogStorage/../secret.md", "/BlogStorage").is_none());
+        assert!(dropbox_web_url("BlogStorage/posts/a.md", "/BlogStorage").is_none());
+        assert!(dropbox_web_url("", "/BlogStorage").is_none());
+    }
+
     #[test]
     fn test_post_creation() {
         let create_data = CreatePost {
@@ -329,6 +1135,107 @@ mod tests {
         assert_eq!(post.get_tags(), vec!["updated"]);
     }
 
+    #[test]
+    fn test_content_hash_ignores_metadata_edits() {
+        let mut post = Post::new(CreatePost {
+            slug: "hash".to_string(),
+            title: "Hash".to_string(),
+            content: "Body".to_string(),
+            html_content: "<p>Body</p>".to_string(),
+            excerpt: None,
+            category: None,
+            tags: vec![],
+            published: false,
+            featured: false,
+            author: None,
+            dropbox_path: "/posts/hash.md".to_string(),
+        });
+        let original_hash = post.content_hash.clone();
+        assert_eq!(original_hash, content_hash("Hash", "Body"));
+
+        post.update(UpdatePost {
+            featured: Some(true),
+            dropbox_path: Some("/posts/moved.md".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(post.content_hash, original_hash);
+        let etag = post.etag("default");
+
+        post.pinned = true;
+        post.version += 1;
+        assert_eq!(post.etag("default"), etag);
+        assert_ne!(post.etag("modern"), etag);
+
+        post.update(UpdatePost {
+            content: Some("New body".to_string()),
+            ..Default::default()
+        });
+        assert_ne!(post.content_hash, original_hash);
+        assert_ne!(post.etag("default"), etag);
+    }
+
+    #[test]
+    fn test_update_metadata_keeps_content_timestamps() {
+        let mut post = Post::new(CreatePost {
+            slug: "meta".to_string(),
+            title: "Meta".to_string(),
+            content: "Body".to_string(),
+            html_content: "<p>Body</p>".to_string(),
+            excerpt: None,
+            category: Some("rust".to_string()),
+            tags: vec![],
+            published: false,
+            featured: false,
+            author: None,
+            dropbox_path: "/posts/meta.md".to_string(),
+        });
+        let updated_at = post.updated_at;
+
+        post.update_metadata(UpdatePostMetadata {
+            category: Some(String::new()),
+            tags: Some(vec!["web".to_string()]),
+            published: Some(true),
+            ..Default::default()
+        });
+
+        assert_eq!(post.category, None);
+        assert_eq!(post.get_tags(), vec!["web"]);
+        assert!(post.published && post.published_at.is_some());
+        assert_eq!(post.updated_at, updated_at);
+        assert_eq!(post.version, 1);
+        assert!(post.metadata_updated_at.is_some());
+    }
+
+    #[test]
+    fn test_replace_tags() {
+        let mut post = Post::new(CreatePost {
+            slug: "tags".to_string(),
+            title: "Tags".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            excerpt: None,
+            category: None,
+            tags: vec![
+                "js".to_string(),
+                "web".to_string(),
+                "javascript".to_string(),
+            ],
+            published: true,
+            featured: false,
+            author: None,
+            dropbox_path: "/posts/tags.md".to_string(),
+        });
+
+        let sources = vec!["js".to_string()];
+        assert!(post.replace_tags(&sources, Some("javascript")));
+        assert_eq!(post.get_tags(), vec!["javascript", "web"]);
+
+        assert!(!post.replace_tags(&sources, Some("javascript")));
+
+        assert!(post.replace_tags(&["web".to_string()], None));
+        assert_eq!(post.get_tags(), vec!["javascript"]);
+    }
+
     #[test]
     fn test_url_path_generation() {
         let create_data = CreatePost {
@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::post::{count_words, LLMArticleImportResponse};
+
+/// Severity of a proofreading finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QualitySeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single proofreading finding
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QualityWarning {
+    pub rule: String,
+    pub message: String,
+    pub line: Option<usize>,
+    pub severity: QualitySeverity,
+}
+
+/// Results of the proofreading step run during import/preview
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct QualityCheckResults {
+    pub passed: bool,
+    pub warnings: Vec<QualityWarning>,
+    /// Names of the linters that ran (e.g. "builtin", "textlint")
+    pub checked_by: Vec<String>,
+}
+
+impl QualityCheckResults {
+    /// Number of findings with error severity
+    pub fn error_count(&self) -> usize {
+        self.warnings
+            .iter()
+            .filter(|w| w.severity == QualitySeverity::Error)
+            .count()
+    }
+}
+
+/// Thresholds an imported article must meet before `save_imported_article`
+/// writes it; admins can override a failed gate explicitly
+#[derive(Debug, Clone, Default)]
+pub struct ImportQualityGates {
+    /// Minimum body length as counted by [`count_words`]; 0 disables the gate
+    pub min_word_count: i64,
+    /// Highest allowed [`content_similarity`] to an existing post (0.0-1.0)
+    pub max_similarity: Option<f64>,
+    /// Metadata that must be present: "title", "excerpt", "category" or "tags"
+    pub required_metadata: Vec<String>,
+}
+
+/// A quality gate an imported article failed, with a hint on how to fix it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityGateViolation {
+    pub gate: String,
+    pub message: String,
+}
+
+impl ImportQualityGates {
+    /// Whether any gate is configured
+    pub fn is_enabled(&self) -> bool {
+        self.min_word_count > 0
+            || self.max_similarity.is_some()
+            || !self.required_metadata.is_empty()
+    }
+
+    /// Check an article against every gate; `closest` is the slug and
+    /// similarity score of the most similar existing post
+    pub fn evaluate(
+        &self,
+        article: &LLMArticleImportResponse,
+        closest: Option<(&str, f64)>,
+    ) -> Vec<QualityGateViolation> {
+        let mut violations = Vec::new();
+
+        let word_count = count_words(&article.formatted_content);
+        if word_count < self.min_word_count {
+            violations.push(QualityGateViolation {
+                gate: "min_word_count".to_string(),
+                message: format!(
+                    "本文が短すぎます（{}語、最低{}語）。内容を追記してください",
+                    word_count, self.min_word_count
+                ),
+            });
+        }
+
+        if let (Some(max), Some((slug, score))) = (self.max_similarity, closest) {
+            if score > max {
+                violations.push(QualityGateViolation {
+                    gate: "max_similarity".to_string(),
+                    message: format!(
+                        "既存の記事「{}」と類似度が高すぎます（{:.2}、上限{:.2}）。重複していないか確認してください",
+                        slug, score, max
+                    ),
+                });
+            }
+        }
+
+        let metadata = &article.suggested_metadata;
+        for field in &self.required_metadata {
+            let missing = match field.as_str() {
+                "title" => metadata.title.trim().is_empty() || metadata.title == "Untitled Article",
+                "excerpt" => metadata
+                    .excerpt
+                    .as_deref()
+                    .is_none_or(|e| e.trim().is_empty()),
+                "category" => metadata
+                    .category
+                    .as_deref()
+                    .is_none_or(|c| c.trim().is_empty()),
+                "tags" => metadata.tags.is_empty(),
+                _ => false,
+            };
+            if missing {
+                violations.push(QualityGateViolation {
+                    gate: format!("required_metadata.{}", field),
+                    message: format!(
+                        "メタデータ「{}」が必要です。指定してから保存してください",
+                        field
+                    ),
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+/// Jaccard similarity of the character bigrams of two texts, ignoring case,
+/// whitespace and punctuation so that reformatted copies still match
+pub fn content_similarity(a: &str, b: &str) -> f64 {
+    fn bigrams(text: &str) -> HashSet<(char, char)> {
+        let chars: Vec<char> = text
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
+    let (a, b) = (bigrams(a), bigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(&b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LLMSuggestedMetadata;
+
+    fn article(content: &str, title: &str) -> LLMArticleImportResponse {
+        LLMArticleImportResponse {
+            slug: "draft".to_string(),
+            suggested_metadata: LLMSuggestedMetadata {
+                title: title.to_string(),
+                excerpt: None,
+                category: Some("tech".to_string()),
+                tags: vec![],
+                author: None,
+                source: "custom".to_string(),
+            },
+            formatted_content: content.to_string(),
+            html_content: String::new(),
+            preview_url: String::new(),
+            dropbox_path: String::new(),
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn test_import_quality_gates_report_each_failure() {
+        let gates = ImportQualityGates {
+            min_word_count: 10,
+            max_similarity: Some(0.8),
+            required_metadata: vec![
+                "title".to_string(),
+                "category".to_string(),
+                "tags".to_string(),
+            ],
+        };
+
+        let violations = gates.evaluate(
+            &article("short text", "Untitled Article"),
+            Some(("old-post", 0.9)),
+        );
+        let failed: Vec<&str> = violations.iter().map(|v| v.gate.as_str()).collect();
+        assert_eq!(
+            failed,
+            [
+                "min_word_count",
+                "max_similarity",
+                "required_metadata.title",
+                "required_metadata.tags"
+            ]
+        );
+        assert!(violations[1].message.contains("old-post"));
+
+        let body = "Rustで作るブログエンジンの設計について詳しく説明します";
+        let violations = gates.evaluate(&article(body, "設計"), Some(("old-post", 0.5)));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].gate, "required_metadata.tags");
+    }
+
+    #[test]
+    fn test_content_similarity() {
+        let original = "# Rust入門\n\nRustは安全で高速な言語です。";
+        assert_eq!(
+            content_similarity(original, "rust入門 Rustは安全で高速な言語です"),
+            1.0
+        );
+        assert!(content_similarity(original, "Pythonでデータ分析をする方法") < 0.2);
+        assert_eq!(content_similarity("", original), 0.0);
+    }
+}
@@ -1,9 +1,13 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::models::{ExpiryAction, PostVisibility};
+
 /// Response model for individual post details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostResponse {
     pub id: Uuid,
     pub slug: String,
@@ -18,20 +22,68 @@ pub struct PostResponse {
     pub author: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// SHA-256 of the title and body; unchanged by metadata-only edits
+    #[serde(default)]
+    pub content_hash: String,
+    /// Last metadata-only edit, which leaves `updated_at` alone
+    #[serde(default)]
+    pub metadata_updated_at: Option<DateTime<Utc>>,
+    /// Version pinned as the live page, if newer drafts exist
+    #[serde(default)]
+    pub published_version: Option<i32>,
     pub published_at: Option<DateTime<Utc>>,
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub expiry_action: ExpiryAction,
+    #[serde(default)]
+    pub expiry_notice_days: Option<i64>,
+    #[serde(default)]
+    pub show_changelog: bool,
+    /// Original URL of a cross-post, used as `rel=canonical`
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// URLs where the post is also published
+    #[serde(default)]
+    pub syndication_links: Vec<String>,
+    /// `published_at` in the site timezone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at_local: Option<DateTime<FixedOffset>>,
+    pub featured_media_id: Option<Uuid>,
     pub url_path: String,
+    pub word_count: i64,
+    pub reading_time: i64,
+    pub summary: Option<String>,
+    pub pinned: bool,
+    pub pin_order: Option<i64>,
+    pub visibility: PostVisibility,
+    #[serde(default)]
+    pub password_protected: bool,
+    /// "Edit in Dropbox" link to the source file, when it lies under the blog root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropbox_url: Option<String>,
 }
 
 /// Summary model for post listings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostSummary {
     pub id: Uuid,
     pub slug: String,
     pub title: String,
     pub excerpt: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub reading_time: i64,
     pub category: Option<String>,
     pub tags: Vec<String>,
     pub featured: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Excerpt and summary are left out for password-protected posts
+    #[serde(default)]
+    pub password_protected: bool,
     pub author: Option<String>,
     pub created_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
@@ -39,7 +91,7 @@ pub struct PostSummary {
 }
 
 /// Response model for post list pages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostListResponse {
     pub posts: Vec<PostSummary>,
     pub total: usize,
@@ -48,8 +100,31 @@ pub struct PostListResponse {
     pub total_pages: usize,
 }
 
+/// Heading of a post with the anchor it can be deep-linked by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OutlineHeading {
+    /// Heading level, 1 for `<h1>` through 6 for `<h6>`
+    pub level: u8,
+    pub text: String,
+    /// `id` of the heading element, used as `#anchor` in links
+    pub anchor: String,
+    /// Subheadings up to the next heading of this level or higher
+    #[serde(default)]
+    pub children: Vec<OutlineHeading>,
+}
+
+/// Response model for the heading outline of a post
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostOutlineResponse {
+    pub slug: String,
+    pub title: String,
+    pub word_count: i64,
+    pub reading_time: i64,
+    pub headings: Vec<OutlineHeading>,
+}
+
 /// Response model for API errors
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: String,
     pub message: String,
@@ -110,6 +185,9 @@ impl From<crate::models::Post> for PostResponse {
     fn from(post: crate::models::Post) -> Self {
         let url_path = post.get_url_path();
         let tags = post.get_tags();
+        let reading_time = post.reading_time();
+        let password_protected = post.is_password_protected();
+        let syndication_links = post.get_syndication_links();
 
         Self {
             id: post.id,
@@ -125,25 +203,81 @@ impl From<crate::models::Post> for PostResponse {
             author: post.author,
             created_at: post.created_at,
             updated_at: post.updated_at,
+            content_hash: post.content_hash,
+            metadata_updated_at: post.metadata_updated_at,
+            published_version: post.published_version,
             published_at: post.published_at,
+            publish_at: post.publish_at,
+            expires_at: post.expires_at,
+            expiry_action: post.expiry_action,
+            expiry_notice_days: post.expiry_notice_days,
+            show_changelog: post.show_changelog,
+            canonical_url: post.canonical_url,
+            syndication_links,
+            published_at_local: None,
+            featured_media_id: post.featured_media_id,
             url_path,
+            word_count: post.word_count,
+            reading_time,
+            summary: post.summary,
+            pinned: post.pinned,
+            pin_order: post.pin_order,
+            visibility: post.visibility,
+            password_protected,
+            dropbox_url: None,
+        }
+    }
+}
+
+impl PostResponse {
+    /// Convert a post, linking its source file in the Dropbox web UI
+    pub fn with_dropbox_link(post: crate::models::Post, blog_root: &str) -> Self {
+        let dropbox_url = post.dropbox_web_url(blog_root);
+        Self {
+            dropbox_url,
+            ..Self::from(post)
         }
     }
+
+    /// Fill in the publication time as seen in the site timezone
+    pub fn with_local_times(mut self, timezone: Tz) -> Self {
+        self.published_at_local = self
+            .published_at
+            .map(|date| date.with_timezone(&timezone).fixed_offset());
+        self
+    }
+
+    /// Drop the body and excerpts of a password-protected post
+    pub fn without_protected_content(mut self) -> Self {
+        if self.password_protected {
+            self.content.clear();
+            self.html_content.clear();
+            self.excerpt = None;
+            self.summary = None;
+        }
+        self
+    }
 }
 
 impl From<crate::models::Post> for PostSummary {
     fn from(post: crate::models::Post) -> Self {
         let url_path = post.get_url_path();
         let tags = post.get_tags();
+        let reading_time = post.reading_time();
+        let password_protected = post.is_password_protected();
 
         Self {
             id: post.id,
             slug: post.slug,
             title: post.title,
-            excerpt: post.excerpt,
+            excerpt: post.excerpt.filter(|_| !password_protected),
+            summary: post.summary.filter(|_| !password_protected),
+            reading_time,
             category: post.category,
             tags,
             featured: post.featured,
+            pinned: post.pinned,
+            password_protected,
             author: post.author,
             created_at: post.created_at,
             published_at: post.published_at,
@@ -165,6 +299,10 @@ impl ErrorResponse {
         Self::new("not_found", message, 404)
     }
 
+    pub fn gone(message: impl Into<String>) -> Self {
+        Self::new("gone", message, 410)
+    }
+
     pub fn internal_error(message: impl Into<String>) -> Self {
         Self::new("internal_server_error", message, 500)
     }
@@ -172,4 +310,8 @@ impl ErrorResponse {
     pub fn bad_request(message: impl Into<String>) -> Self {
         Self::new("bad_request", message, 400)
     }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new("payload_too_large", message, 413)
+    }
 }
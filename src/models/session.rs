@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+/// Logged-in admin session, keyed by the SHA-256 of its cookie token
+#[derive(Debug, Clone)]
+pub struct AdminSession {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What exceeded its threshold
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowLogKind {
+    Query,
+    Request,
+}
+
+/// A database query or request that took longer than its threshold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlowLogEntry {
+    pub kind: SlowLogKind,
+    /// SQL with bind placeholders for queries, method and route pattern for requests
+    pub target: String,
+    pub duration_ms: f64,
+    pub threshold_ms: u64,
+    /// Response status of slow requests
+    pub status: Option<u16>,
+    /// Request the query ran for, or the slow request itself
+    pub request_id: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Thresholds, counters and the most recent slow queries and requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowLogReport {
+    pub query_threshold_ms: u64,
+    pub request_threshold_ms: u64,
+    /// Slow queries since startup, including those dropped from the buffer
+    pub slow_queries: u64,
+    /// Slow requests since startup, including those dropped from the buffer
+    pub slow_requests: u64,
+    /// Newest first
+    pub entries: Vec<SlowLogEntry>,
+}
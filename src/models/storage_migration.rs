@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Request body of `POST /api/admin/storage/migrate`, also the job payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageMigrationRequest {
+    /// Backend to copy from: dropbox, local or s3 (defaults to the one in use)
+    pub source: Option<String>,
+    /// Backend to copy to
+    pub target: String,
+    /// Folder to copy (defaults to `/BlogStorage`)
+    pub source_root: Option<String>,
+    /// Folder the files are copied into (defaults to the source folder)
+    pub target_root: Option<String>,
+}
+
+/// Progress of a running migration, reported through the job queue
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageMigrationProgress {
+    pub total_files: usize,
+    pub processed_files: usize,
+    pub copied_files: usize,
+    /// Files the target already held with the same content
+    pub skipped_files: usize,
+    pub failed_files: usize,
+    pub copied_bytes: u64,
+}
+
+/// Outcome of a migration, stored as the job result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageMigrationReport {
+    pub source: String,
+    pub target: String,
+    pub source_root: String,
+    pub target_root: String,
+    #[serde(flatten)]
+    pub progress: StorageMigrationProgress,
+    /// Database rows whose storage path was moved to the target folder
+    pub rewritten_paths: u64,
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How a post's database copy relates to its Dropbox file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    /// Both copies have the same content
+    InSync,
+    /// Only the Dropbox file changed since the last sync
+    DropboxAhead,
+    /// Only the database copy changed since the last sync
+    DatabaseAhead,
+    /// Both changed since the last sync, or no sync was ever recorded
+    Conflict,
+}
+
+impl SyncState {
+    /// Classify by comparing both content hashes with the hash of the last sync
+    pub fn classify(database_hash: &str, dropbox_hash: &str, sync_hash: Option<&str>) -> Self {
+        if database_hash == dropbox_hash {
+            return Self::InSync;
+        }
+
+        match sync_hash {
+            Some(base) if base == database_hash => Self::DropboxAhead,
+            Some(base) if base == dropbox_hash => Self::DatabaseAhead,
+            _ => Self::Conflict,
+        }
+    }
+}
+
+/// Ways to settle a diverged post
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictResolution {
+    /// Overwrite the Dropbox file with the database copy
+    KeepDb,
+    /// Overwrite the database copy with the Dropbox file
+    KeepDropbox,
+    /// Write merged content supplied by the caller to both sides
+    ManualMerge,
+}
+
+/// Database and Dropbox versions of a post side by side
+#[derive(Debug, Clone, Serialize)]
+pub struct PostSyncStatus {
+    pub slug: String,
+    pub title: String,
+    pub state: SyncState,
+    pub dropbox_path: String,
+    pub database_hash: String,
+    pub dropbox_hash: String,
+    pub database_updated_at: DateTime<Utc>,
+    pub dropbox_updated_at: DateTime<Utc>,
+    /// Resolutions accepted by `POST /api/sync/conflicts/{slug}/resolve`
+    pub actions: Vec<ConflictResolution>,
+}
+
+/// Response for `GET /api/sync/conflicts`
+#[derive(Debug, Serialize)]
+pub struct SyncConflictsResponse {
+    /// Posts whose copies differ, in any state but `in_sync`
+    pub conflicts: Vec<PostSyncStatus>,
+    pub total: usize,
+}
+
+/// Request body for resolving a diverged post
+#[derive(Debug, Deserialize)]
+pub struct ResolveConflictRequest {
+    pub resolution: ConflictResolution,
+    /// Merged markdown body, required for `manual-merge`
+    pub content: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_uses_last_sync_as_base() {
+        assert_eq!(SyncState::classify("a", "a", None), SyncState::InSync);
+        assert_eq!(
+            SyncState::classify("a", "b", Some("a")),
+            SyncState::DropboxAhead
+        );
+        assert_eq!(
+            SyncState::classify("b", "a", Some("a")),
+            SyncState::DatabaseAhead
+        );
+        assert_eq!(
+            SyncState::classify("b", "c", Some("a")),
+            SyncState::Conflict
+        );
+        assert_eq!(SyncState::classify("a", "b", None), SyncState::Conflict);
+    }
+}
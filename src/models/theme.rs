@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Theme settings configuration for blog customization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ThemeSettings {
     pub id: Option<i64>,
     pub name: String,
@@ -22,12 +24,25 @@ pub struct ThemeSettings {
     pub custom_css: Option<String>,
     pub header_style: HeaderStyle,
     pub footer_style: FooterStyle,
+    /// Accents of category archive pages and posts, stored in `category_styles`
+    #[serde(default)]
+    pub category_styles: Vec<CategoryStyle>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// Accent color and hero image of one category
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct CategoryStyle {
+    pub category: String,
+    /// Hex color, e.g. `#DEA584`
+    pub accent_color: String,
+    /// Image URL shown behind the category archive header
+    pub hero_image: Option<String>,
+}
+
 /// Layout configuration options
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub enum ThemeLayout {
     #[serde(rename = "single")]
     Single, // Single column layout
@@ -38,7 +53,7 @@ pub enum ThemeLayout {
 }
 
 /// Header style configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HeaderStyle {
     pub height: String,
     pub background_color: Option<String>,
@@ -50,7 +65,7 @@ pub struct HeaderStyle {
 }
 
 /// Footer style configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FooterStyle {
     pub background_color: Option<String>,
     pub text_color: Option<String>,
@@ -60,7 +75,7 @@ pub struct FooterStyle {
 }
 
 /// Theme creation/update request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateThemeRequest {
     pub name: String,
     pub display_name: String,
@@ -78,10 +93,12 @@ pub struct CreateThemeRequest {
     pub custom_css: Option<String>,
     pub header_style: HeaderStyle,
     pub footer_style: FooterStyle,
+    #[serde(default)]
+    pub category_styles: Vec<CategoryStyle>,
 }
 
 /// Update theme request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateThemeRequest {
     pub display_name: Option<String>,
     pub description: Option<String>,
@@ -98,6 +115,8 @@ pub struct UpdateThemeRequest {
     pub custom_css: Option<String>,
     pub header_style: Option<HeaderStyle>,
     pub footer_style: Option<FooterStyle>,
+    /// Replaces all category styles of the theme
+    pub category_styles: Option<Vec<CategoryStyle>>,
 }
 
 /// Site configuration for global blog settings
@@ -114,10 +133,50 @@ pub struct SiteConfig {
     pub social_links: Vec<SocialLink>,
     pub google_analytics_id: Option<String>,
     pub google_fonts: Vec<String>,
+    /// Language of the UI strings and dates rendered by the templates
+    #[serde(default)]
+    pub locale: Locale,
+    /// IANA timezone that dates are displayed and scheduled posts entered in
+    #[serde(default = "default_timezone")]
+    pub timezone: Tz,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+fn default_timezone() -> Tz {
+    Tz::UTC
+}
+
+/// Languages the template message catalogs are available in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ja" => Ok(Locale::Ja),
+            "en" => Ok(Locale::En),
+            other => Err(anyhow::anyhow!("Unsupported locale: {}", other)),
+        }
+    }
+}
+
 /// Social media link configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialLink {
@@ -170,13 +229,13 @@ pub struct CssVariable {
 }
 
 /// Response types for theme APIs
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ThemeResponse {
     pub success: bool,
     pub data: ThemeSettings,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ThemeListResponse {
     pub success: bool,
     pub data: Vec<ThemeSettings>,
@@ -196,6 +255,27 @@ pub struct ThemePreviewResponse {
     pub variables: Vec<CssVariable>,
 }
 
+/// Schema version written to settings exports; imports must match it
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// Site configuration and themes bundled as one document for cloning environments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsDocument {
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub site_config: SiteConfig,
+    pub themes: Vec<ThemeSettings>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettingsImportResponse {
+    pub success: bool,
+    pub message: String,
+    pub themes_created: usize,
+    pub themes_updated: usize,
+    pub active_theme: Option<String>,
+}
+
 impl Default for ThemeSettings {
     fn default() -> Self {
         Self {
@@ -231,6 +311,7 @@ impl Default for ThemeSettings {
                 show_copyright: true,
                 custom_content: None,
             },
+            category_styles: Vec::new(),
             created_at: None,
             updated_at: None,
         }
@@ -251,6 +332,8 @@ impl Default for SiteConfig {
             social_links: vec![],
             google_analytics_id: None,
             google_fonts: vec!["Inter:wght@400;500;600;700".to_string()],
+            locale: Locale::default(),
+            timezone: default_timezone(),
             created_at: None,
             updated_at: None,
         }
@@ -279,6 +362,7 @@ impl From<CreateThemeRequest> for ThemeSettings {
             custom_css: req.custom_css,
             header_style: req.header_style,
             footer_style: req.footer_style,
+            category_styles: req.category_styles,
             created_at: Some(now),
             updated_at: Some(now),
         }
@@ -352,17 +436,43 @@ impl ThemeSettings {
         ]
     }
 
+    /// Generate the color variables used when dark mode is active
+    ///
+    /// Brand colors are kept as-is while background and text colors are swapped,
+    /// so a theme only has to define a single palette.
+    pub fn to_dark_css_variables(&self) -> Vec<CssVariable> {
+        self.to_css_variables()
+            .into_iter()
+            .filter(|var| var.category == "colors")
+            .map(|mut var| {
+                match var.name.as_str() {
+                    "--color-background" => var.value = self.text_color.clone(),
+                    "--color-text" => var.value = self.background_color.clone(),
+                    _ => {}
+                }
+                var
+            })
+            .collect()
+    }
+
     /// Generate CSS content from theme settings
     pub fn to_css(&self) -> String {
-        let variables = self.to_css_variables();
-        let mut css = String::from(":root {\n");
+        let mut css = css_block(":root", &self.to_css_variables());
 
-        for var in variables {
-            css.push_str(&format!("  {}: {};\n", var.name, var.value));
+        // Dark variables apply when the OS prefers dark unless the page forces light,
+        // and whenever the page explicitly opts in via `data-theme="dark"`
+        if self.dark_mode_enabled {
+            let dark_variables = self.to_dark_css_variables();
+            css.push_str("@media (prefers-color-scheme: dark) {\n");
+            for line in css_block(":root:not([data-theme=\"light\"])", &dark_variables).lines() {
+                if !line.is_empty() {
+                    css.push_str(&format!("  {}\n", line));
+                }
+            }
+            css.push_str("}\n\n");
+            css.push_str(&css_block(":root[data-theme=\"dark\"]", &dark_variables));
         }
 
-        css.push_str("}\n\n");
-
         // Add layout-specific styles
         match self.layout {
             ThemeLayout::Single => {
@@ -386,4 +496,131 @@ impl ThemeSettings {
 
         css
     }
+
+    /// CSS giving each styled category its accent variables, plus the rules
+    /// that apply them to elements marked with `data-category`
+    ///
+    /// Category archive headers use `.category-hero` and post headers
+    /// `.category-accent`; categories without a style keep the theme's look.
+    pub fn category_css(&self) -> String {
+        if self.category_styles.is_empty() {
+            return String::new();
+        }
+
+        let mut css = String::from("/* Category accents */\n");
+        let mut selectors = Vec::new();
+        for style in &self.category_styles {
+            let selector = format!("[data-category={}]", css_string(&style.category));
+            css.push_str(&format!("{} {{\n", selector));
+            css.push_str(&format!("  --category-accent: {};\n", style.accent_color));
+            if let Some(hero_image) = &style.hero_image {
+                css.push_str(&format!(
+                    "  --category-hero-image: url({});\n",
+                    css_string(hero_image)
+                ));
+            }
+            css.push_str("}\n\n");
+            selectors.push(selector);
+        }
+
+        let styled = selectors.join(", ");
+        css.push_str(&format!(
+            ".category-hero:is({}) {{\n  background-color: var(--category-accent);\n  background-image: linear-gradient(rgba(0, 0, 0, 0.35), rgba(0, 0, 0, 0.35)), var(--category-hero-image, none);\n  background-size: cover;\n  background-position: center;\n}}\n\n",
+            styled
+        ));
+        css.push_str(&format!(
+            ".category-accent:is({}) {{\n  border-top: 4px solid var(--category-accent);\n}}\n",
+            styled
+        ));
+        css
+    }
+}
+
+/// Quote a value as a CSS string
+fn css_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\a "),
+            c if c.is_control() => {}
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Render a CSS rule block declaring the given variables
+fn css_block(selector: &str, variables: &[CssVariable]) -> String {
+    let mut css = format!("{} {{\n", selector);
+    for var in variables {
+        css.push_str(&format!("  {}: {};\n", var.name, var.value));
+    }
+    css.push_str("}\n\n");
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_css_emits_dark_variables() {
+        let theme = ThemeSettings::default();
+        let css = theme.to_css();
+
+        assert!(css.contains(":root {\n  --color-primary: #3B82F6;"));
+        assert!(css.contains("@media (prefers-color-scheme: dark) {"));
+        assert!(css.contains(":root:not([data-theme=\"light\"]) {"));
+        assert!(css.contains(":root[data-theme=\"dark\"] {\n  --color-primary: #3B82F6;"));
+        assert!(css.contains("    --color-background: #1F2937;"));
+        assert!(css.contains("    --color-text: #FFFFFF;"));
+    }
+
+    #[test]
+    fn test_to_css_without_dark_mode() {
+        let theme = ThemeSettings {
+            dark_mode_enabled: false,
+            ..Default::default()
+        };
+        let css = theme.to_css();
+
+        assert!(!css.contains("prefers-color-scheme"));
+        assert!(!css.contains("data-theme"));
+    }
+
+    #[test]
+    fn test_category_css_scopes_accents_to_styled_categories() {
+        let theme = ThemeSettings {
+            category_styles: vec![
+                CategoryStyle {
+                    category: "Rust".to_string(),
+                    accent_color: "#DEA584".to_string(),
+                    hero_image: Some("/media/rust.jpg".to_string()),
+                },
+                CategoryStyle {
+                    category: "Say \"hi\"".to_string(),
+                    accent_color: "#10B981".to_string(),
+                    hero_image: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let css = theme.category_css();
+
+        assert!(css.contains(
+            "[data-category=\"Rust\"] {\n  --category-accent: #DEA584;\n  --category-hero-image: url(\"/media/rust.jpg\");\n}"
+        ));
+        assert!(
+            css.contains("[data-category=\"Say \\\"hi\\\"\"] {\n  --category-accent: #10B981;\n}")
+        );
+        assert!(css.contains(
+            ".category-hero:is([data-category=\"Rust\"], [data-category=\"Say \\\"hi\\\"\"])"
+        ));
+        assert!(ThemeSettings::default().category_css().is_empty());
+    }
 }
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// TOTP secret of the admin login; `enabled` enforces a code on every login
+#[derive(Debug, Clone)]
+pub struct AdminTotp {
+    /// Base32-encoded shared secret
+    pub secret: String,
+    pub enabled: bool,
+    pub enabled_at: Option<DateTime<Utc>>,
+}
+
+/// Secret to add to an authenticator app, pending confirmation with a code
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpEnrollment {
+    pub secret: String,
+    /// `otpauth://` URI encoded in the enrollment QR code
+    pub provisioning_uri: String,
+}
+
+/// Request body carrying a code from the authenticator app
+#[derive(Debug, Clone, Deserialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+/// Freshly issued recovery codes; they are shown only once
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpRecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Two-factor state shown on the admin security page
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpStatus {
+    pub enabled: bool,
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub recovery_codes_remaining: i64,
+}
@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Post version information for version history management
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostVersion {
     pub id: i64,
     pub post_id: Uuid,
@@ -37,7 +38,7 @@ pub struct CreatePostVersion {
 }
 
 /// Version comparison data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VersionDiff {
     pub post_id: Uuid,
     pub version_from: i32,
@@ -47,10 +48,46 @@ pub struct VersionDiff {
     pub metadata_diff: Option<serde_json::Value>,
     pub created_at_from: DateTime<Utc>,
     pub created_at_to: DateTime<Utc>,
+    /// Size of the content change, counted over the whole diff
+    pub stats: DiffStats,
+    /// Word-level diff of each prose paragraph that was reworded
+    pub word_diff: Vec<ParagraphDiff>,
+}
+
+/// Lines and words added and removed between two versions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiffStats {
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub words_added: usize,
+    pub words_removed: usize,
+}
+
+/// A paragraph replaced by a reworded one
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParagraphDiff {
+    /// Line number of the paragraph in the newer version, starting at 1
+    pub line: usize,
+    pub segments: Vec<WordDiffSegment>,
+}
+
+/// Run of text that was kept, added or removed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WordDiffSegment {
+    pub kind: DiffChangeKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffChangeKind {
+    Unchanged,
+    Added,
+    Removed,
 }
 
 /// Version history summary
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VersionHistory {
     pub post_id: Uuid,
     pub post_slug: String,
@@ -60,7 +97,7 @@ pub struct VersionHistory {
 }
 
 /// Individual version summary
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VersionSummary {
     pub version: i32,
     pub title: String,
@@ -70,13 +107,81 @@ pub struct VersionSummary {
     pub is_current: bool,
 }
 
-/// Version restore request
+/// Reader-facing changelog of a post, built from version change summaries
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostChangelog {
+    pub post_slug: String,
+    pub last_updated: DateTime<Utc>,
+    /// Newest first; versions without a change summary are left out
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// One line of a post's public changelog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version: i32,
+    pub summary: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to show or hide the changelog on a post page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetChangelogRequest {
+    pub show_changelog: bool,
+}
+
+/// Version restore request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RestoreVersionRequest {
     pub target_version: i32,
     pub change_summary: Option<String>,
 }
 
+/// Which versions the site-wide cleanup keeps for every post; a version
+/// survives when either rule keeps it, and an empty policy keeps everything
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct VersionRetentionPolicy {
+    /// Keep the newest N versions of each post
+    pub keep_versions: Option<i32>,
+    /// Keep versions saved within the last D days
+    pub max_age_days: Option<i64>,
+}
+
+impl VersionRetentionPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.keep_versions.is_some() || self.max_age_days.is_some()
+    }
+
+    /// Reject rules that would keep nothing
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.keep_versions.is_some_and(|keep| keep < 1) {
+            return Err("keep_versions must be at least 1");
+        }
+        if self.max_age_days.is_some_and(|days| days < 1) {
+            return Err("max_age_days must be at least 1");
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a site-wide version cleanup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionCleanupReport {
+    pub posts_checked: usize,
+    /// Version rows deleted across all posts
+    pub deleted_versions: usize,
+}
+
+/// Outcome of importing exported version history into a post
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionImportReport {
+    pub imported: usize,
+    /// Versions the post already had
+    pub skipped: usize,
+    /// Version number of the post afterwards, past every imported version
+    pub post_version: i32,
+}
+
 /// Version filters for querying
 #[derive(Debug, Clone, Default)]
 pub struct VersionFilters {
@@ -180,27 +285,54 @@ impl PostVersion {
 
 /// Response types for API endpoints
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VersionHistoryResponse {
     pub success: bool,
     pub data: VersionHistory,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VersionResponse {
     pub success: bool,
     pub data: PostVersion,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VersionDiffResponse {
     pub success: bool,
     pub data: VersionDiff,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RestoreVersionResponse {
     pub success: bool,
     pub message: String,
     pub new_version: i32,
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublishVersionResponse {
+    pub success: bool,
+    pub message: String,
+    pub published_version: i32,
+    /// Latest version of the post, ahead of the published one while drafting
+    pub latest_version: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostChangelogResponse {
+    pub success: bool,
+    pub data: PostChangelog,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VersionCleanupResponse {
+    pub success: bool,
+    pub data: VersionCleanupReport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VersionImportResponse {
+    pub success: bool,
+    pub data: VersionImportReport,
+}
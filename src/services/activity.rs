@@ -0,0 +1,98 @@
+use anyhow::Result;
+use tracing::{debug, warn};
+
+use crate::models::{merge_activity, ActivityEntry, ActivityKind, MediaFilters};
+use crate::services::DatabaseService;
+
+/// Service that gathers recent events from posts, syncs and media into one feed
+#[derive(Clone)]
+pub struct ActivityService {
+    database: DatabaseService,
+}
+
+impl ActivityService {
+    /// Create a new activity service
+    pub fn new(database: DatabaseService) -> Self {
+        Self { database }
+    }
+
+    /// Newest `limit` events across all sources
+    ///
+    /// A failing source is logged and left out so the rest of the feed still shows.
+    pub async fn recent(&self, limit: usize) -> Vec<ActivityEntry> {
+        let per_source = limit as i64;
+        let sources = [
+            ("post revisions", self.revisions(per_source).await),
+            ("syncs", self.syncs(per_source).await),
+            ("media uploads", self.uploads(per_source).await),
+        ];
+
+        let entries = sources
+            .into_iter()
+            .filter_map(|(name, entries)| match entries {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    warn!("Failed to load recent {} for activity feed: {}", name, e);
+                    None
+                }
+            });
+
+        let feed = merge_activity(entries, limit);
+        debug!("Activity feed has {} entries", feed.len());
+        feed
+    }
+
+    async fn revisions(&self, limit: i64) -> Result<Vec<ActivityEntry>> {
+        let versions = self.database.list_recent_post_versions(limit).await?;
+
+        Ok(versions
+            .into_iter()
+            .map(|(slug, version)| ActivityEntry {
+                kind: ActivityKind::PostRevision,
+                title: version.title,
+                detail: Some(match version.change_summary {
+                    Some(summary) => format!("v{}: {}", version.version, summary),
+                    None => format!("v{}", version.version),
+                }),
+                link: Some(format!("/admin/edit/{}", slug)),
+                occurred_at: version.created_at,
+            })
+            .collect())
+    }
+
+    async fn syncs(&self, limit: i64) -> Result<Vec<ActivityEntry>> {
+        let syncs = self.database.list_recent_syncs(limit).await?;
+
+        Ok(syncs
+            .into_iter()
+            .map(|sync| ActivityEntry {
+                kind: ActivityKind::DropboxSync,
+                title: sync.title,
+                detail: None,
+                link: Some(format!("/admin/edit/{}", sync.slug)),
+                occurred_at: sync.synced_at,
+            })
+            .collect())
+    }
+
+    async fn uploads(&self, limit: i64) -> Result<Vec<ActivityEntry>> {
+        let media = self
+            .database
+            .list_media_files(MediaFilters {
+                limit: Some(limit),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(media
+            .into_iter()
+            .map(|file| ActivityEntry {
+                kind: ActivityKind::MediaUpload,
+                title: file.original_filename,
+                detail: Some(file.mime_type),
+                link: Some(file.url),
+                occurred_at: file.uploaded_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::http::{header, HeaderMap};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration as ChronoDuration, Utc};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::models::AdminSession;
+use crate::services::{ClientInfo, DatabaseService};
+
+/// Name of the admin session cookie
+pub const SESSION_COOKIE: &str = "tobelog_admin_session";
+
+/// Logged-in state of the admin pages, kept in the database
+#[derive(Clone)]
+pub struct AdminSessionService {
+    database: DatabaseService,
+    ttl: Duration,
+    rotate_after: Duration,
+}
+
+/// Outcome of checking a request's session cookie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionCheck {
+    Valid,
+    /// Valid, but old enough to be swapped for this new token
+    Rotated(String),
+    Invalid,
+}
+
+impl AdminSessionService {
+    pub fn new(database: DatabaseService, ttl: Duration, rotate_after: Duration) -> Self {
+        Self {
+            database,
+            ttl,
+            rotate_after,
+        }
+    }
+
+    /// Start a session and return its cookie token
+    pub async fn create(&self) -> Result<String> {
+        let removed = self.database.delete_expired_admin_sessions().await?;
+        if removed > 0 {
+            debug!("Removed {} expired admin sessions", removed);
+        }
+
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = URL_SAFE_NO_PAD.encode(bytes);
+
+        let now = Utc::now();
+        self.database
+            .create_admin_session(&AdminSession {
+                id: token_hash(&token),
+                created_at: now,
+                expires_at: now + ChronoDuration::from_std(self.ttl)?,
+            })
+            .await?;
+
+        Ok(token)
+    }
+
+    /// Check the session cookie of a request, rotating sessions past the rotation age
+    pub async fn check(&self, headers: &HeaderMap) -> Result<SessionCheck> {
+        let Some(token) = session_token(headers) else {
+            return Ok(SessionCheck::Invalid);
+        };
+        let Some(session) = self.database.get_admin_session(&token_hash(token)).await? else {
+            return Ok(SessionCheck::Invalid);
+        };
+
+        let age = (Utc::now() - session.created_at)
+            .to_std()
+            .unwrap_or_default();
+        if age < self.rotate_after {
+            return Ok(SessionCheck::Valid);
+        }
+
+        // Issue the new token before dropping the old one so a failure keeps the user logged in
+        let token = self.create().await?;
+        self.database.delete_admin_session(&session.id).await?;
+        debug!("Rotated admin session");
+        Ok(SessionCheck::Rotated(token))
+    }
+
+    /// End the session of a request, if any
+    pub async fn revoke(&self, headers: &HeaderMap) -> Result<()> {
+        if let Some(token) = session_token(headers) {
+            self.database
+                .delete_admin_session(&token_hash(token))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// `Set-Cookie` value carrying `token`, `Secure` when `client` uses HTTPS
+    pub fn cookie(&self, token: &str, client: &ClientInfo) -> String {
+        format!(
+            "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Strict{}",
+            SESSION_COOKIE,
+            token,
+            self.ttl.as_secs(),
+            secure_attribute(client)
+        )
+    }
+
+    /// `Set-Cookie` value removing the session cookie
+    pub fn clear_cookie(&self, client: &ClientInfo) -> String {
+        format!(
+            "{}=; Path=/; Max-Age=0; HttpOnly; SameSite=Strict{}",
+            SESSION_COOKIE,
+            secure_attribute(client)
+        )
+    }
+}
+
+/// The scheme is the one the client used, so this holds behind a TLS-terminating proxy
+fn secure_attribute(client: &ClientInfo) -> &'static str {
+    if client.is_https() {
+        "; Secure"
+    } else {
+        ""
+    }
+}
+
+pub(crate) fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|cookies| cookies.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find_map(|(name, value)| (name == SESSION_COOKIE).then_some(value))
+        .filter(|value| !value.is_empty())
+}
+
+/// Only this hash is stored, so a leaked database holds no usable sessions
+fn token_hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
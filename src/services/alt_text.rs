@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+
+/// Longest suggestion kept; screen readers handle short descriptions best
+const MAX_SUGGESTION_CHARS: usize = 250;
+
+/// Alt text suggestion configuration
+#[derive(Debug, Clone)]
+pub struct AltTextConfig {
+    /// OpenAI-compatible chat completions endpoint of a vision model
+    pub api_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    /// Language the description should be written in (e.g. "Japanese")
+    pub language: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    content: Option<String>,
+}
+
+/// Service that asks a vision model to describe uploaded images
+#[derive(Clone)]
+pub struct AltTextService {
+    config: AltTextConfig,
+    client: Client,
+}
+
+impl AltTextService {
+    /// Create a new alt text suggestion service
+    pub fn new(config: AltTextConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap_or_default();
+
+        Self { config, client }
+    }
+
+    /// Suggest alt text for an image, or `None` when the model had nothing usable
+    pub async fn suggest(&self, image: &[u8], mime_type: &str) -> Result<Option<String>> {
+        debug!("Requesting alt text suggestion ({} bytes)", image.len());
+
+        let prompt = format!(
+            "Write alt text for this image in {}. Describe what it shows in one short \
+             sentence for screen reader users. Reply with the alt text only.",
+            self.config.language
+        );
+        let body = serde_json::json!({
+            "model": self.config.model,
+            "max_tokens": 200,
+            "messages": [{
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": prompt },
+                    {
+                        "type": "image_url",
+                        "image_url": {
+                            "url": format!("data:{};base64,{}", mime_type, STANDARD.encode(image))
+                        }
+                    }
+                ]
+            }]
+        });
+
+        let mut request = self.client.post(&self.config.api_url).json(&body);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response: ChatCompletionResponse = request
+            .send()
+            .await
+            .context("Failed to reach alt text provider")?
+            .error_for_status()
+            .context("Alt text provider returned an error")?
+            .json()
+            .await
+            .context("Invalid alt text provider response")?;
+
+        let content = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| anyhow!("Alt text provider returned no choices"))?;
+
+        Ok(clean_suggestion(&content))
+    }
+}
+
+/// Strip labels, quotes and extra lines that models tend to wrap around the answer
+fn clean_suggestion(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+
+    let line = ["alt text:", "alt:", "代替テキスト:", "代替テキスト："]
+        .iter()
+        .find_map(|label| {
+            line.get(..label.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(label))
+                .map(|_| &line[label.len()..])
+        })
+        .unwrap_or(line);
+
+    let text = line
+        .trim()
+        .trim_matches(|c| matches!(c, '"' | '\'' | '「' | '」' | '“' | '”'))
+        .trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    Some(text.chars().take(MAX_SUGGESTION_CHARS).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_suggestion_strips_labels_and_quotes() {
+        assert_eq!(
+            clean_suggestion("Alt text: \"A cat sleeping on a keyboard\"\n\nHope this helps!"),
+            Some("A cat sleeping on a keyboard".to_string())
+        );
+        assert_eq!(
+            clean_suggestion("代替テキスト：「桜並木の下を歩く人々」"),
+            Some("桜並木の下を歩く人々".to_string())
+        );
+        assert_eq!(clean_suggestion("  \n \"\" "), None);
+    }
+
+    #[test]
+    fn test_clean_suggestion_truncates_long_text() {
+        let long = "a".repeat(MAX_SUGGESTION_CHARS + 50);
+        assert_eq!(
+            clean_suggestion(&long).map(|s| s.chars().count()),
+            Some(MAX_SUGGESTION_CHARS)
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// URL prefix under which fingerprinted assets are served
+pub const ASSET_URL_PREFIX: &str = "/assets";
+
+/// URL prefix of the raw static directory (used as fallback)
+pub const STATIC_URL_PREFIX: &str = "/static";
+
+/// A minified, fingerprinted asset held in memory
+#[derive(Debug, Clone)]
+pub struct BuiltAsset {
+    pub content: Vec<u8>,
+    pub content_type: &'static str,
+    pub hash: String,
+}
+
+/// Manifest entry exposed for diagnostics
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetManifestEntry {
+    pub logical_path: String,
+    pub fingerprinted_path: String,
+    pub size: usize,
+}
+
+/// Asset pipeline service: minifies CSS/JS and serves them under content-hashed names
+#[derive(Clone, Default)]
+pub struct AssetService {
+    /// Fingerprinted path (e.g. `css/main.1a2b3c4d.css`) -> built asset
+    assets: Arc<HashMap<String, BuiltAsset>>,
+    /// Logical path (e.g. `css/main.css`) -> fingerprinted path
+    manifest: Arc<HashMap<String, String>>,
+}
+
+impl AssetService {
+    /// Create an empty asset service (all URLs fall back to `/static`)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build fingerprinted assets from every CSS/JS file under `static_dir`
+    pub fn build(static_dir: impl AsRef<Path>) -> Result<Self> {
+        let static_dir = static_dir.as_ref();
+        info!("Building assets from {}", static_dir.display());
+
+        let mut assets = HashMap::new();
+        let mut manifest = HashMap::new();
+
+        if !static_dir.exists() {
+            warn!(
+                "Static directory {} not found, asset pipeline disabled",
+                static_dir.display()
+            );
+            return Ok(Self::new());
+        }
+
+        let mut files = Vec::new();
+        collect_files(static_dir, &mut files)?;
+
+        for file in files {
+            let logical_path = file
+                .strip_prefix(static_dir)
+                .context("Asset outside static directory")?
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            let Some(content_type) = content_type_for(&logical_path) else {
+                continue;
+            };
+
+            let source = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read asset: {}", file.display()))?;
+
+            let minified = if content_type.starts_with("text/css") {
+                minify_css(&source)
+            } else {
+                minify_js(&source)
+            };
+
+            let hash = content_hash(minified.as_bytes());
+            let fingerprinted_path = fingerprint_path(&logical_path, &hash);
+
+            debug!(
+                "Built asset {} -> {} ({} -> {} bytes)",
+                logical_path,
+                fingerprinted_path,
+                source.len(),
+                minified.len()
+            );
+
+            manifest.insert(logical_path, fingerprinted_path.clone());
+            assets.insert(
+                fingerprinted_path,
+                BuiltAsset {
+                    content: minified.into_bytes(),
+                    content_type,
+                    hash,
+                },
+            );
+        }
+
+        info!("Asset pipeline built {} assets", assets.len());
+
+        Ok(Self {
+            assets: Arc::new(assets),
+            manifest: Arc::new(manifest),
+        })
+    }
+
+    /// Resolve the public URL for a logical asset path such as `css/main.css`
+    pub fn asset_url(&self, logical_path: &str) -> String {
+        let logical_path = logical_path.trim_start_matches('/');
+        match self.manifest.get(logical_path) {
+            Some(fingerprinted) => format!("{}/{}", ASSET_URL_PREFIX, fingerprinted),
+            None => format!("{}/{}", STATIC_URL_PREFIX, logical_path),
+        }
+    }
+
+    /// Look up a built asset by its fingerprinted path
+    pub fn get(&self, fingerprinted_path: &str) -> Option<&BuiltAsset> {
+        self.assets.get(fingerprinted_path)
+    }
+
+    /// List all manifest entries
+    #[allow(dead_code)]
+    pub fn manifest(&self) -> Vec<AssetManifestEntry> {
+        let mut entries: Vec<AssetManifestEntry> = self
+            .manifest
+            .iter()
+            .map(|(logical, fingerprinted)| AssetManifestEntry {
+                logical_path: logical.clone(),
+                fingerprinted_path: fingerprinted.clone(),
+                size: self
+                    .assets
+                    .get(fingerprinted)
+                    .map(|a| a.content.len())
+                    .unwrap_or(0),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.logical_path.cmp(&b.logical_path));
+        entries
+    }
+}
+
+/// Recursively collect regular files under a directory
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    for entry in
+        fs::read_dir(dir).with_context(|| format!("Failed to read directory: {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Content type for assets handled by the pipeline
+fn content_type_for(path: &str) -> Option<&'static str> {
+    if path.ends_with(".css") {
+        Some("text/css; charset=utf-8")
+    } else if path.ends_with(".js") {
+        Some("application/javascript; charset=utf-8")
+    } else {
+        None
+    }
+}
+
+/// Short hex digest of asset content
+fn content_hash(content: &[u8]) -> String {
+    let digest = Sha256::digest(content);
+    format!("{:x}", digest)[..8].to_string()
+}
+
+/// Insert the hash before the file extension: `css/main.css` -> `css/main.<hash>.css`
+fn fingerprint_path(logical_path: &str, hash: &str) -> String {
+    match logical_path.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !stem.ends_with('/') => {
+            format!("{}.{}.{}", stem, hash, ext)
+        }
+        _ => format!("{}.{}", logical_path, hash),
+    }
+}
+
+/// Minify CSS by stripping comments and collapsing insignificant whitespace
+fn minify_css(source: &str) -> String {
+    // Strip /* ... */ comments
+    let mut without_comments = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        without_comments.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    without_comments.push_str(rest);
+
+    // Collapse whitespace runs into a single space
+    let collapsed = without_comments
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // Remove spaces around structural punctuation
+    let mut output = String::with_capacity(collapsed.len());
+    let chars: Vec<char> = collapsed.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let prev = if i > 0 { chars[i - 1] } else { ' ' };
+            let next = chars.get(i + 1).copied().unwrap_or(' ');
+            if matches!(prev, '{' | '}' | ';' | ',' | ':' | '>')
+                || matches!(next, '{' | '}' | ';' | ',' | '>')
+            {
+                continue;
+            }
+        }
+        output.push(c);
+    }
+
+    output.replace(";}", "}")
+}
+
+/// Conservatively minify JavaScript: drop blank lines, full-line comments and indentation
+///
+/// Newlines are preserved so automatic semicolon insertion keeps working.
+fn minify_js(source: &str) -> String {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minify_css() {
+        let css = "/* header */\nbody {\n    color: red;\n    margin: 0 auto;\n}\n\na:hover, a:focus {\n  color: blue;\n}\n";
+        assert_eq!(
+            minify_css(css),
+            "body{color:red;margin:0 auto}a:hover,a:focus{color:blue}"
+        );
+    }
+
+    #[test]
+    fn test_minify_js_keeps_statements() {
+        let js =
+            "// comment\nfunction f() {\n    return 1;\n}\n\nconst url = 'https://example.com';\n";
+        assert_eq!(
+            minify_js(js),
+            "function f() {\nreturn 1;\n}\nconst url = 'https://example.com';"
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_path_and_fallback_url() {
+        assert_eq!(
+            fingerprint_path("css/main.css", "deadbeef"),
+            "css/main.deadbeef.css"
+        );
+
+        let service = AssetService::new();
+        assert_eq!(service.asset_url("css/main.css"), "/static/css/main.css");
+        assert_eq!(service.asset_url("/js/app.js"), "/static/js/app.js");
+    }
+}
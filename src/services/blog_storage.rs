@@ -6,7 +6,22 @@ use std::sync::Arc;
 use tokio::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
-use super::dropbox::{DropboxClient, FileMetadata};
+use super::dropbox::FileMetadata;
+use super::storage::StorageBackend;
+use crate::models::{is_absolute_http_url, Post, CANONICAL_URL_KEY, SYNDICATION_LINKS_KEY};
+
+/// Frontmatter keys mapped onto `BlogPostMetadata` fields
+pub const METADATA_KEYS: [&str; 9] = [
+    "title",
+    "slug",
+    "created_at",
+    "updated_at",
+    "category",
+    "tags",
+    "published",
+    "author",
+    "excerpt",
+];
 
 /// Blog post metadata extracted from markdown frontmatter
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +35,45 @@ pub struct BlogPostMetadata {
     pub published: bool,
     pub author: Option<String>,
     pub excerpt: Option<String>,
+    /// Other frontmatter keys, written back unchanged after the known fields
+    #[serde(flatten, default)]
+    pub extra: serde_yaml::Mapping,
+}
+
+impl BlogPostMetadata {
+    /// YAML of the extra frontmatter keys for storage, `None` when there are none
+    pub fn extra_yaml(&self) -> Option<String> {
+        if self.extra.is_empty() {
+            return None;
+        }
+        serde_yaml::to_string(&self.extra).ok()
+    }
+
+    /// `canonical_url` frontmatter of a cross-post
+    pub fn canonical_url(&self) -> Option<String> {
+        self.extra
+            .get(CANONICAL_URL_KEY)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|url| is_absolute_http_url(url))
+            .map(str::to_string)
+    }
+
+    /// `syndication_links` frontmatter, a list of URLs or a single one
+    pub fn syndication_links(&self) -> Vec<String> {
+        let values = match self.extra.get(SYNDICATION_LINKS_KEY) {
+            Some(serde_yaml::Value::Sequence(values)) => values.iter().collect(),
+            Some(value) => vec![value],
+            None => vec![],
+        };
+        values
+            .into_iter()
+            .filter_map(|value| value.as_str())
+            .map(str::trim)
+            .filter(|url| is_absolute_http_url(url))
+            .map(str::to_string)
+            .collect()
+    }
 }
 
 /// Complete blog post with content and metadata
@@ -31,6 +85,29 @@ pub struct BlogPost {
     pub file_metadata: Option<FileMetadata>,
 }
 
+impl BlogPost {
+    /// Markdown file contents of a stored post, keeping its custom frontmatter
+    pub fn from_post(post: &Post) -> Self {
+        Self {
+            metadata: BlogPostMetadata {
+                title: post.title.clone(),
+                slug: post.slug.clone(),
+                created_at: post.created_at,
+                updated_at: post.updated_at,
+                category: post.category.clone(),
+                tags: post.get_tags(),
+                published: post.published,
+                author: post.author.clone(),
+                excerpt: post.excerpt.clone(),
+                extra: post.custom_frontmatter_map(),
+            },
+            content: post.content.clone(),
+            dropbox_path: post.dropbox_path.clone(),
+            file_metadata: None,
+        }
+    }
+}
+
 /// Blog folder structure management
 #[derive(Debug, Clone)]
 pub struct BlogFolders {
@@ -94,22 +171,22 @@ impl RateLimiter {
     }
 }
 
-/// High-level blog storage service using Dropbox
+/// High-level blog storage service on top of a storage backend (Dropbox by default)
 #[derive(Clone)]
 pub struct BlogStorageService {
-    dropbox_client: Arc<DropboxClient>,
+    storage: Arc<dyn StorageBackend>,
     folders: BlogFolders,
     rate_limiter: Arc<tokio::sync::Mutex<RateLimiter>>,
 }
 
 impl BlogStorageService {
     /// Create a new blog storage service
-    pub fn new(dropbox_client: Arc<DropboxClient>) -> Self {
+    pub fn new(storage: Arc<dyn StorageBackend>) -> Self {
         // Dropbox allows 500 requests per minute
         let rate_limiter = RateLimiter::new(450, Duration::from_secs(60)); // Leave some buffer
 
         Self {
-            dropbox_client,
+            storage,
             folders: BlogFolders::default(),
             rate_limiter: Arc::new(tokio::sync::Mutex::new(rate_limiter)),
         }
@@ -117,11 +194,11 @@ impl BlogStorageService {
 
     /// Create a new service with custom folder configuration
     #[allow(dead_code)]
-    pub fn with_folders(dropbox_client: Arc<DropboxClient>, folders: BlogFolders) -> Self {
+    pub fn with_folders(storage: Arc<dyn StorageBackend>, folders: BlogFolders) -> Self {
         let rate_limiter = RateLimiter::new(450, Duration::from_secs(60));
 
         Self {
-            dropbox_client,
+            storage,
             folders,
             rate_limiter: Arc::new(tokio::sync::Mutex::new(rate_limiter)),
         }
@@ -133,7 +210,7 @@ impl BlogStorageService {
         limiter.check_rate_limit().await
     }
 
-    /// Initialize blog folder structure in Dropbox
+    /// Initialize blog folder structure in storage
     pub async fn initialize_blog_structure(&self) -> Result<()> {
         info!("Initializing blog folder structure...");
 
@@ -153,13 +230,13 @@ impl BlogStorageService {
         for folder in folders {
             self.check_rate_limit().await?;
 
-            match self.dropbox_client.list_folder(folder).await {
+            match self.storage.list_folder(folder).await {
                 Ok(_) => {
                     debug!("Folder already exists: {}", folder);
                 }
                 Err(_) => {
                     info!("Creating folder: {}", folder);
-                    self.dropbox_client
+                    self.storage
                         .create_folder(folder)
                         .await
                         .with_context(|| format!("Failed to create folder: {}", folder))?;
@@ -177,15 +254,15 @@ impl BlogStorageService {
 
         info!("Listing published blog posts from {}", self.folders.posts);
 
-        let folder_result = self
-            .dropbox_client
+        let entries = self
+            .storage
             .list_folder(&self.folders.posts)
             .await
             .with_context(|| format!("Failed to list posts folder: {}", self.folders.posts))?;
 
         let mut posts = Vec::new();
 
-        for entry in folder_result.entries {
+        for entry in entries {
             if entry.name.ends_with(".md") || entry.name.ends_with(".markdown") {
                 debug!("Found markdown file: {}", entry.name);
 
@@ -219,15 +296,15 @@ impl BlogStorageService {
 
         info!("Listing draft blog posts from {}", self.folders.drafts);
 
-        let folder_result = self
-            .dropbox_client
+        let entries = self
+            .storage
             .list_folder(&self.folders.drafts)
             .await
             .with_context(|| format!("Failed to list drafts folder: {}", self.folders.drafts))?;
 
         let mut posts = Vec::new();
 
-        for entry in folder_result.entries {
+        for entry in entries {
             if entry.name.ends_with(".md") || entry.name.ends_with(".markdown") {
                 debug!("Found draft file: {}", entry.name);
 
@@ -295,7 +372,7 @@ impl BlogStorageService {
 
         info!("Saving post '{}' to {}", post.metadata.title, file_path);
 
-        self.dropbox_client
+        self.storage
             .upload_file(&file_path, &content)
             .await
             .with_context(|| format!("Failed to save post to {}", file_path))?;
@@ -304,6 +381,54 @@ impl BlogStorageService {
         Ok(())
     }
 
+    /// Resolve a sync target to a file in the posts folder
+    ///
+    /// Bare file names are looked up in the posts folder; absolute paths must
+    /// point inside it. Only markdown files are accepted.
+    pub fn resolve_post_path(&self, path: &str) -> Result<String> {
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.folders.posts, path)
+        };
+
+        let folder = format!("{}/", self.folders.posts.to_lowercase());
+        let lower = path.to_lowercase();
+        let name = lower.strip_prefix(&folder).unwrap_or_default();
+        if name.is_empty() || name.contains('/') {
+            anyhow::bail!("{} is not a file in {}", path, self.folders.posts);
+        }
+        if !(name.ends_with(".md") || name.ends_with(".markdown")) {
+            anyhow::bail!("{} is not a markdown file", path);
+        }
+
+        Ok(path)
+    }
+
+    /// Load a single post file without crawling its folder
+    pub async fn load_post(&self, path: &str) -> Result<Option<BlogPost>> {
+        let path = self.resolve_post_path(path)?;
+
+        self.check_rate_limit().await?;
+        let file_metadata = self
+            .storage
+            .get_metadata(&path)
+            .await
+            .with_context(|| format!("Failed to get metadata of {}", path))?;
+
+        self.load_blog_post_from_file(&file_metadata).await
+    }
+
+    /// Download a post's markdown file byte-for-byte, frontmatter included
+    pub async fn download_post_source(&self, dropbox_path: &str) -> Result<String> {
+        self.check_rate_limit().await?;
+
+        self.storage
+            .download_text_file(dropbox_path)
+            .await
+            .with_context(|| format!("Failed to download post source from {}", dropbox_path))
+    }
+
     /// Delete a blog post
     #[allow(dead_code)]
     pub async fn delete_post(&self, slug: &str) -> Result<bool> {
@@ -313,14 +438,14 @@ impl BlogStorageService {
 
         // Try to find and delete from published posts
         let published_path = format!("{}/{}.md", self.folders.posts, slug);
-        if let Ok(_) = self.dropbox_client.delete_file(&published_path).await {
+        if let Ok(_) = self.storage.delete_file(&published_path).await {
             info!("Deleted published post: {}", published_path);
             return Ok(true);
         }
 
         // Try to find and delete from drafts
         let draft_path = format!("{}/{}.md", self.folders.drafts, slug);
-        if let Ok(_) = self.dropbox_client.delete_file(&draft_path).await {
+        if let Ok(_) = self.storage.delete_file(&draft_path).await {
             info!("Deleted draft post: {}", draft_path);
             return Ok(true);
         }
@@ -346,7 +471,7 @@ impl BlogStorageService {
 
                 // Delete from drafts
                 let draft_path = format!("{}/{}.md", self.folders.drafts, slug);
-                if let Ok(_) = self.dropbox_client.delete_file(&draft_path).await {
+                if let Ok(_) = self.storage.delete_file(&draft_path).await {
                     info!("Post '{}' published successfully", slug);
                     return Ok(true);
                 }
@@ -365,7 +490,7 @@ impl BlogStorageService {
         self.check_rate_limit().await?;
 
         let content = self
-            .dropbox_client
+            .storage
             .download_text_file(&file_metadata.path_display)
             .await
             .with_context(|| format!("Failed to download file: {}", file_metadata.path_display))?;
@@ -373,8 +498,8 @@ impl BlogStorageService {
         self.parse_blog_post(&content, file_metadata)
     }
 
-    /// Parse markdown content into BlogPost
-    fn parse_blog_post(
+    /// Parse markdown content into BlogPost, `None` when it has no frontmatter
+    pub fn parse_blog_post(
         &self,
         content: &str,
         file_metadata: &FileMetadata,
@@ -490,6 +615,12 @@ impl BlogStorageService {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let extra = yaml_map
+            .iter()
+            .filter(|(key, _)| !key.as_str().is_some_and(|key| METADATA_KEYS.contains(&key)))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
         Ok(BlogPostMetadata {
             title,
             slug,
@@ -500,12 +631,12 @@ impl BlogStorageService {
             published,
             author,
             excerpt,
+            extra,
         })
     }
 
     /// Serialize blog post to markdown with frontmatter
-    #[allow(dead_code)]
-    fn serialize_blog_post(&self, post: &BlogPost) -> Result<String> {
+    pub fn serialize_blog_post(&self, post: &BlogPost) -> Result<String> {
         let mut frontmatter = serde_yaml::to_string(&post.metadata)
             .context("Failed to serialize metadata to YAML")?;
 
@@ -566,6 +697,7 @@ impl BlogStorageService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::DropboxClient;
     use std::sync::Arc;
 
     fn create_test_service() -> BlogStorageService {
@@ -629,6 +761,7 @@ This is the content."#;
                 published: true,
                 author: Some("Test Author".to_string()),
                 excerpt: None,
+                extra: serde_yaml::Mapping::new(),
             },
             content: "This is the post content.".to_string(),
             dropbox_path: "/test/path".to_string(),
@@ -640,4 +773,52 @@ This is the content."#;
         assert!(serialized.contains("title: Test Post"));
         assert!(serialized.contains("This is the post content."));
     }
+
+    #[test]
+    fn test_custom_frontmatter_round_trip() {
+        let service = create_test_service();
+        let file_metadata = FileMetadata {
+            name: "hello.md".to_string(),
+            path_lower: "/blogstorage/posts/hello.md".to_string(),
+            path_display: "/BlogStorage/posts/hello.md".to_string(),
+            size: None,
+            content_hash: None,
+            client_modified: None,
+            server_modified: None,
+        };
+        let content = "---\ntitle: Hello\nseries: rust-intro\ncover:\n  image: /media/a.png\n  alt: Cover\n---\n\nBody";
+
+        let post = service
+            .parse_blog_post(content, &file_metadata)
+            .unwrap()
+            .unwrap();
+        assert_eq!(post.metadata.extra.len(), 2);
+        assert!(!post.metadata.extra.contains_key("title"));
+
+        let serialized = service.serialize_blog_post(&post).unwrap();
+        assert!(serialized.contains("title: Hello"));
+        assert!(serialized.contains("series: rust-intro"));
+        assert!(serialized.contains("  image: /media/a.png"));
+    }
+
+    #[test]
+    fn test_resolve_post_path() {
+        let service = create_test_service();
+
+        assert_eq!(
+            service.resolve_post_path("hello.md").unwrap(),
+            "/BlogStorage/posts/hello.md"
+        );
+        assert_eq!(
+            service
+                .resolve_post_path("/blogstorage/Posts/Hello.md")
+                .unwrap(),
+            "/blogstorage/Posts/Hello.md"
+        );
+        assert!(service
+            .resolve_post_path("/BlogStorage/drafts/hello.md")
+            .is_err());
+        assert!(service.resolve_post_path("../config/site.md").is_err());
+        assert!(service.resolve_post_path("notes.txt").is_err());
+    }
 }
@@ -1,11 +1,15 @@
 use anyhow::Result;
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+use super::EventBus;
 use crate::models::{Post, PostSummary};
 
 /// Performance metrics for monitoring cache effectiveness
@@ -143,6 +147,33 @@ impl CachedStats {
     }
 }
 
+/// Rendered HTTP response kept for anonymous requests
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    pub cached_at: Instant,
+    pub expires_at: Instant,
+}
+
+impl CachedResponse {
+    pub fn new(status: StatusCode, headers: HeaderMap, body: Bytes, ttl: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            status,
+            headers,
+            body,
+            cached_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() > self.expires_at
+    }
+}
+
 /// Cache configuration
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
@@ -156,6 +187,12 @@ pub struct CacheConfig {
     pub max_posts: usize,
     #[allow(dead_code)]
     pub max_lists: usize,
+    /// Rendered pages and feeds kept by the HTTP response cache
+    pub max_responses: usize,
+    /// How long a slug or media path that was not found is answered without a lookup
+    pub not_found_ttl: Duration,
+    /// Not-found slugs and paths remembered at most
+    pub max_not_found: usize,
     #[allow(dead_code)]
     pub cleanup_interval: Duration,
 }
@@ -168,6 +205,9 @@ impl Default for CacheConfig {
             stats_ttl: Duration::from_secs(900),     // 15 minutes
             max_posts: 1000,
             max_lists: 50,
+            max_responses: 500,
+            not_found_ttl: Duration::from_secs(30),
+            max_not_found: 10_000,
             cleanup_interval: Duration::from_secs(300), // 5 minutes
         }
     }
@@ -179,6 +219,9 @@ pub struct CacheService {
     posts: Arc<RwLock<HashMap<String, CachedPost>>>,
     post_lists: Arc<RwLock<HashMap<String, CachedPostList>>>,
     stats: Arc<RwLock<Option<CachedStats>>>,
+    responses: Arc<RwLock<HashMap<String, CachedResponse>>>,
+    /// Remembered not-found lookups, by `post:` or `media:` key
+    not_found: Arc<RwLock<NotFoundEntries>>,
     metrics: Arc<RwLock<PerformanceMetrics>>,
     #[allow(dead_code)]
     config: CacheConfig,
@@ -198,6 +241,8 @@ impl CacheService {
             posts: Arc::new(RwLock::new(HashMap::new())),
             post_lists: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(None)),
+            responses: Arc::new(RwLock::new(HashMap::new())),
+            not_found: Arc::new(RwLock::new(NotFoundEntries::default())),
             metrics: Arc::new(RwLock::new(PerformanceMetrics::default())),
             config,
             last_cleanup: Arc::new(RwLock::new(Instant::now())),
@@ -343,6 +388,8 @@ impl CacheService {
             let mut stats = self.stats.write().await;
             *stats = None;
         }
+        self.invalidate_responses().await;
+        self.invalidate_not_found().await;
 
         info!("Invalidated all cache entries");
         Ok(())
@@ -368,10 +415,165 @@ impl CacheService {
             *stats = None;
         }
 
+        // Any page may list or link to the post
+        self.invalidate_responses().await;
+
+        // The slug may have just been created
+        {
+            let mut not_found = self.not_found.write().await;
+            not_found.expiry.remove(&not_found_post_key(slug));
+        }
+
         debug!("Invalidated cache for post: {}", slug);
         Ok(())
     }
 
+    /// Drop the cached posts with one of `slugs` or a slug starting with one of
+    /// `prefixes`, along with all post lists and stats; returns the posts dropped
+    pub async fn purge_posts(&self, slugs: &[String], prefixes: &[String]) -> Result<usize> {
+        let purged = {
+            let mut posts = self.posts.write().await;
+            let before = posts.len();
+            posts.retain(|slug, _| {
+                !slugs.contains(slug) && !prefixes.iter().any(|prefix| slug.starts_with(prefix))
+            });
+            before - posts.len()
+        };
+        {
+            let mut not_found = self.not_found.write().await;
+            not_found
+                .expiry
+                .retain(|key, _| match key.strip_prefix(NOT_FOUND_POST_PREFIX) {
+                    Some(slug) => {
+                        !slugs.iter().any(|s| s == slug)
+                            && !prefixes.iter().any(|prefix| slug.starts_with(prefix))
+                    }
+                    None => true,
+                });
+        }
+        {
+            let mut post_lists = self.post_lists.write().await;
+            post_lists.clear();
+        }
+        {
+            let mut stats = self.stats.write().await;
+            *stats = None;
+        }
+        self.invalidate_responses().await;
+
+        info!("Purged {} cached posts", purged);
+        Ok(purged)
+    }
+
+    /// Get a cached HTTP response by key
+    pub async fn get_response(&self, key: &str) -> Option<CachedResponse> {
+        let responses = self.responses.read().await;
+        responses
+            .get(key)
+            .filter(|cached| !cached.is_expired())
+            .cloned()
+    }
+
+    /// Cache an HTTP response
+    pub async fn set_response(&self, key: &str, response: CachedResponse) {
+        let mut responses = self.responses.write().await;
+
+        if responses.len() >= self.config.max_responses && !responses.contains_key(key) {
+            responses.retain(|_, cached| !cached.is_expired());
+        }
+        if responses.len() >= self.config.max_responses && !responses.contains_key(key) {
+            let oldest = responses
+                .iter()
+                .min_by_key(|(_, cached)| cached.cached_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                responses.remove(&oldest);
+            }
+        }
+
+        responses.insert(key.to_string(), response);
+        debug!("Cached response: {}", key);
+    }
+
+    /// Drop every cached HTTP response
+    pub async fn invalidate_responses(&self) {
+        let mut responses = self.responses.write().await;
+        responses.clear();
+    }
+
+    /// Drop cached responses whenever a background task changes published content
+    ///
+    /// Syncs and imports may create posts, so remembered not-found slugs go too.
+    pub fn invalidate_responses_on_events(&self, events: &EventBus) {
+        let cache = self.clone();
+        let mut events = Box::pin(events.subscribe());
+        tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                if event.changes_content() {
+                    debug!("Invalidating cached responses after {}", event.name());
+                    cache.invalidate_responses().await;
+                    cache.invalidate_not_found().await;
+                }
+            }
+        });
+    }
+
+    /// Whether no post had `slug` when it was last looked up
+    pub async fn is_missing_post(&self, slug: &str) -> bool {
+        self.is_missing(&not_found_post_key(slug)).await
+    }
+
+    /// Remember that no post has `slug`, until the TTL passes or the post is created
+    pub async fn remember_missing_post(&self, slug: &str) {
+        self.remember_missing(not_found_post_key(slug)).await;
+    }
+
+    /// Whether the media file at `path` was missing when it was last requested
+    pub async fn is_missing_media(&self, path: &str) -> bool {
+        self.is_missing(&not_found_media_key(path)).await
+    }
+
+    /// Remember that the media file at `path` does not exist
+    pub async fn remember_missing_media(&self, path: &str) {
+        self.remember_missing(not_found_media_key(path)).await;
+    }
+
+    /// Forget the missing media paths, as a new upload may have filled one of them
+    pub async fn forget_missing_media(&self) {
+        let mut not_found = self.not_found.write().await;
+        not_found
+            .expiry
+            .retain(|key, _| !key.starts_with(NOT_FOUND_MEDIA_PREFIX));
+    }
+
+    /// Forget every remembered not-found slug and media path
+    pub async fn invalidate_not_found(&self) {
+        let mut not_found = self.not_found.write().await;
+        *not_found = NotFoundEntries::default();
+    }
+
+    async fn is_missing(&self, key: &str) -> bool {
+        let not_found = self.not_found.read().await;
+        let missing = not_found
+            .expiry
+            .get(key)
+            .is_some_and(|expires_at| Instant::now() <= *expires_at);
+        if missing {
+            debug!("Negative cache hit for {}", key);
+        }
+        missing
+    }
+
+    async fn remember_missing(&self, key: String) {
+        let mut not_found = self.not_found.write().await;
+        // Probing bots ask for endless made-up URLs, so the entries are capped
+        not_found.insert(
+            key,
+            Instant::now() + self.config.not_found_ttl,
+            self.config.max_not_found,
+        );
+    }
+
     /// Generate cache key for post lists based on filters
     #[allow(dead_code)]
     pub fn generate_list_cache_key(
@@ -487,6 +689,20 @@ impl CacheService {
             removed_count += original_len - post_lists.len();
         }
 
+        // Clean up expired responses
+        {
+            let mut responses = self.responses.write().await;
+            let original_len = responses.len();
+            responses.retain(|_, cached| !cached.is_expired());
+            removed_count += original_len - responses.len();
+        }
+
+        // Clean up expired not-found entries
+        {
+            let mut not_found = self.not_found.write().await;
+            removed_count += not_found.remove_expired(Instant::now());
+        }
+
         // Clean up expired stats
         {
             let mut stats = self.stats.write().await;
@@ -549,6 +765,12 @@ impl CacheService {
         let post_lists = self.post_lists.read().await;
         stats.insert("cached_lists".to_string(), post_lists.len());
 
+        let responses = self.responses.read().await;
+        stats.insert("cached_responses".to_string(), responses.len());
+
+        let not_found = self.not_found.read().await;
+        stats.insert("cached_not_found".to_string(), not_found.expiry.len());
+
         let blog_stats = self.stats.read().await;
         stats.insert(
             "cached_stats".to_string(),
@@ -559,6 +781,74 @@ impl CacheService {
     }
 }
 
+/// Remembered not-found lookups, dropped oldest first
+///
+/// Every entry lives for the same TTL, so insertion order is also expiry
+/// order and both eviction and expiry take entries from the queue's front.
+#[derive(Default)]
+struct NotFoundEntries {
+    expiry: HashMap<String, Instant>,
+    /// Keys in insertion order; entries replaced or removed since are skipped
+    order: VecDeque<(String, Instant)>,
+}
+
+impl NotFoundEntries {
+    fn insert(&mut self, key: String, expires_at: Instant, max_entries: usize) {
+        while self.expiry.len() >= max_entries && !self.expiry.contains_key(&key) {
+            if self.pop_oldest().is_none() {
+                break;
+            }
+        }
+        self.expiry.insert(key.clone(), expires_at);
+        self.order.push_back((key, expires_at));
+
+        // Removals leave their keys in the queue until they reach the front
+        if self.order.len() > 2 * max_entries.max(self.expiry.len()) {
+            let expiry = &self.expiry;
+            self.order
+                .retain(|(key, expires_at)| expiry.get(key) == Some(expires_at));
+        }
+    }
+
+    /// Drop the entries expired at `now`, returning how many went
+    fn remove_expired(&mut self, now: Instant) -> usize {
+        let mut removed = 0;
+        while self
+            .order
+            .front()
+            .is_some_and(|(_, expires_at)| *expires_at < now)
+        {
+            if self.pop_oldest() == Some(true) {
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Pop the queue's front, removing its entry unless it was replaced or
+    /// removed since; `None` once the queue is empty
+    fn pop_oldest(&mut self) -> Option<bool> {
+        let (key, expires_at) = self.order.pop_front()?;
+        let current = self.expiry.get(&key) == Some(&expires_at);
+        if current {
+            self.expiry.remove(&key);
+        }
+        Some(current)
+    }
+}
+
+const NOT_FOUND_POST_PREFIX: &str = "post:";
+const NOT_FOUND_MEDIA_PREFIX: &str = "media:";
+
+fn not_found_post_key(slug: &str) -> String {
+    format!("{}{}", NOT_FOUND_POST_PREFIX, slug)
+}
+
+/// `/images/a.png` and `images/a.png` name the same media file
+fn not_found_media_key(path: &str) -> String {
+    format!("{}{}", NOT_FOUND_MEDIA_PREFIX, path.trim_start_matches('/'))
+}
+
 impl Default for CacheService {
     fn default() -> Self {
         Self::new()
@@ -632,6 +922,114 @@ mod tests {
         assert!(cache.get_post("test-post").await.is_none());
     }
 
+    #[tokio::test]
+    async fn test_purge_posts_by_slug_and_prefix() {
+        let cache = CacheService::new();
+        for slug in ["rust-intro", "rust-async", "go-intro", "about"] {
+            let post = Post::new(CreatePost {
+                slug: slug.to_string(),
+                title: slug.to_string(),
+                content: String::new(),
+                html_content: String::new(),
+                category: None,
+                tags: vec![],
+                published: true,
+                featured: false,
+                author: None,
+                excerpt: None,
+                dropbox_path: format!("/posts/{}.md", slug),
+            });
+            cache.set_post(slug, post).await.unwrap();
+        }
+
+        let purged = cache
+            .purge_posts(&["about".to_string()], &["rust-".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(purged, 3);
+        assert!(cache.get_post("go-intro").await.is_some());
+        assert!(cache.get_post("rust-async").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_post_invalidation_drops_cached_responses() {
+        let cache = CacheService::new();
+        let response = CachedResponse::new(
+            StatusCode::OK,
+            HeaderMap::new(),
+            Bytes::from_static(b"<html></html>"),
+            Duration::from_secs(60),
+        );
+        cache.set_response("default|/", response).await;
+        assert!(cache.get_response("default|/").await.is_some());
+
+        cache.invalidate_post("hello").await.unwrap();
+        assert!(cache.get_response("default|/").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_not_found_entries_expire_and_are_invalidated() {
+        let cache = CacheService::with_config(CacheConfig {
+            not_found_ttl: Duration::from_millis(50),
+            max_not_found: 2,
+            ..Default::default()
+        });
+
+        cache.remember_missing_post("wp-login").await;
+        cache.remember_missing_media("/images/ghost.png").await;
+        assert!(cache.is_missing_post("wp-login").await);
+        assert!(cache.is_missing_media("images/ghost.png").await);
+        assert!(!cache.is_missing_post("ghost.png").await);
+
+        // Creating the post forgets it; new uploads forget missing media
+        cache.invalidate_post("wp-login").await.unwrap();
+        assert!(!cache.is_missing_post("wp-login").await);
+        cache.forget_missing_media().await;
+        assert!(!cache.is_missing_media("/images/ghost.png").await);
+
+        // The oldest entry makes room at capacity
+        for slug in ["a", "b", "c"] {
+            cache.remember_missing_post(slug).await;
+        }
+        assert!(!cache.is_missing_post("a").await);
+        assert!(cache.is_missing_post("c").await);
+
+        cache.purge_posts(&[], &["b".to_string()]).await.unwrap();
+        assert!(!cache.is_missing_post("b").await);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(!cache.is_missing_post("c").await);
+    }
+
+    #[test]
+    fn test_not_found_queue_skips_replaced_and_removed_entries() {
+        let now = Instant::now();
+        let at = |secs| now + Duration::from_secs(secs);
+        let mut entries = NotFoundEntries::default();
+
+        entries.insert("a".to_string(), at(1), 2);
+        entries.insert("b".to_string(), at(2), 2);
+        // Remembering "a" again moves it behind "b"
+        entries.insert("a".to_string(), at(3), 2);
+        entries.insert("c".to_string(), at(4), 2);
+        let mut keys: Vec<&String> = entries.expiry.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["a", "c"]);
+
+        // Removed keys are skipped when their turn comes
+        entries.expiry.remove("a");
+        entries.insert("d".to_string(), at(5), 2);
+        entries.insert("e".to_string(), at(6), 2);
+        let mut keys: Vec<&String> = entries.expiry.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["d", "e"]);
+        assert!(entries.order.len() <= 4);
+
+        assert_eq!(entries.remove_expired(at(5) + Duration::from_millis(1)), 1);
+        assert_eq!(entries.expiry.keys().collect::<Vec<_>>(), ["e"]);
+    }
+
     #[tokio::test]
     async fn test_cache_key_generation() {
         let cache = CacheService::new();
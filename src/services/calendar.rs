@@ -0,0 +1,211 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, Utc};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+use crate::models::{CalendarDay, CalendarEntry, CalendarEntryStatus, CalendarMonth, Post};
+use crate::services::DatabaseService;
+
+/// Service that lays posts out on a monthly content calendar
+#[derive(Clone)]
+pub struct CalendarService {
+    database: DatabaseService,
+}
+
+impl CalendarService {
+    /// Create a new calendar service
+    pub fn new(database: DatabaseService) -> Self {
+        Self { database }
+    }
+
+    /// Build the calendar for a `YYYY-MM` month, or the current month when `None`
+    pub async fn get_month(&self, month: Option<&str>) -> Result<CalendarMonth> {
+        let first_day = match month {
+            Some(month) => parse_month(month)?,
+            None => {
+                let today = Utc::now().date_naive();
+                NaiveDate::from_ymd_opt(today.year(), today.month(), 1)
+                    .context("Invalid current date")?
+            }
+        };
+        let next_first_day = next_month(first_day);
+
+        debug!(
+            "Building content calendar for {}",
+            first_day.format("%Y-%m")
+        );
+
+        let posts = self
+            .database
+            .list_posts_for_calendar(
+                first_day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc(),
+                next_first_day
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap_or_default()
+                    .and_utc(),
+            )
+            .await?;
+
+        Ok(build_calendar_month(first_day, &posts))
+    }
+}
+
+/// Parse `YYYY-MM` into the first day of that month
+pub fn parse_month(month: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+        .with_context(|| format!("Invalid month '{}', expected YYYY-MM", month))
+}
+
+fn next_month(first_day: NaiveDate) -> NaiveDate {
+    first_day
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(first_day)
+}
+
+fn previous_month(first_day: NaiveDate) -> NaiveDate {
+    first_day
+        .checked_sub_months(chrono::Months::new(1))
+        .unwrap_or(first_day)
+}
+
+/// Classify a post and pick the timestamp that dates it on the calendar
+fn calendar_entry(post: &Post) -> CalendarEntry {
+    let (status, date) = if post.published {
+        (
+            CalendarEntryStatus::Published,
+            post.published_at.unwrap_or(post.created_at),
+        )
+    } else if let Some(publish_at) = post.publish_at {
+        (CalendarEntryStatus::Scheduled, publish_at)
+    } else {
+        (CalendarEntryStatus::Draft, post.updated_at)
+    };
+
+    CalendarEntry {
+        slug: post.slug.clone(),
+        title: post.title.clone(),
+        category: post.category.clone(),
+        status,
+        date,
+    }
+}
+
+/// Group posts by day within the month starting at `first_day`
+fn build_calendar_month(first_day: NaiveDate, posts: &[Post]) -> CalendarMonth {
+    let next_first_day = next_month(first_day);
+    let mut days: BTreeMap<NaiveDate, Vec<CalendarEntry>> = BTreeMap::new();
+
+    for entry in posts.iter().map(calendar_entry) {
+        let day = entry.date.date_naive();
+        if day >= first_day && day < next_first_day {
+            days.entry(day).or_default().push(entry);
+        }
+    }
+
+    let count = |status: CalendarEntryStatus| {
+        days.values()
+            .flatten()
+            .filter(|entry| entry.status == status)
+            .count()
+    };
+    let published_count = count(CalendarEntryStatus::Published);
+    let scheduled_count = count(CalendarEntryStatus::Scheduled);
+    let draft_count = count(CalendarEntryStatus::Draft);
+
+    CalendarMonth {
+        month: first_day.format("%Y-%m").to_string(),
+        previous_month: previous_month(first_day).format("%Y-%m").to_string(),
+        next_month: next_first_day.format("%Y-%m").to_string(),
+        days: days
+            .into_iter()
+            .map(|(date, mut entries)| {
+                entries.sort_by_key(|entry| entry.date);
+                CalendarDay { date, entries }
+            })
+            .collect(),
+        published_count,
+        scheduled_count,
+        draft_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreatePost;
+    use chrono::TimeZone;
+
+    fn post(slug: &str, published: bool) -> Post {
+        Post::new(CreatePost {
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            excerpt: None,
+            category: None,
+            tags: vec![],
+            published,
+            featured: false,
+            author: None,
+            dropbox_path: String::new(),
+        })
+    }
+
+    #[test]
+    fn test_build_calendar_month_groups_by_status_and_day() {
+        let mut published = post("published", true);
+        published.published_at = Some(Utc.with_ymd_and_hms(2024, 7, 3, 9, 0, 0).unwrap());
+
+        let mut scheduled = post("scheduled", false);
+        scheduled.publish_at = Some(Utc.with_ymd_and_hms(2024, 7, 3, 18, 0, 0).unwrap());
+
+        let mut draft = post("draft", false);
+        draft.updated_at = Utc.with_ymd_and_hms(2024, 7, 20, 12, 0, 0).unwrap();
+
+        let mut outside = post("outside", false);
+        outside.publish_at = Some(Utc.with_ymd_and_hms(2024, 8, 1, 0, 0, 0).unwrap());
+
+        let month = build_calendar_month(
+            parse_month("2024-07").unwrap(),
+            &[scheduled, draft, published, outside],
+        );
+
+        assert_eq!(month.month, "2024-07");
+        assert_eq!(month.previous_month, "2024-06");
+        assert_eq!(month.next_month, "2024-08");
+        assert_eq!(month.days.len(), 2);
+        assert_eq!(
+            month.days[0].date,
+            NaiveDate::from_ymd_opt(2024, 7, 3).unwrap()
+        );
+        assert_eq!(
+            month.days[0]
+                .entries
+                .iter()
+                .map(|e| (e.slug.as_str(), e.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("published", CalendarEntryStatus::Published),
+                ("scheduled", CalendarEntryStatus::Scheduled),
+            ]
+        );
+        assert_eq!(
+            (
+                month.published_count,
+                month.scheduled_count,
+                month.draft_count
+            ),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_parse_month_rejects_invalid_input() {
+        assert!(parse_month("2024-13").is_err());
+        assert!(parse_month("July").is_err());
+        assert_eq!(
+            parse_month("2024-12").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()
+        );
+    }
+}
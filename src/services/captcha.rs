@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::time::Duration;
+use tracing::debug;
+
+/// CAPTCHA provider guarding public forms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaProvider {
+    Turnstile,
+    HCaptcha,
+    ReCaptcha,
+}
+
+impl std::str::FromStr for CaptchaProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "turnstile" => Ok(CaptchaProvider::Turnstile),
+            "hcaptcha" => Ok(CaptchaProvider::HCaptcha),
+            "recaptcha" => Ok(CaptchaProvider::ReCaptcha),
+            other => Err(anyhow::anyhow!(
+                "Unknown CAPTCHA provider '{}' (expected turnstile, hcaptcha or recaptcha)",
+                other
+            )),
+        }
+    }
+}
+
+impl CaptchaProvider {
+    /// Server-side verification endpoint; all three share the siteverify protocol
+    fn verify_url(self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => {
+                "https://challenges.cloudflare.com/turnstile/v0/siteverify"
+            }
+            CaptchaProvider::HCaptcha => "https://api.hcaptcha.com/siteverify",
+            CaptchaProvider::ReCaptcha => "https://www.google.com/recaptcha/api/siteverify",
+        }
+    }
+
+    /// Form field the widget submits its token in
+    pub fn response_field(self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "cf-turnstile-response",
+            CaptchaProvider::HCaptcha => "h-captcha-response",
+            CaptchaProvider::ReCaptcha => "g-recaptcha-response",
+        }
+    }
+
+    fn script_url(self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/api.js",
+            CaptchaProvider::HCaptcha => "https://js.hcaptcha.com/1/api.js",
+            CaptchaProvider::ReCaptcha => "https://www.google.com/recaptcha/api.js",
+        }
+    }
+
+    fn widget_class(self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "cf-turnstile",
+            CaptchaProvider::HCaptcha => "h-captcha",
+            CaptchaProvider::ReCaptcha => "g-recaptcha",
+        }
+    }
+}
+
+/// CAPTCHA configuration
+#[derive(Debug, Clone)]
+pub struct CaptchaConfig {
+    pub provider: CaptchaProvider,
+    /// Public key embedded in the widget
+    pub site_key: String,
+    /// Private key used for server-side verification
+    pub secret_key: String,
+}
+
+/// What a template needs to render the widget inside a form
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptchaWidget {
+    pub script_url: &'static str,
+    pub class: &'static str,
+    pub site_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+    #[serde(rename = "error-codes", default)]
+    error_codes: Vec<String>,
+}
+
+/// Service verifying the CAPTCHA tokens submitted with public forms
+#[derive(Clone)]
+pub struct CaptchaService {
+    config: CaptchaConfig,
+    client: Client,
+}
+
+impl CaptchaService {
+    /// Create a new CAPTCHA verification service
+    pub fn new(config: CaptchaConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        Self { config, client }
+    }
+
+    /// Widget settings for the templates
+    pub fn widget(&self) -> CaptchaWidget {
+        CaptchaWidget {
+            script_url: self.config.provider.script_url(),
+            class: self.config.provider.widget_class(),
+            site_key: self.config.site_key.clone(),
+        }
+    }
+
+    /// Form field holding the token of the configured provider
+    pub fn response_field(&self) -> &'static str {
+        self.config.provider.response_field()
+    }
+
+    /// Whether `token` is a valid, unused solution; a missing token never is
+    ///
+    /// `remote_ip` is the client's address, which the provider checks against
+    /// the one that solved the challenge.
+    pub async fn verify(&self, token: Option<&str>, remote_ip: IpAddr) -> Result<bool> {
+        let Some(token) = token.map(str::trim).filter(|token| !token.is_empty()) else {
+            return Ok(false);
+        };
+
+        let remote_ip = remote_ip.to_string();
+        let response: SiteVerifyResponse = self
+            .client
+            .post(self.config.provider.verify_url())
+            .form(&[
+                ("secret", self.config.secret_key.as_str()),
+                ("response", token),
+                ("remoteip", remote_ip.as_str()),
+            ])
+            .send()
+            .await
+            .context("Failed to reach CAPTCHA provider")?
+            .error_for_status()
+            .context("CAPTCHA provider returned an error")?
+            .json()
+            .await
+            .context("Invalid CAPTCHA provider response")?;
+
+        if !response.success {
+            debug!("CAPTCHA rejected: {:?}", response.error_codes);
+        }
+        Ok(response.success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_names_and_fields() {
+        let provider: CaptchaProvider = "Turnstile".parse().unwrap();
+        assert_eq!(provider, CaptchaProvider::Turnstile);
+        assert_eq!(provider.response_field(), "cf-turnstile-response");
+        assert_eq!(
+            "hcaptcha"
+                .parse::<CaptchaProvider>()
+                .unwrap()
+                .response_field(),
+            "h-captcha-response"
+        );
+        assert!("captcha".parse::<CaptchaProvider>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_rejected_without_calling_the_provider() {
+        let service = CaptchaService::new(CaptchaConfig {
+            provider: CaptchaProvider::Turnstile,
+            site_key: "site".to_string(),
+            secret_key: "secret".to_string(),
+        });
+
+        let ip = IpAddr::from([203, 0, 113, 7]);
+        assert!(!service.verify(None, ip).await.unwrap());
+        assert!(!service.verify(Some("  "), ip).await.unwrap());
+        assert_eq!(service.widget().class, "cf-turnstile");
+    }
+}
@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How long a trial call that never reported back, e.g. a cancelled request,
+/// keeps other calls from becoming the next trial
+const TRIAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// When a circuit breaker opens and how long it stays open
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long calls are short-circuited before a trial call is let through
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls go through
+    Closed,
+    /// Calls fail right away until the cooldown has passed
+    Open,
+    /// The cooldown has passed; the next call decides whether the circuit closes
+    HalfOpen,
+}
+
+/// Breaker state and call counters for status endpoints
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub opened_at: Option<DateTime<Utc>>,
+    /// When the next trial call is let through
+    pub retry_at: Option<DateTime<Utc>>,
+    pub total_calls: u64,
+    pub total_failures: u64,
+    pub short_circuited_calls: u64,
+}
+
+/// Error of a call refused because the circuit is open
+#[derive(Debug, Clone)]
+pub struct CircuitOpen {
+    pub service: &'static str,
+    pub retry_after: Duration,
+}
+
+impl std::fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is unavailable after repeated failures, retrying in {}s",
+            self.service,
+            self.retry_after.as_secs().max(1)
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+impl CircuitOpen {
+    /// The `CircuitOpen` behind an error, if a short-circuited call caused it
+    pub fn find(error: &anyhow::Error) -> Option<&CircuitOpen> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened: Option<(Instant, DateTime<Utc>)>,
+    trial_started: Option<Instant>,
+    total_calls: u64,
+    total_failures: u64,
+    short_circuited_calls: u64,
+}
+
+/// Stops calling a failing service for a cooldown period
+///
+/// Callers ask [`CircuitBreaker::check`] before each call and report its
+/// outcome; clones share the same state.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    service: &'static str,
+    config: CircuitBreakerConfig,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(service: &'static str, config: CircuitBreakerConfig) -> Self {
+        Self {
+            service,
+            config,
+            state: Arc::new(Mutex::new(BreakerState::default())),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state.lock().expect("circuit breaker state poisoned")
+    }
+
+    /// Allow a call, or refuse it while the circuit is open; once the cooldown
+    /// has passed a single trial call goes through
+    pub fn check(&self) -> Result<(), CircuitOpen> {
+        let mut state = self.lock();
+        if let Some((opened, _)) = state.opened {
+            let elapsed = opened.elapsed();
+            let trial_pending = state
+                .trial_started
+                .is_some_and(|started| started.elapsed() < TRIAL_TIMEOUT);
+            if elapsed < self.config.cooldown || trial_pending {
+                state.short_circuited_calls += 1;
+                return Err(CircuitOpen {
+                    service: self.service,
+                    retry_after: self.config.cooldown.saturating_sub(elapsed),
+                });
+            }
+            state.trial_started = Some(Instant::now());
+        }
+        state.total_calls += 1;
+        Ok(())
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.lock();
+        if state.opened.is_some() {
+            info!("{} recovered, closing the circuit", self.service);
+        }
+        state.consecutive_failures = 0;
+        state.opened = None;
+        state.trial_started = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.lock();
+        state.total_failures += 1;
+        state.consecutive_failures += 1;
+        let failed_trial = state.trial_started.take().is_some();
+
+        if failed_trial
+            || (state.opened.is_none()
+                && state.consecutive_failures >= self.config.failure_threshold)
+        {
+            warn!(
+                "{} failed {} times in a row, short-circuiting calls for {}s",
+                self.service,
+                state.consecutive_failures,
+                self.config.cooldown.as_secs()
+            );
+            state.opened = Some((Instant::now(), Utc::now()));
+        }
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        let state = self.lock();
+        let circuit_state = match state.opened {
+            None => CircuitState::Closed,
+            Some((opened, _)) if opened.elapsed() < self.config.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        };
+        let opened_at = state.opened.map(|(_, at)| at);
+
+        CircuitBreakerStatus {
+            state: circuit_state,
+            consecutive_failures: state.consecutive_failures,
+            opened_at,
+            retry_at: opened_at.map(|at| {
+                at + chrono::Duration::from_std(self.config.cooldown)
+                    .unwrap_or_else(|_| chrono::Duration::zero())
+            }),
+            total_calls: state.total_calls,
+            total_failures: state.total_failures,
+            short_circuited_calls: state.short_circuited_calls,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold_and_short_circuits() {
+        let breaker = CircuitBreaker::new(
+            "Dropbox",
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                cooldown: Duration::from_secs(60),
+            },
+        );
+
+        breaker.check().unwrap();
+        breaker.record_failure();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        breaker.check().unwrap();
+        breaker.record_failure();
+
+        let error = breaker.check().unwrap_err();
+        assert!(error.retry_after > Duration::from_secs(50));
+        let status = breaker.status();
+        assert_eq!(status.state, CircuitState::Open);
+        assert_eq!(status.total_calls, 2);
+        assert_eq!(status.short_circuited_calls, 1);
+    }
+
+    #[test]
+    fn test_trial_call_after_cooldown_closes_or_reopens() {
+        let breaker = CircuitBreaker::new(
+            "Dropbox",
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown: Duration::ZERO,
+            },
+        );
+        breaker.record_failure();
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+
+        // Only one trial call at a time; a failed trial reopens the circuit
+        breaker.check().unwrap();
+        assert!(breaker.check().is_err());
+        breaker.record_failure();
+        assert!(breaker.status().opened_at.is_some());
+
+        breaker.check().unwrap();
+        breaker.record_success();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 0);
+    }
+}
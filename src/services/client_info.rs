@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts, HeaderMap},
+};
+use ipnet::IpNet;
+use std::convert::Infallible;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// Client as seen through the trusted reverse proxies
+///
+/// Resolved once per request by the client info middleware and read by
+/// handlers and other middleware through the extractor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    /// Address of the client, not of the proxy in front of the server
+    pub ip: IpAddr,
+    /// `http` or `https`, as the client used it
+    pub scheme: String,
+    /// Host the client asked for, with the port if one was given
+    pub host: Option<String>,
+}
+
+impl ClientInfo {
+    /// Whether the client reached the site over HTTPS
+    pub fn is_https(&self) -> bool {
+        self.scheme == "https"
+    }
+}
+
+/// Reverse proxies whose `X-Forwarded-*` headers are believed
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<IpNet>,
+}
+
+impl TrustedProxies {
+    /// Parse a CIDR list; plain addresses are accepted as single-host networks
+    pub fn new(networks: &[String]) -> Result<Self> {
+        Ok(Self {
+            networks: parse_networks(networks)?,
+        })
+    }
+
+    /// Resolve the client of a request received from `peer`
+    ///
+    /// Forwarded headers are ignored entirely unless the peer itself is a
+    /// trusted proxy, so clients cannot spoof their address, scheme or host.
+    pub fn resolve(&self, peer: IpAddr, headers: &HeaderMap, scheme: Option<&str>) -> ClientInfo {
+        let peer = peer.to_canonical();
+        let direct_host = || header_value(headers, header::HOST.as_str());
+        let scheme = scheme.unwrap_or("http").to_string();
+        if !self.is_trusted(peer) {
+            return ClientInfo {
+                ip: peer,
+                scheme,
+                host: direct_host(),
+            };
+        }
+
+        let forwarded_scheme = header_value(headers, "x-forwarded-proto")
+            .map(|proto| proto.to_ascii_lowercase())
+            .filter(|proto| proto == "http" || proto == "https");
+        ClientInfo {
+            ip: self.client_ip(peer, headers),
+            scheme: forwarded_scheme.unwrap_or(scheme),
+            host: header_value(headers, "x-forwarded-host").or_else(direct_host),
+        }
+    }
+
+    /// Address of the client behind the trusted proxies
+    ///
+    /// `X-Forwarded-For` is read from the right, skipping hops that are trusted
+    /// proxies; it is ignored entirely unless the peer itself is trusted.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut client = peer.to_canonical();
+        if !self.is_trusted(client) {
+            return client;
+        }
+
+        let hops: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|hop| !hop.is_empty())
+            .collect();
+        for hop in hops.into_iter().rev() {
+            let Ok(ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+            client = ip.to_canonical();
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+        client
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|net| net.contains(&ip))
+    }
+}
+
+/// Parse CIDR lists; plain addresses are accepted as single-host networks
+pub fn parse_networks(values: &[String]) -> Result<Vec<IpNet>> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .parse::<IpNet>()
+                .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+                .with_context(|| format!("Invalid network '{}'", value))
+        })
+        .collect()
+}
+
+/// First value of a header; proxies append theirs after the one set by the first hop
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    let value = headers.get(name)?.to_str().ok()?;
+    let first = value.split(',').next()?.trim();
+    (!first.is_empty()).then(|| first.to_string())
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ClientInfo {
+    type Rejection = Infallible;
+
+    /// The client resolved by the middleware; without it, the direct peer
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(client) = parts.extensions.get::<ClientInfo>() {
+            return Ok(client.clone());
+        }
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(peer)| peer.ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        Ok(TrustedProxies::default().resolve(peer, &parts.headers, parts.uri.scheme_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn proxies() -> TrustedProxies {
+        TrustedProxies::new(&["127.0.0.1".to_string(), "10.0.0.0/8".to_string()]).unwrap()
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_forwarded_for_is_only_trusted_from_proxies() {
+        let proxies = proxies();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("192.168.1.20, 203.0.113.7, 10.0.0.2"),
+        );
+
+        // The spoofable left part is skipped once an untrusted hop is found
+        assert_eq!(
+            proxies.client_ip(ip("127.0.0.1"), &headers),
+            ip("203.0.113.7")
+        );
+        // Direct clients cannot claim another address
+        assert_eq!(
+            proxies.client_ip(ip("198.51.100.1"), &headers),
+            ip("198.51.100.1")
+        );
+        assert_eq!(
+            proxies.client_ip(ip("127.0.0.1"), &HeaderMap::new()),
+            ip("127.0.0.1")
+        );
+        assert!(TrustedProxies::new(&["not-a-network".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_scheme_and_host_come_from_trusted_proxies_only() {
+        let proxies = proxies();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, HeaderValue::from_static("127.0.0.1:3000"));
+        headers.insert("x-forwarded-proto", HeaderValue::from_static("HTTPS"));
+        headers.insert(
+            "x-forwarded-host",
+            HeaderValue::from_static("blog.example.com, internal"),
+        );
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+
+        let client = proxies.resolve(ip("::ffff:127.0.0.1"), &headers, None);
+        assert_eq!(
+            client,
+            ClientInfo {
+                ip: ip("203.0.113.7"),
+                scheme: "https".to_string(),
+                host: Some("blog.example.com".to_string()),
+            }
+        );
+
+        let direct = proxies.resolve(ip("198.51.100.1"), &headers, None);
+        assert_eq!(direct.ip, ip("198.51.100.1"));
+        assert_eq!(direct.scheme, "http");
+        assert_eq!(direct.host.as_deref(), Some("127.0.0.1:3000"));
+
+        // Unknown schemes are not passed on
+        headers.insert("x-forwarded-proto", HeaderValue::from_static("gopher"));
+        assert_eq!(
+            proxies.resolve(ip("127.0.0.1"), &headers, None).scheme,
+            "http"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::models::{Locale, PaletteEntry, PaletteEntryKind, Post};
+use crate::services::i18n::translate;
+
+/// An admin page or operation offered by the command palette
+struct StaticEntry {
+    id: &'static str,
+    /// UI string key of the title
+    title_key: &'static str,
+    kind: PaletteEntryKind,
+    method: &'static str,
+    url: &'static str,
+    keywords: &'static [&'static str],
+}
+
+const STATIC_ENTRIES: &[StaticEntry] = &[
+    StaticEntry {
+        id: "page:new-post",
+        title_key: "admin.nav.new_post",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin/new",
+        keywords: &["new post", "create", "write"],
+    },
+    StaticEntry {
+        id: "page:dashboard",
+        title_key: "admin.nav.dashboard",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin",
+        keywords: &["dashboard", "home"],
+    },
+    StaticEntry {
+        id: "page:posts",
+        title_key: "admin.nav.posts",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin/posts",
+        keywords: &["posts", "list"],
+    },
+    StaticEntry {
+        id: "page:calendar",
+        title_key: "admin.nav.calendar",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin/calendar",
+        keywords: &["calendar", "schedule"],
+    },
+    StaticEntry {
+        id: "page:media",
+        title_key: "admin.nav.media",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin/media",
+        keywords: &["media", "images", "upload"],
+    },
+    StaticEntry {
+        id: "page:import",
+        title_key: "admin.import.page_title",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin/import",
+        keywords: &["import", "llm"],
+    },
+    StaticEntry {
+        id: "page:security",
+        title_key: "admin.nav.security",
+        kind: PaletteEntryKind::Page,
+        method: "GET",
+        url: "/admin/security",
+        keywords: &["security", "settings", "two-factor", "2fa", "totp"],
+    },
+    StaticEntry {
+        id: "action:sync-dropbox",
+        title_key: "admin.palette.sync_dropbox",
+        kind: PaletteEntryKind::Action,
+        method: "POST",
+        url: "/api/sync/dropbox",
+        keywords: &["sync", "dropbox"],
+    },
+    StaticEntry {
+        id: "action:reindex",
+        title_key: "admin.palette.reindex",
+        kind: PaletteEntryKind::Action,
+        method: "POST",
+        url: "/api/admin/reindex",
+        keywords: &["reindex", "search index"],
+    },
+    StaticEntry {
+        id: "action:rebuild-stats",
+        title_key: "admin.palette.rebuild_stats",
+        kind: PaletteEntryKind::Action,
+        method: "POST",
+        url: "/api/admin/stats/rebuild",
+        keywords: &["rebuild stats", "statistics"],
+    },
+    StaticEntry {
+        id: "action:check-links",
+        title_key: "admin.palette.check_links",
+        kind: PaletteEntryKind::Action,
+        method: "POST",
+        url: "/api/admin/links/check",
+        keywords: &["check links", "broken links"],
+    },
+    StaticEntry {
+        id: "action:cleanup-versions",
+        title_key: "admin.palette.cleanup_versions",
+        kind: PaletteEntryKind::Action,
+        method: "POST",
+        url: "/api/admin/versions/cleanup",
+        keywords: &["cleanup versions", "history"],
+    },
+    StaticEntry {
+        id: "action:export-settings",
+        title_key: "admin.palette.export_settings",
+        kind: PaletteEntryKind::Action,
+        method: "GET",
+        url: "/api/admin/settings/export",
+        keywords: &["export settings", "backup", "settings"],
+    },
+];
+
+/// Admin pages and operations, titled in `locale`
+pub fn static_entries(locale: Locale) -> Vec<PaletteEntry> {
+    STATIC_ENTRIES
+        .iter()
+        .map(|entry| PaletteEntry {
+            id: entry.id.to_string(),
+            kind: entry.kind,
+            title: translate(locale, entry.title_key, &[]),
+            subtitle: None,
+            url: entry.url.to_string(),
+            method: entry.method.to_string(),
+            keywords: entry.keywords.iter().map(|k| k.to_string()).collect(),
+            score: None,
+        })
+        .collect()
+}
+
+/// Entry that opens the editor of a post, found by title or slug
+pub fn post_entry(post: &Post, locale: Locale) -> PaletteEntry {
+    let status = translate(
+        locale,
+        if post.published {
+            "admin.published"
+        } else {
+            "admin.draft"
+        },
+        &[],
+    );
+    let subtitle = match &post.category {
+        Some(category) if !category.is_empty() => format!("{} · {}", status, category),
+        _ => status,
+    };
+
+    PaletteEntry {
+        id: format!("post:{}", post.slug),
+        kind: PaletteEntryKind::Post,
+        title: post.title.clone(),
+        subtitle: Some(subtitle),
+        url: format!("/admin/edit/{}", post.slug),
+        method: "GET".to_string(),
+        keywords: vec![post.slug.clone()],
+        score: None,
+    }
+}
+
+/// Entries matching `query`, best match first; all entries in order for an empty query
+pub fn search(entries: Vec<PaletteEntry>, query: &str) -> Vec<PaletteEntry> {
+    if query.trim().is_empty() {
+        return entries;
+    }
+
+    let mut matches: Vec<PaletteEntry> = entries
+        .into_iter()
+        .filter_map(|mut entry| {
+            let title_score = fuzzy_score(query, &entry.title);
+            // Keywords are a fallback, so they rank below an equally good title match
+            let keyword_score = entry
+                .keywords
+                .iter()
+                .filter_map(|keyword| fuzzy_score(query, keyword))
+                .max()
+                .map(|score| score - 1);
+            entry.score = Some(title_score.into_iter().chain(keyword_score).max()?);
+            Some(entry)
+        })
+        .collect();
+    // Stable, so equal scores keep actions before posts and recent posts first
+    matches.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    matches
+}
+
+/// Fuzzy match score of `query` in `candidate`, or `None` if it does not match
+///
+/// Every whitespace-separated term of the query must appear in the candidate as
+/// a case-insensitive subsequence. Matches at the start of words, consecutive
+/// characters and prefixes score higher; characters skipped between matches
+/// score lower.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let terms: Vec<Vec<char>> = query
+        .split_whitespace()
+        .map(|term| term.chars().flat_map(char::to_lowercase).collect())
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    let mut total = 0;
+    for term in &terms {
+        // Try every start position of the first character and keep the best alignment
+        total += (0..candidate.len())
+            .filter(|&start| candidate[start] == term[0])
+            .filter_map(|start| score_from(term, &candidate, start))
+            .max()?;
+    }
+    if terms.len() == 1 && terms[0] == candidate {
+        total += 100;
+    }
+    Some(total)
+}
+
+/// Score of greedily matching `term` in `candidate` from `start` onwards
+fn score_from(term: &[char], candidate: &[char], start: usize) -> Option<i64> {
+    let is_word_start = |index: usize| index == 0 || !candidate[index - 1].is_alphanumeric();
+
+    let mut score = if start == 0 { 15 } else { 0 };
+    let mut previous: Option<usize> = None;
+    let mut position = start;
+    for &c in term {
+        let index = position + candidate[position..].iter().position(|&x| x == c)?;
+        score += 1;
+        if is_word_start(index) {
+            score += 8;
+        }
+        match previous {
+            Some(previous) if index == previous + 1 => score += 5,
+            Some(previous) => score -= ((index - previous - 1) as i64).min(3),
+            None => {}
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_requires_every_term() {
+        assert!(fuzzy_score("rbs", "Rebuild statistics").is_some());
+        assert!(fuzzy_score("sync drop", "Sync with Dropbox").is_some());
+        assert!(fuzzy_score("sync zip", "Sync with Dropbox").is_none());
+        assert!(fuzzy_score("", "anything").is_none());
+        assert!(fuzzy_score("ブロ", "ブログを書く").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_prefixes_and_word_starts() {
+        let prefix = fuzzy_score("med", "Media").unwrap();
+        let word_start = fuzzy_score("med", "Open media").unwrap();
+        let scattered = fuzzy_score("med", "Armed").unwrap();
+        assert!(prefix > word_start);
+        assert!(word_start > scattered);
+        assert!(fuzzy_score("media", "media").unwrap() > prefix);
+    }
+
+    #[test]
+    fn test_search_ranks_and_matches_keywords() {
+        let entries = static_entries(Locale::Ja);
+        assert_eq!(search(entries.clone(), "").len(), entries.len());
+
+        // Japanese titles are still found by their English keywords
+        let results = search(entries, "reindex");
+        assert_eq!(results[0].id, "action:reindex");
+        assert!(results[0].score.is_some());
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::models::ConfigReloadResponse;
+
+/// Variables whose new values are applied by a reload
+///
+/// Everything else (listen address, database, storage, credentials, ...) is
+/// wired into services at startup and reported as needing a restart.
+pub const RELOADABLE_VARS: &[&str] = &[
+    "DEMO_MODE",
+    "THEME_PREVIEW",
+    "MAINTENANCE_MODE",
+    "MAINTENANCE_RETRY_AFTER_SECS",
+    "RESPONSE_CACHE_PAGE_TTL_SECS",
+    "RESPONSE_CACHE_FEED_TTL_SECS",
+    "ADMIN_IP_ALLOW",
+    "ADMIN_IP_DENY",
+    "TRUSTED_PROXIES",
+];
+
+type ReloadHook = Box<dyn Fn(&Config) -> Result<()> + Send + Sync>;
+
+/// Re-reads `.env` and hands the new config to the running services
+///
+/// Triggered by SIGHUP or `POST /api/admin/reload`; the server keeps serving
+/// throughout. Variables set in the process environment win over `.env`, as
+/// at startup, and variables removed from `.env` keep their old value. The
+/// reloaded values are kept here; the process environment is never modified.
+#[derive(Clone)]
+pub struct ConfigReloader {
+    env_file: Option<PathBuf>,
+    /// Variables set outside `.env`, never overridden by it
+    process_env: Arc<HashSet<String>>,
+    hooks: Arc<RwLock<Vec<ReloadHook>>>,
+    /// `.env` values applied by reloads, read before the environment
+    applied: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl ConfigReloader {
+    pub fn new(env_file: Option<PathBuf>, process_env: HashSet<String>) -> Self {
+        Self {
+            env_file,
+            process_env: Arc::new(process_env),
+            hooks: Arc::new(RwLock::new(Vec::new())),
+            applied: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Run `hook` with every reloaded config
+    ///
+    /// Hooks run in registration order and stop at the first error, so
+    /// register the ones that validate settings first.
+    pub fn on_reload(&self, hook: impl Fn(&Config) -> Result<()> + Send + Sync + 'static) {
+        self.hooks
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(Box::new(hook));
+    }
+
+    /// Reload the config, keeping the current environment when it is invalid
+    pub fn reload(&self) -> Result<ConfigReloadResponse> {
+        // Held throughout, so reloads apply one at a time
+        let mut applied = self
+            .applied
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let entries = match &self.env_file {
+            Some(path) => read_env_file(path)?,
+            None => Vec::new(),
+        };
+        let changes = changed_vars(&entries, &self.process_env, |name| lookup(&applied, name));
+
+        // Invalid settings leave the previously applied values in place
+        let mut values = applied.clone();
+        values.extend(changes.iter().cloned());
+        self.apply(&values)?;
+        *applied = values;
+
+        let (applied, restart_required): (Vec<String>, Vec<String>) = changes
+            .into_iter()
+            .map(|(name, _)| name)
+            .partition(|name| RELOADABLE_VARS.contains(&name.as_str()));
+        info!(
+            "Configuration reloaded (applied: {:?}, restart required: {:?})",
+            applied, restart_required
+        );
+        if !restart_required.is_empty() {
+            warn!(
+                "Restart the server to apply: {}",
+                restart_required.join(", ")
+            );
+        }
+
+        Ok(ConfigReloadResponse {
+            applied,
+            restart_required,
+            reloaded_at: Utc::now(),
+        })
+    }
+
+    fn apply(&self, values: &HashMap<String, String>) -> Result<()> {
+        let config =
+            Config::from_lookup(|name| lookup(values, name)).context("Invalid configuration")?;
+        for hook in self
+            .hooks
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+        {
+            hook(&config)?;
+        }
+        Ok(())
+    }
+
+    /// Reload whenever the process receives SIGHUP
+    #[cfg(unix)]
+    pub fn spawn_sighup_listener(self) {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        static SIGHUP: AtomicBool = AtomicBool::new(false);
+
+        extern "C" fn on_sighup(_: libc::c_int) {
+            SIGHUP.store(true, Ordering::SeqCst);
+        }
+
+        // Only an atomic store happens in the handler; the reload runs on the runtime
+        let handler = on_sighup as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        if unsafe { libc::signal(libc::SIGHUP, handler) } == libc::SIG_ERR {
+            warn!("Failed to install SIGHUP handler, reload with POST /api/admin/reload");
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(500));
+            loop {
+                interval.tick().await;
+                if !SIGHUP.swap(false, Ordering::SeqCst) {
+                    continue;
+                }
+                info!("SIGHUP received, reloading configuration");
+                let reloader = self.clone();
+                match tokio::task::spawn_blocking(move || reloader.reload()).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => error!("Failed to reload configuration: {:#}", e),
+                    Err(e) => error!("Configuration reload task failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+/// Entries of an env file, without touching the environment
+///
+/// `dotenv::from_path` never overrides variables that are already set, which
+/// is all of them on a reload, so the entries are read and applied by hand.
+#[allow(deprecated)]
+fn read_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    dotenv::from_path_iter(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Value of a variable, from the reloaded `.env` values or else the environment
+fn lookup(values: &HashMap<String, String>, name: &str) -> Option<String> {
+    values.get(name).cloned().or_else(|| env::var(name).ok())
+}
+
+/// `.env` entries whose value differs from the current one, skipping
+/// variables owned by the process environment
+fn changed_vars(
+    entries: &[(String, String)],
+    process_env: &HashSet<String>,
+    current: impl Fn(&str) -> Option<String>,
+) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .filter(|(name, _)| !process_env.contains(name))
+        .filter(|(name, value)| current(name).as_deref() != Some(value.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_vars_skips_unchanged_and_process_env() {
+        let entries = vec![
+            ("DEMO_MODE".to_string(), "true".to_string()),
+            ("SERVER_PORT".to_string(), "3000".to_string()),
+            ("API_KEY".to_string(), "from-file".to_string()),
+            ("TRUSTED_PROXIES".to_string(), "10.0.0.1".to_string()),
+        ];
+        let process_env = HashSet::from(["API_KEY".to_string()]);
+        let current = |name: &str| match name {
+            "DEMO_MODE" => Some("false".to_string()),
+            "SERVER_PORT" => Some("3000".to_string()),
+            _ => None,
+        };
+
+        let changed: Vec<String> = changed_vars(&entries, &process_env, current)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(changed, ["DEMO_MODE", "TRUSTED_PROXIES"]);
+    }
+}
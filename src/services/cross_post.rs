@@ -0,0 +1,546 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use crate::models::{
+    AdminEvent, CrossPost, CrossPostProvider, CrossPostStatus, Post, PostVisibility,
+};
+use crate::services::github::GitHubRepo;
+use crate::services::{DatabaseService, EventBus};
+
+const QIITA_API_URL: &str = "https://qiita.com/api/v2";
+const DEVTO_API_URL: &str = "https://dev.to/api";
+
+/// Tags accepted per article by Qiita and Zenn (topics)
+const QIITA_MAX_TAGS: usize = 5;
+const ZENN_MAX_TOPICS: usize = 5;
+/// Tags accepted per article by dev.to
+const DEVTO_MAX_TAGS: usize = 4;
+
+/// Cross-posting configuration; a provider is enabled once its credentials are set
+#[derive(Debug, Clone, Default)]
+pub struct CrossPostConfig {
+    /// Public URL of the blog, linked from the copies
+    pub site_url: String,
+    pub qiita_token: Option<String>,
+    pub devto_api_key: Option<String>,
+    pub zenn_github_token: Option<String>,
+    /// `owner/repo` of the GitHub repository linked to Zenn
+    pub zenn_repo: Option<String>,
+    pub zenn_branch: String,
+    /// Zenn account name, for the URLs of the published articles
+    pub zenn_username: Option<String>,
+}
+
+/// Post as sent to an external platform
+#[derive(Debug, Clone)]
+pub struct CrossPostArticle {
+    pub slug: String,
+    pub title: String,
+    /// Markdown body with absolute links and a link back to the original
+    pub markdown: String,
+    pub tags: Vec<String>,
+    /// URL of the original, for platforms that support `rel=canonical`
+    pub canonical_url: String,
+}
+
+/// Article created or updated on a platform
+#[derive(Debug, Clone)]
+pub struct RemoteArticle {
+    pub id: String,
+    pub url: Option<String>,
+}
+
+/// Publishes articles to one external platform
+#[async_trait]
+pub trait CrossPostAdapter: Send + Sync {
+    fn provider(&self) -> CrossPostProvider;
+
+    /// Create the article, or update the copy with `remote_id`
+    async fn publish(
+        &self,
+        article: &CrossPostArticle,
+        remote_id: Option<&str>,
+    ) -> Result<RemoteArticle>;
+}
+
+/// Copies published posts to Zenn, Qiita and dev.to and records where they went
+///
+/// Runs when the API publishes or edits a public post and when a scheduled
+/// post goes live; `POST /api/posts/{slug}/cross-posts` retries by hand.
+#[derive(Clone)]
+pub struct CrossPostService {
+    database: DatabaseService,
+    site_url: String,
+    adapters: Arc<Vec<Arc<dyn CrossPostAdapter>>>,
+}
+
+impl CrossPostService {
+    /// Service with an adapter for every provider configured in `config`
+    pub fn new(database: DatabaseService, config: CrossPostConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("tobelog/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+
+        let mut adapters: Vec<Arc<dyn CrossPostAdapter>> = Vec::new();
+        if let (Some(token), Some(repo)) = (&config.zenn_github_token, &config.zenn_repo) {
+            adapters.push(Arc::new(ZennAdapter {
+                repo: GitHubRepo::new(
+                    client.clone(),
+                    token.clone(),
+                    repo.clone(),
+                    config.zenn_branch.clone(),
+                ),
+                username: config.zenn_username.clone(),
+            }));
+        }
+        if let Some(token) = &config.qiita_token {
+            adapters.push(Arc::new(QiitaAdapter {
+                client: client.clone(),
+                api_url: QIITA_API_URL.to_string(),
+                token: token.clone(),
+            }));
+        }
+        if let Some(api_key) = &config.devto_api_key {
+            adapters.push(Arc::new(DevToAdapter {
+                client,
+                api_url: DEVTO_API_URL.to_string(),
+                api_key: api_key.clone(),
+            }));
+        }
+
+        Self::with_adapters(database, config.site_url, adapters)
+    }
+
+    /// Service publishing through `adapters`
+    pub fn with_adapters(
+        database: DatabaseService,
+        site_url: String,
+        adapters: Vec<Arc<dyn CrossPostAdapter>>,
+    ) -> Self {
+        Self {
+            database,
+            site_url: site_url.trim_end_matches('/').to_string(),
+            adapters: Arc::new(adapters),
+        }
+    }
+
+    /// Providers posts are copied to
+    pub fn providers(&self) -> Vec<CrossPostProvider> {
+        self.adapters
+            .iter()
+            .map(|adapter| adapter.provider())
+            .collect()
+    }
+
+    /// Publish or update the copies of `post` on every provider
+    ///
+    /// Only published public posts without a password are copied. Failures
+    /// are recorded per provider rather than returned.
+    pub async fn cross_post(&self, post: &Post) -> Result<Vec<CrossPost>> {
+        if self.adapters.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !post.published
+            || post.visibility != PostVisibility::Public
+            || post.is_password_protected()
+        {
+            debug!("Not cross-posting {}, it isn't public", post.slug);
+            return self.database.list_cross_posts(post.id).await;
+        }
+
+        let article = self.article(post);
+        let existing = self.database.list_cross_posts(post.id).await?;
+        for adapter in self.adapters.iter() {
+            let provider = adapter.provider();
+            let previous = existing.iter().find(|record| record.provider == provider);
+            let remote_id = previous.and_then(|record| record.remote_id.as_deref());
+
+            let record = match adapter.publish(&article, remote_id).await {
+                Ok(remote) => {
+                    info!(
+                        "Cross-posted {} to {}: {}",
+                        post.slug,
+                        provider.as_str(),
+                        remote.url.as_deref().unwrap_or(&remote.id)
+                    );
+                    CrossPost {
+                        post_id: post.id,
+                        provider,
+                        remote_id: Some(remote.id),
+                        remote_url: remote.url,
+                        status: CrossPostStatus::Published,
+                        error: None,
+                        updated_at: Utc::now(),
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to cross-post {} to {}: {:#}",
+                        post.slug,
+                        provider.as_str(),
+                        e
+                    );
+                    CrossPost {
+                        post_id: post.id,
+                        provider,
+                        remote_id: remote_id.map(str::to_string),
+                        remote_url: previous.and_then(|record| record.remote_url.clone()),
+                        status: CrossPostStatus::Failed,
+                        error: Some(format!("{:#}", e)),
+                        updated_at: Utc::now(),
+                    }
+                }
+            };
+            self.database.upsert_cross_post(&record).await?;
+        }
+
+        self.database.list_cross_posts(post.id).await
+    }
+
+    /// Cross-post posts as the API publishes them or scheduled posts go live
+    pub fn spawn_listener(&self, events: &EventBus) {
+        if self.adapters.is_empty() {
+            return;
+        }
+        info!(
+            "Cross-posting enabled for {}",
+            self.providers()
+                .iter()
+                .map(|provider| provider.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let service = self.clone();
+        let mut events = Box::pin(events.subscribe());
+        tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let slug = match event {
+                    AdminEvent::PostPublished { slug, .. }
+                    | AdminEvent::ScheduledPostPublished { slug, .. } => slug,
+                    _ => continue,
+                };
+                match service.database.get_post_by_slug(&slug).await {
+                    Ok(Some(post)) => {
+                        if let Err(e) = service.cross_post(&post).await {
+                            error!("Cross-posting {} failed: {}", slug, e);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => error!("Failed to load {} for cross-posting: {}", slug, e),
+                }
+            }
+        });
+    }
+
+    fn article(&self, post: &Post) -> CrossPostArticle {
+        let original_url = format!("{}{}", self.site_url, post.get_url_path());
+        let markdown = format!(
+            "{}\n\n---\n\n*Originally published at [{}]({})*\n",
+            absolute_links(post.content.trim_end(), &self.site_url),
+            original_url,
+            original_url
+        );
+
+        CrossPostArticle {
+            slug: post.slug.clone(),
+            title: post.title.clone(),
+            markdown,
+            tags: post.get_tags(),
+            canonical_url: post.canonical_url.clone().unwrap_or(original_url),
+        }
+    }
+}
+
+/// Point root-relative Markdown links and images (`](/media/...)`) at the blog
+fn absolute_links(markdown: &str, site_url: &str) -> String {
+    markdown.replace("](/", &format!("]({}/", site_url))
+}
+
+/// Lowercase alphanumeric tags as dev.to and Zenn topics require, deduplicated
+fn simple_tags(tags: &[String], limit: usize) -> Vec<String> {
+    let mut simple: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag: String = tag
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if !tag.is_empty() && !simple.contains(&tag) {
+            simple.push(tag);
+        }
+    }
+    simple.truncate(limit);
+    simple
+}
+
+/// Error for a non-success response, with the body the platform sent back
+async fn error_for_status(
+    response: reqwest::Response,
+    provider: &str,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(anyhow!(
+        "{} returned {}: {}",
+        provider,
+        status,
+        body.chars().take(300).collect::<String>()
+    ))
+}
+
+/// Qiita items through the v2 API
+struct QiitaAdapter {
+    client: Client,
+    api_url: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct QiitaItem {
+    id: String,
+    url: String,
+}
+
+#[async_trait]
+impl CrossPostAdapter for QiitaAdapter {
+    fn provider(&self) -> CrossPostProvider {
+        CrossPostProvider::Qiita
+    }
+
+    async fn publish(
+        &self,
+        article: &CrossPostArticle,
+        remote_id: Option<&str>,
+    ) -> Result<RemoteArticle> {
+        // Qiita requires at least one tag
+        let mut tags: Vec<&str> = article.tags.iter().map(String::as_str).collect();
+        if tags.is_empty() {
+            tags.push("blog");
+        }
+        tags.truncate(QIITA_MAX_TAGS);
+        let body = serde_json::json!({
+            "title": article.title,
+            "body": article.markdown,
+            "tags": tags
+                .iter()
+                .map(|name| serde_json::json!({ "name": name, "versions": [] }))
+                .collect::<Vec<_>>(),
+            "private": false,
+        });
+
+        let request = match remote_id {
+            Some(id) => self.client.patch(format!("{}/items/{}", self.api_url, id)),
+            None => self.client.post(format!("{}/items", self.api_url)),
+        };
+        let response = request
+            .bearer_auth(&self.token)
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to reach Qiita")?;
+        let item: QiitaItem = error_for_status(response, "Qiita")
+            .await?
+            .json()
+            .await
+            .context("Invalid Qiita response")?;
+
+        Ok(RemoteArticle {
+            id: item.id,
+            url: Some(item.url),
+        })
+    }
+}
+
+/// dev.to (Forem) articles through the v1 API
+struct DevToAdapter {
+    client: Client,
+    api_url: String,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DevToArticle {
+    id: u64,
+    url: String,
+}
+
+#[async_trait]
+impl CrossPostAdapter for DevToAdapter {
+    fn provider(&self) -> CrossPostProvider {
+        CrossPostProvider::DevTo
+    }
+
+    async fn publish(
+        &self,
+        article: &CrossPostArticle,
+        remote_id: Option<&str>,
+    ) -> Result<RemoteArticle> {
+        let body = serde_json::json!({
+            "article": {
+                "title": article.title,
+                "body_markdown": article.markdown,
+                "published": true,
+                "tags": simple_tags(&article.tags, DEVTO_MAX_TAGS),
+                "canonical_url": article.canonical_url,
+            }
+        });
+
+        let request = match remote_id {
+            Some(id) => self.client.put(format!("{}/articles/{}", self.api_url, id)),
+            None => self.client.post(format!("{}/articles", self.api_url)),
+        };
+        let response = request
+            .header("api-key", &self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to reach dev.to")?;
+        let created: DevToArticle = error_for_status(response, "dev.to")
+            .await?
+            .json()
+            .await
+            .context("Invalid dev.to response")?;
+
+        Ok(RemoteArticle {
+            id: created.id.to_string(),
+            url: Some(created.url),
+        })
+    }
+}
+
+/// Zenn articles, committed to the GitHub repository Zenn deploys from
+struct ZennAdapter {
+    repo: GitHubRepo,
+    username: Option<String>,
+}
+
+#[async_trait]
+impl CrossPostAdapter for ZennAdapter {
+    fn provider(&self) -> CrossPostProvider {
+        CrossPostProvider::Zenn
+    }
+
+    async fn publish(
+        &self,
+        article: &CrossPostArticle,
+        remote_id: Option<&str>,
+    ) -> Result<RemoteArticle> {
+        let zenn_slug = remote_id
+            .map(str::to_string)
+            .unwrap_or_else(|| zenn_slug(&article.slug));
+        self.repo
+            .put_file(
+                &format!("articles/{}.md", zenn_slug),
+                zenn_markdown(article)?.as_bytes(),
+                &format!("Cross-post {}", article.slug),
+            )
+            .await?;
+
+        Ok(RemoteArticle {
+            url: self
+                .username
+                .as_ref()
+                .map(|username| format!("https://zenn.dev/{}/articles/{}", username, zenn_slug)),
+            id: zenn_slug,
+        })
+    }
+}
+
+/// Zenn article slug for a post: 12 to 50 of `a-z0-9_-`
+fn zenn_slug(slug: &str) -> String {
+    let mut zenn_slug: String = slug
+        .chars()
+        .map(|c| {
+            let c = c.to_ascii_lowercase();
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if zenn_slug.len() < 12 {
+        zenn_slug = format!("{}-tobelog", zenn_slug.trim_end_matches('-'))
+            .trim_start_matches('-')
+            .to_string();
+        while zenn_slug.len() < 12 {
+            zenn_slug.push('x');
+        }
+    }
+    zenn_slug.truncate(50);
+    zenn_slug
+}
+
+/// Markdown file with the frontmatter Zenn expects
+fn zenn_markdown(article: &CrossPostArticle) -> Result<String> {
+    let frontmatter = serde_yaml::to_string(&serde_json::json!({
+        "title": article.title,
+        "emoji": "📝",
+        "type": "tech",
+        "topics": simple_tags(&article.tags, ZENN_MAX_TOPICS),
+        "published": true,
+    }))
+    .context("Failed to write Zenn frontmatter")?;
+    Ok(format!("---\n{}---\n\n{}", frontmatter, article.markdown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zenn_slug_meets_length_and_charset_rules() {
+        assert_eq!(zenn_slug("rust-error-handling"), "rust-error-handling");
+        assert_eq!(zenn_slug("hello"), "hello-tobelog");
+        assert_eq!(zenn_slug("日記"), "tobelogxxxxx");
+        assert_eq!(zenn_slug(&"a".repeat(60)).len(), 50);
+    }
+
+    #[test]
+    fn test_tags_and_links_are_adapted_for_platforms() {
+        let tags = [
+            "Rust".to_string(),
+            "web-dev".to_string(),
+            "rust".to_string(),
+        ];
+        assert_eq!(simple_tags(&tags, 4), ["rust", "webdev"]);
+
+        assert_eq!(
+            absolute_links(
+                "![alt](/media/a.png) [x](https://x.dev)",
+                "https://blog.example"
+            ),
+            "![alt](https://blog.example/media/a.png) [x](https://x.dev)"
+        );
+    }
+
+    #[test]
+    fn test_zenn_markdown_has_frontmatter() {
+        let article = CrossPostArticle {
+            slug: "post".to_string(),
+            title: "Title: with colon".to_string(),
+            markdown: "Body".to_string(),
+            tags: vec!["Rust".to_string()],
+            canonical_url: "https://blog.example/posts/2024/post".to_string(),
+        };
+        let markdown = zenn_markdown(&article).unwrap();
+        let (frontmatter, body) = markdown[4..].split_once("---\n").unwrap();
+        let frontmatter: serde_yaml::Value = serde_yaml::from_str(frontmatter).unwrap();
+        assert_eq!(frontmatter["title"].as_str(), Some("Title: with colon"));
+        assert_eq!(frontmatter["topics"][0].as_str(), Some("rust"));
+        assert_eq!(body.trim(), "Body");
+    }
+}
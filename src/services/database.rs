@@ -1,42 +1,99 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sqlx::sqlite::SqliteRow;
-use sqlx::{Pool, Row, Sqlite, SqlitePool};
+use chrono_tz::Tz;
+use log::LevelFilter;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow, SqliteSynchronous,
+};
+use sqlx::{ConnectOptions, Pool, Row, Sqlite, SqliteConnection};
+use std::collections::HashMap;
 use std::path::Path;
-use tracing::{debug, info};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::models::{
-    CategoryStat, CreatePost, FooterStyle, HeaderStyle, MediaFile, MediaFilters, Post, PostFilters,
-    PostStats, SiteConfig, SocialLink, ThemeFilters, ThemeSettings, UpdatePost, UpdateThemeRequest,
+    content_hash, count_words, AdminSession, AdminTotp, Announcement, AnnouncementRequest,
+    AudioEnclosure, CategoryDescription, CategoryIndexEntry, CategoryStat, CategoryStyle,
+    CreatePost, CrossPost, ExpiryAction, FeaturedImage, FocalPoint, FooterStyle, GonePost,
+    HeaderStyle, Job, JobFilters, JobStatus, MediaFile, MediaFilters, Post, PostAttachment,
+    PostFilters, PostStats, PostVisibility, SearchFilters, SearchHit, SearchIndexStatus,
+    SiteConfig, SocialLink, SyncRecord, TagAlias, TagNormalization, TagStat, ThemeFilters,
+    ThemeSettings, UpdatePost, UpdatePostMetadata, UpdateThemeRequest,
 };
+use crate::services::media::extract_media_urls;
+
+/// Job columns named explicitly so that statements prepared before the
+/// progress column was added still see every column
+const JOB_SELECT: &str = "SELECT id, kind, payload, status, attempts, max_attempts, last_error, \
+    result, progress, run_at, created_at, started_at, finished_at FROM jobs";
+
+/// Connection pool sizing and SQLite pragmas
+#[derive(Debug, Clone)]
+pub struct DatabasePoolConfig {
+    pub max_connections: u32,
+    pub busy_timeout: Duration,
+    /// `journal_mode` pragma (e.g. "wal", "delete")
+    pub journal_mode: String,
+    /// `synchronous` pragma (e.g. "normal", "full")
+    pub synchronous: String,
+    /// Size of a separate read-only pool for search and statistics; 0 disables it
+    pub read_max_connections: u32,
+    /// Database the read pool opens, e.g. a replica; defaults to the primary database
+    pub read_url: Option<String>,
+    /// Statements taking longer are logged by sqlx at warn level
+    pub slow_statement_threshold: Duration,
+}
 
-#[derive(sqlx::FromRow)]
-struct MediaFileRow {
-    id: String,
-    filename: String,
-    original_filename: String,
-    dropbox_path: String,
-    url: String,
-    file_size: i64,
-    mime_type: String,
-    width: Option<i64>,
-    height: Option<i64>,
-    uploaded_at: String,
-    thumbnail_url: Option<String>,
-    alt_text: Option<String>,
-    caption: Option<String>,
+impl Default for DatabasePoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            busy_timeout: Duration::from_secs(5),
+            journal_mode: "wal".to_string(),
+            synchronous: "normal".to_string(),
+            read_max_connections: 0,
+            read_url: None,
+            slow_statement_threshold: Duration::from_secs(1),
+        }
+    }
 }
 
 /// Database service for managing SQLite operations
 #[derive(Clone)]
 pub struct DatabaseService {
     pool: Pool<Sqlite>,
+    /// Read-only pool for heavy queries so they don't hold up writers
+    read_pool: Option<Pool<Sqlite>>,
+    /// Rules applied to tags and categories whenever a post is written
+    tag_normalization: TagNormalization,
 }
 
 impl DatabaseService {
-    /// Create a new database service with connection pool
+    /// Create a new database service with the default pool settings
+    #[allow(dead_code)]
     pub async fn new(database_url: &str) -> Result<Self> {
+        Self::with_pool_config(database_url, &DatabasePoolConfig::default()).await
+    }
+
+    /// Empty in-memory database, for tests and throwaway fixtures
+    ///
+    /// Every connection to `sqlite::memory:` opens a new empty database, so
+    /// the pool is kept to a single connection.
+    pub async fn in_memory() -> Result<Self> {
+        let pool_config = DatabasePoolConfig {
+            max_connections: 1,
+            ..Default::default()
+        };
+        Self::with_pool_config("sqlite::memory:", &pool_config).await
+    }
+
+    /// Create a new database service with connection pool
+    pub async fn with_pool_config(
+        database_url: &str,
+        pool_config: &DatabasePoolConfig,
+    ) -> Result<Self> {
         info!("Connecting to database: {}", database_url);
 
         // Ensure parent directory exists and create database file for file-based SQLite databases
@@ -81,16 +138,73 @@ impl DatabaseService {
             }
         }
 
-        let pool = SqlitePool::connect(database_url)
+        let journal_mode = SqliteJournalMode::from_str(&pool_config.journal_mode)
+            .with_context(|| format!("Invalid journal mode: {}", pool_config.journal_mode))?;
+        let synchronous = SqliteSynchronous::from_str(&pool_config.synchronous)
+            .with_context(|| format!("Invalid synchronous setting: {}", pool_config.synchronous))?;
+        let options = SqliteConnectOptions::from_str(database_url)
+            .context("Invalid database URL")?
+            .busy_timeout(pool_config.busy_timeout)
+            .journal_mode(journal_mode)
+            .synchronous(synchronous)
+            .log_slow_statements(LevelFilter::Warn, pool_config.slow_statement_threshold);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(pool_config.max_connections)
+            .connect_with(options)
             .await
             .context("Failed to connect to database")?;
+        info!(
+            "Database pool ready: {} connections, journal_mode={}, synchronous={}",
+            pool_config.max_connections, pool_config.journal_mode, pool_config.synchronous
+        );
 
-        let service = Self { pool };
+        let mut service = Self {
+            pool,
+            read_pool: None,
+            tag_normalization: TagNormalization::default(),
+        };
         service.run_migrations().await?;
+        service.refresh_pooled_schemas().await?;
+
+        // Open the read pool after migrations so it sees the current schema
+        if pool_config.read_max_connections > 0 {
+            let read_url = pool_config.read_url.as_deref().unwrap_or(database_url);
+            if read_url.contains(":memory:") {
+                warn!("Read pool is not supported for in-memory databases, skipping");
+            } else {
+                let read_options = SqliteConnectOptions::from_str(read_url)
+                    .context("Invalid read database URL")?
+                    .busy_timeout(pool_config.busy_timeout)
+                    .read_only(true)
+                    .log_slow_statements(LevelFilter::Warn, pool_config.slow_statement_threshold);
+                let read_pool = SqlitePoolOptions::new()
+                    .max_connections(pool_config.read_max_connections)
+                    .connect_with(read_options)
+                    .await
+                    .context("Failed to connect to read database")?;
+                info!(
+                    "Read-only pool ready: {} connections",
+                    pool_config.read_max_connections
+                );
+                service.read_pool = Some(read_pool);
+            }
+        }
 
         Ok(service)
     }
 
+    /// Set the rules applied to tags and categories of created and updated posts
+    pub fn with_tag_normalization(mut self, tag_normalization: TagNormalization) -> Self {
+        self.tag_normalization = tag_normalization;
+        self
+    }
+
+    /// Pool for heavy read-only queries, falling back to the primary pool
+    fn read_pool(&self) -> &Pool<Sqlite> {
+        self.read_pool.as_ref().unwrap_or(&self.pool)
+    }
+
     /// Run database migrations
     async fn run_migrations(&self) -> Result<()> {
         info!("Running database migrations");
@@ -137,23 +251,377 @@ impl DatabaseService {
             .await
             .context("Failed to run migration 006")?;
 
+        // Migration 7: Link checks
+        let migration_7 = include_str!("../../migrations/007_create_link_checks_table.sql");
+        sqlx::query(migration_7)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 007")?;
+
+        // Migration 8: Scheduled publishing
+        self.add_column_if_missing("posts", "publish_at", "TEXT")
+            .await
+            .context("Failed to run migration 008")?;
+        let migration_8 = include_str!("../../migrations/008_add_post_publish_at.sql");
+        sqlx::query(migration_8)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 008")?;
+
+        // Migration 9: Featured images
+        self.add_column_if_missing("posts", "featured_media_id", "TEXT")
+            .await
+            .context("Failed to run migration 009")?;
+        let migration_9 = include_str!("../../migrations/009_create_featured_images_table.sql");
+        sqlx::query(migration_9)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 009")?;
+
+        // Migration 10: Media content hashes for deduplication
+        self.add_column_if_missing("media_files", "content_hash", "TEXT")
+            .await
+            .context("Failed to run migration 010")?;
+        let migration_10 = include_str!("../../migrations/010_add_media_content_hash.sql");
+        sqlx::query(migration_10)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 010")?;
+
+        // Migration 11: Video metadata (poster frames reuse thumbnail_url)
+        self.add_column_if_missing("media_files", "duration_seconds", "REAL")
+            .await
+            .context("Failed to run migration 011")?;
+
+        // Migration 12: Podcast audio enclosures
+        let migration_12 = include_str!("../../migrations/012_create_post_audio_table.sql");
+        sqlx::query(migration_12)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 012")?;
+
+        // Migration 13: Pending alt text suggestions
+        self.add_column_if_missing("media_files", "alt_text_suggestion", "TEXT")
+            .await
+            .context("Failed to run migration 013")?;
+
+        // Migration 14: Media collections
+        self.add_column_if_missing("media_files", "collection", "TEXT")
+            .await
+            .context("Failed to run migration 014")?;
+
+        // Migration 15: FTS triggers that remove old index entries correctly
+        let migration_15 = include_str!("../../migrations/015_fix_posts_fts_triggers.sql");
+        sqlx::query(migration_15)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 015")?;
+
+        // Migration 16: Backfill post-media references for posts saved before tracking
+        self.backfill_post_media()
+            .await
+            .context("Failed to run migration 016")?;
+
+        // Migration 17: Content hash of the last DB/Dropbox sync, the base for conflict detection
+        self.add_column_if_missing("posts", "sync_hash", "TEXT")
+            .await
+            .context("Failed to run migration 017")?;
+
+        // Migration 18: Word counts, backfilled from existing content
+        self.add_column_if_missing("posts", "word_count", "INTEGER")
+            .await
+            .context("Failed to run migration 018")?;
+        self.backfill_word_counts()
+            .await
+            .context("Failed to run migration 018")?;
+
+        // Migration 19: Custom frontmatter preserved when writing posts back to Dropbox
+        self.add_column_if_missing("posts", "custom_frontmatter", "TEXT")
+            .await
+            .context("Failed to run migration 019")?;
+
+        // Migration 20: Tag and category aliases
+        let migration_20 = include_str!("../../migrations/020_create_tag_aliases_table.sql");
+        sqlx::query(migration_20)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 020")?;
+
+        // Migration 21: Time of the last recorded sync, shown in the dashboard activity feed
+        self.add_column_if_missing("posts", "synced_at", "TEXT")
+            .await
+            .context("Failed to run migration 021")?;
+
+        // Migration 22: Pinned posts and their manual order
+        self.add_column_if_missing("posts", "pinned", "INTEGER NOT NULL DEFAULT 0")
+            .await
+            .context("Failed to run migration 022")?;
+        self.add_column_if_missing("posts", "pin_order", "INTEGER")
+            .await
+            .context("Failed to run migration 022")?;
+
+        // Migration 23: Generated post summaries
+        self.add_column_if_missing("posts", "summary", "TEXT")
+            .await
+            .context("Failed to run migration 023")?;
+
+        // Migration 24: Persistent background job queue
+        let migration_24 = include_str!("../../migrations/024_create_jobs_table.sql");
+        sqlx::query(migration_24)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 024")?;
+
+        // Migration 25: Post visibility (public, unlisted, private)
+        self.add_column_if_missing("posts", "visibility", "TEXT NOT NULL DEFAULT 'public'")
+            .await
+            .context("Failed to run migration 025")?;
+
+        // Migration 26: Password-protected posts
+        self.add_column_if_missing("posts", "password_hash", "TEXT")
+            .await
+            .context("Failed to run migration 026")?;
+
+        // Migration 27: Template locale
+        self.add_column_if_missing("site_config", "locale", "TEXT NOT NULL DEFAULT 'ja'")
+            .await
+            .context("Failed to run migration 027")?;
+
+        // Migration 28: Site timezone
+        self.add_column_if_missing("site_config", "timezone", "TEXT NOT NULL DEFAULT 'UTC'")
+            .await
+            .context("Failed to run migration 028")?;
+        // Migration 29: Progress reported by running jobs
+        self.add_column_if_missing("jobs", "progress", "TEXT")
+            .await
+            .context("Failed to run migration 029")?;
+
+        // Migration 30: Full-text search over media filenames, alt text and captions
+        let migration_30 = include_str!("../../migrations/030_create_media_fts.sql");
+        sqlx::query(migration_30)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 030")?;
+        self.backfill_media_search_index()
+            .await
+            .context("Failed to run migration 030")?;
+
+        // Migration 31: Per-category theme accents
+        let migration_31 = include_str!("../../migrations/031_create_category_styles_table.sql");
+        sqlx::query(migration_31)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 031")?;
+
+        // Migration 32: Site-wide announcement banners
+        let migration_32 = include_str!("../../migrations/032_create_announcements_table.sql");
+        sqlx::query(migration_32)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 032")?;
+
+        // Migration 33: Scheduled content expiry
+        self.add_column_if_missing("posts", "expires_at", "TEXT")
+            .await
+            .context("Failed to run migration 033")?;
+        self.add_column_if_missing(
+            "posts",
+            "expiry_action",
+            "TEXT NOT NULL DEFAULT 'unpublish'",
+        )
+        .await
+        .context("Failed to run migration 033")?;
+        self.add_column_if_missing("posts", "expiry_notice_days", "INTEGER")
+            .await
+            .context("Failed to run migration 033")?;
+        let migration_33 = include_str!("../../migrations/033_add_post_expiry.sql");
+        sqlx::query(migration_33)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 033")?;
+
+        // Migration 34: Reader-facing changelog flag
+        self.add_column_if_missing("posts", "show_changelog", "INTEGER NOT NULL DEFAULT 0")
+            .await
+            .context("Failed to run migration 034")?;
+
+        // Migration 35: Post attachments
+        let migration_35 = include_str!("../../migrations/035_create_post_attachments_table.sql");
+        sqlx::query(migration_35)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 035")?;
+
+        // Migration 36: Admin login sessions
+        let migration_36 = include_str!("../../migrations/036_create_admin_sessions_table.sql");
+        sqlx::query(migration_36)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 036")?;
+
+        // Migration 37: Admin two-factor authentication
+        let migration_37 = include_str!("../../migrations/037_create_admin_totp_tables.sql");
+        sqlx::query(migration_37)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 037")?;
+
+        // Migration 38: Tombstones for deleted posts
+        let migration_38 = include_str!("../../migrations/038_create_gone_slugs_table.sql");
+        sqlx::query(migration_38)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 038")?;
+
+        // Migration 39: Cross-posting metadata
+        self.add_column_if_missing("posts", "canonical_url", "TEXT")
+            .await
+            .context("Failed to run migration 039")?;
+        self.add_column_if_missing("posts", "syndication_links", "TEXT")
+            .await
+            .context("Failed to run migration 039")?;
+
+        // Migration 40: Cross-posts on external platforms
+        let migration_40 = include_str!("../../migrations/040_create_cross_posts_table.sql");
+        sqlx::query(migration_40)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 040")?;
+
+        // Migration 41: Materialized post counts, seeded from existing posts once
+        let migration_41 = include_str!("../../migrations/041_create_stats_tables.sql");
+        sqlx::query(migration_41)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 041")?;
+        let stats_seeded: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM stats)")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to run migration 041")?;
+        if !stats_seeded {
+            self.rebuild_stats()
+                .await
+                .context("Failed to run migration 041")?;
+        }
+
+        // Migration 42: Category descriptions
+        let migration_42 =
+            include_str!("../../migrations/042_create_category_descriptions_table.sql");
+        sqlx::query(migration_42)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 042")?;
+
+        // Migration 43: Content hashes for cache validation, backfilled from existing posts
+        self.add_column_if_missing("posts", "content_hash", "TEXT")
+            .await
+            .context("Failed to run migration 043")?;
+        self.backfill_content_hashes()
+            .await
+            .context("Failed to run migration 043")?;
+
+        // Migration 44: Time of the last metadata-only edit
+        self.add_column_if_missing("posts", "metadata_updated_at", "TEXT")
+            .await
+            .context("Failed to run migration 044")?;
+
+        // Migration 45: Version pinned as the published page
+        self.add_column_if_missing("posts", "published_version", "INTEGER")
+            .await
+            .context("Failed to run migration 045")?;
+
+        // Migration 46: Focal point of media files for card and Open Graph crops
+        self.add_column_if_missing("media_files", "focal_x", "REAL")
+            .await
+            .context("Failed to run migration 046")?;
+        self.add_column_if_missing("media_files", "focal_y", "REAL")
+            .await
+            .context("Failed to run migration 046")?;
+        let migration_14 = include_str!("../../migrations/014_add_media_collection.sql");
+        sqlx::query(migration_14)
+            .execute(&self.pool)
+            .await
+            .context("Failed to run migration 014")?;
+
         info!("Database migrations completed successfully");
         Ok(())
     }
 
+    /// Make every pooled connection re-read the schema after migrations
+    ///
+    /// Migrations may run on several connections. One that last ran before a
+    /// later `ALTER TABLE` prepares statements against the old schema, and
+    /// sqlx panics when SQLite then returns rows with the added columns.
+    async fn refresh_pooled_schemas(&self) -> Result<()> {
+        // Holding every connection at once makes sure each one is visited
+        let mut connections = Vec::new();
+        for _ in 0..self.pool.size() {
+            connections.push(self.pool.acquire().await?);
+        }
+        for connection in &mut connections {
+            sqlx::query("SELECT * FROM posts LIMIT 0")
+                .persistent(false)
+                .execute(&mut **connection)
+                .await
+                .context("Failed to refresh the database schema")?;
+        }
+
+        Ok(())
+    }
+
+    /// Add a column unless it already exists, keeping migrations re-runnable
+    async fn add_column_if_missing(
+        &self,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> Result<()> {
+        let columns = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await
+            .with_context(|| format!("Failed to read columns of {}", table))?;
+
+        let exists = columns.iter().any(|row| {
+            row.try_get::<String, _>("name")
+                .is_ok_and(|name| name == column)
+        });
+
+        if !exists {
+            info!("Adding column {}.{}", table, column);
+            sqlx::query(&format!(
+                "ALTER TABLE {} ADD COLUMN {} {}",
+                table, column, definition
+            ))
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to add column {}.{}", table, column))?;
+        }
+
+        Ok(())
+    }
+
     /// Create a new post
     #[allow(dead_code)]
-    pub async fn create_post(&self, data: CreatePost) -> Result<Post> {
+    pub async fn create_post(&self, mut data: CreatePost) -> Result<Post> {
         debug!("Creating new post: {}", data.slug);
 
+        let aliases = self.tag_alias_map().await?;
+        let rules = self.tag_normalization;
+        data.tags = rules.tags(&data.tags, &aliases);
+        data.category = data
+            .category
+            .map(|category| rules.category(&category, &aliases))
+            .filter(|category| !category.is_empty());
+
         let post = Post::new(data);
 
         sqlx::query(
             r#"
             INSERT INTO posts (
                 id, slug, title, content, html_content, excerpt, category, tags,
-                published, featured, author, dropbox_path, version, created_at, updated_at, published_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                published, featured, author, dropbox_path, version, created_at, updated_at, published_at,
+                word_count, content_hash
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(post.id.to_string())
@@ -172,10 +640,21 @@ impl DatabaseService {
         .bind(post.created_at.to_rfc3339())
         .bind(post.updated_at.to_rfc3339())
         .bind(post.published_at.map(|dt| dt.to_rfc3339()))
+        .bind(post.word_count)
+        .bind(&post.content_hash)
         .execute(&self.pool)
         .await
         .context("Failed to create post")?;
 
+        // A reused slug no longer answers 410 Gone
+        sqlx::query("DELETE FROM gone_slugs WHERE slug = ?")
+            .bind(&post.slug)
+            .execute(&self.pool)
+            .await
+            .context("Failed to clear gone slug")?;
+
+        self.sync_post_media(&post).await?;
+
         debug!("Created post with ID: {}", post.id);
         Ok(post)
     }
@@ -219,9 +698,18 @@ impl DatabaseService {
 
     /// Update post
     #[allow(dead_code)]
-    pub async fn update_post(&self, id: Uuid, data: UpdatePost) -> Result<Option<Post>> {
+    pub async fn update_post(&self, id: Uuid, mut data: UpdatePost) -> Result<Option<Post>> {
         debug!("Updating post: {}", id);
 
+        if data.tags.is_some() || data.category.is_some() {
+            let aliases = self.tag_alias_map().await?;
+            let rules = self.tag_normalization;
+            data.tags = data.tags.map(|tags| rules.tags(&tags, &aliases));
+            data.category = data
+                .category
+                .map(|category| rules.category(&category, &aliases));
+        }
+
         let mut post = match self.get_post_by_id(id).await? {
             Some(post) => post,
             None => return Ok(None),
@@ -234,7 +722,7 @@ impl DatabaseService {
             UPDATE posts SET
                 title = ?, content = ?, html_content = ?, excerpt = ?, category = ?, tags = ?,
                 published = ?, featured = ?, author = ?, dropbox_path = ?, version = ?,
-                updated_at = ?, published_at = ?
+                updated_at = ?, published_at = ?, word_count = ?, content_hash = ?
             WHERE id = ?
             "#,
         )
@@ -251,241 +739,560 @@ impl DatabaseService {
         .bind(post.version)
         .bind(post.updated_at.to_rfc3339())
         .bind(post.published_at.map(|dt| dt.to_rfc3339()))
+        .bind(post.word_count)
+        .bind(&post.content_hash)
         .bind(id.to_string())
         .execute(&self.pool)
         .await
         .context("Failed to update post")?;
 
+        self.sync_post_media(&post).await?;
+
         debug!("Updated post: {}", id);
         Ok(Some(post))
     }
 
-    /// Delete post
-    #[allow(dead_code)]
-    pub async fn delete_post(&self, id: Uuid) -> Result<bool> {
-        debug!("Deleting post: {}", id);
+    /// Change category, tags and flags of a post without re-rendering it,
+    /// bumping `updated_at` or the version number
+    pub async fn update_post_metadata(
+        &self,
+        id: Uuid,
+        mut data: UpdatePostMetadata,
+    ) -> Result<Option<Post>> {
+        debug!("Updating metadata of post: {}", id);
+
+        if data.tags.is_some() || data.category.is_some() {
+            let aliases = self.tag_alias_map().await?;
+            let rules = self.tag_normalization;
+            data.tags = data.tags.map(|tags| rules.tags(&tags, &aliases));
+            data.category = data
+                .category
+                .map(|category| rules.category(&category, &aliases));
+        }
 
-        let result = sqlx::query("DELETE FROM posts WHERE id = ?")
+        let mut post = match self.get_post_by_id(id).await? {
+            Some(post) => post,
+            None => return Ok(None),
+        };
+
+        post.update_metadata(data);
+
+        sqlx::query(
+            r#"
+            UPDATE posts SET
+                category = ?, tags = ?, published = ?, featured = ?, published_at = ?,
+                metadata_updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&post.category)
+        .bind(&post.tags)
+        .bind(if post.published { 1 } else { 0 })
+        .bind(if post.featured { 1 } else { 0 })
+        .bind(post.published_at.map(|dt| dt.to_rfc3339()))
+        .bind(post.metadata_updated_at.map(|dt| dt.to_rfc3339()))
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to update post metadata")?;
+
+        Ok(Some(post))
+    }
+
+    /// Set or clear the scheduled publication time of a post
+    pub async fn set_post_publish_at(
+        &self,
+        id: Uuid,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<bool> {
+        debug!("Setting publish_at of post {} to {:?}", id, publish_at);
+
+        let result = sqlx::query("UPDATE posts SET publish_at = ?, updated_at = ? WHERE id = ?")
+            .bind(publish_at.map(|dt| dt.to_rfc3339()))
+            .bind(Utc::now().to_rfc3339())
             .bind(id.to_string())
             .execute(&self.pool)
             .await
-            .context("Failed to delete post")?;
+            .context("Failed to update publish_at")?;
 
-        let deleted = result.rows_affected() > 0;
-        if deleted {
-            debug!("Deleted post: {}", id);
-        }
-        Ok(deleted)
+        Ok(result.rows_affected() > 0)
     }
 
-    /// List posts with filters
-    pub async fn list_posts(&self, filters: PostFilters) -> Result<Vec<Post>> {
-        debug!("Listing posts with filters: {:?}", filters);
+    /// Set or clear the expiry of a post
+    pub async fn set_post_expiry(
+        &self,
+        id: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+        action: ExpiryAction,
+        notice_days: Option<i64>,
+    ) -> Result<bool> {
+        debug!(
+            "Setting expiry of post {} to {:?} ({})",
+            id,
+            expires_at,
+            action.as_str()
+        );
 
-        let mut query = "SELECT * FROM posts WHERE 1=1".to_string();
-        let mut params = Vec::new();
+        let result = sqlx::query(
+            r#"
+            UPDATE posts
+            SET expires_at = ?, expiry_action = ?, expiry_notice_days = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(expires_at.map(|dt| dt.to_rfc3339()))
+        .bind(action.as_str())
+        .bind(notice_days)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to update expiry")?;
 
-        if let Some(published) = filters.published {
-            query.push_str(" AND published = ?");
-            params.push(if published { "1" } else { "0" }.to_string());
-        }
+        Ok(result.rows_affected() > 0)
+    }
 
-        if let Some(category) = &filters.category {
-            query.push_str(" AND category = ?");
-            params.push(category.clone());
-        }
+    /// Content hash recorded when the post was last in sync with Dropbox
+    pub async fn get_post_sync_hash(&self, id: Uuid) -> Result<Option<String>> {
+        let sync_hash =
+            sqlx::query_scalar::<_, Option<String>>("SELECT sync_hash FROM posts WHERE id = ?")
+                .bind(id.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to get sync hash")?;
 
-        if let Some(tag) = &filters.tag {
-            query.push_str(" AND tags LIKE ?");
-            params.push(format!("%\"{}\"%", tag));
-        }
+        Ok(sync_hash.flatten())
+    }
 
-        if let Some(author) = &filters.author {
-            query.push_str(" AND author = ?");
-            params.push(author.clone());
-        }
+    /// Record the content hash both the database and Dropbox agree on
+    pub async fn set_post_sync_hash(&self, id: Uuid, sync_hash: &str) -> Result<()> {
+        sqlx::query("UPDATE posts SET sync_hash = ?, synced_at = ? WHERE id = ?")
+            .bind(sync_hash)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update sync hash")?;
 
-        if let Some(featured) = filters.featured {
-            query.push_str(" AND featured = ?");
-            params.push(if featured { "1" } else { "0" }.to_string());
-        }
+        Ok(())
+    }
 
-        query.push_str(" ORDER BY created_at DESC");
+    /// Store a regenerated excerpt and word count without bumping the post version
+    pub async fn set_post_excerpt(
+        &self,
+        id: Uuid,
+        excerpt: Option<&str>,
+        word_count: i64,
+    ) -> Result<bool> {
+        let result = sqlx::query("UPDATE posts SET excerpt = ?, word_count = ? WHERE id = ?")
+            .bind(excerpt)
+            .bind(word_count)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update excerpt")?;
 
-        if let Some(limit) = filters.limit {
-            query.push_str(&format!(" LIMIT {}", limit));
-        }
+        Ok(result.rows_affected() > 0)
+    }
 
-        if let Some(offset) = filters.offset {
-            query.push_str(&format!(" OFFSET {}", offset));
-        }
+    /// Store a generated summary
+    pub async fn set_post_summary(&self, id: Uuid, summary: Option<&str>) -> Result<bool> {
+        let result = sqlx::query("UPDATE posts SET summary = ? WHERE id = ?")
+            .bind(summary)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update summary")?;
 
-        let mut sql_query = sqlx::query(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
-        }
+        Ok(result.rows_affected() > 0)
+    }
 
-        let rows = sql_query
-            .fetch_all(&self.pool)
+    /// Pin or unpin a post; unpinning clears its order
+    pub async fn set_post_pin(
+        &self,
+        id: Uuid,
+        pinned: bool,
+        pin_order: Option<i64>,
+    ) -> Result<bool> {
+        debug!("Setting pin of post {} to {} ({:?})", id, pinned, pin_order);
+
+        let result = sqlx::query("UPDATE posts SET pinned = ?, pin_order = ? WHERE id = ?")
+            .bind(if pinned { 1 } else { 0 })
+            .bind(if pinned { pin_order } else { None })
+            .bind(id.to_string())
+            .execute(&self.pool)
             .await
-            .context("Failed to list posts")?;
+            .context("Failed to update pin")?;
 
-        let posts = rows
-            .iter()
-            .map(|row| self.row_to_post(row))
-            .collect::<Result<Vec<_>>>()?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        debug!("Found {} posts", posts.len());
-        Ok(posts)
+    /// Show or hide the public changelog on a post page
+    pub async fn set_post_show_changelog(&self, id: Uuid, show_changelog: bool) -> Result<bool> {
+        debug!("Setting changelog of post {} to {}", id, show_changelog);
+
+        let result = sqlx::query("UPDATE posts SET show_changelog = ? WHERE id = ?")
+            .bind(if show_changelog { 1 } else { 0 })
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update changelog flag")?;
+
+        Ok(result.rows_affected() > 0)
     }
 
-    /// Search posts using full-text search
-    pub async fn search_posts(&self, query: &str, limit: Option<i64>) -> Result<Vec<Post>> {
-        debug!("Searching posts with query: {}", query);
+    /// Pin the version readers see, or show the latest again with `None`
+    pub async fn set_post_published_version(&self, id: Uuid, version: Option<i32>) -> Result<bool> {
+        debug!("Setting published version of post {} to {:?}", id, version);
 
-        let sql = if limit.is_some() {
-            r#"
-            SELECT p.* FROM posts p
-            JOIN posts_fts fts ON p.rowid = fts.rowid
-            WHERE posts_fts MATCH ?
-            ORDER BY rank
-            LIMIT ?
-            "#
+        let result = sqlx::query("UPDATE posts SET published_version = ? WHERE id = ?")
+            .bind(version)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update published version")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Swap in the version readers see, if the post has one pinned other than
+    /// the latest; returns whether it did
+    pub async fn show_published_version(&self, post: &mut Post) -> Result<bool> {
+        let Some(pinned) = post.published_version.filter(|v| *v != post.version) else {
+            return Ok(false);
+        };
+        match self.get_post_version(post.id, pinned).await? {
+            Some(version) => {
+                post.show_version(&version);
+                Ok(true)
+            }
+            None => {
+                warn!(
+                    "Published version {} of {} is missing, showing the latest",
+                    pinned, post.slug
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Swap in the published version of every post in a list readers see
+    pub async fn show_published_versions(&self, posts: &mut [Post]) -> Result<()> {
+        for post in posts {
+            self.show_published_version(post).await?;
+        }
+        Ok(())
+    }
+
+    /// Set the canonical URL override and the "also posted on" links of a post
+    pub async fn set_post_syndication(
+        &self,
+        id: Uuid,
+        canonical_url: Option<&str>,
+        syndication_links: &[String],
+    ) -> Result<bool> {
+        debug!("Setting cross-posting metadata of post {}", id);
+
+        let links = if syndication_links.is_empty() {
+            None
         } else {
-            r#"
-            SELECT p.* FROM posts p
-            JOIN posts_fts fts ON p.rowid = fts.rowid
-            WHERE posts_fts MATCH ?
-            ORDER BY rank
-            "#
+            Some(serde_json::to_string(syndication_links)?)
         };
+        let result =
+            sqlx::query("UPDATE posts SET canonical_url = ?, syndication_links = ? WHERE id = ?")
+                .bind(canonical_url)
+                .bind(links)
+                .bind(id.to_string())
+                .execute(&self.pool)
+                .await
+                .context("Failed to update cross-posting metadata")?;
 
-        let mut sql_query = sqlx::query(sql).bind(query);
+        Ok(result.rows_affected() > 0)
+    }
 
-        if let Some(limit) = limit {
-            sql_query = sql_query.bind(limit);
-        }
+    /// Change who can see a post
+    pub async fn set_post_visibility(&self, id: Uuid, visibility: PostVisibility) -> Result<bool> {
+        debug!("Setting visibility of post {} to {}", id, visibility.as_str());
 
-        let rows = sql_query
-            .fetch_all(&self.pool)
+        let result = sqlx::query("UPDATE posts SET visibility = ? WHERE id = ?")
+            .bind(visibility.as_str())
+            .bind(id.to_string())
+            .execute(&self.pool)
             .await
-            .context("Failed to search posts")?;
-
-        let posts = rows
-            .iter()
-            .map(|row| self.row_to_post(row))
-            .collect::<Result<Vec<_>>>()?;
+            .context("Failed to update visibility")?;
 
-        debug!("Found {} posts matching search", posts.len());
-        Ok(posts)
+        Ok(result.rows_affected() > 0)
     }
 
-    /// Get post statistics
-    pub async fn get_post_stats(&self) -> Result<PostStats> {
-        debug!("Getting post statistics");
+    /// Set the hashed password of a post, or remove the protection with `None`
+    pub async fn set_post_password(&self, id: Uuid, password_hash: Option<&str>) -> Result<bool> {
+        debug!("Setting password protection of post {}: {}", id, password_hash.is_some());
 
-        let total_posts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts")
-            .fetch_one(&self.pool)
+        let result = sqlx::query("UPDATE posts SET password_hash = ? WHERE id = ?")
+            .bind(password_hash)
+            .bind(id.to_string())
+            .execute(&self.pool)
             .await
-            .context("Failed to get total posts count")?;
+            .context("Failed to update post password")?;
 
-        let published_posts: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE published = true")
-                .fetch_one(&self.pool)
-                .await
-                .context("Failed to get published posts count")?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        let draft_posts = total_posts - published_posts;
+    /// Posts ordered by their last recorded sync, newest first
+    pub async fn list_recent_syncs(&self, limit: i64) -> Result<Vec<SyncRecord>> {
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT slug, title, synced_at FROM posts WHERE synced_at IS NOT NULL ORDER BY synced_at DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list recent syncs")?;
+
+        rows.into_iter()
+            .map(|(slug, title, synced_at)| {
+                Ok(SyncRecord {
+                    slug,
+                    title,
+                    synced_at: DateTime::parse_from_rfc3339(&synced_at)
+                        .context("Invalid synced_at format")?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect()
+    }
 
-        let featured_posts: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE featured = true")
-                .fetch_one(&self.pool)
-                .await
-                .context("Failed to get featured posts count")?;
+    /// Store the YAML of frontmatter keys the blog doesn't map to post fields
+    pub async fn set_post_custom_frontmatter(
+        &self,
+        id: Uuid,
+        custom_frontmatter: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE posts SET custom_frontmatter = ? WHERE id = ?")
+            .bind(custom_frontmatter)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update custom frontmatter")?;
+
+        Ok(())
+    }
 
-        // Get category statistics
-        let category_rows = sqlx::query(
+    /// Apply the expiry action of published posts whose `expires_at` time has
+    /// passed, returning them as they are afterwards
+    ///
+    /// Unpublished posts lose their expiry; archived posts keep it so their
+    /// page can say since when the content is outdated.
+    pub async fn expire_due_posts(&self) -> Result<Vec<Post>> {
+        let rows = sqlx::query(
             r#"
-            SELECT category, COUNT(*) as count
-            FROM posts
-            WHERE category IS NOT NULL AND published = true
-            GROUP BY category
-            ORDER BY count DESC
+            SELECT * FROM posts
+            WHERE published = 1 AND expires_at IS NOT NULL AND expires_at <= ?
+              AND NOT (expiry_action = 'archive' AND visibility = 'unlisted')
             "#,
         )
+        .bind(Utc::now().to_rfc3339())
         .fetch_all(&self.pool)
         .await
-        .context("Failed to get category stats")?;
+        .context("Failed to list expired posts")?;
+
+        let mut expired = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let post = self.row_to_post(row)?;
+            match post.expiry_action {
+                ExpiryAction::Unpublish => {
+                    let update = UpdatePost {
+                        published: Some(false),
+                        ..Default::default()
+                    };
+                    if self.update_post(post.id, update).await?.is_none() {
+                        continue;
+                    }
+                    self.set_post_expiry(post.id, None, post.expiry_action, None)
+                        .await?;
+                }
+                ExpiryAction::Archive => {
+                    self.set_post_visibility(post.id, PostVisibility::Unlisted)
+                        .await?;
+                }
+            }
+            info!(
+                "Expired post ({}): {}",
+                post.expiry_action.as_str(),
+                post.slug
+            );
+            if let Some(post) = self.get_post_by_id(post.id).await? {
+                expired.push(post);
+            }
+        }
 
-        let categories = category_rows
-            .iter()
-            .map(|row| CategoryStat {
-                name: row.get("category"),
-                count: row.get("count"),
-            })
-            .collect();
+        Ok(expired)
+    }
 
-        // Get tag statistics (this is simplified - in a real implementation you'd parse the JSON)
-        let tags = Vec::new(); // TODO: Implement tag parsing from JSON
+    /// Publish drafts whose scheduled `publish_at` time has passed, returning them
+    pub async fn publish_due_posts(&self) -> Result<Vec<Post>> {
+        let rows = sqlx::query(
+            "SELECT * FROM posts WHERE published = 0 AND publish_at IS NOT NULL AND publish_at <= ?",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list due scheduled posts")?;
+
+        let mut published = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let post = self.row_to_post(row)?;
+            let update = UpdatePost {
+                published: Some(true),
+                ..Default::default()
+            };
+            if let Some(post) = self.update_post(post.id, update).await? {
+                self.set_post_publish_at(post.id, None).await?;
+                info!("Published scheduled post: {}", post.slug);
+                published.push(post);
+            }
+        }
 
-        Ok(PostStats {
-            total_posts,
-            published_posts,
-            draft_posts,
-            featured_posts,
-            categories,
-            tags,
-        })
+        Ok(published)
     }
 
-    /// Convert database row to Post struct
-    fn row_to_post(&self, row: &SqliteRow) -> Result<Post> {
-        let id_str: String = row.try_get("id")?;
-        let id = Uuid::parse_str(&id_str).context("Invalid UUID format")?;
+    /// Count published posts edited in `[start, end)` that were published before `start`
+    pub async fn count_posts_updated_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<i64> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM posts
+            WHERE published = 1 AND updated_at >= ? AND updated_at < ?
+              AND COALESCE(published_at, created_at) < ?
+            "#,
+        )
+        .bind(start.to_rfc3339())
+        .bind(end.to_rfc3339())
+        .bind(start.to_rfc3339())
+        .fetch_one(self.read_pool())
+        .await
+        .context("Failed to count updated posts")
+    }
 
-        let created_at_str: String = row.try_get("created_at")?;
-        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
-            .context("Invalid created_at format")?
-            .with_timezone(&Utc);
+    /// Count media files uploaded in `[start, end)`
+    pub async fn count_media_uploaded_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<i64> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM media_files WHERE uploaded_at >= ? AND uploaded_at < ?",
+        )
+        .bind(start.to_rfc3339())
+        .bind(end.to_rfc3339())
+        .fetch_one(self.read_pool())
+        .await
+        .context("Failed to count media uploads")
+    }
 
-        let updated_at_str: String = row.try_get("updated_at")?;
-        let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
-            .context("Invalid updated_at format")?
-            .with_timezone(&Utc);
+    /// List posts whose calendar date falls in `[start, end)`
+    ///
+    /// Published posts are dated by `published_at`, scheduled posts by `publish_at`
+    /// and drafts by their last update.
+    pub async fn list_posts_for_calendar(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Post>> {
+        debug!("Listing calendar posts between {} and {}", start, end);
 
-        let published_at = row
-            .try_get::<Option<String>, _>("published_at")?
-            .and_then(|s| {
-                DateTime::parse_from_rfc3339(&s)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .ok()
-            });
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM posts
+            WHERE CASE
+                WHEN published = 1 THEN COALESCE(published_at, created_at)
+                ELSE COALESCE(publish_at, updated_at)
+            END >= ?
+            AND CASE
+                WHEN published = 1 THEN COALESCE(published_at, created_at)
+                ELSE COALESCE(publish_at, updated_at)
+            END < ?
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(start.to_rfc3339())
+        .bind(end.to_rfc3339())
+        .fetch_all(self.read_pool())
+        .await
+        .context("Failed to list calendar posts")?;
 
-        Ok(Post {
-            id,
-            slug: row.try_get("slug")?,
-            title: row.try_get("title")?,
-            content: row.try_get("content")?,
-            html_content: row.try_get("html_content")?,
-            excerpt: row.try_get("excerpt")?,
-            category: row.try_get("category")?,
-            tags: row.try_get("tags")?,
-            published: row.try_get::<i32, _>("published")? != 0,
-            featured: row.try_get::<i32, _>("featured")? != 0,
-            author: row.try_get("author")?,
-            dropbox_path: row.try_get("dropbox_path")?,
-            version: row.try_get("version")?,
-            created_at,
-            updated_at,
-            published_at,
+        rows.iter().map(|row| self.row_to_post(row)).collect()
+    }
+
+    /// Delete post
+    #[allow(dead_code)]
+    pub async fn delete_post(&self, id: Uuid) -> Result<bool> {
+        debug!("Deleting post: {}", id);
+
+        let mut tx = self.pool.begin().await?;
+
+        // Keep the slug so its URL answers 410 Gone rather than 404
+        sqlx::query(
+            r#"
+            INSERT INTO gone_slugs (slug, title, created_at, deleted_at)
+            SELECT slug, title, created_at, ? FROM posts WHERE id = ?
+            ON CONFLICT(slug) DO UPDATE SET
+                title = excluded.title,
+                created_at = excluded.created_at,
+                deleted_at = excluded.deleted_at
+            "#,
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to record gone slug")?;
+
+        let result = sqlx::query("DELETE FROM posts WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete post")?;
+
+        tx.commit().await?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            debug!("Deleted post: {}", id);
+        }
+        Ok(deleted)
+    }
+
+    /// Get the tombstone left by a deleted post
+    pub async fn get_gone_post(&self, slug: &str) -> Result<Option<GonePost>> {
+        let row = sqlx::query("SELECT * FROM gone_slugs WHERE slug = ?")
+            .bind(slug)
+            .fetch_optional(self.read_pool())
+            .await
+            .context("Failed to get gone slug")?;
+
+        row.map(|row| -> Result<GonePost> {
+            let parse = |column: &str| -> Result<DateTime<Utc>> {
+                let value: String = row.try_get(column)?;
+                Ok(DateTime::parse_from_rfc3339(&value)?.with_timezone(&Utc))
+            };
+            Ok(GonePost {
+                slug: row.try_get("slug")?,
+                title: row.try_get("title")?,
+                created_at: parse("created_at")?,
+                deleted_at: parse("deleted_at")?,
+            })
         })
+        .transpose()
     }
 
-    /// Count posts with filters for efficient pagination
-    pub async fn count_posts(&self, filters: PostFilters) -> Result<i64> {
-        debug!("Counting posts with filters: {:?}", filters);
+    /// List posts with filters
+    pub async fn list_posts(&self, filters: PostFilters) -> Result<Vec<Post>> {
+        debug!("Listing posts with filters: {:?}", filters);
 
-        let mut query = "SELECT COUNT(*) FROM posts WHERE 1=1".to_string();
+        let mut query = "SELECT * FROM posts WHERE 1=1".to_string();
         let mut params = Vec::new();
 
         if let Some(published) = filters.published {
@@ -513,269 +1320,2328 @@ impl DatabaseService {
             params.push(if featured { "1" } else { "0" }.to_string());
         }
 
-        let mut sql_query = sqlx::query_scalar::<_, i64>(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
+        if let Some(pinned) = filters.pinned {
+            query.push_str(" AND pinned = ?");
+            params.push(if pinned { "1" } else { "0" }.to_string());
         }
 
-        let count = sql_query
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to count posts")?;
+        if let Some(visibility) = &filters.visibility {
+            query.push_str(&Self::visibility_clause("visibility", visibility, &mut params));
+        }
 
-        debug!("Found {} posts matching filters", count);
-        Ok(count)
-    }
+        if let Some(password_protected) = filters.password_protected {
+            query.push_str(if password_protected {
+                " AND password_hash IS NOT NULL"
+            } else {
+                " AND password_hash IS NULL"
+            });
+        }
 
-    /// Get database pool reference
-    #[allow(dead_code)]
-    pub fn pool(&self) -> &Pool<Sqlite> {
-        &self.pool
-    }
+        if filters.pinned_first {
+            query.push_str(" ORDER BY pinned DESC, pin_order IS NULL, pin_order, created_at DESC");
+        } else {
+            query.push_str(" ORDER BY created_at DESC");
+        }
 
-    // Media file management methods
+        if let Some(limit) = filters.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
 
-    /// Create a new media file record
-    pub async fn create_media_file(&self, media: &MediaFile) -> Result<()> {
-        debug!("Creating media file: {}", media.filename);
+        if let Some(offset) = filters.offset {
+            query.push_str(&format!(" OFFSET {}", offset));
+        }
 
-        sqlx::query(
-            r#"
-            INSERT INTO media_files (
-                id, filename, original_filename, dropbox_path, url, file_size,
-                mime_type, width, height, uploaded_at, thumbnail_url, alt_text, caption
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(media.id.to_string())
-        .bind(&media.filename)
-        .bind(&media.original_filename)
-        .bind(&media.dropbox_path)
-        .bind(&media.url)
-        .bind(media.file_size as i64)
-        .bind(&media.mime_type)
-        .bind(media.width.map(|w| w as i64))
-        .bind(media.height.map(|h| h as i64))
-        .bind(media.uploaded_at.to_rfc3339())
-        .bind(&media.thumbnail_url)
-        .bind(&media.alt_text)
-        .bind(&media.caption)
-        .execute(&self.pool)
-        .await
-        .context("Failed to insert media file")?;
+        let mut sql_query = sqlx::query(&query);
+        for param in params {
+            sql_query = sql_query.bind(param);
+        }
 
-        info!("Created media file: {}", media.filename);
-        Ok(())
+        let rows = sql_query
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list posts")?;
+
+        let posts = rows
+            .iter()
+            .map(|row| self.row_to_post(row))
+            .collect::<Result<Vec<_>>>()?;
+
+        debug!("Found {} posts", posts.len());
+        Ok(posts)
     }
 
-    /// List media files with filters and pagination
-    pub async fn list_media_files(&self, filters: MediaFilters) -> Result<Vec<MediaFile>> {
-        debug!("Listing media files with filters: {:?}", filters);
+    /// Search posts using full-text search
+    pub async fn search_posts(&self, query: &str, filters: &SearchFilters) -> Result<Vec<Post>> {
+        debug!(
+            "Searching posts with query: {} and filters: {:?}",
+            query, filters
+        );
 
-        let mut query = "SELECT * FROM media_files WHERE 1=1".to_string();
-        let mut params = Vec::new();
+        let mut params = vec![query.to_string()];
+        let mut sql = format!(
+            r#"
+            SELECT p.* FROM posts p
+            JOIN posts_fts fts ON p.rowid = fts.rowid
+            WHERE posts_fts MATCH ?{}
+            ORDER BY rank
+            "#,
+            Self::search_filter_clause(filters, &mut params)
+        );
 
-        if let Some(folder) = &filters.folder {
-            query.push_str(" AND dropbox_path LIKE ?");
-            params.push(format!("%/{}/%", folder));
+        if let Some(limit) = filters.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+            if let Some(offset) = filters.offset {
+                sql.push_str(&format!(" OFFSET {}", offset));
+            }
         }
 
-        if let Some(mime_type) = &filters.mime_type {
-            query.push_str(" AND mime_type LIKE ?");
-            params.push(format!("{}%", mime_type));
+        let mut sql_query = sqlx::query(&sql);
+        for param in params {
+            sql_query = sql_query.bind(param);
         }
 
-        if let Some(search) = &filters.search {
-            query.push_str(" AND (filename LIKE ? OR original_filename LIKE ? OR alt_text LIKE ? OR caption LIKE ?)");
-            let search_param = format!("%{}%", search);
-            params.push(search_param.clone());
-            params.push(search_param.clone());
-            params.push(search_param.clone());
-            params.push(search_param);
+        let rows = sql_query
+            .fetch_all(self.read_pool())
+            .await
+            .context("Failed to search posts")?;
+
+        let posts = rows
+            .iter()
+            .map(|row| self.row_to_post(row))
+            .collect::<Result<Vec<_>>>()?;
+
+        debug!("Found {} posts matching search", posts.len());
+        Ok(posts)
+    }
+
+    /// Count all search matches, ignoring pagination
+    pub async fn count_search_results(&self, query: &str, filters: &SearchFilters) -> Result<i64> {
+        let mut params = vec![query.to_string()];
+        let sql = format!(
+            r#"
+            SELECT COUNT(*) as count FROM posts p
+            JOIN posts_fts fts ON p.rowid = fts.rowid
+            WHERE posts_fts MATCH ?{}
+            "#,
+            Self::search_filter_clause(filters, &mut params)
+        );
+
+        let mut sql_query = sqlx::query(&sql);
+        for param in params {
+            sql_query = sql_query.bind(param);
         }
 
-        query.push_str(" ORDER BY uploaded_at DESC");
+        let row = sql_query
+            .fetch_one(self.read_pool())
+            .await
+            .context("Failed to count search results")?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    /// Search posts like [`search_posts`](Self::search_posts), returning an
+    /// excerpt of the best matching column with each hit
+    pub async fn search_posts_with_snippets(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+    ) -> Result<Vec<SearchHit>> {
+        // \u{1} and \u{2} mark the matched terms; they can't occur in escaped
+        // text, so they are swapped for <mark> after escaping the snippet
+        let mut params = vec![query.to_string()];
+        let mut sql = format!(
+            r#"
+            SELECT p.*, snippet(posts_fts, -1, char(1), char(2), '…', 24) AS search_snippet
+            FROM posts p
+            JOIN posts_fts fts ON p.rowid = fts.rowid
+            WHERE posts_fts MATCH ?{}
+            ORDER BY rank
+            "#,
+            Self::search_filter_clause(filters, &mut params)
+        );
 
         if let Some(limit) = filters.limit {
-            query.push_str(&format!(" LIMIT {}", limit));
+            sql.push_str(&format!(" LIMIT {}", limit));
+            if let Some(offset) = filters.offset {
+                sql.push_str(&format!(" OFFSET {}", offset));
+            }
         }
 
-        if let Some(offset) = filters.offset {
-            query.push_str(&format!(" OFFSET {}", offset));
+        let mut sql_query = sqlx::query(&sql);
+        for param in params {
+            sql_query = sql_query.bind(param);
         }
 
-        let mut sql_query = sqlx::query(&query);
+        let rows = sql_query
+            .fetch_all(self.read_pool())
+            .await
+            .context("Failed to search posts")?;
+
+        rows.iter()
+            .map(|row| {
+                let snippet: Option<String> = row.try_get("search_snippet")?;
+                Ok(SearchHit {
+                    post: self.row_to_post(row)?,
+                    snippet_html: html_escape::encode_text(&snippet.unwrap_or_default())
+                        .replace('\u{1}', "<mark>")
+                        .replace('\u{2}', "</mark>"),
+                })
+            })
+            .collect()
+    }
+
+    /// Number of search matches per category, most matches first; the
+    /// category filter itself is ignored so every facet stays selectable
+    pub async fn search_category_facets(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+    ) -> Result<Vec<CategoryStat>> {
+        let filters = SearchFilters {
+            category: None,
+            ..filters.clone()
+        };
+        let mut params = vec![query.to_string()];
+        let sql = format!(
+            r#"
+            SELECT p.category AS name, COUNT(*) AS count FROM posts p
+            JOIN posts_fts fts ON p.rowid = fts.rowid
+            WHERE posts_fts MATCH ? AND p.category IS NOT NULL AND p.category != ''{}
+            GROUP BY p.category
+            ORDER BY count DESC, p.category
+            "#,
+            Self::search_filter_clause(&filters, &mut params)
+        );
+
+        let mut sql_query = sqlx::query(&sql);
         for param in params {
             sql_query = sql_query.bind(param);
         }
 
         let rows = sql_query
-            .fetch_all(&self.pool)
+            .fetch_all(self.read_pool())
             .await
-            .context("Failed to fetch media files")?;
+            .context("Failed to count search results by category")?;
 
-        let media_files = rows
-            .into_iter()
-            .map(|row| self.row_to_media_file(row))
-            .collect::<Result<Vec<_>>>()?;
+        rows.iter()
+            .map(|row| {
+                Ok(CategoryStat {
+                    name: row.try_get("name")?,
+                    count: row.try_get("count")?,
+                })
+            })
+            .collect()
+    }
 
-        debug!("Found {} media files", media_files.len());
-        Ok(media_files)
+    /// Turn free text into an FTS5 query matching every word, quoting each
+    /// word so operators and punctuation typed by visitors are searched literally
+    pub fn fts_match_query(input: &str) -> String {
+        input
+            .split_whitespace()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
-    /// Count media files with filters
-    pub async fn count_media_files(&self, filters: MediaFilters) -> Result<usize> {
-        debug!("Counting media files with filters: {:?}", filters);
+    /// Build the `AND ...` conditions for search filters, appending bind parameters
+    fn search_filter_clause(filters: &SearchFilters, params: &mut Vec<String>) -> String {
+        let mut clause = String::new();
 
-        let mut query = "SELECT COUNT(*) FROM media_files WHERE 1=1".to_string();
-        let mut params = Vec::new();
+        if let Some(published) = filters.published {
+            clause.push_str(" AND p.published = ?");
+            params.push(if published { "1" } else { "0" }.to_string());
+        }
 
-        if let Some(folder) = &filters.folder {
-            query.push_str(" AND dropbox_path LIKE ?");
-            params.push(format!("%/{}/%", folder));
+        if let Some(category) = &filters.category {
+            clause.push_str(" AND p.category = ?");
+            params.push(category.clone());
         }
 
-        if let Some(mime_type) = &filters.mime_type {
-            query.push_str(" AND mime_type LIKE ?");
-            params.push(format!("{}%", mime_type));
+        if let Some(tag) = &filters.tag {
+            clause.push_str(" AND p.tags LIKE ?");
+            params.push(format!("%\"{}\"%", tag));
         }
 
-        if let Some(search) = &filters.search {
-            query.push_str(" AND (filename LIKE ? OR original_filename LIKE ? OR alt_text LIKE ? OR caption LIKE ?)");
-            let search_param = format!("%{}%", search);
-            params.push(search_param.clone());
-            params.push(search_param.clone());
-            params.push(search_param.clone());
-            params.push(search_param);
+        if let Some(author) = &filters.author {
+            clause.push_str(" AND p.author = ?");
+            params.push(author.clone());
         }
 
-        let mut sql_query = sqlx::query_scalar::<_, i64>(&query);
-        for param in params {
-            sql_query = sql_query.bind(param);
+        if let Some(from) = filters.from {
+            clause.push_str(" AND COALESCE(p.published_at, p.created_at) >= ?");
+            params.push(from.to_rfc3339());
         }
 
-        let count = sql_query
-            .fetch_one(&self.pool)
-            .await
-            .context("Failed to count media files")?;
+        if let Some(to) = filters.to {
+            clause.push_str(" AND COALESCE(p.published_at, p.created_at) < ?");
+            params.push(to.to_rfc3339());
+        }
 
-        debug!("Found {} media files matching filters", count);
-        Ok(count as usize)
+        if let Some(visibility) = &filters.visibility {
+            clause.push_str(&Self::visibility_clause("p.visibility", visibility, params));
+        }
+
+        clause
     }
 
-    /// Get media file by ID
-    pub async fn get_media_file(&self, id: Uuid) -> Result<Option<MediaFile>> {
-        debug!("Getting media file by ID: {}", id);
+    /// `AND column IN (...)` for the given visibilities; matches nothing when empty
+    fn visibility_clause(
+        column: &str,
+        visibility: &[PostVisibility],
+        params: &mut Vec<String>,
+    ) -> String {
+        if visibility.is_empty() {
+            return " AND 0".to_string();
+        }
 
-        let row = sqlx::query_as::<_, MediaFileRow>("SELECT * FROM media_files WHERE id = ?")
-            .bind(id.to_string())
-            .fetch_optional(&self.pool)
+        params.extend(visibility.iter().map(|v| v.as_str().to_string()));
+        format!(
+            " AND {} IN ({})",
+            column,
+            vec!["?"; visibility.len()].join(", ")
+        )
+    }
+
+    /// Compare the `posts_fts` index with the posts table
+    pub async fn check_search_index(&self) -> Result<SearchIndexStatus> {
+        let post_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts")
+            .fetch_one(&self.pool)
             .await
-            .context("Failed to fetch media file")?;
+            .context("Failed to count posts")?;
 
-        match row {
-            Some(row) => {
-                let media_file = MediaFile {
-                    id: Uuid::parse_str(&row.id).context("Invalid UUID in database")?,
-                    filename: row.filename,
-                    original_filename: row.original_filename,
-                    dropbox_path: row.dropbox_path,
-                    url: row.url,
-                    file_size: row.file_size as u64,
-                    mime_type: row.mime_type,
-                    width: row.width.map(|w| w as u32),
-                    height: row.height.map(|h| h as u32),
-                    uploaded_at: DateTime::parse_from_rfc3339(&row.uploaded_at)
-                        .context("Invalid uploaded_at timestamp")?
-                        .with_timezone(&Utc),
-                    thumbnail_url: row.thumbnail_url,
-                    alt_text: row.alt_text,
-                    caption: row.caption,
-                };
-                Ok(Some(media_file))
+        // posts_fts reads its columns from posts, so the docsize shadow table is
+        // the only place that reflects what has actually been indexed
+        let indexed_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts_fts_docsize")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count search index entries")?;
+
+        let missing_slugs: Vec<String> = sqlx::query_scalar(
+            "SELECT slug FROM posts WHERE rowid NOT IN (SELECT id FROM posts_fts_docsize) ORDER BY slug",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to find unindexed posts")?;
+
+        let orphaned_entries: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM posts_fts_docsize WHERE id NOT IN (SELECT rowid FROM posts)",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count orphaned search index entries")?;
+
+        let integrity_ok = match sqlx::query(
+            "INSERT INTO posts_fts(posts_fts, rank) VALUES ('integrity-check', 1)",
+        )
+        .execute(&self.pool)
+        .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("Search index integrity check failed: {}", e);
+                false
             }
-            None => Ok(None),
-        }
+        };
+
+        Ok(SearchIndexStatus {
+            post_count,
+            indexed_count,
+            consistent: integrity_ok
+                && post_count == indexed_count
+                && missing_slugs.is_empty()
+                && orphaned_entries == 0,
+            missing_slugs,
+            orphaned_entries,
+            integrity_ok,
+        })
     }
 
-    /// Delete media file by ID
-    pub async fn delete_media_file(&self, id: Uuid) -> Result<bool> {
-        debug!("Deleting media file by ID: {}", id);
+    /// Rebuild `posts_fts` from the posts table, inserting `batch_size` posts at a time
+    ///
+    /// Runs in a single transaction so searches never see a half-built index and
+    /// concurrent post writes can't interleave with the rebuild.
+    pub async fn rebuild_search_index(&self, batch_size: i64) -> Result<i64> {
+        let batch_size = batch_size.max(1);
+        let mut tx = self.pool.begin().await?;
 
-        let result = sqlx::query("DELETE FROM media_files WHERE id = ?")
-            .bind(id.to_string())
-            .execute(&self.pool)
+        sqlx::query("INSERT INTO posts_fts(posts_fts) VALUES ('delete-all')")
+            .execute(&mut *tx)
             .await
-            .context("Failed to delete media file")?;
+            .context("Failed to clear search index")?;
 
-        let deleted = result.rows_affected() > 0;
-        if deleted {
-            info!("Deleted media file: {}", id);
-        } else {
-            debug!("Media file not found for deletion: {}", id);
+        let mut last_rowid = 0_i64;
+        let mut indexed = 0_i64;
+        loop {
+            let batch: Vec<i64> = sqlx::query_scalar(
+                "SELECT rowid FROM posts WHERE rowid > ? ORDER BY rowid LIMIT ?",
+            )
+            .bind(last_rowid)
+            .bind(batch_size)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to load posts for reindexing")?;
+
+            let Some(&batch_end) = batch.last() else {
+                break;
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO posts_fts(rowid, title, content, excerpt)
+                SELECT rowid, title, content, COALESCE(excerpt, '')
+                FROM posts WHERE rowid > ? AND rowid <= ?
+                "#,
+            )
+            .bind(last_rowid)
+            .bind(batch_end)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to index posts")?;
+
+            indexed += batch.len() as i64;
+            last_rowid = batch_end;
+            debug!("Reindexed {} posts (up to rowid {})", indexed, last_rowid);
         }
 
-        Ok(deleted)
+        tx.commit().await?;
+        info!("Rebuilt search index with {} posts", indexed);
+        Ok(indexed)
     }
 
-    /// Associate media file with a post
-    #[allow(dead_code)]
-    pub async fn associate_media_with_post(&self, post_id: Uuid, media_id: Uuid) -> Result<()> {
-        debug!("Associating media {} with post {}", media_id, post_id);
+    /// Get post statistics
+    pub async fn get_post_stats(&self) -> Result<PostStats> {
+        debug!("Getting post statistics");
+
+        // Counts are kept current by the stats triggers of migration 041
+        let row = sqlx::query(
+            "SELECT total_posts, published_posts, featured_posts FROM stats WHERE id = 1",
+        )
+        .fetch_optional(self.read_pool())
+        .await
+        .context("Failed to get post counts")?;
+        let (total_posts, published_posts, featured_posts): (i64, i64, i64) = row
+            .map(|row| {
+                (
+                    row.get("total_posts"),
+                    row.get("published_posts"),
+                    row.get("featured_posts"),
+                )
+            })
+            .unwrap_or_default();
+
+        let draft_posts = total_posts - published_posts;
+
+        let category_rows =
+            sqlx::query("SELECT category, count FROM category_stats ORDER BY count DESC, category")
+                .fetch_all(self.read_pool())
+                .await
+                .context("Failed to get category stats")?;
+
+        let categories = category_rows
+            .iter()
+            .map(|row| CategoryStat {
+                name: row.get("category"),
+                count: row.get("count"),
+            })
+            .collect();
+
+        // Get tag statistics (this is simplified - in a real implementation you'd parse the JSON)
+        let tags = Vec::new(); // TODO: Implement tag parsing from JSON
+
+        Ok(PostStats {
+            total_posts,
+            published_posts,
+            draft_posts,
+            featured_posts,
+            categories,
+            tags,
+        })
+    }
+
+    /// Recount the materialized post stats from the posts table
+    ///
+    /// The triggers keep the counts current; this repairs them after writes
+    /// that bypassed SQLite's triggers, such as a restored table.
+    pub async fn rebuild_stats(&self) -> Result<PostStats> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM stats")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear post counts")?;
+        sqlx::query(
+            r#"
+            INSERT INTO stats (id, total_posts, published_posts, featured_posts)
+            SELECT 1, COUNT(*), COALESCE(SUM(published), 0), COALESCE(SUM(featured), 0)
+            FROM posts
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to recount posts")?;
+
+        sqlx::query("DELETE FROM category_stats")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear category stats")?;
+        sqlx::query(
+            r#"
+            INSERT INTO category_stats (category, count)
+            SELECT category, COUNT(*) FROM posts
+            WHERE category IS NOT NULL AND published = true
+            GROUP BY category
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to recount categories")?;
+
+        tx.commit().await?;
+        self.get_post_stats().await
+    }
+
+    /// Replace `sources` tags with `target` (or remove them when `None`) on every post
+    ///
+    /// All posts are rewritten in a single transaction; the `posts_fts` update trigger
+    /// re-indexes each rewritten row. Returns the slugs of the affected posts.
+    pub async fn rewrite_tags(
+        &self,
+        sources: &[String],
+        target: Option<&str>,
+    ) -> Result<Vec<String>> {
+        debug!("Rewriting tags {:?} -> {:?}", sources, target);
+
+        if sources.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        // LIKE narrows the candidates; exact matching happens in Post::replace_tags
+        let conditions = vec!["tags LIKE ?"; sources.len()].join(" OR ");
+        let sql = format!("SELECT * FROM posts WHERE {}", conditions);
+        let mut query = sqlx::query(&sql);
+        for source in sources {
+            query = query.bind(format!("%{}%", serde_json::to_string(source)?));
+        }
+        let rows = query
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to find tagged posts")?;
+
+        let now = Utc::now().to_rfc3339();
+        let mut affected = Vec::new();
+
+        for row in &rows {
+            let mut post = self.row_to_post(row)?;
+            if !post.replace_tags(sources, target) {
+                continue;
+            }
+
+            sqlx::query("UPDATE posts SET tags = ?, updated_at = ? WHERE id = ?")
+                .bind(&post.tags)
+                .bind(&now)
+                .bind(post.id.to_string())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to update post tags")?;
+
+            affected.push(post.slug);
+        }
+
+        for source in sources.iter().filter(|s| Some(s.as_str()) != target) {
+            sqlx::query("DELETE FROM tags WHERE name = ?")
+                .bind(source)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to delete tag metadata")?;
+        }
+
+        tx.commit().await.context("Failed to commit tag rewrite")?;
+
+        info!("Rewrote tags on {} posts", affected.len());
+        Ok(affected)
+    }
+
+    /// Every distinct tag used by any post, published or not
+    pub async fn list_tag_names(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT DISTINCT tags FROM posts")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list post tags")?;
+
+        let mut names: Vec<String> = rows
+            .iter()
+            .flat_map(|(tags,)| serde_json::from_str::<Vec<String>>(tags).unwrap_or_default())
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// List tag aliases ordered by alias
+    pub async fn list_tag_aliases(&self) -> Result<Vec<TagAlias>> {
+        let rows = sqlx::query("SELECT * FROM tag_aliases ORDER BY alias")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list tag aliases")?;
+
+        rows.iter()
+            .map(|row| {
+                let created_at: String = row.try_get("created_at")?;
+                Ok(TagAlias {
+                    alias: row.try_get("alias")?,
+                    canonical: row.try_get("canonical")?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .context("Invalid created_at format")?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect()
+    }
+
+    /// Alias lookup table keyed by normalized alias
+    pub async fn tag_alias_map(&self) -> Result<HashMap<String, String>> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT alias, canonical FROM tag_aliases")
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to load tag aliases")?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Create or replace the alias for `canonical`
+    pub async fn upsert_tag_alias(&self, alias: &str, canonical: &str) -> Result<TagAlias> {
+        let alias = TagAlias {
+            alias: TagNormalization::alias_key(alias),
+            canonical: canonical.to_string(),
+            created_at: Utc::now(),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO tag_aliases (alias, canonical, created_at) VALUES (?, ?, ?)
+            ON CONFLICT(alias) DO UPDATE SET canonical = excluded.canonical
+            "#,
+        )
+        .bind(&alias.alias)
+        .bind(&alias.canonical)
+        .bind(alias.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save tag alias")?;
+
+        info!("Tag alias '{}' -> '{}'", alias.alias, alias.canonical);
+        Ok(alias)
+    }
+
+    /// Delete a tag alias, returning whether it existed
+    pub async fn delete_tag_alias(&self, alias: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM tag_aliases WHERE alias = ?")
+            .bind(TagNormalization::alias_key(alias))
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete tag alias")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Thumbnail URL of each media file the post references, keyed by the
+    /// file's URL
+    pub async fn post_media_thumbnails(&self, post_id: Uuid) -> Result<HashMap<String, String>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT m.url, m.thumbnail_url FROM media_files m
+            JOIN posts_media pm ON pm.media_id = m.id
+            WHERE pm.post_id = ? AND m.thumbnail_url IS NOT NULL
+            "#,
+        )
+        .bind(post_id.to_string())
+        .fetch_all(self.read_pool())
+        .await
+        .context("Failed to load post media thumbnails")?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Categories of the matching posts with their post counts and
+    /// descriptions, most posts first
+    pub async fn list_category_index(
+        &self,
+        published: Option<bool>,
+        visibility: Option<&[PostVisibility]>,
+    ) -> Result<Vec<CategoryIndexEntry>> {
+        let mut params = Vec::new();
+        let clause = Self::listing_clause(published, visibility, &mut params);
+        let sql = format!(
+            r#"
+            SELECT p.category AS name, COUNT(*) AS count, d.description
+            FROM posts p
+            LEFT JOIN category_descriptions d ON d.category = p.category
+            WHERE p.category IS NOT NULL AND p.category != ''{}
+            GROUP BY p.category
+            ORDER BY count DESC, p.category
+            "#,
+            clause
+        );
+
+        let mut query = sqlx::query(&sql);
+        for param in params {
+            query = query.bind(param);
+        }
+        let rows = query
+            .fetch_all(self.read_pool())
+            .await
+            .context("Failed to list categories")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(CategoryIndexEntry {
+                    name: row.try_get("name")?,
+                    count: row.try_get("count")?,
+                    description: row.try_get("description")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Tags of the matching posts with their post counts, by name
+    pub async fn list_tag_counts(
+        &self,
+        published: Option<bool>,
+        visibility: Option<&[PostVisibility]>,
+    ) -> Result<Vec<TagStat>> {
+        let mut params = Vec::new();
+        let clause = Self::listing_clause(published, visibility, &mut params);
+        let sql = format!(
+            r#"
+            SELECT t.value AS name, COUNT(*) AS count
+            FROM posts p, json_each(p.tags) t
+            WHERE json_valid(p.tags){}
+            GROUP BY t.value
+            ORDER BY t.value
+            "#,
+            clause
+        );
+
+        let mut query = sqlx::query(&sql);
+        for param in params {
+            query = query.bind(param);
+        }
+        let rows = query
+            .fetch_all(self.read_pool())
+            .await
+            .context("Failed to count tags")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TagStat {
+                    name: row.try_get("name")?,
+                    count: row.try_get("count")?,
+                })
+            })
+            .collect()
+    }
+
+    /// `AND ...` conditions on `p` for the public listings
+    fn listing_clause(
+        published: Option<bool>,
+        visibility: Option<&[PostVisibility]>,
+        params: &mut Vec<String>,
+    ) -> String {
+        let mut clause = String::new();
+        if let Some(published) = published {
+            clause.push_str(" AND p.published = ?");
+            params.push(if published { "1" } else { "0" }.to_string());
+        }
+        if let Some(visibility) = visibility {
+            clause.push_str(&Self::visibility_clause("p.visibility", visibility, params));
+        }
+        clause
+    }
+
+    /// List category descriptions ordered by category
+    pub async fn list_category_descriptions(&self) -> Result<Vec<CategoryDescription>> {
+        let rows = sqlx::query("SELECT * FROM category_descriptions ORDER BY category")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list category descriptions")?;
+
+        rows.iter()
+            .map(|row| {
+                let updated_at: String = row.try_get("updated_at")?;
+                Ok(CategoryDescription {
+                    category: row.try_get("category")?,
+                    description: row.try_get("description")?,
+                    updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                        .context("Invalid updated_at format")?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect()
+    }
+
+    /// Create or replace the description of `category`
+    pub async fn upsert_category_description(
+        &self,
+        category: &str,
+        description: &str,
+    ) -> Result<CategoryDescription> {
+        let entry = CategoryDescription {
+            category: category.to_string(),
+            description: description.to_string(),
+            updated_at: Utc::now(),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO category_descriptions (category, description, updated_at) VALUES (?, ?, ?)
+            ON CONFLICT(category) DO UPDATE SET
+                description = excluded.description,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&entry.category)
+        .bind(&entry.description)
+        .bind(entry.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save category description")?;
+
+        Ok(entry)
+    }
+
+    /// Delete a category description, returning whether it existed
+    pub async fn delete_category_description(&self, category: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM category_descriptions WHERE category = ?")
+            .bind(category)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete category description")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Move the storage paths of posts, media and theme files from the
+    /// `from` folder to the `to` folder, returning the rows changed
+    pub async fn rewrite_storage_paths(&self, from: &str, to: &str) -> Result<u64> {
+        let from = from.trim_end_matches('/');
+        let to = to.trim_end_matches('/');
+        let mut tx = self.pool.begin().await?;
+        let mut rewritten = 0;
+
+        for (table, column) in [
+            ("posts", "dropbox_path"),
+            ("media_files", "dropbox_path"),
+            ("theme_templates", "path"),
+        ] {
+            let query = format!(
+                "UPDATE {table} SET {column} = ? || substr({column}, ?) \
+                 WHERE {column} = ? OR substr({column}, 1, ?) = ?"
+            );
+            let from_len = from.chars().count() as i64;
+            let result = sqlx::query(&query)
+                .bind(to)
+                .bind(from_len + 1)
+                .bind(from)
+                .bind(from_len + 1)
+                .bind(format!("{}/", from))
+                .execute(&mut *tx)
+                .await
+                .with_context(|| format!("Failed to rewrite storage paths in {}", table))?;
+            rewritten += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        info!("Rewrote {} storage paths from {} to {}", rewritten, from, to);
+        Ok(rewritten)
+    }
+
+    /// Add a job to the queue, runnable right away
+    pub async fn enqueue_job(
+        &self,
+        kind: &str,
+        payload: &serde_json::Value,
+        max_attempts: i64,
+    ) -> Result<Job> {
+        let now = Utc::now();
+        let job = Job {
+            id: Uuid::new_v4(),
+            kind: kind.to_string(),
+            payload: payload.clone(),
+            status: JobStatus::Queued,
+            attempts: 0,
+            max_attempts,
+            last_error: None,
+            result: None,
+            progress: None,
+            run_at: now,
+            created_at: now,
+            started_at: None,
+            finished_at: None,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO jobs (id, kind, payload, status, attempts, max_attempts, run_at, created_at)
+            VALUES (?, ?, ?, ?, 0, ?, ?, ?)
+            "#,
+        )
+        .bind(job.id.to_string())
+        .bind(&job.kind)
+        .bind(job.payload.to_string())
+        .bind(job.status.as_str())
+        .bind(max_attempts)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to enqueue job")?;
+
+        debug!("Enqueued {} job {}", job.kind, job.id);
+        Ok(job)
+    }
+
+    /// Atomically take the next due job, marking it running and counting the attempt
+    pub async fn claim_next_job(&self) -> Result<Option<Job>> {
+        let now = Utc::now().to_rfc3339();
+        let row = sqlx::query(
+            r#"
+            UPDATE jobs SET status = 'running', attempts = attempts + 1, started_at = ?
+            WHERE id = (
+                SELECT id FROM jobs WHERE status = 'queued' AND run_at <= ?
+                ORDER BY run_at, created_at LIMIT 1
+            )
+            RETURNING id, kind, payload, status, attempts, max_attempts, last_error,
+                result, progress, run_at, created_at, started_at, finished_at
+            "#,
+        )
+        .bind(&now)
+        .bind(&now)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to claim job")?;
+
+        row.map(|row| self.row_to_job(&row)).transpose()
+    }
+
+    /// Mark a running job as succeeded
+    pub async fn complete_job(&self, id: Uuid, result: Option<&serde_json::Value>) -> Result<()> {
+        sqlx::query(
+            "UPDATE jobs SET status = 'succeeded', result = ?, last_error = NULL, finished_at = ? WHERE id = ?",
+        )
+        .bind(result.map(|value| value.to_string()))
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to complete job")?;
+
+        Ok(())
+    }
+
+    /// Record a failed attempt: the job is queued again at `retry_at`, or
+    /// moved to the dead-letter state when there is no retry left
+    pub async fn fail_job(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let query = match retry_at {
+            Some(retry_at) => sqlx::query(
+                "UPDATE jobs SET status = 'queued', last_error = ?, run_at = ? WHERE id = ?",
+            )
+            .bind(error)
+            .bind(retry_at.to_rfc3339()),
+            None => sqlx::query(
+                "UPDATE jobs SET status = 'dead', last_error = ?, finished_at = ? WHERE id = ?",
+            )
+            .bind(error)
+            .bind(Utc::now().to_rfc3339()),
+        };
+
+        query
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to record job failure")?;
+
+        Ok(())
+    }
+
+    /// Store the latest progress of a running job
+    pub async fn update_job_progress(&self, id: Uuid, progress: &serde_json::Value) -> Result<()> {
+        sqlx::query("UPDATE jobs SET progress = ? WHERE id = ?")
+            .bind(progress.to_string())
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update job progress")?;
+
+        Ok(())
+    }
+
+    /// Put jobs left running by a previous process back in the queue
+    pub async fn requeue_interrupted_jobs(&self) -> Result<u64> {
+        let result = sqlx::query("UPDATE jobs SET status = 'queued' WHERE status = 'running'")
+            .execute(&self.pool)
+            .await
+            .context("Failed to requeue interrupted jobs")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Get a job by ID
+    pub async fn get_job(&self, id: Uuid) -> Result<Option<Job>> {
+        let row = sqlx::query(&format!("{} WHERE id = ?", JOB_SELECT))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to get job")?;
+
+        row.map(|row| self.row_to_job(&row)).transpose()
+    }
+
+    /// List jobs, newest first
+    pub async fn list_jobs(&self, filters: &JobFilters) -> Result<Vec<Job>> {
+        let mut query = format!("{} WHERE 1=1", JOB_SELECT);
+        if filters.status.is_some() {
+            query.push_str(" AND status = ?");
+        }
+        if filters.kind.is_some() {
+            query.push_str(" AND kind = ?");
+        }
+        query.push_str(" ORDER BY created_at DESC LIMIT ?");
+
+        let mut sql_query = sqlx::query(&query);
+        if let Some(status) = filters.status {
+            sql_query = sql_query.bind(status.as_str());
+        }
+        if let Some(kind) = &filters.kind {
+            sql_query = sql_query.bind(kind);
+        }
+        let rows = sql_query
+            .bind(filters.limit.unwrap_or(50))
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list jobs")?;
+
+        rows.iter().map(|row| self.row_to_job(row)).collect()
+    }
+
+    fn row_to_job(&self, row: &SqliteRow) -> Result<Job> {
+        let parse_time = |value: String| -> Result<DateTime<Utc>> {
+            Ok(DateTime::parse_from_rfc3339(&value)
+                .context("Invalid job timestamp")?
+                .with_timezone(&Utc))
+        };
+        let id: String = row.try_get("id")?;
+        let payload: String = row.try_get("payload")?;
+        let status: String = row.try_get("status")?;
+        let result: Option<String> = row.try_get("result")?;
+        let progress: Option<String> = row.try_get("progress")?;
+        let started_at: Option<String> = row.try_get("started_at")?;
+        let finished_at: Option<String> = row.try_get("finished_at")?;
+
+        Ok(Job {
+            id: Uuid::parse_str(&id).context("Invalid job ID")?,
+            kind: row.try_get("kind")?,
+            payload: serde_json::from_str(&payload).context("Invalid job payload")?,
+            status: status.parse()?,
+            attempts: row.try_get("attempts")?,
+            max_attempts: row.try_get("max_attempts")?,
+            last_error: row.try_get("last_error")?,
+            result: result
+                .map(|result| serde_json::from_str(&result))
+                .transpose()
+                .context("Invalid job result")?,
+            progress: progress
+                .map(|progress| serde_json::from_str(&progress))
+                .transpose()
+                .context("Invalid job progress")?,
+            run_at: parse_time(row.try_get("run_at")?)?,
+            created_at: parse_time(row.try_get("created_at")?)?,
+            started_at: started_at.map(parse_time).transpose()?,
+            finished_at: finished_at.map(parse_time).transpose()?,
+        })
+    }
+
+    /// Convert database row to Post struct
+    fn row_to_post(&self, row: &SqliteRow) -> Result<Post> {
+        let id_str: String = row.try_get("id")?;
+        let id = Uuid::parse_str(&id_str).context("Invalid UUID format")?;
+
+        let created_at_str: String = row.try_get("created_at")?;
+        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+            .context("Invalid created_at format")?
+            .with_timezone(&Utc);
+
+        let updated_at_str: String = row.try_get("updated_at")?;
+        let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
+            .context("Invalid updated_at format")?
+            .with_timezone(&Utc);
+
+        let published_at = row
+            .try_get::<Option<String>, _>("published_at")?
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+            });
+
+        let publish_at = row
+            .try_get::<Option<String>, _>("publish_at")?
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+            });
+
+        let expires_at = row
+            .try_get::<Option<String>, _>("expires_at")
+            .ok()
+            .flatten()
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+            });
+
+        let featured_media_id = row
+            .try_get::<Option<String>, _>("featured_media_id")?
+            .and_then(|s| Uuid::parse_str(&s).ok());
+
+        let metadata_updated_at = row
+            .try_get::<Option<String>, _>("metadata_updated_at")
+            .ok()
+            .flatten()
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+            });
+
+        Ok(Post {
+            id,
+            slug: row.try_get("slug")?,
+            title: row.try_get("title")?,
+            content: row.try_get("content")?,
+            html_content: row.try_get("html_content")?,
+            excerpt: row.try_get("excerpt")?,
+            category: row.try_get("category")?,
+            tags: row.try_get("tags")?,
+            published: row.try_get::<i32, _>("published")? != 0,
+            featured: row.try_get::<i32, _>("featured")? != 0,
+            author: row.try_get("author")?,
+            dropbox_path: row.try_get("dropbox_path")?,
+            version: row.try_get("version")?,
+            created_at,
+            updated_at,
+            published_at,
+            publish_at,
+            featured_media_id,
+            word_count: row
+                .try_get::<Option<i64>, _>("word_count")
+                .ok()
+                .flatten()
+                .unwrap_or(0),
+            custom_frontmatter: row.try_get("custom_frontmatter").ok().flatten(),
+            pinned: row
+                .try_get::<Option<i64>, _>("pinned")
+                .ok()
+                .flatten()
+                .is_some_and(|pinned| pinned != 0),
+            pin_order: row.try_get("pin_order").ok().flatten(),
+            summary: row.try_get("summary").ok().flatten(),
+            visibility: row
+                .try_get::<Option<String>, _>("visibility")
+                .ok()
+                .flatten()
+                .and_then(|visibility| visibility.parse().ok())
+                .unwrap_or_default(),
+            password_hash: row.try_get("password_hash").ok().flatten(),
+            expires_at,
+            expiry_action: row
+                .try_get::<Option<String>, _>("expiry_action")
+                .ok()
+                .flatten()
+                .and_then(|action| action.parse().ok())
+                .unwrap_or_default(),
+            expiry_notice_days: row.try_get("expiry_notice_days").ok().flatten(),
+            show_changelog: row
+                .try_get::<Option<i64>, _>("show_changelog")
+                .ok()
+                .flatten()
+                .is_some_and(|show| show != 0),
+            canonical_url: row.try_get("canonical_url").ok().flatten(),
+            syndication_links: row.try_get("syndication_links").ok().flatten(),
+            content_hash: row
+                .try_get::<Option<String>, _>("content_hash")
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+            metadata_updated_at,
+            published_version: row.try_get("published_version").ok().flatten(),
+        })
+    }
+
+    /// Count posts with filters for efficient pagination
+    pub async fn count_posts(&self, filters: PostFilters) -> Result<i64> {
+        debug!("Counting posts with filters: {:?}", filters);
+
+        let mut query = "SELECT COUNT(*) FROM posts WHERE 1=1".to_string();
+        let mut params = Vec::new();
+
+        if let Some(published) = filters.published {
+            query.push_str(" AND published = ?");
+            params.push(if published { "1" } else { "0" }.to_string());
+        }
+
+        if let Some(category) = &filters.category {
+            query.push_str(" AND category = ?");
+            params.push(category.clone());
+        }
+
+        if let Some(tag) = &filters.tag {
+            query.push_str(" AND tags LIKE ?");
+            params.push(format!("%\"{}\"%", tag));
+        }
+
+        if let Some(author) = &filters.author {
+            query.push_str(" AND author = ?");
+            params.push(author.clone());
+        }
+
+        if let Some(featured) = filters.featured {
+            query.push_str(" AND featured = ?");
+            params.push(if featured { "1" } else { "0" }.to_string());
+        }
+
+        if let Some(pinned) = filters.pinned {
+            query.push_str(" AND pinned = ?");
+            params.push(if pinned { "1" } else { "0" }.to_string());
+        }
+
+        if let Some(visibility) = &filters.visibility {
+            query.push_str(&Self::visibility_clause("visibility", visibility, &mut params));
+        }
+
+        if let Some(password_protected) = filters.password_protected {
+            query.push_str(if password_protected {
+                " AND password_hash IS NOT NULL"
+            } else {
+                " AND password_hash IS NULL"
+            });
+        }
+
+        let mut sql_query = sqlx::query_scalar::<_, i64>(&query);
+        for param in params {
+            sql_query = sql_query.bind(param);
+        }
+
+        let count = sql_query
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count posts")?;
+
+        debug!("Found {} posts matching filters", count);
+        Ok(count)
+    }
+
+    /// Get database pool reference
+    #[allow(dead_code)]
+    pub fn pool(&self) -> &Pool<Sqlite> {
+        &self.pool
+    }
+
+    // Media file management methods
+
+    /// Create a new media file record
+    pub async fn create_media_file(&self, media: &MediaFile) -> Result<()> {
+        debug!("Creating media file: {}", media.filename);
+
+        sqlx::query(
+            r#"
+            INSERT INTO media_files (
+                id, filename, original_filename, dropbox_path, url, file_size,
+                mime_type, width, height, uploaded_at, thumbnail_url, alt_text, caption,
+                content_hash, duration_seconds, collection, focal_x, focal_y
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(media.id.to_string())
+        .bind(&media.filename)
+        .bind(&media.original_filename)
+        .bind(&media.dropbox_path)
+        .bind(&media.url)
+        .bind(media.file_size as i64)
+        .bind(&media.mime_type)
+        .bind(media.width.map(|w| w as i64))
+        .bind(media.height.map(|h| h as i64))
+        .bind(media.uploaded_at.to_rfc3339())
+        .bind(&media.thumbnail_url)
+        .bind(&media.alt_text)
+        .bind(&media.caption)
+        .bind(&media.content_hash)
+        .bind(media.duration_seconds)
+        .bind(&media.collection)
+        .bind(media.focal_point.map(|point| point.x))
+        .bind(media.focal_point.map(|point| point.y))
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert media file")?;
+
+        info!("Created media file: {}", media.filename);
+        Ok(())
+    }
+
+    /// Distinct media collection names, alphabetically
+    pub async fn list_media_collections(&self) -> Result<Vec<String>> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT collection FROM media_files WHERE collection IS NOT NULL ORDER BY collection",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list media collections")?;
+
+        rows.iter()
+            .map(|row| row.try_get("collection").map_err(Into::into))
+            .collect()
+    }
+
+    /// Update the editable metadata (alt text, caption, display name, collection, focal point)
+    /// of a media file
+    pub async fn update_media_metadata(&self, media: &MediaFile) -> Result<bool> {
+        debug!("Updating metadata of media file: {}", media.id);
+
+        let result = sqlx::query(
+            r#"
+            UPDATE media_files SET
+                alt_text = ?, caption = ?, alt_text_suggestion = ?, original_filename = ?,
+                collection = ?, focal_x = ?, focal_y = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&media.alt_text)
+        .bind(&media.caption)
+        .bind(&media.alt_text_suggestion)
+        .bind(&media.original_filename)
+        .bind(&media.collection)
+        .bind(media.focal_point.map(|point| point.x))
+        .bind(media.focal_point.map(|point| point.y))
+        .bind(Utc::now().to_rfc3339())
+        .bind(media.id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to update media metadata")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Store a pending alt text suggestion unless alt text was set in the meantime
+    pub async fn set_alt_text_suggestion(&self, id: Uuid, suggestion: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE media_files SET alt_text_suggestion = ? WHERE id = ? AND alt_text IS NULL",
+        )
+        .bind(suggestion)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to store alt text suggestion")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List media files with filters and pagination
+    pub async fn list_media_files(&self, filters: MediaFilters) -> Result<Vec<MediaFile>> {
+        debug!("Listing media files with filters: {:?}", filters);
+
+        let mut params = Vec::new();
+        let (join, clause) = Self::media_filter_clause(&filters, &mut params);
+        let mut query = format!("SELECT m.* FROM media_files m{} WHERE 1=1{}", join, clause);
+
+        // Best matches first when searching, otherwise newest first
+        if join.is_empty() {
+            query.push_str(" ORDER BY m.uploaded_at DESC");
+        } else {
+            query.push_str(" ORDER BY media_fts.rank, m.uploaded_at DESC");
+        }
+
+        if let Some(limit) = filters.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        if let Some(offset) = filters.offset {
+            query.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        let mut sql_query = sqlx::query(&query);
+        for param in params {
+            sql_query = sql_query.bind(param);
+        }
+
+        let rows = sql_query
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch media files")?;
+
+        let media_files = rows
+            .into_iter()
+            .map(|row| self.row_to_media_file(row))
+            .collect::<Result<Vec<_>>>()?;
+
+        debug!("Found {} media files", media_files.len());
+        Ok(media_files)
+    }
+
+    /// Count media files with filters
+    pub async fn count_media_files(&self, filters: MediaFilters) -> Result<usize> {
+        debug!("Counting media files with filters: {:?}", filters);
+
+        let mut params = Vec::new();
+        let (join, clause) = Self::media_filter_clause(&filters, &mut params);
+        let query = format!("SELECT COUNT(*) FROM media_files m{} WHERE 1=1{}", join, clause);
+
+        let mut sql_query = sqlx::query_scalar::<_, i64>(&query);
+        for param in params {
+            sql_query = sql_query.bind(param);
+        }
+
+        let count = sql_query
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count media files")?;
+
+        debug!("Found {} media files matching filters", count);
+        Ok(count as usize)
+    }
+
+    /// Build the search join and `AND ...` conditions for media filters, appending
+    /// bind parameters; media rows are aliased `m`
+    fn media_filter_clause(filters: &MediaFilters, params: &mut Vec<String>) -> (String, String) {
+        let mut join = String::new();
+        let mut clause = String::new();
+
+        if let Some(folder) = &filters.folder {
+            clause.push_str(" AND m.dropbox_path LIKE ?");
+            params.push(format!("%/{}/%", folder));
+        }
+
+        if let Some(mime_type) = &filters.mime_type {
+            clause.push_str(" AND m.mime_type LIKE ?");
+            params.push(format!("{}%", mime_type));
+        }
+
+        if let Some(collection) = &filters.collection {
+            clause.push_str(" AND m.collection = ?");
+            params.push(collection.clone());
+        }
+
+        if let Some(search) = filters.search.as_deref().and_then(media_search_query) {
+            join.push_str(" JOIN media_fts ON m.rowid = media_fts.rowid");
+            clause.push_str(" AND media_fts MATCH ?");
+            params.push(search);
+        }
+
+        (join, clause)
+    }
+
+    /// Get media file by ID
+    pub async fn get_media_file(&self, id: Uuid) -> Result<Option<MediaFile>> {
+        debug!("Getting media file by ID: {}", id);
+
+        let row = sqlx::query("SELECT * FROM media_files WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to fetch media file")?;
+
+        row.map(|row| self.row_to_media_file(row)).transpose()
+    }
+
+    /// Find the earliest upload with the given SHA-256 content hash
+    pub async fn find_media_file_by_hash(&self, content_hash: &str) -> Result<Option<MediaFile>> {
+        debug!("Looking up media file by hash: {}", content_hash);
+
+        let row = sqlx::query(
+            "SELECT * FROM media_files WHERE content_hash = ? ORDER BY uploaded_at ASC LIMIT 1",
+        )
+        .bind(content_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch media file by hash")?;
+
+        row.map(|row| self.row_to_media_file(row)).transpose()
+    }
+
+    /// Delete media file by ID
+    pub async fn delete_media_file(&self, id: Uuid) -> Result<bool> {
+        debug!("Deleting media file by ID: {}", id);
+
+        let result = sqlx::query("DELETE FROM media_files WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete media file")?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            info!("Deleted media file: {}", id);
+        } else {
+            debug!("Media file not found for deletion: {}", id);
+        }
+
+        Ok(deleted)
+    }
+
+    /// Associate media file with a post
+    #[allow(dead_code)]
+    pub async fn associate_media_with_post(&self, post_id: Uuid, media_id: Uuid) -> Result<()> {
+        debug!("Associating media {} with post {}", media_id, post_id);
+
+        sqlx::query("INSERT OR IGNORE INTO posts_media (post_id, media_id) VALUES (?, ?)")
+            .bind(post_id.to_string())
+            .bind(media_id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to associate media with post")?;
+
+        Ok(())
+    }
+
+    /// Slugs of posts that display a media file inline, as featured image, as audio or as attachment
+    pub async fn get_post_slugs_using_media(&self, media_id: Uuid) -> Result<Vec<String>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT slug FROM posts WHERE id IN (
+                SELECT post_id FROM posts_media WHERE media_id = ?1
+                UNION SELECT post_id FROM featured_images WHERE media_id = ?1
+                UNION SELECT post_id FROM post_audio WHERE media_id = ?1
+                UNION SELECT post_id FROM post_attachments WHERE media_id = ?1
+            )
+            "#,
+        )
+        .bind(media_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to find posts using media")?;
+
+        rows.iter()
+            .map(|row| row.try_get("slug").map_err(Into::into))
+            .collect()
+    }
+
+    /// Replace a post's media references with the `/media/...` files its content links to
+    pub async fn sync_post_media(&self, post: &Post) -> Result<usize> {
+        let mut urls = extract_media_urls(&post.content);
+        urls.extend(extract_media_urls(&post.html_content));
+        urls.sort();
+        urls.dedup();
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM posts_media WHERE post_id = ?")
+            .bind(post.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear post media references")?;
+
+        let mut linked = 0;
+        for url in &urls {
+            let result = sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO posts_media (post_id, media_id)
+                SELECT ?, id FROM media_files WHERE url = ? OR thumbnail_url = ?
+                "#,
+            )
+            .bind(post.id.to_string())
+            .bind(url)
+            .bind(url)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to store post media reference")?;
+            linked += result.rows_affected() as usize;
+        }
+
+        tx.commit().await?;
+
+        debug!("Post {} references {} media files", post.slug, linked);
+        Ok(linked)
+    }
+
+    /// Rebuild media references of every post when none have been recorded yet
+    async fn backfill_post_media(&self) -> Result<()> {
+        let tracked: i64 = sqlx::query("SELECT COUNT(*) as count FROM posts_media")
+            .fetch_one(&self.pool)
+            .await?
+            .try_get("count")?;
+        if tracked > 0 {
+            return Ok(());
+        }
+
+        let rows = sqlx::query("SELECT * FROM posts WHERE content LIKE '%/media/%'")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to load posts for media backfill")?;
+        for row in rows {
+            self.sync_post_media(&self.row_to_post(&row)?).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Index media uploaded before `media_fts` existed
+    async fn backfill_media_search_index(&self) -> Result<()> {
+        let indexed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM media_fts_docsize")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count media search index entries")?;
+        if indexed > 0 {
+            return Ok(());
+        }
+
+        sqlx::query("INSERT INTO media_fts(media_fts) VALUES ('rebuild')")
+            .execute(&self.pool)
+            .await
+            .context("Failed to build media search index")?;
+        Ok(())
+    }
+
+    /// Count words of posts stored before word counts were tracked
+    async fn backfill_word_counts(&self) -> Result<()> {
+        let rows = sqlx::query("SELECT id, content FROM posts WHERE word_count IS NULL")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to load posts for word count backfill")?;
+        for row in rows {
+            let content: String = row.try_get("content")?;
+            sqlx::query("UPDATE posts SET word_count = ? WHERE id = ?")
+                .bind(count_words(&content))
+                .bind(row.try_get::<String, _>("id")?)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Hash the content of posts stored before content hashes were tracked
+    async fn backfill_content_hashes(&self) -> Result<()> {
+        let rows = sqlx::query("SELECT id, title, content FROM posts WHERE content_hash IS NULL")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to load posts for content hash backfill")?;
+        for row in rows {
+            let title: String = row.try_get("title")?;
+            let content: String = row.try_get("content")?;
+            sqlx::query("UPDATE posts SET content_hash = ? WHERE id = ?")
+                .bind(content_hash(&title, &content))
+                .bind(row.try_get::<String, _>("id")?)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Number of posts using each of the given media files (inline, featured, audio or attachment)
+    pub async fn get_media_usage_counts(&self, media_ids: &[Uuid]) -> Result<HashMap<Uuid, i64>> {
+        if media_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let placeholders = vec!["?"; media_ids.len()].join(", ");
+        let query = format!(
+            r#"
+            SELECT media_id, COUNT(DISTINCT post_id) as count FROM (
+                SELECT post_id, media_id FROM posts_media
+                UNION ALL SELECT post_id, media_id FROM featured_images
+                UNION ALL SELECT post_id, media_id FROM post_audio
+                UNION ALL SELECT post_id, media_id FROM post_attachments
+            )
+            WHERE media_id IN ({})
+            GROUP BY media_id
+            "#,
+            placeholders
+        );
+
+        let mut sql_query = sqlx::query(&query);
+        for id in media_ids {
+            sql_query = sql_query.bind(id.to_string());
+        }
+
+        let rows = sql_query
+            .fetch_all(self.read_pool())
+            .await
+            .context("Failed to count media usage")?;
+
+        rows.iter()
+            .map(|row| {
+                let media_id: String = row.try_get("media_id")?;
+                Ok((
+                    Uuid::parse_str(&media_id).context("Invalid media ID")?,
+                    row.try_get("count")?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Get media files associated with a post
+    #[allow(dead_code)]
+    pub async fn get_post_media(&self, post_id: Uuid) -> Result<Vec<MediaFile>> {
+        debug!("Getting media files for post: {}", post_id);
+
+        let rows = sqlx::query(
+            r#"
+            SELECT m.* FROM media_files m
+            JOIN posts_media pm ON m.id = pm.media_id
+            WHERE pm.post_id = ?
+            ORDER BY m.uploaded_at DESC
+            "#,
+        )
+        .bind(post_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch post media")?;
+
+        let media_files = rows
+            .into_iter()
+            .map(|row| self.row_to_media_file(row))
+            .collect::<Result<Vec<_>>>()?;
+
+        debug!("Found {} media files for post", media_files.len());
+        Ok(media_files)
+    }
+
+    /// Set a post's featured image and store its cropped renditions
+    pub async fn set_featured_image(&self, featured: &FeaturedImage) -> Result<()> {
+        debug!(
+            "Setting featured image {} for post {}",
+            featured.media_id, featured.post_id
+        );
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query("UPDATE posts SET featured_media_id = ?, updated_at = ? WHERE id = ?")
+            .bind(featured.media_id.to_string())
+            .bind(featured.updated_at.to_rfc3339())
+            .bind(featured.post_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to update featured media")?;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO featured_images (
+                post_id, media_id, focal_x, focal_y, card_url, og_url, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(featured.post_id.to_string())
+        .bind(featured.media_id.to_string())
+        .bind(featured.focal_point.x)
+        .bind(featured.focal_point.y)
+        .bind(&featured.card_url)
+        .bind(&featured.og_url)
+        .bind(featured.updated_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to save featured image")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit featured image")?;
+
+        Ok(())
+    }
+
+    /// IDs of the posts whose featured image was cropped from a media file
+    pub async fn list_featured_image_posts(&self, media_id: Uuid) -> Result<Vec<Uuid>> {
+        let rows = sqlx::query("SELECT post_id FROM featured_images WHERE media_id = ?")
+            .bind(media_id.to_string())
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list featured images of media")?;
+
+        rows.iter()
+            .map(|row| {
+                let post_id: String = row.try_get("post_id")?;
+                Uuid::parse_str(&post_id).context("Invalid post ID")
+            })
+            .collect()
+    }
+
+    /// Get the featured image renditions of a post
+    pub async fn get_featured_image(&self, post_id: Uuid) -> Result<Option<FeaturedImage>> {
+        let row = sqlx::query("SELECT * FROM featured_images WHERE post_id = ?")
+            .bind(post_id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to get featured image")?;
+
+        row.map(|row| {
+            let post_id: String = row.try_get("post_id")?;
+            let media_id: String = row.try_get("media_id")?;
+            let updated_at: String = row.try_get("updated_at")?;
+
+            Ok(FeaturedImage {
+                post_id: Uuid::parse_str(&post_id).context("Invalid post ID")?,
+                media_id: Uuid::parse_str(&media_id).context("Invalid media ID")?,
+                focal_point: FocalPoint {
+                    x: row.try_get("focal_x")?,
+                    y: row.try_get("focal_y")?,
+                },
+                card_url: row.try_get("card_url")?,
+                og_url: row.try_get("og_url")?,
+                updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                    .context("Invalid updated_at format")?
+                    .with_timezone(&Utc),
+            })
+        })
+        .transpose()
+    }
+
+    /// Attach an audio enclosure to a post, replacing any previous one
+    pub async fn set_post_audio(
+        &self,
+        post_id: Uuid,
+        media_id: Uuid,
+        mime_type: &str,
+        duration_seconds: Option<f64>,
+    ) -> Result<()> {
+        debug!("Attaching audio {} to post {}", media_id, post_id);
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO post_audio (
+                post_id, media_id, mime_type, duration_seconds, updated_at
+            ) VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(post_id.to_string())
+        .bind(media_id.to_string())
+        .bind(mime_type)
+        .bind(duration_seconds)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to attach post audio")?;
+
+        Ok(())
+    }
+
+    /// Remove a post's audio enclosure
+    pub async fn remove_post_audio(&self, post_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM post_audio WHERE post_id = ?")
+            .bind(post_id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to remove post audio")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get the audio enclosure of a post
+    pub async fn get_post_audio(&self, post_id: Uuid) -> Result<Option<AudioEnclosure>> {
+        let row = sqlx::query(
+            r#"
+            SELECT a.*, m.url, m.file_size FROM post_audio a
+            JOIN media_files m ON m.id = a.media_id
+            WHERE a.post_id = ?
+            "#,
+        )
+        .bind(post_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to get post audio")?;
+
+        row.map(|row| self.row_to_audio_enclosure(&row)).transpose()
+    }
+
+    /// Attach a media file to a post as a download; attaching it again is a no-op
+    pub async fn add_post_attachment(
+        &self,
+        post_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<PostAttachment> {
+        debug!("Attaching media {} to post {}", media_id, post_id);
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO post_attachments (id, post_id, media_id, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(post_id.to_string())
+        .bind(media_id.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to attach file to post")?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT a.*, m.original_filename, m.mime_type, m.file_size FROM post_attachments a
+            JOIN media_files m ON m.id = a.media_id
+            WHERE a.post_id = ? AND a.media_id = ?
+            "#,
+        )
+        .bind(post_id.to_string())
+        .bind(media_id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to get post attachment")?;
+
+        self.row_to_post_attachment(&row)
+    }
+
+    /// Attachments of a post, in the order they were added
+    pub async fn list_post_attachments(&self, post_id: Uuid) -> Result<Vec<PostAttachment>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.*, m.original_filename, m.mime_type, m.file_size FROM post_attachments a
+            JOIN media_files m ON m.id = a.media_id
+            WHERE a.post_id = ?
+            ORDER BY a.created_at, m.original_filename
+            "#,
+        )
+        .bind(post_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list post attachments")?;
+
+        rows.iter()
+            .map(|row| self.row_to_post_attachment(row))
+            .collect()
+    }
+
+    /// Get an attachment by ID
+    pub async fn get_post_attachment(&self, id: Uuid) -> Result<Option<PostAttachment>> {
+        let row = sqlx::query(
+            r#"
+            SELECT a.*, m.original_filename, m.mime_type, m.file_size FROM post_attachments a
+            JOIN media_files m ON m.id = a.media_id
+            WHERE a.id = ?
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to get post attachment")?;
+
+        row.map(|row| self.row_to_post_attachment(&row)).transpose()
+    }
+
+    /// Detach a file from a post; the media file itself is kept
+    pub async fn remove_post_attachment(&self, post_id: Uuid, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM post_attachments WHERE id = ? AND post_id = ?")
+            .bind(id.to_string())
+            .bind(post_id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to remove post attachment")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Count one download of an attachment
+    pub async fn record_attachment_download(&self, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE post_attachments SET download_count = download_count + 1 WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to count attachment download")?;
+
+        Ok(())
+    }
+
+    fn row_to_post_attachment(&self, row: &SqliteRow) -> Result<PostAttachment> {
+        let id: String = row.try_get("id")?;
+        let post_id: String = row.try_get("post_id")?;
+        let media_id: String = row.try_get("media_id")?;
+        let created_at: String = row.try_get("created_at")?;
+        let id = Uuid::parse_str(&id).context("Invalid attachment ID")?;
+
+        Ok(PostAttachment {
+            id,
+            post_id: Uuid::parse_str(&post_id).context("Invalid post ID")?,
+            media_id: Uuid::parse_str(&media_id).context("Invalid media ID")?,
+            filename: row.try_get("original_filename")?,
+            mime_type: row.try_get("mime_type")?,
+            file_size: row.try_get::<i64, _>("file_size")? as u64,
+            download_url: PostAttachment::download_path(id),
+            download_count: row.try_get("download_count")?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .context("Invalid created_at format")?
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Record the outcome of cross-posting a post to one provider
+    pub async fn upsert_cross_post(&self, cross_post: &CrossPost) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO cross_posts (
+                post_id, provider, remote_id, remote_url, status, error, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(post_id, provider) DO UPDATE SET
+                remote_id = excluded.remote_id,
+                remote_url = excluded.remote_url,
+                status = excluded.status,
+                error = excluded.error,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(cross_post.post_id.to_string())
+        .bind(cross_post.provider.as_str())
+        .bind(&cross_post.remote_id)
+        .bind(&cross_post.remote_url)
+        .bind(cross_post.status.as_str())
+        .bind(&cross_post.error)
+        .bind(cross_post.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record cross-post")?;
+
+        Ok(())
+    }
+
+    /// Cross-posts of a post, by provider
+    pub async fn list_cross_posts(&self, post_id: Uuid) -> Result<Vec<CrossPost>> {
+        let rows = sqlx::query("SELECT * FROM cross_posts WHERE post_id = ? ORDER BY provider")
+            .bind(post_id.to_string())
+            .fetch_all(self.read_pool())
+            .await
+            .context("Failed to list cross-posts")?;
+
+        rows.iter().map(|row| self.row_to_cross_post(row)).collect()
+    }
+
+    fn row_to_cross_post(&self, row: &SqliteRow) -> Result<CrossPost> {
+        let post_id: String = row.try_get("post_id")?;
+        let provider: String = row.try_get("provider")?;
+        let status: String = row.try_get("status")?;
+        let updated_at: String = row.try_get("updated_at")?;
+
+        Ok(CrossPost {
+            post_id: Uuid::parse_str(&post_id).context("Invalid post ID")?,
+            provider: provider.parse()?,
+            remote_id: row.try_get("remote_id")?,
+            remote_url: row.try_get("remote_url")?,
+            status: status.parse()?,
+            error: row.try_get("error")?,
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .context("Invalid updated_at format")?
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Store a new admin session
+    pub async fn create_admin_session(&self, session: &AdminSession) -> Result<()> {
+        sqlx::query("INSERT INTO admin_sessions (id, created_at, expires_at) VALUES (?, ?, ?)")
+            .bind(&session.id)
+            .bind(session.created_at.to_rfc3339())
+            .bind(session.expires_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to create admin session")?;
+
+        Ok(())
+    }
+
+    /// Unexpired admin session with the given ID
+    pub async fn get_admin_session(&self, id: &str) -> Result<Option<AdminSession>> {
+        let row = sqlx::query("SELECT * FROM admin_sessions WHERE id = ? AND expires_at > ?")
+            .bind(id)
+            .bind(Utc::now().to_rfc3339())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to get admin session")?;
+
+        row.map(|row| {
+            let created_at: String = row.try_get("created_at")?;
+            let expires_at: String = row.try_get("expires_at")?;
+            Ok(AdminSession {
+                id: row.try_get("id")?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .context("Invalid created_at format")?
+                    .with_timezone(&Utc),
+                expires_at: DateTime::parse_from_rfc3339(&expires_at)
+                    .context("Invalid expires_at format")?
+                    .with_timezone(&Utc),
+            })
+        })
+        .transpose()
+    }
+
+    /// Delete an admin session; returns whether it existed
+    pub async fn delete_admin_session(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM admin_sessions WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete admin session")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete expired admin sessions, returning how many were removed
+    pub async fn delete_expired_admin_sessions(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM admin_sessions WHERE expires_at <= ?")
+            .bind(Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete expired admin sessions")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// TOTP settings of the admin login, if enrollment was started
+    pub async fn get_admin_totp(&self) -> Result<Option<AdminTotp>> {
+        let row = sqlx::query("SELECT * FROM admin_totp WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to get admin TOTP settings")?;
+
+        row.map(|row| {
+            let enabled_at: Option<String> = row.try_get("enabled_at")?;
+            Ok(AdminTotp {
+                secret: row.try_get("secret")?,
+                enabled: row.try_get("enabled")?,
+                enabled_at: enabled_at
+                    .map(|value| DateTime::parse_from_rfc3339(&value))
+                    .transpose()
+                    .context("Invalid enabled_at format")?
+                    .map(|value| value.with_timezone(&Utc)),
+            })
+        })
+        .transpose()
+    }
+
+    /// Store a new TOTP secret awaiting confirmation, replacing any earlier pending one
+    pub async fn save_pending_admin_totp(&self, secret: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO admin_totp (id, secret, enabled, last_used_step, created_at, enabled_at)
+            VALUES (1, ?, FALSE, NULL, ?, NULL)
+            ON CONFLICT(id) DO UPDATE SET
+                secret = excluded.secret,
+                enabled = FALSE,
+                last_used_step = NULL,
+                created_at = excluded.created_at,
+                enabled_at = NULL
+            "#,
+        )
+        .bind(secret)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save admin TOTP secret")?;
+
+        Ok(())
+    }
+
+    /// Turn on the pending TOTP secret together with its recovery codes
+    pub async fn enable_admin_totp(
+        &self,
+        step: i64,
+        recovery_code_hashes: &[String],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE admin_totp SET enabled = TRUE, enabled_at = ?, last_used_step = ? WHERE id = 1",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(step)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to enable admin TOTP")?;
+
+        Self::insert_admin_recovery_codes(&mut tx, recovery_code_hashes).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Replace all recovery codes
+    pub async fn replace_admin_recovery_codes(
+        &self,
+        recovery_code_hashes: &[String],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        Self::insert_admin_recovery_codes(&mut tx, recovery_code_hashes).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn insert_admin_recovery_codes(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        recovery_code_hashes: &[String],
+    ) -> Result<()> {
+        sqlx::query("DELETE FROM admin_recovery_codes")
+            .execute(&mut **tx)
+            .await
+            .context("Failed to delete admin recovery codes")?;
+
+        let now = Utc::now().to_rfc3339();
+        for code_hash in recovery_code_hashes {
+            sqlx::query("INSERT INTO admin_recovery_codes (code_hash, created_at) VALUES (?, ?)")
+                .bind(code_hash)
+                .bind(&now)
+                .execute(&mut **tx)
+                .await
+                .context("Failed to store admin recovery code")?;
+        }
+
+        Ok(())
+    }
+
+    /// Record an accepted TOTP time step; false if it (or a later one) was already used
+    pub async fn claim_admin_totp_step(&self, step: i64) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE admin_totp SET last_used_step = ?
+            WHERE id = 1 AND enabled AND (last_used_step IS NULL OR last_used_step < ?)
+            "#,
+        )
+        .bind(step)
+        .bind(step)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record admin TOTP step")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark an unused recovery code as used; false if there is none with this hash
+    pub async fn use_admin_recovery_code(&self, code_hash: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE admin_recovery_codes SET used_at = ? WHERE code_hash = ? AND used_at IS NULL",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(code_hash)
+        .execute(&self.pool)
+        .await
+        .context("Failed to use admin recovery code")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Number of recovery codes not used yet
+    pub async fn count_admin_recovery_codes(&self) -> Result<i64> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM admin_recovery_codes WHERE used_at IS NULL")
+                .fetch_one(&self.pool)
+                .await
+                .context("Failed to count admin recovery codes")?;
+
+        Ok(count)
+    }
 
-        sqlx::query("INSERT OR IGNORE INTO posts_media (post_id, media_id) VALUES (?, ?)")
-            .bind(post_id.to_string())
-            .bind(media_id.to_string())
-            .execute(&self.pool)
+    /// Remove the TOTP secret and recovery codes
+    pub async fn delete_admin_totp(&self) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM admin_recovery_codes")
+            .execute(&mut *tx)
             .await
-            .context("Failed to associate media with post")?;
+            .context("Failed to delete admin recovery codes")?;
+        sqlx::query("DELETE FROM admin_totp")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete admin TOTP settings")?;
+        tx.commit().await?;
 
         Ok(())
     }
 
-    /// Get media files associated with a post
-    #[allow(dead_code)]
-    pub async fn get_post_media(&self, post_id: Uuid) -> Result<Vec<MediaFile>> {
-        debug!("Getting media files for post: {}", post_id);
+    /// List published public posts of a series, newest first, for the series feed
+    pub async fn list_series_posts(&self, series: &str, limit: i64) -> Result<Vec<Post>> {
+        debug!("Listing posts of series {}", series);
 
-        let rows = sqlx::query_as::<_, MediaFileRow>(
+        // The LIKE narrows the scan; the frontmatter key is checked exactly below
+        let rows = sqlx::query(
             r#"
-            SELECT m.* FROM media_files m
-            JOIN posts_media pm ON m.id = pm.media_id
-            WHERE pm.post_id = ?
-            ORDER BY m.uploaded_at DESC
+            SELECT * FROM posts
+            WHERE published = true AND visibility = 'public' AND password_hash IS NULL
+              AND custom_frontmatter LIKE ?
+            ORDER BY COALESCE(published_at, created_at) DESC
             "#,
         )
-        .bind(post_id.to_string())
+        .bind(format!("%{}%", series))
         .fetch_all(&self.pool)
         .await
-        .context("Failed to fetch post media")?;
+        .context("Failed to list series posts")?;
 
-        let media_files = rows
-            .into_iter()
-            .map(|row| -> Result<MediaFile> {
-                Ok(MediaFile {
-                    id: Uuid::parse_str(&row.id).context("Invalid UUID in database")?,
-                    filename: row.filename,
-                    original_filename: row.original_filename,
-                    dropbox_path: row.dropbox_path,
-                    url: row.url,
-                    file_size: row.file_size as u64,
-                    mime_type: row.mime_type,
-                    width: row.width.map(|w| w as u32),
-                    height: row.height.map(|h| h as u32),
-                    uploaded_at: DateTime::parse_from_rfc3339(&row.uploaded_at)
-                        .context("Invalid uploaded_at timestamp")?
-                        .with_timezone(&Utc),
-                    thumbnail_url: row.thumbnail_url,
-                    alt_text: row.alt_text,
-                    caption: row.caption,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let mut posts = Vec::new();
+        for row in &rows {
+            let post = self.row_to_post(row)?;
+            if post.series().as_deref() == Some(series) {
+                posts.push(post);
+            }
+            if posts.len() as i64 >= limit {
+                break;
+            }
+        }
+        Ok(posts)
+    }
 
-        debug!("Found {} media files for post", media_files.len());
-        Ok(media_files)
+    /// List published posts with audio, newest first, for the podcast feed
+    pub async fn list_podcast_episodes(&self, limit: i64) -> Result<Vec<(Post, AudioEnclosure)>> {
+        debug!("Listing podcast episodes");
+
+        let rows = sqlx::query(
+            r#"
+            SELECT p.*, a.post_id, a.media_id, a.mime_type, a.duration_seconds,
+                   a.updated_at AS audio_updated_at, m.url, m.file_size
+            FROM posts p
+            JOIN post_audio a ON a.post_id = p.id
+            JOIN media_files m ON m.id = a.media_id
+            WHERE p.published = true AND p.visibility = 'public' AND p.password_hash IS NULL
+            ORDER BY COALESCE(p.published_at, p.created_at) DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list podcast episodes")?;
+
+        rows.iter()
+            .map(|row| Ok((self.row_to_post(row)?, self.row_to_audio_enclosure(row)?)))
+            .collect()
+    }
+
+    fn row_to_audio_enclosure(&self, row: &SqliteRow) -> Result<AudioEnclosure> {
+        let post_id: String = row.try_get("post_id")?;
+        let media_id: String = row.try_get("media_id")?;
+        let updated_at: String = row
+            .try_get("audio_updated_at")
+            .or_else(|_| row.try_get("updated_at"))?;
+
+        Ok(AudioEnclosure {
+            post_id: Uuid::parse_str(&post_id).context("Invalid post ID")?,
+            media_id: Uuid::parse_str(&media_id).context("Invalid media ID")?,
+            url: row.try_get("url")?,
+            file_size: row.try_get::<i64, _>("file_size")? as u64,
+            mime_type: row.try_get("mime_type")?,
+            duration_seconds: row.try_get("duration_seconds")?,
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .context("Invalid updated_at format")?
+                .with_timezone(&Utc),
+        })
     }
 
     /// Helper method to convert SqliteRow to MediaFile
@@ -796,6 +3662,14 @@ impl DatabaseService {
             thumbnail_url: row.try_get("thumbnail_url")?,
             alt_text: row.try_get("alt_text")?,
             caption: row.try_get("caption")?,
+            content_hash: row.try_get("content_hash")?,
+            duration_seconds: row.try_get("duration_seconds")?,
+            alt_text_suggestion: row.try_get("alt_text_suggestion")?,
+            collection: row.try_get("collection")?,
+            focal_point: match (row.try_get("focal_x")?, row.try_get("focal_y")?) {
+                (Some(x), Some(y)) => Some(FocalPoint { x, y }),
+                _ => None,
+            },
         })
     }
 
@@ -860,6 +3734,65 @@ impl DatabaseService {
         })
     }
 
+    /// Store a version exported from another post or instance under `post_id`,
+    /// keeping its timestamps; false when the post already has that version
+    pub async fn import_post_version(
+        &self,
+        post_id: Uuid,
+        version: &crate::models::PostVersion,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO post_versions (
+                post_id, version, title, content, html_content, excerpt, category, tags,
+                metadata, change_summary, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(post_id.to_string())
+        .bind(version.version)
+        .bind(&version.title)
+        .bind(&version.content)
+        .bind(&version.html_content)
+        .bind(&version.excerpt)
+        .bind(&version.category)
+        .bind(serde_json::to_string(&version.tags)?)
+        .bind(
+            version
+                .metadata
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?,
+        )
+        .bind(&version.change_summary)
+        .bind(version.created_at.to_rfc3339())
+        .bind(&version.created_by)
+        .execute(&self.pool)
+        .await
+        .context("Failed to import post version")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Raise the version number of a post to at least `version`, so new
+    /// snapshots don't collide with imported ones; returns the number it has
+    pub async fn advance_post_version(&self, post_id: Uuid, version: i32) -> Result<i32> {
+        sqlx::query("UPDATE posts SET version = ? WHERE id = ? AND version < ?")
+            .bind(version)
+            .bind(post_id.to_string())
+            .bind(version)
+            .execute(&self.pool)
+            .await
+            .context("Failed to advance post version")?;
+
+        let version = sqlx::query_scalar("SELECT version FROM posts WHERE id = ?")
+            .bind(post_id.to_string())
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to read post version")?;
+        Ok(version)
+    }
+
     /// Get a specific version of a post
     pub async fn get_post_version(
         &self,
@@ -928,17 +3861,43 @@ impl DatabaseService {
         Ok(versions)
     }
 
-    /// Delete old versions, keeping only the most recent N versions
+    /// Latest versions across all posts with the slug of their post, newest first
+    pub async fn list_recent_post_versions(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(String, crate::models::PostVersion)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.*, p.slug AS post_slug FROM post_versions v
+            JOIN posts p ON p.id = v.post_id
+            ORDER BY v.created_at DESC LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch recent post versions")?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("post_slug")?, self.row_to_post_version(row)?)))
+            .collect()
+    }
+
+    /// Delete old versions, keeping only the most recent N versions, the
+    /// published version and, when `keep_since` is set, any version saved at
+    /// or after it
     pub async fn cleanup_old_versions(
         &self,
         post_id: uuid::Uuid,
         keep_versions: i32,
+        keep_since: Option<DateTime<Utc>>,
     ) -> Result<usize> {
         debug!(
-            "Cleaning up old versions for post {}, keeping {} versions",
-            post_id, keep_versions
+            "Cleaning up old versions for post {}, keeping {} versions (and those since {:?})",
+            post_id, keep_versions, keep_since
         );
 
+        let keep_since = keep_since.map(|since| since.to_rfc3339());
         let result = sqlx::query(
             r#"
             DELETE FROM post_versions 
@@ -949,11 +3908,19 @@ impl DatabaseService {
                 ORDER BY version DESC 
                 LIMIT ?
             )
+            AND version NOT IN (
+                SELECT published_version FROM posts
+                WHERE id = ? AND published_version IS NOT NULL
+            )
+            AND (? IS NULL OR created_at < ?)
             "#,
         )
         .bind(post_id.to_string())
         .bind(post_id.to_string())
         .bind(keep_versions)
+        .bind(post_id.to_string())
+        .bind(&keep_since)
+        .bind(&keep_since)
         .execute(&self.pool)
         .await
         .context("Failed to cleanup old versions")?;
@@ -963,6 +3930,21 @@ impl DatabaseService {
         Ok(deleted_count)
     }
 
+    /// IDs of every post with at least one stored version
+    pub async fn list_versioned_post_ids(&self) -> Result<Vec<Uuid>> {
+        let rows = sqlx::query("SELECT DISTINCT post_id FROM post_versions")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list versioned posts")?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("post_id")?;
+                Uuid::parse_str(&id).context("Invalid post ID in post_versions")
+            })
+            .collect()
+    }
+
     /// Helper method to convert SqliteRow to PostVersion
     fn row_to_post_version(
         &self,
@@ -1003,6 +3985,7 @@ impl DatabaseService {
         let now = Utc::now();
         let header_style_json = serde_json::to_string(&theme.header_style)?;
         let footer_style_json = serde_json::to_string(&theme.footer_style)?;
+        let mut tx = self.pool.begin().await?;
 
         let theme_id = sqlx::query(
             r#"
@@ -1033,11 +4016,13 @@ impl DatabaseService {
         .bind(footer_style_json)
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .context("Failed to insert theme")?;
 
         let id = theme_id.last_insert_rowid();
+        Self::replace_category_styles(&mut tx, &theme.name, &theme.category_styles).await?;
+        tx.commit().await?;
 
         Ok(ThemeSettings {
             id: Some(id),
@@ -1058,7 +4043,9 @@ impl DatabaseService {
             .context("Failed to get theme by name")?;
 
         if let Some(row) = row {
-            let theme = self.row_to_theme(&row)?;
+            let mut themes = [self.row_to_theme(&row)?];
+            self.load_category_styles(&mut themes).await?;
+            let [theme] = themes;
             Ok(Some(theme))
         } else {
             Ok(None)
@@ -1075,7 +4062,9 @@ impl DatabaseService {
             .context("Failed to get active theme")?;
 
         if let Some(row) = row {
-            let theme = self.row_to_theme(&row)?;
+            let mut themes = [self.row_to_theme(&row)?];
+            self.load_category_styles(&mut themes).await?;
+            let [theme] = themes;
             Ok(Some(theme))
         } else {
             Ok(None)
@@ -1125,12 +4114,16 @@ impl DatabaseService {
             custom_css: request.custom_css.or(existing_theme.custom_css),
             header_style: request.header_style.unwrap_or(existing_theme.header_style),
             footer_style: request.footer_style.unwrap_or(existing_theme.footer_style),
+            category_styles: request
+                .category_styles
+                .unwrap_or(existing_theme.category_styles),
             updated_at: Some(now),
             ..existing_theme
         };
 
         let header_style_json = serde_json::to_string(&updated_theme.header_style)?;
         let footer_style_json = serde_json::to_string(&updated_theme.footer_style)?;
+        let mut tx = self.pool.begin().await?;
 
         sqlx::query(
             r#"
@@ -1159,10 +4152,13 @@ impl DatabaseService {
         .bind(footer_style_json)
         .bind(now.to_rfc3339())
         .bind(name)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .context("Failed to update theme")?;
 
+        Self::replace_category_styles(&mut tx, name, &updated_theme.category_styles).await?;
+        tx.commit().await?;
+
         Ok(Some(updated_theme))
     }
 
@@ -1170,11 +4166,14 @@ impl DatabaseService {
     pub async fn delete_theme(&self, name: &str) -> Result<bool> {
         debug!("Deleting theme: {}", name);
 
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query("DELETE FROM themes WHERE name = ?")
             .bind(name)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .context("Failed to delete theme")?;
+        Self::replace_category_styles(&mut tx, name, &[]).await?;
+        tx.commit().await?;
 
         Ok(result.rows_affected() > 0)
     }
@@ -1258,15 +4257,69 @@ impl DatabaseService {
             .await
             .context("Failed to list themes")?;
 
-        let themes = rows
+        let mut themes = rows
             .iter()
             .map(|row| self.row_to_theme(row))
             .collect::<Result<Vec<_>>>()?;
+        self.load_category_styles(&mut themes).await?;
 
         debug!("Found {} themes", themes.len());
         Ok(themes)
     }
 
+    /// Attach the category styles of each theme
+    async fn load_category_styles(&self, themes: &mut [ThemeSettings]) -> Result<()> {
+        let rows = sqlx::query(
+            "SELECT theme_name, category, accent_color, hero_image FROM category_styles ORDER BY category",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load category styles")?;
+
+        let mut styles: HashMap<String, Vec<CategoryStyle>> = HashMap::new();
+        for row in rows {
+            styles
+                .entry(row.try_get("theme_name")?)
+                .or_default()
+                .push(CategoryStyle {
+                    category: row.try_get("category")?,
+                    accent_color: row.try_get("accent_color")?,
+                    hero_image: row.try_get("hero_image")?,
+                });
+        }
+        for theme in themes {
+            theme.category_styles = styles.remove(&theme.name).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// Replace the category styles of a theme inside the caller's transaction
+    async fn replace_category_styles(
+        conn: &mut SqliteConnection,
+        theme_name: &str,
+        styles: &[CategoryStyle],
+    ) -> Result<()> {
+        sqlx::query("DELETE FROM category_styles WHERE theme_name = ?")
+            .bind(theme_name)
+            .execute(&mut *conn)
+            .await
+            .context("Failed to clear category styles")?;
+
+        for style in styles {
+            sqlx::query(
+                "INSERT INTO category_styles (theme_name, category, accent_color, hero_image) VALUES (?, ?, ?, ?)",
+            )
+            .bind(theme_name)
+            .bind(&style.category)
+            .bind(&style.accent_color)
+            .bind(&style.hero_image)
+            .execute(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to save style of category {}", style.category))?;
+        }
+        Ok(())
+    }
+
     /// Helper method to convert SqliteRow to ThemeSettings
     fn row_to_theme(&self, row: &SqliteRow) -> Result<ThemeSettings> {
         let layout_str: String = row.try_get("layout")?;
@@ -1302,14 +4355,16 @@ impl DatabaseService {
             custom_css: row.try_get("custom_css")?,
             header_style,
             footer_style,
-            created_at: DateTime::parse_from_rfc3339(row.try_get("created_at")?)
-                .context("Invalid created_at timestamp")?
-                .with_timezone(&Utc)
-                .into(),
-            updated_at: DateTime::parse_from_rfc3339(row.try_get("updated_at")?)
-                .context("Invalid updated_at timestamp")?
-                .with_timezone(&Utc)
-                .into(),
+            // Filled in by `load_category_styles`
+            category_styles: Vec::new(),
+            created_at: Some(
+                parse_seeded_timestamp(row.try_get("created_at")?)
+                    .context("Invalid created_at timestamp")?,
+            ),
+            updated_at: Some(
+                parse_seeded_timestamp(row.try_get("updated_at")?)
+                    .context("Invalid updated_at timestamp")?,
+            ),
         })
     }
 
@@ -1319,10 +4374,22 @@ impl DatabaseService {
     pub async fn get_site_config(&self) -> Result<Option<SiteConfig>> {
         debug!("Getting site configuration");
 
-        let row = sqlx::query("SELECT * FROM site_config LIMIT 1")
-            .fetch_optional(&self.pool)
-            .await
-            .context("Failed to get site config")?;
+        // Name the columns so that a pooled connection still holding the
+        // schema from before the latest migrations re-reads it instead of
+        // preparing a statement without the new columns
+        let row = sqlx::query(
+            r#"
+            SELECT id, site_title, site_description, site_logo, favicon,
+                   author_name, author_email, author_bio, social_links,
+                   google_analytics_id, google_fonts, locale, timezone,
+                   created_at, updated_at
+            FROM site_config
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to get site config")?;
 
         if let Some(row) = row {
             let config = self.row_to_site_config(&row)?;
@@ -1345,9 +4412,9 @@ impl DatabaseService {
             INSERT INTO site_config (
                 site_title, site_description, site_logo, favicon,
                 author_name, author_email, author_bio,
-                social_links, google_analytics_id, google_fonts,
+                social_links, google_analytics_id, google_fonts, locale, timezone,
                 created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&config.site_title)
@@ -1360,6 +4427,8 @@ impl DatabaseService {
         .bind(social_links_json)
         .bind(&config.google_analytics_id)
         .bind(google_fonts_json)
+        .bind(config.locale.as_str())
+        .bind(config.timezone.name())
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
@@ -1390,7 +4459,7 @@ impl DatabaseService {
                 site_title = ?, site_description = ?, site_logo = ?, favicon = ?,
                 author_name = ?, author_email = ?, author_bio = ?,
                 social_links = ?, google_analytics_id = ?, google_fonts = ?,
-                updated_at = ?
+                locale = ?, timezone = ?, updated_at = ?
             WHERE id = (SELECT MIN(id) FROM site_config)
             "#,
         )
@@ -1404,6 +4473,8 @@ impl DatabaseService {
         .bind(social_links_json)
         .bind(&config.google_analytics_id)
         .bind(google_fonts_json)
+        .bind(config.locale.as_str())
+        .bind(config.timezone.name())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await
@@ -1415,6 +4486,161 @@ impl DatabaseService {
         })
     }
 
+    /// Restore site configuration and themes in one transaction
+    ///
+    /// Themes are matched by name; existing themes not in `themes` are kept. The
+    /// active theme only changes when one of the imported themes is active.
+    /// Returns the number of created and updated themes.
+    pub async fn import_settings(
+        &self,
+        config: &SiteConfig,
+        themes: &[ThemeSettings],
+    ) -> Result<(usize, usize)> {
+        debug!("Importing site configuration and {} themes", themes.len());
+
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        let social_links_json = serde_json::to_string(&config.social_links)?;
+        let google_fonts_json = serde_json::to_string(&config.google_fonts)?;
+        let updated = sqlx::query(
+            r#"
+            UPDATE site_config SET
+                site_title = ?, site_description = ?, site_logo = ?, favicon = ?,
+                author_name = ?, author_email = ?, author_bio = ?,
+                social_links = ?, google_analytics_id = ?, google_fonts = ?,
+                locale = ?, timezone = ?, updated_at = ?
+            WHERE id = (SELECT MIN(id) FROM site_config)
+            "#,
+        )
+        .bind(&config.site_title)
+        .bind(&config.site_description)
+        .bind(&config.site_logo)
+        .bind(&config.favicon)
+        .bind(&config.author_name)
+        .bind(&config.author_email)
+        .bind(&config.author_bio)
+        .bind(&social_links_json)
+        .bind(&config.google_analytics_id)
+        .bind(&google_fonts_json)
+        .bind(config.locale.as_str())
+        .bind(config.timezone.name())
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update site config")?;
+
+        if updated.rows_affected() == 0 {
+            sqlx::query(
+                r#"
+                INSERT INTO site_config (
+                    site_title, site_description, site_logo, favicon,
+                    author_name, author_email, author_bio,
+                    social_links, google_analytics_id, google_fonts, locale, timezone,
+                    created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&config.site_title)
+            .bind(&config.site_description)
+            .bind(&config.site_logo)
+            .bind(&config.favicon)
+            .bind(&config.author_name)
+            .bind(&config.author_email)
+            .bind(&config.author_bio)
+            .bind(&social_links_json)
+            .bind(&config.google_analytics_id)
+            .bind(&google_fonts_json)
+            .bind(config.locale.as_str())
+            .bind(config.timezone.name())
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert site config")?;
+        }
+
+        // Only one theme may be active at a time (unique partial index)
+        let activates_theme = themes.iter().any(|theme| theme.is_active);
+        if activates_theme {
+            sqlx::query("UPDATE themes SET is_active = FALSE WHERE is_active = TRUE")
+                .execute(&mut *tx)
+                .await
+                .context("Failed to deactivate themes")?;
+        }
+
+        let mut created = 0;
+        let mut updated = 0;
+        for theme in themes {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM themes WHERE name = ?)")
+                    .bind(&theme.name)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .context("Failed to look up theme")?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO themes (
+                    name, display_name, description, is_active,
+                    primary_color, secondary_color, background_color, text_color, accent_color,
+                    font_family, heading_font, font_size_base, layout, dark_mode_enabled,
+                    custom_css, header_style, footer_style, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(name) DO UPDATE SET
+                    display_name = excluded.display_name, description = excluded.description,
+                    is_active = CASE WHEN ? THEN excluded.is_active ELSE themes.is_active END,
+                    primary_color = excluded.primary_color,
+                    secondary_color = excluded.secondary_color,
+                    background_color = excluded.background_color,
+                    text_color = excluded.text_color, accent_color = excluded.accent_color,
+                    font_family = excluded.font_family, heading_font = excluded.heading_font,
+                    font_size_base = excluded.font_size_base, layout = excluded.layout,
+                    dark_mode_enabled = excluded.dark_mode_enabled,
+                    custom_css = excluded.custom_css, header_style = excluded.header_style,
+                    footer_style = excluded.footer_style, updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(&theme.name)
+            .bind(&theme.display_name)
+            .bind(&theme.description)
+            .bind(activates_theme && theme.is_active)
+            .bind(&theme.primary_color)
+            .bind(&theme.secondary_color)
+            .bind(&theme.background_color)
+            .bind(&theme.text_color)
+            .bind(&theme.accent_color)
+            .bind(&theme.font_family)
+            .bind(&theme.heading_font)
+            .bind(&theme.font_size_base)
+            .bind(format!("{:?}", theme.layout).to_lowercase())
+            .bind(theme.dark_mode_enabled)
+            .bind(&theme.custom_css)
+            .bind(serde_json::to_string(&theme.header_style)?)
+            .bind(serde_json::to_string(&theme.footer_style)?)
+            .bind(&now)
+            .bind(&now)
+            .bind(activates_theme)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to import theme {}", theme.name))?;
+            Self::replace_category_styles(&mut tx, &theme.name, &theme.category_styles).await?;
+
+            if exists {
+                updated += 1;
+            } else {
+                created += 1;
+            }
+        }
+
+        tx.commit().await?;
+        info!(
+            "Imported site configuration: {} themes created, {} updated",
+            created, updated
+        );
+        Ok((created, updated))
+    }
+
     /// Helper method to convert SqliteRow to SiteConfig
     fn row_to_site_config(&self, row: &SqliteRow) -> Result<SiteConfig> {
         let social_links_json: String = row.try_get("social_links")?;
@@ -1435,14 +4661,266 @@ impl DatabaseService {
             social_links,
             google_analytics_id: row.try_get("google_analytics_id")?,
             google_fonts,
-            created_at: DateTime::parse_from_rfc3339(row.try_get("created_at")?)
-                .context("Invalid created_at timestamp")?
-                .with_timezone(&Utc)
-                .into(),
-            updated_at: DateTime::parse_from_rfc3339(row.try_get("updated_at")?)
-                .context("Invalid updated_at timestamp")?
-                .with_timezone(&Utc)
-                .into(),
+            locale: row
+                .try_get::<String, _>("locale")?
+                .parse()
+                .unwrap_or_default(),
+            timezone: row
+                .try_get::<String, _>("timezone")?
+                .parse()
+                .unwrap_or(Tz::UTC),
+            created_at: Some(
+                parse_seeded_timestamp(row.try_get("created_at")?)
+                    .context("Invalid created_at timestamp")?,
+            ),
+            updated_at: Some(
+                parse_seeded_timestamp(row.try_get("updated_at")?)
+                    .context("Invalid updated_at timestamp")?,
+            ),
+        })
+    }
+
+    // Announcement methods
+
+    /// List announcements, latest start first
+    pub async fn list_announcements(&self) -> Result<Vec<Announcement>> {
+        let rows = sqlx::query(
+            "SELECT * FROM announcements ORDER BY COALESCE(starts_at, created_at) DESC, id DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list announcements")?;
+
+        rows.iter()
+            .map(|row| self.row_to_announcement(row))
+            .collect()
+    }
+
+    /// Get an announcement by ID
+    pub async fn get_announcement(&self, id: i64) -> Result<Option<Announcement>> {
+        let row = sqlx::query("SELECT * FROM announcements WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to get announcement")?;
+
+        row.map(|row| self.row_to_announcement(&row)).transpose()
+    }
+
+    /// Create an announcement
+    pub async fn create_announcement(&self, request: &AnnouncementRequest) -> Result<Announcement> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO announcements (
+                message, link, style, starts_at, ends_at, dismissible, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&request.message)
+        .bind(&request.link)
+        .bind(request.style.as_str())
+        .bind(request.starts_at.map(|at| at.to_rfc3339()))
+        .bind(request.ends_at.map(|at| at.to_rfc3339()))
+        .bind(request.dismissible)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .context("Failed to create announcement")?;
+
+        let id = result.last_insert_rowid();
+        info!("Created announcement {}", id);
+        self.get_announcement(id)
+            .await?
+            .context("Created announcement not found")
+    }
+
+    /// Replace the fields of an announcement, returning `None` if it doesn't exist
+    pub async fn update_announcement(
+        &self,
+        id: i64,
+        request: &AnnouncementRequest,
+    ) -> Result<Option<Announcement>> {
+        let result = sqlx::query(
+            r#"
+            UPDATE announcements SET
+                message = ?, link = ?, style = ?, starts_at = ?, ends_at = ?,
+                dismissible = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&request.message)
+        .bind(&request.link)
+        .bind(request.style.as_str())
+        .bind(request.starts_at.map(|at| at.to_rfc3339()))
+        .bind(request.ends_at.map(|at| at.to_rfc3339()))
+        .bind(request.dismissible)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update announcement")?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.get_announcement(id).await
+    }
+
+    /// Delete an announcement, returning whether it existed
+    pub async fn delete_announcement(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM announcements WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete announcement")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_announcement(&self, row: &SqliteRow) -> Result<Announcement> {
+        let parse_time = |value: String| -> Result<DateTime<Utc>> {
+            Ok(DateTime::parse_from_rfc3339(&value)
+                .context("Invalid announcement timestamp")?
+                .with_timezone(&Utc))
+        };
+        let style: String = row.try_get("style")?;
+        let starts_at: Option<String> = row.try_get("starts_at")?;
+        let ends_at: Option<String> = row.try_get("ends_at")?;
+
+        Ok(Announcement {
+            id: row.try_get("id")?,
+            message: row.try_get("message")?,
+            link: row.try_get("link")?,
+            style: style.parse()?,
+            starts_at: starts_at.map(parse_time).transpose()?,
+            ends_at: ends_at.map(parse_time).transpose()?,
+            dismissible: row.try_get("dismissible")?,
+            created_at: parse_time(row.try_get("created_at")?)?,
+            updated_at: parse_time(row.try_get("updated_at")?)?,
+        })
+    }
+
+    // Link check methods
+
+    /// Replace the recorded link check results of a post
+    pub async fn replace_link_checks(
+        &self,
+        post_id: Uuid,
+        checks: &[crate::models::CreateLinkCheck],
+    ) -> Result<()> {
+        debug!("Saving {} link checks for post {}", checks.len(), post_id);
+
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query("DELETE FROM link_checks WHERE post_id = ?")
+            .bind(post_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear link checks")?;
+
+        for check in checks {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO link_checks (
+                    post_id, url, link_type, status_code, is_broken, error, checked_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(post_id.to_string())
+            .bind(&check.url)
+            .bind(check.link_type.as_str())
+            .bind(check.status_code.map(i64::from))
+            .bind(check.is_broken)
+            .bind(&check.error)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert link check")?;
+        }
+
+        tx.commit().await.context("Failed to commit link checks")?;
+
+        Ok(())
+    }
+
+    /// List broken links grouped by post
+    pub async fn get_broken_link_report(&self) -> Result<Vec<crate::models::BrokenLinkReport>> {
+        debug!("Building broken link report");
+
+        let rows = sqlx::query(
+            r#"
+            SELECT lc.*, p.slug AS post_slug, p.title AS post_title
+            FROM link_checks lc
+            JOIN posts p ON p.id = lc.post_id
+            WHERE lc.is_broken = TRUE
+            ORDER BY p.title ASC, lc.url ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch broken links")?;
+
+        let mut reports: Vec<crate::models::BrokenLinkReport> = Vec::new();
+        for row in &rows {
+            let check = self.row_to_link_check(row)?;
+            match reports.last_mut() {
+                Some(report) if report.post_id == check.post_id => report.broken_links.push(check),
+                _ => reports.push(crate::models::BrokenLinkReport {
+                    post_id: check.post_id,
+                    post_slug: row.try_get("post_slug")?,
+                    post_title: row.try_get("post_title")?,
+                    broken_links: vec![check],
+                }),
+            }
+        }
+
+        Ok(reports)
+    }
+
+    /// Helper method to convert SqliteRow to LinkCheck
+    fn row_to_link_check(&self, row: &SqliteRow) -> Result<crate::models::LinkCheck> {
+        let link_type: String = row.try_get("link_type")?;
+        let status_code: Option<i64> = row.try_get("status_code")?;
+
+        Ok(crate::models::LinkCheck {
+            id: row.try_get("id")?,
+            post_id: Uuid::parse_str(row.try_get("post_id")?)
+                .context("Invalid UUID in database")?,
+            url: row.try_get("url")?,
+            link_type: link_type.parse()?,
+            status_code: status_code.and_then(|code| u16::try_from(code).ok()),
+            is_broken: row.try_get("is_broken")?,
+            error: row.try_get("error")?,
+            checked_at: DateTime::parse_from_rfc3339(row.try_get("checked_at")?)
+                .context("Invalid checked_at timestamp")?
+                .with_timezone(&Utc),
         })
     }
 }
+
+/// Parse a timestamp written either by the service (RFC 3339) or by the
+/// `datetime('now')` defaults used when migrations seed rows
+fn parse_seeded_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    Ok(chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")?.and_utc())
+}
+
+/// FTS5 query matching every word of a media search as a prefix, or `None`
+/// when the search has no words; quoting keeps FTS syntax out of user input
+fn media_search_query(search: &str) -> Option<String> {
+    let terms: Vec<String> = search
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
@@ -0,0 +1,251 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use reqwest::Client;
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::models::{DigestPost, Locale, MonthlyDigest, SiteConfig};
+use crate::services::calendar::parse_month;
+use crate::services::i18n::translate;
+use crate::services::{DatabaseService, JobQueue};
+
+/// Job kind of queued digest deliveries
+pub const DIGEST_JOB: &str = "monthly_digest";
+
+/// Compiles monthly blog activity and posts it to a Slack webhook
+///
+/// Covers new and updated posts, media uploads and broken links. Page views
+/// and comments aren't tracked by the blog, so they aren't part of it.
+#[derive(Clone)]
+pub struct DigestService {
+    database: DatabaseService,
+    client: Client,
+    site_url: String,
+    slack_webhook_url: Option<String>,
+}
+
+impl DigestService {
+    pub fn new(
+        database: DatabaseService,
+        site_url: String,
+        slack_webhook_url: Option<String>,
+    ) -> Self {
+        Self {
+            database,
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            site_url: site_url.trim_end_matches('/').to_string(),
+            slack_webhook_url,
+        }
+    }
+
+    async fn site_config(&self) -> Result<SiteConfig> {
+        Ok(self.database.get_site_config().await?.unwrap_or_default())
+    }
+
+    /// Digest of a `YYYY-MM` month, or of the previous month when `None`
+    pub async fn compile(&self, month: Option<&str>) -> Result<MonthlyDigest> {
+        let site = self.site_config().await?;
+        let first_day = match month {
+            Some(month) => parse_month(month)?,
+            None => previous_month(Utc::now().with_timezone(&site.timezone).date_naive()),
+        };
+        let next_first_day = first_day
+            .checked_add_months(chrono::Months::new(1))
+            .context("Invalid digest month")?;
+        let start = start_of_day(first_day, site.timezone);
+        let end = start_of_day(next_first_day, site.timezone);
+
+        let new_posts = self
+            .database
+            .list_posts_for_calendar(start, end)
+            .await?
+            .into_iter()
+            .filter(|post| post.published)
+            .map(|post| DigestPost {
+                url: format!("{}{}", self.site_url, post.get_url_path()),
+                title: post.title,
+            })
+            .collect();
+        let broken_links = self
+            .database
+            .get_broken_link_report()
+            .await?
+            .iter()
+            .map(|report| report.broken_links.len())
+            .sum();
+
+        Ok(MonthlyDigest {
+            month: first_day.format("%Y-%m").to_string(),
+            site_title: site.site_title,
+            new_posts,
+            updated_posts: self
+                .database
+                .count_posts_updated_between(start, end)
+                .await?,
+            media_uploads: self
+                .database
+                .count_media_uploaded_between(start, end)
+                .await?,
+            broken_links,
+            total_published: self.database.get_post_stats().await?.published_posts,
+        })
+    }
+
+    /// Compile the digest of a month and post it to Slack
+    pub async fn send(&self, month: Option<&str>) -> Result<MonthlyDigest> {
+        let url = self
+            .slack_webhook_url
+            .as_deref()
+            .context("No digest webhook is configured")?;
+        let digest = self.compile(month).await?;
+        let locale = self.site_config().await?.locale;
+
+        self.client
+            .post(url)
+            .json(&serde_json::json!({ "text": format_digest(&digest, locale) }))
+            .send()
+            .await
+            .context("Failed to reach the Slack webhook")?
+            .error_for_status()
+            .context("Slack webhook rejected the digest")?;
+
+        info!("Sent the digest for {}", digest.month);
+        Ok(digest)
+    }
+
+    /// Queue the previous month's digest at the start of every month
+    pub fn spawn_monthly(&self, jobs: JobQueue) {
+        if self.slack_webhook_url.is_none() {
+            return;
+        }
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let timezone = match service.site_config().await {
+                    Ok(site) => site.timezone,
+                    Err(e) => {
+                        error!("Failed to load the site timezone for the digest: {}", e);
+                        Tz::UTC
+                    }
+                };
+                let today = Utc::now().with_timezone(&timezone).date_naive();
+                let next_first_day = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)
+                    .and_then(|day| day.checked_add_months(chrono::Months::new(1)))
+                    .unwrap_or(today);
+                let wait = (start_of_day(next_first_day, timezone) - Utc::now())
+                    .to_std()
+                    .unwrap_or_default();
+                info!("Next monthly digest due on {}", next_first_day);
+                tokio::time::sleep(wait).await;
+
+                let month = previous_month(next_first_day).format("%Y-%m").to_string();
+                if let Err(e) = jobs
+                    .enqueue(DIGEST_JOB, &serde_json::json!({ "month": month }))
+                    .await
+                {
+                    error!("Failed to queue the monthly digest: {}", e);
+                }
+                // Don't fire twice when the clock lands right on the boundary
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        });
+    }
+}
+
+/// First day of the month before the one containing `day`
+fn previous_month(day: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(day.year(), day.month(), 1)
+        .and_then(|first| first.checked_sub_months(chrono::Months::new(1)))
+        .unwrap_or(day)
+}
+
+/// UTC instant of local midnight starting `day`
+fn start_of_day(day: NaiveDate, timezone: Tz) -> chrono::DateTime<Utc> {
+    let midnight = day.and_hms_opt(0, 0, 0).unwrap_or_default();
+    timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Slack message (mrkdwn) for a digest
+pub fn format_digest(digest: &MonthlyDigest, locale: Locale) -> String {
+    let count = |key: &str, count: String| translate(locale, key, &[("count", &count)]);
+
+    let mut lines = vec![
+        format!(
+            "*{}*",
+            translate(
+                locale,
+                "digest.title",
+                &[("site", &digest.site_title), ("month", &digest.month)],
+            )
+        ),
+        count("digest.new_posts", digest.new_posts.len().to_string()),
+    ];
+    lines.extend(
+        digest
+            .new_posts
+            .iter()
+            .map(|post| format!("• <{}|{}>", post.url, post.title)),
+    );
+    lines.push(count(
+        "digest.updated_posts",
+        digest.updated_posts.to_string(),
+    ));
+    lines.push(count(
+        "digest.media_uploads",
+        digest.media_uploads.to_string(),
+    ));
+    lines.push(count(
+        "digest.broken_links",
+        digest.broken_links.to_string(),
+    ));
+    lines.push(count(
+        "digest.total_published",
+        digest.total_published.to_string(),
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_previous_month_wraps_the_year() {
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(
+            previous_month(day),
+            NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_digest_lists_new_posts() {
+        let digest = MonthlyDigest {
+            month: "2024-07".to_string(),
+            site_title: "tobelog".to_string(),
+            new_posts: vec![DigestPost {
+                title: "Hello".to_string(),
+                url: "https://blog.example/posts/2024/hello".to_string(),
+            }],
+            updated_posts: 2,
+            media_uploads: 0,
+            broken_links: 1,
+            total_published: 10,
+        };
+
+        let message = format_digest(&digest, Locale::En);
+        assert!(message.starts_with("*tobelog"));
+        assert!(message.contains("2024-07"));
+        assert!(message.contains("• <https://blog.example/posts/2024/hello|Hello>"));
+        assert!(message.contains("Broken links: 1"));
+    }
+}
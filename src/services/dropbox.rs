@@ -1,16 +1,22 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use reqwest::{
     header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
-    Client,
+    Client, RequestBuilder, Response, StatusCode,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tracing::instrument;
+
+use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStatus};
+use super::storage::StorageBackend;
 
 #[derive(Debug, Clone)]
 pub struct DropboxClient {
     client: Client,
     access_token: String,
     base_url: String,
+    breaker: CircuitBreaker,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +50,65 @@ struct DownloadRequest {
     path: String,
 }
 
+/// Response of `/2/users/get_space_usage`
+#[derive(Debug, Deserialize)]
+struct SpaceUsageResponse {
+    used: u64,
+    allocation: SpaceAllocation,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+enum SpaceAllocation {
+    Individual {
+        allocated: u64,
+    },
+    /// Team accounts share one quota; `used` is the team's total
+    Team {
+        used: u64,
+        allocated: u64,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Storage the Dropbox account uses and how much it may use
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct SpaceUsage {
+    pub used: u64,
+    /// `None` when Dropbox reports no allocation this client understands
+    pub allocated: Option<u64>,
+}
+
+impl SpaceUsage {
+    /// Share of the allocation in use, from 0 to 100
+    pub fn percent_used(&self) -> Option<f64> {
+        match self.allocated {
+            Some(allocated) if allocated > 0 => Some(self.used as f64 * 100.0 / allocated as f64),
+            _ => None,
+        }
+    }
+}
+
+impl From<SpaceUsageResponse> for SpaceUsage {
+    fn from(response: SpaceUsageResponse) -> Self {
+        match response.allocation {
+            SpaceAllocation::Individual { allocated } => Self {
+                used: response.used,
+                allocated: Some(allocated),
+            },
+            SpaceAllocation::Team { used, allocated } => Self {
+                used,
+                allocated: Some(allocated),
+            },
+            SpaceAllocation::Other => Self {
+                used: response.used,
+                allocated: None,
+            },
+        }
+    }
+}
+
 impl DropboxClient {
     pub fn new(access_token: String) -> Self {
         let client = Client::new();
@@ -51,6 +116,41 @@ impl DropboxClient {
             client,
             access_token,
             base_url: "https://api.dropboxapi.com".to_string(),
+            breaker: CircuitBreaker::new("Dropbox", CircuitBreakerConfig::default()),
+        }
+    }
+
+    /// Short-circuit API calls for a while after repeated failures
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.breaker = CircuitBreaker::new("Dropbox", config);
+        self
+    }
+
+    pub fn circuit_breaker_status(&self) -> CircuitBreakerStatus {
+        self.breaker.status()
+    }
+
+    /// Send a request unless the circuit is open; transport errors, rate
+    /// limiting and server errors count as failures, other responses are left
+    /// to the caller
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        self.breaker.check()?;
+        match request.send().await {
+            Ok(response)
+                if response.status().is_server_error()
+                    || response.status() == StatusCode::TOO_MANY_REQUESTS =>
+            {
+                self.breaker.record_failure();
+                Ok(response)
+            }
+            Ok(response) => {
+                self.breaker.record_success();
+                Ok(response)
+            }
+            Err(e) => {
+                self.breaker.record_failure();
+                Err(e.into())
+            }
         }
     }
 
@@ -78,15 +178,13 @@ impl DropboxClient {
         Ok(headers)
     }
 
+    #[instrument(name = "dropbox.test_connection", skip(self), fields(otel.kind = "client"))]
     pub async fn test_connection(&self) -> Result<HashMap<String, serde_json::Value>> {
         let url = format!("{}/2/users/get_current_account", self.base_url);
         let headers = self.create_auth_headers()?;
 
         let response = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .send()
+            .send(self.client.post(&url).headers(headers))
             .await
             .context("Failed to send test connection request")?;
 
@@ -108,6 +206,35 @@ impl DropboxClient {
         Ok(account_info)
     }
 
+    #[instrument(name = "dropbox.get_space_usage", skip(self), fields(otel.kind = "client"))]
+    pub async fn get_space_usage(&self) -> Result<SpaceUsage> {
+        let url = format!("{}/2/users/get_space_usage", self.base_url);
+        let headers = self.create_auth_headers()?;
+
+        let response = self
+            .send(self.client.post(&url).headers(headers))
+            .await
+            .context("Failed to send space usage request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Dropbox API space usage failed with status {}: {}",
+                status,
+                error_text
+            );
+        }
+
+        let usage: SpaceUsageResponse = response
+            .json()
+            .await
+            .context("Failed to parse space usage response")?;
+
+        Ok(usage.into())
+    }
+
+    #[instrument(name = "dropbox.list_folder", skip(self), fields(otel.kind = "client"))]
     pub async fn list_folder(&self, path: &str) -> Result<ListFolderResult> {
         let url = format!("{}/2/files/list_folder", self.base_url);
         let headers = self.create_headers()?;
@@ -120,11 +247,7 @@ impl DropboxClient {
         };
 
         let response = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .json(&request_body)
-            .send()
+            .send(self.client.post(&url).headers(headers).json(&request_body))
             .await
             .context("Failed to send list folder request")?;
 
@@ -146,6 +269,42 @@ impl DropboxClient {
         Ok(result)
     }
 
+    /// Metadata of a single file, e.g. to load it without listing its folder
+    #[instrument(name = "dropbox.get_metadata", skip(self), fields(otel.kind = "client"))]
+    pub async fn get_metadata(&self, path: &str) -> Result<FileMetadata> {
+        let url = format!("{}/2/files/get_metadata", self.base_url);
+        let headers = self.create_headers()?;
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers)
+            .json(&DownloadRequest {
+                path: path.to_string(),
+            })
+            .send()
+            .await
+            .context("Failed to send get metadata request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Dropbox get metadata failed with status {}: {}",
+                status,
+                error_text
+            );
+        }
+
+        let metadata: FileMetadata = response
+            .json()
+            .await
+            .context("Failed to parse get metadata response")?;
+
+        Ok(metadata)
+    }
+
+    #[instrument(name = "dropbox.download_file", skip(self), fields(otel.kind = "client"))]
     pub async fn download_file(&self, path: &str) -> Result<Vec<u8>> {
         let url = "https://content.dropboxapi.com/2/files/download";
 
@@ -167,10 +326,7 @@ impl DropboxClient {
         );
 
         let response = self
-            .client
-            .post(url)
-            .headers(headers)
-            .send()
+            .send(self.client.post(url).headers(headers))
             .await
             .context("Failed to send download file request")?;
 
@@ -198,6 +354,7 @@ impl DropboxClient {
     }
 
     #[allow(dead_code)]
+    #[instrument(name = "dropbox.upload_file", skip(self, content), fields(otel.kind = "client"))]
     pub async fn upload_file(&self, path: &str, content: &str) -> Result<FileMetadata> {
         let url = "https://content.dropboxapi.com/2/files/upload";
 
@@ -226,11 +383,12 @@ impl DropboxClient {
         );
 
         let response = self
-            .client
-            .post(url)
-            .headers(headers)
-            .body(content.to_string())
-            .send()
+            .send(
+                self.client
+                    .post(url)
+                    .headers(headers)
+                    .body(content.to_string()),
+            )
             .await
             .context("Failed to send upload file request")?;
 
@@ -252,6 +410,7 @@ impl DropboxClient {
         Ok(metadata)
     }
 
+    #[instrument(name = "dropbox.upload_binary_file", skip(self, data), fields(otel.kind = "client"))]
     pub async fn upload_binary_file(&self, path: &str, data: &[u8]) -> Result<FileMetadata> {
         let url = "https://content.dropboxapi.com/2/files/upload";
 
@@ -280,11 +439,7 @@ impl DropboxClient {
         );
 
         let response = self
-            .client
-            .post(url)
-            .headers(headers)
-            .body(data.to_vec())
-            .send()
+            .send(self.client.post(url).headers(headers).body(data.to_vec()))
             .await
             .context("Failed to send upload file request")?;
 
@@ -307,6 +462,7 @@ impl DropboxClient {
     }
 
     #[allow(dead_code)]
+    #[instrument(name = "dropbox.delete_file", skip(self), fields(otel.kind = "client"))]
     pub async fn delete_file(&self, path: &str) -> Result<FileMetadata> {
         let url = format!("{}/2/files/delete_v2", self.base_url);
         let headers = self.create_headers()?;
@@ -316,11 +472,7 @@ impl DropboxClient {
         });
 
         let response = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .json(&request_body)
-            .send()
+            .send(self.client.post(&url).headers(headers).json(&request_body))
             .await
             .context("Failed to send delete file request")?;
 
@@ -345,6 +497,7 @@ impl DropboxClient {
         Ok(metadata)
     }
 
+    #[instrument(name = "dropbox.create_folder", skip(self), fields(otel.kind = "client"))]
     pub async fn create_folder(&self, path: &str) -> Result<FileMetadata> {
         let url = format!("{}/2/files/create_folder_v2", self.base_url);
         let headers = self.create_headers()?;
@@ -355,11 +508,7 @@ impl DropboxClient {
         });
 
         let response = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .json(&request_body)
-            .send()
+            .send(self.client.post(&url).headers(headers).json(&request_body))
             .await
             .context("Failed to send create folder request")?;
 
@@ -385,6 +534,55 @@ impl DropboxClient {
     }
 }
 
+#[async_trait]
+impl StorageBackend for DropboxClient {
+    fn name(&self) -> &'static str {
+        "dropbox"
+    }
+
+    async fn check_connection(&self) -> Result<String> {
+        let account_info = self.test_connection().await?;
+        let display_name = account_info
+            .get("name")
+            .and_then(|name| name.get("display_name"))
+            .and_then(|name| name.as_str())
+            .unwrap_or("unknown");
+        Ok(format!("Dropbox account {}", display_name))
+    }
+
+    async fn list_folder(&self, path: &str) -> Result<Vec<FileMetadata>> {
+        Ok(DropboxClient::list_folder(self, path).await?.entries)
+    }
+
+    async fn get_metadata(&self, path: &str) -> Result<FileMetadata> {
+        DropboxClient::get_metadata(self, path).await
+    }
+
+    async fn download_file(&self, path: &str) -> Result<Vec<u8>> {
+        DropboxClient::download_file(self, path).await
+    }
+
+    async fn download_text_file(&self, path: &str) -> Result<String> {
+        DropboxClient::download_text_file(self, path).await
+    }
+
+    async fn upload_file(&self, path: &str, content: &str) -> Result<FileMetadata> {
+        DropboxClient::upload_file(self, path, content).await
+    }
+
+    async fn upload_binary_file(&self, path: &str, data: &[u8]) -> Result<FileMetadata> {
+        DropboxClient::upload_binary_file(self, path, data).await
+    }
+
+    async fn delete_file(&self, path: &str) -> Result<FileMetadata> {
+        DropboxClient::delete_file(self, path).await
+    }
+
+    async fn create_folder(&self, path: &str) -> Result<()> {
+        DropboxClient::create_folder(self, path).await.map(|_| ())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,4 +605,25 @@ mod tests {
         let auth_header = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
         assert_eq!(auth_header, "Bearer test_token");
     }
+
+    #[test]
+    fn test_space_usage_from_response() {
+        let individual: SpaceUsageResponse = serde_json::from_str(
+            r#"{"used": 900, "allocation": {".tag": "individual", "allocated": 1000}}"#,
+        )
+        .unwrap();
+        let usage = SpaceUsage::from(individual);
+        assert_eq!(usage.allocated, Some(1000));
+        assert_eq!(usage.percent_used(), Some(90.0));
+
+        let team: SpaceUsageResponse = serde_json::from_str(
+            r#"{"used": 10, "allocation": {".tag": "team", "used": 500, "allocated": 2000}}"#,
+        )
+        .unwrap();
+        assert_eq!(SpaceUsage::from(team).percent_used(), Some(25.0));
+
+        let unknown: SpaceUsageResponse =
+            serde_json::from_str(r#"{"used": 10, "allocation": {".tag": "other"}}"#).unwrap();
+        assert_eq!(SpaceUsage::from(unknown).percent_used(), None);
+    }
 }
@@ -0,0 +1,76 @@
+use futures_util::Stream;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, warn};
+
+use crate::models::AdminEvent;
+
+/// Events buffered per subscriber before slow ones start missing events
+const EVENT_BUFFER: usize = 64;
+
+/// In-process broadcast channel that services publish admin notifications to
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<AdminEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        Self { sender }
+    }
+
+    /// Send an event to every current subscriber; a no-op when nobody listens
+    pub fn publish(&self, event: AdminEvent) {
+        debug!("Admin event: {}", event.name());
+        let _ = self.sender.send(event);
+    }
+
+    /// Events published from now on; subscribers that fall behind skip the
+    /// events they missed rather than disconnecting
+    pub fn subscribe(&self) -> impl Stream<Item = AdminEvent> {
+        futures_util::stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Admin event subscriber lagged, skipped {} events", skipped)
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_published_events() {
+        let bus = EventBus::new();
+        bus.publish(AdminEvent::SyncCompleted {
+            synced: 0,
+            errors: 0,
+            conflicts: 0,
+        });
+
+        let events = bus.subscribe();
+        bus.publish(AdminEvent::ScheduledPostPublished {
+            slug: "hello".to_string(),
+            title: "Hello".to_string(),
+        });
+        drop(bus);
+
+        let received: Vec<AdminEvent> = events.collect().await;
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].name(), "scheduled_post_published");
+    }
+}
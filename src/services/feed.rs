@@ -0,0 +1,240 @@
+use anyhow::Result;
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use std::fmt::Write;
+use tracing::debug;
+
+use crate::models::{Post, PostFilters, PostVisibility};
+use crate::services::DatabaseService;
+
+/// Characters escaped in a tag or series name used as a URL path segment
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Maximum number of posts listed in a feed
+const FEED_ITEM_LIMIT: i64 = 50;
+
+/// Channel-level metadata shared by every feed
+#[derive(Debug, Clone)]
+struct FeedChannel {
+    title: String,
+    description: String,
+    language: String,
+}
+
+/// Service that publishes RSS feeds scoped to one tag or one post series
+///
+/// Feeds only list published, public posts without a password, newest first,
+/// as their pinned versions when they have one.
+#[derive(Clone)]
+pub struct FeedService {
+    database: DatabaseService,
+    /// Public base URL used to build absolute links
+    site_url: String,
+}
+
+impl FeedService {
+    /// Create a new feed service
+    pub fn new(database: DatabaseService, site_url: String) -> Self {
+        Self { database, site_url }
+    }
+
+    /// Build the RSS document for `/tag/{tag}/feed.xml`; `None` when no post has the tag
+    pub async fn tag_feed(&self, tag: &str) -> Result<Option<String>> {
+        let mut posts = self
+            .database
+            .list_posts(PostFilters {
+                published: Some(true),
+                tag: Some(tag.to_string()),
+                visibility: Some(vec![PostVisibility::Public]),
+                // Filtered in the query so locked posts don't use up the limit
+                password_protected: Some(false),
+                limit: Some(FEED_ITEM_LIMIT),
+                ..Default::default()
+            })
+            .await?;
+        self.database.show_published_versions(&mut posts).await?;
+        if posts.is_empty() {
+            return Ok(None);
+        }
+        debug!("Building feed for tag {} with {} posts", tag, posts.len());
+
+        let channel = self.channel(&format!("#{}", tag)).await?;
+        let path = format!("/tag/{}/feed.xml", utf8_percent_encode(tag, PATH_SEGMENT));
+        Ok(Some(render_feed(&self.site_url, &path, &channel, &posts)))
+    }
+
+    /// Build the RSS document for `/series/{slug}/feed.xml`; `None` when the
+    /// series has no posts
+    ///
+    /// A post joins a series through the `series` key of its frontmatter.
+    pub async fn series_feed(&self, series: &str) -> Result<Option<String>> {
+        let mut posts = self
+            .database
+            .list_series_posts(series, FEED_ITEM_LIMIT)
+            .await?;
+        self.database.show_published_versions(&mut posts).await?;
+        if posts.is_empty() {
+            return Ok(None);
+        }
+        debug!(
+            "Building feed for series {} with {} posts",
+            series,
+            posts.len()
+        );
+
+        let channel = self.channel(series).await?;
+        let path = format!(
+            "/series/{}/feed.xml",
+            utf8_percent_encode(series, PATH_SEGMENT)
+        );
+        Ok(Some(render_feed(&self.site_url, &path, &channel, &posts)))
+    }
+
+    /// Channel named after the site and the feed's `scope`
+    async fn channel(&self, scope: &str) -> Result<FeedChannel> {
+        Ok(match self.database.get_site_config().await? {
+            Some(site) => FeedChannel {
+                title: format!("{} - {}", site.site_title, scope),
+                description: site.site_description,
+                language: site.locale.as_str().to_string(),
+            },
+            None => FeedChannel {
+                title: format!("Tobelog - {}", scope),
+                description: "Personal Blog System built with Rust".to_string(),
+                language: "ja".to_string(),
+            },
+        })
+    }
+}
+
+fn render_feed(site_url: &str, path: &str, channel: &FeedChannel, posts: &[Post]) -> String {
+    let site_url = site_url.trim_end_matches('/');
+    let mut xml = String::new();
+
+    // Writing to a String cannot fail
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">"#
+    );
+    let _ = writeln!(xml, "<channel>");
+    let _ = writeln!(xml, "<title>{}</title>", encode_text(&channel.title));
+    let _ = writeln!(xml, "<link>{}</link>", encode_text(site_url));
+    let _ = writeln!(
+        xml,
+        r#"<atom:link href="{}{}" rel="self" type="application/rss+xml"/>"#,
+        encode_double_quoted_attribute(site_url),
+        encode_double_quoted_attribute(path)
+    );
+    let _ = writeln!(
+        xml,
+        "<description>{}</description>",
+        encode_text(&channel.description)
+    );
+    let _ = writeln!(
+        xml,
+        "<language>{}</language>",
+        encode_text(&channel.language)
+    );
+    if let Some(latest) = posts.first() {
+        let _ = writeln!(
+            xml,
+            "<lastBuildDate>{}</lastBuildDate>",
+            latest.updated_at.to_rfc2822()
+        );
+    }
+
+    for post in posts {
+        let link = format!("{}{}", site_url, post.get_url_path());
+        let summary = post
+            .excerpt
+            .as_deref()
+            .or(post.summary.as_deref())
+            .unwrap_or(&post.title);
+
+        let _ = writeln!(xml, "<item>");
+        let _ = writeln!(xml, "<title>{}</title>", encode_text(&post.title));
+        let _ = writeln!(xml, "<link>{}</link>", encode_text(&link));
+        let _ = writeln!(
+            xml,
+            r#"<guid isPermaLink="false">{}</guid>"#,
+            encode_text(&post.id.to_string())
+        );
+        let _ = writeln!(
+            xml,
+            "<pubDate>{}</pubDate>",
+            post.published_at.unwrap_or(post.created_at).to_rfc2822()
+        );
+        if let Some(author) = &post.author {
+            let _ = writeln!(xml, "<dc:creator>{}</dc:creator>", encode_text(author));
+        }
+        for tag in post.get_tags() {
+            let _ = writeln!(xml, "<category>{}</category>", encode_text(&tag));
+        }
+        let _ = writeln!(xml, "<description>{}</description>", encode_text(summary));
+        let _ = writeln!(xml, "</item>");
+    }
+
+    let _ = writeln!(xml, "</channel>");
+    let _ = writeln!(xml, "</rss>");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreatePost;
+
+    #[test]
+    fn test_render_feed_links_items_and_escapes_text() {
+        let post = Post::new(CreatePost {
+            slug: "axum-routing".to_string(),
+            title: "Axum & Tower".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            excerpt: Some("<Routing> basics".to_string()),
+            category: None,
+            tags: vec!["rust".to_string(), "web".to_string()],
+            published: true,
+            featured: false,
+            author: None,
+            dropbox_path: String::new(),
+        });
+        let channel = FeedChannel {
+            title: "Tobelog - #rust".to_string(),
+            description: "Posts".to_string(),
+            language: "en".to_string(),
+        };
+
+        let xml = render_feed(
+            "https://blog.example.com/",
+            "/tag/rust/feed.xml",
+            &channel,
+            std::slice::from_ref(&post),
+        );
+
+        assert!(xml.contains("<title>Tobelog - #rust</title>"));
+        assert!(xml.contains(
+            r#"<atom:link href="https://blog.example.com/tag/rust/feed.xml" rel="self" type="application/rss+xml"/>"#
+        ));
+        assert!(xml.contains("<title>Axum &amp; Tower</title>"));
+        assert!(xml.contains(&format!(
+            "<link>https://blog.example.com{}</link>",
+            post.get_url_path()
+        )));
+        assert!(xml.contains("<description>&lt;Routing&gt; basics</description>"));
+        assert!(xml.contains("<category>web</category>"));
+        assert!(xml.contains("<language>en</language>"));
+    }
+}
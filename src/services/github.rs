@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// One branch of a GitHub repository, written through the contents API
+#[derive(Clone)]
+pub struct GitHubRepo {
+    client: Client,
+    api_url: String,
+    token: String,
+    /// `owner/repo`
+    repo: String,
+    branch: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubContent {
+    sha: String,
+}
+
+impl GitHubRepo {
+    pub fn new(client: Client, token: String, repo: String, branch: String) -> Self {
+        Self {
+            client,
+            api_url: GITHUB_API_URL.to_string(),
+            token,
+            repo,
+            branch,
+        }
+    }
+
+    fn contents_url(&self, path: &str) -> String {
+        format!(
+            "{}/repos/{}/contents/{}",
+            self.api_url,
+            self.repo,
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// Blob SHA of the file at `path`, if it exists on the branch
+    pub async fn file_sha(&self, path: &str) -> Result<Option<String>> {
+        let response = self
+            .client
+            .get(self.contents_url(path))
+            .query(&[("ref", &self.branch)])
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .context("Failed to reach GitHub")?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let content: GitHubContent = error_for_status(response)
+            .await?
+            .json()
+            .await
+            .context("Invalid GitHub response")?;
+        Ok(Some(content.sha))
+    }
+
+    /// Commit `content` to `path`, creating or replacing the file
+    ///
+    /// Returns false without committing when the file already has `content`.
+    pub async fn put_file(&self, path: &str, content: &[u8], message: &str) -> Result<bool> {
+        let sha = self.file_sha(path).await?;
+        if sha.as_deref() == Some(blob_sha(content).as_str()) {
+            return Ok(false);
+        }
+
+        let mut body = serde_json::json!({
+            "message": message,
+            "content": STANDARD.encode(content),
+            "branch": self.branch,
+        });
+        if let Some(sha) = sha {
+            body["sha"] = sha.into();
+        }
+        let response = self
+            .client
+            .put(self.contents_url(path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to reach GitHub")?;
+        error_for_status(response).await?;
+        Ok(true)
+    }
+}
+
+/// SHA git gives a blob with `content`, as the contents API reports it
+fn blob_sha(content: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("blob {}\0", content.len()).as_bytes());
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Error for a non-success response, with the message GitHub sent back
+async fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(anyhow!(
+        "GitHub returned {}: {}",
+        status,
+        body.chars().take(300).collect::<String>()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_sha_matches_git() {
+        // `printf 'hello\n' | git hash-object --stdin`
+        assert_eq!(
+            blob_sha(b"hello\n"),
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+    }
+}
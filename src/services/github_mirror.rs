@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::models::{AdminEvent, Post, VersionFilters};
+use crate::services::blog_storage::BlogPost;
+use crate::services::github::GitHubRepo;
+use crate::services::version::versions_to_jsonl;
+use crate::services::{BlogStorageService, DatabaseService, EventBus};
+
+/// GitHub mirror configuration
+#[derive(Debug, Clone)]
+pub struct GitHubMirrorConfig {
+    pub token: String,
+    /// `owner/repo` receiving the commits
+    pub repo: String,
+    pub branch: String,
+    /// Folder of the repository holding the posts
+    pub folder: String,
+}
+
+/// Commits published posts, frontmatter included, to a GitHub repository
+///
+/// Gives the content a git history and a backup that doesn't depend on
+/// Dropbox. Each post's version history goes next to it as a JSON Lines
+/// sidecar that `POST /api/posts/{slug}/versions/import` reads back. Runs when the API publishes or edits a post and when a scheduled
+/// post goes live.
+#[derive(Clone)]
+pub struct GitHubMirrorService {
+    database: DatabaseService,
+    blog_storage: Arc<BlogStorageService>,
+    repo: GitHubRepo,
+    folder: String,
+}
+
+impl GitHubMirrorService {
+    pub fn new(
+        database: DatabaseService,
+        blog_storage: Arc<BlogStorageService>,
+        config: GitHubMirrorConfig,
+    ) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("tobelog/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            database,
+            blog_storage,
+            repo: GitHubRepo::new(client, config.token, config.repo, config.branch),
+            folder: config.folder.trim_matches('/').to_string(),
+        }
+    }
+
+    /// Path of a post's file in the repository, with `extension`
+    fn path(&self, post: &Post, extension: &str) -> String {
+        if self.folder.is_empty() {
+            format!("{}.{}", post.slug, extension)
+        } else {
+            format!("{}/{}.{}", self.folder, post.slug, extension)
+        }
+    }
+
+    /// Commit the post's Markdown file and version history; false when the
+    /// mirror was up to date
+    pub async fn mirror_post(&self, post: &Post) -> Result<bool> {
+        let markdown = self
+            .blog_storage
+            .serialize_blog_post(&BlogPost::from_post(post))?;
+        let post_changed = self
+            .repo
+            .put_file(
+                &self.path(post, "md"),
+                markdown.as_bytes(),
+                &format!("Update {}", post.slug),
+            )
+            .await
+            .with_context(|| format!("Failed to mirror {}", post.slug))?;
+
+        let mut versions = self
+            .database
+            .list_post_versions(VersionFilters {
+                post_id: Some(post.id),
+                ..Default::default()
+            })
+            .await?;
+        if versions.is_empty() {
+            return Ok(post_changed);
+        }
+        versions.reverse();
+        let history_changed = self
+            .repo
+            .put_file(
+                &self.path(post, "versions.jsonl"),
+                versions_to_jsonl(&versions)?.as_bytes(),
+                &format!("Update version history of {}", post.slug),
+            )
+            .await
+            .with_context(|| format!("Failed to mirror version history of {}", post.slug))?;
+
+        Ok(post_changed || history_changed)
+    }
+
+    /// Mirror posts as the API publishes them or scheduled posts go live
+    pub fn spawn_listener(&self, events: &EventBus) {
+        let service = self.clone();
+        let mut events = Box::pin(events.subscribe());
+        tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let slug = match event {
+                    AdminEvent::PostPublished { slug, .. }
+                    | AdminEvent::ScheduledPostPublished { slug, .. } => slug,
+                    _ => continue,
+                };
+                match service.database.get_post_by_slug(&slug).await {
+                    Ok(Some(post)) => match service.mirror_post(&post).await {
+                        Ok(true) => info!("Mirrored {} to GitHub", slug),
+                        Ok(false) => {}
+                        Err(e) => error!("{:#}", e),
+                    },
+                    Ok(None) => {}
+                    Err(e) => error!("Failed to load {} for the GitHub mirror: {}", slug, e),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,209 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::warn;
+
+use crate::models::Locale;
+
+type Catalog = HashMap<String, String>;
+
+/// Message catalogs compiled into the binary
+const CATALOG_SOURCES: [(Locale, &str); 2] = [
+    (Locale::Ja, include_str!("../../locales/ja.toml")),
+    (Locale::En, include_str!("../../locales/en.toml")),
+];
+
+/// Catalogs keyed by dotted message key (`common.home`)
+fn catalogs() -> &'static HashMap<Locale, Catalog> {
+    static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        CATALOG_SOURCES
+            .iter()
+            .map(|(locale, source)| {
+                let table: toml::Table = source.parse().unwrap_or_else(|e| {
+                    panic!("Invalid {} message catalog: {}", locale.as_str(), e)
+                });
+                let mut catalog = Catalog::new();
+                flatten_table("", &table, &mut catalog);
+                (*locale, catalog)
+            })
+            .collect()
+    })
+}
+
+fn flatten_table(prefix: &str, table: &toml::Table, catalog: &mut Catalog) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(table) => flatten_table(&key, table, catalog),
+            toml::Value::String(message) => {
+                catalog.insert(key, message.clone());
+            }
+            other => {
+                catalog.insert(key, other.to_string());
+            }
+        }
+    }
+}
+
+/// Message for `key` with its `{name}` placeholders filled from `args`
+///
+/// Keys missing from the locale's catalog fall back to Japanese, then to the
+/// key itself. Placeholders without an argument are left in place so that
+/// scripts can fill them in the browser.
+pub fn translate(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    let catalogs = catalogs();
+    let message = catalogs
+        .get(&locale)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| {
+            catalogs
+                .get(&Locale::Ja)
+                .and_then(|catalog| catalog.get(key))
+        });
+    let Some(message) = message else {
+        warn!("Missing translation for '{}'", key);
+        return key.to_string();
+    };
+
+    args.iter().fold(message.clone(), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Date layouts offered to templates by the `localized_date` filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateStyle {
+    /// `2024年01月15日` / `January 15, 2024`
+    Long,
+    /// `2024/01/15` / `Jan 15, 2024`
+    Short,
+    /// Short date followed by the time of day
+    DateTime,
+}
+
+impl std::str::FromStr for DateStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "long" => Ok(DateStyle::Long),
+            "short" => Ok(DateStyle::Short),
+            "datetime" => Ok(DateStyle::DateTime),
+            other => Err(anyhow::anyhow!("Unknown date style: {}", other)),
+        }
+    }
+}
+
+/// Format `date` the way readers of `locale` expect
+pub fn format_date<Tz>(date: &DateTime<Tz>, locale: Locale, style: DateStyle) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    let format = match (locale, style) {
+        (Locale::Ja, DateStyle::Long) => "%Y年%m月%d日",
+        (Locale::Ja, DateStyle::Short) => "%Y/%m/%d",
+        (Locale::Ja, DateStyle::DateTime) => "%Y/%m/%d %H:%M",
+        (Locale::En, DateStyle::Long) => "%B %-d, %Y",
+        (Locale::En, DateStyle::Short) => "%b %-d, %Y",
+        (Locale::En, DateStyle::DateTime) => "%b %-d, %Y %H:%M",
+    };
+    date.format(format).to_string()
+}
+
+/// Site timezone shared by the templates, API responses and the scheduler
+///
+/// Timestamps are stored in UTC; this only affects how they are shown and how
+/// wall-clock times entered by the author are read.
+#[derive(Debug, Clone)]
+pub struct SiteTimezone(Arc<RwLock<Tz>>);
+
+impl SiteTimezone {
+    pub fn new(timezone: Tz) -> Self {
+        Self(Arc::new(RwLock::new(timezone)))
+    }
+
+    pub fn get(&self) -> Tz {
+        *self.0.read().expect("site timezone lock poisoned")
+    }
+
+    pub fn set(&self, timezone: Tz) {
+        *self.0.write().expect("site timezone lock poisoned") = timezone;
+    }
+
+    /// `date` as the site's wall-clock time
+    pub fn to_local<Tz2: TimeZone>(&self, date: &DateTime<Tz2>) -> DateTime<Tz> {
+        date.with_timezone(&self.get())
+    }
+
+    /// UTC instant of a wall-clock time in the site timezone
+    ///
+    /// Times repeated by a DST change resolve to their first occurrence;
+    /// times skipped by one don't exist and yield `None`.
+    pub fn resolve_local(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        self.get()
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|date| date.with_timezone(&Utc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_catalogs_define_the_same_keys() {
+        let catalogs = catalogs();
+        let mut ja: Vec<&String> = catalogs[&Locale::Ja].keys().collect();
+        let mut en: Vec<&String> = catalogs[&Locale::En].keys().collect();
+        ja.sort();
+        en.sort();
+        assert_eq!(ja, en);
+
+        assert_eq!(
+            translate(Locale::En, "category.post_count", &[("count", "3")]),
+            "3 posts in this category"
+        );
+        assert_eq!(translate(Locale::Ja, "missing.key", &[]), "missing.key");
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = Utc.with_ymd_and_hms(2024, 1, 5, 9, 30, 0).unwrap();
+        assert_eq!(
+            format_date(&date, Locale::Ja, DateStyle::Long),
+            "2024年01月05日"
+        );
+        assert_eq!(
+            format_date(&date, Locale::En, DateStyle::Long),
+            "January 5, 2024"
+        );
+        assert_eq!(
+            format_date(&date, Locale::En, DateStyle::DateTime),
+            "Jan 5, 2024 09:30"
+        );
+    }
+
+    #[test]
+    fn test_site_timezone_round_trip() {
+        let timezone = SiteTimezone::new(Tz::Asia__Tokyo);
+        let date = Utc.with_ymd_and_hms(2024, 1, 5, 20, 30, 0).unwrap();
+        let local = timezone.to_local(&date);
+        assert_eq!(local.to_rfc3339(), "2024-01-06T05:30:00+09:00");
+        assert_eq!(timezone.resolve_local(local.naive_local()), Some(date));
+
+        // 02:30 is skipped when New York springs forward
+        timezone.set(Tz::America__New_York);
+        let skipped = NaiveDateTime::parse_from_str("2024-03-10 02:30", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(timezone.resolve_local(skipped), None);
+    }
+}
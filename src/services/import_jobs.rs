@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use futures_util::Stream;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::models::{
+    AdminEvent, BatchImportRequest, ImportJob, ImportJobEvent, ImportJobStatus, ImportSummary, Job,
+    JobStatus,
+};
+use crate::services::llm_import::{BatchArticleOutcome, LLMImportService};
+use crate::services::{job_queue, EventBus, JobQueue};
+
+/// Job kind of queued batch imports
+pub const BATCH_IMPORT_JOB: &str = "batch_import";
+
+/// How often event streams look for new progress of an unfinished job
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs batch imports on the persistent job queue and tracks their progress
+///
+/// A job's progress is the list of [`ImportJobEvent`]s so far, stored with
+/// the job, so snapshots and event streams survive restarts.
+#[derive(Clone)]
+pub struct ImportJobService {
+    llm_import: LLMImportService,
+    jobs: JobQueue,
+    events: Option<EventBus>,
+}
+
+impl ImportJobService {
+    pub fn new(llm_import: LLMImportService, jobs: JobQueue) -> Self {
+        Self {
+            llm_import,
+            jobs,
+            events: None,
+        }
+    }
+
+    /// Notify the admin UI when a job finishes
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Register the handler of queued batch imports
+    pub fn register_jobs(&self) {
+        let service = self.clone();
+        self.jobs.register(BATCH_IMPORT_JOB, move |payload| {
+            let service = service.clone();
+            async move { Ok(service.run(serde_json::from_value(payload)?).await) }
+        });
+    }
+
+    /// Queue a batch import for the job queue's workers
+    pub async fn start(&self, request: BatchImportRequest) -> Result<ImportJob> {
+        let job = self.jobs.enqueue(BATCH_IMPORT_JOB, &request).await?;
+        Ok(snapshot(&job, &[]))
+    }
+
+    /// Current snapshot of a job; `None` for unknown IDs and other kinds of job
+    pub async fn get(&self, id: &str) -> Result<Option<ImportJob>> {
+        Ok(self
+            .load(id)
+            .await?
+            .map(|(job, events)| snapshot(&job, &events)))
+    }
+
+    /// All of a job's events from the first one, ending after it finishes
+    pub async fn subscribe(&self, id: &str) -> Result<Option<impl Stream<Item = ImportJobEvent>>> {
+        if self.load(id).await?.is_none() {
+            return Ok(None);
+        }
+        let service = self.clone();
+        let id = id.to_string();
+
+        Ok(Some(futures_util::stream::unfold(
+            (0, VecDeque::new(), false, false),
+            move |(mut cursor, mut pending, mut finished, mut polled)| {
+                let service = service.clone();
+                let id = id.clone();
+                async move {
+                    loop {
+                        if let Some(event) = pending.pop_front() {
+                            return Some((event, (cursor, pending, finished, polled)));
+                        }
+                        if finished {
+                            return None;
+                        }
+                        if polled {
+                            tokio::time::sleep(EVENT_POLL_INTERVAL).await;
+                        }
+                        polled = true;
+
+                        let (job, events) = match service.load(&id).await {
+                            Ok(Some(loaded)) => loaded,
+                            Ok(None) => return None,
+                            Err(e) => {
+                                warn!("Failed to load batch import job {}: {}", id, e);
+                                return None;
+                            }
+                        };
+                        // A job restarted after a crash replays from the start;
+                        // events already sent are skipped
+                        if events.len() > cursor {
+                            let count = events.len();
+                            pending.extend(events.into_iter().skip(cursor));
+                            cursor = count;
+                        }
+                        finished = matches!(job.status, JobStatus::Succeeded | JobStatus::Dead);
+                    }
+                }
+            },
+        )))
+    }
+
+    /// The job and its events so far
+    async fn load(&self, id: &str) -> Result<Option<(Job, Vec<ImportJobEvent>)>> {
+        let Ok(id) = Uuid::parse_str(id) else {
+            return Ok(None);
+        };
+        let Some(job) = self.jobs.get(id).await? else {
+            return Ok(None);
+        };
+        if job.kind != BATCH_IMPORT_JOB {
+            return Ok(None);
+        }
+        let events = match &job.progress {
+            Some(progress) => serde_json::from_value(progress.clone())
+                .context("Failed to read batch import progress")?,
+            None => Vec::new(),
+        };
+        Ok(Some((job, events)))
+    }
+
+    async fn run(&self, request: BatchImportRequest) -> ImportSummary {
+        let total = request.articles.len();
+        let mut events = Vec::new();
+        self.publish(&mut events, ImportJobEvent::Started { total })
+            .await;
+
+        for (index, article) in request.articles.into_iter().enumerate() {
+            let event = match self.llm_import.process_batch_article(article).await {
+                BatchArticleOutcome::Imported(result) => ImportJobEvent::ArticleImported {
+                    index,
+                    total,
+                    result,
+                },
+                BatchArticleOutcome::Duplicate(error) => ImportJobEvent::ArticleFailed {
+                    index,
+                    total,
+                    duplicate: true,
+                    error,
+                },
+                BatchArticleOutcome::Failed(error) => ImportJobEvent::ArticleFailed {
+                    index,
+                    total,
+                    duplicate: false,
+                    error,
+                },
+            };
+            self.publish(&mut events, event).await;
+        }
+
+        let mut job = ImportJob::new(String::new(), total);
+        for event in &events {
+            job.apply(event);
+        }
+        let summary = job.summary();
+        let job_id = job_queue::current_job()
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        info!(
+            "Batch import job {} finished: {} imported, {} failed",
+            job_id, summary.successful, summary.failed
+        );
+        if let Some(admin_events) = &self.events {
+            admin_events.publish(AdminEvent::ImportJobFinished {
+                job_id,
+                successful: summary.successful,
+                failed: summary.failed,
+            });
+        }
+        self.publish(
+            &mut events,
+            ImportJobEvent::Completed {
+                summary: summary.clone(),
+            },
+        )
+        .await;
+        summary
+    }
+
+    /// Append an event and store the events as the job's progress
+    async fn publish(&self, events: &mut Vec<ImportJobEvent>, event: ImportJobEvent) {
+        events.push(event);
+        if let Err(e) = self.jobs.report_progress(events).await {
+            warn!("Failed to record batch import progress: {}", e);
+        }
+    }
+}
+
+/// Snapshot of a batch import job folded from its events
+fn snapshot(job: &Job, events: &[ImportJobEvent]) -> ImportJob {
+    let total = job
+        .payload
+        .get("articles")
+        .and_then(|articles| articles.as_array())
+        .map_or(0, |articles| articles.len());
+    let mut snapshot = ImportJob::new(job.id.to_string(), total);
+    snapshot.created_at = job.created_at;
+    for event in events {
+        snapshot.apply(event);
+    }
+
+    if job.status == JobStatus::Dead {
+        snapshot.status = ImportJobStatus::Failed;
+    }
+    if snapshot.finished_at.is_some() || job.status == JobStatus::Dead {
+        snapshot.finished_at = job.finished_at.or(snapshot.finished_at);
+    }
+    snapshot
+}
@@ -0,0 +1,245 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use futures_util::future::BoxFuture;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{debug, error, field, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+use crate::models::{retry_backoff, AdminEvent, Job};
+use crate::services::{DatabaseService, EventBus};
+
+tokio::task_local! {
+    /// ID of the job whose handler runs on the current task
+    static CURRENT_JOB: Uuid;
+}
+
+/// ID of the job being run, inside a job handler
+pub fn current_job() -> Option<Uuid> {
+    CURRENT_JOB.try_with(|id| *id).ok()
+}
+
+type JobHandler =
+    Arc<dyn Fn(serde_json::Value) -> BoxFuture<'static, Result<serde_json::Value>> + Send + Sync>;
+
+/// Worker pool and retry settings of the job queue
+#[derive(Debug, Clone)]
+pub struct JobQueueConfig {
+    pub workers: usize,
+    /// Attempts before a job is moved to the dead-letter state
+    pub max_attempts: i64,
+    /// Delay before the first retry, doubled on every further failure
+    pub retry_base_delay: Duration,
+    /// How often idle workers look for jobs that became due
+    pub poll_interval: Duration,
+}
+
+impl Default for JobQueueConfig {
+    fn default() -> Self {
+        Self {
+            workers: 2,
+            max_attempts: 3,
+            retry_base_delay: Duration::from_secs(30),
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Persistent background job queue backed by the `jobs` table
+///
+/// Handlers are registered per job kind before [`JobQueue::start`]; jobs
+/// survive restarts and ones interrupted mid-run are picked up again.
+#[derive(Clone)]
+pub struct JobQueue {
+    database: DatabaseService,
+    config: JobQueueConfig,
+    handlers: Arc<RwLock<HashMap<String, JobHandler>>>,
+    wake: Arc<Notify>,
+    events: Option<EventBus>,
+}
+
+impl JobQueue {
+    pub fn new(database: DatabaseService, config: JobQueueConfig) -> Self {
+        Self {
+            database,
+            config,
+            handlers: Arc::new(RwLock::new(HashMap::new())),
+            wake: Arc::new(Notify::new()),
+            events: None,
+        }
+    }
+
+    /// Notify the admin UI when a job moves to the dead-letter state
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Run jobs of `kind` with `handler`; its output is stored as the job result
+    pub fn register<F, Fut, T>(&self, kind: &str, handler: F)
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Serialize,
+    {
+        let handler: JobHandler = Arc::new(move |payload| {
+            let run = handler(payload);
+            Box::pin(async move { Ok(serde_json::to_value(run.await?)?) })
+        });
+        self.handlers
+            .write()
+            .expect("job handler registry poisoned")
+            .insert(kind.to_string(), handler);
+    }
+
+    /// Persist a job and wake an idle worker
+    pub async fn enqueue(&self, kind: &str, payload: &impl Serialize) -> Result<Job> {
+        let payload = serde_json::to_value(payload).context("Failed to serialize job payload")?;
+        let job = self
+            .database
+            .enqueue_job(kind, &payload, self.config.max_attempts)
+            .await?;
+        self.wake.notify_one();
+        Ok(job)
+    }
+
+    /// A job with its status, progress and result
+    pub async fn get(&self, id: Uuid) -> Result<Option<Job>> {
+        self.database.get_job(id).await
+    }
+
+    /// Record the progress of the job being run, shown as `progress` in the
+    /// job status; does nothing outside of a job handler
+    pub async fn report_progress(&self, progress: &impl Serialize) -> Result<()> {
+        let Some(id) = current_job() else {
+            return Ok(());
+        };
+        let progress =
+            serde_json::to_value(progress).context("Failed to serialize job progress")?;
+        self.database.update_job_progress(id, &progress).await
+    }
+
+    /// Requeue interrupted jobs and spawn the worker pool
+    pub async fn start(&self) -> Result<()> {
+        let requeued = self.database.requeue_interrupted_jobs().await?;
+        if requeued > 0 {
+            warn!(
+                "Requeued {} jobs interrupted by the last shutdown",
+                requeued
+            );
+        }
+
+        for worker in 0..self.config.workers {
+            let queue = self.clone();
+            tokio::spawn(async move { queue.work(worker).await });
+        }
+        info!("Job queue started with {} workers", self.config.workers);
+        Ok(())
+    }
+
+    async fn work(&self, worker: usize) {
+        loop {
+            match self.database.claim_next_job().await {
+                Ok(Some(job)) => self.run(worker, job).await,
+                Ok(None) => {
+                    tokio::select! {
+                        _ = self.wake.notified() => {}
+                        _ = tokio::time::sleep(self.config.poll_interval) => {}
+                    }
+                }
+                Err(e) => {
+                    error!("Job worker {} failed to claim a job: {}", worker, e);
+                    tokio::time::sleep(self.config.poll_interval).await;
+                }
+            }
+        }
+    }
+
+    async fn run(&self, worker: usize, job: Job) {
+        debug!(
+            "Worker {} running {} job {} (attempt {}/{})",
+            worker, job.kind, job.id, job.attempts, job.max_attempts
+        );
+
+        // Traced as its own root span; jobs outlive the request that queued them
+        let span = info_span!(
+            parent: None,
+            "job",
+            otel.name = %format!("job {}", job.kind),
+            otel.kind = "consumer",
+            otel.status_code = field::Empty,
+            job.id = %job.id,
+            job.kind = %job.kind,
+            job.attempt = job.attempts,
+        );
+
+        let handler = self
+            .handlers
+            .read()
+            .expect("job handler registry poisoned")
+            .get(&job.kind)
+            .cloned();
+        // A panicking handler must not take the worker down with it
+        let outcome = match handler {
+            Some(handler) => tokio::spawn(
+                CURRENT_JOB
+                    .scope(job.id, handler(job.payload.clone()))
+                    .instrument(span.clone()),
+            )
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Job handler panicked: {}", e))),
+            None => Err(anyhow::anyhow!(
+                "No handler registered for job kind '{}'",
+                job.kind
+            )),
+        };
+
+        if outcome.is_err() {
+            span.record("otel.status_code", "ERROR");
+        }
+
+        let recorded = match outcome {
+            Ok(result) => {
+                info!("{} job {} succeeded", job.kind, job.id);
+                self.database.complete_job(job.id, Some(&result)).await
+            }
+            Err(e) => {
+                let retry_at = (job.attempts < job.max_attempts).then(|| {
+                    let delay = chrono::Duration::from_std(self.config.retry_base_delay)
+                        .unwrap_or_else(|_| chrono::Duration::seconds(30));
+                    Utc::now() + retry_backoff(delay, job.attempts)
+                });
+                match retry_at {
+                    Some(retry_at) => warn!(
+                        "{} job {} failed (attempt {}), retrying at {}: {}",
+                        job.kind, job.id, job.attempts, retry_at, e
+                    ),
+                    None => {
+                        error!(
+                            "{} job {} failed after {} attempts, moved to dead letters: {}",
+                            job.kind, job.id, job.attempts, e
+                        );
+                        if let Some(events) = &self.events {
+                            events.publish(AdminEvent::JobDead {
+                                job_id: job.id,
+                                kind: job.kind.clone(),
+                                error: e.to_string(),
+                            });
+                        }
+                    }
+                }
+                self.database
+                    .fail_job(job.id, &format!("{:#}", e), retry_at)
+                    .await
+            }
+        };
+
+        if let Err(e) = recorded {
+            error!("Failed to record outcome of job {}: {}", job.id, e);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use reqwest::{Client, StatusCode};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, warn};
+
+use crate::models::{BrokenLinkReport, CreateLinkCheck, LinkCheckSummary, LinkType, PostFilters};
+use crate::services::DatabaseService;
+
+/// Maximum number of links checked at the same time
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Per-request timeout for link checks
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Service that finds broken links in published posts
+#[derive(Clone)]
+pub struct LinkCheckService {
+    database: DatabaseService,
+    client: Client,
+    site_url: String,
+    concurrency: usize,
+}
+
+impl LinkCheckService {
+    /// Create a new link check service; internal links are resolved against `site_url`
+    pub fn new(database: DatabaseService, site_url: impl Into<String>) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("tobelog-link-checker/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to build HTTP client for link checks")?;
+
+        Ok(Self {
+            database,
+            client,
+            site_url: site_url.into().trim_end_matches('/').to_string(),
+            concurrency: DEFAULT_CONCURRENCY,
+        })
+    }
+
+    /// Run the checker every `interval`, starting one interval after startup
+    pub fn spawn_periodic(self, interval: Duration) {
+        info!("Scheduling link checks every {:?}", interval);
+
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now() + interval;
+            let mut ticker = tokio::time::interval_at(start, interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.check_all_posts().await {
+                    error!("Scheduled link check failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Check every link of every published post and record the results
+    pub async fn check_all_posts(&self) -> Result<LinkCheckSummary> {
+        let started_at = Utc::now();
+        info!("Starting link check run");
+
+        let posts = self
+            .database
+            .list_posts(PostFilters {
+                published: Some(true),
+                ..Default::default()
+            })
+            .await?;
+
+        // Collect links per post and check each distinct URL only once
+        let post_links: Vec<_> = posts
+            .iter()
+            .map(|post| (post.id, extract_links(&post.html_content)))
+            .collect();
+
+        let unique_urls: BTreeSet<String> = post_links
+            .iter()
+            .flat_map(|(_, links)| links.iter().map(|link| self.resolve(link)))
+            .collect();
+
+        let results = self.check_urls(unique_urls).await;
+
+        let mut links_checked = 0;
+        let mut broken_links = 0;
+        for (post_id, links) in &post_links {
+            let checks: Vec<CreateLinkCheck> = links
+                .iter()
+                .filter_map(|link| {
+                    let (status_code, error) = results.get(&self.resolve(link))?.clone();
+                    let is_broken = is_broken(status_code, error.as_deref());
+                    Some(CreateLinkCheck {
+                        url: link.clone(),
+                        link_type: self.link_type(link),
+                        status_code,
+                        is_broken,
+                        error,
+                    })
+                })
+                .collect();
+
+            links_checked += checks.len();
+            broken_links += checks.iter().filter(|c| c.is_broken).count();
+
+            self.database.replace_link_checks(*post_id, &checks).await?;
+        }
+
+        let summary = LinkCheckSummary {
+            posts_checked: posts.len(),
+            links_checked,
+            broken_links,
+            started_at,
+            finished_at: Utc::now(),
+        };
+
+        info!(
+            "Link check finished: {} posts, {} links, {} broken",
+            summary.posts_checked, summary.links_checked, summary.broken_links
+        );
+        Ok(summary)
+    }
+
+    /// Broken links recorded by the last run, grouped by post
+    pub async fn get_report(&self) -> Result<Vec<BrokenLinkReport>> {
+        self.database.get_broken_link_report().await
+    }
+
+    /// Check URLs concurrently, returning status code or error per URL
+    async fn check_urls(
+        &self,
+        urls: BTreeSet<String>,
+    ) -> HashMap<String, (Option<u16>, Option<String>)> {
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+
+        for url in urls {
+            let client = self.client.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = check_url(&client, &url).await;
+                (url, result)
+            });
+        }
+
+        let mut results = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((url, Ok(status))) => {
+                    results.insert(url, (Some(status.as_u16()), None));
+                }
+                Ok((url, Err(e))) => {
+                    debug!("Link check request failed for {}: {}", url, e);
+                    results.insert(url, (None, Some(e.to_string())));
+                }
+                Err(e) => warn!("Link check task failed: {}", e),
+            }
+        }
+        results
+    }
+
+    /// Turn a root-relative link into an absolute URL
+    fn resolve(&self, link: &str) -> String {
+        if link.starts_with('/') {
+            format!("{}{}", self.site_url, link)
+        } else {
+            link.to_string()
+        }
+    }
+
+    fn link_type(&self, link: &str) -> LinkType {
+        if link.starts_with('/') || link.starts_with(&self.site_url) {
+            LinkType::Internal
+        } else {
+            LinkType::External
+        }
+    }
+}
+
+/// Issue a HEAD request, falling back to GET for servers that reject HEAD
+async fn check_url(client: &Client, url: &str) -> reqwest::Result<StatusCode> {
+    match client.head(url).send().await {
+        Ok(response)
+            if !matches!(
+                response.status(),
+                StatusCode::METHOD_NOT_ALLOWED
+                    | StatusCode::NOT_IMPLEMENTED
+                    | StatusCode::FORBIDDEN
+            ) =>
+        {
+            Ok(response.status())
+        }
+        _ => Ok(client.get(url).send().await?.status()),
+    }
+}
+
+fn is_broken(status_code: Option<u16>, error: Option<&str>) -> bool {
+    error.is_some() || status_code.is_none_or(|code| code >= 400)
+}
+
+/// Extract checkable links (absolute http(s) and root-relative) from rendered HTML
+fn extract_links(html: &str) -> Vec<String> {
+    static HREF: OnceLock<Regex> = OnceLock::new();
+    let href = HREF.get_or_init(|| {
+        Regex::new(r#"(?i)<a\s[^>]*?href\s*=\s*["']([^"']+)["']"#).expect("valid href regex")
+    });
+
+    let mut links = BTreeSet::new();
+    for capture in href.captures_iter(html) {
+        let link = html_escape::decode_html_entities(capture[1].trim()).to_string();
+        // Fragments only matter within the page itself
+        let link = link.split('#').next().unwrap_or_default().to_string();
+
+        if link.starts_with("http://")
+            || link.starts_with("https://")
+            || (link.starts_with('/') && !link.starts_with("//"))
+        {
+            links.insert(link);
+        }
+    }
+    links.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links_filters_and_dedupes() {
+        let html = r##"<p><a href="https://example.com/a">A</a>
+            <a class="x" href='/posts/2024/hello#intro'>B</a>
+            <a href="#top">top</a><a href="mailto:me@example.com">mail</a>
+            <a href="https://example.com/a">A again</a>
+            <a href="https://example.com/?a=1&amp;b=2">Q</a></p>"##;
+
+        assert_eq!(
+            extract_links(html),
+            vec![
+                "/posts/2024/hello".to_string(),
+                "https://example.com/?a=1&b=2".to_string(),
+                "https://example.com/a".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_broken() {
+        assert!(!is_broken(Some(200), None));
+        assert!(!is_broken(Some(301), None));
+        assert!(is_broken(Some(404), None));
+        assert!(is_broken(None, Some("timed out")));
+    }
+}
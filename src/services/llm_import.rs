@@ -1,18 +1,49 @@
+use std::fmt;
+
 use chrono::Utc;
 use regex::Regex;
 use tracing::{debug, warn};
 
 use crate::models::{
-    BatchImportRequest, BatchImportResponse, CreatePost, ImportError, ImportSummary,
-    LLMArticleImportRequest, LLMArticleImportResponse, LLMSuggestedMetadata,
+    content_similarity, CreatePost, ImportError, ImportQualityGates, LLMArticleImportRequest,
+    LLMArticleImportResponse, LLMSuggestedMetadata, PostFilters, QualityGateViolation,
 };
-use crate::services::{DatabaseService, MarkdownService};
+use crate::services::{DatabaseService, MarkdownService, ProofreadService};
 
 /// LLM記事インポート処理サービス
 #[derive(Clone)]
 pub struct LLMImportService {
     markdown_service: MarkdownService,
     database_service: DatabaseService,
+    proofreader: Option<ProofreadService>,
+    quality_gates: ImportQualityGates,
+}
+
+/// 品質ゲートを通過できなかったため保存を拒否したことを示すエラー
+#[derive(Debug, Clone)]
+pub struct QualityGateError {
+    pub violations: Vec<QualityGateViolation>,
+}
+
+impl fmt::Display for QualityGateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<&str> = self.violations.iter().map(|v| v.message.as_str()).collect();
+        write!(
+            f,
+            "品質ゲートを通過できませんでした: {}",
+            messages.join(" / ")
+        )
+    }
+}
+
+impl std::error::Error for QualityGateError {}
+
+/// バッチ内の1記事の処理結果
+#[derive(Debug, Clone)]
+pub enum BatchArticleOutcome {
+    Imported(Box<LLMArticleImportResponse>),
+    Duplicate(ImportError),
+    Failed(ImportError),
 }
 
 impl LLMImportService {
@@ -20,7 +51,65 @@ impl LLMImportService {
         Self {
             markdown_service,
             database_service,
+            proofreader: None,
+            quality_gates: ImportQualityGates::default(),
+        }
+    }
+
+    /// 校正ステップを有効化
+    pub fn with_proofreader(mut self, proofreader: ProofreadService) -> Self {
+        self.proofreader = Some(proofreader);
+        self
+    }
+
+    /// 保存前に適用する品質ゲートを設定
+    pub fn with_quality_gates(mut self, quality_gates: ImportQualityGates) -> Self {
+        self.quality_gates = quality_gates;
+        self
+    }
+
+    /// 品質ゲートを評価し、違反があれば一覧を返す
+    pub async fn check_quality_gates(
+        &self,
+        import_response: &LLMArticleImportResponse,
+    ) -> Result<Vec<QualityGateViolation>, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.quality_gates.is_enabled() {
+            return Ok(Vec::new());
         }
+
+        let closest = match self.quality_gates.max_similarity {
+            Some(_) => {
+                self.most_similar_post(&import_response.formatted_content)
+                    .await?
+            }
+            None => None,
+        };
+
+        Ok(self.quality_gates.evaluate(
+            import_response,
+            closest
+                .as_ref()
+                .map(|(slug, score)| (slug.as_str(), *score)),
+        ))
+    }
+
+    /// 既存記事のうち最も内容が似ている記事のスラグと類似度
+    async fn most_similar_post(
+        &self,
+        content: &str,
+    ) -> Result<Option<(String, f64)>, Box<dyn std::error::Error + Send + Sync>> {
+        let posts = self
+            .database_service
+            .list_posts(PostFilters::default())
+            .await?;
+
+        Ok(posts
+            .into_iter()
+            .map(|post| {
+                let score = content_similarity(content, &post.content);
+                (post.slug, score)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1)))
     }
 
     /// 単一の記事をインポート処理
@@ -66,6 +155,12 @@ impl LLMImportService {
         // 9. プレビューURLの生成
         let preview_url = format!("/posts/{}/{}", Utc::now().format("%Y"), slug);
 
+        // 10. 校正（設定されている場合のみ）
+        let quality = match &self.proofreader {
+            Some(proofreader) => Some(proofreader.check(&formatted_content).await),
+            None => None,
+        };
+
         Ok(LLMArticleImportResponse {
             slug,
             suggested_metadata,
@@ -73,53 +168,33 @@ impl LLMImportService {
             html_content,
             preview_url,
             dropbox_path,
+            quality,
         })
     }
 
-    /// バッチインポート処理
-    pub async fn process_batch_import(&self, request: BatchImportRequest) -> BatchImportResponse {
-        let total_attempted = request.articles.len();
-        let mut successful = Vec::new();
-        let mut failed = Vec::new();
-        let mut duplicates_detected = 0;
-
-        for article in request.articles {
-            let content_preview = article.content.chars().take(100).collect::<String>();
-
-            // 重複チェック
-            if self.check_duplicate_content(&article.content).await {
-                duplicates_detected += 1;
-                failed.push(ImportError {
-                    content_preview,
-                    error_message: "重複するコンテンツが検出されました".to_string(),
-                    source: article.source.clone(),
-                });
-                continue;
-            }
-
-            match self.process_single_article(article).await {
-                Ok(result) => successful.push(result),
-                Err(e) => {
-                    failed.push(ImportError {
-                        content_preview,
-                        error_message: e.to_string(),
-                        source: "unknown".to_string(),
-                    });
-                }
-            }
+    /// バッチ内の1記事を処理（重複チェックを含む）
+    pub async fn process_batch_article(
+        &self,
+        article: LLMArticleImportRequest,
+    ) -> BatchArticleOutcome {
+        let content_preview = article.content.chars().take(100).collect::<String>();
+
+        // 重複チェック
+        if self.check_duplicate_content(&article.content).await {
+            return BatchArticleOutcome::Duplicate(ImportError {
+                content_preview,
+                error_message: "重複するコンテンツが検出されました".to_string(),
+                source: article.source.clone(),
+            });
         }
 
-        let summary = ImportSummary {
-            total_attempted,
-            successful: successful.len(),
-            failed: failed.len(),
-            duplicates_detected,
-        };
-
-        BatchImportResponse {
-            successful,
-            failed,
-            summary,
+        match self.process_single_article(article).await {
+            Ok(result) => BatchArticleOutcome::Imported(Box::new(result)),
+            Err(e) => BatchArticleOutcome::Failed(ImportError {
+                content_preview,
+                error_message: e.to_string(),
+                source: "unknown".to_string(),
+            }),
         }
     }
 
@@ -150,8 +225,8 @@ impl LLMImportService {
         for line in content.lines() {
             let line = line.trim();
             if !line.is_empty() && !line.starts_with('#') {
-                let title = if line.len() > 50 {
-                    format!("{}...", &line[..47])
+                let title = if line.chars().count() > 50 {
+                    format!("{}...", line.chars().take(47).collect::<String>())
                 } else {
                     line.to_string()
                 };
@@ -241,8 +316,8 @@ impl LLMImportService {
         for line in content.lines() {
             let line = line.trim();
             if !line.is_empty() && !line.starts_with('#') && !line.starts_with("```") {
-                if line.len() > 200 {
-                    return Some(format!("{}...", &line[..197]));
+                if line.chars().count() > 200 {
+                    return Some(format!("{}...", line.chars().take(197).collect::<String>()));
                 } else {
                     return Some(line.to_string());
                 }
@@ -396,12 +471,28 @@ impl LLMImportService {
         false // 現在は常にfalseを返す
     }
 
-    /// CreatePostを生成してデータベースに保存
+    /// 品質ゲートを確認した上でCreatePostを生成してデータベースに保存
+    ///
+    /// ゲートに違反した場合は [`QualityGateError`] を返す。`override_gates` は
+    /// 管理者が違反を承知で保存する場合のみ指定する
     pub async fn save_imported_article(
         &self,
         import_response: LLMArticleImportResponse,
         published: bool,
+        override_gates: bool,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let violations = self.check_quality_gates(&import_response).await?;
+        if !violations.is_empty() {
+            if !override_gates {
+                return Err(Box::new(QualityGateError { violations }));
+            }
+            warn!(
+                "品質ゲートを上書きして保存します: slug={}, violations={}",
+                import_response.slug,
+                violations.len()
+            );
+        }
+
         let create_post = CreatePost {
             slug: import_response.slug,
             title: import_response.suggested_metadata.title,
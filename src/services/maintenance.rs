@@ -0,0 +1,160 @@
+use chrono::Utc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+use crate::models::{MaintenanceStatus, UpdateMaintenance};
+
+/// Maintenance mode switch shared by the middleware and the admin API
+///
+/// Starts from `MAINTENANCE_MODE` and can be flipped at runtime. The runtime
+/// setting lives in memory only, so a restart goes back to the configured
+/// value; keep `MAINTENANCE_MODE=true` set for maintenance spanning restarts.
+#[derive(Clone)]
+pub struct MaintenanceService {
+    status: Arc<RwLock<MaintenanceStatus>>,
+    /// `MAINTENANCE_MODE` as last loaded from the config
+    configured: Arc<AtomicBool>,
+}
+
+impl MaintenanceService {
+    pub fn new(enabled: bool, retry_after_secs: u64) -> Self {
+        Self {
+            status: Arc::new(RwLock::new(MaintenanceStatus {
+                enabled,
+                retry_after_secs,
+                message: None,
+                since: enabled.then(Utc::now),
+            })),
+            configured: Arc::new(AtomicBool::new(enabled)),
+        }
+    }
+
+    /// Apply a reloaded config
+    ///
+    /// The mode only follows `MAINTENANCE_MODE` when that value changed, so a
+    /// reload doesn't undo a switch made through the admin API.
+    pub fn apply_config(&self, enabled: bool, retry_after_secs: u64) {
+        let previous = self.configured.swap(enabled, Ordering::Relaxed);
+        let status = self.status();
+        self.update(UpdateMaintenance {
+            enabled: if enabled != previous {
+                enabled
+            } else {
+                status.enabled
+            },
+            retry_after_secs: Some(retry_after_secs),
+            message: status.message,
+        });
+    }
+
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .enabled
+    }
+
+    /// Apply an update and return the new status
+    pub fn update(&self, update: UpdateMaintenance) -> MaintenanceStatus {
+        let mut status = self
+            .status
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if update.enabled && !status.enabled {
+            status.since = Some(Utc::now());
+        } else if !update.enabled {
+            status.since = None;
+        }
+        if update.enabled != status.enabled {
+            info!(
+                "Maintenance mode {}",
+                if update.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+        status.enabled = update.enabled;
+        if let Some(retry_after_secs) = update.retry_after_secs {
+            status.retry_after_secs = retry_after_secs;
+        }
+        status.message = update
+            .message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty());
+
+        status.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_tracks_since_and_keeps_retry_after() {
+        let service = MaintenanceService::new(false, 300);
+        assert!(service.status().since.is_none());
+
+        let status = service.update(UpdateMaintenance {
+            enabled: true,
+            retry_after_secs: None,
+            message: Some("  Upgrading the database  ".to_string()),
+        });
+        assert!(status.enabled);
+        assert_eq!(status.retry_after_secs, 300);
+        assert_eq!(status.message.as_deref(), Some("Upgrading the database"));
+        let since = status.since.unwrap();
+
+        // Staying on keeps the original start time
+        let status = service.update(UpdateMaintenance {
+            enabled: true,
+            retry_after_secs: Some(60),
+            message: Some(String::new()),
+        });
+        assert_eq!(status.since, Some(since));
+        assert_eq!(status.retry_after_secs, 60);
+        assert!(status.message.is_none());
+
+        let status = service.update(UpdateMaintenance {
+            enabled: false,
+            retry_after_secs: None,
+            message: None,
+        });
+        assert!(!status.enabled);
+        assert!(status.since.is_none());
+        assert!(!service.is_enabled());
+    }
+
+    #[test]
+    fn test_apply_config_only_follows_changed_setting() {
+        let service = MaintenanceService::new(false, 300);
+        service.update(UpdateMaintenance {
+            enabled: true,
+            retry_after_secs: None,
+            message: Some("Migrating".to_string()),
+        });
+
+        // Unchanged MAINTENANCE_MODE=false keeps the runtime switch
+        service.apply_config(false, 600);
+        let status = service.status();
+        assert!(status.enabled);
+        assert_eq!(status.retry_after_secs, 600);
+        assert_eq!(status.message.as_deref(), Some("Migrating"));
+
+        service.apply_config(true, 600);
+        assert!(service.is_enabled());
+        service.apply_config(false, 600);
+        assert!(!service.is_enabled());
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use pulldown_cmark::{html, Options, Parser};
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, warn};
@@ -48,7 +48,7 @@ impl MarkdownService {
 
     /// Detect frontmatter format
     fn detect_frontmatter_format(&self, content: &str) -> FrontmatterFormat {
-        let trimmed = content.trim_start();
+        let trimmed = content.trim_ascii_start();
         
         if trimmed.starts_with("---") {
             FrontmatterFormat::Yaml
@@ -63,8 +63,7 @@ impl MarkdownService {
     }
 
     /// Extract frontmatter from markdown content (supports YAML, TOML, JSON)
-    #[allow(dead_code)]
-    fn extract_frontmatter(
+    pub fn extract_frontmatter(
         &self,
         content: &str,
     ) -> Result<(HashMap<String, serde_yaml::Value>, String)> {
@@ -86,16 +85,16 @@ impl MarkdownService {
         &self,
         content: &str,
     ) -> Result<(HashMap<String, serde_yaml::Value>, String)> {
-        let content = content.trim();
+        // Only ASCII whitespace surrounds the fences; other Unicode spaces such as
+        // U+00A0 can end an unquoted value and must survive
+        let content = content.trim_ascii();
 
-        let parts: Vec<&str> = content.splitn(3, "---").collect();
-        if parts.len() < 3 {
+        let Some((frontmatter_str, markdown_content)) = split_frontmatter(content, "---") else {
             warn!("Invalid YAML frontmatter format");
             return Ok((HashMap::new(), content.to_string()));
-        }
-
-        let frontmatter_str = parts[1].trim();
-        let markdown_content = parts[2].trim();
+        };
+        let frontmatter_str = frontmatter_str.trim_ascii();
+        let markdown_content = markdown_content.trim_ascii();
 
         let frontmatter: HashMap<String, serde_yaml::Value> = if frontmatter_str.is_empty() {
             HashMap::new()
@@ -118,16 +117,14 @@ impl MarkdownService {
         &self,
         content: &str,
     ) -> Result<(HashMap<String, serde_yaml::Value>, String)> {
-        let content = content.trim();
+        let content = content.trim_ascii();
 
-        let parts: Vec<&str> = content.splitn(3, "+++").collect();
-        if parts.len() < 3 {
+        let Some((frontmatter_str, markdown_content)) = split_frontmatter(content, "+++") else {
             warn!("Invalid TOML frontmatter format");
             return Ok((HashMap::new(), content.to_string()));
-        }
-
-        let frontmatter_str = parts[1].trim();
-        let markdown_content = parts[2].trim();
+        };
+        let frontmatter_str = frontmatter_str.trim_ascii();
+        let markdown_content = markdown_content.trim_ascii();
 
         // Parse TOML and convert to serde_yaml::Value
         let toml_value: toml::Value = match toml::from_str(frontmatter_str) {
@@ -153,7 +150,7 @@ impl MarkdownService {
         &self,
         content: &str,
     ) -> Result<(HashMap<String, serde_yaml::Value>, String)> {
-        let content = content.trim_start();
+        let content = content.trim_ascii_start();
         
         // Try to find the end of JSON by looking for balanced braces
         // This is a simplified but more robust approach than manual parsing
@@ -191,7 +188,7 @@ impl MarkdownService {
         }
         
         let frontmatter_str = &content[..json_end];
-        let markdown_content = content[json_end..].trim();
+        let markdown_content = content[json_end..].trim_ascii();
         
         // Parse JSON and convert to serde_yaml::Value
         let json_value: serde_json::Value = match serde_json::from_str(frontmatter_str) {
@@ -325,6 +322,32 @@ impl MarkdownService {
         self.extract_frontmatter_field::<String>(frontmatter, "excerpt")
     }
 
+    /// Plain text of the first paragraph, cut to `max_chars` characters
+    pub fn first_paragraph_excerpt(&self, markdown: &str, max_chars: usize) -> Option<String> {
+        let mut text = String::new();
+        let mut in_paragraph = false;
+        for event in Parser::new(markdown) {
+            match event {
+                Event::Start(Tag::Paragraph) => in_paragraph = true,
+                Event::End(TagEnd::Paragraph) if !text.trim().is_empty() => break,
+                Event::End(TagEnd::Paragraph) => in_paragraph = false,
+                Event::Text(t) | Event::Code(t) if in_paragraph => text.push_str(&t),
+                Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+                _ => {}
+            }
+        }
+
+        let text = text.trim();
+        if text.is_empty() {
+            None
+        } else if text.chars().count() > max_chars {
+            let truncated: String = text.chars().take(max_chars).collect();
+            Some(format!("{}...", truncated.trim_end()))
+        } else {
+            Some(text.to_string())
+        }
+    }
+
     /// Generate excerpt from content if not provided in frontmatter
     #[allow(dead_code)]
     pub fn generate_excerpt(&self, content: &str, max_words: usize) -> String {
@@ -345,6 +368,21 @@ impl Default for MarkdownService {
     }
 }
 
+/// Split `content` opened by `delimiter` at the first line holding only the
+/// delimiter, so values and body text containing it stay intact
+fn split_frontmatter<'a>(content: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let rest = content.strip_prefix(delimiter)?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        // The first "line" is the remainder of the opening delimiter line
+        if offset > 0 && line.trim_ascii() == delimiter {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,6 +443,23 @@ This is a test post."#;
         assert_eq!(excerpt, "This is a long piece...");
     }
 
+    #[test]
+    fn test_first_paragraph_excerpt_skips_headings() {
+        let service = MarkdownService::new();
+        let content =
+            "# 見出し\n\n最初の段落は **強調** と `code` を含む。\n次の行。\n\n二つ目の段落";
+
+        assert_eq!(
+            service.first_paragraph_excerpt(content, 200).as_deref(),
+            Some("最初の段落は 強調 と code を含む。 次の行。")
+        );
+        assert_eq!(
+            service.first_paragraph_excerpt(content, 6).as_deref(),
+            Some("最初の段落は...")
+        );
+        assert_eq!(service.first_paragraph_excerpt("# Only a title", 200), None);
+    }
+
     // 新しいテスト: TOMLフロントマター対応
     #[test]
     fn test_parse_markdown_with_toml_frontmatter() {
@@ -1,48 +1,156 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use axum_extra::extract::multipart::Field;
+use base64::{engine::general_purpose::STANDARD, Engine};
 use chrono::Utc;
 use image::{DynamicImage, ImageFormat};
+use regex::Regex;
 use sha2::{Digest, Sha256};
 use std::io::Cursor;
+use std::sync::OnceLock;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::models::media::{
-    CreateMediaFile, ImageProcessingConfig, MediaConstraints, MediaFile, MediaFilters, MediaType,
+    CreateMediaFile, FeaturedImage, FocalPoint, ImageProcessingConfig, MediaConstraints, MediaFile,
+    MediaFilters, MediaType,
 };
-use crate::services::{BlogStorageService, DatabaseService, DropboxClient};
+use crate::services::mime_sniff::verify_content_type;
+use crate::services::svg_sanitize::sanitize_svg;
+use crate::services::video::{self, VideoMetadata};
+use crate::services::{
+    AltTextService, BlogStorageService, DatabaseService, StorageBackend, StorageQuotaService,
+};
+
+const SVG_MIME_TYPE: &str = "image/svg+xml";
+
+/// Storage folder that `/media/...` URLs are served from
+const MEDIA_ROOT: &str = "/BlogStorage/media";
+
+/// Error of a `/media/...` request whose path could reach outside the media folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMediaPath {
+    pub path: String,
+}
+
+impl std::fmt::Display for InvalidMediaPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Debug formatting escapes control characters before they reach the logs
+        write!(f, "Invalid media path {:?}", self.path)
+    }
+}
+
+impl std::error::Error for InvalidMediaPath {}
+
+impl InvalidMediaPath {
+    /// The `InvalidMediaPath` behind an error, if a rejected path caused it
+    pub fn find(error: &anyhow::Error) -> Option<&InvalidMediaPath> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+}
+
+/// Map a path below `/media` onto the storage path of the file
+///
+/// The path is relative to the media folder, with or without a leading `/`.
+/// Only plain segments are accepted: no `.` or `..`, no empty segments, no
+/// backslashes, colons or control characters, so the result always stays
+/// under the media folder.
+pub fn media_storage_path(path: &str) -> Result<String, InvalidMediaPath> {
+    let relative = path.strip_prefix('/').unwrap_or(path);
+    let valid = !relative.is_empty()
+        && relative.split('/').all(|segment| {
+            !matches!(segment, "" | "." | "..")
+                && !segment.contains(['\\', ':'])
+                && !segment.chars().any(char::is_control)
+        });
+    if !valid {
+        return Err(InvalidMediaPath {
+            path: path.to_string(),
+        });
+    }
+    Ok(format!("{}/{}", MEDIA_ROOT, relative))
+}
+
+/// A file from an upload form, read into memory
+///
+/// Multipart fields have to be consumed before the next one can be read, so
+/// handlers that look at later fields first buffer the file this way.
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl UploadedFile {
+    pub async fn from_field(mut field: Field) -> Result<Self> {
+        let filename = field
+            .file_name()
+            .ok_or_else(|| anyhow!("No filename provided"))?
+            .to_string();
+        let content_type = field
+            .content_type()
+            .map(|ct| ct.to_string())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            data.extend_from_slice(&chunk);
+        }
+
+        Ok(Self {
+            filename,
+            content_type,
+            data,
+        })
+    }
+}
 
 #[derive(Clone)]
 pub struct MediaService {
-    dropbox_client: std::sync::Arc<DropboxClient>,
+    storage: std::sync::Arc<dyn StorageBackend>,
     #[allow(dead_code)]
     blog_storage: std::sync::Arc<BlogStorageService>,
     database: DatabaseService,
     constraints: MediaConstraints,
     image_config: ImageProcessingConfig,
+    alt_text: Option<AltTextService>,
+    quota: Option<StorageQuotaService>,
 }
 
 impl MediaService {
     pub fn new(
-        dropbox_client: std::sync::Arc<DropboxClient>,
+        storage: std::sync::Arc<dyn StorageBackend>,
         blog_storage: std::sync::Arc<BlogStorageService>,
         database: DatabaseService,
     ) -> Self {
         Self {
-            dropbox_client,
+            storage,
             blog_storage,
             database,
             constraints: MediaConstraints::default(),
             image_config: ImageProcessingConfig::default(),
+            alt_text: None,
+            quota: None,
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_constraints(mut self, constraints: MediaConstraints) -> Self {
         self.constraints = constraints;
         self
     }
 
+    /// Suggest alt text for images uploaded without it
+    pub fn with_alt_text_suggestions(mut self, alt_text: AltTextService) -> Self {
+        self.alt_text = Some(alt_text);
+        self
+    }
+
+    /// Check the storage quota after each upload
+    pub fn with_storage_quota(mut self, quota: StorageQuotaService) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
     #[allow(dead_code)]
     pub fn with_image_config(mut self, config: ImageProcessingConfig) -> Self {
         self.image_config = config;
@@ -50,36 +158,37 @@ impl MediaService {
     }
 
     /// Upload a media file from multipart field
+    ///
+    /// Uploads whose bytes match an existing file return that record instead of storing a
+    /// duplicate, unless `force_new` is set. The flag in the result tells whether an
+    /// existing record was reused.
     pub async fn upload_file(
         &self,
-        mut field: Field,
+        field: Field,
         alt_text: Option<String>,
         caption: Option<String>,
-    ) -> Result<MediaFile> {
-        // Get field information
-        let filename = field
-            .file_name()
-            .ok_or_else(|| anyhow!("No filename provided"))?
-            .to_string();
+        force_new: bool,
+    ) -> Result<(MediaFile, bool)> {
+        let file = UploadedFile::from_field(field).await?;
+        self.upload_data(file, alt_text, caption, force_new).await
+    }
 
-        let content_type = field
-            .content_type()
-            .map(|ct| ct.to_string())
-            .unwrap_or_else(|| "application/octet-stream".to_string());
+    /// Upload a media file read into memory, as [`MediaService::upload_file`]
+    pub async fn upload_data(
+        &self,
+        file: UploadedFile,
+        alt_text: Option<String>,
+        caption: Option<String>,
+        force_new: bool,
+    ) -> Result<(MediaFile, bool)> {
+        let UploadedFile {
+            filename,
+            content_type,
+            data: mut file_data,
+        } = file;
 
         info!("Uploading file: {} ({})", filename, content_type);
 
-        // Validate MIME type
-        if !self.constraints.allowed_mime_types.contains(&content_type) {
-            return Err(anyhow!("File type '{}' not allowed", content_type));
-        }
-
-        // Read file data
-        let mut file_data = Vec::new();
-        while let Some(chunk) = field.chunk().await? {
-            file_data.extend_from_slice(&chunk);
-        }
-
         // Validate file size
         if file_data.len() as u64 > self.constraints.max_file_size {
             return Err(anyhow!(
@@ -89,6 +198,40 @@ impl MediaService {
             ));
         }
 
+        // Don't trust the client's type: check it against the content and extension
+        let content_type = verify_content_type(&content_type, &filename, &file_data)?;
+
+        // Validate MIME type
+        if !self.constraints.allowed_mime_types.contains(&content_type)
+            || (content_type == SVG_MIME_TYPE && !self.constraints.allow_svg)
+        {
+            return Err(anyhow!("File type '{}' not allowed", content_type));
+        }
+
+        // SVG can carry scripts; keep only inert content before hashing and storing
+        if content_type == SVG_MIME_TYPE {
+            let svg =
+                String::from_utf8(file_data).map_err(|_| anyhow!("SVG file is not valid UTF-8"))?;
+            file_data = sanitize_svg(&svg)?.into_bytes();
+        }
+
+        // Reuse an identical earlier upload
+        let content_hash = format!("{:x}", Sha256::digest(&file_data));
+        if !force_new {
+            if let Some(existing) = self
+                .database
+                .find_media_file_by_hash(&content_hash)
+                .await
+                .map_err(|e| anyhow!("Failed to look up duplicate media: {}", e))?
+            {
+                info!(
+                    "Upload of {} matches existing media {}, skipping storage",
+                    filename, existing.filename
+                );
+                return Ok((existing, true));
+            }
+        }
+
         // Generate unique filename
         let media_type = MediaType::from_mime_type(&content_type);
         let unique_filename = self.generate_unique_filename(&filename)?;
@@ -104,24 +247,46 @@ impl MediaService {
             folder_name, year, month, unique_filename
         );
 
-        // Process image if it's an image file
-        let (processed_data, width, height, thumbnail_data) = if media_type == MediaType::Image {
-            self.process_image(&file_data, &content_type).await?
-        } else {
-            (file_data, None, None, None)
-        };
-
-        // Upload main file to Dropbox
-        self.upload_to_dropbox(&dropbox_path, &processed_data)
+        let mut thumbnail_filename = format!("thumb_{}", unique_filename);
+        let mut duration_seconds = None;
+
+        // Process raster images (SVG is stored as sanitized source) and probe videos
+        let (processed_data, width, height, thumbnail_data) =
+            if media_type == MediaType::Image && content_type != SVG_MIME_TYPE {
+                self.process_image(&file_data, &content_type).await?
+            } else if media_type == MediaType::Video && video::is_supported() {
+                let extension = std::path::Path::new(&unique_filename)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("mp4");
+                let metadata = video::extract_metadata(&file_data, extension)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Failed to extract video metadata: {}", e);
+                        VideoMetadata::default()
+                    });
+
+                // The poster frame is a JPEG whatever the video container
+                if let Some((stem, _)) = thumbnail_filename.rsplit_once('.') {
+                    thumbnail_filename = format!("{}.jpg", stem);
+                }
+                duration_seconds = metadata.duration_seconds;
+                (file_data, metadata.width, metadata.height, metadata.poster)
+            } else {
+                (file_data, None, None, None)
+            };
+
+        // Upload main file to storage
+        self.upload_to_storage(&dropbox_path, &processed_data)
             .await?;
 
         // Upload thumbnail if generated
         let thumbnail_url = if let Some(thumb_data) = thumbnail_data {
             let thumbnail_path = format!(
-                "/BlogStorage/media/thumbnails/{}/{}/{}/thumb_{}",
-                year, month, folder_name, unique_filename
+                "/BlogStorage/media/thumbnails/{}/{}/{}/{}",
+                year, month, folder_name, thumbnail_filename
             );
-            self.upload_to_dropbox(&thumbnail_path, &thumb_data).await?;
+            self.upload_to_storage(&thumbnail_path, &thumb_data).await?;
             Some(self.generate_media_url(&thumbnail_path))
         } else {
             None
@@ -143,13 +308,128 @@ impl MediaService {
             thumbnail_url,
             alt_text,
             caption,
+            content_hash: Some(content_hash),
+            duration_seconds,
         };
 
         // Save to database
         let media_file = self.save_to_database(create_data).await?;
 
+        // Describe raster images in the background so the upload isn't held up
+        if media_file.alt_text.is_none()
+            && media_type == MediaType::Image
+            && media_file.mime_type != SVG_MIME_TYPE
+        {
+            self.spawn_alt_text_suggestion(&media_file, processed_data);
+        }
+        if let Some(quota) = &self.quota {
+            quota.spawn_check();
+        }
+
         info!("Successfully uploaded file: {}", unique_filename);
-        Ok(media_file)
+        Ok((media_file, false))
+    }
+
+    /// Ask the vision model for alt text and store it as a pending suggestion
+    fn spawn_alt_text_suggestion(&self, media_file: &MediaFile, image: Vec<u8>) {
+        let Some(alt_text) = self.alt_text.clone() else {
+            return;
+        };
+        let database = self.database.clone();
+        let id = media_file.id;
+        let mime_type = media_file.mime_type.clone();
+
+        tokio::spawn(async move {
+            match alt_text.suggest(&image, &mime_type).await {
+                Ok(Some(suggestion)) => {
+                    match database.set_alt_text_suggestion(id, &suggestion).await {
+                        Ok(true) => info!("Stored alt text suggestion for media {}", id),
+                        Ok(false) => debug!("Alt text for media {} was set meanwhile", id),
+                        Err(e) => warn!("Failed to store alt text suggestion: {}", e),
+                    }
+                }
+                Ok(None) => debug!("No alt text suggested for media {}", id),
+                Err(e) => warn!("Alt text suggestion failed for media {}: {}", id, e),
+            }
+        });
+    }
+
+    /// Crop card and OG renditions of an image around a focal point and make it the
+    /// post's featured image
+    pub async fn set_featured_image(
+        &self,
+        post_id: Uuid,
+        media_file: &MediaFile,
+        focal_point: FocalPoint,
+    ) -> Result<FeaturedImage> {
+        if MediaType::from_mime_type(&media_file.mime_type) != MediaType::Image {
+            return Err(anyhow!(
+                "Media '{}' is not an image ({})",
+                media_file.filename,
+                media_file.mime_type
+            ));
+        }
+
+        let focal_point = focal_point.clamped();
+        let data = self
+            .storage
+            .download_file(&media_file.dropbox_path)
+            .await
+            .map_err(|e| anyhow!("Failed to download from storage: {}", e))?;
+        let img =
+            image::load_from_memory(&data).map_err(|e| anyhow!("Failed to parse image: {}", e))?;
+
+        let config = &self.image_config.featured_config;
+        let stem = std::path::Path::new(&media_file.filename)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("image");
+
+        let card_path = format!("/BlogStorage/media/featured/{}/card_{}.jpg", post_id, stem);
+        let card = crop_to_focal_point(&img, config.card_width, config.card_height, focal_point);
+        self.upload_to_storage(&card_path, &self.encode_image(&card, "image/jpeg")?)
+            .await?;
+
+        let og_path = format!("/BlogStorage/media/featured/{}/og_{}.jpg", post_id, stem);
+        let og = crop_to_focal_point(&img, config.og_width, config.og_height, focal_point);
+        self.upload_to_storage(&og_path, &self.encode_image(&og, "image/jpeg")?)
+            .await?;
+
+        let featured_image = FeaturedImage {
+            post_id,
+            media_id: media_file.id,
+            focal_point,
+            card_url: self.generate_media_url(&card_path),
+            og_url: self.generate_media_url(&og_path),
+            updated_at: Utc::now(),
+        };
+
+        self.database
+            .set_featured_image(&featured_image)
+            .await
+            .map_err(|e| anyhow!("Failed to save featured image: {}", e))?;
+
+        info!(
+            "Set featured image {} for post {}",
+            media_file.filename, post_id
+        );
+        Ok(featured_image)
+    }
+
+    /// Re-crop the featured images made from `media_file` around its focal point
+    ///
+    /// Returns the number of posts whose card and OG images were regenerated.
+    pub async fn recrop_featured_images(&self, media_file: &MediaFile) -> Result<usize> {
+        let post_ids = self
+            .database
+            .list_featured_image_posts(media_file.id)
+            .await?;
+        let focal_point = media_file.focal_point.unwrap_or_default();
+        for post_id in &post_ids {
+            self.set_featured_image(*post_id, media_file, focal_point)
+                .await?;
+        }
+        Ok(post_ids.len())
     }
 
     /// Generate a unique filename to avoid conflicts
@@ -333,8 +613,8 @@ impl MediaService {
         Ok(buffer)
     }
 
-    /// Upload data to Dropbox
-    async fn upload_to_dropbox(&self, path: &str, data: &[u8]) -> Result<()> {
+    /// Upload data to the storage backend
+    async fn upload_to_storage(&self, path: &str, data: &[u8]) -> Result<()> {
         // Create directory structure if needed
         let parent_dir = std::path::Path::new(path)
             .parent()
@@ -342,18 +622,18 @@ impl MediaService {
             .unwrap_or("");
 
         if !parent_dir.is_empty() {
-            if let Err(e) = self.dropbox_client.create_folder(parent_dir).await {
+            if let Err(e) = self.storage.create_folder(parent_dir).await {
                 warn!("Failed to create directory {}: {}", parent_dir, e);
             }
         }
 
         // Upload file
-        self.dropbox_client
+        self.storage
             .upload_binary_file(path, data)
             .await
-            .map_err(|e| anyhow!("Failed to upload to Dropbox: {}", e))?;
+            .map_err(|e| anyhow!("Failed to upload to storage: {}", e))?;
 
-        debug!("Uploaded to Dropbox: {}", path);
+        debug!("Uploaded to storage: {}", path);
         Ok(())
     }
 
@@ -388,6 +668,11 @@ impl MediaService {
             thumbnail_url: create_data.thumbnail_url,
             alt_text: create_data.alt_text,
             caption: create_data.caption,
+            content_hash: create_data.content_hash,
+            duration_seconds: create_data.duration_seconds,
+            alt_text_suggestion: None,
+            collection: None,
+            focal_point: None,
         };
 
         // Save to database (implementation will be added with database service)
@@ -423,6 +708,14 @@ impl MediaService {
             .map_err(|e| anyhow!("Failed to get media file: {}", e))
     }
 
+    /// Save edited alt text and caption
+    pub async fn update_media_metadata(&self, media_file: &MediaFile) -> Result<bool> {
+        self.database
+            .update_media_metadata(media_file)
+            .await
+            .map_err(|e| anyhow!("Failed to update media file: {}", e))
+    }
+
     /// Delete media file
     pub async fn delete_media_file(&self, id: Uuid) -> Result<bool> {
         let media_file = match self.get_media_file(id).await? {
@@ -430,22 +723,18 @@ impl MediaService {
             None => return Ok(false),
         };
 
-        // Delete from Dropbox
-        if let Err(e) = self
-            .dropbox_client
-            .delete_file(&media_file.dropbox_path)
-            .await
-        {
-            warn!("Failed to delete file from Dropbox: {}", e);
+        // Delete from storage
+        if let Err(e) = self.storage.delete_file(&media_file.dropbox_path).await {
+            warn!("Failed to delete file from storage: {}", e);
         }
 
         // Delete thumbnail if exists
         if let Some(thumbnail_url) = &media_file.thumbnail_url {
-            // Convert URL back to Dropbox path for deletion
+            // Convert URL back to a storage path for deletion
             // This is a simplified approach; in production, store thumbnail path separately
             let thumbnail_path = thumbnail_url.replace("/media", "/BlogStorage/media");
-            if let Err(e) = self.dropbox_client.delete_file(&thumbnail_path).await {
-                warn!("Failed to delete thumbnail from Dropbox: {}", e);
+            if let Err(e) = self.storage.delete_file(&thumbnail_path).await {
+                warn!("Failed to delete thumbnail from storage: {}", e);
             }
         }
 
@@ -459,15 +748,18 @@ impl MediaService {
         Ok(true)
     }
 
-    /// Serve media file from Dropbox
+    /// Serve media file from storage
+    ///
+    /// Paths that could leave the media folder fail with [`InvalidMediaPath`].
     pub async fn serve_media_file(&self, path: &str) -> Result<(Vec<u8>, String)> {
-        let dropbox_path = format!("/BlogStorage/media{}", path);
+        let dropbox_path = media_storage_path(path)?;
 
+        // Keep the cause so that callers can tell an unavailable backend apart
         let data = self
-            .dropbox_client
+            .storage
             .download_file(&dropbox_path)
             .await
-            .map_err(|e| anyhow!("Failed to download from Dropbox: {}", e))?;
+            .context("Failed to download from storage")?;
 
         // Determine MIME type from file extension
         let mime_type = self.get_mime_type_from_path(path);
@@ -497,8 +789,230 @@ impl MediaService {
             "pdf" => "application/pdf",
             "txt" => "text/plain",
             "md" => "text/markdown",
+            "zip" => "application/zip",
+            "gz" => "application/gzip",
+            "csv" => "text/csv",
+            "json" => "application/json",
             _ => "application/octet-stream",
         }
         .to_string()
     }
 }
+
+/// Collect the `/media/...` paths linked from Markdown or HTML, absolute URLs included
+pub fn extract_media_urls(content: &str) -> Vec<String> {
+    static MEDIA_URL: OnceLock<Regex> = OnceLock::new();
+    let pattern = MEDIA_URL.get_or_init(|| {
+        Regex::new(r#"(?:https?://[^/\s"'()<>]+)?(/media/[^\s"'()<>?#\]]+)"#)
+            .expect("valid media URL regex")
+    });
+
+    let mut urls: Vec<String> = pattern
+        .captures_iter(content)
+        .map(|captures| captures[1].to_string())
+        .collect();
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+/// Decode a pasted base64 image, returning the MIME type when `data` is a data URL
+pub fn decode_pasted_image(data: &str) -> Result<(Option<String>, Vec<u8>)> {
+    let data = data.trim();
+    let (content_type, encoded) = match data.strip_prefix("data:") {
+        Some(url) => {
+            let (meta, encoded) = url
+                .split_once(',')
+                .ok_or_else(|| anyhow!("Invalid data URL"))?;
+            let content_type = meta
+                .strip_suffix(";base64")
+                .ok_or_else(|| anyhow!("Data URL is not base64 encoded"))?;
+            (Some(content_type.to_string()), encoded)
+        }
+        None => (None, data),
+    };
+
+    // Editors and clipboards may wrap long base64 lines
+    let encoded: String = encoded.split_whitespace().collect();
+    let bytes = STANDARD
+        .decode(encoded)
+        .context("Invalid base64 image data")?;
+    Ok((content_type, bytes))
+}
+
+/// Filename for a pasted image that has none, like `paste-20240701-120000.png`
+pub fn pasted_image_filename(content_type: &str) -> String {
+    let extension = match content_type.strip_prefix("image/").unwrap_or("bin") {
+        "jpeg" => "jpg",
+        "svg+xml" => "svg",
+        subtype => subtype,
+    };
+    format!("paste-{}.{}", Utc::now().format("%Y%m%d-%H%M%S"), extension)
+}
+
+/// Markdown that embeds an image, with the alt text escaped
+pub fn image_markdown(alt: &str, url: &str) -> String {
+    let alt: String = alt
+        .chars()
+        .filter(|c| !c.is_control())
+        .flat_map(|c| match c {
+            '[' | ']' | '\\' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect();
+    if url.contains([' ', '(', ')']) {
+        format!("![{}](<{}>)", alt, url)
+    } else {
+        format!("![{}]({})", alt, url)
+    }
+}
+
+/// Largest region with the target aspect ratio that keeps the focal point as centered as
+/// the image bounds allow, as `(x, y, width, height)`
+fn focal_crop_region(
+    width: u32,
+    height: u32,
+    target_width: u32,
+    target_height: u32,
+    focal_point: FocalPoint,
+) -> (u32, u32, u32, u32) {
+    let target_ratio = target_width as f64 / target_height as f64;
+    let (crop_width, crop_height) = if width as f64 / height as f64 > target_ratio {
+        ((height as f64 * target_ratio).round() as u32, height)
+    } else {
+        (width, (width as f64 / target_ratio).round() as u32)
+    };
+    let (crop_width, crop_height) = (crop_width.clamp(1, width), crop_height.clamp(1, height));
+
+    let center_x = width as f64 * focal_point.x / 100.0;
+    let center_y = height as f64 * focal_point.y / 100.0;
+    let x = (center_x - crop_width as f64 / 2.0).clamp(0.0, (width - crop_width) as f64);
+    let y = (center_y - crop_height as f64 / 2.0).clamp(0.0, (height - crop_height) as f64);
+
+    (x.round() as u32, y.round() as u32, crop_width, crop_height)
+}
+
+/// Crop an image to the target aspect ratio around a focal point, then resize it
+fn crop_to_focal_point(
+    img: &DynamicImage,
+    target_width: u32,
+    target_height: u32,
+    focal_point: FocalPoint,
+) -> DynamicImage {
+    let (x, y, width, height) = focal_crop_region(
+        img.width(),
+        img.height(),
+        target_width,
+        target_height,
+        focal_point,
+    );
+
+    img.crop_imm(x, y, width, height).resize_exact(
+        target_width,
+        target_height,
+        image::imageops::FilterType::Lanczos3,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focal_crop_region_keeps_focal_point() {
+        // Wide image cropped to a square around a subject on the right
+        let region = focal_crop_region(2000, 1000, 600, 600, FocalPoint { x: 70.0, y: 50.0 });
+        assert_eq!(region, (900, 0, 1000, 1000));
+
+        // Focal point near the edge is clamped to the image bounds
+        let region = focal_crop_region(2000, 1000, 600, 600, FocalPoint { x: 100.0, y: 0.0 });
+        assert_eq!(region, (1000, 0, 1000, 1000));
+
+        // Tall image cropped to OG proportions around the default center
+        let region = focal_crop_region(1000, 2000, 1200, 630, FocalPoint::default());
+        assert_eq!(region, (0, 738, 1000, 525));
+    }
+
+    #[test]
+    fn test_media_storage_path_stays_under_media_root() {
+        assert_eq!(
+            media_storage_path("/images/2024/07/photo.jpg").unwrap(),
+            "/BlogStorage/media/images/2024/07/photo.jpg"
+        );
+        assert_eq!(
+            media_storage_path("thumbnails/thumb_photo.jpg").unwrap(),
+            "/BlogStorage/media/thumbnails/thumb_photo.jpg"
+        );
+
+        for path in [
+            "",
+            "/",
+            "../posts/draft.md",
+            "/images/../../posts/draft.md",
+            "images/./photo.jpg",
+            "images/..",
+            "//etc/passwd",
+            "images//photo.jpg",
+            "images/",
+            "..\\posts\\draft.md",
+            "C:/Windows/win.ini",
+            "images/photo.jpg\0",
+            "images/photo\n.jpg",
+        ] {
+            assert!(media_storage_path(path).is_err(), "{:?} was accepted", path);
+        }
+    }
+
+    #[test]
+    fn test_decode_pasted_image() {
+        let (content_type, bytes) =
+            decode_pasted_image("data:image/png;base64,iVBO\nRw==").unwrap();
+        assert_eq!(content_type.as_deref(), Some("image/png"));
+        assert_eq!(bytes, [0x89, b'P', b'N', b'G']);
+
+        let (content_type, bytes) = decode_pasted_image("R0lG").unwrap();
+        assert_eq!(content_type, None);
+        assert_eq!(bytes, b"GIF");
+
+        assert!(decode_pasted_image("data:image/png,raw").is_err());
+        assert!(decode_pasted_image("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_image_markdown_escapes_alt_text() {
+        assert_eq!(
+            image_markdown("a [b] c", "/media/images/2024/07/x.png"),
+            "![a \\[b\\] c](/media/images/2024/07/x.png)"
+        );
+        assert_eq!(
+            image_markdown("", "/media/a b.png"),
+            "![](</media/a b.png>)"
+        );
+        assert!(pasted_image_filename("image/jpeg").ends_with(".jpg"));
+        assert!(pasted_image_filename("image/svg+xml").starts_with("paste-"));
+    }
+
+    #[test]
+    fn test_extract_media_urls() {
+        let content = r#"![Cat](/media/images/2024/07/cat_1.jpg "title")
+<img src="https://blog.example.com/media/images/2024/07/dog_2.png?w=300">
+[pdf](/media/documents/2024/07/spec.pdf) and again /media/images/2024/07/cat_1.jpg
+![external](https://example.com/images/other.png)"#;
+
+        assert_eq!(
+            extract_media_urls(content),
+            vec![
+                "/media/documents/2024/07/spec.pdf",
+                "/media/images/2024/07/cat_1.jpg",
+                "/media/images/2024/07/dog_2.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crop_to_focal_point_output_size() {
+        let img = DynamicImage::new_rgb8(320, 240);
+        let cropped = crop_to_focal_point(&img, 120, 63, FocalPoint::default());
+        assert_eq!((cropped.width(), cropped.height()), (120, 63));
+    }
+}
@@ -0,0 +1,290 @@
+use std::path::Path;
+
+/// MIME type sent by clients that don't know what they are uploading
+const UNKNOWN_MIME_TYPE: &str = "application/octet-stream";
+
+/// Error of an upload whose bytes don't match its declared type or extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentTypeMismatch {
+    pub claimed: String,
+    /// Type recognized from the content, if any
+    pub detected: Option<String>,
+    pub filename: String,
+}
+
+impl std::fmt::Display for ContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.detected {
+            Some(detected) => write!(
+                f,
+                "'{}' was uploaded as {} but its content is {}",
+                self.filename, self.claimed, detected
+            ),
+            None => write!(
+                f,
+                "'{}' was uploaded as {} but its content is not",
+                self.filename, self.claimed
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ContentTypeMismatch {}
+
+impl ContentTypeMismatch {
+    /// The `ContentTypeMismatch` behind an error, if a rejected upload caused it
+    pub fn find(error: &anyhow::Error) -> Option<&ContentTypeMismatch> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+}
+
+/// Recognize a binary file format from its leading bytes, in canonical form
+///
+/// Text formats have no reliable signature and yield `None`, as does unknown
+/// content. Ogg is reported as `application/ogg` since the container holds
+/// both audio and video.
+pub fn sniff_mime_type(data: &[u8]) -> Option<String> {
+    let kind = infer::get(data).filter(|kind| kind.matcher_type() != infer::MatcherType::Text)?;
+    Some(match kind.mime_type() {
+        "audio/ogg" | "audio/opus" => "application/ogg".to_string(),
+        mime_type => canonical_mime_type(mime_type),
+    })
+}
+
+/// Lowercase MIME type without parameters, with common aliases replaced
+pub fn canonical_mime_type(content_type: &str) -> String {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match essence.as_str() {
+        "image/jpg" | "image/pjpeg" => "image/jpeg",
+        "audio/mp3" | "audio/mpeg3" | "audio/x-mpeg" => "audio/mpeg",
+        "audio/x-wav" | "audio/wave" | "audio/vnd.wave" => "audio/wav",
+        "audio/m4a" | "audio/x-m4a" => "audio/mp4",
+        "application/x-pdf" => "application/pdf",
+        "application/x-zip-compressed" | "application/x-zip" => "application/zip",
+        "application/x-gzip" => "application/gzip",
+        "text/x-markdown" => "text/markdown",
+        _ => return essence,
+    }
+    .to_string()
+}
+
+/// MIME types a file extension may hold, as served from the media routes
+fn extension_mime_types(filename: &str) -> Option<&'static [&'static str]> {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())?
+        .to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "jpg" | "jpeg" => &["image/jpeg"],
+        "png" => &["image/png"],
+        "gif" => &["image/gif"],
+        "webp" => &["image/webp"],
+        "svg" => &["image/svg+xml"],
+        "mp4" | "m4v" => &["video/mp4"],
+        "webm" => &["video/webm"],
+        "ogg" => &["video/ogg", "audio/ogg"],
+        "ogv" => &["video/ogg"],
+        "oga" => &["audio/ogg"],
+        "mp3" => &["audio/mpeg"],
+        "wav" => &["audio/wav"],
+        "pdf" => &["application/pdf"],
+        "txt" => &["text/plain"],
+        "md" | "markdown" => &["text/markdown"],
+        "zip" => &["application/zip"],
+        "gz" => &["application/gzip"],
+        "csv" => &["text/csv"],
+        "json" => &["application/json"],
+        _ => return None,
+    })
+}
+
+/// Whether `data` is readable text: UTF-8 without NUL bytes
+fn is_text(data: &[u8]) -> bool {
+    std::str::from_utf8(data).is_ok_and(|text| !text.contains('\0'))
+}
+
+/// Whether the content is a valid file of the text-based `mime_type`
+fn matches_text_type(mime_type: &str, data: &[u8]) -> bool {
+    if !is_text(data) {
+        return false;
+    }
+    match mime_type {
+        "image/svg+xml" => String::from_utf8_lossy(data).contains("<svg"),
+        "application/json" => serde_json::from_slice::<serde::de::IgnoredAny>(data).is_ok(),
+        _ => mime_type.starts_with("text/"),
+    }
+}
+
+/// Check an upload's content against its declared type and file extension
+///
+/// Returns the MIME type to store: the declared one in canonical form, or the
+/// type recognized from the content when the client sent a generic type.
+/// Uploads whose bytes or extension contradict the type are rejected with a
+/// [`ContentTypeMismatch`].
+pub fn verify_content_type(
+    claimed: &str,
+    filename: &str,
+    data: &[u8],
+) -> Result<String, ContentTypeMismatch> {
+    let claimed = canonical_mime_type(claimed);
+    let detected = sniff_mime_type(data);
+    let detected = detected.as_deref();
+    let mismatch = |detected: Option<&str>| ContentTypeMismatch {
+        claimed: claimed.clone(),
+        detected: detected.map(str::to_string),
+        filename: filename.to_string(),
+    };
+
+    let mime_type = match detected {
+        // Ogg holds both audio and video; the declared type or extension decides
+        Some("application/ogg") if matches!(claimed.as_str(), "audio/ogg" | "video/ogg") => {
+            claimed.clone()
+        }
+        Some("application/ogg") if claimed.is_empty() || claimed == UNKNOWN_MIME_TYPE => {
+            match extension_mime_types(filename) {
+                Some(["audio/ogg"]) => "audio/ogg".to_string(),
+                _ => "video/ogg".to_string(),
+            }
+        }
+        Some(detected) if claimed == detected => claimed.clone(),
+        Some(detected) if claimed.is_empty() || claimed == UNKNOWN_MIME_TYPE => {
+            detected.to_string()
+        }
+        Some(detected) => return Err(mismatch(Some(detected))),
+        None if claimed.is_empty() || claimed == UNKNOWN_MIME_TYPE => {
+            // Without a signature, only the extension can name a text type
+            match extension_mime_types(filename) {
+                Some([text_type]) if matches_text_type(text_type, data) => text_type.to_string(),
+                _ => return Err(mismatch(None)),
+            }
+        }
+        None if matches_text_type(&claimed, data) => claimed.clone(),
+        None => return Err(mismatch(None)),
+    };
+
+    // Media are served with the type of their extension, so it has to agree
+    match extension_mime_types(filename) {
+        Some(types) if !types.contains(&mime_type.as_str()) => Err(mismatch(Some(&mime_type))),
+        _ => Ok(mime_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10, b'J', b'F', b'I', b'F'];
+
+    #[test]
+    fn test_sniff_recognizes_signatures() {
+        assert_eq!(sniff_mime_type(PNG).as_deref(), Some("image/png"));
+        assert_eq!(sniff_mime_type(JPEG).as_deref(), Some("image/jpeg"));
+        assert_eq!(
+            sniff_mime_type(b"GIF89a\x01\0").as_deref(),
+            Some("image/gif")
+        );
+        assert_eq!(
+            sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 ").as_deref(),
+            Some("image/webp")
+        );
+        assert_eq!(
+            sniff_mime_type(b"RIFF\0\0\0\0WAVEfmt ").as_deref(),
+            Some("audio/wav")
+        );
+        assert_eq!(
+            sniff_mime_type(b"\0\0\0\x18ftypmp42").as_deref(),
+            Some("video/mp4")
+        );
+        assert_eq!(
+            sniff_mime_type(b"\0\0\0\x18ftypheic\0\0\0\0mif1heic").as_deref(),
+            Some("image/heif")
+        );
+        assert_eq!(sniff_mime_type(b"ID3\x04\0").as_deref(), Some("audio/mpeg"));
+        assert_eq!(
+            sniff_mime_type(b"OggS\0\x02").as_deref(),
+            Some("application/ogg")
+        );
+        assert_eq!(
+            sniff_mime_type(b"%PDF-1.7").as_deref(),
+            Some("application/pdf")
+        );
+        assert_eq!(
+            sniff_mime_type(b"PK\x03\x04").as_deref(),
+            Some("application/zip")
+        );
+        assert_eq!(sniff_mime_type(b"hello"), None);
+        assert_eq!(sniff_mime_type(b""), None);
+    }
+
+    #[test]
+    fn test_matching_uploads_keep_a_canonical_type() {
+        assert_eq!(
+            verify_content_type("image/PNG; charset=binary", "a.png", PNG).unwrap(),
+            "image/png"
+        );
+        assert_eq!(
+            verify_content_type("image/jpg", "photo.JPEG", JPEG).unwrap(),
+            "image/jpeg"
+        );
+        assert_eq!(
+            verify_content_type("text/csv", "data.csv", b"a,b\n1,2\n").unwrap(),
+            "text/csv"
+        );
+        assert_eq!(
+            verify_content_type("image/svg+xml", "logo.svg", b"<svg xmlns=\"\"/>").unwrap(),
+            "image/svg+xml"
+        );
+        assert_eq!(
+            verify_content_type("audio/ogg", "song.ogg", b"OggS\0\x02").unwrap(),
+            "audio/ogg"
+        );
+    }
+
+    #[test]
+    fn test_generic_types_are_corrected_from_the_content() {
+        assert_eq!(
+            verify_content_type("application/octet-stream", "a.png", PNG).unwrap(),
+            "image/png"
+        );
+        assert_eq!(
+            verify_content_type("", "notes.md", b"# Notes\n").unwrap(),
+            "text/markdown"
+        );
+        assert_eq!(
+            verify_content_type("application/octet-stream", "clip.oga", b"OggS\0").unwrap(),
+            "audio/ogg"
+        );
+    }
+
+    #[test]
+    fn test_mismatches_are_rejected() {
+        // Content disagrees with the declared type
+        let error = verify_content_type("image/png", "a.png", JPEG).unwrap_err();
+        assert_eq!(error.detected.as_deref(), Some("image/jpeg"));
+        assert!(verify_content_type("image/png", "a.png", b"<html>").is_err());
+        assert!(verify_content_type("text/plain", "a.txt", PNG).is_err());
+        assert!(verify_content_type("text/plain", "a.txt", b"MZ\0\0").is_err());
+        assert!(verify_content_type("application/json", "a.json", b"{oops").is_err());
+        assert!(verify_content_type("image/svg+xml", "a.svg", b"plain").is_err());
+
+        // Extension disagrees with the content
+        assert!(verify_content_type("image/jpeg", "a.png", JPEG).is_err());
+        assert!(verify_content_type("application/octet-stream", "a.gif", PNG).is_err());
+        assert!(verify_content_type("application/octet-stream", "blob", b"text").is_err());
+    }
+
+    #[test]
+    fn test_find_mismatch_behind_anyhow_error() {
+        let error =
+            anyhow::Error::new(verify_content_type("image/png", "a.png", JPEG).unwrap_err())
+                .context("Upload failed");
+        assert!(ContentTypeMismatch::find(&error).is_some());
+        assert!(ContentTypeMismatch::find(&anyhow::anyhow!("other")).is_none());
+    }
+}
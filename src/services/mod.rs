@@ -1,23 +1,107 @@
 // Services module for business logic
 
+pub mod activity;
+pub mod admin_session;
+pub mod alt_text;
+pub mod assets;
 pub mod blog_storage;
 pub mod cache;
+pub mod calendar;
+pub mod captcha;
+pub mod circuit_breaker;
+pub mod client_info;
+pub mod command_palette;
+pub mod config_reload;
+pub mod cross_post;
 pub mod database;
+pub mod digest;
 pub mod dropbox;
+pub mod events;
+pub mod feed;
+pub mod github;
+pub mod github_mirror;
+pub mod i18n;
+pub mod import_jobs;
+pub mod job_queue;
+pub mod link_check;
 pub mod llm_import;
+pub mod maintenance;
 pub mod markdown;
 pub mod media;
+pub mod mime_sniff;
+pub mod notifications;
+pub mod og_image;
+pub mod outline;
+pub mod pdf;
+pub mod podcast;
+pub mod post_password;
+pub mod proofread;
+pub mod reader_mode;
+pub mod s3;
+pub mod scheduled_publish;
+pub mod secrets;
+pub mod seed;
+pub mod site_files;
+pub mod slow_log;
+pub mod storage;
+pub mod storage_migration;
+pub mod storage_quota;
+pub mod structured_data;
+pub mod summary;
+pub mod svg_sanitize;
+pub mod sync_conflicts;
+pub mod telemetry;
 pub mod template;
 pub mod theme;
+pub mod theme_preview;
+pub mod totp;
 pub mod version;
+pub mod video;
 
+pub use activity::ActivityService;
+pub use admin_session::{AdminSessionService, SessionCheck};
+pub use alt_text::{AltTextConfig, AltTextService};
+pub use assets::AssetService;
 pub use blog_storage::BlogStorageService;
-pub use cache::CacheService;
-pub use database::DatabaseService;
+pub use cache::{CacheService, CachedResponse};
+pub use calendar::CalendarService;
+pub use captcha::{CaptchaConfig, CaptchaService};
+pub use circuit_breaker::CircuitOpen;
+pub use client_info::{ClientInfo, TrustedProxies};
+pub use config_reload::ConfigReloader;
+pub use cross_post::{CrossPostConfig, CrossPostService};
+pub use database::{DatabasePoolConfig, DatabaseService};
+pub use digest::DigestService;
 pub use dropbox::DropboxClient;
-pub use llm_import::LLMImportService;
+pub use events::EventBus;
+pub use feed::FeedService;
+pub use github_mirror::{GitHubMirrorConfig, GitHubMirrorService};
+pub use import_jobs::ImportJobService;
+pub use job_queue::{JobQueue, JobQueueConfig};
+pub use link_check::LinkCheckService;
+pub use llm_import::{LLMImportService, QualityGateError};
+pub use maintenance::MaintenanceService;
 pub use markdown::MarkdownService;
-pub use media::MediaService;
+pub use media::{InvalidMediaPath, MediaService, UploadedFile};
+pub use mime_sniff::ContentTypeMismatch;
+pub use notifications::{NotificationConfig, NotificationService};
+pub use og_image::{OgImageConfig, OgImageService};
+pub use pdf::{PdfConfig, PdfService};
+pub use podcast::{PodcastConfig, PodcastService};
+pub use post_password::{hash_post_password, PostPasswordService};
+pub use proofread::{ProofreadConfig, ProofreadService};
+pub use scheduled_publish::ScheduledPublishService;
+pub use site_files::{SiteFilesConfig, SiteFilesService};
+pub use slow_log::{SlowLog, SlowLogConfig, SlowQueryLayer};
+pub use storage::{dropbox_client, open_storage, StorageBackend, StorageKind};
+pub use storage_migration::StorageMigrationService;
+pub use storage_quota::StorageQuotaService;
+pub use structured_data::{Breadcrumb, StructuredDataService};
+pub use summary::{SummaryConfig, SummaryService};
+pub use sync_conflicts::SyncConflictService;
+pub use telemetry::{OtlpConfig, OtlpLayer};
 pub use template::TemplateService;
 pub use theme::ThemeService;
+pub use theme_preview::ThemePreviewService;
+pub use totp::{TotpError, TotpService};
 pub use version::VersionService;
@@ -0,0 +1,263 @@
+use anyhow::Context;
+use futures_util::StreamExt;
+use reqwest::Client;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::models::{
+    AdminEvent, Locale, NotificationResult, NotificationTarget, Post, PostVisibility,
+};
+use crate::services::i18n::translate;
+use crate::services::{DatabaseService, EventBus};
+
+/// Publishing event a notification announces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// A post was published through the API or the admin UI
+    Published,
+    /// A scheduled post reached its `publish_at` time
+    ScheduledPublished,
+}
+
+impl NotificationEvent {
+    fn message_key(&self) -> &'static str {
+        match self {
+            NotificationEvent::Published => "notifications.published",
+            NotificationEvent::ScheduledPublished => "notifications.scheduled_published",
+        }
+    }
+}
+
+/// Webhooks and per-event toggles of publishing notifications
+#[derive(Debug, Clone, Default)]
+pub struct NotificationConfig {
+    pub site_url: String,
+    pub slack_webhook_url: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub on_publish: bool,
+    pub on_scheduled_publish: bool,
+}
+
+/// Posts a message with the title, excerpt and link of newly published
+/// public posts to Slack and Discord webhooks
+#[derive(Clone)]
+pub struct NotificationService {
+    database: DatabaseService,
+    client: Client,
+    config: NotificationConfig,
+}
+
+impl NotificationService {
+    pub fn new(database: DatabaseService, mut config: NotificationConfig) -> Self {
+        config.site_url = config.site_url.trim_end_matches('/').to_string();
+        Self {
+            database,
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            config,
+        }
+    }
+
+    fn targets(&self) -> Vec<(NotificationTarget, &str)> {
+        [
+            (NotificationTarget::Slack, &self.config.slack_webhook_url),
+            (
+                NotificationTarget::Discord,
+                &self.config.discord_webhook_url,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(target, url)| url.as_deref().map(|url| (target, url)))
+        .collect()
+    }
+
+    fn is_enabled_for(&self, event: NotificationEvent) -> bool {
+        match event {
+            NotificationEvent::Published => self.config.on_publish,
+            NotificationEvent::ScheduledPublished => self.config.on_scheduled_publish,
+        }
+    }
+
+    async fn locale(&self) -> Locale {
+        match self.database.get_site_config().await {
+            Ok(site) => site.map(|site| site.locale).unwrap_or_default(),
+            Err(e) => {
+                warn!("Failed to load the site locale for notifications: {}", e);
+                Locale::default()
+            }
+        }
+    }
+
+    /// Announce a published post on every configured webhook
+    ///
+    /// Only public posts without a password are announced, and only when the
+    /// event's toggle is on.
+    pub async fn notify_post(
+        &self,
+        post: &Post,
+        event: NotificationEvent,
+    ) -> Vec<NotificationResult> {
+        if !self.is_enabled_for(event)
+            || !post.published
+            || post.visibility != PostVisibility::Public
+            || post.is_password_protected()
+        {
+            return Vec::new();
+        }
+
+        let heading = translate(self.locale().await, event.message_key(), &[]);
+        let message = NotificationMessage {
+            heading,
+            title: post.title.clone(),
+            excerpt: post.excerpt.clone().unwrap_or_default(),
+            url: format!("{}{}", self.config.site_url, post.get_url_path()),
+        };
+        self.deliver(&message).await
+    }
+
+    /// Send a test message to every configured webhook
+    pub async fn send_test(&self) -> Vec<NotificationResult> {
+        let locale = self.locale().await;
+        let message = NotificationMessage {
+            heading: translate(locale, "notifications.test", &[]),
+            title: translate(locale, "notifications.test_title", &[]),
+            excerpt: String::new(),
+            url: format!("{}/", self.config.site_url),
+        };
+        self.deliver(&message).await
+    }
+
+    async fn deliver(&self, message: &NotificationMessage) -> Vec<NotificationResult> {
+        let mut results = Vec::new();
+        for (target, url) in self.targets() {
+            let body = match target {
+                NotificationTarget::Slack => message.slack_payload(),
+                NotificationTarget::Discord => message.discord_payload(),
+            };
+            let sent = self
+                .client
+                .post(url)
+                .json(&body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .with_context(|| format!("Failed to notify {:?}", target));
+            if let Err(e) = &sent {
+                error!("{:#}", e);
+            }
+            results.push(NotificationResult {
+                target,
+                delivered: sent.is_ok(),
+                error: sent.err().map(|e| format!("{:#}", e)),
+            });
+        }
+        results
+    }
+
+    /// Notify as the API publishes posts and scheduled posts go live
+    pub fn spawn_listener(&self, events: &EventBus) {
+        if self.targets().is_empty() {
+            return;
+        }
+        info!("Publishing notifications enabled");
+
+        let service = self.clone();
+        let mut events = Box::pin(events.subscribe());
+        tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let (slug, event) = match event {
+                    AdminEvent::PostPublished {
+                        slug,
+                        newly_published: true,
+                        ..
+                    } => (slug, NotificationEvent::Published),
+                    AdminEvent::ScheduledPostPublished { slug, .. } => {
+                        (slug, NotificationEvent::ScheduledPublished)
+                    }
+                    _ => continue,
+                };
+                if !service.is_enabled_for(event) {
+                    continue;
+                }
+                match service.database.get_post_by_slug(&slug).await {
+                    Ok(Some(post)) => {
+                        service.notify_post(&post, event).await;
+                    }
+                    Ok(None) => {}
+                    Err(e) => error!("Failed to load {} for notifications: {}", slug, e),
+                }
+            }
+        });
+    }
+}
+
+/// Content of a notification, rendered per chat service
+struct NotificationMessage {
+    heading: String,
+    title: String,
+    excerpt: String,
+    url: String,
+}
+
+impl NotificationMessage {
+    /// Slack incoming webhook payload (mrkdwn)
+    fn slack_payload(&self) -> serde_json::Value {
+        let mut text = format!("{}\n*<{}|{}>*", self.heading, self.url, self.title);
+        if !self.excerpt.is_empty() {
+            text.push('\n');
+            text.push_str(&self.excerpt);
+        }
+        serde_json::json!({ "text": text })
+    }
+
+    /// Discord webhook payload with the post as an embed
+    fn discord_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "content": self.heading,
+            "embeds": [{
+                "title": self.title,
+                "url": self.url,
+                "description": self.excerpt,
+            }],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(excerpt: &str) -> NotificationMessage {
+        NotificationMessage {
+            heading: "New post".to_string(),
+            title: "Hello".to_string(),
+            excerpt: excerpt.to_string(),
+            url: "https://blog.example/posts/2024/hello".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_slack_payload_links_the_title() {
+        assert_eq!(
+            message("Summary").slack_payload()["text"],
+            "New post\n*<https://blog.example/posts/2024/hello|Hello>*\nSummary"
+        );
+        assert_eq!(
+            message("").slack_payload()["text"],
+            "New post\n*<https://blog.example/posts/2024/hello|Hello>*"
+        );
+    }
+
+    #[test]
+    fn test_discord_payload_embeds_the_post() {
+        let payload = message("Summary").discord_payload();
+        assert_eq!(payload["content"], "New post");
+        assert_eq!(
+            payload["embeds"][0]["url"],
+            "https://blog.example/posts/2024/hello"
+        );
+        assert_eq!(payload["embeds"][0]["description"], "Summary");
+    }
+}
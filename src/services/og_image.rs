@@ -0,0 +1,314 @@
+use anyhow::{Context, Result};
+use image::{ImageOutputFormat, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut};
+use imageproc::rect::Rect;
+use rusttype::{point, Font, Scale};
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::models::{Post, PostVisibility, ThemeSettings};
+use crate::services::DatabaseService;
+
+/// Open Graph image dimensions recommended by most social networks
+pub const OG_IMAGE_WIDTH: u32 = 1200;
+pub const OG_IMAGE_HEIGHT: u32 = 630;
+
+const PADDING: i32 = 80;
+const TITLE_SIZE: f32 = 64.0;
+const TITLE_LINE_HEIGHT: i32 = 84;
+const TITLE_MAX_LINES: usize = 4;
+const BRAND_SIZE: f32 = 36.0;
+const ACCENT_BAR_HEIGHT: i32 = 16;
+
+/// Fonts tried in order when no font is configured (CJK-capable fonts first)
+const FALLBACK_FONTS: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Bold.ttc",
+    "/usr/share/fonts/truetype/noto/NotoSansJP-Bold.ttf",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Bold.ttc",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf",
+];
+
+/// OG image generation configuration
+#[derive(Debug, Clone)]
+pub struct OgImageConfig {
+    /// TrueType/OpenType font used for titles; system fonts are probed when unset
+    pub font_path: Option<String>,
+    /// Directory where rendered PNGs are cached
+    pub cache_dir: PathBuf,
+}
+
+impl Default for OgImageConfig {
+    fn default() -> Self {
+        Self {
+            font_path: None,
+            cache_dir: PathBuf::from("cache/og"),
+        }
+    }
+}
+
+/// Service that renders social share images for posts
+#[derive(Clone)]
+pub struct OgImageService {
+    database: DatabaseService,
+    font: Option<Arc<Font<'static>>>,
+    cache_dir: PathBuf,
+}
+
+impl OgImageService {
+    /// Create a new OG image service, loading the title font
+    pub fn new(database: DatabaseService, config: OgImageConfig) -> Self {
+        let font = load_font(config.font_path.as_deref());
+        if font.is_none() {
+            warn!("No font available for OG images, titles will not be rendered");
+        }
+
+        Self {
+            database,
+            font: font.map(Arc::new),
+            cache_dir: config.cache_dir,
+        }
+    }
+
+    /// Get the PNG share image for a published, non-private post, rendering it on cache miss
+    pub async fn get_post_image(&self, slug: &str) -> Result<Option<Vec<u8>>> {
+        let Some(post) = self.database.get_post_by_slug(slug).await? else {
+            return Ok(None);
+        };
+        if !post.published || post.visibility == PostVisibility::Private {
+            return Ok(None);
+        }
+
+        let theme = self.database.get_active_theme().await?.unwrap_or_default();
+        let site_title = self
+            .database
+            .get_site_config()
+            .await?
+            .map(|config| config.site_title)
+            .unwrap_or_else(|| "Tobelog".to_string());
+
+        let cache_path = self.cache_dir.join(format!(
+            "{}-{}.png",
+            post.slug,
+            cache_key(&post, &theme, &site_title)
+        ));
+
+        if let Ok(png) = tokio::fs::read(&cache_path).await {
+            debug!("Serving cached OG image: {}", cache_path.display());
+            return Ok(Some(png));
+        }
+
+        let png = self.render(&post.title, &site_title, &theme)?;
+        self.store(&post.slug, &cache_path, &png).await;
+
+        Ok(Some(png))
+    }
+
+    /// Render title and site branding over the theme's primary color
+    fn render(&self, title: &str, site_title: &str, theme: &ThemeSettings) -> Result<Vec<u8>> {
+        let background = parse_hex_color(&theme.primary_color).unwrap_or(Rgba([37, 99, 235, 255]));
+        let foreground = parse_hex_color(&theme.background_color)
+            .filter(|color| *color != background)
+            .unwrap_or(Rgba([255, 255, 255, 255]));
+        let accent = parse_hex_color(&theme.accent_color).unwrap_or(foreground);
+
+        let mut image = RgbaImage::from_pixel(OG_IMAGE_WIDTH, OG_IMAGE_HEIGHT, background);
+        draw_filled_rect_mut(
+            &mut image,
+            Rect::at(0, OG_IMAGE_HEIGHT as i32 - ACCENT_BAR_HEIGHT)
+                .of_size(OG_IMAGE_WIDTH, ACCENT_BAR_HEIGHT as u32),
+            accent,
+        );
+
+        if let Some(font) = &self.font {
+            let max_width = (OG_IMAGE_WIDTH as i32 - PADDING * 2) as f32;
+            let title_scale = Scale::uniform(TITLE_SIZE);
+            let lines = wrap_text(title, TITLE_MAX_LINES, |text| {
+                text_width(font, title_scale, text) <= max_width
+            });
+
+            for (index, line) in lines.iter().enumerate() {
+                let y = PADDING + index as i32 * TITLE_LINE_HEIGHT;
+                draw_text_mut(&mut image, foreground, PADDING, y, title_scale, font, line);
+            }
+
+            let brand_y = OG_IMAGE_HEIGHT as i32 - ACCENT_BAR_HEIGHT - PADDING;
+            draw_text_mut(
+                &mut image,
+                foreground,
+                PADDING,
+                brand_y,
+                Scale::uniform(BRAND_SIZE),
+                font,
+                site_title,
+            );
+        }
+
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+            .context("Failed to encode OG image")?;
+        Ok(png)
+    }
+
+    /// Write a rendered image to the cache, replacing older renders of the same post
+    async fn store(&self, slug: &str, cache_path: &Path, png: &[u8]) {
+        if let Err(e) = tokio::fs::create_dir_all(&self.cache_dir).await {
+            warn!("Failed to create OG image cache directory: {}", e);
+            return;
+        }
+
+        if let Ok(mut entries) = tokio::fs::read_dir(&self.cache_dir).await {
+            let prefix = format!("{}-", slug);
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_stale = name
+                    .strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_suffix(".png"))
+                    .is_some_and(|hash| !hash.contains('-'));
+                if is_stale {
+                    let _ = tokio::fs::remove_file(entry.path()).await;
+                }
+            }
+        }
+
+        match tokio::fs::write(cache_path, png).await {
+            Ok(()) => info!("Cached OG image: {}", cache_path.display()),
+            Err(e) => warn!("Failed to cache OG image {}: {}", cache_path.display(), e),
+        }
+    }
+}
+
+/// Load the configured font, or the first available fallback font
+fn load_font(font_path: Option<&str>) -> Option<Font<'static>> {
+    font_path
+        .into_iter()
+        .chain(FALLBACK_FONTS.iter().copied())
+        .find_map(|path| {
+            let data = std::fs::read(path).ok()?;
+            let font = Font::try_from_vec(data);
+            if font.is_some() {
+                info!("Using OG image font: {}", path);
+            }
+            font
+        })
+}
+
+/// Short digest of everything that affects the rendered image
+fn cache_key(post: &Post, theme: &ThemeSettings, site_title: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        post.title.as_str(),
+        site_title,
+        &theme.primary_color,
+        &theme.background_color,
+        &theme.accent_color,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())[..12].to_string()
+}
+
+/// Parse `#rgb` or `#rrggbb` into an opaque color
+fn parse_hex_color(value: &str) -> Option<Rgba<u8>> {
+    let hex = value.trim().strip_prefix('#')?;
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Rgba([channel(0)?, channel(2)?, channel(4)?, 255]))
+}
+
+fn text_width(font: &Font, scale: Scale, text: &str) -> f32 {
+    font.layout(text, scale, point(0.0, 0.0))
+        .last()
+        .map(|glyph| glyph.position().x + glyph.unpositioned().h_metrics().advance_width)
+        .unwrap_or(0.0)
+}
+
+/// Greedily wrap text into at most `max_lines` lines that satisfy `fits`
+///
+/// Latin words are kept together while CJK text may break between any characters.
+/// Overflowing text is truncated with an ellipsis.
+fn wrap_text(text: &str, max_lines: usize, fits: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    for c in text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+    {
+        match tokens.last_mut() {
+            Some(last) if c.is_ascii() && c != ' ' && !last.ends_with(' ') && last.is_ascii() => {
+                last.push(c)
+            }
+            _ => tokens.push(c.to_string()),
+        }
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for token in tokens {
+        let candidate = format!("{}{}", current, token);
+        if current.is_empty() || fits(candidate.trim_end()) {
+            current = candidate;
+            continue;
+        }
+
+        lines.push(current.trim_end().to_string());
+        current = token.trim_start().to_string();
+        if lines.len() == max_lines {
+            break;
+        }
+    }
+    if lines.len() < max_lines && !current.is_empty() {
+        lines.push(current.trim_end().to_string());
+        current.clear();
+    }
+
+    if !current.is_empty() {
+        if let Some(last) = lines.last_mut() {
+            while !last.is_empty() && !fits(&format!("{}…", last)) {
+                last.pop();
+            }
+            *last = format!("{}…", last.trim_end());
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#2563eb"), Some(Rgba([37, 99, 235, 255])));
+        assert_eq!(parse_hex_color("#fff"), Some(Rgba([255, 255, 255, 255])));
+        assert_eq!(parse_hex_color("blue"), None);
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_words_and_truncates() {
+        let fits = |line: &str| line.chars().count() <= 10;
+
+        assert_eq!(
+            wrap_text("Hello brave new world", 3, fits),
+            vec!["Hello", "brave new", "world"]
+        );
+        assert_eq!(
+            wrap_text("日本語のタイトルを折り返す", 2, fits),
+            vec!["日本語のタイトルを折", "り返す"]
+        );
+        assert_eq!(
+            wrap_text("one two three four five six", 2, fits),
+            vec!["one two", "three fou…"]
+        );
+    }
+}
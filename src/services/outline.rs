@@ -0,0 +1,169 @@
+use regex::{Captures, Regex};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::models::OutlineHeading;
+
+/// Give every heading of rendered post HTML an `id` and a permalink, and
+/// return the headings as a tree
+///
+/// Headings that already have an `id` keep it. Generated anchors are
+/// derived from the heading text and made unique within the post.
+pub fn add_heading_anchors(html: &str) -> (String, Vec<OutlineHeading>) {
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    static ID: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+
+    let heading = HEADING.get_or_init(|| {
+        Regex::new(r"(?is)<h([1-6])\b([^>]*)>(.*?)</h[1-6]\s*>").expect("valid regex")
+    });
+    let id =
+        ID.get_or_init(|| Regex::new(r#"(?i)\bid\s*=\s*["']([^"']*)["']"#).expect("valid regex"));
+    let tag = TAG.get_or_init(|| Regex::new(r"(?s)<[^>]*>").expect("valid regex"));
+
+    let mut used = HashSet::new();
+    let mut headings = Vec::new();
+
+    let html = heading.replace_all(html, |captures: &Captures| {
+        let level: u8 = captures[1].parse().unwrap_or(1);
+        let attributes = &captures[2];
+        let inner = &captures[3];
+        let text = html_escape::decode_html_entities(&tag.replace_all(inner, ""))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let (anchor, attributes) = match id.captures(attributes) {
+            Some(existing) => (existing[1].to_string(), attributes.to_string()),
+            None => {
+                let anchor = unique_anchor(&anchor_slug(&text), headings.len() + 1, &used);
+                (anchor.clone(), format!(r#" id="{}"{}"#, anchor, attributes))
+            }
+        };
+        used.insert(anchor.clone());
+        headings.push(OutlineHeading {
+            level,
+            text,
+            anchor: anchor.clone(),
+            children: Vec::new(),
+        });
+
+        format!(
+            r##"<h{level}{attributes}>{inner}<a class="heading-anchor" href="#{anchor}" aria-hidden="true">#</a></h{level}>"##,
+            level = level,
+            attributes = attributes,
+            inner = inner,
+            anchor = html_escape::encode_double_quoted_attribute(&anchor),
+        )
+    });
+
+    (html.into_owned(), outline_tree(headings))
+}
+
+/// Anchor slug of a heading: lowercase letters and digits of any script,
+/// with runs of spaces and punctuation collapsed into `-`
+pub fn anchor_slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Suffix `-1`, `-2`, ... until the anchor is unused; empty slugs become `section-<n>`
+fn unique_anchor(slug: &str, position: usize, used: &HashSet<String>) -> String {
+    let base = if slug.is_empty() {
+        format!("section-{}", position)
+    } else {
+        slug.to_string()
+    };
+    if !used.contains(&base) {
+        return base;
+    }
+    (1..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !used.contains(candidate))
+        .expect("unbounded suffixes")
+}
+
+/// Number of headings in an outline, at any depth
+pub fn heading_count(outline: &[OutlineHeading]) -> usize {
+    outline
+        .iter()
+        .map(|heading| 1 + heading_count(&heading.children))
+        .sum()
+}
+
+/// Nest a flat list of headings under the closest preceding higher-level heading
+fn outline_tree(headings: Vec<OutlineHeading>) -> Vec<OutlineHeading> {
+    let mut roots: Vec<OutlineHeading> = Vec::new();
+    // Open headings from the outermost to the innermost
+    let mut stack: Vec<OutlineHeading> = Vec::new();
+
+    for heading in headings {
+        while stack.last().is_some_and(|open| open.level >= heading.level) {
+            close(&mut stack, &mut roots);
+        }
+        stack.push(heading);
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+
+    roots
+}
+
+fn close(stack: &mut Vec<OutlineHeading>, roots: &mut Vec<OutlineHeading>) {
+    if let Some(done) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(done),
+            None => roots.push(done),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_slug() {
+        assert_eq!(anchor_slug("Getting Started!"), "getting-started");
+        assert_eq!(anchor_slug("  Rust -- async/await "), "rust-asyncawait");
+        assert_eq!(anchor_slug("はじめに 1"), "はじめに-1");
+        assert_eq!(anchor_slug("???"), "");
+    }
+
+    #[test]
+    fn test_add_heading_anchors_keeps_ids_and_deduplicates() {
+        let html = r#"<h2>Setup</h2><p>x</p><h2 id="custom">Other</h2><h2>Setup</h2><h3>???</h3>"#;
+        let (html, outline) = add_heading_anchors(html);
+
+        assert!(html.starts_with(r##"<h2 id="setup">Setup<a class="heading-anchor" href="#setup" aria-hidden="true">#</a></h2>"##));
+        assert!(html.contains(r#"<h2 id="custom">Other"#));
+        assert!(html.contains(r#"<h2 id="setup-1">Setup"#));
+        assert!(html.contains(r#"<h3 id="section-4">???"#));
+
+        let anchors: Vec<_> = outline.iter().map(|h| h.anchor.as_str()).collect();
+        assert_eq!(anchors, ["setup", "custom", "setup-1"]);
+        assert_eq!(outline[2].children[0].anchor, "section-4");
+    }
+
+    #[test]
+    fn test_outline_tree_nests_by_level() {
+        let (_, outline) = add_heading_anchors(
+            "<h2>A</h2><h3>A.1</h3><h4>A.1.a</h4><h3>A.2</h3><h2>B &amp; <code>C</code></h2>",
+        );
+
+        assert_eq!(outline.len(), 2);
+        assert_eq!(heading_count(&outline), 5);
+        assert_eq!(outline[0].children.len(), 2);
+        assert_eq!(outline[0].children[0].children[0].text, "A.1.a");
+        assert_eq!(outline[1].text, "B & C");
+        assert_eq!(outline[1].anchor, "b-c");
+    }
+}
@@ -0,0 +1,271 @@
+use anyhow::{anyhow, Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::models::{Post, PostVisibility};
+use crate::services::reader_mode::simplify_html;
+use crate::services::template::{LitePageContext, PostData};
+use crate::services::{DatabaseService, TemplateService};
+
+/// PDF export configuration
+#[derive(Debug, Clone)]
+pub struct PdfConfig {
+    /// Chromium (or Chrome) binary run in headless mode to print pages
+    pub chromium_path: String,
+    /// Directory where rendered PDFs are cached
+    pub cache_dir: PathBuf,
+}
+
+impl Default for PdfConfig {
+    fn default() -> Self {
+        Self {
+            chromium_path: "chromium".to_string(),
+            cache_dir: PathBuf::from("cache/pdf"),
+        }
+    }
+}
+
+/// Whether PDF export was compiled in (`pdf` feature)
+pub fn is_supported() -> bool {
+    cfg!(feature = "pdf")
+}
+
+/// Service that prints posts to PDF for offline reading
+#[derive(Clone)]
+pub struct PdfService {
+    database: DatabaseService,
+    templates: TemplateService,
+    /// Public base URL that relative links and images resolve against
+    site_url: String,
+    config: PdfConfig,
+}
+
+impl PdfService {
+    /// Create a new PDF export service
+    pub fn new(
+        database: DatabaseService,
+        templates: TemplateService,
+        site_url: String,
+        config: PdfConfig,
+    ) -> Self {
+        Self {
+            database,
+            templates,
+            site_url,
+            config,
+        }
+    }
+
+    /// Get the PDF of a published public post, printing it on cache miss
+    ///
+    /// Private and password-protected posts are never exported.
+    pub async fn get_post_pdf(&self, slug: &str) -> Result<Option<Vec<u8>>> {
+        let Some(mut post) = self.database.get_post_by_slug(slug).await? else {
+            return Ok(None);
+        };
+        if !is_exportable(&post) {
+            return Ok(None);
+        }
+        self.database.show_published_version(&mut post).await?;
+
+        let site_title = self
+            .database
+            .get_site_config()
+            .await?
+            .map(|config| config.site_title)
+            .unwrap_or_else(|| "Tobelog".to_string());
+
+        let cache_path = self.config.cache_dir.join(format!(
+            "{}-{}.pdf",
+            post.slug,
+            cache_key(&post, &site_title)
+        ));
+
+        if let Ok(pdf) = tokio::fs::read(&cache_path).await {
+            debug!("Serving cached PDF: {}", cache_path.display());
+            return Ok(Some(pdf));
+        }
+
+        let html = self.render_html(post.clone(), site_title)?;
+        let pdf = print_to_pdf(&self.config.chromium_path, &html).await?;
+        self.store(&post.slug, &cache_path, &pdf).await;
+
+        Ok(Some(pdf))
+    }
+
+    /// Render the reader-mode page of a post as a standalone document
+    fn render_html(&self, post: Post, site_title: String) -> Result<String> {
+        let site_url = self.site_url.trim_end_matches('/');
+        let full_url = post.get_url_path();
+        let canonical_url = post
+            .canonical_url
+            .clone()
+            .unwrap_or_else(|| format!("{}{}", site_url, full_url));
+
+        // Print full-size images rather than the lite page's thumbnails
+        let mut post_data = PostData::from(post);
+        post_data.html_content = simplify_html(&post_data.html_content, &HashMap::new());
+
+        let context = LitePageContext {
+            site_title,
+            post: post_data,
+            canonical_url,
+            full_url,
+        };
+        let html = self
+            .templates
+            .render("lite.html", &context)
+            .context("Failed to render post for PDF export")?;
+
+        Ok(with_base_url(&html, site_url))
+    }
+
+    /// Write a rendered PDF to the cache, replacing older renders of the same post
+    async fn store(&self, slug: &str, cache_path: &Path, pdf: &[u8]) {
+        let cache_dir = &self.config.cache_dir;
+        if let Err(e) = tokio::fs::create_dir_all(cache_dir).await {
+            warn!("Failed to create PDF cache directory: {}", e);
+            return;
+        }
+
+        if let Ok(mut entries) = tokio::fs::read_dir(cache_dir).await {
+            let prefix = format!("{}-", slug);
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_stale = name
+                    .strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_suffix(".pdf"))
+                    .is_some_and(|hash| !hash.contains('-'));
+                if is_stale {
+                    let _ = tokio::fs::remove_file(entry.path()).await;
+                }
+            }
+        }
+
+        match tokio::fs::write(cache_path, pdf).await {
+            Ok(()) => info!("Cached PDF: {}", cache_path.display()),
+            Err(e) => warn!("Failed to cache PDF {}: {}", cache_path.display(), e),
+        }
+    }
+}
+
+/// Only posts anyone may read are exported, since PDFs are cached and served without auth
+pub fn is_exportable(post: &Post) -> bool {
+    post.published && post.visibility != PostVisibility::Private && !post.is_password_protected()
+}
+
+/// Print an HTML document with headless Chromium
+#[cfg(feature = "pdf")]
+async fn print_to_pdf(chromium_path: &str, html: &str) -> Result<Vec<u8>> {
+    let id = uuid::Uuid::new_v4();
+    let html_path = std::env::temp_dir().join(format!("tobelog-pdf-{}.html", id));
+    let pdf_path = std::env::temp_dir().join(format!("tobelog-pdf-{}.pdf", id));
+    tokio::fs::write(&html_path, html)
+        .await
+        .context("Failed to write temporary HTML file")?;
+
+    let result = tokio::task::spawn_blocking({
+        let chromium_path = chromium_path.to_string();
+        let html_path = html_path.clone();
+        let pdf_path = pdf_path.clone();
+        move || run_chromium(&chromium_path, &html_path, &pdf_path)
+    })
+    .await
+    .map_err(|e| anyhow!("PDF rendering task failed: {}", e))
+    .and_then(|result| result);
+
+    let _ = tokio::fs::remove_file(&html_path).await;
+    let _ = tokio::fs::remove_file(&pdf_path).await;
+    result
+}
+
+/// Without the `pdf` feature no PDF can be printed
+#[cfg(not(feature = "pdf"))]
+async fn print_to_pdf(_chromium_path: &str, _html: &str) -> Result<Vec<u8>> {
+    Err(anyhow!("PDF export requires the `pdf` feature"))
+}
+
+#[cfg(feature = "pdf")]
+fn run_chromium(chromium_path: &str, html_path: &Path, pdf_path: &Path) -> Result<Vec<u8>> {
+    use std::process::Command;
+
+    let output = Command::new(chromium_path)
+        .args(chromium_args(html_path, pdf_path))
+        .output()
+        .with_context(|| format!("Failed to run {}", chromium_path))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} failed: {}",
+            chromium_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    std::fs::read(pdf_path).context("Chromium did not write a PDF")
+}
+
+/// Arguments that print `html_path` to `pdf_path` without browser headers and footers
+#[cfg_attr(not(feature = "pdf"), allow(dead_code))]
+fn chromium_args(html_path: &Path, pdf_path: &Path) -> Vec<String> {
+    vec![
+        "--headless".to_string(),
+        "--disable-gpu".to_string(),
+        "--no-sandbox".to_string(),
+        "--no-pdf-header-footer".to_string(),
+        format!("--print-to-pdf={}", pdf_path.display()),
+        format!("file://{}", html_path.display()),
+    ]
+}
+
+/// Resolve the page's relative links and images against the public site
+fn with_base_url(html: &str, site_url: &str) -> String {
+    let base = format!(
+        r#"<base href="{}/">"#,
+        html_escape::encode_double_quoted_attribute(site_url)
+    );
+    match html.find("<head>") {
+        Some(index) => {
+            let end = index + "<head>".len();
+            format!("{}{}{}", &html[..end], base, &html[end..])
+        }
+        None => format!("{}{}", base, html),
+    }
+}
+
+/// Short digest of everything that affects the printed document
+fn cache_key(post: &Post, site_title: &str) -> String {
+    let mut hasher = Sha256::new();
+    // The ETag covers the body and the metadata printed with it
+    for part in [post.etag("pdf").as_str(), site_title] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())[..12].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_base_url_inserts_base_into_head() {
+        assert_eq!(
+            with_base_url(
+                "<html><head><title>t</title></head></html>",
+                "https://blog.example.com"
+            ),
+            r#"<html><head><base href="https://blog.example.com/"><title>t</title></head></html>"#
+        );
+    }
+
+    #[test]
+    fn test_chromium_args_print_without_headers() {
+        let args = chromium_args(Path::new("/tmp/post.html"), Path::new("/tmp/post.pdf"));
+        assert!(args.contains(&"--headless".to_string()));
+        assert!(args.contains(&"--no-pdf-header-footer".to_string()));
+        assert!(args.contains(&"--print-to-pdf=/tmp/post.pdf".to_string()));
+        assert_eq!(args.last().unwrap(), "file:///tmp/post.html");
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::Result;
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use std::fmt::Write;
+use tracing::debug;
+
+use crate::models::{AudioEnclosure, Post};
+use crate::services::DatabaseService;
+
+/// Maximum number of episodes listed in the feed
+const FEED_EPISODE_LIMIT: i64 = 100;
+
+/// Podcast feed configuration
+#[derive(Debug, Clone)]
+pub struct PodcastConfig {
+    /// Public base URL used to build absolute links
+    pub site_url: String,
+    /// Top-level iTunes category (e.g. "Technology")
+    pub category: String,
+    /// RSS language code
+    pub language: String,
+    pub explicit: bool,
+}
+
+/// Channel-level metadata of the podcast
+#[derive(Debug, Clone)]
+struct PodcastChannel {
+    title: String,
+    description: String,
+    author: String,
+    email: Option<String>,
+    image: Option<String>,
+}
+
+/// Service that publishes posts with audio as an iTunes-compatible podcast feed
+#[derive(Clone)]
+pub struct PodcastService {
+    database: DatabaseService,
+    config: PodcastConfig,
+}
+
+impl PodcastService {
+    /// Create a new podcast service
+    pub fn new(database: DatabaseService, config: PodcastConfig) -> Self {
+        Self { database, config }
+    }
+
+    /// Build the RSS document for `/podcast.xml`
+    pub async fn generate_feed(&self) -> Result<String> {
+        let channel = match self.database.get_site_config().await? {
+            Some(site) => PodcastChannel {
+                title: site.site_title,
+                description: site.site_description,
+                author: site.author_name,
+                email: site.author_email,
+                image: site.site_logo,
+            },
+            None => PodcastChannel {
+                title: "Tobelog".to_string(),
+                description: "Personal Blog System built with Rust".to_string(),
+                author: "Tobelog".to_string(),
+                email: None,
+                image: None,
+            },
+        };
+
+        let episodes = self
+            .database
+            .list_podcast_episodes(FEED_EPISODE_LIMIT)
+            .await?;
+        debug!("Building podcast feed with {} episodes", episodes.len());
+
+        Ok(render_feed(&self.config, &channel, &episodes))
+    }
+}
+
+fn absolute_url(site_url: &str, url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!(
+            "{}/{}",
+            site_url.trim_end_matches('/'),
+            url.trim_start_matches('/')
+        )
+    }
+}
+
+fn render_feed(
+    config: &PodcastConfig,
+    channel: &PodcastChannel,
+    episodes: &[(Post, AudioEnclosure)],
+) -> String {
+    let site_url = config.site_url.trim_end_matches('/');
+    let explicit = if config.explicit { "true" } else { "false" };
+    let mut xml = String::new();
+
+    // Writing to a String cannot fail
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd" xmlns:atom="http://www.w3.org/2005/Atom">"#
+    );
+    let _ = writeln!(xml, "<channel>");
+    let _ = writeln!(xml, "<title>{}</title>", encode_text(&channel.title));
+    let _ = writeln!(xml, "<link>{}</link>", encode_text(site_url));
+    let _ = writeln!(
+        xml,
+        r#"<atom:link href="{}/podcast.xml" rel="self" type="application/rss+xml"/>"#,
+        encode_double_quoted_attribute(site_url)
+    );
+    let _ = writeln!(
+        xml,
+        "<description>{}</description>",
+        encode_text(&channel.description)
+    );
+    let _ = writeln!(
+        xml,
+        "<language>{}</language>",
+        encode_text(&config.language)
+    );
+    let _ = writeln!(
+        xml,
+        "<itunes:author>{}</itunes:author>",
+        encode_text(&channel.author)
+    );
+    let _ = writeln!(
+        xml,
+        "<itunes:summary>{}</itunes:summary>",
+        encode_text(&channel.description)
+    );
+    let _ = write!(
+        xml,
+        "<itunes:owner><itunes:name>{}</itunes:name>",
+        encode_text(&channel.author)
+    );
+    if let Some(email) = &channel.email {
+        let _ = write!(xml, "<itunes:email>{}</itunes:email>", encode_text(email));
+    }
+    let _ = writeln!(xml, "</itunes:owner>");
+    if let Some(image) = &channel.image {
+        let _ = writeln!(
+            xml,
+            r#"<itunes:image href="{}"/>"#,
+            encode_double_quoted_attribute(&absolute_url(site_url, image))
+        );
+    }
+    let _ = writeln!(
+        xml,
+        r#"<itunes:category text="{}"/>"#,
+        encode_double_quoted_attribute(&config.category)
+    );
+    let _ = writeln!(xml, "<itunes:explicit>{}</itunes:explicit>", explicit);
+
+    for (post, audio) in episodes {
+        let link = absolute_url(site_url, &post.get_url_path());
+        let summary = post.excerpt.as_deref().unwrap_or(&post.title);
+
+        let _ = writeln!(xml, "<item>");
+        let _ = writeln!(xml, "<title>{}</title>", encode_text(&post.title));
+        let _ = writeln!(xml, "<link>{}</link>", encode_text(&link));
+        let _ = writeln!(
+            xml,
+            r#"<guid isPermaLink="false">{}</guid>"#,
+            encode_text(&post.id.to_string())
+        );
+        let _ = writeln!(
+            xml,
+            "<pubDate>{}</pubDate>",
+            post.published_at.unwrap_or(post.created_at).to_rfc2822()
+        );
+        let _ = writeln!(xml, "<description>{}</description>", encode_text(summary));
+        let _ = writeln!(
+            xml,
+            "<itunes:summary>{}</itunes:summary>",
+            encode_text(summary)
+        );
+        let _ = writeln!(
+            xml,
+            r#"<enclosure url="{}" length="{}" type="{}"/>"#,
+            encode_double_quoted_attribute(&absolute_url(site_url, &audio.url)),
+            audio.file_size,
+            encode_double_quoted_attribute(&audio.mime_type)
+        );
+        if let Some(duration) = audio.formatted_duration() {
+            let _ = writeln!(xml, "<itunes:duration>{}</itunes:duration>", duration);
+        }
+        let _ = writeln!(xml, "<itunes:explicit>{}</itunes:explicit>", explicit);
+        let _ = writeln!(xml, "</item>");
+    }
+
+    let _ = writeln!(xml, "</channel>");
+    let _ = writeln!(xml, "</rss>");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreatePost;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_render_feed_includes_enclosure_and_escapes_text() {
+        let post = Post::new(CreatePost {
+            slug: "episode-1".to_string(),
+            title: "Rust & Axum".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            excerpt: Some("<Intro> episode".to_string()),
+            category: None,
+            tags: vec![],
+            published: true,
+            featured: false,
+            author: None,
+            dropbox_path: String::new(),
+        });
+        let audio = AudioEnclosure {
+            post_id: post.id,
+            media_id: Uuid::new_v4(),
+            url: "/media/audio/2024/07/episode.mp3".to_string(),
+            file_size: 12345,
+            mime_type: "audio/mpeg".to_string(),
+            duration_seconds: Some(3725.4),
+            updated_at: Utc::now(),
+        };
+        let config = PodcastConfig {
+            site_url: "https://blog.example.com/".to_string(),
+            category: "Technology".to_string(),
+            language: "ja".to_string(),
+            explicit: false,
+        };
+        let channel = PodcastChannel {
+            title: "Tobelog Radio".to_string(),
+            description: "Talks".to_string(),
+            author: "Tobe".to_string(),
+            email: None,
+            image: Some("/static/cover.png".to_string()),
+        };
+
+        let xml = render_feed(&config, &channel, &[(post, audio)]);
+
+        assert!(xml.contains("<title>Rust &amp; Axum</title>"));
+        assert!(xml.contains("<description>&lt;Intro&gt; episode</description>"));
+        assert!(xml.contains(
+            r#"<enclosure url="https://blog.example.com/media/audio/2024/07/episode.mp3" length="12345" type="audio/mpeg"/>"#
+        ));
+        assert!(xml.contains("<itunes:duration>01:02:05</itunes:duration>"));
+        assert!(xml.contains(r#"<itunes:image href="https://blog.example.com/static/cover.png"/>"#));
+        assert!(xml.contains(r#"<itunes:category text="Technology"/>"#));
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Result};
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, SaltString};
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+use axum::http::{header, HeaderMap};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::Post;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hash a post password for storage
+pub fn hash_post_password(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow!("Failed to hash post password: {}", e))
+}
+
+/// Checks post passwords and signs the cookies that unlock protected posts
+#[derive(Clone)]
+pub struct PostPasswordService {
+    secret: Arc<Vec<u8>>,
+    cookie_ttl: Duration,
+}
+
+impl PostPasswordService {
+    /// Without a configured secret a random one is used, so unlock cookies
+    /// stop working when the server restarts
+    pub fn new(secret: Option<&str>, cookie_ttl: Duration) -> Self {
+        let secret = match secret {
+            Some(secret) => secret.as_bytes().to_vec(),
+            None => [Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat(),
+        };
+
+        Self {
+            secret: Arc::new(secret),
+            cookie_ttl,
+        }
+    }
+
+    /// Whether `password` matches the post's password
+    pub fn verify_password(&self, post: &Post, password: &str) -> bool {
+        let Some(hash) = &post.password_hash else {
+            return false;
+        };
+        PasswordHash::new(hash).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+    }
+
+    /// `Set-Cookie` value unlocking `post` for the cookie lifetime
+    pub fn unlock_cookie(&self, post: &Post) -> String {
+        let expires = Utc::now().timestamp() + self.cookie_ttl.as_secs() as i64;
+        format!(
+            "{}={}.{}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax",
+            cookie_name(post),
+            expires,
+            URL_SAFE_NO_PAD.encode(self.signature(post, expires).finalize().into_bytes()),
+            self.cookie_ttl.as_secs()
+        )
+    }
+
+    /// Whether the request carries an unexpired unlock cookie for `post`
+    pub fn is_unlocked(&self, headers: &HeaderMap, post: &Post) -> bool {
+        let name = cookie_name(post);
+        let Some(value) = headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|cookies| cookies.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find_map(|(cookie, value)| (cookie == name).then_some(value))
+        else {
+            return false;
+        };
+
+        let Some((expires, signature)) = value.split_once('.') else {
+            return false;
+        };
+        let Ok(expires) = expires.parse::<i64>() else {
+            return false;
+        };
+        if expires <= Utc::now().timestamp() {
+            return false;
+        }
+
+        URL_SAFE_NO_PAD.decode(signature).is_ok_and(|signature| {
+            self.signature(post, expires)
+                .verify_slice(&signature)
+                .is_ok()
+        })
+    }
+
+    /// Covers the password hash so that changing the password revokes issued cookies
+    fn signature(&self, post: &Post, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(post.id.as_bytes());
+        mac.update(&expires.to_be_bytes());
+        mac.update(post.password_hash.as_deref().unwrap_or_default().as_bytes());
+        mac
+    }
+}
+
+fn cookie_name(post: &Post) -> String {
+    format!("post_unlock_{}", post.id.simple())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreatePost;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_unlock_cookie_is_bound_to_the_password() {
+        let service = PostPasswordService::new(Some("secret"), Duration::from_secs(60));
+        let mut post = Post::new(CreatePost {
+            slug: "protected".to_string(),
+            title: "Protected".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            excerpt: None,
+            category: None,
+            tags: vec![],
+            published: true,
+            featured: false,
+            author: None,
+            dropbox_path: "/posts/protected.md".to_string(),
+        });
+        post.password_hash = Some(hash_post_password("hunter2").unwrap());
+        assert!(service.verify_password(&post, "hunter2"));
+        assert!(!service.verify_password(&post, "wrong"));
+
+        let cookie = service.unlock_cookie(&post);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(cookie.split(';').next().unwrap()).unwrap(),
+        );
+        assert!(service.is_unlocked(&headers, &post));
+        assert!(!service.is_unlocked(&HeaderMap::new(), &post));
+
+        post.password_hash = Some(hash_post_password("changed").unwrap());
+        assert!(!service.is_unlocked(&headers, &post));
+    }
+}
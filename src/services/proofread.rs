@@ -0,0 +1,296 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::models::{QualityCheckResults, QualitySeverity, QualityWarning};
+
+/// Particles that are almost always a typo when doubled (e.g. 「のの」「をを」)
+const DOUBLED_PARTICLES: &[char] = &['の', 'を', 'に', 'が', 'で', 'と'];
+
+/// Sentence terminators for Japanese and English text
+const SENTENCE_TERMINATORS: &[char] = &['。', '！', '？', '!', '?'];
+
+/// Proofreading configuration
+#[derive(Debug, Clone)]
+pub struct ProofreadConfig {
+    /// Endpoint of an external textlint server; built-in heuristics only when unset
+    pub textlint_url: Option<String>,
+    /// Maximum characters in a Japanese sentence
+    pub max_sentence_length: usize,
+    /// Maximum words in an English sentence
+    pub max_sentence_words: usize,
+}
+
+impl Default for ProofreadConfig {
+    fn default() -> Self {
+        Self {
+            textlint_url: None,
+            max_sentence_length: 100,
+            max_sentence_words: 40,
+        }
+    }
+}
+
+/// Message reported by a textlint server
+#[derive(Debug, Deserialize)]
+struct TextlintMessage {
+    #[serde(rename = "ruleId")]
+    rule_id: Option<String>,
+    message: String,
+    line: Option<usize>,
+    severity: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextlintResponse {
+    messages: Vec<TextlintMessage>,
+}
+
+/// Proofreading service for Japanese and English drafts
+#[derive(Clone)]
+pub struct ProofreadService {
+    config: ProofreadConfig,
+    client: Client,
+}
+
+impl ProofreadService {
+    /// Create a new proofreading service
+    pub fn new(config: ProofreadConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        Self { config, client }
+    }
+
+    /// Run every configured linter against a Markdown draft
+    pub async fn check(&self, markdown: &str) -> QualityCheckResults {
+        let mut warnings = self.check_builtin(markdown);
+        let mut checked_by = vec!["builtin".to_string()];
+
+        if let Some(url) = &self.config.textlint_url {
+            match self.check_textlint(url, markdown).await {
+                Ok(textlint_warnings) => {
+                    warnings.extend(textlint_warnings);
+                    checked_by.push("textlint".to_string());
+                }
+                Err(e) => {
+                    // An unreachable linter must not block the import
+                    warn!("textlint check failed: {}", e);
+                    warnings.push(QualityWarning {
+                        rule: "textlint".to_string(),
+                        message: format!("textlint server unavailable: {}", e),
+                        line: None,
+                        severity: QualitySeverity::Info,
+                    });
+                }
+            }
+        }
+
+        let mut results = QualityCheckResults {
+            passed: true,
+            warnings,
+            checked_by,
+        };
+        results.passed = results.error_count() == 0;
+
+        debug!(
+            "Proofreading finished with {} findings",
+            results.warnings.len()
+        );
+        results
+    }
+
+    /// Built-in heuristics: doubled particles/words and overly long sentences
+    fn check_builtin(&self, markdown: &str) -> Vec<QualityWarning> {
+        let mut warnings = Vec::new();
+        let mut in_code_block = false;
+
+        for (index, raw_line) in markdown.lines().enumerate() {
+            let line_number = index + 1;
+
+            if raw_line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+
+            let line = strip_inline_markup(raw_line);
+
+            for particle in find_doubled_particles(&line) {
+                warnings.push(QualityWarning {
+                    rule: "doubled-particle".to_string(),
+                    message: format!("助詞「{}」が連続しています", particle),
+                    line: Some(line_number),
+                    severity: QualitySeverity::Warning,
+                });
+            }
+
+            for word in find_doubled_words(&line) {
+                warnings.push(QualityWarning {
+                    rule: "doubled-word".to_string(),
+                    message: format!("Repeated word \"{}\"", word),
+                    line: Some(line_number),
+                    severity: QualitySeverity::Warning,
+                });
+            }
+
+            for sentence in line.split_inclusive(SENTENCE_TERMINATORS) {
+                let sentence = sentence.trim();
+                if let Some(message) = self.long_sentence_message(sentence) {
+                    warnings.push(QualityWarning {
+                        rule: "sentence-length".to_string(),
+                        message,
+                        line: Some(line_number),
+                        severity: QualitySeverity::Info,
+                    });
+                }
+            }
+        }
+
+        warnings
+    }
+
+    fn long_sentence_message(&self, sentence: &str) -> Option<String> {
+        if sentence.chars().any(is_japanese) {
+            let length = sentence.chars().count();
+            (length > self.config.max_sentence_length).then(|| {
+                format!(
+                    "一文が長すぎます（{}文字 / 上限{}文字）",
+                    length, self.config.max_sentence_length
+                )
+            })
+        } else {
+            let words = sentence
+                .split(". ")
+                .map(|s| s.split_whitespace().count())
+                .max()
+                .unwrap_or(0);
+            (words > self.config.max_sentence_words).then(|| {
+                format!(
+                    "Sentence is too long ({} words, limit {})",
+                    words, self.config.max_sentence_words
+                )
+            })
+        }
+    }
+
+    /// Send the draft to a textlint server and convert its messages
+    async fn check_textlint(&self, url: &str, markdown: &str) -> Result<Vec<QualityWarning>> {
+        let response = self
+            .client
+            .post(url)
+            .json(&serde_json::json!({ "text": markdown, "ext": ".md" }))
+            .send()
+            .await
+            .context("Failed to reach textlint server")?
+            .error_for_status()
+            .context("textlint server returned an error")?;
+
+        let result: TextlintResponse =
+            response.json().await.context("Invalid textlint response")?;
+
+        Ok(result
+            .messages
+            .into_iter()
+            .map(|m| QualityWarning {
+                rule: m.rule_id.unwrap_or_else(|| "textlint".to_string()),
+                message: m.message,
+                line: m.line,
+                severity: match m.severity {
+                    Some(2) => QualitySeverity::Error,
+                    Some(0) => QualitySeverity::Info,
+                    _ => QualitySeverity::Warning,
+                },
+            })
+            .collect())
+    }
+}
+
+fn is_japanese(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30ff}' | '\u{4e00}'..='\u{9fff}')
+}
+
+/// Drop inline code and link targets so they are not proofread
+fn strip_inline_markup(line: &str) -> String {
+    static INLINE_CODE: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+
+    let inline_code = INLINE_CODE.get_or_init(|| Regex::new(r"`[^`]*`").expect("valid regex"));
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").expect("valid regex"));
+
+    let without_code = inline_code.replace_all(line, "");
+    link.replace_all(&without_code, "$1").into_owned()
+}
+
+fn find_doubled_particles(line: &str) -> Vec<char> {
+    let chars: Vec<char> = line.chars().collect();
+    chars
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1] && DOUBLED_PARTICLES.contains(&pair[0]))
+        .map(|pair| pair[0])
+        .collect()
+}
+
+fn find_doubled_words(line: &str) -> Vec<String> {
+    let words: Vec<String> = line
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .collect();
+
+    words
+        .windows(2)
+        .filter(|pair| {
+            !pair[0].is_empty()
+                && pair[0] == pair[1]
+                && pair[0].chars().all(|c| c.is_ascii_alphabetic())
+        })
+        .map(|pair| pair[0].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_checks() {
+        let service = ProofreadService::new(ProofreadConfig {
+            max_sentence_length: 20,
+            ..Default::default()
+        });
+
+        let markdown = "私のの本です。\nThis is the the answer.\n```\nのの the the\n```\nこれはとても長い文章でありまして二十文字を超えてしまっているのです。";
+        let warnings = service.check_builtin(markdown);
+        let rules: Vec<(&str, Option<usize>)> =
+            warnings.iter().map(|w| (w.rule.as_str(), w.line)).collect();
+
+        assert_eq!(
+            rules,
+            vec![
+                ("doubled-particle", Some(1)),
+                ("doubled-word", Some(2)),
+                ("sentence-length", Some(6)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_code_is_ignored() {
+        assert!(find_doubled_words(&strip_inline_markup("use `the the` here")).is_empty());
+        assert_eq!(
+            strip_inline_markup("see [the docs](https://example.com)"),
+            "see the docs"
+        );
+    }
+}
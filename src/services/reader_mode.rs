@@ -0,0 +1,98 @@
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Simplify rendered post HTML for the lite page: drop scripts, styles and
+/// embeds, strip presentational and event attributes, and point images at
+/// their thumbnails
+///
+/// `thumbnails` maps media URLs used in the post to their thumbnail URLs.
+/// Embedded frames are replaced by a plain link to what they embed.
+pub fn simplify_html(html: &str, thumbnails: &HashMap<String, String>) -> String {
+    static REMOVED: OnceLock<Vec<Regex>> = OnceLock::new();
+    static IFRAME: OnceLock<Regex> = OnceLock::new();
+    static ATTRIBUTES: OnceLock<Regex> = OnceLock::new();
+    static IMG: OnceLock<Regex> = OnceLock::new();
+    static SRC: OnceLock<Regex> = OnceLock::new();
+
+    let removed = REMOVED.get_or_init(|| {
+        [
+            "script", "style", "noscript", "object", "form", "button", "template",
+        ]
+        .iter()
+        .map(|element| format!(r"(?is)<{}\b.*?</{}\s*>", element, element))
+        .chain([r"(?is)<(?:embed|link|input|source)\b[^>]*>".to_string()])
+        .map(|pattern| Regex::new(&pattern).expect("valid regex"))
+        .collect()
+    });
+    let iframe = IFRAME.get_or_init(|| {
+        Regex::new(r#"(?is)<iframe\b[^>]*?\bsrc\s*=\s*["']([^"']+)["'][^>]*>.*?</iframe\s*>"#)
+            .expect("valid regex")
+    });
+    let attributes = ATTRIBUTES.get_or_init(|| {
+        Regex::new(
+            r#"(?i)\s+(?:on[a-z]+|style|class|srcset|sizes|loading|decoding)\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+)"#,
+        )
+        .expect("valid regex")
+    });
+    let img = IMG.get_or_init(|| Regex::new(r"(?is)<img\b[^>]*>").expect("valid regex"));
+    let src = SRC
+        .get_or_init(|| Regex::new(r#"(?i)\bsrc\s*=\s*(["'])([^"']*)["']"#).expect("valid regex"));
+
+    let mut simplified = removed.iter().fold(html.to_string(), |text, pattern| {
+        pattern.replace_all(&text, "").into_owned()
+    });
+    simplified = iframe
+        .replace_all(&simplified, r#"<p><a href="$1">$1</a></p>"#)
+        .into_owned();
+    simplified = attributes.replace_all(&simplified, "").into_owned();
+
+    img.replace_all(&simplified, |captures: &Captures| {
+        let tag = src.replace(&captures[0], |src: &Captures| {
+            let url = &src[2];
+            let path = url.split(['?', '#']).next().unwrap_or(url);
+            let thumbnail = thumbnails
+                .get(url)
+                .or_else(|| thumbnails.get(path))
+                .map(String::as_str)
+                .unwrap_or(url);
+            format!("src={}{}{}", &src[1], thumbnail, &src[1])
+        });
+        // Defer offscreen images on slow connections
+        tag.replacen("<img", r#"<img loading="lazy""#, 1)
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simplify_html_strips_scripts_styles_and_attributes() {
+        let html = r#"<p class="lead" style="color:red" onclick="go()">Hi</p><script>alert(1)</script><style>p{}</style><form><input name="q"></form>"#;
+        assert_eq!(simplify_html(html, &HashMap::new()), "<p>Hi</p>");
+    }
+
+    #[test]
+    fn test_simplify_html_links_embeds() {
+        let html = r#"<iframe width="560" src="https://www.youtube.com/embed/abc" allowfullscreen></iframe>"#;
+        assert_eq!(
+            simplify_html(html, &HashMap::new()),
+            r#"<p><a href="https://www.youtube.com/embed/abc">https://www.youtube.com/embed/abc</a></p>"#
+        );
+    }
+
+    #[test]
+    fn test_simplify_html_uses_thumbnails() {
+        let thumbnails = HashMap::from([(
+            "/media/images/2024/07/cat.png".to_string(),
+            "/media/thumbnails/2024/07/images/thumb_cat.jpg".to_string(),
+        )]);
+        let html = r#"<img src="/media/images/2024/07/cat.png?w=800" srcset="a 1x, b 2x" alt="Cat"><img src='https://example.com/dog.png' alt="Dog">"#;
+        assert_eq!(
+            simplify_html(html, &thumbnails),
+            r#"<img loading="lazy" src="/media/thumbnails/2024/07/images/thumb_cat.jpg" alt="Cat"><img loading="lazy" src='https://example.com/dog.png' alt="Dog">"#
+        );
+    }
+}
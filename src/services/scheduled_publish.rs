@@ -0,0 +1,66 @@
+use anyhow::Result;
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::models::{AdminEvent, Post};
+use crate::services::{DatabaseService, EventBus};
+
+/// Publishes scheduled posts once their `publish_at` time has passed, and
+/// unpublishes or archives posts once their `expires_at` time has
+#[derive(Clone)]
+pub struct ScheduledPublishService {
+    database: DatabaseService,
+    events: EventBus,
+}
+
+impl ScheduledPublishService {
+    pub fn new(database: DatabaseService, events: EventBus) -> Self {
+        Self { database, events }
+    }
+
+    /// Check for due and expired posts every `interval`
+    pub fn spawn_periodic(self, interval: Duration) {
+        info!(
+            "Checking for scheduled and expired posts every {:?}",
+            interval
+        );
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.publish_due().await {
+                    error!("Scheduled publishing failed: {}", e);
+                }
+                if let Err(e) = self.expire_due().await {
+                    error!("Post expiry failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Publish every due post and notify the admin UI about each
+    pub async fn publish_due(&self) -> Result<Vec<Post>> {
+        let published = self.database.publish_due_posts().await?;
+        for post in &published {
+            self.events.publish(AdminEvent::ScheduledPostPublished {
+                slug: post.slug.clone(),
+                title: post.title.clone(),
+            });
+        }
+        Ok(published)
+    }
+
+    /// Unpublish or archive every expired post and notify the admin UI about each
+    pub async fn expire_due(&self) -> Result<Vec<Post>> {
+        let expired = self.database.expire_due_posts().await?;
+        for post in &expired {
+            self.events.publish(AdminEvent::PostExpired {
+                slug: post.slug.clone(),
+                title: post.title.clone(),
+                action: post.expiry_action,
+            });
+        }
+        Ok(expired)
+    }
+}
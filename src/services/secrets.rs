@@ -0,0 +1,266 @@
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use std::path::Path;
+
+/// Prefix of values encrypted with a [`SecretBox`]
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Environment variables holding credentials, decrypted at startup when encrypted
+#[allow(dead_code)] // Used by the secrets CLI
+pub const SECRET_VARS: &[&str] = &[
+    "DROPBOX_ACCESS_TOKEN",
+    "S3_ACCESS_KEY_ID",
+    "S3_SECRET_ACCESS_KEY",
+    "API_KEY",
+    "CACHE_PURGE_TOKEN",
+    "POST_PASSWORD_SECRET",
+    "ALT_TEXT_API_KEY",
+    "SUMMARY_API_KEY",
+    "CAPTCHA_SECRET_KEY",
+    "CROSSPOST_QIITA_TOKEN",
+    "CROSSPOST_DEVTO_API_KEY",
+    "CROSSPOST_ZENN_GITHUB_TOKEN",
+    "GITHUB_MIRROR_TOKEN",
+    "DIGEST_SLACK_WEBHOOK_URL",
+    "NOTIFY_SLACK_WEBHOOK_URL",
+    "NOTIFY_DISCORD_WEBHOOK_URL",
+];
+
+const KEY_LEN: usize = 32;
+
+/// AES-256-GCM encryption of credentials at rest, keyed from a key file
+///
+/// Encrypted values look like `enc:v1:<base64 nonce+ciphertext>`. The name of the
+/// variable is authenticated with the value, so ciphertexts cannot be swapped
+/// between variables.
+pub struct SecretBox {
+    key: LessSafeKey,
+}
+
+impl SecretBox {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| anyhow!("Secrets key must be {} bytes", KEY_LEN))?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+        })
+    }
+
+    /// Load a key file holding the key in base64 (as written by [`SecretBox::generate_key_file`])
+    pub fn from_key_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read secrets key file {}", path.display()))?;
+        let key = STANDARD
+            .decode(contents.trim())
+            .with_context(|| format!("Secrets key file {} is not base64", path.display()))?;
+        Self::new(&key)
+    }
+
+    /// Write a new random key file readable only by its owner
+    #[allow(dead_code)] // Used by the secrets CLI
+    pub fn generate_key_file(path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+
+        let mut key = [0u8; KEY_LEN];
+        OsRng.fill_bytes(&mut key);
+        std::fs::write(path, format!("{}\n", STANDARD.encode(key)))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    #[allow(dead_code)] // Used by the secrets CLI
+    pub fn encrypt(&self, name: &str, plaintext: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut sealed = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(name.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow!("Failed to encrypt {}", name))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&sealed);
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(payload)))
+    }
+
+    pub fn decrypt(&self, name: &str, value: &str) -> Result<String> {
+        let encoded = value
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .ok_or_else(|| anyhow!("{} is not an encrypted value", name))?;
+        let mut payload = STANDARD
+            .decode(encoded)
+            .with_context(|| format!("{} is not valid base64", name))?;
+        if payload.len() < NONCE_LEN {
+            bail!("{} is too short to be encrypted", name);
+        }
+
+        let mut sealed = payload.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&payload)
+            .map_err(|_| anyhow!("{} has an invalid nonce", name))?;
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(name.as_bytes()), &mut sealed)
+            .map_err(|_| anyhow!("Failed to decrypt {} (wrong key?)", name))?;
+
+        String::from_utf8(plaintext.to_vec()).with_context(|| format!("{} is not UTF-8", name))
+    }
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Plain value of a secret variable, decrypting it when needed
+pub fn reveal(name: &str, value: String, secrets: Option<&SecretBox>) -> Result<String> {
+    if !is_encrypted(&value) {
+        return Ok(value);
+    }
+    match secrets {
+        Some(secrets) => secrets.decrypt(name, &value),
+        None => bail!("{} is encrypted but SECRETS_KEY_FILE is not set", name),
+    }
+}
+
+/// Re-encrypt the secret variables of a `.env` file with `new`
+///
+/// Values encrypted with `old` are decrypted first; plain values of [`SECRET_VARS`]
+/// are encrypted too. Other lines are kept as they are. Returns the new contents
+/// and the number of values written.
+#[allow(dead_code)] // Used by the secrets CLI
+pub fn reencrypt_env_file(
+    contents: &str,
+    old: Option<&SecretBox>,
+    new: &SecretBox,
+) -> Result<(String, usize)> {
+    let mut output = String::with_capacity(contents.len());
+    let mut count = 0;
+
+    for line in contents.lines() {
+        let assignment = line
+            .split_once('=')
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .filter(|(name, value)| {
+                !name.starts_with('#')
+                    && !value.is_empty()
+                    && (is_encrypted(value) || SECRET_VARS.contains(name))
+            });
+
+        match assignment {
+            Some((name, value)) => {
+                let value = unquote(value);
+                let value = reveal(name, value.to_string(), old)?;
+                output.push_str(&format!("{}={}\n", name, new.encrypt(name, &value)?));
+                count += 1;
+            }
+            None => {
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+    }
+
+    Ok((output, count))
+}
+
+/// Value without the quotes `.env` files allow around it
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret_box() -> SecretBox {
+        let mut key = [0u8; KEY_LEN];
+        OsRng.fill_bytes(&mut key);
+        SecretBox::new(&key).unwrap()
+    }
+
+    #[test]
+    fn test_values_round_trip_and_are_bound_to_their_name() {
+        let secrets = secret_box();
+        let encrypted = secrets.encrypt("API_KEY", "hunter2").unwrap();
+
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("hunter2"));
+        assert_eq!(secrets.decrypt("API_KEY", &encrypted).unwrap(), "hunter2");
+        // Fresh nonce each time
+        assert_ne!(encrypted, secrets.encrypt("API_KEY", "hunter2").unwrap());
+
+        assert!(secrets.decrypt("DROPBOX_ACCESS_TOKEN", &encrypted).is_err());
+        assert!(secret_box().decrypt("API_KEY", &encrypted).is_err());
+        assert!(SecretBox::new(b"short").is_err());
+    }
+
+    #[test]
+    fn test_reveal_passes_plain_values_through() {
+        let secrets = secret_box();
+        let encrypted = secrets.encrypt("API_KEY", "hunter2").unwrap();
+
+        assert_eq!(
+            reveal("API_KEY", "plain".to_string(), None).unwrap(),
+            "plain"
+        );
+        assert_eq!(
+            reveal("API_KEY", encrypted.clone(), Some(&secrets)).unwrap(),
+            "hunter2"
+        );
+        assert!(reveal("API_KEY", encrypted, None).is_err());
+    }
+
+    #[test]
+    fn test_env_file_is_reencrypted_with_the_new_key() {
+        let old = secret_box();
+        let new = secret_box();
+        let contents = format!(
+            "# Credentials\nAPI_KEY={}\nDROPBOX_ACCESS_TOKEN=\"sl.token\"\nSERVER_PORT=3000\n# S3_SECRET_ACCESS_KEY=example\n",
+            old.encrypt("API_KEY", "hunter2").unwrap()
+        );
+
+        let (rotated, count) = reencrypt_env_file(&contents, Some(&old), &new).unwrap();
+        assert_eq!(count, 2);
+        assert!(rotated.contains("SERVER_PORT=3000\n"));
+        assert!(rotated.contains("# S3_SECRET_ACCESS_KEY=example\n"));
+        assert!(!rotated.contains("sl.token"));
+
+        let value = |name: &str| {
+            rotated
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("{}=", name)))
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            new.decrypt("API_KEY", &value("API_KEY")).unwrap(),
+            "hunter2"
+        );
+        assert_eq!(
+            new.decrypt("DROPBOX_ACCESS_TOKEN", &value("DROPBOX_ACCESS_TOKEN"))
+                .unwrap(),
+            "sl.token"
+        );
+
+        // The old key is needed to read values encrypted with it
+        assert!(reencrypt_env_file(&contents, None, &new).is_err());
+    }
+}
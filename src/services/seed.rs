@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use image::{ImageOutputFormat, Rgb, RgbImage};
+use std::io::Cursor;
+use tracing::{debug, info};
+
+use crate::models::CreatePost;
+use crate::services::{DatabaseService, MarkdownService, MediaService, ThemeService, UploadedFile};
+
+/// Sample post written by the seed command
+struct SamplePost {
+    slug: &'static str,
+    title: &'static str,
+    category: &'static str,
+    tags: &'static [&'static str],
+    published: bool,
+    featured: bool,
+    content: &'static str,
+}
+
+/// Sample image written by the seed command, a two-color gradient
+struct SampleImage {
+    filename: &'static str,
+    alt_text: &'static str,
+    from: [u8; 3],
+    to: [u8; 3],
+}
+
+const SAMPLE_IMAGES: &[SampleImage] = &[
+    SampleImage {
+        filename: "seed-sunrise.png",
+        alt_text: "オレンジから紫へのグラデーション",
+        from: [251, 146, 60],
+        to: [139, 92, 246],
+    },
+    SampleImage {
+        filename: "seed-ocean.png",
+        alt_text: "水色から紺へのグラデーション",
+        from: [56, 189, 248],
+        to: [30, 58, 138],
+    },
+];
+
+const SAMPLE_POSTS: &[SamplePost] = &[
+    SamplePost {
+        slug: "welcome-to-tobelog",
+        title: "Tobelogへようこそ",
+        category: "お知らせ",
+        tags: &["tobelog", "はじめに"],
+        published: true,
+        featured: true,
+        content: "# Tobelogへようこそ\n\nこれはシードコマンドが作成したサンプル記事です。\n\n![{image_alt}]({image_url})\n\n記事はDropboxのMarkdownファイルから同期され、管理画面から編集できます。",
+    },
+    SamplePost {
+        slug: "rust-error-handling",
+        title: "Rustのエラーハンドリング入門",
+        category: "tech",
+        tags: &["rust", "anyhow"],
+        published: true,
+        featured: false,
+        content: "# Rustのエラーハンドリング入門\n\n`Result` と `?` 演算子で失敗を呼び出し元へ返します。\n\n```rust\nfn parse(input: &str) -> anyhow::Result<u32> {\n    Ok(input.trim().parse()?)\n}\n```\n\nアプリケーションでは `anyhow::Context` で原因を書き添えると調査が楽になります。",
+    },
+    SamplePost {
+        slug: "async-sqlite-notes",
+        title: "非同期RustでSQLiteを使うメモ",
+        category: "tech",
+        tags: &["rust", "sqlite", "async"],
+        published: true,
+        featured: false,
+        content: "# 非同期RustでSQLiteを使うメモ\n\n`sqlx` の接続プールはWALモードと組み合わせると読み込みが書き込みを待たなくなります。\n\n- マイグレーションは起動時に適用する\n- `busy_timeout` を設定してロック待ちを許す",
+    },
+    SamplePost {
+        slug: "weekend-hiking",
+        title: "週末の山歩き",
+        category: "life",
+        tags: &["登山", "写真"],
+        published: true,
+        featured: false,
+        content: "# 週末の山歩き\n\n朝早く出発して、昼前に山頂へ着きました。\n\n> 山頂からの景色は、登った人だけのごほうびです。\n\n帰りは温泉に寄って帰りました。",
+    },
+    SamplePost {
+        slug: "reading-list",
+        title: "今月読んだ本",
+        category: "life",
+        tags: &["読書"],
+        published: true,
+        featured: false,
+        content: "# 今月読んだ本\n\n1. プログラミングRust\n2. データ指向アプリケーションデザイン\n3. エッセイ集を一冊\n\nどれも付箋だらけになりました。",
+    },
+    SamplePost {
+        slug: "draft-ideas",
+        title: "書きかけのアイデア",
+        category: "notes",
+        tags: &["下書き"],
+        published: false,
+        featured: false,
+        content: "# 書きかけのアイデア\n\n公開前の下書きの例です。管理画面の下書き一覧に表示されます。",
+    },
+];
+
+/// Numbers of records written by [`SeedService::seed`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeedSummary {
+    pub posts: usize,
+    pub media: usize,
+    pub themes: usize,
+    /// Sample posts left alone because their slug already exists
+    pub skipped_posts: usize,
+}
+
+/// Fills an empty blog with sample posts, media and themes
+///
+/// For local development, demos and screenshots. Running it again only adds
+/// what is missing: posts are matched by slug and images by content hash.
+#[derive(Clone)]
+#[allow(dead_code)] // Used by the seed binary
+pub struct SeedService {
+    database: DatabaseService,
+    markdown: MarkdownService,
+    media: MediaService,
+    themes: ThemeService,
+}
+
+#[allow(dead_code)] // Used by the seed binary
+impl SeedService {
+    pub fn new(
+        database: DatabaseService,
+        markdown: MarkdownService,
+        media: MediaService,
+        themes: ThemeService,
+    ) -> Self {
+        Self {
+            database,
+            markdown,
+            media,
+            themes,
+        }
+    }
+
+    /// Write the sample data that is not there yet
+    pub async fn seed(&self) -> Result<SeedSummary> {
+        let mut summary = SeedSummary::default();
+
+        let themes_before = self.themes.list_themes(Default::default()).await?.len();
+        self.themes.create_preset_themes().await?;
+        summary.themes = self.themes.list_themes(Default::default()).await?.len() - themes_before;
+
+        let mut images = Vec::new();
+        for sample in SAMPLE_IMAGES {
+            let (media, reused) = self
+                .media
+                .upload_data(
+                    UploadedFile {
+                        filename: sample.filename.to_string(),
+                        content_type: "image/png".to_string(),
+                        data: gradient_png(sample.from, sample.to)?,
+                    },
+                    Some(sample.alt_text.to_string()),
+                    None,
+                    false,
+                )
+                .await
+                .with_context(|| format!("Failed to store sample image {}", sample.filename))?;
+            if !reused {
+                summary.media += 1;
+            }
+            images.push(media);
+        }
+
+        for sample in SAMPLE_POSTS {
+            if self.database.get_post_by_slug(sample.slug).await?.is_some() {
+                debug!("Sample post {} already exists", sample.slug);
+                summary.skipped_posts += 1;
+                continue;
+            }
+
+            let content = match images.first() {
+                Some(image) => sample
+                    .content
+                    .replace("{image_url}", &image.url)
+                    .replace("{image_alt}", image.alt_text.as_deref().unwrap_or_default()),
+                None => sample.content.to_string(),
+            };
+            self.database
+                .create_post(CreatePost {
+                    slug: sample.slug.to_string(),
+                    title: sample.title.to_string(),
+                    html_content: self.markdown.markdown_to_html(&content)?,
+                    content,
+                    category: Some(sample.category.to_string()),
+                    tags: sample.tags.iter().map(|tag| tag.to_string()).collect(),
+                    published: sample.published,
+                    featured: sample.featured,
+                    author: Some("Tobelog".to_string()),
+                    excerpt: None,
+                    dropbox_path: format!("/BlogStorage/posts/{}.md", sample.slug),
+                })
+                .await
+                .with_context(|| format!("Failed to create sample post {}", sample.slug))?;
+            summary.posts += 1;
+        }
+
+        info!(
+            "Seeded {} posts, {} media files and {} themes",
+            summary.posts, summary.media, summary.themes
+        );
+        Ok(summary)
+    }
+}
+
+/// 640x360 PNG fading from `from` to `to`
+fn gradient_png(from: [u8; 3], to: [u8; 3]) -> Result<Vec<u8>> {
+    let (width, height) = (640, 360);
+    let image = RgbImage::from_fn(width, height, |x, _| {
+        let t = x as f32 / (width - 1) as f32;
+        Rgb(std::array::from_fn(|i| {
+            (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8
+        }))
+    });
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+        .context("Failed to encode sample image")?;
+    Ok(png)
+}
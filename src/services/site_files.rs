@@ -0,0 +1,170 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+
+use crate::models::SiteConfig;
+use crate::services::DatabaseService;
+
+/// How long a generated security.txt stays valid (RFC 9116 `Expires`)
+const SECURITY_TXT_VALIDITY_DAYS: i64 = 365;
+
+/// Configuration of the generated robots.txt, security.txt and humans.txt
+#[derive(Debug, Clone)]
+pub struct SiteFilesConfig {
+    /// Public base URL used to build absolute links
+    pub site_url: String,
+    /// Deployment environment; anything but "production" disallows all crawling
+    pub environment: String,
+    /// Paths crawlers are asked to skip in production
+    pub robots_disallow: Vec<String>,
+    /// security.txt contact URI; falls back to the author's email address
+    pub security_contact: Option<String>,
+}
+
+impl SiteFilesConfig {
+    pub fn is_production(&self) -> bool {
+        self.environment.eq_ignore_ascii_case("production")
+    }
+}
+
+/// Service that generates well-known text files from the site configuration
+#[derive(Clone)]
+pub struct SiteFilesService {
+    database: DatabaseService,
+    config: SiteFilesConfig,
+}
+
+impl SiteFilesService {
+    /// Create a new site files service
+    pub fn new(database: DatabaseService, config: SiteFilesConfig) -> Self {
+        Self { database, config }
+    }
+
+    async fn site_config(&self) -> Result<SiteConfig> {
+        Ok(self.database.get_site_config().await?.unwrap_or_default())
+    }
+
+    /// Body of `/robots.txt`
+    pub fn robots_txt(&self) -> String {
+        render_robots_txt(&self.config)
+    }
+
+    /// Body of `/.well-known/security.txt`, or `None` when no contact is configured
+    pub async fn security_txt(&self) -> Result<Option<String>> {
+        let site = self.site_config().await?;
+        Ok(render_security_txt(&self.config, &site, Utc::now()))
+    }
+
+    /// Body of `/humans.txt`
+    pub async fn humans_txt(&self) -> Result<String> {
+        let site = self.site_config().await?;
+        Ok(render_humans_txt(&self.config, &site))
+    }
+}
+
+fn render_robots_txt(config: &SiteFilesConfig) -> String {
+    let mut robots = String::from("User-agent: *\n");
+
+    // Writing to a String cannot fail
+    if !config.is_production() {
+        let _ = writeln!(robots, "# {} environment: do not index", config.environment);
+        let _ = writeln!(robots, "Disallow: /");
+        return robots;
+    }
+
+    if config.robots_disallow.is_empty() {
+        let _ = writeln!(robots, "Disallow:");
+    }
+    for path in &config.robots_disallow {
+        let _ = writeln!(robots, "Disallow: {}", path);
+    }
+    robots
+}
+
+fn render_security_txt(
+    config: &SiteFilesConfig,
+    site: &SiteConfig,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let contact = config.security_contact.clone().or_else(|| {
+        site.author_email
+            .as_ref()
+            .map(|email| format!("mailto:{}", email))
+    })?;
+
+    let mut security = String::new();
+    let _ = writeln!(security, "Contact: {}", contact);
+    let _ = writeln!(
+        security,
+        "Expires: {}",
+        (now + Duration::days(SECURITY_TXT_VALIDITY_DAYS)).format("%Y-%m-%dT%H:%M:%SZ")
+    );
+    let _ = writeln!(security, "Preferred-Languages: ja, en");
+    let _ = writeln!(
+        security,
+        "Canonical: {}/.well-known/security.txt",
+        config.site_url.trim_end_matches('/')
+    );
+    Some(security)
+}
+
+fn render_humans_txt(config: &SiteFilesConfig, site: &SiteConfig) -> String {
+    let mut humans = String::from("/* TEAM */\n");
+    let _ = writeln!(humans, "Author: {}", site.author_name);
+    if let Some(email) = &site.author_email {
+        let _ = writeln!(humans, "Contact: {}", email);
+    }
+    for link in &site.social_links {
+        let _ = writeln!(humans, "{}: {}", link.platform, link.url);
+    }
+
+    let _ = writeln!(humans, "\n/* SITE */");
+    let _ = writeln!(humans, "Title: {}", site.site_title);
+    let _ = writeln!(humans, "URL: {}", config.site_url.trim_end_matches('/'));
+    if let Some(updated_at) = site.updated_at {
+        let _ = writeln!(humans, "Last update: {}", updated_at.format("%Y/%m/%d"));
+    }
+    let _ = writeln!(humans, "Language: Japanese");
+    let _ = writeln!(humans, "Software: Tobelog (Rust, Axum, SQLite)");
+    humans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(environment: &str) -> SiteFilesConfig {
+        SiteFilesConfig {
+            site_url: "https://blog.example.com/".to_string(),
+            environment: environment.to_string(),
+            robots_disallow: vec!["/admin".to_string(), "/api/".to_string()],
+            security_contact: None,
+        }
+    }
+
+    #[test]
+    fn test_robots_txt_disallows_everything_outside_production() {
+        let staging = render_robots_txt(&config("staging"));
+        assert!(staging.contains("Disallow: /\n"));
+        assert!(!staging.contains("Disallow: /admin"));
+
+        let production = render_robots_txt(&config("production"));
+        assert!(production.contains("Disallow: /admin\n"));
+        assert!(!production.contains("Disallow: /\n"));
+    }
+
+    #[test]
+    fn test_security_txt_requires_contact() {
+        let now = DateTime::parse_from_rfc3339("2024-07-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut site = SiteConfig::default();
+        assert_eq!(render_security_txt(&config("production"), &site, now), None);
+
+        site.author_email = Some("tobe@example.com".to_string());
+        let security = render_security_txt(&config("production"), &site, now).unwrap();
+        assert!(security.contains("Contact: mailto:tobe@example.com\n"));
+        assert!(security.contains("Expires: 2025-07-01T00:00:00Z\n"));
+        assert!(security.contains("Canonical: https://blog.example.com/.well-known/security.txt"));
+    }
+}
@@ -0,0 +1,340 @@
+use chrono::Utc;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{warn, Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use super::telemetry::{QueryFields, SQLX_QUERY_TARGET};
+use crate::models::{SlowLogEntry, SlowLogKind, SlowLogReport};
+
+/// Longest SQL kept per entry
+const MAX_SQL_LEN: usize = 2000;
+
+/// Slow log thresholds and size
+#[derive(Debug, Clone)]
+pub struct SlowLogConfig {
+    pub query_threshold: Duration,
+    pub request_threshold: Duration,
+    /// Entries kept; the oldest are dropped first
+    pub capacity: usize,
+}
+
+impl Default for SlowLogConfig {
+    fn default() -> Self {
+        Self {
+            query_threshold: Duration::from_millis(250),
+            request_threshold: Duration::from_millis(1000),
+            capacity: 200,
+        }
+    }
+}
+
+/// Ring buffer of the most recent queries and requests that exceeded their threshold
+#[derive(Clone)]
+pub struct SlowLog {
+    config: Arc<SlowLogConfig>,
+    entries: Arc<Mutex<VecDeque<SlowLogEntry>>>,
+    slow_queries: Arc<AtomicU64>,
+    slow_requests: Arc<AtomicU64>,
+}
+
+impl SlowLog {
+    pub fn new(config: SlowLogConfig) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(config.capacity))),
+            config: Arc::new(config),
+            slow_queries: Arc::new(AtomicU64::new(0)),
+            slow_requests: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn query_threshold(&self) -> Duration {
+        self.config.query_threshold
+    }
+
+    /// Record a query if it took at least the query threshold
+    ///
+    /// The statement is reduced to its shape: literals become `?` and
+    /// whitespace is collapsed, so repeated queries look the same.
+    pub fn record_query(&self, sql: &str, elapsed: Duration, request_id: Option<String>) {
+        if elapsed < self.config.query_threshold {
+            return;
+        }
+        self.slow_queries.fetch_add(1, Ordering::Relaxed);
+        self.push(SlowLogEntry {
+            kind: SlowLogKind::Query,
+            target: sql_shape(sql),
+            duration_ms: elapsed.as_secs_f64() * 1000.0,
+            threshold_ms: self.config.query_threshold.as_millis() as u64,
+            status: None,
+            request_id,
+            recorded_at: Utc::now(),
+        });
+    }
+
+    /// Log and record a request if it took at least the request threshold
+    ///
+    /// `route` is the matched route pattern (e.g. `GET /posts/:slug`) so
+    /// requests for different posts are grouped together.
+    pub fn record_request(
+        &self,
+        route: &str,
+        status: u16,
+        elapsed: Duration,
+        request_id: Option<String>,
+    ) {
+        if elapsed < self.config.request_threshold {
+            return;
+        }
+        let duration_ms = elapsed.as_secs_f64() * 1000.0;
+        warn!(
+            "Slow request: {} returned {} in {:.2}ms (threshold {}ms)",
+            route,
+            status,
+            duration_ms,
+            self.config.request_threshold.as_millis()
+        );
+        self.slow_requests.fetch_add(1, Ordering::Relaxed);
+        self.push(SlowLogEntry {
+            kind: SlowLogKind::Request,
+            target: route.to_string(),
+            duration_ms,
+            threshold_ms: self.config.request_threshold.as_millis() as u64,
+            status: Some(status),
+            request_id,
+            recorded_at: Utc::now(),
+        });
+    }
+
+    /// Number of slow queries and slow requests since startup
+    pub fn counts(&self) -> (u64, u64) {
+        (
+            self.slow_queries.load(Ordering::Relaxed),
+            self.slow_requests.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Thresholds, counters and buffered entries, newest first
+    pub fn report(&self) -> SlowLogReport {
+        let (slow_queries, slow_requests) = self.counts();
+        let entries = self
+            .entries
+            .lock()
+            .map(|entries| entries.iter().rev().cloned().collect())
+            .unwrap_or_default();
+        SlowLogReport {
+            query_threshold_ms: self.config.query_threshold.as_millis() as u64,
+            request_threshold_ms: self.config.request_threshold.as_millis() as u64,
+            slow_queries,
+            slow_requests,
+            entries,
+        }
+    }
+
+    fn push(&self, entry: SlowLogEntry) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() >= self.config.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// SQL with string and number literals replaced by `?` and whitespace collapsed
+fn sql_shape(sql: &str) -> String {
+    let mut shape = String::with_capacity(sql.len().min(MAX_SQL_LEN));
+    let mut chars = sql.trim().chars().peekable();
+    // Whether the previous character can be part of an identifier
+    let mut in_word = false;
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // '' is an escaped quote inside the literal
+            while let Some(c) = chars.next() {
+                if c == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    break;
+                }
+            }
+            shape.push('?');
+            in_word = false;
+        } else if c.is_ascii_digit() && !in_word {
+            while chars.next_if(|c| c.is_ascii_digit() || *c == '.').is_some() {}
+            shape.push('?');
+        } else if c.is_whitespace() {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            shape.push(' ');
+            in_word = false;
+        } else {
+            shape.push(c);
+            // Digits after `?`, `$` or `:` number bind parameters, not literals
+            in_word = c.is_alphanumeric() || matches!(c, '_' | '?' | '$' | ':');
+        }
+        if shape.len() >= MAX_SQL_LEN {
+            shape.push('…');
+            break;
+        }
+    }
+    shape
+}
+
+/// Tracing layer feeding slow sqlx statements into the [`SlowLog`]
+///
+/// sqlx reports statements over its `log_slow_statements` threshold at warn
+/// level, which the layer only needs to see: give the database the same
+/// threshold as the slow log. Queries run while handling a request are
+/// tagged with its request ID.
+pub struct SlowQueryLayer {
+    slow_log: SlowLog,
+}
+
+impl SlowQueryLayer {
+    pub fn new(slow_log: SlowLog) -> Self {
+        Self { slow_log }
+    }
+}
+
+/// Request ID of a span, stored in its extensions
+struct RequestId(String);
+
+impl<S> Layer<S> for SlowQueryLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = RequestIdVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(request_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(RequestId(request_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != SQLX_QUERY_TARGET {
+            return;
+        }
+        let mut query = QueryFields::default();
+        event.record(&mut query);
+        let elapsed = Duration::from_secs_f64(query.elapsed_secs.max(0.0));
+        if elapsed < self.slow_log.query_threshold() {
+            return;
+        }
+
+        let request_id = ctx.event_scope(event).and_then(|scope| {
+            scope.into_iter().find_map(|span| {
+                let extensions = span.extensions();
+                extensions.get::<RequestId>().map(|id| id.0.clone())
+            })
+        });
+        // sqlx only sends the formatted statement when it differs from the summary
+        let sql = query
+            .statement
+            .filter(|statement| !statement.trim().is_empty())
+            .or(query.summary)
+            .unwrap_or_default();
+        self.slow_log.record_query(&sql, elapsed, request_id);
+    }
+}
+
+#[derive(Default)]
+struct RequestIdVisitor(Option<String>);
+
+impl Visit for RequestIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "request_id" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "request_id" {
+            self.0 = Some(format!("{:?}", value).trim_matches('"').to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn slow_log(capacity: usize) -> SlowLog {
+        SlowLog::new(SlowLogConfig {
+            query_threshold: Duration::from_millis(100),
+            request_threshold: Duration::from_millis(500),
+            capacity,
+        })
+    }
+
+    #[test]
+    fn test_sql_shape_replaces_literals() {
+        assert_eq!(
+            sql_shape("SELECT *\n  FROM posts\n WHERE slug = 'it''s' AND id IN (1, 22) LIMIT 10"),
+            "SELECT * FROM posts WHERE slug = ? AND id IN (?, ?) LIMIT ?"
+        );
+        assert_eq!(
+            sql_shape("SELECT t1.id FROM tags t1 WHERE t1.count > ?1"),
+            "SELECT t1.id FROM tags t1 WHERE t1.count > ?1"
+        );
+    }
+
+    #[test]
+    fn test_only_entries_over_threshold_are_recorded() {
+        let log = slow_log(10);
+        log.record_query("SELECT 1", Duration::from_millis(50), None);
+        log.record_query("SELECT 2", Duration::from_millis(150), None);
+        log.record_request("GET /", 200, Duration::from_millis(100), None);
+        log.record_request("GET /posts/:slug", 200, Duration::from_millis(600), None);
+
+        let report = log.report();
+        assert_eq!((report.slow_queries, report.slow_requests), (1, 1));
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.entries[0].kind, SlowLogKind::Request);
+        assert_eq!(report.entries[0].target, "GET /posts/:slug");
+        assert_eq!(report.entries[1].target, "SELECT ?");
+    }
+
+    #[test]
+    fn test_oldest_entries_are_dropped() {
+        let log = slow_log(2);
+        for table in ["a", "b", "c"] {
+            log.record_query(
+                &format!("SELECT * FROM {}", table),
+                Duration::from_secs(1),
+                None,
+            );
+        }
+
+        let report = log.report();
+        assert_eq!(report.slow_queries, 3);
+        let targets: Vec<_> = report.entries.iter().map(|e| e.target.as_str()).collect();
+        assert_eq!(targets, ["SELECT * FROM c", "SELECT * FROM b"]);
+    }
+
+    #[test]
+    fn test_layer_records_slow_query_events_with_request_id() {
+        let log = slow_log(10);
+        let subscriber = tracing_subscriber::registry().with(SlowQueryLayer::new(log.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info_span!("http.request", request_id = "req-1").in_scope(|| {
+                tracing::warn!(target: "sqlx::query", summary = "SELECT * FROM posts", elapsed_secs = 0.3);
+                tracing::debug!(target: "sqlx::query", summary = "SELECT 1", elapsed_secs = 0.01);
+            });
+        });
+
+        let report = log.report();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].target, "SELECT * FROM posts");
+        assert_eq!(report.entries[0].request_id.as_deref(), Some("req-1"));
+    }
+}
@@ -0,0 +1,559 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::circuit_breaker::CircuitBreakerConfig;
+use super::dropbox::{DropboxClient, FileMetadata};
+use super::s3::{S3Config, S3Storage};
+use crate::config::Config;
+
+/// Where posts, media and theme files are kept
+///
+/// Paths are absolute, slash-separated paths under the blog root such as
+/// `/BlogStorage/posts/hello.md`, whatever the backend. File metadata follows
+/// the Dropbox API shape the services were written against.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Backend name for logs and status output
+    fn name(&self) -> &'static str;
+
+    /// Check that the backend is reachable, describing what it is connected to
+    async fn check_connection(&self) -> Result<String>;
+
+    /// Files and folders directly inside `path`
+    async fn list_folder(&self, path: &str) -> Result<Vec<FileMetadata>>;
+
+    async fn get_metadata(&self, path: &str) -> Result<FileMetadata>;
+
+    async fn download_file(&self, path: &str) -> Result<Vec<u8>>;
+
+    async fn download_text_file(&self, path: &str) -> Result<String> {
+        let data = self.download_file(path).await?;
+        String::from_utf8(data).with_context(|| format!("{} is not valid UTF-8", path))
+    }
+
+    /// Write `content`, replacing any existing file
+    async fn upload_file(&self, path: &str, content: &str) -> Result<FileMetadata> {
+        self.upload_binary_file(path, content.as_bytes()).await
+    }
+
+    /// Write `data`, replacing any existing file
+    async fn upload_binary_file(&self, path: &str, data: &[u8]) -> Result<FileMetadata>;
+
+    async fn delete_file(&self, path: &str) -> Result<FileMetadata>;
+
+    /// Create a folder; backends without real folders accept this as a no-op
+    async fn create_folder(&self, path: &str) -> Result<()>;
+}
+
+/// Storage backend selected with `STORAGE_BACKEND`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    Dropbox,
+    Local,
+    S3,
+}
+
+impl std::str::FromStr for StorageKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dropbox" => Ok(StorageKind::Dropbox),
+            "local" => Ok(StorageKind::Local),
+            "s3" => Ok(StorageKind::S3),
+            other => Err(anyhow::anyhow!(
+                "Unknown storage backend '{}' (expected dropbox, local or s3)",
+                other
+            )),
+        }
+    }
+}
+
+/// Dropbox client for the token and circuit breaker settings in `config`
+pub fn dropbox_client(config: &Config) -> Result<DropboxClient> {
+    if config.dropbox_access_token.is_empty() {
+        anyhow::bail!("DROPBOX_ACCESS_TOKEN is required for the dropbox storage backend");
+    }
+    let breaker = CircuitBreakerConfig {
+        failure_threshold: config.dropbox_breaker_failures.max(1),
+        cooldown: Duration::from_secs(config.dropbox_breaker_cooldown_secs),
+    };
+    Ok(DropboxClient::new(config.dropbox_access_token.clone()).with_circuit_breaker(breaker))
+}
+
+/// Open the backend of `kind` with the settings in `config`
+pub fn open_storage(kind: StorageKind, config: &Config) -> Result<Arc<dyn StorageBackend>> {
+    let required = |value: &Option<String>, name: &str| {
+        value
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("{} is required for the s3 storage backend", name))
+    };
+
+    Ok(match kind {
+        StorageKind::Dropbox => Arc::new(dropbox_client(config)?),
+        StorageKind::Local => Arc::new(LocalStorage::new(&config.storage_local_path)),
+        StorageKind::S3 => Arc::new(S3Storage::new(S3Config {
+            endpoint: required(&config.s3_endpoint, "S3_ENDPOINT")?,
+            bucket: required(&config.s3_bucket, "S3_BUCKET")?,
+            region: config.s3_region.clone(),
+            access_key_id: required(&config.s3_access_key_id, "S3_ACCESS_KEY_ID")?,
+            secret_access_key: required(&config.s3_secret_access_key, "S3_SECRET_ACCESS_KEY")?,
+            prefix: config.s3_prefix.clone(),
+        })),
+    })
+}
+
+/// Files kept in a directory on the server
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// File system location of a storage path, refusing paths that leave the root
+    fn resolve(&self, path: &str) -> Result<PathBuf> {
+        let relative = Path::new(path.trim_start_matches('/'));
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            anyhow::bail!("Invalid storage path: {}", path);
+        }
+        Ok(self.root.join(relative))
+    }
+
+    async fn metadata(&self, path: &str, file: &Path) -> Result<FileMetadata> {
+        let metadata = tokio::fs::metadata(file)
+            .await
+            .with_context(|| format!("File not found: {}", path))?;
+        let modified = metadata.modified().ok().map(|time| {
+            DateTime::<Utc>::from(time)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string()
+        });
+        let path_display = format!("/{}", path.trim_start_matches('/'));
+
+        Ok(FileMetadata {
+            name: file
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path_lower: path_display.to_lowercase(),
+            path_display,
+            size: metadata.is_file().then_some(metadata.len()),
+            content_hash: None,
+            client_modified: modified.clone(),
+            server_modified: modified,
+        })
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn check_connection(&self) -> Result<String> {
+        tokio::fs::create_dir_all(&self.root)
+            .await
+            .with_context(|| format!("Failed to create {}", self.root.display()))?;
+        Ok(format!("local directory {}", self.root.display()))
+    }
+
+    async fn list_folder(&self, path: &str) -> Result<Vec<FileMetadata>> {
+        let folder = self.resolve(path)?;
+        let mut entries = tokio::fs::read_dir(&folder)
+            .await
+            .with_context(|| format!("Failed to list folder: {}", path))?;
+
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let entry_path = format!("{}/{}", path.trim_end_matches('/'), name);
+            files.push(self.metadata(&entry_path, &entry.path()).await?);
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(files)
+    }
+
+    async fn get_metadata(&self, path: &str) -> Result<FileMetadata> {
+        let file = self.resolve(path)?;
+        self.metadata(path, &file).await
+    }
+
+    async fn download_file(&self, path: &str) -> Result<Vec<u8>> {
+        let file = self.resolve(path)?;
+        tokio::fs::read(&file)
+            .await
+            .with_context(|| format!("Failed to read {}", path))
+    }
+
+    async fn upload_binary_file(&self, path: &str, data: &[u8]) -> Result<FileMetadata> {
+        let file = self.resolve(path)?;
+        if let Some(parent) = file.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create folder for {}", path))?;
+        }
+        tokio::fs::write(&file, data)
+            .await
+            .with_context(|| format!("Failed to write {}", path))?;
+        self.metadata(path, &file).await
+    }
+
+    async fn delete_file(&self, path: &str) -> Result<FileMetadata> {
+        let file = self.resolve(path)?;
+        let metadata = self.metadata(path, &file).await?;
+        tokio::fs::remove_file(&file)
+            .await
+            .with_context(|| format!("Failed to delete {}", path))?;
+        Ok(metadata)
+    }
+
+    async fn create_folder(&self, path: &str) -> Result<()> {
+        let folder = self.resolve(path)?;
+        tokio::fs::create_dir_all(&folder)
+            .await
+            .with_context(|| format!("Failed to create folder: {}", path))
+    }
+}
+
+/// Files kept in memory, a stand-in for Dropbox in tests
+///
+/// Paths are matched case-insensitively like Dropbox. Folders exist once
+/// created or once a file is written below them. Clones share the same files.
+#[derive(Debug, Clone, Default)]
+#[allow(dead_code)] // Used by tests and the load_test binary
+pub struct MemoryStorage {
+    tree: Arc<Mutex<MemoryTree>>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryTree {
+    /// Files by lowercased path
+    files: BTreeMap<String, MemoryFile>,
+    /// Explicitly created folders by lowercased path, with their display path
+    folders: BTreeMap<String, String>,
+}
+
+#[derive(Debug)]
+struct MemoryFile {
+    path_display: String,
+    data: Vec<u8>,
+    modified: DateTime<Utc>,
+}
+
+#[allow(dead_code)] // Used by tests and the load_test binary
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Paths of all stored files, sorted
+    pub fn file_paths(&self) -> Vec<String> {
+        let tree = self.lock();
+        let mut paths: Vec<String> = tree
+            .files
+            .values()
+            .map(|file| file.path_display.clone())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryTree> {
+        self.tree.lock().expect("memory storage poisoned")
+    }
+}
+
+/// `/`-prefixed path without empty, `.` or `..` segments
+fn normalize_memory_path(path: &str) -> Result<String> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments
+        .iter()
+        .any(|segment| matches!(*segment, "." | ".."))
+    {
+        anyhow::bail!("Invalid storage path: {}", path);
+    }
+    Ok(format!("/{}", segments.join("/")))
+}
+
+/// Path of `path`'s first `depth` segments
+fn memory_path_prefix(path: &str, depth: usize) -> String {
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .take(depth)
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+fn memory_folder_metadata(path_display: &str) -> FileMetadata {
+    FileMetadata {
+        name: path_display
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        path_lower: path_display.to_lowercase(),
+        path_display: path_display.to_string(),
+        size: None,
+        content_hash: None,
+        client_modified: None,
+        server_modified: None,
+    }
+}
+
+impl MemoryFile {
+    fn metadata(&self) -> FileMetadata {
+        let modified = self.modified.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        FileMetadata {
+            size: Some(self.data.len() as u64),
+            client_modified: Some(modified.clone()),
+            server_modified: Some(modified),
+            ..memory_folder_metadata(&self.path_display)
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for MemoryStorage {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn check_connection(&self) -> Result<String> {
+        Ok(format!(
+            "in-memory storage with {} files",
+            self.lock().files.len()
+        ))
+    }
+
+    async fn list_folder(&self, path: &str) -> Result<Vec<FileMetadata>> {
+        let folder = normalize_memory_path(path)?;
+        let folder_key = folder.to_lowercase();
+        let depth = folder.split('/').filter(|s| !s.is_empty()).count();
+        let prefix = if depth == 0 {
+            folder_key.clone()
+        } else {
+            format!("{}/", folder_key)
+        };
+        let tree = self.lock();
+
+        let mut entries = BTreeMap::new();
+        for (key, file) in tree.files.range(prefix.clone()..) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            let child = memory_path_prefix(&file.path_display, depth + 1);
+            let metadata = if child == file.path_display {
+                file.metadata()
+            } else {
+                memory_folder_metadata(&child)
+            };
+            entries.entry(child.to_lowercase()).or_insert(metadata);
+        }
+        for (key, display) in tree.folders.range(prefix.clone()..) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            let child = memory_path_prefix(display, depth + 1);
+            entries
+                .entry(child.to_lowercase())
+                .or_insert_with(|| memory_folder_metadata(&child));
+        }
+
+        if entries.is_empty() && depth > 0 && !tree.folders.contains_key(&folder_key) {
+            anyhow::bail!("Failed to list folder: {}", path);
+        }
+        let mut entries: Vec<FileMetadata> = entries.into_values().collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    async fn get_metadata(&self, path: &str) -> Result<FileMetadata> {
+        let path = normalize_memory_path(path)?;
+        let key = path.to_lowercase();
+        let tree = self.lock();
+        if let Some(file) = tree.files.get(&key) {
+            return Ok(file.metadata());
+        }
+        if let Some(display) = tree.folders.get(&key) {
+            return Ok(memory_folder_metadata(display));
+        }
+        let prefix = format!("{}/", key);
+        match tree.files.range(prefix.clone()..).next() {
+            Some((child, _)) if child.starts_with(&prefix) => Ok(memory_folder_metadata(&path)),
+            _ => anyhow::bail!("File not found: {}", path),
+        }
+    }
+
+    async fn download_file(&self, path: &str) -> Result<Vec<u8>> {
+        let key = normalize_memory_path(path)?.to_lowercase();
+        self.lock()
+            .files
+            .get(&key)
+            .map(|file| file.data.clone())
+            .ok_or_else(|| anyhow::anyhow!("File not found: {}", path))
+    }
+
+    async fn upload_binary_file(&self, path: &str, data: &[u8]) -> Result<FileMetadata> {
+        let path_display = normalize_memory_path(path)?;
+        let file = MemoryFile {
+            path_display: path_display.clone(),
+            data: data.to_vec(),
+            modified: Utc::now(),
+        };
+        let metadata = file.metadata();
+        self.lock().files.insert(path_display.to_lowercase(), file);
+        Ok(metadata)
+    }
+
+    async fn delete_file(&self, path: &str) -> Result<FileMetadata> {
+        let key = normalize_memory_path(path)?.to_lowercase();
+        self.lock()
+            .files
+            .remove(&key)
+            .map(|file| file.metadata())
+            .ok_or_else(|| anyhow::anyhow!("File not found: {}", path))
+    }
+
+    async fn create_folder(&self, path: &str) -> Result<()> {
+        let path_display = normalize_memory_path(path)?;
+        self.lock()
+            .folders
+            .insert(path_display.to_lowercase(), path_display);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_storage_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path());
+
+        storage
+            .upload_file("/BlogStorage/posts/hello.md", "# Hello")
+            .await
+            .unwrap();
+        let files = storage.list_folder("/BlogStorage/posts").await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path_display, "/BlogStorage/posts/hello.md");
+        assert_eq!(files[0].size, Some(7));
+        assert_eq!(
+            storage
+                .download_text_file(&files[0].path_display)
+                .await
+                .unwrap(),
+            "# Hello"
+        );
+
+        storage
+            .delete_file("/BlogStorage/posts/hello.md")
+            .await
+            .unwrap();
+        assert!(storage
+            .list_folder("/BlogStorage/posts")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(storage.list_folder("/BlogStorage/drafts").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_rejects_paths_outside_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path().join("root"));
+
+        assert!(storage
+            .upload_file("/BlogStorage/../../escape.md", "nope")
+            .await
+            .is_err());
+        assert!(!dir.path().join("escape.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_lists_files_and_implicit_folders() {
+        let storage = MemoryStorage::new();
+        storage
+            .upload_file("/BlogStorage/posts/Hello.md", "# Hello")
+            .await
+            .unwrap();
+        storage
+            .upload_binary_file("BlogStorage/media/images/2024/07/a.png", &[1, 2, 3])
+            .await
+            .unwrap();
+        storage.create_folder("/BlogStorage/drafts").await.unwrap();
+
+        let root: Vec<String> = storage
+            .list_folder("/BlogStorage")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.path_display)
+            .collect();
+        assert_eq!(
+            root,
+            [
+                "/BlogStorage/drafts",
+                "/BlogStorage/media",
+                "/BlogStorage/posts"
+            ]
+        );
+        assert!(storage
+            .list_folder("/BlogStorage/drafts")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(storage.list_folder("/BlogStorage/missing").await.is_err());
+
+        let files = storage.list_folder("/blogstorage/POSTS").await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "Hello.md");
+        assert_eq!(files[0].size, Some(7));
+        assert_eq!(
+            storage
+                .download_text_file("/blogstorage/posts/hello.md")
+                .await
+                .unwrap(),
+            "# Hello"
+        );
+        assert!(storage
+            .get_metadata("/BlogStorage/media/images")
+            .await
+            .unwrap()
+            .size
+            .is_none());
+
+        storage
+            .delete_file("/BlogStorage/posts/Hello.md")
+            .await
+            .unwrap();
+        assert!(storage
+            .download_file("/BlogStorage/posts/Hello.md")
+            .await
+            .is_err());
+        assert!(storage
+            .upload_file("/BlogStorage/../escape.md", "nope")
+            .await
+            .is_err());
+        assert_eq!(
+            storage.file_paths(),
+            ["/BlogStorage/media/images/2024/07/a.png"]
+        );
+    }
+}
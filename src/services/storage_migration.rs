@@ -0,0 +1,245 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::models::{StorageMigrationProgress, StorageMigrationReport, StorageMigrationRequest};
+use crate::services::dropbox::FileMetadata;
+use crate::services::{open_storage, DatabaseService, JobQueue, StorageBackend, StorageKind};
+
+/// Folder copied when the request names none
+const DEFAULT_ROOT: &str = "/BlogStorage";
+
+/// Copies the blog's files from one storage backend to another
+///
+/// Every copy is read back from the target and compared by SHA-256. Files the
+/// target already holds with the same content are skipped, so a failed run can
+/// simply be retried. Database paths are only moved to the target folder once
+/// every file has been verified.
+#[derive(Clone)]
+pub struct StorageMigrationService {
+    database: DatabaseService,
+    config: Config,
+    jobs: Option<JobQueue>,
+}
+
+impl StorageMigrationService {
+    pub fn new(database: DatabaseService, config: Config) -> Self {
+        Self {
+            database,
+            config,
+            jobs: None,
+        }
+    }
+
+    /// Report progress on the job running the migration
+    pub fn with_jobs(mut self, jobs: JobQueue) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    /// Run a migration between the configured backends named in `request`
+    pub async fn migrate(
+        &self,
+        request: &StorageMigrationRequest,
+    ) -> Result<StorageMigrationReport> {
+        let source_kind: StorageKind = request
+            .source
+            .as_deref()
+            .unwrap_or(&self.config.storage_backend)
+            .parse()?;
+        let target_kind: StorageKind = request.target.parse()?;
+        let source_root = normalize_root(request.source_root.as_deref().unwrap_or(DEFAULT_ROOT));
+        let target_root = request
+            .target_root
+            .as_deref()
+            .map(normalize_root)
+            .unwrap_or_else(|| source_root.clone());
+
+        if source_kind == target_kind && source_root == target_root {
+            anyhow::bail!("Source and target of the migration are the same");
+        }
+
+        let source = open_storage(source_kind, &self.config)?;
+        let target = open_storage(target_kind, &self.config)?;
+        self.migrate_between(source.as_ref(), target.as_ref(), &source_root, &target_root)
+            .await
+    }
+
+    /// Copy everything under `source_root` to `target_root` and move the
+    /// database paths along; fails when any file could not be verified
+    pub async fn migrate_between(
+        &self,
+        source: &dyn StorageBackend,
+        target: &dyn StorageBackend,
+        source_root: &str,
+        target_root: &str,
+    ) -> Result<StorageMigrationReport> {
+        info!(
+            "Migrating storage from {}:{} to {}:{}",
+            source.name(),
+            source_root,
+            target.name(),
+            target_root
+        );
+
+        let files = list_files(source, source_root).await?;
+        let mut progress = StorageMigrationProgress {
+            total_files: files.len(),
+            ..Default::default()
+        };
+        let mut failures = Vec::new();
+        self.report(&progress).await;
+
+        for file in &files {
+            let target_path = target_path(&file.path_display, source_root, target_root);
+            match copy_file(source, target, &file.path_display, &target_path).await {
+                Ok(Some(bytes)) => {
+                    progress.copied_files += 1;
+                    progress.copied_bytes += bytes;
+                }
+                Ok(None) => progress.skipped_files += 1,
+                Err(e) => {
+                    warn!("Failed to migrate {}: {:#}", file.path_display, e);
+                    progress.failed_files += 1;
+                    failures.push(format!("{}: {:#}", file.path_display, e));
+                }
+            }
+            progress.processed_files += 1;
+            self.report(&progress).await;
+        }
+
+        if !failures.is_empty() {
+            anyhow::bail!(
+                "{} of {} files failed to migrate: {}",
+                failures.len(),
+                files.len(),
+                failures.join("; ")
+            );
+        }
+
+        let rewritten_paths = if source_root == target_root {
+            0
+        } else {
+            self.database
+                .rewrite_storage_paths(source_root, target_root)
+                .await?
+        };
+
+        info!(
+            "Storage migration finished: {} copied, {} already present, {} paths rewritten",
+            progress.copied_files, progress.skipped_files, rewritten_paths
+        );
+        Ok(StorageMigrationReport {
+            source: source.name().to_string(),
+            target: target.name().to_string(),
+            source_root: source_root.to_string(),
+            target_root: target_root.to_string(),
+            progress,
+            rewritten_paths,
+        })
+    }
+
+    async fn report(&self, progress: &StorageMigrationProgress) {
+        if let Some(jobs) = &self.jobs {
+            if let Err(e) = jobs.report_progress(progress).await {
+                warn!("Failed to report storage migration progress: {}", e);
+            }
+        }
+    }
+}
+
+/// `/BlogStorage/` and `BlogStorage` both become `/BlogStorage`
+fn normalize_root(root: &str) -> String {
+    format!("/{}", root.trim_matches('/'))
+}
+
+/// Where a file below `source_root` goes below `target_root`
+fn target_path(path: &str, source_root: &str, target_root: &str) -> String {
+    let relative = path
+        .get(source_root.len()..)
+        .unwrap_or(path)
+        .trim_start_matches('/');
+    format!("{}/{}", target_root.trim_end_matches('/'), relative)
+}
+
+/// All files below `root`, walking into subfolders
+async fn list_files(storage: &dyn StorageBackend, root: &str) -> Result<Vec<FileMetadata>> {
+    let mut folders = vec![root.to_string()];
+    let mut files = Vec::new();
+
+    while let Some(folder) = folders.pop() {
+        let entries = storage
+            .list_folder(&folder)
+            .await
+            .with_context(|| format!("Failed to list {} in {}", folder, storage.name()))?;
+        for entry in entries {
+            // Folders come without a size on every backend
+            match entry.size {
+                Some(_) => files.push(entry),
+                None => folders.push(entry.path_display),
+            }
+        }
+    }
+
+    files.sort_by(|a, b| a.path_display.cmp(&b.path_display));
+    Ok(files)
+}
+
+/// Copy one file and verify it, returning the bytes written or `None` when
+/// the target already had the same content
+async fn copy_file(
+    source: &dyn StorageBackend,
+    target: &dyn StorageBackend,
+    source_path: &str,
+    target_path: &str,
+) -> Result<Option<u64>> {
+    let data = source
+        .download_file(source_path)
+        .await
+        .context("Failed to download from the source")?;
+    let expected = Sha256::digest(&data);
+
+    let existing = match target.get_metadata(target_path).await {
+        Ok(metadata) if metadata.size == Some(data.len() as u64) => {
+            target.download_file(target_path).await.ok()
+        }
+        _ => None,
+    };
+    if existing.is_some_and(|existing| Sha256::digest(&existing) == expected) {
+        return Ok(None);
+    }
+
+    target
+        .upload_binary_file(target_path, &data)
+        .await
+        .context("Failed to upload to the target")?;
+    let written = target
+        .download_file(target_path)
+        .await
+        .context("Failed to read back the copy")?;
+    if Sha256::digest(&written) != expected {
+        anyhow::bail!("Copy in the target does not match the source (SHA-256 differs)");
+    }
+
+    Ok(Some(data.len() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_path_moves_files_between_roots() {
+        let source_root = normalize_root("/BlogStorage/");
+        let target_root = normalize_root("archive/blog");
+        assert_eq!(
+            target_path("/BlogStorage/posts/hello.md", &source_root, &target_root),
+            "/archive/blog/posts/hello.md"
+        );
+        assert_eq!(
+            target_path("/BlogStorage/media/a.png", &source_root, "/"),
+            "/media/a.png"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::models::AdminEvent;
+use crate::services::dropbox::SpaceUsage;
+use crate::services::{DropboxClient, EventBus};
+
+/// How long a fetched usage is shown before Dropbox is asked again
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Dropbox space usage measured against the warning threshold
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageQuota {
+    pub used: u64,
+    pub allocated: Option<u64>,
+    pub percent_used: Option<f64>,
+    pub warning_percent: f64,
+    /// Usage is at or above the warning threshold
+    pub warning: bool,
+}
+
+impl StorageQuota {
+    fn new(usage: SpaceUsage, warning_percent: f64) -> Self {
+        let percent_used = usage.percent_used();
+        Self {
+            used: usage.used,
+            allocated: usage.allocated,
+            percent_used,
+            warning_percent,
+            warning: percent_used.is_some_and(|percent| percent >= warning_percent),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct QuotaState {
+    last: Option<(Instant, StorageQuota)>,
+    /// Admins were warned and usage has not dropped below the threshold since
+    warned: bool,
+}
+
+/// Watches the Dropbox account's storage quota
+///
+/// Usage is fetched after media uploads and cached for status pages. Admins
+/// are notified once when usage crosses the warning threshold, and again only
+/// after it has dropped below and crossed it anew.
+#[derive(Clone)]
+pub struct StorageQuotaService {
+    dropbox: Arc<DropboxClient>,
+    events: EventBus,
+    warning_percent: f64,
+    state: Arc<Mutex<QuotaState>>,
+}
+
+impl StorageQuotaService {
+    pub fn new(dropbox: Arc<DropboxClient>, events: EventBus, warning_percent: f64) -> Self {
+        Self {
+            dropbox,
+            events,
+            warning_percent,
+            state: Arc::new(Mutex::new(QuotaState::default())),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QuotaState> {
+        self.state.lock().expect("storage quota state poisoned")
+    }
+
+    /// The latest usage, fetched again once the cached one is stale
+    pub async fn usage(&self) -> Result<StorageQuota> {
+        if let Some((fetched, quota)) = &self.lock().last {
+            if fetched.elapsed() < CACHE_TTL {
+                return Ok(quota.clone());
+            }
+        }
+        self.refresh().await
+    }
+
+    /// Fetch the usage from Dropbox, notifying admins when it crossed the
+    /// warning threshold
+    pub async fn refresh(&self) -> Result<StorageQuota> {
+        let usage = self.dropbox.get_space_usage().await?;
+        let quota = StorageQuota::new(usage, self.warning_percent);
+        if let Some(event) = self.record(&quota) {
+            self.events.publish(event);
+        }
+        Ok(quota)
+    }
+
+    /// Check the quota after a media upload without holding up the response
+    pub fn spawn_check(&self) {
+        let quota = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = quota.refresh().await {
+                warn!("Failed to check Dropbox storage quota: {:#}", e);
+            }
+        });
+    }
+
+    /// Cache `quota` and return the warning to publish, if this crossed the threshold
+    fn record(&self, quota: &StorageQuota) -> Option<AdminEvent> {
+        let mut state = self.lock();
+        state.last = Some((Instant::now(), quota.clone()));
+
+        if !quota.warning {
+            state.warned = false;
+            return None;
+        }
+        if std::mem::replace(&mut state.warned, true) {
+            return None;
+        }
+
+        let (allocated, percent) = quota.allocated.zip(quota.percent_used)?;
+        info!(
+            "Dropbox storage at {:.1}% of the quota ({} of {} bytes)",
+            percent, quota.used, allocated
+        );
+        Some(AdminEvent::StorageQuotaWarning {
+            used: quota.used,
+            allocated,
+            percent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warns_once_per_threshold_crossing() {
+        let service = StorageQuotaService::new(
+            Arc::new(DropboxClient::new("test_token".to_string())),
+            EventBus::new(),
+            90.0,
+        );
+        let quota = |used| {
+            StorageQuota::new(
+                SpaceUsage {
+                    used,
+                    allocated: Some(1000),
+                },
+                90.0,
+            )
+        };
+
+        assert!(service.record(&quota(500)).is_none());
+        assert!(matches!(
+            service.record(&quota(900)),
+            Some(AdminEvent::StorageQuotaWarning { percent, .. }) if percent == 90.0
+        ));
+        assert!(service.record(&quota(950)).is_none());
+
+        assert!(service.record(&quota(800)).is_none());
+        assert!(service.record(&quota(910)).is_some());
+    }
+}
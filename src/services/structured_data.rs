@@ -0,0 +1,236 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::models::{Post, SiteConfig};
+use crate::services::i18n::translate;
+use crate::services::DatabaseService;
+
+/// One step of a breadcrumb trail below the home page
+#[derive(Debug, Clone)]
+pub struct Breadcrumb {
+    pub name: String,
+    /// Site-relative path, or an absolute URL
+    pub path: String,
+    /// `name` is a UI string key, translated into the site locale
+    pub translate: bool,
+}
+
+impl Breadcrumb {
+    pub fn new(name: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            translate: false,
+        }
+    }
+
+    /// Step named by the UI string `key`, such as `common.tags`
+    pub fn translated(key: &str, path: impl Into<String>) -> Self {
+        Self {
+            translate: true,
+            ..Self::new(key, path)
+        }
+    }
+}
+
+/// Service that builds the schema.org JSON-LD embedded in public pages
+#[derive(Clone)]
+pub struct StructuredDataService {
+    database: DatabaseService,
+    /// Public base URL used to build absolute links
+    site_url: String,
+}
+
+impl StructuredDataService {
+    /// Create a new structured data service
+    pub fn new(database: DatabaseService, site_url: String) -> Self {
+        Self { database, site_url }
+    }
+
+    async fn site_config(&self) -> Result<SiteConfig> {
+        Ok(self.database.get_site_config().await?.unwrap_or_default())
+    }
+
+    /// `WebSite` with a `SearchAction` pointing at `/search`, for the home page
+    pub async fn home(&self) -> Result<String> {
+        let site = self.site_config().await?;
+        Ok(to_script_json(vec![website(&self.site_url, &site)]))
+    }
+
+    /// `BreadcrumbList` from the home page through `trail`, for listing pages
+    pub async fn listing(&self, trail: &[Breadcrumb]) -> Result<String> {
+        let site = self.site_config().await?;
+        Ok(to_script_json(vec![breadcrumb_list(
+            &self.site_url,
+            &site,
+            trail,
+        )]))
+    }
+
+    /// `BlogPosting` and its `BreadcrumbList`, for a post page
+    pub async fn post(
+        &self,
+        post: &Post,
+        canonical_url: &str,
+        image_url: Option<&str>,
+    ) -> Result<String> {
+        let site = self.site_config().await?;
+        let mut trail = Vec::new();
+        if let Some(category) = post.category.as_deref().filter(|c| !c.is_empty()) {
+            trail.push(Breadcrumb::new(category, format!("/category/{}", category)));
+        }
+        trail.push(Breadcrumb::new(&post.title, post.get_url_path()));
+
+        Ok(to_script_json(vec![
+            blog_posting(&site, post, canonical_url, image_url),
+            breadcrumb_list(&self.site_url, &site, &trail),
+        ]))
+    }
+}
+
+fn absolute_url(site_url: &str, path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        path.to_string()
+    } else {
+        format!("{}{}", site_url.trim_end_matches('/'), path)
+    }
+}
+
+fn website(site_url: &str, site: &SiteConfig) -> Value {
+    json!({
+        "@type": "WebSite",
+        "name": site.site_title,
+        "description": site.site_description,
+        "url": absolute_url(site_url, "/"),
+        "inLanguage": site.locale.as_str(),
+        "potentialAction": {
+            "@type": "SearchAction",
+            "target": {
+                "@type": "EntryPoint",
+                "urlTemplate": absolute_url(site_url, "/search?q={search_term_string}"),
+            },
+            "query-input": "required name=search_term_string",
+        },
+    })
+}
+
+fn breadcrumb_list(site_url: &str, site: &SiteConfig, trail: &[Breadcrumb]) -> Value {
+    let home = Breadcrumb::translated("common.home", "/");
+    let items: Vec<Value> = std::iter::once(&home)
+        .chain(trail)
+        .enumerate()
+        .map(|(index, crumb)| {
+            let name = if crumb.translate {
+                translate(site.locale, &crumb.name, &[])
+            } else {
+                crumb.name.clone()
+            };
+            json!({
+                "@type": "ListItem",
+                "position": index + 1,
+                "name": name,
+                "item": absolute_url(site_url, &crumb.path),
+            })
+        })
+        .collect();
+
+    json!({
+        "@type": "BreadcrumbList",
+        "itemListElement": items,
+    })
+}
+
+fn blog_posting(
+    site: &SiteConfig,
+    post: &Post,
+    canonical_url: &str,
+    image_url: Option<&str>,
+) -> Value {
+    let mut posting = json!({
+        "@type": "BlogPosting",
+        "headline": post.title,
+        "url": canonical_url,
+        "mainEntityOfPage": {
+            "@type": "WebPage",
+            "@id": canonical_url,
+        },
+        "datePublished": post.published_at.unwrap_or(post.created_at).to_rfc3339(),
+        "dateModified": post.updated_at.to_rfc3339(),
+        "author": {
+            "@type": "Person",
+            "name": post.author.as_deref().unwrap_or(&site.author_name),
+        },
+        "publisher": {
+            "@type": "Organization",
+            "name": site.site_title,
+        },
+        "inLanguage": site.locale.as_str(),
+        "wordCount": post.word_count,
+    });
+
+    // Like the listings, never reveal anything of a password-protected post
+    if let Some(excerpt) = post
+        .excerpt
+        .as_deref()
+        .filter(|_| post.password_hash.is_none())
+    {
+        posting["description"] = json!(excerpt);
+    }
+    if let Some(image_url) = image_url {
+        posting["image"] = json!(image_url);
+    }
+    if let Some(category) = &post.category {
+        posting["articleSection"] = json!(category);
+    }
+    let tags = post.get_tags();
+    if !tags.is_empty() {
+        posting["keywords"] = json!(tags.join(", "));
+    }
+    posting
+}
+
+/// Serialize the nodes as one JSON-LD document that is safe to place inside
+/// a `<script>` element
+fn to_script_json(nodes: Vec<Value>) -> String {
+    json!({
+        "@context": "https://schema.org",
+        "@graph": nodes,
+    })
+    .to_string()
+    // Keep "</script>" and "<!--" in titles from ending the element
+    .replace('<', "\\u003c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breadcrumbs_start_at_home_and_use_absolute_urls() {
+        let site = SiteConfig::default();
+        let list = breadcrumb_list(
+            "https://blog.example.com/",
+            &site,
+            &[
+                Breadcrumb::new("rust", "/category/rust"),
+                Breadcrumb::new("Post", "https://other.example.com/post"),
+            ],
+        );
+
+        let items = list["itemListElement"].as_array().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["name"], "ホーム");
+        assert_eq!(items[0]["item"], "https://blog.example.com/");
+        assert_eq!(items[1]["position"], 2);
+        assert_eq!(items[1]["item"], "https://blog.example.com/category/rust");
+        assert_eq!(items[2]["item"], "https://other.example.com/post");
+    }
+
+    #[test]
+    fn test_script_json_escapes_closing_tags() {
+        let script = to_script_json(vec![json!({ "headline": "</script><script>" })]);
+        assert!(!script.contains('<'));
+        let parsed: Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(parsed["@graph"][0]["headline"], "</script><script>");
+    }
+}
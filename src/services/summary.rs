@@ -0,0 +1,134 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+
+/// Longest summary kept; list pages show a few lines at most
+const MAX_SUMMARY_CHARS: usize = 400;
+
+/// Characters of the post body sent to the model
+const MAX_INPUT_CHARS: usize = 12_000;
+
+/// Post summary configuration
+#[derive(Debug, Clone)]
+pub struct SummaryConfig {
+    /// OpenAI-compatible chat completions endpoint
+    pub api_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    /// Language the summary should be written in (e.g. "Japanese")
+    pub language: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    content: Option<String>,
+}
+
+/// Service that asks a language model for a short summary of a post
+#[derive(Clone)]
+pub struct SummaryService {
+    config: SummaryConfig,
+    client: Client,
+}
+
+impl SummaryService {
+    /// Create a new summary service
+    pub fn new(config: SummaryConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap_or_default();
+
+        Self { config, client }
+    }
+
+    /// Summarize a post, or `None` when the model had nothing usable
+    pub async fn summarize(&self, title: &str, markdown: &str) -> Result<Option<String>> {
+        debug!("Requesting summary of '{}'", title);
+
+        let body: String = markdown.chars().take(MAX_INPUT_CHARS).collect();
+        let prompt = format!(
+            "Summarize this blog post in {} in two or three sentences for a list of posts. \
+             Reply with the summary only.\n\nTitle: {}\n\n{}",
+            self.config.language, title, body
+        );
+        let request_body = serde_json::json!({
+            "model": self.config.model,
+            "max_tokens": 300,
+            "messages": [{ "role": "user", "content": prompt }]
+        });
+
+        let mut request = self.client.post(&self.config.api_url).json(&request_body);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response: ChatCompletionResponse = request
+            .send()
+            .await
+            .context("Failed to reach summary provider")?
+            .error_for_status()
+            .context("Summary provider returned an error")?
+            .json()
+            .await
+            .context("Invalid summary provider response")?;
+
+        let content = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| anyhow!("Summary provider returned no choices"))?;
+
+        Ok(clean_summary(&content))
+    }
+}
+
+/// Collapse the reply to one paragraph without a leading "Summary:" label
+fn clean_summary(raw: &str) -> Option<String> {
+    let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = ["summary:", "要約:", "要約："]
+        .iter()
+        .find_map(|label| {
+            text.get(..label.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(label))
+                .map(|_| text[label.len()..].trim_start())
+        })
+        .unwrap_or(&text);
+
+    if text.is_empty() {
+        return None;
+    }
+
+    Some(text.chars().take(MAX_SUMMARY_CHARS).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_summary_joins_lines_and_strips_label() {
+        assert_eq!(
+            clean_summary("Summary: Rust makes\n\nconcurrency safe."),
+            Some("Rust makes concurrency safe.".to_string())
+        );
+        assert_eq!(
+            clean_summary("要約：ブログを作りました。"),
+            Some("ブログを作りました。".to_string())
+        );
+        assert_eq!(clean_summary(" \n "), None);
+    }
+}
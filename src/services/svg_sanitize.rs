@@ -0,0 +1,401 @@
+use anyhow::{anyhow, Result};
+use roxmltree::{Document, Node, NodeType, ParsingOptions};
+use std::fmt::Write;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// `Content-Security-Policy` served with uploaded SVGs, so anything that gets
+/// past the sanitizer still cannot run scripts or load resources
+pub const SVG_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; style-src 'unsafe-inline'";
+
+/// Elements kept in sanitized SVGs; scripting, embedded HTML and animation
+/// (which can rewrite attributes after sanitizing) are left out
+const ALLOWED_ELEMENTS: &[&str] = &[
+    "a",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "stop",
+    "style",
+    "svg",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "title",
+    "tspan",
+    "use",
+    "view",
+];
+
+/// Attributes without a namespace kept in sanitized SVGs
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+    "alignment-baseline",
+    "amplitude",
+    "aria-hidden",
+    "aria-label",
+    "azimuth",
+    "baseFrequency",
+    "baseProfile",
+    "bias",
+    "class",
+    "clip-path",
+    "clip-rule",
+    "clipPathUnits",
+    "color",
+    "color-interpolation-filters",
+    "cx",
+    "cy",
+    "d",
+    "diffuseConstant",
+    "display",
+    "divisor",
+    "dominant-baseline",
+    "dx",
+    "dy",
+    "edgeMode",
+    "elevation",
+    "exponent",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "filterUnits",
+    "flood-color",
+    "flood-opacity",
+    "font-family",
+    "font-size",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "fr",
+    "fx",
+    "fy",
+    "gradientTransform",
+    "gradientUnits",
+    "height",
+    "href",
+    "id",
+    "in",
+    "in2",
+    "intercept",
+    "k1",
+    "k2",
+    "k3",
+    "k4",
+    "kernelMatrix",
+    "lang",
+    "lengthAdjust",
+    "letter-spacing",
+    "lighting-color",
+    "limitingConeAngle",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "mask",
+    "maskContentUnits",
+    "maskUnits",
+    "method",
+    "mode",
+    "numOctaves",
+    "offset",
+    "opacity",
+    "operator",
+    "order",
+    "orient",
+    "overflow",
+    "paint-order",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "points",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "r",
+    "radius",
+    "refX",
+    "refY",
+    "result",
+    "role",
+    "rotate",
+    "rx",
+    "ry",
+    "scale",
+    "seed",
+    "shape-rendering",
+    "side",
+    "slope",
+    "spacing",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "style",
+    "surfaceScale",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "text-anchor",
+    "text-decoration",
+    "text-rendering",
+    "textLength",
+    "transform",
+    "type",
+    "values",
+    "vector-effect",
+    "version",
+    "viewBox",
+    "visibility",
+    "width",
+    "word-spacing",
+    "writing-mode",
+    "x",
+    "x1",
+    "x2",
+    "xChannelSelector",
+    "y",
+    "y1",
+    "y2",
+    "yChannelSelector",
+    "z",
+];
+
+/// Rebuild an SVG document from its allowlisted elements and attributes
+///
+/// The document is parsed as XML, so names are compared with their namespace
+/// resolved and attribute values with entities expanded. DTDs are rejected,
+/// as is anything that isn't well-formed or whose root isn't an SVG `<svg>`.
+/// Disallowed elements are dropped with their content, and links may only
+/// point at fragments, web pages or raster `data:` images.
+pub fn sanitize_svg(svg: &str) -> Result<String> {
+    let document = Document::parse_with_options(
+        svg,
+        ParsingOptions {
+            allow_dtd: false,
+            ..ParsingOptions::default()
+        },
+    )
+    .map_err(|e| anyhow!("SVG file is not valid XML: {}", e))?;
+
+    let root = document.root_element();
+    if !is_allowed_element(root) || root.tag_name().name() != "svg" {
+        return Err(anyhow!("SVG file has no <svg> root element"));
+    }
+
+    let mut output = String::new();
+    write_element(&mut output, root, true);
+    Ok(output)
+}
+
+fn is_allowed_element(node: Node) -> bool {
+    let name = node.tag_name();
+    name.namespace() == Some(SVG_NAMESPACE) && ALLOWED_ELEMENTS.contains(&name.name())
+}
+
+fn write_element(output: &mut String, node: Node, is_root: bool) {
+    let name = node.tag_name().name();
+    let _ = write!(output, "<{}", name);
+    if is_root {
+        let _ = write!(output, " xmlns=\"{}\"", SVG_NAMESPACE);
+        let uses_xlink = node
+            .descendants()
+            .flat_map(|descendant| descendant.attributes())
+            .any(|attribute| attribute.namespace() == Some(XLINK_NAMESPACE));
+        if uses_xlink {
+            let _ = write!(output, " xmlns:xlink=\"{}\"", XLINK_NAMESPACE);
+        }
+    }
+
+    for attribute in node.attributes() {
+        let qualified = match attribute.namespace() {
+            None if ALLOWED_ATTRIBUTES.contains(&attribute.name()) => attribute.name().to_string(),
+            Some(XLINK_NAMESPACE) if attribute.name() == "href" => "xlink:href".to_string(),
+            _ => continue,
+        };
+        if attribute.name() == "href" && !is_safe_link(attribute.value()) {
+            continue;
+        }
+        let _ = write!(
+            output,
+            " {}=\"{}\"",
+            qualified,
+            html_escape::encode_double_quoted_attribute(attribute.value())
+        );
+    }
+
+    let children: Vec<Node> = node
+        .children()
+        .filter(|child| match child.node_type() {
+            NodeType::Element => is_allowed_element(*child),
+            NodeType::Text => true,
+            _ => false,
+        })
+        .collect();
+    if children.is_empty() {
+        output.push_str("/>");
+        return;
+    }
+
+    output.push('>');
+    for child in children {
+        if child.is_element() {
+            write_element(output, child, false);
+        } else if let Some(text) = child.text() {
+            output.push_str(&html_escape::encode_text(text));
+        }
+    }
+    let _ = write!(output, "</{}>", name);
+}
+
+/// Whether a link stays inert: a fragment, a relative or web URL, or a raster data image
+fn is_safe_link(value: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme
+    let normalized: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let Some(colon) = normalized.find(':') else {
+        return true;
+    };
+    if normalized[..colon].contains(['/', '?', '#']) {
+        // The colon is in the path, so the link is relative
+        return true;
+    }
+    match &normalized[..colon] {
+        "http" | "https" | "mailto" => true,
+        "data" => ["image/png", "image/jpeg", "image/gif", "image/webp"]
+            .iter()
+            .any(|mime_type| normalized[colon + 1..].starts_with(mime_type)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_content_is_removed() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" onload="alert(1)"><script>alert(2)</script><foreignObject><iframe xmlns="http://www.w3.org/1999/xhtml" src="x"/></foreignObject><a xlink:href="javascript:alert(3)"><rect width="10" height="10" onclick='go()'/></a><a href="https://example.com"><circle r="5"/></a><!-- note --></svg>"#;
+
+        assert_eq!(
+            sanitize_svg(svg).unwrap(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><a><rect width="10" height="10"/></a><a href="https://example.com"><circle r="5"/></a></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_prefixed_script_element_is_removed() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><x:script xmlns:x="http://www.w3.org/2000/svg">alert(1)</x:script><h:script xmlns:h="http://www.w3.org/1999/xhtml">alert(2)</h:script><rect width="1" height="1"/></svg>"#;
+
+        assert_eq!(
+            sanitize_svg(svg).unwrap(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="1" height="1"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_entity_encoded_javascript_link_is_removed() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><a href="&#106;avascript:alert(1)"><text>x</text></a><a href="java&#x09;script:alert(2)"><text>y</text></a></svg>"#;
+
+        assert_eq!(
+            sanitize_svg(svg).unwrap(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><a><text>x</text></a><a><text>y</text></a></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_animations_cannot_rewrite_links() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg"><a href="#top"><animate attributeName="href" values="javascript:alert(1)"/><set attributeName="href" to="javascript:alert(2)"/><text>click</text></a></svg>"##;
+
+        assert_eq!(
+            sanitize_svg(svg).unwrap(),
+            r##"<svg xmlns="http://www.w3.org/2000/svg"><a href="#top"><text>click</text></a></svg>"##
+        );
+    }
+
+    #[test]
+    fn test_dtds_and_malformed_documents_are_rejected() {
+        assert!(sanitize_svg(
+            r#"<!DOCTYPE svg [<!ENTITY x "boom">]><svg xmlns="http://www.w3.org/2000/svg">&x;</svg>"#
+        )
+        .is_err());
+        assert!(sanitize_svg("<svg><scr<script></script>ipt>alert(1)</script></svg>").is_err());
+        assert!(sanitize_svg(r#"<html xmlns="http://www.w3.org/1999/xhtml"/>"#).is_err());
+        assert!(sanitize_svg("<svg/>").is_err());
+    }
+
+    #[test]
+    fn test_text_and_styles_are_escaped() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><style><![CDATA[a > b { fill: red }]]></style><text x="1">1 &lt; 2 &amp; "q"</text><image href="data:image/png;base64,AA==" width="1" height="1"/></svg>"#;
+
+        assert_eq!(
+            sanitize_svg(svg).unwrap(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><style>a &gt; b { fill: red }</style><text x="1">1 &lt; 2 &amp; "q"</text><image href="data:image/png;base64,AA==" width="1" height="1"/></svg>"#
+        );
+    }
+}
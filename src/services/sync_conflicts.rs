@@ -0,0 +1,183 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::models::{ConflictResolution, Post, PostSyncStatus, SyncState, UpdatePost};
+use crate::services::blog_storage::BlogPost;
+use crate::services::{BlogStorageService, DatabaseService, MarkdownService};
+
+/// Hash of a markdown body, ignoring line-ending and trailing whitespace differences
+pub fn content_hash(content: &str) -> String {
+    let normalized = content.replace("\r\n", "\n");
+    format!("{:x}", Sha256::digest(normalized.trim().as_bytes()))
+}
+
+/// Detects and settles posts whose database copy and Dropbox file diverged
+#[derive(Clone)]
+pub struct SyncConflictService {
+    database: DatabaseService,
+    blog_storage: Arc<BlogStorageService>,
+    markdown: MarkdownService,
+}
+
+impl SyncConflictService {
+    /// Create a new sync conflict service
+    pub fn new(
+        database: DatabaseService,
+        blog_storage: Arc<BlogStorageService>,
+        markdown: MarkdownService,
+    ) -> Self {
+        Self {
+            database,
+            blog_storage,
+            markdown,
+        }
+    }
+
+    /// Compare a post with its Dropbox file against the hash of the last sync
+    ///
+    /// Matching copies become the new sync base, so later edits on either side
+    /// are attributed correctly.
+    pub async fn inspect(&self, db_post: &Post, dropbox_post: &BlogPost) -> Result<PostSyncStatus> {
+        let database_hash = content_hash(&db_post.content);
+        let dropbox_hash = content_hash(&dropbox_post.content);
+        let sync_hash = self.database.get_post_sync_hash(db_post.id).await?;
+
+        let state = SyncState::classify(&database_hash, &dropbox_hash, sync_hash.as_deref());
+        if state == SyncState::InSync && sync_hash.as_deref() != Some(database_hash.as_str()) {
+            self.database
+                .set_post_sync_hash(db_post.id, &database_hash)
+                .await?;
+        }
+
+        let actions = match state {
+            SyncState::InSync => Vec::new(),
+            _ => vec![
+                ConflictResolution::KeepDb,
+                ConflictResolution::KeepDropbox,
+                ConflictResolution::ManualMerge,
+            ],
+        };
+
+        Ok(PostSyncStatus {
+            slug: db_post.slug.clone(),
+            title: db_post.title.clone(),
+            state,
+            dropbox_path: dropbox_post.dropbox_path.clone(),
+            database_hash,
+            dropbox_hash,
+            database_updated_at: db_post.updated_at,
+            dropbox_updated_at: dropbox_post.metadata.updated_at,
+            actions,
+        })
+    }
+
+    /// Posts whose database and Dropbox content differ
+    pub async fn find_diverged(&self) -> Result<Vec<PostSyncStatus>> {
+        let dropbox_posts = self.blog_storage.list_published_posts().await?;
+
+        let mut diverged = Vec::new();
+        for dropbox_post in &dropbox_posts {
+            let Some(db_post) = self
+                .database
+                .get_post_by_slug(&dropbox_post.metadata.slug)
+                .await?
+            else {
+                continue;
+            };
+
+            let status = self.inspect(&db_post, dropbox_post).await?;
+            if status.state != SyncState::InSync {
+                diverged.push(status);
+            }
+        }
+
+        debug!("Found {} diverged posts", diverged.len());
+        Ok(diverged)
+    }
+
+    /// Settle a diverged post; `merged_content` is required for a manual merge
+    pub async fn resolve(
+        &self,
+        db_post: Post,
+        resolution: ConflictResolution,
+        merged_content: Option<String>,
+    ) -> Result<Post> {
+        info!(
+            "Resolving sync conflict of {} with {:?}",
+            db_post.slug, resolution
+        );
+
+        let post = match resolution {
+            ConflictResolution::KeepDb => {
+                self.upload(&db_post).await?;
+                db_post
+            }
+            ConflictResolution::KeepDropbox => {
+                let dropbox_post = self
+                    .blog_storage
+                    .get_post_by_slug(&db_post.slug)
+                    .await?
+                    .with_context(|| format!("Post '{}' not found in Dropbox", db_post.slug))?;
+                self.update_content(&db_post, dropbox_post.content).await?
+            }
+            ConflictResolution::ManualMerge => {
+                let content = merged_content.context("Merged content is required")?;
+                let post = self.update_content(&db_post, content).await?;
+                self.upload(&post).await?;
+                post
+            }
+        };
+
+        self.database
+            .set_post_sync_hash(post.id, &content_hash(&post.content))
+            .await?;
+        Ok(post)
+    }
+
+    /// Replace the post's markdown body and re-render its HTML
+    async fn update_content(&self, db_post: &Post, content: String) -> Result<Post> {
+        let html = self.markdown.parse_markdown(&content)?.html;
+        let update = UpdatePost {
+            title: None,
+            content: Some(content),
+            html_content: Some(html),
+            excerpt: None,
+            category: None,
+            tags: None,
+            published: None,
+            featured: None,
+            author: None,
+            dropbox_path: None,
+        };
+
+        self.database
+            .update_post(db_post.id, update)
+            .await?
+            .with_context(|| format!("Post '{}' disappeared while resolving", db_post.slug))
+    }
+
+    /// Overwrite the Dropbox file with the database copy
+    async fn upload(&self, post: &Post) -> Result<()> {
+        let blog_post = BlogPost::from_post(post);
+        self.blog_storage.save_post(&blog_post, false).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_ignores_line_endings() {
+        assert_eq!(
+            content_hash("# Title\r\nBody\r\n"),
+            content_hash("# Title\nBody")
+        );
+        assert_ne!(
+            content_hash("# Title\nBody"),
+            content_hash("# Title\nOther")
+        );
+    }
+}
@@ -0,0 +1,540 @@
+use serde_json::{json, Value};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{warn, Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use uuid::Uuid;
+
+/// Spans sent to the collector in one request
+const MAX_BATCH: usize = 512;
+
+/// Finished spans waiting for export; newer spans are dropped beyond this
+const QUEUE_CAPACITY: usize = 8192;
+
+/// Pause between exports, letting spans accumulate into batches
+const EXPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Target of the query events sqlx logs after each statement
+pub(super) const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+/// OTLP trace export configuration
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Collector base URL; spans are posted to `<endpoint>/v1/traces`
+    pub endpoint: String,
+    /// `service.name` resource attribute
+    pub service_name: String,
+}
+
+/// Tracing layer exporting spans to an OpenTelemetry collector over OTLP/HTTP (JSON)
+///
+/// Spans of the `tracing` crate become OTLP spans. A few fields have special
+/// meaning, following the `tracing-opentelemetry` conventions:
+/// `otel.name` renames the span, `otel.kind` sets its kind ("server",
+/// "client", ...) and `otel.status_code` = "ERROR" marks it failed. A root
+/// span with a UUID `request_id` field uses it as the trace ID, so a request
+/// ID from the logs or the `X-Request-Id` header finds its trace directly.
+///
+/// sqlx query events are turned into database spans under the span they were
+/// logged in.
+pub struct OtlpLayer {
+    sender: mpsc::Sender<SpanData>,
+}
+
+impl OtlpLayer {
+    /// Create the layer and spawn the task posting spans to the collector
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn new(config: OtlpConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(export_loop(config, receiver));
+        Self { sender }
+    }
+
+    fn send(&self, span: SpanData) {
+        // Never block the instrumented code; drop spans if the collector falls behind
+        let _ = self.sender.try_send(span);
+    }
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanData>()
+                .map(|data| (data.trace_id, data.span_id))
+        });
+        let trace_id = match parent {
+            Some((trace_id, _)) => trace_id,
+            None => fields
+                .request_id
+                .as_deref()
+                .and_then(|request_id| Uuid::parse_str(request_id).ok())
+                .map(|uuid| uuid.into_bytes())
+                .unwrap_or_else(new_trace_id),
+        };
+
+        let mut data = SpanData {
+            trace_id,
+            span_id: new_span_id(),
+            parent_span_id: parent.map(|(_, span_id)| span_id),
+            name: attrs.metadata().name().to_string(),
+            kind: SpanKind::Internal,
+            error: false,
+            start: SystemTime::now(),
+            end: None,
+            attributes: Vec::new(),
+        };
+        data.apply(fields);
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanFields::default();
+        values.record(&mut fields);
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            data.apply(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != SQLX_QUERY_TARGET {
+            return;
+        }
+        let Some(parent) = ctx.event_span(event) else {
+            // Queries outside any span (startup, migrations) aren't part of a trace
+            return;
+        };
+        let Some((trace_id, parent_span_id)) = parent
+            .extensions()
+            .get::<SpanData>()
+            .map(|data| (data.trace_id, data.span_id))
+        else {
+            return;
+        };
+
+        let mut query = QueryFields::default();
+        event.record(&mut query);
+        let end = SystemTime::now();
+        let elapsed = Duration::try_from_secs_f64(query.elapsed_secs).unwrap_or_default();
+        let summary = query.summary.unwrap_or_else(|| "query".to_string());
+        let statement = query
+            .statement
+            .map(|statement| statement.trim().to_string())
+            .filter(|statement| !statement.is_empty())
+            .unwrap_or_else(|| summary.clone());
+
+        self.send(SpanData {
+            trace_id,
+            span_id: new_span_id(),
+            parent_span_id: Some(parent_span_id),
+            name: summary,
+            kind: SpanKind::Client,
+            error: false,
+            start: end.checked_sub(elapsed).unwrap_or(end),
+            end: Some(end),
+            attributes: vec![
+                ("db.system".to_string(), json!({ "stringValue": "sqlite" })),
+                (
+                    "db.statement".to_string(),
+                    json!({ "stringValue": statement }),
+                ),
+                (
+                    "db.rows_returned".to_string(),
+                    json!({ "intValue": query.rows_returned.to_string() }),
+                ),
+                (
+                    "db.rows_affected".to_string(),
+                    json!({ "intValue": query.rows_affected.to_string() }),
+                ),
+            ],
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let data = span.extensions_mut().remove::<SpanData>();
+        if let Some(mut data) = data {
+            data.end = Some(SystemTime::now());
+            self.send(data);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpanKind {
+    Internal,
+    Server,
+    Client,
+    Producer,
+    Consumer,
+}
+
+impl SpanKind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind.to_ascii_lowercase().as_str() {
+            "internal" => Some(SpanKind::Internal),
+            "server" => Some(SpanKind::Server),
+            "client" => Some(SpanKind::Client),
+            "producer" => Some(SpanKind::Producer),
+            "consumer" => Some(SpanKind::Consumer),
+            _ => None,
+        }
+    }
+
+    /// OTLP `SpanKind` enum value
+    fn code(self) -> u8 {
+        match self {
+            SpanKind::Internal => 1,
+            SpanKind::Server => 2,
+            SpanKind::Client => 3,
+            SpanKind::Producer => 4,
+            SpanKind::Consumer => 5,
+        }
+    }
+}
+
+/// A span being recorded or waiting for export
+#[derive(Debug, Clone)]
+struct SpanData {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    name: String,
+    kind: SpanKind,
+    error: bool,
+    start: SystemTime,
+    end: Option<SystemTime>,
+    /// OTLP attributes as key and `AnyValue` JSON
+    attributes: Vec<(String, Value)>,
+}
+
+impl SpanData {
+    fn apply(&mut self, fields: SpanFields) {
+        if let Some(name) = fields.name {
+            self.name = name;
+        }
+        if let Some(kind) = fields.kind {
+            self.kind = kind;
+        }
+        self.error |= fields.error;
+        for (key, value) in fields.attributes {
+            match self
+                .attributes
+                .iter_mut()
+                .find(|(existing, _)| *existing == key)
+            {
+                Some((_, existing)) => *existing = value,
+                None => self.attributes.push((key, value)),
+            }
+        }
+    }
+
+    /// OTLP JSON encoding of the span
+    fn to_otlp(&self) -> Value {
+        let mut span = json!({
+            "traceId": hex(&self.trace_id),
+            "spanId": hex(&self.span_id),
+            "name": self.name,
+            "kind": self.kind.code(),
+            "startTimeUnixNano": unix_nanos(self.start).to_string(),
+            "endTimeUnixNano": unix_nanos(self.end.unwrap_or(self.start)).to_string(),
+            "attributes": self
+                .attributes
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": value }))
+                .collect::<Vec<_>>(),
+            // STATUS_CODE_ERROR or STATUS_CODE_UNSET
+            "status": { "code": if self.error { 2 } else { 0 } },
+        });
+        if let Some(parent_span_id) = &self.parent_span_id {
+            span["parentSpanId"] = json!(hex(parent_span_id));
+        }
+        span
+    }
+}
+
+/// Span fields, split into OTLP attributes and the special `otel.*` fields
+#[derive(Default)]
+struct SpanFields {
+    name: Option<String>,
+    kind: Option<SpanKind>,
+    error: bool,
+    request_id: Option<String>,
+    attributes: Vec<(String, Value)>,
+}
+
+impl SpanFields {
+    fn record_value(&mut self, field: &Field, value: Value) {
+        match field.name() {
+            "otel.name" => {
+                self.name = value
+                    .get("stringValue")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            }
+            "otel.kind" => {
+                self.kind = value
+                    .get("stringValue")
+                    .and_then(Value::as_str)
+                    .and_then(SpanKind::parse)
+            }
+            "otel.status_code" => {
+                self.error = value
+                    .get("stringValue")
+                    .and_then(Value::as_str)
+                    .is_some_and(|status| status.eq_ignore_ascii_case("error"))
+            }
+            name => {
+                if name == "request_id" {
+                    self.request_id = value
+                        .get("stringValue")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                }
+                self.attributes.push((name.to_string(), value));
+            }
+        }
+    }
+}
+
+impl Visit for SpanFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, json!({ "stringValue": value }));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field, json!({ "boolValue": value }));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field, json!({ "intValue": value.to_string() }));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field, json!({ "intValue": value.to_string() }));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_value(field, json!({ "doubleValue": value }));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_value(field, json!({ "stringValue": format!("{:?}", value) }));
+    }
+}
+
+/// Fields of a sqlx query event
+#[derive(Default)]
+pub(super) struct QueryFields {
+    pub(super) summary: Option<String>,
+    pub(super) statement: Option<String>,
+    rows_returned: u64,
+    rows_affected: u64,
+    pub(super) elapsed_secs: f64,
+}
+
+impl Visit for QueryFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = Some(value.to_string()),
+            "db.statement" => self.statement = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "rows_returned" => self.rows_returned = value,
+            "rows_affected" => self.rows_affected = value,
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        // sqlx records the summary and statement as `Display`/`Debug` values
+        match field.name() {
+            "summary" => self.summary = Some(format!("{:?}", value).trim_matches('"').to_string()),
+            "db.statement" => {
+                self.statement = Some(
+                    format!("{:?}", value)
+                        .trim_matches('"')
+                        .replace("\\n", "\n"),
+                )
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn export_loop(config: OtlpConfig, mut receiver: mpsc::Receiver<SpanData>) {
+    let url = format!("{}/v1/traces", config.endpoint.trim_end_matches('/'));
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_default();
+    let mut batch = Vec::with_capacity(MAX_BATCH);
+
+    while receiver.recv_many(&mut batch, MAX_BATCH).await > 0 {
+        let body = export_request(&config.service_name, &batch);
+        batch.clear();
+        match client.post(&url).json(&body).send().await {
+            Ok(response) if !response.status().is_success() => {
+                warn!("OTLP collector rejected spans: {}", response.status())
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to export spans to {}: {}", url, e),
+        }
+        tokio::time::sleep(EXPORT_INTERVAL).await;
+    }
+}
+
+/// OTLP `ExportTraceServiceRequest` body for a batch of spans
+fn export_request(service_name: &str, spans: &[SpanData]) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": service_name } },
+                    { "key": "service.version", "value": { "stringValue": env!("CARGO_PKG_VERSION") } },
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "tobelog" },
+                "spans": spans.iter().map(SpanData::to_otlp).collect::<Vec<_>>(),
+            }]
+        }]
+    })
+}
+
+fn new_trace_id() -> [u8; 16] {
+    Uuid::new_v4().into_bytes()
+}
+
+fn new_span_id() -> [u8; 8] {
+    let bytes = Uuid::new_v4().into_bytes();
+    let mut span_id = [0; 8];
+    span_id.copy_from_slice(&bytes[..8]);
+    span_id
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Layer writing finished spans to a channel instead of a collector
+    fn capture() -> (OtlpLayer, mpsc::Receiver<SpanData>) {
+        let (sender, receiver) = mpsc::channel(64);
+        (OtlpLayer { sender }, receiver)
+    }
+
+    #[test]
+    fn test_spans_share_trace_of_request_id() {
+        let (layer, mut receiver) = capture();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let request_id = "0f8fad5b-d9cb-469f-a165-70867728950e";
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::info_span!(
+                "http.request",
+                otel.kind = "server",
+                otel.name = "GET /",
+                request_id,
+                http.status_code = tracing::field::Empty,
+            );
+            let _entered = request.enter();
+            tracing::info_span!("load_post")
+                .in_scope(|| tracing::debug!(target: "sqlx::query", summary = "SELECT * FROM posts", rows_returned = 3u64, elapsed_secs = 0.25));
+            request.record("http.status_code", 200);
+        });
+
+        let query = receiver.try_recv().unwrap();
+        let load = receiver.try_recv().unwrap();
+        let request = receiver.try_recv().unwrap();
+
+        assert_eq!(hex(&request.trace_id), request_id.replace('-', ""));
+        assert_eq!(request.name, "GET /");
+        assert_eq!(request.kind, SpanKind::Server);
+        assert!(request.parent_span_id.is_none());
+        assert!(request
+            .attributes
+            .contains(&("http.status_code".to_string(), json!({ "intValue": "200" }))));
+
+        assert_eq!(load.trace_id, request.trace_id);
+        assert_eq!(load.parent_span_id, Some(request.span_id));
+        assert_eq!(load.kind, SpanKind::Internal);
+
+        assert_eq!(query.trace_id, request.trace_id);
+        assert_eq!(query.parent_span_id, Some(load.span_id));
+        assert_eq!(query.name, "SELECT * FROM posts");
+        let elapsed = query.end.unwrap().duration_since(query.start).unwrap();
+        assert_eq!(elapsed, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_export_request_encodes_otlp_json() {
+        let span = SpanData {
+            trace_id: [1; 16],
+            span_id: [2; 8],
+            parent_span_id: Some([3; 8]),
+            name: "job".to_string(),
+            kind: SpanKind::Consumer,
+            error: true,
+            start: UNIX_EPOCH + Duration::from_secs(1),
+            end: Some(UNIX_EPOCH + Duration::from_secs(2)),
+            attributes: vec![("job.kind".to_string(), json!({ "stringValue": "digest" }))],
+        };
+
+        let body = export_request("tobelog", &[span]);
+        let resource = &body["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "tobelog"
+        );
+        let span = &resource["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], "01010101010101010101010101010101");
+        assert_eq!(span["parentSpanId"], "0303030303030303");
+        assert_eq!(span["kind"], 5);
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["status"]["code"], 2);
+        assert_eq!(span["attributes"][0]["key"], "job.kind");
+    }
+}
@@ -3,15 +3,30 @@ use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 use tera::Tera;
 use tracing::{debug, info, warn};
 
+use crate::models::{
+    Announcement, AudioEnclosure, Locale, OutlineHeading, PostAttachment, PostChangelog,
+    PostVisibility,
+};
+use crate::services::assets::AssetService;
+use crate::services::captcha::CaptchaWidget;
+use crate::services::i18n::{self, DateStyle, SiteTimezone};
+
 /// Template service for rendering HTML using Tera
 #[derive(Clone)]
 pub struct TemplateService {
     tera: Tera,
     #[allow(dead_code)]
     theme: String,
+    /// Shared with the registered i18n helpers so a locale change applies to
+    /// every clone of the service
+    locale: Arc<RwLock<Locale>>,
+    timezone: SiteTimezone,
+    /// Announcement banners added to every page while they are active
+    announcements: Arc<RwLock<Vec<Announcement>>>,
 }
 
 impl TemplateService {
@@ -43,6 +58,20 @@ impl TemplateService {
         // Register custom filters
         tera.register_filter("truncate", truncate_filter);
 
+        // Register asset_url helper (falls back to /static until assets are built)
+        tera.register_function("asset_url", make_asset_url_function(AssetService::new()));
+
+        // Register i18n helpers: t(key=...), locale(), timezone() and the localized_date filter
+        let locale = Arc::new(RwLock::new(Locale::default()));
+        let timezone = SiteTimezone::new(chrono_tz::Tz::UTC);
+        tera.register_function("t", make_translate_function(locale.clone()));
+        tera.register_function("locale", make_locale_function(locale.clone()));
+        tera.register_function("timezone", make_timezone_function(timezone.clone()));
+        tera.register_filter(
+            "localized_date",
+            make_localized_date_filter(locale.clone(), timezone.clone()),
+        );
+
         info!("Template engine initialized successfully with theme: {}", actual_theme);
         debug!(
             "Available templates: {:?}",
@@ -52,9 +81,74 @@ impl TemplateService {
         Ok(Self { 
             tera,
             theme: actual_theme,
+            locale,
+            timezone,
+            announcements: Arc::new(RwLock::new(Vec::new())),
         })
     }
     
+    /// Resolve asset URLs in templates through the given asset pipeline
+    pub fn with_assets(mut self, assets: AssetService) -> Self {
+        self.tera
+            .register_function("asset_url", make_asset_url_function(assets));
+        self
+    }
+
+    /// Switch the language of UI strings and dates in rendered pages
+    pub fn set_locale(&self, locale: Locale) {
+        info!("Template locale set to {}", locale.as_str());
+        *self.locale.write().expect("template locale lock poisoned") = locale;
+    }
+
+    /// Current template locale
+    pub fn locale(&self) -> Locale {
+        *self.locale.read().expect("template locale lock poisoned")
+    }
+
+    /// Switch the timezone dates in rendered pages are shown in
+    pub fn set_timezone(&self, timezone: chrono_tz::Tz) {
+        info!("Template timezone set to {}", timezone.name());
+        self.timezone.set(timezone);
+    }
+
+    /// Site timezone handle, shared with every clone of the service
+    pub fn site_timezone(&self) -> &SiteTimezone {
+        &self.timezone
+    }
+
+    /// Replace the announcements shown on rendered pages
+    pub fn set_announcements(&self, announcements: Vec<Announcement>) {
+        debug!("Template announcements set: {}", announcements.len());
+        *self
+            .announcements
+            .write()
+            .expect("template announcements lock poisoned") = announcements;
+    }
+
+    /// Announcements active right now, as added to the context of every page
+    pub fn active_announcements(&self) -> Vec<Announcement> {
+        let now = chrono::Utc::now();
+        self.announcements
+            .read()
+            .expect("template announcements lock poisoned")
+            .iter()
+            .filter(|announcement| announcement.is_active(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Template context of `context` plus the variables shared by every page
+    fn page_context<T: Serialize>(&self, context: &T) -> Result<tera::Context> {
+        let mut tera_context = tera::Context::from_serialize(context)?;
+        tera_context.insert("announcements", &self.active_announcements());
+        Ok(tera_context)
+    }
+
+    /// Translate a UI string set from Rust, such as a page title
+    pub fn translate(&self, key: &str, args: &[(&str, &str)]) -> String {
+        i18n::translate(self.locale(), key, args)
+    }
+
     /// Get current theme name
     #[allow(dead_code)]
     pub fn get_theme(&self) -> &str {
@@ -73,7 +167,7 @@ impl TemplateService {
 
         let result = self
             .tera
-            .render(template_name, &tera::Context::from_serialize(context)?)
+            .render(template_name, &self.page_context(context)?)
             .with_context(|| format!("Failed to render template: {}", template_name))?;
 
         debug!(
@@ -84,7 +178,6 @@ impl TemplateService {
     }
 
     /// Render template with additional context variables
-    #[allow(dead_code)]
     pub fn render_with_context<T: Serialize>(
         &self,
         template_name: &str,
@@ -96,7 +189,7 @@ impl TemplateService {
             template_name
         );
 
-        let mut tera_context = tera::Context::from_serialize(context)?;
+        let mut tera_context = self.page_context(context)?;
         for (key, value) in additional_context {
             tera_context.insert(key, &value);
         }
@@ -142,6 +235,97 @@ fn truncate_filter(
     }
 }
 
+/// Build the `asset_url(path="css/main.css")` template function
+fn make_asset_url_function(
+    assets: AssetService,
+) -> impl Fn(&HashMap<String, tera::Value>) -> tera::Result<tera::Value> + Send + Sync {
+    move |args: &HashMap<String, tera::Value>| {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("asset_url requires a `path` argument"))?;
+        Ok(tera::Value::String(assets.asset_url(path)))
+    }
+}
+
+/// Build the `t(key="common.home", name=value)` template function
+fn make_translate_function(
+    locale: Arc<RwLock<Locale>>,
+) -> impl Fn(&HashMap<String, tera::Value>) -> tera::Result<tera::Value> + Send + Sync {
+    move |args: &HashMap<String, tera::Value>| {
+        let key = args
+            .get("key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("t requires a `key` argument"))?;
+        let values: Vec<(&str, String)> = args
+            .iter()
+            .filter(|(name, _)| name.as_str() != "key")
+            .map(|(name, value)| {
+                let value = match value {
+                    tera::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+        let values: Vec<(&str, &str)> = values.iter().map(|(name, value)| (*name, value.as_str())).collect();
+        let locale = *locale.read().expect("template locale lock poisoned");
+        Ok(tera::Value::String(i18n::translate(locale, key, &values)))
+    }
+}
+
+/// Build the `locale()` template function, used for `<html lang>`
+fn make_locale_function(
+    locale: Arc<RwLock<Locale>>,
+) -> impl Fn(&HashMap<String, tera::Value>) -> tera::Result<tera::Value> + Send + Sync {
+    move |_: &HashMap<String, tera::Value>| {
+        let locale = *locale.read().expect("template locale lock poisoned");
+        Ok(tera::Value::String(locale.as_str().to_string()))
+    }
+}
+
+/// Build the `timezone()` template function, naming the site timezone
+fn make_timezone_function(
+    timezone: SiteTimezone,
+) -> impl Fn(&HashMap<String, tera::Value>) -> tera::Result<tera::Value> + Send + Sync {
+    move |_: &HashMap<String, tera::Value>| Ok(tera::Value::String(timezone.get().name().to_string()))
+}
+
+/// Build the `localized_date(style="long")` filter for RFC 3339 timestamps,
+/// shown in the site timezone
+fn make_localized_date_filter(
+    locale: Arc<RwLock<Locale>>,
+    timezone: SiteTimezone,
+) -> impl Fn(&tera::Value, &HashMap<String, tera::Value>) -> tera::Result<tera::Value> + Send + Sync {
+    move |value: &tera::Value, args: &HashMap<String, tera::Value>| {
+        let style: DateStyle = args
+            .get("style")
+            .and_then(|v| v.as_str())
+            .unwrap_or("long")
+            .parse()
+            .map_err(|e| tera::Error::msg(format!("localized_date: {}", e)))?;
+        let date = match value {
+            tera::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s).map_err(|e| {
+                tera::Error::msg(format!("localized_date: invalid timestamp '{}': {}", s, e))
+            })?,
+            tera::Value::Number(n) => n
+                .as_i64()
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|date| date.fixed_offset())
+                .ok_or_else(|| tera::Error::msg("localized_date: invalid timestamp"))?,
+            other => {
+                return Err(tera::Error::msg(format!(
+                    "localized_date: expected a timestamp, got {}",
+                    other
+                )))
+            }
+        };
+        let locale = *locale.read().expect("template locale lock poisoned");
+        let date = timezone.to_local(&date);
+        Ok(tera::Value::String(i18n::format_date(&date, locale, style)))
+    }
+}
+
 /// Context for home page template
 #[derive(Debug, Serialize)]
 pub struct HomePageContext {
@@ -157,6 +341,45 @@ pub struct PostPageContext {
     pub site_title: String,
     pub site_description: String,
     pub post: PostData,
+    /// Absolute URL of the social share image
+    pub og_image_url: Option<String>,
+    /// `rel=canonical` URL: the original of a cross-post, else the post itself
+    pub canonical_url: Option<String>,
+    /// Podcast enclosure rendered as an audio player
+    pub audio: Option<AudioEnclosure>,
+    /// "Last updated" section, for posts with `show_changelog`
+    pub changelog: Option<PostChangelog>,
+    /// Downloadable files listed at the end of the post
+    pub attachments: Vec<PostAttachment>,
+    /// PDF download link, when PDF export is built in and the post is public
+    pub pdf_url: Option<String>,
+    /// Headings of the post, linked by the anchors added to `post.html_content`
+    pub outline: Vec<OutlineHeading>,
+    /// Values exposed as `data-*` attributes for the reading progress bar
+    pub reading_progress: ReadingProgress,
+}
+
+/// Data attributes read by the client-side reading progress bar
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReadingProgress {
+    pub word_count: i64,
+    /// Estimated minutes to read the post
+    pub reading_time: i64,
+    /// Number of headings the progress can be reported against
+    pub sections: usize,
+    /// `GET /api/posts/{slug}/outline` for clients that fetch the heading tree
+    pub outline_url: String,
+}
+
+/// Context for the lite version of a post page
+#[derive(Debug, Serialize)]
+pub struct LitePageContext {
+    pub site_title: String,
+    /// Post with its HTML simplified for reader mode
+    pub post: PostData,
+    pub canonical_url: String,
+    /// Path of the full post page
+    pub full_url: String,
 }
 
 /// Context for category page template
@@ -183,6 +406,140 @@ pub struct TagPageContext {
     pub total_pages: usize,
 }
 
+/// Context for the search results page
+#[derive(Debug, Serialize)]
+pub struct SearchPageContext {
+    pub site_title: String,
+    pub site_description: String,
+    pub query: String,
+    /// Category facet currently applied
+    pub category: Option<String>,
+    pub results: Vec<SearchResultSummary>,
+    /// Matches per category, ignoring the applied category
+    pub facets: Vec<crate::models::CategoryStat>,
+    pub total_results: usize,
+    pub page: usize,
+    pub total_pages: usize,
+    /// Recent posts offered when nothing matched
+    pub suggested_posts: Vec<PostSummary>,
+    /// Popular categories offered when nothing matched
+    pub suggested_categories: Vec<crate::models::CategoryStat>,
+}
+
+/// A search match for templates
+#[derive(Debug, Serialize)]
+pub struct SearchResultSummary {
+    #[serde(flatten)]
+    pub post: PostSummary,
+    /// Escaped excerpt with `<mark>`ed terms, safe to render unescaped
+    pub snippet_html: String,
+}
+
+impl From<crate::models::SearchHit> for SearchResultSummary {
+    fn from(hit: crate::models::SearchHit) -> Self {
+        // Same rule as the excerpt: no content from password-protected posts
+        let snippet_html = if hit.post.password_hash.is_some() {
+            String::new()
+        } else {
+            hit.snippet_html
+        };
+        Self {
+            post: PostSummary::from(hit.post),
+            snippet_html,
+        }
+    }
+}
+
+/// Context for the tag cloud page
+#[derive(Debug, Serialize)]
+pub struct TagIndexContext {
+    pub site_title: String,
+    pub site_description: String,
+    /// Tags by name
+    pub tags: Vec<TagCloudEntry>,
+}
+
+/// A tag in the tag cloud
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TagCloudEntry {
+    pub name: String,
+    pub count: i64,
+    /// Size class from 1 (fewest posts) to 5 (most posts)
+    pub weight: u8,
+}
+
+/// Weigh tags on a logarithmic scale so a few very common tags don't flatten
+/// the rest of the cloud
+pub fn tag_cloud(tags: Vec<crate::models::TagStat>) -> Vec<TagCloudEntry> {
+    let counts = tags.iter().map(|tag| (tag.count.max(1) as f64).ln());
+    let min = counts.clone().fold(f64::INFINITY, f64::min);
+    let max = counts.fold(f64::NEG_INFINITY, f64::max);
+
+    tags.into_iter()
+        .map(|tag| {
+            let weight = if max > min {
+                1 + (((tag.count.max(1) as f64).ln() - min) / (max - min) * 4.0).round() as u8
+            } else {
+                3
+            };
+            TagCloudEntry {
+                name: tag.name,
+                count: tag.count,
+                weight,
+            }
+        })
+        .collect()
+}
+
+/// Context for the category index page
+#[derive(Debug, Serialize)]
+pub struct CategoryIndexContext {
+    pub site_title: String,
+    pub site_description: String,
+    /// Categories, most posts first
+    pub categories: Vec<crate::models::CategoryIndexEntry>,
+}
+
+/// Context for the password form shown instead of a protected post
+#[derive(Debug, Serialize)]
+pub struct PostPasswordContext {
+    pub site_title: String,
+    pub site_description: String,
+    pub post_title: String,
+    /// URL of the post, where the form is posted
+    pub action: String,
+    /// Set after a wrong password
+    pub error: Option<String>,
+    /// Widget rendered in the form when a CAPTCHA is configured
+    pub captcha: Option<CaptchaWidget>,
+}
+
+/// Context for the 404 and error page templates
+#[derive(Debug, Serialize)]
+pub struct ErrorPageContext {
+    pub site_title: String,
+    pub site_description: String,
+    pub status: u16,
+    pub message: String,
+    /// Requested path, shown on the 404 page
+    pub path: String,
+    /// Recent posts suggested on the 404 page
+    pub recent_posts: Vec<PostSummary>,
+    /// ID to quote when reporting a server error; matches the server log
+    pub incident_id: Option<String>,
+}
+
+/// Context for the maintenance page template
+#[derive(Debug, Serialize)]
+pub struct MaintenancePageContext {
+    pub site_title: String,
+    pub site_description: String,
+    /// Custom message set through the admin API, shown instead of the default text
+    pub message: Option<String>,
+    /// Suggested wait before retrying, rounded up to whole minutes
+    pub retry_after_minutes: u64,
+}
+
 /// Post summary for templates
 #[derive(Debug, Serialize)]
 pub struct PostSummary {
@@ -195,6 +552,8 @@ pub struct PostSummary {
     pub author: Option<String>,
     pub published: bool,
     pub featured: bool,
+    pub pinned: bool,
+    pub visibility: PostVisibility,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -213,8 +572,16 @@ pub struct PostData {
     pub author: Option<String>,
     pub published: bool,
     pub featured: bool,
+    pub visibility: PostVisibility,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Show the banner warning that the content may be outdated
+    pub outdated_notice: bool,
+    /// The post has expired and was archived
+    pub archived: bool,
+    /// URLs where the post is also published
+    pub syndication_links: Vec<String>,
 }
 
 /// Blog statistics for templates
@@ -245,16 +612,20 @@ pub struct TagStat {
 impl From<crate::models::Post> for PostSummary {
     fn from(post: crate::models::Post) -> Self {
         let tags = post.get_tags();
+        // Lists must not reveal the content of password-protected posts
+        let excerpt = post.excerpt.filter(|_| post.password_hash.is_none());
         Self {
             id: post.id.to_string(),
             slug: post.slug,
             title: post.title,
-            excerpt: post.excerpt,
+            excerpt,
             category: post.category,
             tags,
             author: post.author,
             published: post.published,
             featured: post.featured,
+            pinned: post.pinned,
+            visibility: post.visibility,
             created_at: post.created_at,
             published_at: post.published_at,
         }
@@ -264,6 +635,11 @@ impl From<crate::models::Post> for PostSummary {
 impl From<crate::models::Post> for PostData {
     fn from(post: crate::models::Post) -> Self {
         let tags = post.get_tags();
+        let syndication_links = post.get_syndication_links();
+        let now = chrono::Utc::now();
+        let outdated_notice = post.shows_outdated_notice(now);
+        let archived = post.expiry_action == crate::models::ExpiryAction::Archive
+            && post.expires_at.is_some_and(|expires_at| now >= expires_at);
         Self {
             id: post.id.to_string(),
             slug: post.slug,
@@ -276,8 +652,13 @@ impl From<crate::models::Post> for PostData {
             author: post.author,
             published: post.published,
             featured: post.featured,
+            visibility: post.visibility,
             created_at: post.created_at,
             published_at: post.published_at,
+            expires_at: post.expires_at,
+            outdated_notice,
+            archived,
+            syndication_links,
         }
     }
 }
@@ -361,4 +742,35 @@ mod tests {
 
         assert_eq!(result.as_str().unwrap(), "This is a ...");
     }
+
+    #[test]
+    fn test_tag_cloud_weights_on_log_scale() {
+        let tag = |name: &str, count| crate::models::TagStat {
+            name: name.to_string(),
+            count,
+        };
+        let cloud = tag_cloud(vec![tag("a", 1), tag("b", 10), tag("c", 100)]);
+        let weights: Vec<u8> = cloud.iter().map(|entry| entry.weight).collect();
+        assert_eq!(weights, vec![1, 3, 5]);
+
+        let even = tag_cloud(vec![tag("a", 4), tag("b", 4)]);
+        assert!(even.iter().all(|entry| entry.weight == 3));
+    }
+
+    #[test]
+    fn test_locale_and_timezone_switch_apply_to_clones() {
+        let service = TemplateService::new().unwrap();
+        let clone = service.clone();
+        service.set_locale(Locale::En);
+        service.set_timezone(chrono_tz::Tz::Asia__Tokyo);
+
+        let mut tera = clone.tera().clone();
+        let html = tera
+            .render_str(
+                "{{ locale() }}|{{ t(key='common.home') }}|{{ '2024-03-01T20:00:00Z' | localized_date }}|{{ timezone() }}",
+                &tera::Context::new(),
+            )
+            .unwrap();
+        assert_eq!(html, "en|Home|March 2, 2024|Asia/Tokyo");
+    }
 }
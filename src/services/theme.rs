@@ -3,22 +3,24 @@ use chrono::Utc;
 use tracing::{debug, info, warn};
 
 use crate::models::{
-    CreateThemeRequest, CssVariable, DropboxTemplate, DropboxTemplateType, SiteConfig,
-    ThemeFilters, ThemeSettings, UpdateThemeRequest,
+    CategoryStyle, CreateThemeRequest, CssVariable, DropboxTemplate, DropboxTemplateType,
+    SettingsDocument, SettingsImportResponse, SiteConfig, ThemeFilters, ThemeSettings,
+    UpdateThemeRequest, SETTINGS_SCHEMA_VERSION,
 };
-use crate::services::{DatabaseService, DropboxClient};
+use crate::services::{DatabaseService, StorageBackend};
 
 /// Service for managing blog themes and custom design features
 #[derive(Clone)]
 pub struct ThemeService {
     database: DatabaseService,
-    dropbox: std::sync::Arc<DropboxClient>,
+    /// Holds the CSS templates under `/BlogStorage/templates`
+    storage: std::sync::Arc<dyn StorageBackend>,
 }
 
 impl ThemeService {
     /// Create a new theme service
-    pub fn new(database: DatabaseService, dropbox: std::sync::Arc<DropboxClient>) -> Self {
-        Self { database, dropbox }
+    pub fn new(database: DatabaseService, storage: std::sync::Arc<dyn StorageBackend>) -> Self {
+        Self { database, storage }
     }
 
     /// Get the currently active theme
@@ -57,6 +59,7 @@ impl ThemeService {
             ));
         }
 
+        validate_category_styles(&request.category_styles)?;
         let theme = ThemeSettings::from(request);
         let created_theme = self.database.create_theme(&theme).await?;
 
@@ -75,6 +78,10 @@ impl ThemeService {
     ) -> Result<ThemeSettings> {
         debug!("Updating theme: {}", name);
 
+        if let Some(styles) = &request.category_styles {
+            validate_category_styles(styles)?;
+        }
+
         let updated_theme = self
             .database
             .update_theme(name, request)
@@ -145,8 +152,9 @@ impl ThemeService {
             .await?
             .ok_or_else(|| anyhow::anyhow!("Theme '{}' not found", name))?;
 
-        // Start with base theme CSS
+        // Start with base theme CSS and the category accents
         let mut css = theme.to_css();
+        css.push_str(&theme.category_css());
 
         // Load custom CSS from Dropbox if available
         if let Ok(dropbox_css) = self.load_dropbox_css(&theme.name).await {
@@ -165,13 +173,19 @@ impl ThemeService {
         Ok(css)
     }
 
+    /// Generate CSS for the currently active theme
+    pub async fn generate_active_theme_css(&self) -> Result<String> {
+        let theme = self.get_active_theme().await?;
+        self.generate_theme_css(&theme.name).await
+    }
+
     /// Load CSS from Dropbox template folder
     async fn load_dropbox_css(&self, theme_name: &str) -> Result<String> {
         debug!("Loading CSS from Dropbox for theme: {}", theme_name);
 
         let css_path = "/BlogStorage/templates/style.css".to_string();
 
-        match self.dropbox.download_file(&css_path).await {
+        match self.storage.download_file(&css_path).await {
             Ok(content) => {
                 debug!("Loaded main CSS from Dropbox: {} bytes", content.len());
                 Ok(String::from_utf8(content)?)
@@ -189,7 +203,7 @@ impl ThemeService {
 
         let theme_css_path = format!("/BlogStorage/templates/themes/{}.css", theme_name);
 
-        match self.dropbox.download_file(&theme_css_path).await {
+        match self.storage.download_file(&theme_css_path).await {
             Ok(content) => {
                 debug!("Loaded theme CSS from Dropbox: {} bytes", content.len());
                 Ok(String::from_utf8(content)?)
@@ -209,13 +223,13 @@ impl ThemeService {
         let mut synced_templates = Vec::new();
 
         // List files in templates directory
-        match self.dropbox.list_folder(templates_path).await {
-            Ok(response) => {
-                for entry in &response.entries {
+        match self.storage.list_folder(templates_path).await {
+            Ok(entries) => {
+                for entry in &entries {
                     // Process CSS files
                     if entry.name.ends_with(".css") {
                         match self
-                            .process_dropbox_css_file(&entry.name, &entry.path_lower)
+                            .process_dropbox_css_file(&entry.name, &entry.path_display)
                             .await
                         {
                             Ok(template) => synced_templates.push(template),
@@ -246,12 +260,12 @@ impl ThemeService {
         let themes_path = "/BlogStorage/templates/themes";
         let mut theme_templates = Vec::new();
 
-        match self.dropbox.list_folder(themes_path).await {
-            Ok(response) => {
-                for entry in &response.entries {
+        match self.storage.list_folder(themes_path).await {
+            Ok(entries) => {
+                for entry in &entries {
                     if entry.name.ends_with(".css") {
                         match self
-                            .process_dropbox_css_file(&entry.name, &entry.path_lower)
+                            .process_dropbox_css_file(&entry.name, &entry.path_display)
                             .await
                         {
                             Ok(template) => theme_templates.push(template),
@@ -274,7 +288,7 @@ impl ThemeService {
     async fn process_dropbox_css_file(&self, name: &str, path: &str) -> Result<DropboxTemplate> {
         debug!("Processing Dropbox CSS file: {}", name);
 
-        let content = self.dropbox.download_file(path).await?;
+        let content = self.storage.download_file(path).await?;
         let content_str = String::from_utf8(content.clone())?;
 
         // Determine template type based on location and name
@@ -318,8 +332,81 @@ impl ThemeService {
         self.database.update_site_config(config).await
     }
 
+    /// Export site configuration and all themes as one document
+    pub async fn export_settings(&self) -> Result<SettingsDocument> {
+        debug!("Exporting site settings");
+
+        Ok(SettingsDocument {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            exported_at: Utc::now(),
+            site_config: self.get_site_config().await?,
+            themes: self.list_themes(ThemeFilters::default()).await?,
+        })
+    }
+
+    /// Restore a document produced by [`ThemeService::export_settings`]
+    pub async fn import_settings(
+        &self,
+        document: SettingsDocument,
+    ) -> Result<SettingsImportResponse> {
+        info!(
+            "Importing site settings exported at {}",
+            document.exported_at
+        );
+
+        if document.schema_version != SETTINGS_SCHEMA_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported settings schema version {} (expected {})",
+                document.schema_version,
+                SETTINGS_SCHEMA_VERSION
+            ));
+        }
+
+        if document
+            .themes
+            .iter()
+            .filter(|theme| theme.is_active)
+            .count()
+            > 1
+        {
+            return Err(anyhow::anyhow!("Only one theme can be active"));
+        }
+
+        for theme in &document.themes {
+            if theme.name.trim().is_empty() {
+                return Err(anyhow::anyhow!("Theme name cannot be empty"));
+            }
+            if let Some(css) = &theme.custom_css {
+                for warning in self.validate_css(css)? {
+                    warn!("Theme {}: {}", theme.name, warning);
+                }
+            }
+            validate_category_styles(&theme.category_styles)?;
+        }
+
+        let (themes_created, themes_updated) = self
+            .database
+            .import_settings(&document.site_config, &document.themes)
+            .await?;
+        let active_theme = self
+            .database
+            .get_active_theme()
+            .await?
+            .map(|theme| theme.name);
+
+        Ok(SettingsImportResponse {
+            success: true,
+            message: format!(
+                "Imported site configuration and {} themes",
+                document.themes.len()
+            ),
+            themes_created,
+            themes_updated,
+            active_theme,
+        })
+    }
+
     /// Validate CSS content
-    #[allow(dead_code)]
     pub fn validate_css(&self, css: &str) -> Result<Vec<String>> {
         debug!("Validating CSS content");
 
@@ -403,6 +490,7 @@ impl ThemeService {
                 show_copyright: true,
                 custom_content: None,
             },
+            category_styles: Vec::new(),
         };
 
         // Minimal theme
@@ -439,6 +527,7 @@ impl ThemeService {
                 show_copyright: true,
                 custom_content: None,
             },
+            category_styles: Vec::new(),
         };
 
         // Create themes if they don't exist
@@ -455,6 +544,41 @@ impl ThemeService {
     }
 }
 
+/// Reject category styles that would emit broken or unsafe CSS
+fn validate_category_styles(styles: &[CategoryStyle]) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for style in styles {
+        if style.category.trim().is_empty() {
+            anyhow::bail!("Invalid category style: category cannot be empty");
+        }
+        if !seen.insert(style.category.as_str()) {
+            anyhow::bail!(
+                "Invalid category style: {} is styled more than once",
+                style.category
+            );
+        }
+        let hex = style.accent_color.strip_prefix('#').unwrap_or_default();
+        if ![3, 4, 6, 8].contains(&hex.len()) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!(
+                "Invalid category style: accent color of {} must be a hex color like #DEA584",
+                style.category
+            );
+        }
+        if let Some(hero_image) = &style.hero_image {
+            if hero_image.trim().is_empty()
+                || hero_image.starts_with("javascript:")
+                || hero_image.chars().any(char::is_control)
+            {
+                anyhow::bail!(
+                    "Invalid category style: hero image of {} is not a usable URL",
+                    style.category
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 impl From<ThemeSettings> for CreateThemeRequest {
     fn from(theme: ThemeSettings) -> Self {
         Self {
@@ -474,6 +598,7 @@ impl From<ThemeSettings> for CreateThemeRequest {
             custom_css: theme.custom_css,
             header_style: theme.header_style,
             footer_style: theme.footer_style,
+            category_styles: theme.category_styles,
         }
     }
 }
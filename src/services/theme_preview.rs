@@ -0,0 +1,138 @@
+use axum::http::{header, HeaderMap};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const COOKIE_NAME: &str = "theme_preview";
+
+/// Lets a request render with another theme than the active one
+///
+/// A `?theme=` query parameter or a signed cookie set through the admin API
+/// picks the theme. Meant for comparing themes during development, so it does
+/// nothing unless enabled. The signing key is generated at startup; preview
+/// cookies stop working when the server restarts.
+#[derive(Clone)]
+pub struct ThemePreviewService {
+    enabled: Arc<AtomicBool>,
+    secret: Arc<Vec<u8>>,
+}
+
+impl ThemePreviewService {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            secret: Arc::new([Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turn previews on or off for every clone of the service
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Theme requested by the query parameter, or else by a valid preview cookie
+    pub fn requested_theme(
+        &self,
+        query_theme: Option<&str>,
+        headers: &HeaderMap,
+    ) -> Option<String> {
+        if !self.is_enabled() {
+            return None;
+        }
+        if let Some(theme) = query_theme.filter(|theme| !theme.is_empty()) {
+            return Some(theme.to_string());
+        }
+
+        let value = headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|cookies| cookies.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find_map(|(name, value)| (name == COOKIE_NAME).then_some(value))?;
+        let (theme, signature) = value.split_once('.')?;
+        let theme = String::from_utf8(URL_SAFE_NO_PAD.decode(theme).ok()?).ok()?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        self.signature(&theme)
+            .verify_slice(&signature)
+            .is_ok()
+            .then_some(theme)
+    }
+
+    /// `Set-Cookie` value previewing `theme` for the browser session
+    pub fn preview_cookie(&self, theme: &str) -> String {
+        format!(
+            "{}={}.{}; Path=/; HttpOnly; SameSite=Lax",
+            COOKIE_NAME,
+            URL_SAFE_NO_PAD.encode(theme),
+            URL_SAFE_NO_PAD.encode(self.signature(theme).finalize().into_bytes())
+        )
+    }
+
+    /// `Set-Cookie` value ending the preview
+    pub fn clear_cookie(&self) -> String {
+        format!(
+            "{}=; Path=/; Max-Age=0; HttpOnly; SameSite=Lax",
+            COOKIE_NAME
+        )
+    }
+
+    fn signature(&self, theme: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(theme.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn cookie_headers(cookie: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(cookie.split(';').next().unwrap()).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_preview_cookie_is_signed_and_query_wins() {
+        let preview = ThemePreviewService::new(true);
+        let headers = cookie_headers(&preview.preview_cookie("dark"));
+        assert_eq!(
+            preview.requested_theme(None, &headers).as_deref(),
+            Some("dark")
+        );
+        assert_eq!(
+            preview
+                .requested_theme(Some("minimal"), &headers)
+                .as_deref(),
+            Some("minimal")
+        );
+
+        // A cookie signed by another server, or forged, is ignored
+        let other = ThemePreviewService::new(true);
+        assert_eq!(other.requested_theme(None, &headers), None);
+        let forged = format!("theme_preview={}.AAAA", URL_SAFE_NO_PAD.encode("dark"));
+        assert_eq!(
+            preview.requested_theme(None, &cookie_headers(&forged)),
+            None
+        );
+
+        let disabled = ThemePreviewService::new(false);
+        assert_eq!(disabled.requested_theme(Some("dark"), &headers), None);
+    }
+}
@@ -0,0 +1,343 @@
+use anyhow::{Context, Result};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+use crate::models::{TotpEnrollment, TotpStatus};
+use crate::services::DatabaseService;
+
+/// Seconds each code is valid for (RFC 6238 default, used by all authenticator apps)
+const STEP_SECONDS: i64 = 30;
+/// Steps either side of the current one still accepted, for clock drift
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+const CODE_DIGITS: u32 = 6;
+const SECRET_BYTES: usize = 20;
+const RECOVERY_CODE_COUNT: usize = 10;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Outcome of enrollment or management calls that need a valid code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TotpError {
+    /// Enrollment was not started, or two-factor login is already on
+    InvalidState,
+    InvalidCode,
+}
+
+/// Two-factor authentication of the admin login with time-based one-time passwords
+#[derive(Clone)]
+pub struct TotpService {
+    database: DatabaseService,
+    /// Issuer shown in authenticator apps
+    issuer: String,
+}
+
+impl TotpService {
+    pub fn new(database: DatabaseService, issuer: impl Into<String>) -> Self {
+        Self {
+            database,
+            issuer: issuer.into(),
+        }
+    }
+
+    /// Whether logins must present a code
+    pub async fn is_enabled(&self) -> Result<bool> {
+        Ok(self
+            .database
+            .get_admin_totp()
+            .await?
+            .is_some_and(|totp| totp.enabled))
+    }
+
+    pub async fn status(&self) -> Result<TotpStatus> {
+        let totp = self.database.get_admin_totp().await?.filter(|t| t.enabled);
+        Ok(TotpStatus {
+            enabled: totp.is_some(),
+            enabled_at: totp.and_then(|totp| totp.enabled_at),
+            recovery_codes_remaining: self.database.count_admin_recovery_codes().await?,
+        })
+    }
+
+    /// Generate a new secret; it takes effect once confirmed with a code
+    pub async fn begin_enrollment(&self) -> Result<Result<TotpEnrollment, TotpError>> {
+        if self.is_enabled().await? {
+            return Ok(Err(TotpError::InvalidState));
+        }
+
+        let mut secret = [0u8; SECRET_BYTES];
+        OsRng.fill_bytes(&mut secret);
+        let secret = base32_encode(&secret);
+        self.database.save_pending_admin_totp(&secret).await?;
+
+        debug!("Started admin TOTP enrollment");
+        Ok(Ok(TotpEnrollment {
+            provisioning_uri: self.provisioning_uri(&secret),
+            secret,
+        }))
+    }
+
+    /// Enable two-factor login once the app shows the right code; returns the recovery codes
+    pub async fn confirm_enrollment(&self, code: &str) -> Result<Result<Vec<String>, TotpError>> {
+        let Some(totp) = self.database.get_admin_totp().await? else {
+            return Ok(Err(TotpError::InvalidState));
+        };
+        if totp.enabled {
+            return Ok(Err(TotpError::InvalidState));
+        }
+
+        let secret = base32_decode(&totp.secret).context("Stored TOTP secret is not base32")?;
+        let Some(step) = matching_step(&secret, code, Utc::now().timestamp()) else {
+            return Ok(Err(TotpError::InvalidCode));
+        };
+
+        let codes = generate_recovery_codes();
+        let hashes: Vec<String> = codes.iter().map(|code| recovery_code_hash(code)).collect();
+        self.database.enable_admin_totp(step, &hashes).await?;
+
+        info!("Enabled two-factor authentication for the admin login");
+        Ok(Ok(codes))
+    }
+
+    /// Check a login code: a TOTP code, or an unused recovery code (which is then used up)
+    pub async fn verify(&self, code: &str) -> Result<bool> {
+        let Some(totp) = self.database.get_admin_totp().await?.filter(|t| t.enabled) else {
+            return Ok(false);
+        };
+
+        let code = code.trim();
+        if code.len() == CODE_DIGITS as usize && code.bytes().all(|b| b.is_ascii_digit()) {
+            let secret = base32_decode(&totp.secret).context("Stored TOTP secret is not base32")?;
+            return match matching_step(&secret, code, Utc::now().timestamp()) {
+                // A code is accepted only once, even within its window
+                Some(step) => self.database.claim_admin_totp_step(step).await,
+                None => Ok(false),
+            };
+        }
+
+        let used = self
+            .database
+            .use_admin_recovery_code(&recovery_code_hash(code))
+            .await?;
+        if used {
+            info!("Admin logged in with a recovery code");
+        }
+        Ok(used)
+    }
+
+    /// Replace the recovery codes, after checking a current code
+    pub async fn regenerate_recovery_codes(
+        &self,
+        code: &str,
+    ) -> Result<Result<Vec<String>, TotpError>> {
+        if !self.is_enabled().await? {
+            return Ok(Err(TotpError::InvalidState));
+        }
+        if !self.verify(code).await? {
+            return Ok(Err(TotpError::InvalidCode));
+        }
+
+        let codes = generate_recovery_codes();
+        let hashes: Vec<String> = codes.iter().map(|code| recovery_code_hash(code)).collect();
+        self.database.replace_admin_recovery_codes(&hashes).await?;
+        Ok(Ok(codes))
+    }
+
+    /// Turn two-factor login off, after checking a current code
+    pub async fn disable(&self, code: &str) -> Result<Result<(), TotpError>> {
+        if !self.is_enabled().await? {
+            return Ok(Err(TotpError::InvalidState));
+        }
+        if !self.verify(code).await? {
+            return Ok(Err(TotpError::InvalidCode));
+        }
+
+        self.database.delete_admin_totp().await?;
+        info!("Disabled two-factor authentication for the admin login");
+        Ok(Ok(()))
+    }
+
+    /// `otpauth://` URI understood by authenticator apps
+    fn provisioning_uri(&self, secret: &str) -> String {
+        let issuer = uri_component(&self.issuer);
+        format!(
+            "otpauth://totp/{}:admin?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            issuer, secret, issuer, CODE_DIGITS, STEP_SECONDS
+        )
+    }
+}
+
+/// Code an authenticator app shows for the base32 `secret` at `unix_time`
+#[allow(dead_code)] // Used by tests
+pub fn totp_code(secret: &str, unix_time: i64) -> Option<String> {
+    let secret = base32_decode(secret)?;
+    Some(format!(
+        "{:0width$}",
+        code_at(&secret, unix_time.div_euclid(STEP_SECONDS)),
+        width = CODE_DIGITS as usize
+    ))
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+fn uri_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// HOTP value (RFC 4226) of `secret` for the counter `step`
+fn code_at(secret: &[u8], step: i64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    value % 10u32.pow(CODE_DIGITS)
+}
+
+/// Time step within the allowed drift whose code matches, if any
+fn matching_step(secret: &[u8], code: &str, unix_time: i64) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != CODE_DIGITS as usize {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+
+    let current = unix_time.div_euclid(STEP_SECONDS);
+    (current - ALLOWED_DRIFT_STEPS..=current + ALLOWED_DRIFT_STEPS)
+        .find(|step| code_at(secret, *step) == code)
+}
+
+/// Recovery codes like `k3m9p-2xq7d`
+fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let mut bytes = [0u8; 10];
+            OsRng.fill_bytes(&mut bytes);
+            let code: String = bytes
+                .iter()
+                .map(|byte| BASE32_ALPHABET[(*byte % 32) as usize].to_ascii_lowercase() as char)
+                .collect();
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect()
+}
+
+/// Hash stored for a recovery code; dashes, spaces and case are ignored
+fn recovery_code_hash(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Unpadded RFC 4648 base32, the secret format of `otpauth://` URIs
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    output
+}
+
+fn base32_decode(value: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(value.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in value.trim_end_matches('=').bytes() {
+        let index = BASE32_ALPHABET
+            .iter()
+            .position(|a| *a == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | index as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shared secret of the RFC 6238 SHA-1 test vectors
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_codes_match_rfc_6238_vectors() {
+        // The RFC lists 8-digit values; the 6-digit codes are their last six digits
+        for (time, expected) in [
+            (59, 287082),
+            (1111111109, 81804),
+            (1111111111, 50471),
+            (1234567890, 5924),
+            (2000000000, 279037),
+        ] {
+            assert_eq!(code_at(RFC_SECRET, time / STEP_SECONDS), expected);
+        }
+    }
+
+    #[test]
+    fn test_matching_step_allows_one_step_of_drift() {
+        let code = totp_code(&base32_encode(RFC_SECRET), 1234567890).unwrap();
+        assert_eq!(code, "005924");
+
+        assert_eq!(
+            matching_step(RFC_SECRET, &code, 1234567890),
+            Some(1234567890 / STEP_SECONDS)
+        );
+        assert!(matching_step(RFC_SECRET, &code, 1234567890 + STEP_SECONDS).is_some());
+        assert!(matching_step(RFC_SECRET, &code, 1234567890 + 3 * STEP_SECONDS).is_none());
+        assert!(matching_step(RFC_SECRET, "12345", 1234567890).is_none());
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("mzxw6ytboi").unwrap(), b"foobar");
+        assert_eq!(
+            base32_decode(&base32_encode(RFC_SECRET)).unwrap(),
+            RFC_SECRET
+        );
+        assert!(base32_decode("not base32!").is_none());
+    }
+
+    #[test]
+    fn test_recovery_codes_are_normalized_before_hashing() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert_eq!(codes[0].len(), 11);
+        assert_eq!(
+            recovery_code_hash(&codes[0]),
+            recovery_code_hash(&codes[0].to_uppercase().replace('-', " "))
+        );
+    }
+}
@@ -1,13 +1,18 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
-use tracing::{debug, info};
+use std::time::Duration;
+use tracing::{debug, error, info};
 
 use crate::models::{
-    CreatePostVersion, Post, PostVersion, VersionDiff, VersionFilters, VersionHistory,
-    VersionSummary,
+    ChangelogEntry, CreatePostVersion, DiffChangeKind, DiffStats, ParagraphDiff, Post,
+    PostChangelog, PostVersion, VersionCleanupReport, VersionDiff, VersionFilters, VersionHistory,
+    VersionImportReport, VersionRetentionPolicy, VersionSummary, WordDiffSegment,
 };
 use crate::services::{DatabaseService, MarkdownService};
 
+/// Longest paragraph, in tokens, that gets a word-level diff
+const MAX_WORD_DIFF_TOKENS: usize = 500;
+
 /// Service for managing post version history
 #[derive(Clone)]
 pub struct VersionService {
@@ -90,6 +95,35 @@ impl VersionService {
         })
     }
 
+    /// Build the reader-facing changelog of a post from its version change summaries
+    pub async fn changelog(&self, post: &Post) -> Result<PostChangelog> {
+        debug!("Building changelog for post {}", post.id);
+
+        let filters = VersionFilters {
+            post_id: Some(post.id),
+            ..Default::default()
+        };
+        let versions = self.database.list_post_versions(filters).await?;
+
+        let entries = versions
+            .into_iter()
+            .filter_map(|v| {
+                let summary = v.change_summary?.trim().to_string();
+                (!summary.is_empty()).then_some(ChangelogEntry {
+                    version: v.version,
+                    summary,
+                    updated_at: v.created_at,
+                })
+            })
+            .collect();
+
+        Ok(PostChangelog {
+            post_slug: post.slug.clone(),
+            last_updated: post.updated_at,
+            entries,
+        })
+    }
+
     /// Get a specific version of a post
     pub async fn get_version(
         &self,
@@ -101,6 +135,51 @@ impl VersionService {
         self.database.get_post_version(post_id, version).await
     }
 
+    /// Version history of a post as JSON Lines, oldest first
+    pub async fn export_versions(&self, post_id: uuid::Uuid) -> Result<String> {
+        let mut versions = self
+            .database
+            .list_post_versions(VersionFilters {
+                post_id: Some(post_id),
+                ..Default::default()
+            })
+            .await?;
+        versions.reverse();
+        versions_to_jsonl(&versions)
+    }
+
+    /// Store exported versions under a post, skipping versions it already has
+    ///
+    /// The versions keep their numbers and timestamps but are attached to
+    /// `post_id`, so history survives moving to an instance where the post
+    /// has a new ID. The post's own version number moves past the imported
+    /// ones so later snapshots don't collide with them.
+    pub async fn import_versions(
+        &self,
+        post_id: uuid::Uuid,
+        versions: &[PostVersion],
+    ) -> Result<VersionImportReport> {
+        let mut report = VersionImportReport::default();
+        for version in versions {
+            if self.database.import_post_version(post_id, version).await? {
+                report.imported += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+
+        let next_version = versions.iter().map(|v| v.version + 1).max().unwrap_or(0);
+        report.post_version = self
+            .database
+            .advance_post_version(post_id, next_version)
+            .await?;
+        info!(
+            "Imported {} versions into post {} ({} already present)",
+            report.imported, post_id, report.skipped
+        );
+        Ok(report)
+    }
+
     /// Compare two versions of a post
     pub async fn compare_versions(
         &self,
@@ -134,6 +213,8 @@ impl VersionService {
 
         let content_diff =
             self.generate_text_diff(&version_from_data.content, &version_to_data.content);
+        let (stats, word_diff) =
+            content_changes(&version_from_data.content, &version_to_data.content);
 
         // Generate metadata diff (simplified)
         let metadata_diff = if version_from_data.metadata != version_to_data.metadata {
@@ -154,9 +235,34 @@ impl VersionService {
             metadata_diff,
             created_at_from: version_from_data.created_at,
             created_at_to: version_to_data.created_at,
+            stats,
+            word_diff,
         })
     }
 
+    /// Compare a stored version with the current state of its post
+    pub fn diff_against_post(&self, version: &PostVersion, post: &Post) -> VersionDiff {
+        let title_diff = if version.title != post.title {
+            Some(self.generate_text_diff(&version.title, &post.title))
+        } else {
+            None
+        };
+        let (stats, word_diff) = content_changes(&version.content, &post.content);
+
+        VersionDiff {
+            post_id: post.id,
+            version_from: version.version,
+            version_to: post.version,
+            title_diff,
+            content_diff: self.generate_text_diff(&version.content, &post.content),
+            metadata_diff: None,
+            created_at_from: version.created_at,
+            created_at_to: post.updated_at,
+            stats,
+            word_diff,
+        }
+    }
+
     /// Restore a post to a previous version
     ///
     /// Note: This operation involves multiple database writes and should ideally be wrapped
@@ -260,84 +366,14 @@ impl VersionService {
             return "No changes".to_string();
         }
 
-        let from_lines: Vec<&str> = from.lines().collect();
-        let to_lines: Vec<&str> = to.lines().collect();
-
-        // Use a simple diff algorithm that tracks common subsequences
-        let mut diff = Vec::new();
-        let mut i = 0; // index for from_lines
-        let mut j = 0; // index for to_lines
-
-        while i < from_lines.len() || j < to_lines.len() {
-            match (from_lines.get(i), to_lines.get(j)) {
-                (Some(from_line), Some(to_line)) => {
-                    if from_line == to_line {
-                        // Lines are identical - show context
-                        diff.push(format!("  {}", from_line));
-                        i += 1;
-                        j += 1;
-                    } else {
-                        // Lines differ - look ahead to see if we can find a match
-                        let mut found_match = false;
-
-                        // Look for the to_line in the next few from_lines (deletion case)
-                        for look_ahead in (i + 1)..=(i + 3).min(from_lines.len()) {
-                            if from_lines.get(look_ahead) == Some(to_line) {
-                                // Found the to_line later in from_lines, so lines were deleted
-                                for del_idx in i..look_ahead {
-                                    if let Some(del_line) = from_lines.get(del_idx) {
-                                        diff.push(format!("- {}", del_line));
-                                    }
-                                }
-                                diff.push(format!("  {}", to_line));
-                                i = look_ahead + 1;
-                                j += 1;
-                                found_match = true;
-                                break;
-                            }
-                        }
-
-                        if !found_match {
-                            // Look for the from_line in the next few to_lines (insertion case)
-                            for look_ahead in (j + 1)..=(j + 3).min(to_lines.len()) {
-                                if to_lines.get(look_ahead) == Some(from_line) {
-                                    // Found the from_line later in to_lines, so lines were inserted
-                                    for ins_idx in j..look_ahead {
-                                        if let Some(ins_line) = to_lines.get(ins_idx) {
-                                            diff.push(format!("+ {}", ins_line));
-                                        }
-                                    }
-                                    diff.push(format!("  {}", from_line));
-                                    i += 1;
-                                    j = look_ahead + 1;
-                                    found_match = true;
-                                    break;
-                                }
-                            }
-                        }
-
-                        if !found_match {
-                            // No match found, treat as substitution
-                            diff.push(format!("- {}", from_line));
-                            diff.push(format!("+ {}", to_line));
-                            i += 1;
-                            j += 1;
-                        }
-                    }
-                }
-                (Some(from_line), None) => {
-                    // Remaining lines in from (deletions)
-                    diff.push(format!("- {}", from_line));
-                    i += 1;
-                }
-                (None, Some(to_line)) => {
-                    // Remaining lines in to (insertions)
-                    diff.push(format!("+ {}", to_line));
-                    j += 1;
-                }
-                (None, None) => break,
-            }
-        }
+        let mut diff: Vec<String> = line_changes(from, to)
+            .into_iter()
+            .map(|change| match change {
+                LineChange::Unchanged(line) => format!("  {}", line),
+                LineChange::Removed(line) => format!("- {}", line),
+                LineChange::Added(line) => format!("+ {}", line),
+            })
+            .collect();
 
         if diff.is_empty() {
             "No changes".to_string()
@@ -409,7 +445,7 @@ impl VersionService {
 
         let deleted_count = self
             .database
-            .cleanup_old_versions(post_id, keep_versions)
+            .cleanup_old_versions(post_id, keep_versions, None)
             .await?;
 
         if deleted_count > 0 {
@@ -421,4 +457,368 @@ impl VersionService {
 
         Ok(deleted_count)
     }
+
+    /// Apply the retention policy to the versions of every post
+    pub async fn apply_retention(
+        &self,
+        policy: &VersionRetentionPolicy,
+    ) -> Result<VersionCleanupReport> {
+        let mut report = VersionCleanupReport::default();
+        if !policy.is_enabled() {
+            return Ok(report);
+        }
+
+        // Without a count rule only the age rule keeps versions, and vice versa
+        let keep_versions = policy.keep_versions.unwrap_or(0);
+        let keep_since = policy
+            .max_age_days
+            .map(|days| Utc::now() - chrono::Duration::days(days));
+
+        for post_id in self.database.list_versioned_post_ids().await? {
+            report.posts_checked += 1;
+            report.deleted_versions += self
+                .database
+                .cleanup_old_versions(post_id, keep_versions, keep_since)
+                .await?;
+        }
+
+        info!(
+            "Version cleanup removed {} versions across {} posts",
+            report.deleted_versions, report.posts_checked
+        );
+        Ok(report)
+    }
+
+    /// Apply the retention policy every `interval`
+    pub fn spawn_periodic(self, policy: VersionRetentionPolicy, interval: Duration) {
+        info!(
+            "Cleaning up post versions every {:?} ({:?})",
+            interval, policy
+        );
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.apply_retention(&policy).await {
+                    error!("Version cleanup failed: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Versions as JSON Lines, one version per line
+pub fn versions_to_jsonl(versions: &[PostVersion]) -> Result<String> {
+    let mut jsonl = String::new();
+    for version in versions {
+        jsonl.push_str(&serde_json::to_string(version)?);
+        jsonl.push('\n');
+    }
+    Ok(jsonl)
+}
+
+/// Versions from JSON Lines written by [`versions_to_jsonl`]; blank lines are skipped
+pub fn parse_versions_jsonl(jsonl: &str) -> Result<Vec<PostVersion>> {
+    jsonl
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid version on line {}", index + 1))
+        })
+        .collect()
+}
+
+/// One line of a line diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineChange<'a> {
+    Unchanged(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line diff that looks a few lines ahead to tell insertions and deletions
+/// from substitutions
+fn line_changes<'a>(from: &'a str, to: &'a str) -> Vec<LineChange<'a>> {
+    let from_lines: Vec<&str> = from.lines().collect();
+    let to_lines: Vec<&str> = to.lines().collect();
+
+    // Use a simple diff algorithm that tracks common subsequences
+    let mut diff = Vec::new();
+    let mut i = 0; // index for from_lines
+    let mut j = 0; // index for to_lines
+
+    while i < from_lines.len() || j < to_lines.len() {
+        match (from_lines.get(i), to_lines.get(j)) {
+            (Some(from_line), Some(to_line)) => {
+                if from_line == to_line {
+                    // Lines are identical - show context
+                    diff.push(LineChange::Unchanged(from_line));
+                    i += 1;
+                    j += 1;
+                } else {
+                    // Lines differ - look ahead to see if we can find a match
+                    let mut found_match = false;
+
+                    // Look for the to_line in the next few from_lines (deletion case)
+                    for look_ahead in (i + 1)..=(i + 3).min(from_lines.len()) {
+                        if from_lines.get(look_ahead) == Some(to_line) {
+                            // Found the to_line later in from_lines, so lines were deleted
+                            for del_idx in i..look_ahead {
+                                if let Some(del_line) = from_lines.get(del_idx) {
+                                    diff.push(LineChange::Removed(del_line));
+                                }
+                            }
+                            diff.push(LineChange::Unchanged(to_line));
+                            i = look_ahead + 1;
+                            j += 1;
+                            found_match = true;
+                            break;
+                        }
+                    }
+
+                    if !found_match {
+                        // Look for the from_line in the next few to_lines (insertion case)
+                        for look_ahead in (j + 1)..=(j + 3).min(to_lines.len()) {
+                            if to_lines.get(look_ahead) == Some(from_line) {
+                                // Found the from_line later in to_lines, so lines were inserted
+                                for ins_idx in j..look_ahead {
+                                    if let Some(ins_line) = to_lines.get(ins_idx) {
+                                        diff.push(LineChange::Added(ins_line));
+                                    }
+                                }
+                                diff.push(LineChange::Unchanged(from_line));
+                                i += 1;
+                                j = look_ahead + 1;
+                                found_match = true;
+                                break;
+                            }
+                        }
+                    }
+
+                    if !found_match {
+                        // No match found, treat as substitution
+                        diff.push(LineChange::Removed(from_line));
+                        diff.push(LineChange::Added(to_line));
+                        i += 1;
+                        j += 1;
+                    }
+                }
+            }
+            (Some(from_line), None) => {
+                // Remaining lines in from (deletions)
+                diff.push(LineChange::Removed(from_line));
+                i += 1;
+            }
+            (None, Some(to_line)) => {
+                // Remaining lines in to (insertions)
+                diff.push(LineChange::Added(to_line));
+                j += 1;
+            }
+            (None, None) => break,
+        }
+    }
+
+    diff
+}
+
+/// Change counts and word-level diffs of reworded prose paragraphs
+///
+/// A run of removed lines followed by added lines is paired up line by line;
+/// each pair of prose lines that still share a word is diffed word by word,
+/// and the word counts come from that diff. Other changed lines count all
+/// their words.
+fn content_changes(from: &str, to: &str) -> (DiffStats, Vec<ParagraphDiff>) {
+    let mut stats = DiffStats::default();
+    let mut paragraphs = Vec::new();
+    if from == to {
+        return (stats, paragraphs);
+    }
+
+    let changes = line_changes(from, to);
+    // Whether each side is inside a fenced code block
+    let mut from_fence = false;
+    let mut to_fence = false;
+    let mut to_line = 0;
+    let mut i = 0;
+    while i < changes.len() {
+        let mut removed = Vec::new();
+        while let Some(LineChange::Removed(line)) = changes.get(i) {
+            removed.push((*line, is_prose(line, &mut from_fence)));
+            i += 1;
+        }
+        let mut added = Vec::new();
+        while let Some(LineChange::Added(line)) = changes.get(i) {
+            to_line += 1;
+            added.push((*line, to_line, is_prose(line, &mut to_fence)));
+            i += 1;
+        }
+        if removed.is_empty() && added.is_empty() {
+            if let Some(LineChange::Unchanged(line)) = changes.get(i) {
+                is_prose(line, &mut from_fence);
+                is_prose(line, &mut to_fence);
+                to_line += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        stats.lines_removed += removed.len();
+        stats.lines_added += added.len();
+        for k in 0..removed.len().max(added.len()) {
+            let old = removed.get(k);
+            let new = added.get(k);
+            if let (Some(&(old, true)), Some(&(new, line, true))) = (old, new) {
+                if let Some(segments) = word_diff(old, new) {
+                    for segment in &segments {
+                        match segment.kind {
+                            DiffChangeKind::Added => {
+                                stats.words_added += count_words(&segment.text)
+                            }
+                            DiffChangeKind::Removed => {
+                                stats.words_removed += count_words(&segment.text)
+                            }
+                            DiffChangeKind::Unchanged => {}
+                        }
+                    }
+                    paragraphs.push(ParagraphDiff { line, segments });
+                    continue;
+                }
+            }
+            if let Some((old, _)) = old {
+                stats.words_removed += count_words(old);
+            }
+            if let Some((new, _, _)) = new {
+                stats.words_added += count_words(new);
+            }
+        }
+    }
+
+    (stats, paragraphs)
+}
+
+/// Whether a Markdown line is prose, toggling `in_fence` on code fences
+fn is_prose(line: &str, in_fence: &mut bool) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        *in_fence = !*in_fence;
+        return false;
+    }
+    !*in_fence
+        && !trimmed.is_empty()
+        && !line.starts_with("    ")
+        && !line.starts_with('\t')
+        && !trimmed.starts_with('|')
+        && !trimmed.starts_with('<')
+}
+
+/// Split text into words, runs of whitespace and single other characters
+///
+/// Scripts written without spaces, such as Japanese, come out one character
+/// per token so a changed particle doesn't mark the whole sentence.
+fn tokenize(text: &str) -> Vec<&str> {
+    // 0: whitespace, 1: word character, 2: anything else, one per token
+    fn class(c: char) -> u8 {
+        if c.is_whitespace() {
+            0
+        } else if (c.is_alphanumeric() || c == '_') && (c as u32) < 0x2E80 {
+            1
+        } else {
+            2
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        let kind = class(c);
+        if kind != 2 {
+            while let Some((i, next)) = chars.next_if(|&(_, next)| class(next) == kind) {
+                end = i + next.len_utf8();
+            }
+        }
+        tokens.push(&text[start..end]);
+    }
+    tokens
+}
+
+/// Tokens containing a letter or digit
+fn count_words(text: &str) -> usize {
+    tokenize(text)
+        .into_iter()
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Word-level diff of two lines by longest common subsequence
+///
+/// `None` when the lines share no word, or are too long to diff this way.
+fn word_diff(from: &str, to: &str) -> Option<Vec<WordDiffSegment>> {
+    let from_tokens = tokenize(from);
+    let to_tokens = tokenize(to);
+    let prefix = from_tokens
+        .iter()
+        .zip(&to_tokens)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = from_tokens[prefix..]
+        .iter()
+        .rev()
+        .zip(to_tokens[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &from_tokens[prefix..from_tokens.len() - suffix];
+    let b = &to_tokens[prefix..to_tokens.len() - suffix];
+    if a.len() > MAX_WORD_DIFF_TOKENS || b.len() > MAX_WORD_DIFF_TOKENS {
+        return None;
+    }
+
+    // lcs[i][j] is the common subsequence length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut segments: Vec<WordDiffSegment> = Vec::new();
+    let mut push = |kind: DiffChangeKind, token: &str| match segments.last_mut() {
+        Some(last) if last.kind == kind => last.text.push_str(token),
+        _ => segments.push(WordDiffSegment {
+            kind,
+            text: token.to_string(),
+        }),
+    };
+    for token in &from_tokens[..prefix] {
+        push(DiffChangeKind::Unchanged, token);
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            push(DiffChangeKind::Unchanged, a[i]);
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push(DiffChangeKind::Removed, a[i]);
+            i += 1;
+        } else {
+            push(DiffChangeKind::Added, b[j]);
+            j += 1;
+        }
+    }
+    for token in &from_tokens[from_tokens.len() - suffix..] {
+        push(DiffChangeKind::Unchanged, token);
+    }
+
+    let shares_word = segments
+        .iter()
+        .any(|s| s.kind == DiffChangeKind::Unchanged && count_words(&s.text) > 0);
+    shares_word.then_some(segments)
 }
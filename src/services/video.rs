@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+/// Metadata extracted from an uploaded video
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VideoMetadata {
+    pub duration_seconds: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// JPEG poster frame
+    pub poster: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    format: Option<FfprobeFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeStream {
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeFormat {
+    duration: Option<String>,
+}
+
+/// Whether video metadata extraction was compiled in (`ffmpeg` feature)
+pub fn is_supported() -> bool {
+    cfg!(feature = "ffmpeg")
+}
+
+/// Extract duration, dimensions and a poster frame with ffprobe/ffmpeg
+#[cfg(feature = "ffmpeg")]
+pub async fn extract_metadata(data: &[u8], extension: &str) -> Result<VideoMetadata> {
+    let path = std::env::temp_dir().join(format!(
+        "tobelog-video-{}.{}",
+        uuid::Uuid::new_v4(),
+        extension
+    ));
+    tokio::fs::write(&path, data)
+        .await
+        .context("Failed to write temporary video file")?;
+
+    let result = tokio::task::spawn_blocking({
+        let path = path.clone();
+        move || run_ffmpeg(&path)
+    })
+    .await
+    .map_err(|e| anyhow!("Video processing task failed: {}", e))
+    .and_then(|result| result);
+
+    let _ = tokio::fs::remove_file(&path).await;
+    result
+}
+
+/// Without the `ffmpeg` feature no metadata can be extracted
+#[cfg(not(feature = "ffmpeg"))]
+pub async fn extract_metadata(_data: &[u8], _extension: &str) -> Result<VideoMetadata> {
+    Err(anyhow!(
+        "Video metadata extraction requires the `ffmpeg` feature"
+    ))
+}
+
+#[cfg(feature = "ffmpeg")]
+fn run_ffmpeg(path: &std::path::Path) -> Result<VideoMetadata> {
+    use std::process::Command;
+
+    let probe = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=width,height:format=duration",
+            "-of",
+            "json",
+        ])
+        .arg(path)
+        .output()
+        .context("Failed to run ffprobe")?;
+    if !probe.status.success() {
+        return Err(anyhow!(
+            "ffprobe failed: {}",
+            String::from_utf8_lossy(&probe.stderr).trim()
+        ));
+    }
+    let mut metadata = parse_ffprobe_output(&String::from_utf8_lossy(&probe.stdout))?;
+
+    // Grab a frame a little way in, falling back to the first frame for short clips
+    let seek = if metadata.duration_seconds.unwrap_or(0.0) > 2.0 {
+        "1"
+    } else {
+        "0"
+    };
+    let frame = Command::new("ffmpeg")
+        .args(["-v", "error", "-ss", seek, "-i"])
+        .arg(path)
+        .args(["-frames:v", "1", "-f", "image2", "-c:v", "mjpeg", "pipe:1"])
+        .output()
+        .context("Failed to run ffmpeg")?;
+    if frame.status.success() && !frame.stdout.is_empty() {
+        metadata.poster = Some(frame.stdout);
+    }
+
+    Ok(metadata)
+}
+
+/// Parse `ffprobe -of json` output for the first video stream
+#[cfg_attr(not(feature = "ffmpeg"), allow(dead_code))]
+fn parse_ffprobe_output(output: &str) -> Result<VideoMetadata> {
+    let probe: FfprobeOutput = serde_json::from_str(output).context("Invalid ffprobe output")?;
+    let stream = probe.streams.first();
+
+    Ok(VideoMetadata {
+        duration_seconds: probe
+            .format
+            .and_then(|format| format.duration)
+            .and_then(|duration| duration.parse::<f64>().ok()),
+        width: stream.and_then(|s| s.width),
+        height: stream.and_then(|s| s.height),
+        poster: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ffprobe_output() {
+        let output = r#"{
+            "programs": [],
+            "streams": [{ "width": 1920, "height": 1080 }],
+            "format": { "duration": "12.345000" }
+        }"#;
+
+        assert_eq!(
+            parse_ffprobe_output(output).unwrap(),
+            VideoMetadata {
+                duration_seconds: Some(12.345),
+                width: Some(1920),
+                height: Some(1080),
+                poster: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ffprobe_output_without_video_stream() {
+        let metadata = parse_ffprobe_output(r#"{ "format": {} }"#).unwrap();
+        assert_eq!(metadata, VideoMetadata::default());
+        assert!(parse_ffprobe_output("not json").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+use std::time::Duration;
+use tobelog::services::totp::totp_code;
+use tobelog::services::{
+    AdminSessionService, ClientInfo, SessionCheck, TemplateService, TotpError, TotpService,
+};
+
+use super::test_database;
+
+fn cookie_headers(set_cookie: &str) -> HeaderMap {
+    let pair = set_cookie.split(';').next().unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::COOKIE, HeaderValue::from_str(pair).unwrap());
+    headers
+}
+
+fn client(scheme: &str) -> ClientInfo {
+    ClientInfo {
+        ip: [203, 0, 113, 7].into(),
+        scheme: scheme.to_string(),
+        host: Some("blog.example.com".to_string()),
+    }
+}
+
+fn token_of(set_cookie: &str) -> &str {
+    set_cookie
+        .split(';')
+        .next()
+        .and_then(|pair| pair.split_once('='))
+        .map(|(_, token)| token)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_ログインセッションの発行と更新と破棄() {
+    let (_temp_dir, database) = test_database().await;
+    let hour = Duration::from_secs(3600);
+
+    let https = client("https");
+    let sessions = AdminSessionService::new(database.clone(), hour, hour);
+    let token = sessions.create().await.expect("Failed to create session");
+    let cookie = sessions.cookie(&token, &https);
+    assert!(cookie.contains("HttpOnly"));
+    assert!(cookie.contains("SameSite=Strict"));
+    assert!(cookie.contains("Secure"));
+    // HTTP のクライアントには Secure を付けない
+    assert!(!sessions.cookie(&token, &client("http")).contains("Secure"));
+
+    let headers = cookie_headers(&cookie);
+    assert_eq!(sessions.check(&headers).await.unwrap(), SessionCheck::Valid);
+    assert_eq!(
+        sessions.check(&HeaderMap::new()).await.unwrap(),
+        SessionCheck::Invalid
+    );
+    assert_eq!(
+        sessions
+            .check(&cookie_headers("tobelog_admin_session=forged"))
+            .await
+            .unwrap(),
+        SessionCheck::Invalid
+    );
+
+    // トークンはハッシュだけが保存される
+    let stored: Vec<String> = sqlx::query_scalar("SELECT id FROM admin_sessions")
+        .fetch_all(database.pool())
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_ne!(stored[0], token);
+
+    // 更新間隔を過ぎたセッションは新しいトークンに置き換わる
+    let rotating = AdminSessionService::new(database.clone(), hour, Duration::ZERO);
+    let SessionCheck::Rotated(new_token) = rotating.check(&headers).await.unwrap() else {
+        panic!("Session should have been rotated");
+    };
+    assert_ne!(new_token, token);
+    assert_eq!(
+        sessions.check(&headers).await.unwrap(),
+        SessionCheck::Invalid
+    );
+    let new_headers = cookie_headers(&sessions.cookie(&new_token, &https));
+    assert_eq!(
+        sessions.check(&new_headers).await.unwrap(),
+        SessionCheck::Valid
+    );
+
+    // ログアウトでセッションは無効になる
+    sessions.revoke(&new_headers).await.unwrap();
+    assert_eq!(
+        sessions.check(&new_headers).await.unwrap(),
+        SessionCheck::Invalid
+    );
+    let cleared = sessions.clear_cookie(&https);
+    assert!(cleared.contains("Max-Age=0"));
+    assert!(cleared.contains("Secure"));
+
+    // 有効期限切れのセッションは使えない
+    let expired = AdminSessionService::new(database.clone(), Duration::ZERO, hour);
+    let token = expired.create().await.unwrap();
+    assert_eq!(token_of(&expired.cookie(&token, &https)), token);
+    assert_eq!(
+        expired
+            .check(&cookie_headers(&expired.cookie(&token, &https)))
+            .await
+            .unwrap(),
+        SessionCheck::Invalid
+    );
+}
+
+#[test]
+fn test_ログイン画面に戻り先とエラーを表示する() {
+    let templates = TemplateService::new().expect("Failed to load templates");
+    let html = templates
+        .render(
+            "admin/login.html",
+            &serde_json::json!({
+                "page_title": "Log in",
+                "next": "/admin/posts",
+                "error": "Invalid API key",
+            }),
+        )
+        .expect("Failed to render login page");
+
+    // 戻り先はエスケープされてフォームに残る
+    assert!(html.contains(r#"name="next" value="&#x2F;admin&#x2F;posts""#));
+    assert!(html.contains("Invalid API key"));
+    assert!(html.contains(r#"action="/admin/login""#));
+}
+
+#[tokio::test]
+async fn test_二要素認証の登録とログインコードの検証() {
+    let (_temp_dir, database) = test_database().await;
+    let totp = TotpService::new(database.clone(), "blog.example.com");
+    assert!(!totp.is_enabled().await.unwrap());
+
+    let enrollment = totp.begin_enrollment().await.unwrap().unwrap();
+    assert!(enrollment
+        .provisioning_uri
+        .starts_with("otpauth://totp/blog.example.com:admin?secret="));
+    assert!(enrollment.provisioning_uri.contains(&enrollment.secret));
+
+    // 確認コードが正しくなければ有効にならない
+    let now = chrono::Utc::now().timestamp();
+    let code = totp_code(&enrollment.secret, now).unwrap();
+    let wrong = if code == "000000" { "111111" } else { "000000" };
+    assert_eq!(
+        totp.confirm_enrollment(wrong).await.unwrap(),
+        Err(TotpError::InvalidCode)
+    );
+    assert!(!totp.is_enabled().await.unwrap());
+
+    let recovery_codes = totp.confirm_enrollment(&code).await.unwrap().unwrap();
+    assert_eq!(recovery_codes.len(), 10);
+    assert!(totp.is_enabled().await.unwrap());
+    assert_eq!(
+        totp.begin_enrollment().await.unwrap().unwrap_err(),
+        TotpError::InvalidState
+    );
+
+    // 一度使ったコードは再利用できない
+    assert!(!totp.verify(&code).await.unwrap());
+    assert!(!totp.verify(wrong).await.unwrap());
+
+    // リカバリーコードはハッシュだけが保存され、一度だけ使える
+    let stored: Vec<String> = sqlx::query_scalar("SELECT code_hash FROM admin_recovery_codes")
+        .fetch_all(database.pool())
+        .await
+        .unwrap();
+    assert!(!stored.contains(&recovery_codes[0]));
+    assert!(totp
+        .verify(&recovery_codes[0].to_uppercase())
+        .await
+        .unwrap());
+    assert!(!totp.verify(&recovery_codes[0]).await.unwrap());
+    assert_eq!(totp.status().await.unwrap().recovery_codes_remaining, 9);
+
+    assert!(totp.disable(&recovery_codes[1]).await.unwrap().is_ok());
+    assert!(!totp.is_enabled().await.unwrap());
+    assert_eq!(totp.status().await.unwrap().recovery_codes_remaining, 0);
+}
+
+#[test]
+fn test_二要素認証が有効ならログイン画面でコードを求める() {
+    let templates = TemplateService::new().expect("Failed to load templates");
+    let render = |two_factor: bool| {
+        templates
+            .render(
+                "admin/login.html",
+                &serde_json::json!({
+                    "page_title": "Log in",
+                    "next": "/admin",
+                    "error": null,
+                    "two_factor": two_factor,
+                }),
+            )
+            .expect("Failed to render login page")
+    };
+
+    assert!(render(true).contains(r#"autocomplete="one-time-code""#));
+    assert!(!render(false).contains(r#"name="code""#));
+}
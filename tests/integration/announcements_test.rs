@@ -0,0 +1,81 @@
+use chrono::{Duration, Utc};
+use tobelog::models::{AnnouncementRequest, AnnouncementStyle};
+use tobelog::services::TemplateService;
+
+use super::test_database;
+
+fn announcement(message: &str) -> AnnouncementRequest {
+    AnnouncementRequest {
+        message: message.to_string(),
+        link: None,
+        style: AnnouncementStyle::Info,
+        starts_at: None,
+        ends_at: None,
+        dismissible: true,
+    }
+}
+
+#[tokio::test]
+async fn test_期間中のお知らせだけを全ページに表示する() {
+    let (_temp_dir, database) = test_database().await;
+    let now = Utc::now();
+
+    let current = database
+        .create_announcement(&AnnouncementRequest {
+            link: Some("/posts/2026/maintenance".to_string()),
+            style: AnnouncementStyle::Warning,
+            ends_at: Some(now + Duration::days(1)),
+            dismissible: false,
+            ..announcement("Maintenance tonight")
+        })
+        .await
+        .unwrap();
+    let upcoming = database
+        .create_announcement(&AnnouncementRequest {
+            starts_at: Some(now + Duration::days(7)),
+            ..announcement("Conference next week")
+        })
+        .await
+        .unwrap();
+    assert_eq!(current.style, AnnouncementStyle::Warning);
+    assert!(!current.dismissible);
+    assert_eq!(database.list_announcements().await.unwrap().len(), 2);
+
+    let templates = TemplateService::new().expect("Failed to load templates");
+    templates.set_announcements(database.list_announcements().await.unwrap());
+    assert_eq!(templates.active_announcements(), vec![current.clone()]);
+    assert!(upcoming.is_active(now + Duration::days(8)));
+    assert!(!current.is_active(now + Duration::days(8)));
+
+    // 更新はすべての項目を置き換える
+    let updated = database
+        .update_announcement(current.id, &announcement("Maintenance done"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.link, None);
+    assert_eq!(updated.ends_at, None);
+    assert!(database
+        .update_announcement(9999, &announcement("Missing"))
+        .await
+        .unwrap()
+        .is_none());
+
+    templates.set_announcements(database.list_announcements().await.unwrap());
+    let html = templates
+        .render(
+            "404.html",
+            &serde_json::json!({
+                "site_title": "Tobelog",
+                "message": "Not found",
+                "path": "/missing",
+                "recent_posts": [],
+            }),
+        )
+        .expect("Failed to render page");
+    assert!(html.contains("Maintenance done"));
+    assert!(!html.contains("Conference next week"));
+
+    assert!(database.delete_announcement(current.id).await.unwrap());
+    assert!(!database.delete_announcement(current.id).await.unwrap());
+}
@@ -0,0 +1,111 @@
+use chrono::Utc;
+use tobelog::models::MediaFile;
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+fn dataset_media_file(original_filename: &str) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        filename: "dataset_abcd1234.csv".to_string(),
+        original_filename: original_filename.to_string(),
+        dropbox_path: "/BlogStorage/media/files/2024/07/dataset_abcd1234.csv".to_string(),
+        url: "/media/files/2024/07/dataset_abcd1234.csv".to_string(),
+        file_size: 2_048,
+        mime_type: "text/csv".to_string(),
+        width: None,
+        height: None,
+        uploaded_at: Utc::now(),
+        thumbnail_url: None,
+        alt_text: None,
+        caption: None,
+        content_hash: None,
+        duration_seconds: None,
+        alt_text_suggestion: None,
+        collection: None,
+        focal_point: None,
+    }
+}
+
+#[tokio::test]
+async fn test_添付ファイルを記事に紐付けてダウンロード数を数える() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database
+        .create_post(create_post("with-data"))
+        .await
+        .expect("Failed to create post");
+    let media = dataset_media_file("売上データ.csv");
+    database
+        .create_media_file(&media)
+        .await
+        .expect("Failed to create media file");
+
+    let attachment = database
+        .add_post_attachment(post.id, media.id)
+        .await
+        .expect("Failed to attach file");
+    assert_eq!(attachment.filename, "売上データ.csv");
+    assert_eq!(attachment.mime_type, "text/csv");
+    assert_eq!(attachment.file_size, 2_048);
+    assert_eq!(
+        attachment.download_url,
+        format!("/attachments/{}", attachment.id)
+    );
+    assert_eq!(attachment.download_count, 0);
+
+    // 同じファイルを二度添付しても一件のまま
+    let again = database
+        .add_post_attachment(post.id, media.id)
+        .await
+        .expect("Failed to attach file");
+    assert_eq!(again.id, attachment.id);
+    assert_eq!(
+        database.list_post_attachments(post.id).await.unwrap().len(),
+        1
+    );
+
+    // 添付は記事での使用として数えられる
+    assert_eq!(
+        database.get_post_slugs_using_media(media.id).await.unwrap(),
+        vec!["with-data".to_string()]
+    );
+    let usage = database.get_media_usage_counts(&[media.id]).await.unwrap();
+    assert_eq!(usage.get(&media.id), Some(&1));
+
+    for _ in 0..2 {
+        database
+            .record_attachment_download(attachment.id)
+            .await
+            .expect("Failed to count download");
+    }
+    let stored = database
+        .get_post_attachment(attachment.id)
+        .await
+        .unwrap()
+        .expect("Attachment should exist");
+    assert_eq!(stored.download_count, 2);
+
+    // 日本語のファイル名は RFC 5987 形式で渡し、ASCII の代替名も付ける
+    assert_eq!(
+        stored.content_disposition(),
+        "attachment; filename=\"_____.csv\"; \
+         filename*=UTF-8''%E5%A3%B2%E4%B8%8A%E3%83%87%E3%83%BC%E3%82%BF.csv"
+    );
+
+    // 別の記事の ID では外せない
+    assert!(!database
+        .remove_post_attachment(Uuid::new_v4(), attachment.id)
+        .await
+        .unwrap());
+    assert!(database
+        .remove_post_attachment(post.id, attachment.id)
+        .await
+        .unwrap());
+    assert!(database
+        .list_post_attachments(post.id)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(database.get_media_file(media.id).await.unwrap().is_some());
+}
@@ -0,0 +1,120 @@
+use tobelog::models::{CategoryIndexEntry, CreatePost, PostVisibility, TagStat};
+use tobelog::services::template::{tag_cloud, CategoryIndexContext, TagIndexContext};
+use tobelog::services::TemplateService;
+
+use super::{create_post, test_database};
+
+fn categorized(slug: &str, category: &str, tags: &[&str], published: bool) -> CreatePost {
+    CreatePost {
+        category: Some(category.to_string()),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        published,
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_公開記事のカテゴリとタグを説明付きで集計する() {
+    let (_temp_dir, database) = test_database().await;
+
+    for post in [
+        categorized("tech-1", "tech", &["rust", "web"], true),
+        categorized("tech-2", "tech", &["rust"], true),
+        categorized("tech-draft", "tech", &["rust"], false),
+        categorized("life-1", "life", &["diary"], true),
+    ] {
+        database
+            .create_post(post)
+            .await
+            .expect("Failed to create post");
+    }
+    let private = database
+        .create_post(categorized("secret", "secret", &["rust"], true))
+        .await
+        .unwrap();
+    database
+        .set_post_visibility(private.id, PostVisibility::Private)
+        .await
+        .unwrap();
+    database
+        .upsert_category_description("tech", "技術的な記事")
+        .await
+        .unwrap();
+
+    let public = [PostVisibility::Public];
+    let categories = database
+        .list_category_index(Some(true), Some(&public))
+        .await
+        .unwrap();
+    assert_eq!(
+        categories,
+        vec![
+            CategoryIndexEntry {
+                name: "tech".to_string(),
+                count: 2,
+                description: Some("技術的な記事".to_string()),
+            },
+            CategoryIndexEntry {
+                name: "life".to_string(),
+                count: 1,
+                description: None,
+            },
+        ],
+        "下書きと非公開記事は数えないはずです"
+    );
+
+    let tags = database
+        .list_tag_counts(Some(true), Some(&public))
+        .await
+        .unwrap();
+    let counts: Vec<(&str, i64)> = tags.iter().map(|t| (t.name.as_str(), t.count)).collect();
+    assert_eq!(counts, vec![("diary", 1), ("rust", 2), ("web", 1)]);
+
+    // ステージング表示ではすべての記事を数える
+    let all = database.list_tag_counts(None, None).await.unwrap();
+    assert_eq!(all.iter().find(|t| t.name == "rust").unwrap().count, 4);
+
+    assert!(database.delete_category_description("tech").await.unwrap());
+    assert!(!database.delete_category_description("tech").await.unwrap());
+    assert!(database
+        .list_category_descriptions()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_タグ一覧とカテゴリ一覧を表示する() {
+    let templates = TemplateService::new_with_theme("default").expect("Failed to load templates");
+
+    let tags = TagIndexContext {
+        site_title: "Tobelog".to_string(),
+        site_description: String::new(),
+        tags: tag_cloud(vec![
+            TagStat {
+                name: "rare".to_string(),
+                count: 1,
+            },
+            TagStat {
+                name: "rust".to_string(),
+                count: 20,
+            },
+        ]),
+    };
+    let html = templates.render("tags.html", &tags).unwrap();
+    assert!(html.contains("tag-weight-5"));
+    assert!(html.contains("/tag/rare"));
+
+    let categories = CategoryIndexContext {
+        site_title: "Tobelog".to_string(),
+        site_description: String::new(),
+        categories: vec![CategoryIndexEntry {
+            name: "tech".to_string(),
+            count: 2,
+            description: Some("技術的な記事".to_string()),
+        }],
+    };
+    let html = templates.render("categories.html", &categories).unwrap();
+    assert!(html.contains("/category/tech"));
+    assert!(html.contains("技術的な記事"));
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use tobelog::models::{CategoryStyle, UpdateThemeRequest};
+use tobelog::services::storage::LocalStorage;
+use tobelog::services::ThemeService;
+
+use super::test_database;
+
+fn update_styles(styles: Vec<CategoryStyle>) -> UpdateThemeRequest {
+    UpdateThemeRequest {
+        display_name: None,
+        description: None,
+        primary_color: None,
+        secondary_color: None,
+        background_color: None,
+        text_color: None,
+        accent_color: None,
+        font_family: None,
+        heading_font: None,
+        font_size_base: None,
+        layout: None,
+        dark_mode_enabled: None,
+        custom_css: None,
+        header_style: None,
+        footer_style: None,
+        category_styles: Some(styles),
+    }
+}
+
+#[tokio::test]
+async fn test_カテゴリごとのアクセントをテーマのcssに出力する() {
+    let (temp_dir, database) = test_database().await;
+    let themes = ThemeService::new(
+        database,
+        Arc::new(LocalStorage::new(temp_dir.path().join("storage"))),
+    );
+    let theme = themes.get_active_theme().await.unwrap();
+    assert!(theme.category_styles.is_empty());
+
+    let rust = CategoryStyle {
+        category: "Rust".to_string(),
+        accent_color: "#DEA584".to_string(),
+        hero_image: Some("/media/images/rust.jpg".to_string()),
+    };
+    let updated = themes
+        .update_theme(&theme.name, update_styles(vec![rust.clone()]))
+        .await
+        .unwrap();
+    assert_eq!(updated.category_styles, vec![rust.clone()]);
+
+    // 他の項目だけを更新してもカテゴリのスタイルは残る
+    let mut request = update_styles(Vec::new());
+    request.category_styles = None;
+    request.display_name = Some("Renamed".to_string());
+    themes.update_theme(&theme.name, request).await.unwrap();
+    let stored = themes.get_theme(&theme.name).await.unwrap().unwrap();
+    assert_eq!(stored.category_styles, vec![rust]);
+
+    let css = themes.generate_active_theme_css().await.unwrap();
+    assert!(css.contains("[data-category=\"Rust\"] {\n  --category-accent: #DEA584;"));
+    assert!(css.contains("url(\"/media/images/rust.jpg\")"));
+    assert!(css.contains(".category-hero:is([data-category=\"Rust\"])"));
+
+    // CSSを壊す色は保存しない
+    let invalid = CategoryStyle {
+        category: "Go".to_string(),
+        accent_color: "red; } body { display: none".to_string(),
+        hero_image: None,
+    };
+    let error = themes
+        .update_theme(&theme.name, update_styles(vec![invalid]))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("Invalid category style"));
+    let stored = themes.get_theme(&theme.name).await.unwrap().unwrap();
+    assert_eq!(stored.category_styles.len(), 1);
+}
@@ -0,0 +1,45 @@
+use tobelog::models::{CreatePost, Locale, PaletteEntryKind, PostFilters};
+use tobelog::services::command_palette::{post_entry, search, static_entries};
+
+use super::{create_post, test_database};
+
+fn titled(slug: &str, title: &str, published: bool) -> CreatePost {
+    CreatePost {
+        title: title.to_string(),
+        category: Some("rust".to_string()),
+        published,
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_コマンドパレットが記事と操作をあいまい検索する() {
+    let (_temp_dir, database) = test_database().await;
+
+    for post in [
+        titled("async-rust", "Async Rust in Practice", true),
+        titled("draft-notes", "Tokio Notes", false),
+    ] {
+        database.create_post(post).await.unwrap();
+    }
+    let posts = database.list_posts(PostFilters::default()).await.unwrap();
+
+    let entries: Vec<_> = static_entries(Locale::En)
+        .into_iter()
+        .chain(posts.iter().map(|post| post_entry(post, Locale::En)))
+        .collect();
+
+    let results = search(entries.clone(), "asr");
+    assert_eq!(results[0].id, "post:async-rust");
+    assert_eq!(results[0].url, "/admin/edit/async-rust");
+    assert_eq!(results[0].subtitle.as_deref(), Some("Published · rust"));
+
+    // スラッグでも見つかり、下書きも対象になる
+    let results = search(entries.clone(), "draft-notes");
+    assert_eq!(results[0].title, "Tokio Notes");
+
+    let results = search(entries, "sync");
+    assert_eq!(results[0].kind, PaletteEntryKind::Action);
+    assert_eq!(results[0].method, "POST");
+    assert_eq!(results[0].url, "/api/sync/dropbox");
+}
@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::tempdir;
+use tobelog::services::ConfigReloader;
+
+#[test]
+fn test_envファイルの変更を再読み込みしてフックに渡す() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let env_file = temp_dir.path().join(".env");
+    std::fs::write(
+        &env_file,
+        "TOBELOG_RELOAD_TEST_VALUE=first\nSLOW_LOG_SIZE=17\n",
+    )
+    .unwrap();
+
+    let reloader = ConfigReloader::new(Some(env_file.clone()), HashSet::new());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let slow_log_size = Arc::new(AtomicUsize::new(0));
+    reloader.on_reload({
+        let calls = calls.clone();
+        let slow_log_size = slow_log_size.clone();
+        move |config| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            slow_log_size.store(config.slow_log_size, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    let response = reloader.reload().expect("Failed to reload");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(response.applied.is_empty());
+    assert_eq!(
+        response.restart_required,
+        ["TOBELOG_RELOAD_TEST_VALUE", "SLOW_LOG_SIZE"]
+    );
+    assert_eq!(slow_log_size.load(Ordering::SeqCst), 17);
+    // The values reach the config without touching the process environment
+    assert!(std::env::var("TOBELOG_RELOAD_TEST_VALUE").is_err());
+
+    // Unchanged values are not reported again
+    let response = reloader.reload().expect("Failed to reload");
+    assert!(response.restart_required.is_empty());
+
+    // Variables removed from `.env` keep their reloaded value
+    std::fs::write(&env_file, "TOBELOG_RELOAD_TEST_VALUE=second\n").unwrap();
+    let response = reloader.reload().expect("Failed to reload");
+    assert_eq!(response.restart_required, ["TOBELOG_RELOAD_TEST_VALUE"]);
+    assert_eq!(slow_log_size.load(Ordering::SeqCst), 17);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_フックが失敗すると前の設定を保つ() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let env_file = temp_dir.path().join(".env");
+    std::fs::write(&env_file, "SLOW_LOG_SIZE=0\n").unwrap();
+
+    let reloader = ConfigReloader::new(Some(env_file.clone()), HashSet::new());
+    reloader.on_reload(|config| {
+        if config.slow_log_size == 0 {
+            return Err(anyhow::anyhow!("invalid setting"));
+        }
+        Ok(())
+    });
+
+    assert!(reloader.reload().is_err());
+    // The rejected value was not kept, so it is still reported as changed
+    std::fs::write(&env_file, "SLOW_LOG_SIZE=5\nTOBELOG_RELOAD_TEST_OTHER=x\n").unwrap();
+    let response = reloader.reload().expect("Failed to reload");
+    assert_eq!(
+        response.restart_required,
+        ["SLOW_LOG_SIZE", "TOBELOG_RELOAD_TEST_OTHER"]
+    );
+}
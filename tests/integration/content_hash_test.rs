@@ -0,0 +1,59 @@
+use tempfile::tempdir;
+use tobelog::models::{content_hash, UpdatePost};
+use tobelog::services::DatabaseService;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_メタデータだけの更新ではコンテンツハッシュが変わらない() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database.create_post(create_post("hashed")).await.unwrap();
+    let stored = database.get_post_by_slug("hashed").await.unwrap().unwrap();
+    assert_eq!(stored.content_hash, content_hash("Post hashed", "Content"));
+
+    let updated = database
+        .update_post(
+            post.id,
+            UpdatePost {
+                featured: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(updated.updated_at > stored.updated_at);
+    assert_eq!(updated.content_hash, stored.content_hash);
+
+    database
+        .update_post(
+            post.id,
+            UpdatePost {
+                content: Some("New body".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let stored = database.get_post_by_slug("hashed").await.unwrap().unwrap();
+    assert_eq!(stored.content_hash, content_hash("Post hashed", "New body"));
+}
+
+#[tokio::test]
+async fn test_既存の記事のコンテンツハッシュが補完される() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("content_hash_backfill.db");
+    let database_url = format!("sqlite:{}", db_path.to_str().unwrap());
+    let database = DatabaseService::new(&database_url).await.unwrap();
+    database.create_post(create_post("hashed")).await.unwrap();
+    sqlx::query("UPDATE posts SET content_hash = NULL")
+        .execute(database.pool())
+        .await
+        .unwrap();
+    drop(database);
+
+    let database = DatabaseService::new(&database_url).await.unwrap();
+    let post = database.get_post_by_slug("hashed").await.unwrap().unwrap();
+    assert_eq!(post.content_hash, content_hash("Post hashed", "Content"));
+}
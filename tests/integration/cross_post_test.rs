@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use tobelog::models::{CreatePost, CrossPostProvider, CrossPostStatus};
+use tobelog::services::cross_post::{CrossPostAdapter, CrossPostArticle, RemoteArticle};
+use tobelog::services::CrossPostService;
+
+use super::{create_post, test_database};
+
+/// Markdown and remote ID of each publish call
+type PublishCalls = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+/// Adapter recording what it was asked to publish
+struct FakeAdapter {
+    provider: CrossPostProvider,
+    fail: bool,
+    calls: PublishCalls,
+}
+
+#[async_trait]
+impl CrossPostAdapter for FakeAdapter {
+    fn provider(&self) -> CrossPostProvider {
+        self.provider
+    }
+
+    async fn publish(
+        &self,
+        article: &CrossPostArticle,
+        remote_id: Option<&str>,
+    ) -> Result<RemoteArticle> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((article.markdown.clone(), remote_id.map(str::to_string)));
+        if self.fail {
+            return Err(anyhow!("401 Unauthorized"));
+        }
+        Ok(RemoteArticle {
+            id: "remote-1".to_string(),
+            url: Some("https://dev.to/me/remote-1".to_string()),
+        })
+    }
+}
+
+fn post_with_image(slug: &str, published: bool) -> CreatePost {
+    CreatePost {
+        content: "![図](/media/images/a.png)".to_string(),
+        html_content: String::new(),
+        tags: vec!["Rust".to_string()],
+        published,
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_公開記事を転載して転載先を記録し更新時に再利用する() {
+    let (_temp_dir, database) = test_database().await;
+
+    let devto_calls = Arc::new(Mutex::new(Vec::new()));
+    let qiita_calls = Arc::new(Mutex::new(Vec::new()));
+    let service = CrossPostService::with_adapters(
+        database.clone(),
+        "https://blog.example/".to_string(),
+        vec![
+            Arc::new(FakeAdapter {
+                provider: CrossPostProvider::DevTo,
+                fail: false,
+                calls: devto_calls.clone(),
+            }),
+            Arc::new(FakeAdapter {
+                provider: CrossPostProvider::Qiita,
+                fail: true,
+                calls: qiita_calls.clone(),
+            }),
+        ],
+    );
+
+    let post = database
+        .create_post(post_with_image("cross-posted", true))
+        .await
+        .unwrap();
+    let records = service.cross_post(&post).await.unwrap();
+    assert_eq!(records.len(), 2);
+
+    let devto = records
+        .iter()
+        .find(|r| r.provider == CrossPostProvider::DevTo)
+        .unwrap();
+    assert_eq!(devto.status, CrossPostStatus::Published);
+    assert_eq!(devto.remote_id.as_deref(), Some("remote-1"));
+    assert_eq!(
+        devto.remote_url.as_deref(),
+        Some("https://dev.to/me/remote-1")
+    );
+
+    let qiita = records
+        .iter()
+        .find(|r| r.provider == CrossPostProvider::Qiita)
+        .unwrap();
+    assert_eq!(qiita.status, CrossPostStatus::Failed);
+    assert!(qiita.error.as_deref().unwrap().contains("401"));
+
+    let (markdown, remote_id) = devto_calls.lock().unwrap()[0].clone();
+    assert!(remote_id.is_none());
+    assert!(
+        markdown.contains("](https://blog.example/media/images/a.png)"),
+        "相対リンクは絶対URLになるはずです"
+    );
+    assert!(markdown.contains(&format!("https://blog.example{}", post.get_url_path())));
+
+    // 2回目は既存の記事を更新する
+    service.cross_post(&post).await.unwrap();
+    assert_eq!(
+        devto_calls.lock().unwrap()[1].1.as_deref(),
+        Some("remote-1")
+    );
+    assert_eq!(qiita_calls.lock().unwrap()[1].1, None);
+}
+
+#[tokio::test]
+async fn test_非公開記事は転載しない() {
+    let (_temp_dir, database) = test_database().await;
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let service = CrossPostService::with_adapters(
+        database.clone(),
+        "https://blog.example".to_string(),
+        vec![Arc::new(FakeAdapter {
+            provider: CrossPostProvider::Zenn,
+            fail: false,
+            calls: calls.clone(),
+        })],
+    );
+
+    let draft = database
+        .create_post(post_with_image("draft", false))
+        .await
+        .unwrap();
+    assert!(service.cross_post(&draft).await.unwrap().is_empty());
+    assert!(calls.lock().unwrap().is_empty());
+}
@@ -143,4 +143,61 @@ async fn test_メインサーバー起動時のデータベース接続() {
     );
     
     // テンポラリディレクトリは自動的にクリーンアップされる
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_読み取り専用プールとwalモードで接続できる() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let test_db_path = temp_dir.path().join("pooled.db");
+    let database_url = format!("sqlite:{}", test_db_path.to_str().unwrap());
+
+    let pool_config = tobelog::services::DatabasePoolConfig {
+        max_connections: 2,
+        read_max_connections: 2,
+        ..Default::default()
+    };
+    let database = tobelog::services::DatabaseService::with_pool_config(&database_url, &pool_config)
+        .await
+        .expect("プール設定付きの接続に失敗しました");
+
+    database
+        .create_post(tobelog::models::CreatePost {
+            slug: "pooled".to_string(),
+            title: "Pooled search".to_string(),
+            content: "Replica friendly content".to_string(),
+            html_content: "<p>Replica friendly content</p>".to_string(),
+            excerpt: None,
+            category: None,
+            tags: vec![],
+            published: true,
+            featured: false,
+            author: None,
+            dropbox_path: "/test/pooled.md".to_string(),
+        })
+        .await
+        .expect("記事の作成に失敗しました");
+
+    // 書き込みは主プール、検索と統計は読み取りプールから行われる
+    let results = database
+        .search_posts("Replica", &tobelog::models::SearchFilters::default())
+        .await
+        .expect("読み取りプールでの検索に失敗しました");
+    assert_eq!(results.len(), 1);
+
+    let stats = database.get_post_stats().await.expect("統計の取得に失敗しました");
+    assert_eq!(stats.total_posts, 1);
+
+    // WALモードではジャーナルが別ファイルになる
+    assert!(temp_dir.path().join("pooled.db-wal").exists());
+}
+
+#[tokio::test]
+async fn test_不正なジャーナルモードはエラーになる() {
+    let pool_config = tobelog::services::DatabasePoolConfig {
+        journal_mode: "sideways".to_string(),
+        ..Default::default()
+    };
+    let result =
+        tobelog::services::DatabaseService::with_pool_config("sqlite::memory:", &pool_config).await;
+    assert!(result.is_err());
+}
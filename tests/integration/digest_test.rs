@@ -0,0 +1,45 @@
+use chrono::Utc;
+use tobelog::models::{CreatePost, SiteConfig};
+use tobelog::services::DigestService;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_月次レポートに今月の公開記事が入る() {
+    let (_temp_dir, database) = test_database().await;
+    database
+        .create_post(create_post("published"))
+        .await
+        .unwrap();
+    database
+        .create_post(CreatePost {
+            published: false,
+            ..create_post("draft")
+        })
+        .await
+        .unwrap();
+
+    let service = DigestService::new(database, "https://blog.example/".to_string(), None);
+    let month = Utc::now()
+        .with_timezone(&SiteConfig::default().timezone)
+        .format("%Y-%m")
+        .to_string();
+
+    let digest = service.compile(Some(&month)).await.unwrap();
+    assert_eq!(digest.month, month);
+    assert_eq!(digest.new_posts.len(), 1, "下書きは含まれないはずです");
+    assert_eq!(digest.new_posts[0].title, "Post published");
+    assert!(digest.new_posts[0]
+        .url
+        .starts_with("https://blog.example/posts/"));
+    assert_eq!(digest.total_published, 1);
+
+    let previous = service.compile(None).await.unwrap();
+    assert_ne!(previous.month, month);
+    assert!(previous.new_posts.is_empty());
+
+    assert!(
+        service.send(None).await.is_err(),
+        "Webhook未設定では送信できないはずです"
+    );
+}
@@ -0,0 +1,72 @@
+use chrono::Utc;
+use tobelog::models::{CreatePost, FeaturedImage, FocalPoint, MediaFile};
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_アイキャッチ画像を設定して取得できる() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database
+        .create_post(CreatePost {
+            title: "Featured".to_string(),
+            ..create_post("featured")
+        })
+        .await
+        .expect("Failed to create post");
+
+    let media = MediaFile {
+        id: Uuid::new_v4(),
+        filename: "photo_abcd1234.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        dropbox_path: "/BlogStorage/media/images/2024/07/photo_abcd1234.jpg".to_string(),
+        url: "/media/images/2024/07/photo_abcd1234.jpg".to_string(),
+        file_size: 1024,
+        mime_type: "image/jpeg".to_string(),
+        width: Some(1600),
+        height: Some(900),
+        uploaded_at: Utc::now(),
+        thumbnail_url: None,
+        alt_text: None,
+        caption: None,
+        content_hash: None,
+        duration_seconds: None,
+        alt_text_suggestion: None,
+        collection: None,
+        focal_point: None,
+    };
+    database
+        .create_media_file(&media)
+        .await
+        .expect("Failed to create media file");
+
+    let featured = FeaturedImage {
+        post_id: post.id,
+        media_id: media.id,
+        focal_point: FocalPoint { x: 30.0, y: 40.0 },
+        card_url: format!("/media/featured/{}/card_photo_abcd1234.jpg", post.id),
+        og_url: format!("/media/featured/{}/og_photo_abcd1234.jpg", post.id),
+        updated_at: Utc::now(),
+    };
+    database
+        .set_featured_image(&featured)
+        .await
+        .expect("Failed to set featured image");
+
+    let post = database
+        .get_post_by_slug("featured")
+        .await
+        .unwrap()
+        .expect("post exists");
+    assert_eq!(post.featured_media_id, Some(media.id));
+
+    let stored = database
+        .get_featured_image(post.id)
+        .await
+        .unwrap()
+        .expect("featured image exists");
+    assert_eq!(stored.media_id, media.id);
+    assert_eq!(stored.focal_point, FocalPoint { x: 30.0, y: 40.0 });
+    assert_eq!(stored.og_url, featured.og_url);
+}
@@ -0,0 +1,169 @@
+use chrono::{TimeZone, Utc};
+use proptest::prelude::*;
+use std::sync::Arc;
+use tempfile::tempdir;
+use tobelog::handlers::api::generate_excerpt;
+use tobelog::services::blog_storage::{BlogPost, BlogPostMetadata};
+use tobelog::services::dropbox::FileMetadata;
+use tobelog::services::storage::LocalStorage;
+use tobelog::services::{BlogStorageService, MarkdownService};
+
+fn file_metadata() -> FileMetadata {
+    FileMetadata {
+        name: "post.md".to_string(),
+        path_lower: "/blogstorage/posts/post.md".to_string(),
+        path_display: "/BlogStorage/posts/post.md".to_string(),
+        size: None,
+        content_hash: None,
+        client_modified: None,
+        server_modified: None,
+    }
+}
+
+/// Single-line text including multi-byte characters and delimiter look-alikes
+fn text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "\\PC{0,40}",
+        "[a-zあ-ん漢字🦀 ]{0,20}(---|\\+\\+\\+|\\{|\\}|\"|\\\\)[a-zあ-ん ]{0,20}",
+    ]
+}
+
+/// Markdown body whose surrounding whitespace is not significant
+fn body() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop_oneof!["\\PC{0,60}", Just("---".to_string())], 0..6)
+        .prop_map(|lines| lines.join("\n").trim().to_string())
+}
+
+/// Arbitrary input starting like one of the frontmatter formats
+fn untrusted_markdown() -> impl Strategy<Value = String> {
+    (
+        prop_oneof![
+            Just(""),
+            Just("---\n"),
+            Just("+++\n"),
+            Just("{"),
+            Just("  \n---")
+        ],
+        "(?s).{0,200}",
+    )
+        .prop_map(|(prefix, rest)| format!("{}{}", prefix, rest))
+}
+
+proptest! {
+    #[test]
+    fn test_yamlフロントマターの値と本文が往復で保たれる(
+        title in text(),
+        tags in prop::collection::vec(text(), 0..4),
+        body in body(),
+    ) {
+        let frontmatter = serde_yaml::to_string(&serde_json::json!({
+            "title": title,
+            "tags": tags,
+        }))
+        .unwrap();
+        let content = format!("---\n{}---\n\n{}", frontmatter, body);
+
+        let (fields, markdown) = MarkdownService::new().extract_frontmatter(&content).unwrap();
+
+        prop_assert_eq!(fields["title"].as_str(), Some(title.as_str()));
+        let parsed_tags: Vec<String> = serde_yaml::from_value(fields["tags"].clone()).unwrap();
+        prop_assert_eq!(parsed_tags, tags);
+        prop_assert_eq!(markdown, body);
+    }
+
+    #[test]
+    fn test_tomlフロントマターの値と本文が往復で保たれる(
+        title in text(),
+        category in text(),
+        body in body(),
+    ) {
+        let mut table = toml::map::Map::new();
+        table.insert("title".to_string(), toml::Value::String(title.clone()));
+        table.insert("category".to_string(), toml::Value::String(category.clone()));
+        let content = format!("+++\n{}+++\n\n{}", toml::to_string(&table).unwrap(), body);
+
+        let (fields, markdown) = MarkdownService::new().extract_frontmatter(&content).unwrap();
+
+        prop_assert_eq!(fields["title"].as_str(), Some(title.as_str()));
+        prop_assert_eq!(fields["category"].as_str(), Some(category.as_str()));
+        prop_assert_eq!(markdown, body);
+    }
+
+    #[test]
+    fn test_jsonフロントマターの値と本文が往復で保たれる(
+        title in text(),
+        tags in prop::collection::vec(text(), 0..4),
+        body in body(),
+    ) {
+        let frontmatter = serde_json::json!({ "title": title, "tags": tags });
+        let content = format!("{}\n\n{}", frontmatter, body);
+
+        let (fields, markdown) = MarkdownService::new().extract_frontmatter(&content).unwrap();
+
+        prop_assert_eq!(fields["title"].as_str(), Some(title.as_str()));
+        let parsed_tags: Vec<String> = serde_yaml::from_value(fields["tags"].clone()).unwrap();
+        prop_assert_eq!(parsed_tags, tags);
+        prop_assert_eq!(markdown, body);
+    }
+
+    #[test]
+    fn test_保存した記事を読み込むとメタデータと本文が保たれる(
+        title in text(),
+        category in proptest::option::of(text()),
+        tags in prop::collection::vec(text(), 0..4),
+        published in any::<bool>(),
+        body in body(),
+    ) {
+        let temp_dir = tempdir().unwrap();
+        let service = BlogStorageService::new(Arc::new(LocalStorage::new(temp_dir.path())));
+        let created_at = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let post = BlogPost {
+            metadata: BlogPostMetadata {
+                title,
+                slug: "round-trip".to_string(),
+                created_at,
+                updated_at: created_at,
+                category,
+                tags,
+                published,
+                author: Some("著者".to_string()),
+                excerpt: None,
+                extra: Default::default(),
+            },
+            content: body,
+            dropbox_path: "/BlogStorage/posts/post.md".to_string(),
+            file_metadata: None,
+        };
+
+        let content = service.serialize_blog_post(&post).unwrap();
+        let parsed = service
+            .parse_blog_post(&content, &file_metadata())
+            .unwrap()
+            .expect("serialized posts have frontmatter");
+
+        prop_assert_eq!(&parsed.metadata.title, &post.metadata.title);
+        prop_assert_eq!(&parsed.metadata.slug, &post.metadata.slug);
+        prop_assert_eq!(parsed.metadata.created_at, created_at);
+        prop_assert_eq!(&parsed.metadata.category, &post.metadata.category);
+        prop_assert_eq!(&parsed.metadata.tags, &post.metadata.tags);
+        prop_assert_eq!(parsed.metadata.published, published);
+        prop_assert_eq!(parsed.content.trim(), post.content.as_str());
+    }
+
+    #[test]
+    fn test_不正なフロントマターでもパニックしない(content in untrusted_markdown()) {
+        let markdown = MarkdownService::new();
+        let _ = markdown.extract_frontmatter(&content);
+        let _ = markdown.parse_markdown(&content);
+
+        let temp_dir = tempdir().unwrap();
+        let service = BlogStorageService::new(Arc::new(LocalStorage::new(temp_dir.path())));
+        let _ = service.parse_blog_post(&content, &file_metadata());
+    }
+
+    #[test]
+    fn test_抜粋は文字の途中で切らない(content in "\\PC{0,300}", max_length in 0usize..250) {
+        let excerpt = generate_excerpt(&content, max_length);
+        prop_assert!(excerpt.trim_end_matches("...").chars().count() <= max_length);
+    }
+}
@@ -0,0 +1,30 @@
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_削除した記事のスラッグが記録され再作成で消える() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database.create_post(create_post("farewell")).await.unwrap();
+    assert!(database.get_gone_post("farewell").await.unwrap().is_none());
+
+    assert!(database.delete_post(post.id).await.unwrap());
+    let gone = database
+        .get_gone_post("farewell")
+        .await
+        .unwrap()
+        .expect("deleted posts leave a tombstone");
+    assert_eq!(gone.title, "Post farewell");
+    assert_eq!(gone.created_at.timestamp(), post.created_at.timestamp());
+
+    // 同じスラッグで書き直した記事は410にしない
+    database.create_post(create_post("farewell")).await.unwrap();
+    assert!(database.get_gone_post("farewell").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_存在しない記事の削除では記録しない() {
+    let (_temp_dir, database) = test_database().await;
+
+    assert!(!database.delete_post(uuid::Uuid::new_v4()).await.unwrap());
+    assert!(database.get_gone_post("missing").await.unwrap().is_none());
+}
@@ -0,0 +1,86 @@
+use futures_util::StreamExt;
+use tobelog::models::{
+    BatchImportRequest, ImportJobEvent, ImportJobStatus, LLMArticleImportRequest,
+};
+use tobelog::services::{
+    ImportJobService, JobQueue, JobQueueConfig, LLMImportService, MarkdownService,
+};
+
+use super::test_database;
+
+fn article(content: &str) -> LLMArticleImportRequest {
+    LLMArticleImportRequest {
+        content: content.to_string(),
+        suggested_title: None,
+        category_hint: None,
+        tags_hint: None,
+        source: "custom".to_string(),
+        published: None,
+        featured: None,
+        override_quality_gates: false,
+    }
+}
+
+#[tokio::test]
+async fn test_バッチインポートの進捗をイベントで受け取れる() {
+    let (_temp_dir, database) = test_database().await;
+    let queue = JobQueue::new(database.clone(), JobQueueConfig::default());
+    let jobs = ImportJobService::new(
+        LLMImportService::new(MarkdownService::new(), database),
+        queue.clone(),
+    );
+    jobs.register_jobs();
+
+    let job = jobs
+        .start(BatchImportRequest {
+            articles: vec![article("# 最初の記事\n\n本文"), article("# 二つ目\n\n本文")],
+            default_category: None,
+            default_published: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(job.total, 2);
+    assert_eq!(job.status, ImportJobStatus::Queued);
+
+    // ジョブはキューに永続化され、ワーカーが処理する
+    queue.start().await.unwrap();
+    let events: Vec<ImportJobEvent> = jobs
+        .subscribe(&job.id)
+        .await
+        .unwrap()
+        .expect("Job should exist")
+        .collect()
+        .await;
+    let names: Vec<&str> = events.iter().map(|event| event.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "started",
+            "article_imported",
+            "article_imported",
+            "completed"
+        ]
+    );
+
+    let finished = jobs.get(&job.id).await.unwrap().expect("Job should exist");
+    assert_eq!(finished.status, ImportJobStatus::Completed);
+    assert_eq!(finished.processed, 2);
+    assert_eq!(finished.successful[1].suggested_metadata.title, "二つ目");
+    assert!(finished.finished_at.is_some());
+
+    // 完了後に購読しても全イベントが再生される
+    let replayed = jobs
+        .subscribe(&job.id)
+        .await
+        .unwrap()
+        .unwrap()
+        .count()
+        .await;
+    assert_eq!(replayed, 4);
+    assert!(jobs.subscribe("unknown").await.unwrap().is_none());
+    assert!(jobs
+        .get(&uuid::Uuid::new_v4().to_string())
+        .await
+        .unwrap()
+        .is_none());
+}
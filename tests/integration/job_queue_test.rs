@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use tobelog::models::{Job, JobFilters, JobStatus};
+use tobelog::services::{DatabaseService, JobQueue, JobQueueConfig};
+
+use super::test_database;
+
+async fn wait_until_finished(database: &DatabaseService, job: &Job) -> Job {
+    for _ in 0..200 {
+        let job = database.get_job(job.id).await.unwrap().unwrap();
+        if matches!(job.status, JobStatus::Succeeded | JobStatus::Dead) {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("Job {} did not finish", job.id);
+}
+
+#[tokio::test]
+async fn test_ジョブの実行とリトライ後のデッドレター() {
+    let (_temp_dir, database) = test_database().await;
+
+    let queue = JobQueue::new(
+        database.clone(),
+        JobQueueConfig {
+            workers: 2,
+            max_attempts: 3,
+            retry_base_delay: Duration::ZERO,
+            poll_interval: Duration::from_millis(10),
+        },
+    );
+    queue.register("double", |payload| async move {
+        let value = payload["value"].as_i64().unwrap_or_default();
+        Ok(serde_json::json!({ "doubled": value * 2 }))
+    });
+    let reporter = queue.clone();
+    queue.register("progress", move |_payload| {
+        let reporter = reporter.clone();
+        async move {
+            reporter
+                .report_progress(&serde_json::json!({ "processed": 1 }))
+                .await
+        }
+    });
+    queue.register("broken", |_payload| async move {
+        Err::<(), _>(anyhow::anyhow!("Dropbox unavailable"))
+    });
+    queue.start().await.unwrap();
+
+    let succeeded = queue
+        .enqueue("double", &serde_json::json!({ "value": 21 }))
+        .await
+        .unwrap();
+    let failed = queue.enqueue("broken", &()).await.unwrap();
+    let reported = queue.enqueue("progress", &()).await.unwrap();
+    let unknown = queue.enqueue("missing_handler", &()).await.unwrap();
+
+    let succeeded = wait_until_finished(&database, &succeeded).await;
+    assert_eq!(succeeded.status, JobStatus::Succeeded);
+    assert_eq!(succeeded.attempts, 1);
+    assert_eq!(succeeded.result, Some(serde_json::json!({ "doubled": 42 })));
+
+    // ハンドラが報告した進捗はジョブに残る
+    let reported = wait_until_finished(&database, &reported).await;
+    assert_eq!(
+        reported.progress,
+        Some(serde_json::json!({ "processed": 1 }))
+    );
+
+    // 失敗したジョブは上限まで再試行され、デッドレターになる
+    let failed = wait_until_finished(&database, &failed).await;
+    assert_eq!(failed.status, JobStatus::Dead);
+    assert_eq!(failed.attempts, 3);
+    assert_eq!(failed.last_error.as_deref(), Some("Dropbox unavailable"));
+
+    let unknown = wait_until_finished(&database, &unknown).await;
+    assert_eq!(unknown.status, JobStatus::Dead);
+
+    let dead = database
+        .list_jobs(&JobFilters {
+            status: Some(JobStatus::Dead),
+            kind: Some("broken".to_string()),
+            limit: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].id, failed.id);
+}
@@ -0,0 +1,94 @@
+use chrono::Utc;
+use tobelog::models::{CreatePost, MediaFile};
+use tobelog::services::reader_mode::simplify_html;
+use tobelog::services::template::{LitePageContext, PostData};
+use tobelog::services::TemplateService;
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+fn media_file(filename: &str, thumbnail: bool) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        filename: filename.to_string(),
+        original_filename: filename.to_string(),
+        dropbox_path: format!("/BlogStorage/media/images/2024/07/{}", filename),
+        url: format!("/media/images/2024/07/{}", filename),
+        file_size: 2048,
+        mime_type: "image/png".to_string(),
+        width: Some(1600),
+        height: Some(1200),
+        uploaded_at: Utc::now(),
+        thumbnail_url: thumbnail
+            .then(|| format!("/media/thumbnails/2024/07/images/thumb_{}", filename)),
+        alt_text: None,
+        caption: None,
+        content_hash: None,
+        duration_seconds: None,
+        alt_text_suggestion: None,
+        collection: None,
+        focal_point: None,
+    }
+}
+
+#[tokio::test]
+async fn test_軽量版は画像をサムネイルに置き換えてスクリプトを除く() {
+    let (_temp_dir, database) = test_database().await;
+
+    database
+        .create_media_file(&media_file("cat.png", true))
+        .await
+        .unwrap();
+    database
+        .create_media_file(&media_file("dog.png", false))
+        .await
+        .unwrap();
+
+    let html = concat!(
+        r#"<p style="color:red">写真</p>"#,
+        r#"<img src="/media/images/2024/07/cat.png" alt="猫">"#,
+        r#"<img src="/media/images/2024/07/dog.png" alt="犬">"#,
+        r#"<script>track()</script>"#,
+    );
+    let post = database
+        .create_post(CreatePost {
+            title: "写真".to_string(),
+            content: "![猫](/media/images/2024/07/cat.png)\n![犬](/media/images/2024/07/dog.png)"
+                .to_string(),
+            html_content: html.to_string(),
+            ..create_post("photos")
+        })
+        .await
+        .unwrap();
+    database.sync_post_media(&post).await.unwrap();
+
+    let thumbnails = database.post_media_thumbnails(post.id).await.unwrap();
+    assert_eq!(
+        thumbnails.len(),
+        1,
+        "サムネイルのない画像は含めないはずです"
+    );
+
+    let full_url = post.get_url_path();
+    let mut post_data = PostData::from(post);
+    post_data.html_content = simplify_html(&post_data.html_content, &thumbnails);
+    let context = LitePageContext {
+        site_title: "Tobelog".to_string(),
+        post: post_data,
+        canonical_url: format!("https://blog.example.com{}", full_url),
+        full_url: full_url.clone(),
+    };
+
+    let templates = TemplateService::new_with_theme("default").expect("Failed to load templates");
+    let page = templates.render("lite.html", &context).unwrap();
+    assert!(page.contains(r#"src="/media/thumbnails/2024/07/images/thumb_cat.png""#));
+    assert!(page.contains(r#"src="/media/images/2024/07/dog.png""#));
+    assert!(page.contains("<p>写真</p>"));
+    assert!(
+        !page.contains("<script"),
+        "軽量版にスクリプトが残っています"
+    );
+    // Tera escapes "/" in attribute values
+    let full_link = format!(r#"href="{}""#, full_url.replace('/', "&#x2F;"));
+    assert!(page.contains(&full_link), "通常版へのリンクがありません");
+}
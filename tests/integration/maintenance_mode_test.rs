@@ -0,0 +1,81 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use tobelog::handlers::maintenance;
+use tobelog::models::MaintenanceStatus;
+use tobelog::services::template::MaintenancePageContext;
+use tobelog::services::{MaintenanceService, TemplateService};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn test_管理apiでメンテナンスモードを切り替える() {
+    let service = MaintenanceService::new(false, 120);
+    let app = Router::new()
+        .route(
+            "/api/admin/maintenance",
+            get(maintenance::get_maintenance).put(maintenance::update_maintenance),
+        )
+        .with_state(service.clone());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/api/admin/maintenance")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    r#"{"enabled": true, "message": "DB移行中です"}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(service.is_enabled());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/admin/maintenance")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let status: MaintenanceStatus = serde_json::from_slice(&body).unwrap();
+    assert!(status.enabled);
+    assert_eq!(status.retry_after_secs, 120);
+    assert_eq!(status.message.as_deref(), Some("DB移行中です"));
+    assert!(status.since.is_some());
+}
+
+#[tokio::test]
+async fn test_メンテナンスページに任意のメッセージと再訪の目安を表示する() {
+    for theme in ["default", "blog", "minimal", "modern"] {
+        let templates = TemplateService::new_with_theme(theme).expect("Failed to load templates");
+        let html = templates
+            .render(
+                "maintenance.html",
+                &MaintenancePageContext {
+                    site_title: "Tobelog".to_string(),
+                    site_description: "テスト".to_string(),
+                    message: Some("<b>DB移行中</b>".to_string()),
+                    retry_after_minutes: 5,
+                },
+            )
+            .expect("Failed to render maintenance page");
+
+        assert!(html.contains("503"), "{}", theme);
+        assert!(
+            html.contains("&lt;b&gt;DB移行中&lt;&#x2F;b&gt;"),
+            "{}",
+            theme
+        );
+        assert!(html.contains("5分ほど"), "{}", theme);
+    }
+}
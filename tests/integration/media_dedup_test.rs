@@ -0,0 +1,60 @@
+use chrono::{Duration, Utc};
+use tobelog::models::MediaFile;
+use uuid::Uuid;
+
+use super::test_database;
+
+fn media_file(filename: &str, content_hash: Option<&str>, age_minutes: i64) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        filename: filename.to_string(),
+        original_filename: filename.to_string(),
+        dropbox_path: format!("/BlogStorage/media/images/2024/07/{}", filename),
+        url: format!("/media/images/2024/07/{}", filename),
+        file_size: 2048,
+        mime_type: "image/png".to_string(),
+        width: None,
+        height: None,
+        uploaded_at: Utc::now() - Duration::minutes(age_minutes),
+        thumbnail_url: None,
+        alt_text: None,
+        caption: None,
+        content_hash: content_hash.map(str::to_string),
+        duration_seconds: None,
+        alt_text_suggestion: None,
+        collection: None,
+        focal_point: None,
+    }
+}
+
+#[tokio::test]
+async fn test_同じハッシュのメディアは最初のアップロードを返す() {
+    let (_temp_dir, database) = test_database().await;
+
+    let original = media_file("original.png", Some("abc123"), 10);
+    let forced_copy = media_file("copy.png", Some("abc123"), 0);
+    let legacy = media_file("legacy.png", None, 20);
+    for media in [&original, &forced_copy, &legacy] {
+        database
+            .create_media_file(media)
+            .await
+            .expect("Failed to create media file");
+    }
+
+    let found = database
+        .find_media_file_by_hash("abc123")
+        .await
+        .unwrap()
+        .expect("duplicate should be found");
+    assert_eq!(found.id, original.id, "最も古いアップロードを返すはずです");
+    assert_eq!(found.content_hash.as_deref(), Some("abc123"));
+
+    assert!(database
+        .find_media_file_by_hash("unknown")
+        .await
+        .unwrap()
+        .is_none());
+
+    let fetched = database.get_media_file(legacy.id).await.unwrap().unwrap();
+    assert_eq!(fetched.content_hash, None);
+}
@@ -0,0 +1,144 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::{patch, post},
+    Router,
+};
+use std::io::Cursor;
+use tobelog::handlers::api;
+use tobelog::models::{FocalPoint, MediaFile, MediaUploadResponse};
+use tobelog::services::storage::MemoryStorage;
+use tower::ServiceExt;
+
+use super::create_post;
+use super::mock_storage_test::fixture_state;
+
+fn multipart_body(fields: &[(&str, &str)], file: Option<&[u8]>) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--BOUNDARY\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                name, value
+            )
+            .as_bytes(),
+        );
+    }
+    if let Some(data) = file {
+        body.extend_from_slice(
+            b"--BOUNDARY\r\nContent-Disposition: form-data; name=\"file\"; filename=\"portrait.png\"\r\n\
+              Content-Type: image/png\r\n\r\n",
+        );
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(b"--BOUNDARY--\r\n");
+    body
+}
+
+fn multipart_request(path: &str, body: Vec<u8>) -> Request<Body> {
+    Request::post(path)
+        .header(
+            header::CONTENT_TYPE,
+            "multipart/form-data; boundary=BOUNDARY",
+        )
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_メディアの焦点をpatchで設定するとアイキャッチが切り抜き直される() {
+    let state = fixture_state(MemoryStorage::new()).await;
+    let database = state.database.clone();
+    let app = Router::new()
+        .route("/api/media/upload", post(api::upload_media_api))
+        .route("/api/media/:id", patch(api::update_media_api))
+        .route(
+            "/api/posts/:slug/featured-image",
+            post(api::set_featured_image_api),
+        )
+        .with_state(state);
+
+    let mut png = Vec::new();
+    image::RgbImage::new(80, 40)
+        .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .unwrap();
+    let response = app
+        .clone()
+        .oneshot(multipart_request(
+            "/api/media/upload",
+            multipart_body(&[], Some(&png)),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let uploaded: MediaUploadResponse = serde_json::from_slice(&raw).unwrap();
+    let media: MediaFile = uploaded.media.expect("uploaded media");
+    assert_eq!(media.focal_point, None);
+
+    let post = database.create_post(create_post("portrait")).await.unwrap();
+    let media_id = media.id.to_string();
+    let response = app
+        .clone()
+        .oneshot(multipart_request(
+            "/api/posts/portrait/featured-image",
+            multipart_body(&[("media_id", &media_id)], None),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let featured = database.get_featured_image(post.id).await.unwrap().unwrap();
+    assert_eq!(featured.focal_point, FocalPoint::default());
+
+    let update = |body: &'static str| {
+        Request::patch(format!("/api/media/{}", media_id))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+    let response = app
+        .clone()
+        .oneshot(update(r#"{"focal_point": {"x": 120.0, "y": 50.0}}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(update(r#"{"focal_point": {"x": 15.0, "y": 80.0}}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let updated: serde_json::Value = serde_json::from_slice(&raw).unwrap();
+    assert_eq!(
+        updated["media"]["focal_point"],
+        serde_json::json!({"x": 15.0, "y": 80.0})
+    );
+    let focal_point = FocalPoint { x: 15.0, y: 80.0 };
+
+    // The existing featured image follows the new focal point
+    let recropped = database.get_featured_image(post.id).await.unwrap().unwrap();
+    assert_eq!(recropped.focal_point, focal_point);
+
+    // New featured images default to the media's focal point, per coordinate
+    let other = database
+        .create_post(create_post("landscape"))
+        .await
+        .unwrap();
+    let response = app
+        .oneshot(multipart_request(
+            "/api/posts/landscape/featured-image",
+            multipart_body(&[("media_id", &media_id), ("focal_y", "30")], None),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let featured = database
+        .get_featured_image(other.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(featured.focal_point, FocalPoint { x: 15.0, y: 30.0 });
+}
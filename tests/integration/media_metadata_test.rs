@@ -0,0 +1,161 @@
+use chrono::Utc;
+use tobelog::models::{
+    CreatePost, FocalPoint, MediaFile, MediaFilters, UpdateMediaRequest, UpdatePost,
+};
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+fn media_file(filename: &str) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        filename: filename.to_string(),
+        original_filename: filename.to_string(),
+        dropbox_path: format!("/BlogStorage/media/images/2024/07/{}", filename),
+        url: format!("/media/images/2024/07/{}", filename),
+        file_size: 2048,
+        mime_type: "image/png".to_string(),
+        width: None,
+        height: None,
+        uploaded_at: Utc::now(),
+        thumbnail_url: None,
+        alt_text: None,
+        caption: None,
+        content_hash: None,
+        duration_seconds: None,
+        alt_text_suggestion: Some("A diagram".to_string()),
+        collection: None,
+        focal_point: None,
+    }
+}
+
+#[tokio::test]
+async fn test_メディアのメタデータを更新しコレクションで絞り込める() {
+    let (_temp_dir, database) = test_database().await;
+
+    let mut diagram = media_file("diagram.png");
+    let other = media_file("other.png");
+    for media in [&diagram, &other] {
+        database
+            .create_media_file(media)
+            .await
+            .expect("Failed to create media file");
+    }
+
+    diagram
+        .apply_update(UpdateMediaRequest {
+            alt_text: Some("Architecture diagram".to_string()),
+            caption: Some("System overview".to_string()),
+            filename: Some("architecture.png".to_string()),
+            collection: Some("diagrams".to_string()),
+            alt_text_suggestion: None,
+            focal_point: Some(FocalPoint { x: 20.0, y: 75.0 }),
+        })
+        .expect("valid update");
+    assert!(database.update_media_metadata(&diagram).await.unwrap());
+
+    let stored = database
+        .get_media_file(diagram.id)
+        .await
+        .unwrap()
+        .expect("media exists");
+    assert_eq!(stored.alt_text.as_deref(), Some("Architecture diagram"));
+    assert_eq!(stored.alt_text_suggestion, None);
+    assert_eq!(stored.caption.as_deref(), Some("System overview"));
+    assert_eq!(stored.original_filename, "architecture.png");
+    assert_eq!(stored.url, diagram.url);
+    assert_eq!(stored.focal_point, Some(FocalPoint { x: 20.0, y: 75.0 }));
+
+    let filters = MediaFilters {
+        collection: Some("diagrams".to_string()),
+        ..Default::default()
+    };
+    let listed = database.list_media_files(filters.clone()).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, diagram.id);
+    assert_eq!(database.count_media_files(filters).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_メディアを使用している記事を取得できる() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database
+        .create_post(CreatePost {
+            title: "Uses media".to_string(),
+            content: "![](/media/images/2024/07/photo.png)".to_string(),
+            html_content: "<p><img src=\"/media/images/2024/07/photo.png\"></p>".to_string(),
+            ..create_post("uses-media")
+        })
+        .await
+        .expect("Failed to create post");
+
+    let media = media_file("photo.png");
+    database.create_media_file(&media).await.unwrap();
+    assert!(database
+        .get_post_slugs_using_media(media.id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    database
+        .associate_media_with_post(post.id, media.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        database.get_post_slugs_using_media(media.id).await.unwrap(),
+        vec!["uses-media".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_記事の保存時にメディア参照を追跡する() {
+    let (_temp_dir, database) = test_database().await;
+
+    let cat = media_file("cat.png");
+    let dog = media_file("dog.png");
+    for media in [&cat, &dog] {
+        database.create_media_file(media).await.unwrap();
+    }
+
+    let post = database
+        .create_post(CreatePost {
+            title: "Pets".to_string(),
+            content: format!("![cat]({})", cat.url),
+            html_content: format!("<p><img src=\"{}\"></p>", cat.url),
+            ..create_post("pets")
+        })
+        .await
+        .expect("Failed to create post");
+
+    let ids: Vec<Uuid> = database
+        .get_post_media(post.id)
+        .await
+        .unwrap()
+        .iter()
+        .map(|media| media.id)
+        .collect();
+    assert_eq!(ids, vec![cat.id]);
+
+    database
+        .update_post(
+            post.id,
+            UpdatePost {
+                content: Some(format!("![dog](https://blog.example.com{})", dog.url)),
+                html_content: Some(format!(
+                    "<p><img src=\"https://blog.example.com{}\"></p>",
+                    dog.url
+                )),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let counts = database
+        .get_media_usage_counts(&[cat.id, dog.id])
+        .await
+        .unwrap();
+    assert_eq!(counts.get(&dog.id), Some(&1));
+    assert_eq!(counts.get(&cat.id), None);
+}
@@ -0,0 +1,120 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use tobelog::handlers::api;
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::StorageBackend;
+use tower::ServiceExt;
+
+use super::mock_storage_test::fixture_state;
+
+#[tokio::test]
+async fn test_メディア配信はメディアフォルダの外を読めない() {
+    let storage = MemoryStorage::new();
+    storage
+        .upload_file("/BlogStorage/media/files/notes.txt", "public notes")
+        .await
+        .unwrap();
+    storage
+        .upload_file("/BlogStorage/posts/draft.md", "secret draft")
+        .await
+        .unwrap();
+    let state = fixture_state(storage).await;
+    let app = Router::new()
+        .route("/media/*path", get(api::serve_media_file))
+        .with_state(state);
+    let fetch = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, String::from_utf8_lossy(&raw).to_string())
+        }
+    };
+
+    let (status, body) = fetch("/media/files/notes.txt").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "public notes");
+
+    for uri in [
+        "/media/../posts/draft.md",
+        "/media/files/../../posts/draft.md",
+        "/media/%2e%2e/posts/draft.md",
+        "/media/files/..%2F..%2Fposts%2Fdraft.md",
+        "/media/..%5Cposts%5Cdraft.md",
+        "/media/files/./notes.txt",
+        "/media/files/notes.txt%00.md",
+        "/media/C:%2Fposts%2Fdraft.md",
+    ] {
+        let (status, body) = fetch(uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{} was not rejected", uri);
+        assert!(!body.contains("secret draft"));
+    }
+
+    // Absolute paths are still resolved under the media folder
+    let (status, body) = fetch("/media//BlogStorage/posts/draft.md").await;
+    assert_ne!(status, StatusCode::OK);
+    assert!(!body.contains("secret draft"));
+}
+
+#[tokio::test]
+async fn test_svgはスクリプトを禁止するヘッダー付きで配信される() {
+    let storage = MemoryStorage::new();
+    storage
+        .upload_file(
+            "/BlogStorage/media/images/logo.svg",
+            r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#,
+        )
+        .await
+        .unwrap();
+    storage
+        .upload_file("/BlogStorage/media/files/notes.txt", "notes")
+        .await
+        .unwrap();
+    let state = fixture_state(storage).await;
+    let app = Router::new()
+        .route("/media/*path", get(api::serve_media_file))
+        .with_state(state);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/media/images/logo.svg")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers[header::CONTENT_TYPE], "image/svg+xml");
+    assert_eq!(
+        headers[header::CONTENT_SECURITY_POLICY],
+        "default-src 'none'; style-src 'unsafe-inline'"
+    );
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+
+    // Other media are not sniffed either and need no policy
+    let response = app
+        .oneshot(
+            Request::get("/media/files/notes.txt")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()[header::X_CONTENT_TYPE_OPTIONS],
+        "nosniff"
+    );
+    assert!(!response
+        .headers()
+        .contains_key(header::CONTENT_SECURITY_POLICY));
+}
@@ -0,0 +1,108 @@
+use chrono::Utc;
+use tobelog::models::{MediaFile, MediaFilters};
+use tobelog::services::DatabaseService;
+use uuid::Uuid;
+
+use super::test_database;
+
+fn media_file(filename: &str, alt_text: Option<&str>, caption: Option<&str>) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        filename: filename.to_string(),
+        original_filename: filename.to_string(),
+        dropbox_path: format!("/BlogStorage/media/images/2024/07/{}", filename),
+        url: format!("/media/images/2024/07/{}", filename),
+        file_size: 2048,
+        mime_type: "image/png".to_string(),
+        width: None,
+        height: None,
+        uploaded_at: Utc::now(),
+        thumbnail_url: None,
+        alt_text: alt_text.map(str::to_string),
+        caption: caption.map(str::to_string),
+        content_hash: None,
+        duration_seconds: None,
+        alt_text_suggestion: None,
+        collection: None,
+        focal_point: None,
+    }
+}
+
+fn search(query: &str) -> MediaFilters {
+    MediaFilters {
+        search: Some(query.to_string()),
+        ..Default::default()
+    }
+}
+
+async fn filenames(database: &DatabaseService, query: &str) -> Vec<String> {
+    database
+        .list_media_files(search(query))
+        .await
+        .expect("Failed to search media")
+        .into_iter()
+        .map(|media| media.filename)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_メディアを単語単位で検索し関連度順に並べる() {
+    let (_temp_dir, database) = test_database().await;
+
+    for media in [
+        media_file("telegram.png", None, None),
+        media_file("network.png", Some("Network diagram"), None),
+        media_file(
+            "diagram-overview.png",
+            Some("Architecture diagram"),
+            Some("Diagram of the system"),
+        ),
+    ] {
+        database
+            .create_media_file(&media)
+            .await
+            .expect("Failed to create media file");
+    }
+
+    // Prefixes of words match, substrings inside words do not
+    assert_eq!(
+        filenames(&database, "diag").await,
+        vec!["diagram-overview.png", "network.png"]
+    );
+    assert_eq!(filenames(&database, "gram").await, Vec::<String>::new());
+    assert_eq!(
+        filenames(&database, "network diagram").await,
+        vec!["network.png"]
+    );
+    assert_eq!(
+        database.count_media_files(search("diagram")).await.unwrap(),
+        2
+    );
+
+    // FTS syntax in the search is treated as text; punctuation alone filters nothing
+    assert_eq!(
+        filenames(&database, "\"diagram OR").await,
+        Vec::<String>::new()
+    );
+    assert_eq!(filenames(&database, " - ").await.len(), 3);
+}
+
+#[tokio::test]
+async fn test_メディアの更新と削除が検索インデックスに反映される() {
+    let (_temp_dir, database) = test_database().await;
+
+    let mut media = media_file("photo.jpg", None, None);
+    database
+        .create_media_file(&media)
+        .await
+        .expect("Failed to create media file");
+    assert!(filenames(&database, "sunset").await.is_empty());
+
+    media.alt_text = Some("Sunset over the sea".to_string());
+    assert!(database.update_media_metadata(&media).await.unwrap());
+    assert_eq!(filenames(&database, "sunset").await, vec!["photo.jpg"]);
+
+    assert!(database.delete_media_file(media.id).await.unwrap());
+    assert!(filenames(&database, "sunset").await.is_empty());
+    assert!(filenames(&database, "photo").await.is_empty());
+}
@@ -0,0 +1,97 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use std::io::Cursor;
+use tobelog::handlers::api;
+use tobelog::models::MediaUploadResponse;
+use tobelog::services::storage::MemoryStorage;
+use tower::ServiceExt;
+
+use super::mock_storage_test::fixture_state;
+
+fn png_bytes() -> Vec<u8> {
+    let mut png = Vec::new();
+    image::RgbImage::new(2, 2)
+        .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .unwrap();
+    png
+}
+
+async fn upload(
+    storage: MemoryStorage,
+    filename: &str,
+    content_type: &str,
+    data: &[u8],
+) -> (StatusCode, Vec<u8>) {
+    let state = fixture_state(storage).await;
+    let app = Router::new()
+        .route("/api/media/upload", post(api::upload_media_api))
+        .with_state(state);
+
+    let mut body = format!(
+        "--BOUNDARY\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+         Content-Type: {}\r\n\r\n",
+        filename, content_type
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\r\n--BOUNDARY--\r\n");
+
+    let response = app
+        .oneshot(
+            Request::post("/api/media/upload")
+                .header(
+                    header::CONTENT_TYPE,
+                    "multipart/form-data; boundary=BOUNDARY",
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, raw.to_vec())
+}
+
+#[tokio::test]
+async fn test_宣言と中身が違うファイルは415で拒否される() {
+    let storage = MemoryStorage::new();
+
+    let (status, raw) = upload(
+        storage.clone(),
+        "avatar.png",
+        "image/png",
+        b"<script>alert(1)</script>",
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert!(String::from_utf8_lossy(&raw).contains("avatar.png"));
+
+    // The content is a PNG, but the extension would serve it as JPEG
+    let (status, _) = upload(storage.clone(), "avatar.jpg", "image/png", &png_bytes()).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    assert!(storage.file_paths().is_empty());
+}
+
+#[tokio::test]
+async fn test_汎用のmime型は中身から判定して保存する() {
+    let (status, raw) = upload(
+        MemoryStorage::new(),
+        "pixel.png",
+        "application/octet-stream",
+        &png_bytes(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&raw));
+
+    let uploaded: MediaUploadResponse = serde_json::from_slice(&raw).unwrap();
+    assert_eq!(
+        uploaded.media.expect("uploaded media").mime_type,
+        "image/png"
+    );
+}
@@ -0,0 +1,200 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use std::io::Cursor;
+use std::sync::Arc;
+use tobelog::handlers::api::{self, ApiState, SyncDropboxRequest};
+use tobelog::models::MediaUploadResponse;
+use tobelog::services::i18n::SiteTimezone;
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::{
+    BlogStorageService, CacheService, CalendarService, DatabaseService, EventBus, ImportJobService,
+    JobQueue, JobQueueConfig, LLMImportService, MarkdownService, MediaService, StorageBackend,
+    SyncConflictService,
+};
+use tower::ServiceExt;
+
+/// API state over an in-memory database and `storage` in place of Dropbox
+pub async fn fixture_state(storage: MemoryStorage) -> ApiState {
+    let database = DatabaseService::in_memory()
+        .await
+        .expect("Failed to create database");
+    let markdown = MarkdownService::new();
+    let storage: Arc<dyn StorageBackend> = Arc::new(storage);
+    let blog_storage = Arc::new(BlogStorageService::new(storage.clone()));
+    let llm_import = LLMImportService::new(markdown.clone(), database.clone());
+    let jobs = JobQueue::new(database.clone(), JobQueueConfig::default());
+
+    ApiState {
+        database: database.clone(),
+        markdown: markdown.clone(),
+        blog_storage: blog_storage.clone(),
+        llm_import: llm_import.clone(),
+        media: MediaService::new(storage, blog_storage.clone(), database.clone()),
+        calendar: CalendarService::new(database.clone()),
+        cache: CacheService::new(),
+        api_key: None,
+        dropbox_root: "/BlogStorage".to_string(),
+        timezone: SiteTimezone::new(chrono_tz::UTC),
+        sync_conflicts: SyncConflictService::new(database.clone(), blog_storage, markdown),
+        max_pinned_posts: 3,
+        summarizer: None,
+        import_jobs: ImportJobService::new(llm_import, jobs.clone()),
+        jobs,
+        events: EventBus::new(),
+    }
+}
+
+fn sync_all() -> SyncDropboxRequest {
+    SyncDropboxRequest {
+        force: None,
+        paths: None,
+        slugs: None,
+    }
+}
+
+#[tokio::test]
+async fn test_dropboxの記事を同期してデータベースに反映する() {
+    let storage = MemoryStorage::new();
+    let state = fixture_state(storage.clone()).await;
+    storage
+        .upload_file(
+            "/BlogStorage/posts/hello.md",
+            "---\ntitle: こんにちは\nslug: hello\ntags: [rust]\npublished: true\n---\n\n# 見出し\n\n本文です。",
+        )
+        .await
+        .unwrap();
+    storage
+        .upload_file(
+            "/BlogStorage/posts/draft.md",
+            "---\ntitle: 下書き\nslug: draft\npublished: false\n---\n\n未公開",
+        )
+        .await
+        .unwrap();
+
+    let result = api::run_dropbox_sync(&state, sync_all()).await.unwrap();
+    assert_eq!(
+        result.synced_count,
+        Some(1),
+        "公開済みの記事だけを同期するはずです"
+    );
+
+    let post = state
+        .database
+        .get_post_by_slug("hello")
+        .await
+        .unwrap()
+        .expect("synced post");
+    assert_eq!(post.title, "こんにちは");
+    assert!(post.html_content.contains("本文です。"));
+    assert!(state
+        .database
+        .get_post_by_slug("draft")
+        .await
+        .unwrap()
+        .is_none());
+
+    storage
+        .upload_file(
+            "/BlogStorage/posts/hello.md",
+            "---\ntitle: こんにちは（改訂）\nslug: hello\npublished: true\n---\n\n書き直しました。",
+        )
+        .await
+        .unwrap();
+    api::run_dropbox_sync(&state, sync_all()).await.unwrap();
+
+    let post = state
+        .database
+        .get_post_by_slug("hello")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.title, "こんにちは（改訂）");
+    assert!(post.html_content.contains("書き直しました。"));
+}
+
+#[tokio::test]
+async fn test_下書きを公開すると投稿フォルダに移動して同期される() {
+    let storage = MemoryStorage::new();
+    let state = fixture_state(storage.clone()).await;
+    state
+        .blog_storage
+        .initialize_blog_structure()
+        .await
+        .unwrap();
+    storage
+        .upload_file(
+            "/BlogStorage/drafts/announce.md",
+            "---\ntitle: お知らせ\nslug: announce\npublished: false\n---\n\n近日公開",
+        )
+        .await
+        .unwrap();
+
+    assert!(state.blog_storage.publish_post("announce").await.unwrap());
+    assert_eq!(
+        storage.file_paths(),
+        ["/BlogStorage/posts/announce.md"],
+        "下書きフォルダから削除されるはずです"
+    );
+
+    api::run_dropbox_sync(&state, sync_all()).await.unwrap();
+    let post = state
+        .database
+        .get_post_by_slug("announce")
+        .await
+        .unwrap()
+        .expect("published post");
+    assert!(post.published);
+}
+
+#[tokio::test]
+async fn test_アップロードしたメディアがストレージに保存される() {
+    let storage = MemoryStorage::new();
+    let state = fixture_state(storage.clone()).await;
+    let app = Router::new()
+        .route("/api/media/upload", post(api::upload_media_api))
+        .with_state(state.clone());
+
+    let mut png = Vec::new();
+    image::RgbImage::new(4, 3)
+        .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .unwrap();
+    let mut body = b"--BOUNDARY\r\nContent-Disposition: form-data; name=\"alt_text\"\r\n\r\n\
+        \xe7\x94\xbb\xe5\x83\x8f\r\n--BOUNDARY\r\nContent-Disposition: form-data; name=\"file\"; \
+        filename=\"pixel.png\"\r\nContent-Type: image/png\r\n\r\n"
+        .to_vec();
+    body.extend_from_slice(&png);
+    body.extend_from_slice(b"\r\n--BOUNDARY--\r\n");
+
+    let response = app
+        .oneshot(
+            Request::post("/api/media/upload")
+                .header(
+                    header::CONTENT_TYPE,
+                    "multipart/form-data; boundary=BOUNDARY",
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&raw));
+    let uploaded: MediaUploadResponse = serde_json::from_slice(&raw).unwrap();
+    let media = uploaded.media.expect("uploaded media");
+    assert_eq!(media.alt_text.as_deref(), Some("画像"));
+    assert_eq!((media.width, media.height), (Some(4), Some(3)));
+
+    assert!(storage.file_paths().contains(&media.dropbox_path));
+    let (data, mime_type) = state
+        .media
+        .serve_media_file(media.url.trim_start_matches("/media"))
+        .await
+        .unwrap();
+    assert_eq!(data, png);
+    assert_eq!(mime_type, "image/png");
+}
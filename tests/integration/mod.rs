@@ -1,3 +1,88 @@
 pub mod cicd_config_test;
 pub mod ssl_config_test;
 pub mod database_connection_test;
+pub mod search_filters_test;
+pub mod tag_management_test;
+pub mod featured_image_test;
+pub mod media_dedup_test;
+pub mod podcast_test;
+pub mod media_metadata_test;
+pub mod search_index_test;
+pub mod settings_transfer_test;
+pub mod sync_conflicts_test;
+pub mod post_pinning_test;
+pub mod import_jobs_test;
+pub mod job_queue_test;
+pub mod scheduled_publish_test;
+pub mod post_visibility_test;
+pub mod storage_migration_test;
+pub mod media_search_test;
+pub mod category_styles_test;
+pub mod announcements_test;
+pub mod post_changelog_test;
+pub mod version_browser_test;
+pub mod version_retention_test;
+pub mod attachments_test;
+pub mod admin_session_test;
+pub mod openapi_test;
+pub mod frontmatter_parsing_test;
+pub mod mock_storage_test;
+pub mod seed_test;
+pub mod gone_posts_test;
+pub mod syndication_test;
+pub mod cross_post_test;
+pub mod stats_test;
+pub mod digest_test;
+pub mod notifications_test;
+pub mod search_page_test;
+pub mod category_index_test;
+pub mod structured_data_test;
+pub mod lite_page_test;
+pub mod pdf_export_test;
+pub mod post_outline_test;
+pub mod command_palette_test;
+pub mod maintenance_mode_test;
+pub mod config_reload_test;
+pub mod slow_log_test;
+pub mod content_hash_test;
+pub mod post_metadata_test;
+pub mod version_word_diff_test;
+pub mod published_version_test;
+pub mod version_export_test;
+pub mod scoped_feed_test;
+pub mod media_sniff_test;
+pub mod media_focal_point_test;
+pub mod media_path_test;
+pub mod negative_cache_test;
+pub mod response_cache_test;
+
+use tempfile::TempDir;
+use tobelog::models::CreatePost;
+use tobelog::services::DatabaseService;
+
+/// A published post with placeholder content; override fields with `..create_post(slug)`
+pub fn create_post(slug: &str) -> CreatePost {
+    CreatePost {
+        slug: slug.to_string(),
+        title: format!("Post {}", slug),
+        content: "Content".to_string(),
+        html_content: "<p>Content</p>".to_string(),
+        excerpt: None,
+        category: None,
+        tags: vec![],
+        published: true,
+        featured: false,
+        author: None,
+        dropbox_path: format!("/test/{}.md", slug),
+    }
+}
+
+/// A migrated database in a temporary directory, removed when the `TempDir` drops
+pub async fn test_database() -> (TempDir, DatabaseService) {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("test.db");
+    let database = DatabaseService::new(&format!("sqlite:{}", db_path.to_str().unwrap()))
+        .await
+        .expect("Failed to create database");
+    (temp_dir, database)
+}
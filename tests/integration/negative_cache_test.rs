@@ -0,0 +1,111 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use std::io::Cursor;
+use tobelog::handlers::api;
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::StorageBackend;
+use tower::ServiceExt;
+
+use super::create_post;
+use super::mock_storage_test::fixture_state;
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_存在しないスラッグは作成されるまで404を覚えておく() {
+    let state = fixture_state(MemoryStorage::new()).await;
+    let database = state.database.clone();
+    let cache = state.cache.clone();
+    let app = Router::new()
+        .route("/api/posts", post(api::create_post_api))
+        .route("/api/posts/:slug", get(api::get_post_api))
+        .with_state(state);
+
+    let status = |uri: &'static str| {
+        let app = app.clone();
+        async move { app.oneshot(get_request(uri)).await.unwrap().status() }
+    };
+
+    assert_eq!(status("/api/posts/lurker").await, StatusCode::NOT_FOUND);
+    assert_eq!(
+        cache.get_cache_stats().await.get("cached_not_found"),
+        Some(&1)
+    );
+
+    // A post written behind the cache's back stays hidden until the entry goes
+    database.create_post(create_post("lurker")).await.unwrap();
+    assert_eq!(status("/api/posts/lurker").await, StatusCode::NOT_FOUND);
+    cache.invalidate_post("lurker").await.unwrap();
+    assert_eq!(status("/api/posts/lurker").await, StatusCode::OK);
+
+    // Creating the post through the API forgets the not-found slug
+    assert_eq!(status("/api/posts/ghost-post").await, StatusCode::NOT_FOUND);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/api/posts")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    r#"{"title": "Ghost Post", "content": "Boo", "published": true}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&raw).unwrap();
+    assert_eq!(created["slug"], "ghost-post");
+    assert_eq!(status("/api/posts/ghost-post").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_存在しないメディアはアップロードされるまで404を覚えておく() {
+    let storage = MemoryStorage::new();
+    let state = fixture_state(storage.clone()).await;
+    let app = Router::new()
+        .route("/api/media/upload", post(api::upload_media_api))
+        .route("/media/*path", get(api::serve_media_file))
+        .with_state(state);
+    let status = |uri: &'static str| {
+        let app = app.clone();
+        async move { app.oneshot(get_request(uri)).await.unwrap().status() }
+    };
+
+    assert_eq!(status("/media/files/late.txt").await, StatusCode::NOT_FOUND);
+    storage
+        .upload_file("/BlogStorage/media/files/late.txt", "late")
+        .await
+        .unwrap();
+    assert_eq!(status("/media/files/late.txt").await, StatusCode::NOT_FOUND);
+
+    // Any upload forgets the missing media paths
+    let mut png = Vec::new();
+    image::RgbImage::new(2, 2)
+        .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .unwrap();
+    let mut body = b"--BOUNDARY\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.png\"\r\nContent-Type: image/png\r\n\r\n".to_vec();
+    body.extend_from_slice(&png);
+    body.extend_from_slice(b"\r\n--BOUNDARY--\r\n");
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/api/media/upload")
+                .header(
+                    header::CONTENT_TYPE,
+                    "multipart/form-data; boundary=BOUNDARY",
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(status("/media/files/late.txt").await, StatusCode::OK);
+}
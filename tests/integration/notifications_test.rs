@@ -0,0 +1,91 @@
+use axum::{extract::State, routing::post, Json, Router};
+use std::sync::{Arc, Mutex};
+use tobelog::models::{CreatePost, NotificationTarget};
+use tobelog::services::notifications::NotificationEvent;
+use tobelog::services::{NotificationConfig, NotificationService};
+
+use super::{create_post, test_database};
+
+type Received = Arc<Mutex<Vec<serde_json::Value>>>;
+
+/// Local webhook endpoint recording the payloads it receives
+async fn webhook_server() -> (String, Received) {
+    let received = Received::default();
+    let app = Router::new()
+        .route(
+            "/hook",
+            post(
+                |State(received): State<Received>, Json(body): Json<serde_json::Value>| async move {
+                    received.lock().unwrap().push(body);
+                },
+            ),
+        )
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, received)
+}
+
+#[tokio::test]
+async fn test_公開した記事をスラックに通知する() {
+    let (_temp_dir, database) = test_database().await;
+    let (url, received) = webhook_server().await;
+
+    let service = NotificationService::new(
+        database.clone(),
+        NotificationConfig {
+            site_url: "https://blog.example".to_string(),
+            slack_webhook_url: Some(url),
+            discord_webhook_url: None,
+            on_publish: true,
+            on_scheduled_publish: false,
+        },
+    );
+
+    let post = database
+        .create_post(CreatePost {
+            excerpt: Some("記事の概要".to_string()),
+            ..create_post("hello")
+        })
+        .await
+        .unwrap();
+    let results = service
+        .notify_post(&post, NotificationEvent::Published)
+        .await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].target, NotificationTarget::Slack);
+    assert!(results[0].delivered);
+
+    let text = received.lock().unwrap()[0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(text.contains(&format!(
+        "<https://blog.example{}|Post hello>",
+        post.get_url_path()
+    )));
+    assert!(text.contains("記事の概要"));
+
+    // 予約投稿の通知はオフ、下書きは通知しない
+    assert!(service
+        .notify_post(&post, NotificationEvent::ScheduledPublished)
+        .await
+        .is_empty());
+    let draft = database
+        .create_post(CreatePost {
+            published: false,
+            ..create_post("draft")
+        })
+        .await
+        .unwrap();
+    assert!(service
+        .notify_post(&draft, NotificationEvent::Published)
+        .await
+        .is_empty());
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    let results = service.send_test().await;
+    assert!(results[0].delivered);
+    assert_eq!(received.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,67 @@
+use serde_json::Value;
+use tobelog::handlers::openapi::ApiDoc;
+use utoipa::OpenApi;
+
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                refs.push(reference.clone());
+            }
+            map.values().for_each(|value| collect_refs(value, refs));
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_refs(value, refs)),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_仕様書が主要なエンドポイントを含む() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).expect("Failed to serialize spec");
+
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+    for path in [
+        "/api/posts",
+        "/api/posts/{slug}",
+        "/api/search",
+        "/api/media/upload",
+        "/api/media/{id}",
+        "/api/import/llm-article",
+        "/api/import/batch",
+        "/api/themes/{name}",
+        "/api/posts/{slug}/versions",
+        "/api/posts/{slug}/restore/{version}",
+    ] {
+        assert!(spec["paths"].get(path).is_some(), "{} is missing", path);
+    }
+
+    // 更新系は API キーが必要
+    assert_eq!(
+        spec["paths"]["/api/posts"]["post"]["security"][0]["api_key"],
+        serde_json::json!([])
+    );
+    assert!(spec["paths"]["/api/posts"]["get"].get("security").is_none());
+    assert_eq!(
+        spec["components"]["securitySchemes"]["api_key"]["name"],
+        "X-API-Key"
+    );
+}
+
+#[test]
+fn test_参照されるスキーマがすべて定義されている() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).expect("Failed to serialize spec");
+    let mut refs = Vec::new();
+    collect_refs(&spec, &mut refs);
+    assert!(!refs.is_empty());
+
+    for reference in refs {
+        let name = reference
+            .strip_prefix("#/components/schemas/")
+            .unwrap_or_else(|| panic!("Unexpected reference {}", reference));
+        assert!(
+            spec["components"]["schemas"].get(name).is_some(),
+            "Schema {} is referenced but not defined",
+            name
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use tobelog::models::{CreatePost, PostVisibility};
+use tobelog::services::pdf::{self, PdfConfig, PdfService};
+use tobelog::services::TemplateService;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_公開記事だけがpdfに書き出される() {
+    let (temp_dir, database) = test_database().await;
+
+    let public = database.create_post(create_post("public")).await.unwrap();
+    let private = database.create_post(create_post("private")).await.unwrap();
+    database
+        .set_post_visibility(private.id, PostVisibility::Private)
+        .await
+        .unwrap();
+    let protected = database
+        .create_post(create_post("protected"))
+        .await
+        .unwrap();
+    database
+        .set_post_password(protected.id, Some("hash"))
+        .await
+        .unwrap();
+    database
+        .create_post(CreatePost {
+            published: false,
+            ..create_post("draft")
+        })
+        .await
+        .unwrap();
+
+    assert!(pdf::is_exportable(&public));
+    for slug in ["private", "protected", "draft"] {
+        let post = database.get_post_by_slug(slug).await.unwrap().unwrap();
+        assert!(!pdf::is_exportable(&post), "{} が書き出し対象です", slug);
+    }
+
+    let pdfs = PdfService::new(
+        database,
+        TemplateService::new_with_theme("default").expect("Failed to load templates"),
+        "https://blog.example.com".to_string(),
+        PdfConfig {
+            chromium_path: "/nonexistent/chromium".to_string(),
+            cache_dir: temp_dir.path().join("pdf"),
+        },
+    );
+
+    // 非公開の記事はChromiumを起動する前に見つからない扱いになる
+    for slug in ["private", "protected", "draft", "missing"] {
+        assert!(pdfs.get_post_pdf(slug).await.unwrap().is_none());
+    }
+    assert!(pdfs.get_post_pdf("public").await.is_err());
+}
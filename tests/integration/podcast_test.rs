@@ -0,0 +1,84 @@
+use chrono::Utc;
+use tobelog::models::{CreatePost, MediaFile};
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+fn audio_media_file() -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        filename: "episode_abcd1234.mp3".to_string(),
+        original_filename: "episode.mp3".to_string(),
+        dropbox_path: "/BlogStorage/media/audio/2024/07/episode_abcd1234.mp3".to_string(),
+        url: "/media/audio/2024/07/episode_abcd1234.mp3".to_string(),
+        file_size: 4_096_000,
+        mime_type: "audio/mpeg".to_string(),
+        width: None,
+        height: None,
+        uploaded_at: Utc::now(),
+        thumbnail_url: None,
+        alt_text: None,
+        caption: None,
+        content_hash: None,
+        duration_seconds: Some(1800.0),
+        alt_text_suggestion: None,
+        collection: None,
+        focal_point: None,
+    }
+}
+
+#[tokio::test]
+async fn test_音声を添付した公開記事だけがポッドキャストに含まれる() {
+    let (_temp_dir, database) = test_database().await;
+
+    let published = database
+        .create_post(create_post("published"))
+        .await
+        .expect("Failed to create post");
+    let draft = database
+        .create_post(CreatePost {
+            published: false,
+            ..create_post("draft")
+        })
+        .await
+        .expect("Failed to create post");
+    database
+        .create_post(create_post("no-audio"))
+        .await
+        .expect("Failed to create post");
+
+    let media = audio_media_file();
+    database
+        .create_media_file(&media)
+        .await
+        .expect("Failed to create media file");
+
+    for post_id in [published.id, draft.id] {
+        database
+            .set_post_audio(post_id, media.id, "audio/mpeg", Some(1800.0))
+            .await
+            .expect("Failed to attach audio");
+    }
+
+    let audio = database
+        .get_post_audio(published.id)
+        .await
+        .unwrap()
+        .expect("audio exists");
+    assert_eq!(audio.url, media.url);
+    assert_eq!(audio.file_size, media.file_size);
+    assert_eq!(audio.formatted_duration().as_deref(), Some("00:30:00"));
+
+    let episodes = database.list_podcast_episodes(10).await.unwrap();
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(episodes[0].0.slug, "published");
+    assert_eq!(episodes[0].1.media_id, media.id);
+
+    assert!(database.remove_post_audio(published.id).await.unwrap());
+    assert!(database
+        .get_post_audio(published.id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(!database.remove_post_audio(published.id).await.unwrap());
+}
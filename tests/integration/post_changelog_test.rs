@@ -0,0 +1,46 @@
+use tobelog::services::{MarkdownService, VersionService};
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_変更概要から新しい順の変更履歴を作る() {
+    let (_temp_dir, database) = test_database().await;
+    let versions = VersionService::new(database.clone(), MarkdownService::new());
+
+    let mut post = database
+        .create_post(create_post("long-lived"))
+        .await
+        .expect("Failed to create post");
+    assert!(!post.show_changelog);
+
+    // 概要のない版と空白だけの版は変更履歴に載らない
+    for (version, summary) in [
+        (1, Some("初版を公開")),
+        (2, None),
+        (3, Some("  ")),
+        (4, Some("Rust 1.80 に対応")),
+    ] {
+        post.version = version;
+        versions
+            .create_version(&post, summary.map(str::to_string))
+            .await
+            .expect("Failed to create version");
+    }
+
+    let changelog = versions.changelog(&post).await.unwrap();
+    assert_eq!(changelog.post_slug, "long-lived");
+    assert_eq!(changelog.last_updated, post.updated_at);
+    let entries: Vec<(i32, &str)> = changelog
+        .entries
+        .iter()
+        .map(|entry| (entry.version, entry.summary.as_str()))
+        .collect();
+    assert_eq!(entries, vec![(4, "Rust 1.80 に対応"), (1, "初版を公開")]);
+
+    assert!(database
+        .set_post_show_changelog(post.id, true)
+        .await
+        .unwrap());
+    let post = database.get_post_by_id(post.id).await.unwrap().unwrap();
+    assert!(post.show_changelog);
+}
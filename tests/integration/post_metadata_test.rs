@@ -0,0 +1,81 @@
+use tobelog::models::{CreatePost, UpdatePostMetadata};
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_メタデータ更新は本文の更新日時とバージョンを変えない() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database
+        .create_post(CreatePost {
+            title: "Metadata".to_string(),
+            content: "Body".to_string(),
+            html_content: "<p>Body</p>".to_string(),
+            category: Some("Rust".to_string()),
+            tags: vec!["old".to_string()],
+            published: false,
+            ..create_post("metadata")
+        })
+        .await
+        .expect("Failed to create post");
+
+    let updated = database
+        .update_post_metadata(
+            post.id,
+            UpdatePostMetadata {
+                category: Some("Web".to_string()),
+                tags: Some(vec!["new".to_string(), "api".to_string()]),
+                featured: Some(true),
+                published: Some(true),
+            },
+        )
+        .await
+        .expect("Failed to update metadata")
+        .expect("Post not found");
+    assert!(updated.metadata_updated_at.is_some());
+
+    let stored = database
+        .get_post_by_slug("metadata")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.category.as_deref(), Some("Web"));
+    assert_eq!(stored.get_tags(), vec!["new", "api"]);
+    assert!(stored.featured);
+    assert!(stored.published && stored.published_at.is_some());
+    assert_eq!(stored.updated_at, post.updated_at);
+    assert_eq!(stored.version, post.version);
+    assert_eq!(stored.content_hash, post.content_hash);
+    assert_eq!(stored.html_content, post.html_content);
+    assert_eq!(stored.metadata_updated_at, updated.metadata_updated_at);
+
+    // 空文字のカテゴリはカテゴリを外す
+    database
+        .update_post_metadata(
+            post.id,
+            UpdatePostMetadata {
+                category: Some(String::new()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let stored = database
+        .get_post_by_slug("metadata")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.category, None);
+    assert_eq!(stored.get_tags(), vec!["new", "api"]);
+}
+
+#[tokio::test]
+async fn test_存在しない記事のメタデータ更新はnoneを返す() {
+    let (_temp_dir, database) = test_database().await;
+    let result = database
+        .update_post_metadata(Uuid::new_v4(), UpdatePostMetadata::default())
+        .await
+        .expect("Failed to update metadata");
+    assert!(result.is_none());
+}
@@ -0,0 +1,59 @@
+use tobelog::models::CreatePost;
+use tobelog::services::outline::{add_heading_anchors, heading_count};
+use tobelog::services::template::{PostData, PostPageContext, ReadingProgress};
+use tobelog::services::{MarkdownService, TemplateService};
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_見出しにアンカーが付き目次と読書進捗の属性が描画される() {
+    let (_temp_dir, database) = test_database().await;
+
+    let content = "## はじめに\n\n本文\n\n### 準備\n\n本文\n\n## Getting Started\n\n本文\n";
+    let html_content = MarkdownService::new().markdown_to_html(content).unwrap();
+    let post = database
+        .create_post(CreatePost {
+            title: "目次のある記事".to_string(),
+            content: content.to_string(),
+            html_content,
+            ..create_post("outline")
+        })
+        .await
+        .unwrap();
+
+    let (html_content, outline) = add_heading_anchors(&post.html_content);
+    assert_eq!(heading_count(&outline), 3);
+    assert_eq!(outline[0].anchor, "はじめに");
+    assert_eq!(outline[0].children[0].anchor, "準備");
+    assert_eq!(outline[1].anchor, "getting-started");
+
+    let reading_progress = ReadingProgress {
+        word_count: post.word_count,
+        reading_time: post.reading_time(),
+        sections: heading_count(&outline),
+        outline_url: "/api/posts/outline/outline".to_string(),
+    };
+    let context = PostPageContext {
+        site_title: "Tobelog".to_string(),
+        site_description: String::new(),
+        post: PostData {
+            html_content,
+            ..PostData::from(post)
+        },
+        og_image_url: None,
+        canonical_url: None,
+        audio: None,
+        changelog: None,
+        attachments: vec![],
+        pdf_url: None,
+        outline,
+        reading_progress,
+    };
+
+    let templates = TemplateService::new_with_theme("default").expect("Failed to load templates");
+    let html = templates.render("post.html", &context).unwrap();
+    assert!(html.contains(r#"<h2 id="getting-started">Getting Started"#));
+    assert!(html.contains(r#"data-toc-anchor="準備""#));
+    assert!(html.contains(r#"data-sections="3""#));
+    assert!(html.contains("data-outline-url="));
+}
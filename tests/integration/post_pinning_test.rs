@@ -0,0 +1,59 @@
+use tobelog::models::PostFilters;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_固定記事が時系列より先に並ぶ() {
+    let (_temp_dir, database) = test_database().await;
+
+    let mut ids = Vec::new();
+    for slug in ["oldest", "older", "newer", "newest"] {
+        let post = database
+            .create_post(create_post(slug))
+            .await
+            .expect("Failed to create post");
+        ids.push(post.id);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    // 順序なしの固定は順序付きの固定の後に並ぶ
+    database.set_post_pin(ids[0], true, None).await.unwrap();
+    database.set_post_pin(ids[1], true, Some(1)).await.unwrap();
+
+    let slugs = |posts: Vec<tobelog::models::Post>| -> Vec<String> {
+        posts.into_iter().map(|post| post.slug).collect()
+    };
+
+    let pinned_first = database
+        .list_posts(PostFilters {
+            pinned_first: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        slugs(pinned_first),
+        vec!["older", "oldest", "newest", "newer"]
+    );
+
+    let chronological = database.list_posts(PostFilters::default()).await.unwrap();
+    assert_eq!(
+        slugs(chronological),
+        vec!["newest", "newer", "older", "oldest"]
+    );
+
+    let pinned_count = database
+        .count_posts(PostFilters {
+            pinned: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(pinned_count, 2);
+
+    // 固定を外すと順序も消える
+    database.set_post_pin(ids[1], false, Some(1)).await.unwrap();
+    let post = database.get_post_by_id(ids[1]).await.unwrap().unwrap();
+    assert!(!post.pinned);
+    assert_eq!(post.pin_order, None);
+}
@@ -0,0 +1,70 @@
+use tobelog::models::{PostFilters, PostVisibility, SearchFilters};
+
+use super::{create_post, test_database};
+
+fn slugs(posts: &[tobelog::models::Post]) -> Vec<&str> {
+    let mut slugs: Vec<&str> = posts.iter().map(|post| post.slug.as_str()).collect();
+    slugs.sort();
+    slugs
+}
+
+#[tokio::test]
+async fn test_限定公開と非公開の記事は一覧と検索から除外される() {
+    let (_temp_dir, database) = test_database().await;
+
+    for (slug, visibility) in [
+        ("public", PostVisibility::Public),
+        ("unlisted", PostVisibility::Unlisted),
+        ("private", PostVisibility::Private),
+    ] {
+        let post = database
+            .create_post(create_post(slug))
+            .await
+            .expect("Failed to create post");
+        assert_eq!(post.visibility, PostVisibility::Public);
+        assert!(database
+            .set_post_visibility(post.id, visibility)
+            .await
+            .unwrap());
+    }
+
+    let unlisted = database
+        .get_post_by_slug("unlisted")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unlisted.visibility, PostVisibility::Unlisted);
+
+    // 管理画面など、フィルタなしではすべての記事が返る
+    let all = database.list_posts(PostFilters::default()).await.unwrap();
+    assert_eq!(all.len(), 3);
+
+    let anonymous = PostFilters {
+        visibility: Some(PostVisibility::listed(false)),
+        ..Default::default()
+    };
+    let posts = database.list_posts(anonymous.clone()).await.unwrap();
+    assert_eq!(slugs(&posts), vec!["public"]);
+    assert_eq!(database.count_posts(anonymous).await.unwrap(), 1);
+
+    let member = PostFilters {
+        visibility: Some(PostVisibility::listed(true)),
+        ..Default::default()
+    };
+    let posts = database.list_posts(member).await.unwrap();
+    assert_eq!(slugs(&posts), vec!["private", "public"]);
+
+    let search = SearchFilters {
+        visibility: Some(PostVisibility::listed(false)),
+        ..Default::default()
+    };
+    let posts = database.search_posts("Content", &search).await.unwrap();
+    assert_eq!(slugs(&posts), vec!["public"]);
+    assert_eq!(
+        database
+            .count_search_results("Content", &search)
+            .await
+            .unwrap(),
+        1
+    );
+}
@@ -0,0 +1,290 @@
+use axum::body::{to_bytes, Body};
+use axum::extract::{Path, State};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use tobelog::handlers::api;
+use tobelog::handlers::version::{publish_version, VersionState};
+use tobelog::models::{CreatePost, UpdatePost, VersionRetentionPolicy};
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::{
+    CacheService, FeedService, MarkdownService, StorageBackend, VersionService,
+};
+use tower::ServiceExt;
+
+use super::mock_storage_test::fixture_state;
+use super::{create_post, test_database};
+
+async fn setup() -> (tempfile::TempDir, VersionState) {
+    let (temp_dir, database) = test_database().await;
+    let state = VersionState {
+        version_service: VersionService::new(database.clone(), MarkdownService::new()),
+        database,
+        cache: CacheService::new(),
+        retention: VersionRetentionPolicy::default(),
+    };
+    (temp_dir, state)
+}
+
+async fn edit(state: &VersionState, slug: &str, content: &str) {
+    let post = state
+        .database
+        .get_post_by_slug(slug)
+        .await
+        .unwrap()
+        .unwrap();
+    let versions = &state.version_service;
+    if versions
+        .get_version(post.id, post.version)
+        .await
+        .unwrap()
+        .is_none()
+    {
+        versions.create_version(&post, None).await.unwrap();
+    }
+    state
+        .database
+        .update_post(
+            post.id,
+            UpdatePost {
+                content: Some(content.to_string()),
+                html_content: Some(format!("<p>{}</p>", content)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_公開バージョンを固定したまま下書きを進められる() {
+    let (_temp_dir, state) = setup().await;
+    state
+        .database
+        .create_post(CreatePost {
+            title: "Pinned".to_string(),
+            content: "公開中の本文".to_string(),
+            html_content: "<p>公開中の本文</p>".to_string(),
+            ..create_post("pinned")
+        })
+        .await
+        .unwrap();
+
+    // 最新版を固定するとスナップショットが保存される
+    let response = publish_version(Path(("pinned".to_string(), 1)), State(state.clone()))
+        .await
+        .expect("Failed to publish version")
+        .0;
+    assert_eq!(response.published_version, 1);
+    assert_eq!(response.latest_version, 1);
+
+    edit(&state, "pinned", "下書き 1").await;
+    edit(&state, "pinned", "下書き 2").await;
+
+    let mut post = state
+        .database
+        .get_post_by_slug("pinned")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.version, 3);
+    assert_eq!(post.published_version, Some(1));
+
+    // 公開ページは固定したバージョンの本文で表示する
+    let pinned = state
+        .version_service
+        .get_version(post.id, 1)
+        .await
+        .unwrap()
+        .expect("Pinned version should be stored");
+    let latest_hash = post.content_hash.clone();
+    post.show_version(&pinned);
+    assert_eq!(post.content, "公開中の本文");
+    assert_eq!(post.html_content, "<p>公開中の本文</p>");
+    assert_ne!(post.content_hash, latest_hash);
+
+    // 保持ポリシーでも固定中のバージョンは削除しない
+    state
+        .version_service
+        .cleanup_old_versions(post.id, 1)
+        .await
+        .unwrap();
+    assert!(state
+        .version_service
+        .get_version(post.id, 1)
+        .await
+        .unwrap()
+        .is_some());
+
+    // 固定を進める
+    let response = publish_version(Path(("pinned".to_string(), 2)), State(state.clone()))
+        .await
+        .expect("Failed to advance the pin")
+        .0;
+    assert_eq!(response.published_version, 2);
+    assert_eq!(response.latest_version, 3);
+    let post = state
+        .database
+        .get_post_by_slug("pinned")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.published_version, Some(2));
+}
+
+#[tokio::test]
+async fn test_存在しないバージョンは固定できない() {
+    let (_temp_dir, state) = setup().await;
+    let (status, _) = publish_version(Path(("missing".to_string(), 1)), State(state.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    state
+        .database
+        .create_post(CreatePost {
+            title: "Unpinned".to_string(),
+            content: "本文".to_string(),
+            html_content: "<p>本文</p>".to_string(),
+            ..create_post("unpinned")
+        })
+        .await
+        .unwrap();
+    let (status, _) = publish_version(Path(("unpinned".to_string(), 5)), State(state.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let post = state
+        .database
+        .get_post_by_slug("unpinned")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.published_version, None);
+}
+
+#[tokio::test]
+async fn test_apiとフィードも固定したバージョンを返す() {
+    let api_state = fixture_state(MemoryStorage::new()).await;
+    let database = api_state.database.clone();
+    let state = VersionState {
+        version_service: VersionService::new(database.clone(), MarkdownService::new()),
+        database: database.clone(),
+        cache: CacheService::new(),
+        retention: VersionRetentionPolicy::default(),
+    };
+    database
+        .create_post(CreatePost {
+            title: "公開中のタイトル".to_string(),
+            content: "公開中の本文".to_string(),
+            html_content: "<p>公開中の本文</p>".to_string(),
+            excerpt: Some("公開中の抜粋".to_string()),
+            tags: vec!["rust".to_string()],
+            ..create_post("pinned")
+        })
+        .await
+        .unwrap();
+    let response = publish_version(Path(("pinned".to_string(), 1)), State(state.clone()))
+        .await
+        .expect("Failed to publish version");
+    assert_eq!(response.0.published_version, 1);
+    let post = database.get_post_by_slug("pinned").await.unwrap().unwrap();
+    database
+        .update_post(
+            post.id,
+            UpdatePost {
+                title: Some("下書きのタイトル".to_string()),
+                content: Some("下書きの本文".to_string()),
+                html_content: Some("<p>下書きの本文</p>".to_string()),
+                excerpt: Some("下書きの抜粋".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let app = Router::new()
+        .route("/api/posts", get(api::list_posts_api))
+        .route("/api/posts/:slug", get(api::get_post_api))
+        .route("/api/posts/:slug/outline", get(api::get_post_outline_api))
+        .with_state(api_state);
+    for uri in [
+        "/api/posts",
+        "/api/posts/pinned",
+        "/api/posts/pinned/outline",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(raw.to_vec()).unwrap();
+        assert!(body.contains("公開中のタイトル"), "{}: {}", uri, body);
+        assert!(!body.contains("下書き"), "{}: {}", uri, body);
+    }
+
+    let feeds = FeedService::new(database, "https://blog.example.com".to_string());
+    let xml = feeds.tag_feed("rust").await.unwrap().expect("tag feed");
+    assert!(xml.contains("<title>公開中のタイトル</title>"));
+    assert!(xml.contains("公開中の抜粋"));
+    assert!(!xml.contains("下書き"));
+}
+
+#[tokio::test]
+async fn test_固定したバージョンのmarkdownソースを返す() {
+    let storage = MemoryStorage::new();
+    storage
+        .upload_file(
+            "/test/pinned.md",
+            "---\ntitle: 下書きのタイトル\n---\n下書きの本文",
+        )
+        .await
+        .unwrap();
+    let mut api_state = fixture_state(storage).await;
+    api_state.api_key = Some("secret".to_string());
+    let database = api_state.database.clone();
+    let state = VersionState {
+        version_service: VersionService::new(database.clone(), MarkdownService::new()),
+        database: database.clone(),
+        cache: CacheService::new(),
+        retention: VersionRetentionPolicy::default(),
+    };
+    database
+        .create_post(CreatePost {
+            content: "公開中の本文".to_string(),
+            ..create_post("pinned")
+        })
+        .await
+        .unwrap();
+    let response = publish_version(Path(("pinned".to_string(), 1)), State(state.clone()))
+        .await
+        .expect("Failed to publish version");
+    assert_eq!(response.0.published_version, 1);
+    edit(&state, "pinned", "下書きの本文").await;
+
+    let app = Router::new()
+        .route("/api/posts/:slug/raw", get(api::get_post_source_api))
+        .with_state(api_state);
+    let fetch = |api_key: Option<&'static str>| {
+        let app = app.clone();
+        async move {
+            let mut request = Request::get("/api/posts/pinned/raw");
+            if let Some(api_key) = api_key {
+                request = request.header("X-API-Key", api_key);
+            }
+            let response = app
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            String::from_utf8(raw.to_vec()).unwrap()
+        }
+    };
+
+    // 読者には固定したバージョン、API キーがあれば Dropbox の最新の下書き
+    assert_eq!(fetch(None).await, "公開中の本文");
+    assert!(fetch(Some("secret")).await.contains("下書きの本文"));
+}
@@ -0,0 +1,75 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, HeaderMap, Request, StatusCode},
+    routing::put,
+    Json, Router,
+};
+use std::time::Duration;
+use tobelog::handlers::api;
+use tobelog::handlers::version::{restore_version, VersionState};
+use tobelog::models::{RestoreVersionRequest, VersionRetentionPolicy};
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::{CacheService, CachedResponse, MarkdownService, VersionService};
+use tower::ServiceExt;
+
+use super::create_post;
+use super::mock_storage_test::fixture_state;
+
+async fn cache_page(cache: &CacheService) {
+    let response = CachedResponse::new(
+        StatusCode::OK,
+        HeaderMap::new(),
+        Bytes::from("<html>Post</html>"),
+        Duration::from_secs(60),
+    );
+    cache.set_response("/posts/2024/cached", response).await;
+}
+
+#[tokio::test]
+async fn test_投稿の更新と復元でキャッシュしたページを破棄する() {
+    let state = fixture_state(MemoryStorage::new()).await;
+    let database = state.database.clone();
+    let cache = state.cache.clone();
+    let post = database.create_post(create_post("cached")).await.unwrap();
+    let versions = VersionService::new(database.clone(), MarkdownService::new());
+    versions.create_version(&post, None).await.unwrap();
+    let app = Router::new()
+        .route("/api/posts/:slug", put(api::update_post_api))
+        .with_state(state);
+
+    cache_page(&cache).await;
+    let response = app
+        .oneshot(
+            Request::put("/api/posts/cached")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"content": "Edited"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(cache.get_response("/posts/2024/cached").await.is_none());
+
+    cache_page(&cache).await;
+    let version_state = VersionState {
+        version_service: versions,
+        database: database.clone(),
+        cache: cache.clone(),
+        retention: VersionRetentionPolicy::default(),
+    };
+    let response = restore_version(
+        Path(("cached".to_string(), 1)),
+        State(version_state),
+        Json(RestoreVersionRequest {
+            target_version: 1,
+            change_summary: None,
+        }),
+    )
+    .await
+    .expect("Failed to restore version");
+    assert!(response.0.success);
+    assert!(cache.get_response("/posts/2024/cached").await.is_none());
+    let restored = database.get_post_by_slug("cached").await.unwrap().unwrap();
+    assert_eq!(restored.content, "Content");
+}
@@ -0,0 +1,120 @@
+use chrono::{Duration, Utc};
+use futures_util::StreamExt;
+use tobelog::models::{AdminEvent, CreatePost, ExpiryAction, PostVisibility};
+use tobelog::services::{EventBus, ScheduledPublishService};
+
+use super::{create_post, test_database};
+
+fn draft(slug: &str) -> CreatePost {
+    CreatePost {
+        title: format!("Title of {}", slug),
+        content: "content".to_string(),
+        html_content: "<p>content</p>".to_string(),
+        published: false,
+        dropbox_path: format!("/BlogStorage/drafts/{}.md", slug),
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_予約投稿の公開と管理画面への通知() {
+    let (_temp_dir, database) = test_database().await;
+
+    let due = database.create_post(draft("due-post")).await.unwrap();
+    let later = database.create_post(draft("later-post")).await.unwrap();
+    database
+        .set_post_publish_at(due.id, Some(Utc::now() - Duration::minutes(1)))
+        .await
+        .unwrap();
+    database
+        .set_post_publish_at(later.id, Some(Utc::now() + Duration::hours(1)))
+        .await
+        .unwrap();
+
+    let events = EventBus::new();
+    let mut received = Box::pin(events.subscribe());
+    let service = ScheduledPublishService::new(database.clone(), events);
+
+    let published = service.publish_due().await.unwrap();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].slug, "due-post");
+
+    let due = database.get_post_by_id(due.id).await.unwrap().unwrap();
+    assert!(due.published);
+    assert!(due.publish_at.is_none());
+    let later = database.get_post_by_id(later.id).await.unwrap().unwrap();
+    assert!(!later.published);
+
+    match received.next().await {
+        Some(AdminEvent::ScheduledPostPublished { slug, .. }) => assert_eq!(slug, "due-post"),
+        other => panic!("Unexpected event: {:?}", other),
+    }
+
+    // 公開済みの記事は二度公開されない
+    assert!(service.publish_due().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_期限切れ記事の非公開化とアーカイブ() {
+    let (_temp_dir, database) = test_database().await;
+
+    let published = |slug: &str| CreatePost {
+        published: true,
+        ..draft(slug)
+    };
+    let event = database.create_post(published("event-post")).await.unwrap();
+    let guide = database.create_post(published("guide-post")).await.unwrap();
+    let upcoming = database
+        .create_post(published("upcoming-post"))
+        .await
+        .unwrap();
+    let past = Utc::now() - Duration::minutes(1);
+    database
+        .set_post_expiry(event.id, Some(past), ExpiryAction::Unpublish, None)
+        .await
+        .unwrap();
+    database
+        .set_post_expiry(guide.id, Some(past), ExpiryAction::Archive, None)
+        .await
+        .unwrap();
+    database
+        .set_post_expiry(
+            upcoming.id,
+            Some(Utc::now() + Duration::days(3)),
+            ExpiryAction::Unpublish,
+            Some(7),
+        )
+        .await
+        .unwrap();
+
+    let events = EventBus::new();
+    let mut received = Box::pin(events.subscribe());
+    let service = ScheduledPublishService::new(database.clone(), events);
+
+    let expired = service.expire_due().await.unwrap();
+    assert_eq!(expired.len(), 2);
+
+    let event = database.get_post_by_id(event.id).await.unwrap().unwrap();
+    assert!(!event.published);
+    assert!(event.expires_at.is_none());
+
+    // アーカイブした記事は公開のまま一覧から外れ、古い内容である旨を表示する
+    let guide = database.get_post_by_id(guide.id).await.unwrap().unwrap();
+    assert!(guide.published);
+    assert_eq!(guide.visibility, PostVisibility::Unlisted);
+    assert!(guide.shows_outdated_notice(Utc::now()));
+
+    // 期限の7日前からは告知を表示するが、まだ公開のまま
+    let upcoming = database.get_post_by_id(upcoming.id).await.unwrap().unwrap();
+    assert!(upcoming.published);
+    assert!(upcoming.shows_outdated_notice(Utc::now()));
+    assert!(!upcoming.shows_outdated_notice(Utc::now() - Duration::days(5)));
+
+    match received.next().await {
+        Some(AdminEvent::PostExpired { .. }) => {}
+        other => panic!("Unexpected event: {:?}", other),
+    }
+
+    // 処理済みの記事は再び期限切れとして扱わない
+    assert!(service.expire_due().await.unwrap().is_empty());
+}
@@ -0,0 +1,131 @@
+use axum::body::to_bytes;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use tobelog::handlers::feed::{serve_series_feed, serve_tag_feed};
+use tobelog::models::{CreatePost, PostVisibility};
+use tobelog::services::{DatabaseService, FeedService};
+use uuid::Uuid;
+
+use super::{create_post, test_database};
+
+async fn setup() -> (tempfile::TempDir, DatabaseService, FeedService) {
+    let (temp_dir, database) = test_database().await;
+    let feeds = FeedService::new(database.clone(), "https://blog.example.com".to_string());
+    (temp_dir, database, feeds)
+}
+
+async fn create(database: &DatabaseService, slug: &str, tags: &[&str], published: bool) -> Uuid {
+    database
+        .create_post(CreatePost {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            published,
+            ..create_post(slug)
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+async fn body_text(response: axum::response::Response) -> String {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_タグのフィードには公開記事だけが載る() {
+    let (_temp_dir, database, feeds) = setup().await;
+    create(&database, "rust-public", &["rust"], true).await;
+    create(&database, "rust-draft", &["rust"], false).await;
+    create(&database, "go-public", &["go"], true).await;
+    let unlisted = create(&database, "rust-unlisted", &["rust"], true).await;
+    database
+        .set_post_visibility(unlisted, PostVisibility::Unlisted)
+        .await
+        .unwrap();
+    let protected = create(&database, "rust-protected", &["rust"], true).await;
+    database
+        .set_post_password(protected, Some("hash"))
+        .await
+        .unwrap();
+
+    let response = serve_tag_feed(Path("rust".to_string()), State(feeds))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/rss+xml; charset=utf-8"
+    );
+
+    let xml = body_text(response).await;
+    assert!(xml.contains("https://blog.example.com/tag/rust/feed.xml"));
+    assert!(xml.contains("Post rust-public"));
+    assert!(!xml.contains("rust-draft"));
+    assert!(!xml.contains("go-public"));
+    assert!(!xml.contains("rust-unlisted"));
+    assert!(!xml.contains("rust-protected"));
+}
+
+#[tokio::test]
+async fn test_シリーズのフィードはフロントマターのseriesで絞り込む() {
+    let (_temp_dir, database, feeds) = setup().await;
+    let first = create(&database, "intro-part-1", &[], true).await;
+    database
+        .set_post_custom_frontmatter(first, Some("series: intro\n"))
+        .await
+        .unwrap();
+    let other = create(&database, "introduction-other", &[], true).await;
+    database
+        .set_post_custom_frontmatter(other, Some("series: introduction\n"))
+        .await
+        .unwrap();
+
+    let response = serve_series_feed(Path("intro".to_string()), State(feeds.clone()))
+        .await
+        .unwrap();
+    let xml = body_text(response).await;
+    assert!(xml.contains("Post intro-part-1"));
+    assert!(!xml.contains("introduction-other"));
+
+    let (status, _) = serve_series_feed(Path("missing".to_string()), State(feeds))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_記事のないタグのフィードは404() {
+    let (_temp_dir, _database, feeds) = setup().await;
+
+    let (status, _) = serve_tag_feed(Path("nothing".to_string()), State(feeds))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_パスワード付き記事はタグのフィードの件数を使わない() {
+    let (_temp_dir, database, feeds) = setup().await;
+    create(&database, "rust-old", &["rust async"], true).await;
+    for index in 0..50 {
+        let protected = create(
+            &database,
+            &format!("locked-{}", index),
+            &["rust async"],
+            true,
+        )
+        .await;
+        database
+            .set_post_password(protected, Some("hash"))
+            .await
+            .unwrap();
+    }
+
+    let response = serve_tag_feed(Path("rust async".to_string()), State(feeds))
+        .await
+        .unwrap();
+    let xml = body_text(response).await;
+    assert!(xml.contains("Post rust-old"));
+    assert!(!xml.contains("locked-"));
+    assert!(xml.contains("https://blog.example.com/tag/rust%20async/feed.xml"));
+}
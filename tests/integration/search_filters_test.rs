@@ -0,0 +1,66 @@
+use tobelog::models::{CreatePost, SearchFilters};
+
+use super::{create_post, test_database};
+
+fn rust_post(slug: &str, category: &str, tags: &[&str], published: bool) -> CreatePost {
+    CreatePost {
+        content: "Rust search filters".to_string(),
+        html_content: "<p>Rust search filters</p>".to_string(),
+        category: Some(category.to_string()),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        published,
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_全文検索とフィルタを組み合わせて検索できる() {
+    let (_temp_dir, database) = test_database().await;
+
+    for post in [
+        rust_post("tech-1", "tech", &["rust", "web"], true),
+        rust_post("tech-2", "tech", &["rust"], true),
+        rust_post("tech-draft", "tech", &["rust"], false),
+        rust_post("life-1", "life", &["rust"], true),
+    ] {
+        database
+            .create_post(post)
+            .await
+            .expect("Failed to create post");
+    }
+
+    let filters = SearchFilters {
+        published: Some(true),
+        category: Some("tech".to_string()),
+        tag: Some("rust".to_string()),
+        limit: Some(1),
+        offset: Some(0),
+        ..Default::default()
+    };
+
+    let posts = database.search_posts("Rust", &filters).await.unwrap();
+    let total = database
+        .count_search_results("Rust", &filters)
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 1, "limitが適用されていません");
+    assert_eq!(total, 2, "総件数はページングに影響されないはずです");
+
+    let web_only = SearchFilters {
+        tag: Some("web".to_string()),
+        ..Default::default()
+    };
+    let posts = database.search_posts("Rust", &web_only).await.unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].slug, "tech-1");
+
+    let future = SearchFilters {
+        from: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+        ..Default::default()
+    };
+    let total = database
+        .count_search_results("Rust", &future)
+        .await
+        .unwrap();
+    assert_eq!(total, 0, "日付範囲外の記事が含まれています");
+}
@@ -0,0 +1,74 @@
+use tempfile::tempdir;
+use tobelog::models::{CreatePost, SearchFilters};
+use tobelog::services::DatabaseService;
+
+use super::create_post;
+
+fn sample_post(slug: &str, title: &str) -> CreatePost {
+    CreatePost {
+        title: title.to_string(),
+        content: format!("{} body", title),
+        html_content: format!("<p>{} body</p>", title),
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_検索インデックスの欠落を検出して再構築できる() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("search_index.db");
+    let database_url = format!("sqlite:{}", db_path.to_str().unwrap());
+    let database = DatabaseService::new(&database_url)
+        .await
+        .expect("Failed to create database");
+
+    for (slug, title) in [("first", "Ferris"), ("second", "Tokio"), ("third", "Axum")] {
+        database
+            .create_post(sample_post(slug, title))
+            .await
+            .expect("Failed to create post");
+    }
+
+    let status = database.check_search_index().await.unwrap();
+    assert!(status.consistent);
+    assert_eq!(status.indexed_count, 3);
+
+    // 手動編集などでインデックスから記事が抜け落ちた状態を再現する
+    let pool = sqlx::SqlitePool::connect(&database_url).await.unwrap();
+    sqlx::query(
+        "INSERT INTO posts_fts(posts_fts, rowid, title, content, excerpt)
+         SELECT 'delete', rowid, title, content, COALESCE(excerpt, '') FROM posts WHERE slug = 'second'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let status = database.check_search_index().await.unwrap();
+    assert!(!status.consistent);
+    assert_eq!(status.post_count, 3);
+    assert_eq!(status.indexed_count, 2);
+    assert_eq!(status.missing_slugs, vec!["second".to_string()]);
+
+    let filters = SearchFilters::default();
+    assert!(database
+        .search_posts("Tokio", &filters)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // バッチサイズ2で再構築する
+    let indexed = database.rebuild_search_index(2).await.unwrap();
+    assert_eq!(indexed, 3);
+
+    let status = database.check_search_index().await.unwrap();
+    assert!(status.consistent);
+    assert!(status.missing_slugs.is_empty());
+    assert_eq!(
+        database
+            .search_posts("Tokio", &filters)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,152 @@
+use tobelog::models::{CategoryStat, CreatePost, SearchFilters};
+use tobelog::services::template::{SearchPageContext, SearchResultSummary};
+use tobelog::services::{DatabaseService, TemplateService};
+
+use super::{create_post, test_database};
+
+fn about(slug: &str, category: &str, content: &str) -> CreatePost {
+    CreatePost {
+        content: content.to_string(),
+        html_content: format!("<p>{}</p>", content),
+        category: Some(category.to_string()),
+        ..create_post(slug)
+    }
+}
+
+#[tokio::test]
+async fn test_検索結果に強調済みの抜粋とカテゴリ別件数が付く() {
+    let (_temp_dir, database) = test_database().await;
+
+    for post in [
+        about("tech-1", "tech", "Learning tokio <runtime> internals"),
+        about("tech-2", "tech", "Why tokio is fast"),
+        about("life-1", "life", "Cooking with tokio on the side"),
+        about("other", "life", "Nothing relevant here"),
+    ] {
+        database
+            .create_post(post)
+            .await
+            .expect("Failed to create post");
+    }
+
+    let query = DatabaseService::fts_match_query("tokio");
+    let filters = SearchFilters {
+        published: Some(true),
+        category: Some("tech".to_string()),
+        ..Default::default()
+    };
+
+    let hits = database
+        .search_posts_with_snippets(&query, &filters)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 2);
+    let snippet = &hits
+        .iter()
+        .find(|hit| hit.post.slug == "tech-1")
+        .unwrap()
+        .snippet_html;
+    assert!(snippet.contains("<mark>tokio</mark>"), "{}", snippet);
+    assert!(
+        snippet.contains("&lt;runtime&gt;"),
+        "本文のHTMLがエスケープされていません: {}",
+        snippet
+    );
+
+    // 絞り込み中のカテゴリに関係なく、すべてのカテゴリの件数を返す
+    let facets = database
+        .search_category_facets(&query, &filters)
+        .await
+        .unwrap();
+    assert_eq!(
+        facets,
+        vec![
+            CategoryStat {
+                name: "tech".to_string(),
+                count: 2
+            },
+            CategoryStat {
+                name: "life".to_string(),
+                count: 1
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_検索語の記号は演算子として解釈されない() {
+    let (_temp_dir, database) = test_database().await;
+    database
+        .create_post(about("quote", "tech", "say \"hello\" world"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        DatabaseService::fts_match_query(" hello  \"world "),
+        "\"hello\" \"\"\"world\""
+    );
+    assert_eq!(DatabaseService::fts_match_query("   "), "");
+
+    for input in ["hello\"", "hello AND", "NOT world", "hello*("] {
+        let query = DatabaseService::fts_match_query(input);
+        database
+            .count_search_results(&query, &SearchFilters::default())
+            .await
+            .unwrap_or_else(|e| panic!("{} の検索に失敗しました: {}", input, e));
+    }
+}
+
+#[tokio::test]
+async fn test_検索ページが結果と空状態の候補を表示する() {
+    let (_temp_dir, database) = test_database().await;
+    database
+        .create_post(about("tokio", "tech", "about the tokio runtime"))
+        .await
+        .unwrap();
+    let hits = database
+        .search_posts_with_snippets(
+            &DatabaseService::fts_match_query("tokio runtime"),
+            &SearchFilters::default(),
+        )
+        .await
+        .unwrap();
+    let templates = TemplateService::new_with_theme("default").expect("Failed to load templates");
+
+    let results = SearchPageContext {
+        site_title: "Tobelog".to_string(),
+        site_description: String::new(),
+        query: "tokio runtime".to_string(),
+        category: None,
+        results: hits.into_iter().map(SearchResultSummary::from).collect(),
+        facets: vec![CategoryStat {
+            name: "tech".to_string(),
+            count: 1,
+        }],
+        total_results: 11,
+        page: 1,
+        total_pages: 2,
+        suggested_posts: vec![],
+        suggested_categories: vec![],
+    };
+    let html = templates.render("search.html", &results).unwrap();
+    assert!(html.contains("the <mark>tokio</mark> <mark>runtime</mark>"));
+    assert!(html.contains("/search?q=tokio%20runtime&category=tech"));
+    assert!(html.contains("/search?q=tokio%20runtime&page=2"));
+
+    let empty = SearchPageContext {
+        results: vec![],
+        facets: vec![],
+        total_results: 0,
+        total_pages: 0,
+        suggested_categories: vec![CategoryStat {
+            name: "life".to_string(),
+            count: 3,
+        }],
+        ..results
+    };
+    let html = templates.render("search.html", &empty).unwrap();
+    assert!(
+        html.contains("/category/life"),
+        "空状態にカテゴリの候補がありません"
+    );
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use tobelog::services::seed::SeedService;
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::{
+    BlogStorageService, DatabaseService, MarkdownService, MediaService, StorageBackend,
+    ThemeService,
+};
+
+#[tokio::test]
+async fn test_サンプルデータは不足分だけ追加される() {
+    let database = DatabaseService::in_memory()
+        .await
+        .expect("Failed to create database");
+    let memory = MemoryStorage::new();
+    let storage: Arc<dyn StorageBackend> = Arc::new(memory.clone());
+    let blog_storage = Arc::new(BlogStorageService::new(storage.clone()));
+    let seed = SeedService::new(
+        database.clone(),
+        MarkdownService::new(),
+        MediaService::new(storage.clone(), blog_storage, database.clone()),
+        ThemeService::new(database.clone(), storage),
+    );
+
+    let first = seed.seed().await.expect("Failed to seed");
+    assert_eq!(first.posts, 6);
+    assert_eq!(first.media, 2);
+    assert!(first.themes > 0, "プリセットテーマが作成されるはずです");
+    assert_eq!(
+        memory
+            .file_paths()
+            .iter()
+            .filter(|path| path.contains("/media/images/"))
+            .count(),
+        2
+    );
+
+    let welcome = database
+        .get_post_by_slug("welcome-to-tobelog")
+        .await
+        .unwrap()
+        .expect("sample post");
+    assert!(welcome.featured);
+    assert!(welcome.html_content.contains("<img"));
+    assert!(!welcome.content.contains("{image_url}"));
+    let draft = database
+        .get_post_by_slug("draft-ideas")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!draft.published);
+
+    let second = seed.seed().await.expect("Failed to seed again");
+    assert_eq!(second.posts, 0);
+    assert_eq!(second.media, 0, "同じ画像は重複して保存しないはずです");
+    assert_eq!(second.themes, 0);
+    assert_eq!(second.skipped_posts, 6);
+}
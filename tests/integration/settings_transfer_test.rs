@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use tempfile::tempdir;
+use tobelog::models::{SettingsDocument, ThemeSettings, SETTINGS_SCHEMA_VERSION};
+use tobelog::services::{DatabaseService, DropboxClient, ThemeService};
+
+async fn theme_service(path: &std::path::Path) -> ThemeService {
+    let database = DatabaseService::new(&format!("sqlite:{}", path.to_str().unwrap()))
+        .await
+        .expect("Failed to create database");
+    ThemeService::new(database, Arc::new(DropboxClient::new("test".to_string())))
+}
+
+#[tokio::test]
+async fn test_設定をエクスポートして別環境にインポートできる() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let staging = theme_service(&temp_dir.path().join("staging.db")).await;
+    let production = theme_service(&temp_dir.path().join("production.db")).await;
+
+    let mut site_config = staging.get_site_config().await.unwrap();
+    site_config.site_title = "Staging Blog".to_string();
+    site_config.author_name = "Tobe".to_string();
+    staging.update_site_config(site_config).await.unwrap();
+
+    let mut document = staging.export_settings().await.unwrap();
+    assert_eq!(document.schema_version, SETTINGS_SCHEMA_VERSION);
+
+    // ステージングで作ったテーマを有効化した状態で持ち込む
+    for theme in &mut document.themes {
+        theme.is_active = false;
+    }
+    document.themes.push(ThemeSettings {
+        name: "autumn".to_string(),
+        display_name: "Autumn".to_string(),
+        primary_color: "#B45309".to_string(),
+        is_active: true,
+        ..Default::default()
+    });
+
+    // JSON経由で往復できること
+    let json = serde_json::to_string(&document).unwrap();
+    let document: SettingsDocument = serde_json::from_str(&json).unwrap();
+
+    let result = production.import_settings(document.clone()).await.unwrap();
+    // 既定テーマは両環境にあるため更新、autumnは新規作成になる
+    assert_eq!(result.themes_created, 1);
+    assert_eq!(result.themes_updated, document.themes.len() - 1);
+    assert_eq!(result.active_theme.as_deref(), Some("autumn"));
+
+    let imported = production.get_site_config().await.unwrap();
+    assert_eq!(imported.site_title, "Staging Blog");
+    assert_eq!(imported.author_name, "Tobe");
+
+    let autumn = production.get_theme("autumn").await.unwrap().unwrap();
+    assert_eq!(autumn.primary_color, "#B45309");
+
+    // 再インポートは更新として扱われる
+    let result = production.import_settings(document.clone()).await.unwrap();
+    assert_eq!(result.themes_created, 0);
+    assert_eq!(result.themes_updated, document.themes.len());
+}
+
+#[tokio::test]
+async fn test_異なるスキーマバージョンはインポートできない() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let service = theme_service(&temp_dir.path().join("settings.db")).await;
+
+    let mut document = service.export_settings().await.unwrap();
+    document.schema_version = SETTINGS_SCHEMA_VERSION + 1;
+
+    assert!(service.import_settings(document).await.is_err());
+}
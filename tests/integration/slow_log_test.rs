@@ -0,0 +1,76 @@
+use axum::extract::State;
+use std::path::PathBuf;
+use std::time::Duration;
+use tobelog::handlers::performance::{get_slow_log, PerformanceState};
+use tobelog::models::SlowLogKind;
+use tobelog::services::{
+    CacheService, DatabasePoolConfig, DatabaseService, SlowLog, SlowLogConfig, SlowQueryLayer,
+};
+use tracing::Instrument;
+use tracing_subscriber::layer::SubscriberExt;
+
+#[tokio::test]
+async fn test_閾値を超えたクエリがリクエストid付きで記録される() {
+    // A zero threshold makes every statement slow
+    let slow_log = SlowLog::new(SlowLogConfig {
+        query_threshold: Duration::ZERO,
+        ..Default::default()
+    });
+    // sqlx runs statements on its own worker thread, which only sees the global subscriber
+    let subscriber = tracing_subscriber::registry().with(SlowQueryLayer::new(slow_log.clone()));
+    tracing::subscriber::set_global_default(subscriber).expect("Failed to set subscriber");
+
+    let pool_config = DatabasePoolConfig {
+        max_connections: 1,
+        slow_statement_threshold: slow_log.query_threshold(),
+        ..Default::default()
+    };
+    let database = DatabaseService::with_pool_config("sqlite::memory:", &pool_config)
+        .await
+        .expect("Failed to create database");
+    let before = slow_log.counts().0;
+
+    database
+        .get_post_by_slug("missing-post")
+        .instrument(tracing::info_span!(
+            "http.request",
+            request_id = "slow-req-1"
+        ))
+        .await
+        .expect("Failed to query post");
+
+    let report = slow_log.report();
+    assert!(report.slow_queries > before);
+    // Other tests may run queries at the same time
+    let entry = report
+        .entries
+        .iter()
+        .find(|entry| entry.request_id.as_deref() == Some("slow-req-1"))
+        .expect("Query of the request was not recorded");
+    assert!(entry.target.contains("FROM posts"), "{}", entry.target);
+    assert!(!entry.target.contains("missing-post"));
+    assert_eq!(entry.kind, SlowLogKind::Query);
+}
+
+#[tokio::test]
+async fn test_slowlogエンドポイントは新しい順に返す() {
+    let slow_log = SlowLog::new(SlowLogConfig::default());
+    slow_log.record_request("GET /posts/:slug", 200, Duration::from_secs(2), None);
+    slow_log.record_request("GET /api/search", 500, Duration::from_secs(3), None);
+    slow_log.record_request("GET /", 200, Duration::from_millis(10), None);
+
+    let state = PerformanceState {
+        cache: CacheService::new(),
+        load_test_report: PathBuf::from("missing-load-test.json"),
+        slow_log,
+    };
+    let body = get_slow_log(State(state)).await.0;
+
+    assert_eq!(body["data"]["slow_requests"], 2);
+    assert_eq!(body["data"]["request_threshold_ms"], 1000);
+    let entries = body["data"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["target"], "GET /api/search");
+    assert_eq!(entries[0]["status"], 500);
+    assert_eq!(entries[0]["kind"], "request");
+}
@@ -0,0 +1,102 @@
+use tobelog::models::{CreatePost, UpdatePost};
+
+use super::{create_post, test_database};
+
+fn categorized(slug: &str, category: Option<&str>, published: bool) -> CreatePost {
+    CreatePost {
+        category: category.map(str::to_string),
+        published,
+        ..create_post(slug)
+    }
+}
+
+fn publish(published: bool, category: Option<&str>) -> UpdatePost {
+    UpdatePost {
+        title: None,
+        content: None,
+        html_content: None,
+        excerpt: None,
+        category: category.map(str::to_string),
+        tags: None,
+        published: Some(published),
+        featured: Some(true),
+        author: None,
+        dropbox_path: None,
+    }
+}
+
+#[tokio::test]
+async fn test_記事の作成公開削除で統計が更新される() {
+    let (_temp_dir, database) = test_database().await;
+
+    let rust = database
+        .create_post(categorized("rust", Some("tech"), true))
+        .await
+        .unwrap();
+    let draft = database
+        .create_post(categorized("draft", Some("tech"), false))
+        .await
+        .unwrap();
+    database
+        .create_post(categorized("diary", Some("life"), true))
+        .await
+        .unwrap();
+
+    let stats = database.get_post_stats().await.unwrap();
+    assert_eq!(
+        (stats.total_posts, stats.published_posts, stats.draft_posts),
+        (3, 2, 1)
+    );
+    assert_eq!(stats.categories.len(), 2);
+
+    // 下書きを公開し、公開記事をカテゴリごと移動する
+    database
+        .update_post(draft.id, publish(true, None))
+        .await
+        .unwrap();
+    database
+        .update_post(rust.id, publish(true, Some("life")))
+        .await
+        .unwrap();
+    let stats = database.get_post_stats().await.unwrap();
+    assert_eq!((stats.published_posts, stats.featured_posts), (3, 2));
+    let counts: Vec<_> = stats
+        .categories
+        .iter()
+        .map(|c| (c.name.as_str(), c.count))
+        .collect();
+    assert_eq!(counts, [("life", 2), ("tech", 1)]);
+
+    database.delete_post(draft.id).await.unwrap();
+    let stats = database.get_post_stats().await.unwrap();
+    assert_eq!((stats.total_posts, stats.featured_posts), (2, 1));
+    assert_eq!(stats.categories.len(), 1, "空のカテゴリは消えるはずです");
+
+    assert_eq!(
+        database.rebuild_stats().await.unwrap(),
+        stats,
+        "トリガーの集計は再集計と一致するはずです"
+    );
+}
+
+#[tokio::test]
+async fn test_ずれた統計を再集計で修復する() {
+    let (_temp_dir, database) = test_database().await;
+    database
+        .create_post(categorized("one", Some("tech"), true))
+        .await
+        .unwrap();
+
+    sqlx::query("UPDATE stats SET total_posts = 42")
+        .execute(database.pool())
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM category_stats")
+        .execute(database.pool())
+        .await
+        .unwrap();
+
+    let stats = database.rebuild_stats().await.unwrap();
+    assert_eq!((stats.total_posts, stats.published_posts), (1, 1));
+    assert_eq!(stats.categories[0].name, "tech");
+}
@@ -0,0 +1,64 @@
+use tobelog::models::CreatePost;
+use tobelog::services::storage::LocalStorage;
+use tobelog::services::{StorageBackend, StorageMigrationService};
+use tobelog::Config;
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_ストレージ移行でファイルを検証付きでコピーしパスを書き換える() {
+    let (temp_dir, database) = test_database().await;
+
+    let post = database
+        .create_post(CreatePost {
+            title: "Hello".to_string(),
+            content: "Hello".to_string(),
+            html_content: "<p>Hello</p>".to_string(),
+            dropbox_path: "/BlogStorage/posts/hello.md".to_string(),
+            ..create_post("hello")
+        })
+        .await
+        .expect("Failed to create post");
+
+    let source = LocalStorage::new(temp_dir.path().join("source"));
+    let target = LocalStorage::new(temp_dir.path().join("target"));
+    source
+        .upload_file("/BlogStorage/posts/hello.md", "# Hello")
+        .await
+        .unwrap();
+    source
+        .upload_binary_file(
+            "/BlogStorage/media/images/2024/01/a.png",
+            &[0x89, 0x50, 0x4e],
+        )
+        .await
+        .unwrap();
+
+    let migrator = StorageMigrationService::new(database.clone(), Config::from_env().unwrap());
+    let report = migrator
+        .migrate_between(&source, &target, "/BlogStorage", "/Blog")
+        .await
+        .expect("Migration failed");
+    assert_eq!(report.progress.total_files, 2);
+    assert_eq!(report.progress.copied_files, 2);
+    assert_eq!(report.progress.copied_bytes, 10);
+    assert_eq!(report.rewritten_paths, 1);
+
+    assert_eq!(
+        target
+            .download_text_file("/Blog/posts/hello.md")
+            .await
+            .unwrap(),
+        "# Hello"
+    );
+    let post = database.get_post_by_id(post.id).await.unwrap().unwrap();
+    assert_eq!(post.dropbox_path, "/Blog/posts/hello.md");
+
+    // 再実行では同じ内容のファイルをコピーしない
+    let rerun = migrator
+        .migrate_between(&source, &target, "/BlogStorage", "/Blog")
+        .await
+        .expect("Migration failed");
+    assert_eq!(rerun.progress.copied_files, 0);
+    assert_eq!(rerun.progress.skipped_files, 2);
+}
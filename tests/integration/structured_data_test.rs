@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use tobelog::models::CreatePost;
+use tobelog::services::{Breadcrumb, StructuredDataService, TemplateService};
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_記事ページの構造化データにパンくずと記事情報が含まれる() {
+    let (_temp_dir, database) = test_database().await;
+    let post = database
+        .create_post(CreatePost {
+            title: "Hello </script>".to_string(),
+            excerpt: Some("挨拶の記事".to_string()),
+            category: Some("tech".to_string()),
+            tags: vec!["rust".to_string(), "web".to_string()],
+            author: Some("山田".to_string()),
+            ..create_post("hello")
+        })
+        .await
+        .unwrap();
+
+    let service = StructuredDataService::new(database, "https://blog.example.com/".to_string());
+    let canonical = format!("https://blog.example.com{}", post.get_url_path());
+    let script = service
+        .post(&post, &canonical, Some("https://blog.example.com/og.png"))
+        .await
+        .unwrap();
+    assert!(
+        !script.contains("</script>"),
+        "scriptタグを閉じてしまいます"
+    );
+
+    let data: serde_json::Value = serde_json::from_str(&script).unwrap();
+    assert_eq!(data["@context"], "https://schema.org");
+    let posting = &data["@graph"][0];
+    assert_eq!(posting["@type"], "BlogPosting");
+    assert_eq!(posting["headline"], "Hello </script>");
+    assert_eq!(posting["description"], "挨拶の記事");
+    assert_eq!(posting["author"]["name"], "山田");
+    assert_eq!(posting["keywords"], "rust, web");
+    assert_eq!(posting["mainEntityOfPage"]["@id"], canonical.as_str());
+
+    let crumbs = data["@graph"][1]["itemListElement"].as_array().unwrap();
+    let names: Vec<&str> = crumbs.iter().map(|c| c["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["ホーム", "tech", "Hello </script>"]);
+    assert_eq!(crumbs[1]["item"], "https://blog.example.com/category/tech");
+
+    let home: serde_json::Value = serde_json::from_str(&service.home().await.unwrap()).unwrap();
+    let action = &home["@graph"][0]["potentialAction"];
+    assert_eq!(action["@type"], "SearchAction");
+    assert_eq!(
+        action["target"]["urlTemplate"],
+        "https://blog.example.com/search?q={search_term_string}"
+    );
+
+    let listing: serde_json::Value = serde_json::from_str(
+        &service
+            .listing(&[Breadcrumb::translated("common.tags", "/tags")])
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(listing["@graph"][0]["itemListElement"][1]["name"], "タグ");
+
+    // テンプレートではエスケープせずにscript要素へ埋め込む
+    let templates = TemplateService::new_with_theme("default").expect("Failed to load templates");
+    let mut extra = HashMap::new();
+    extra.insert(
+        "structured_data".to_string(),
+        tera::Value::String(script.clone()),
+    );
+    let context = serde_json::json!({
+        "site_title": "Tobelog",
+        "site_description": "",
+        "tags": [],
+    });
+    let html = templates
+        .render_with_context("tags.html", &context, extra)
+        .unwrap();
+    assert!(html.contains(&format!(
+        "<script type=\"application/ld+json\">{}</script>",
+        script
+    )));
+}
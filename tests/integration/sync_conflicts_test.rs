@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use tobelog::models::{CreatePost, SyncState, UpdatePost};
+use tobelog::services::blog_storage::{BlogPost, BlogPostMetadata};
+use tobelog::services::{BlogStorageService, DropboxClient, MarkdownService, SyncConflictService};
+
+use super::{create_post, test_database};
+
+fn dropbox_copy(slug: &str, content: &str) -> BlogPost {
+    BlogPost {
+        metadata: BlogPostMetadata {
+            title: "Sync".to_string(),
+            slug: slug.to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            category: None,
+            tags: vec![],
+            published: true,
+            author: None,
+            excerpt: None,
+            extra: Default::default(),
+        },
+        content: content.to_string(),
+        dropbox_path: format!("/BlogStorage/posts/{}.md", slug),
+        file_metadata: None,
+    }
+}
+
+fn content_update(content: &str) -> UpdatePost {
+    UpdatePost {
+        title: None,
+        content: Some(content.to_string()),
+        html_content: None,
+        excerpt: None,
+        category: None,
+        tags: None,
+        published: None,
+        featured: None,
+        author: None,
+        dropbox_path: None,
+    }
+}
+
+#[tokio::test]
+async fn test_前回同期からの変更で競合を判定する() {
+    let (_temp_dir, database) = test_database().await;
+
+    let post = database
+        .create_post(CreatePost {
+            title: "Sync".to_string(),
+            content: "original".to_string(),
+            html_content: "<p>original</p>".to_string(),
+            dropbox_path: "/BlogStorage/posts/sync.md".to_string(),
+            ..create_post("sync")
+        })
+        .await
+        .expect("Failed to create post");
+
+    // 比較だけなので Dropbox には接続しない
+    let blog_storage = Arc::new(BlogStorageService::new(Arc::new(DropboxClient::new(
+        "unused".to_string(),
+    ))));
+    let service = SyncConflictService::new(database.clone(), blog_storage, MarkdownService::new());
+
+    // 同じ内容なら同期済みとして基準ハッシュが記録される
+    let status = service
+        .inspect(&post, &dropbox_copy("sync", "original\r\n"))
+        .await
+        .unwrap();
+    assert_eq!(status.state, SyncState::InSync);
+    assert!(status.actions.is_empty());
+    assert!(database
+        .get_post_sync_hash(post.id)
+        .await
+        .unwrap()
+        .is_some());
+
+    // Dropbox 側だけの変更は通常の同期対象
+    let status = service
+        .inspect(&post, &dropbox_copy("sync", "edited in dropbox"))
+        .await
+        .unwrap();
+    assert_eq!(status.state, SyncState::DropboxAhead);
+
+    // 両方が変更されていれば競合
+    let post = database
+        .update_post(post.id, content_update("edited in db"))
+        .await
+        .unwrap()
+        .unwrap();
+    let status = service
+        .inspect(&post, &dropbox_copy("sync", "original"))
+        .await
+        .unwrap();
+    assert_eq!(status.state, SyncState::DatabaseAhead);
+
+    let status = service
+        .inspect(&post, &dropbox_copy("sync", "edited in dropbox"))
+        .await
+        .unwrap();
+    assert_eq!(status.state, SyncState::Conflict);
+    assert_eq!(status.actions.len(), 3);
+}
@@ -0,0 +1,145 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::put,
+    Router,
+};
+use tobelog::handlers::api::{self, SyncDropboxRequest};
+use tobelog::services::blog_storage::BlogPost;
+use tobelog::services::storage::MemoryStorage;
+use tobelog::services::StorageBackend;
+use tower::ServiceExt;
+
+use super::create_post;
+use super::mock_storage_test::fixture_state;
+
+#[tokio::test]
+async fn test_フロントマターのクロスポスト情報を同期して書き戻す() {
+    let storage = MemoryStorage::new();
+    let state = fixture_state(storage.clone()).await;
+    storage
+        .upload_file(
+            "/BlogStorage/posts/cross.md",
+            "---\ntitle: 転載記事\nslug: cross\npublished: true\n\
+             canonical_url: https://zenn.dev/me/articles/cross\n\
+             syndication_links:\n  - https://dev.to/me/cross\n  - not a url\n\
+             series: 連載\n---\n\n本文",
+        )
+        .await
+        .unwrap();
+
+    api::run_dropbox_sync(
+        &state,
+        SyncDropboxRequest {
+            force: None,
+            paths: None,
+            slugs: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let post = state
+        .database
+        .get_post_by_slug("cross")
+        .await
+        .unwrap()
+        .expect("synced post");
+    assert_eq!(
+        post.canonical_url.as_deref(),
+        Some("https://zenn.dev/me/articles/cross")
+    );
+    assert_eq!(
+        post.get_syndication_links(),
+        ["https://dev.to/me/cross"],
+        "URLでない値は無視するはずです"
+    );
+
+    let extra = BlogPost::from_post(&post).metadata.extra;
+    assert_eq!(
+        extra["canonical_url"].as_str(),
+        Some("https://zenn.dev/me/articles/cross")
+    );
+    assert_eq!(extra["series"].as_str(), Some("連載"));
+}
+
+#[tokio::test]
+async fn test_apiでクロスポスト情報を更新して解除できる() {
+    let storage = MemoryStorage::new();
+    let state = fixture_state(storage.clone()).await;
+    state
+        .blog_storage
+        .initialize_blog_structure()
+        .await
+        .unwrap();
+    let post = state
+        .database
+        .create_post(tobelog::models::CreatePost {
+            title: "ミラー".to_string(),
+            content: "本文".to_string(),
+            html_content: "<p>本文</p>".to_string(),
+            dropbox_path: "/BlogStorage/posts/mirror.md".to_string(),
+            ..create_post("mirror")
+        })
+        .await
+        .unwrap();
+    let app = Router::new()
+        .route("/api/posts/:slug", put(api::update_post_api))
+        .with_state(state.clone());
+    let update = |body: &'static str| {
+        Request::put("/api/posts/mirror")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(update(r#"{"canonical_url": "javascript:alert(1)"}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(update(
+            r#"{"canonical_url": " https://dev.to/me/mirror ", "syndication_links": ["https://zenn.dev/me/mirror"]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let post = state
+        .database
+        .get_post_by_id(post.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        post.canonical_url.as_deref(),
+        Some("https://dev.to/me/mirror")
+    );
+    assert_eq!(post.get_syndication_links(), ["https://zenn.dev/me/mirror"]);
+    let file = storage
+        .download_file("/BlogStorage/posts/mirror.md")
+        .await
+        .unwrap();
+    let file = String::from_utf8(file).unwrap();
+    assert!(file.contains("canonical_url: https://dev.to/me/mirror"));
+
+    // 空文字で正規URLを解除し、掲載先は指定しなければそのまま
+    let response = app
+        .oneshot(update(r#"{"canonical_url": ""}"#))
+        .await
+        .unwrap();
+    let status = response.status();
+    let raw = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&raw));
+    let post = state
+        .database
+        .get_post_by_id(post.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.canonical_url, None);
+    assert_eq!(post.get_syndication_links(), ["https://zenn.dev/me/mirror"]);
+}
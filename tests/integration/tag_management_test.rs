@@ -0,0 +1,117 @@
+use tobelog::models::{CreatePost, TagNormalization, UpdatePost};
+use tobelog::services::DatabaseService;
+
+use super::{create_post, test_database};
+
+fn tagged(slug: &str, tags: &[&str]) -> CreatePost {
+    CreatePost {
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..create_post(slug)
+    }
+}
+
+async fn tags_of(database: &DatabaseService, slug: &str) -> Vec<String> {
+    database
+        .get_post_by_slug(slug)
+        .await
+        .unwrap()
+        .expect("post exists")
+        .get_tags()
+}
+
+#[tokio::test]
+async fn test_タグの名前変更_統合_削除が全記事に反映される() {
+    let (_temp_dir, database) = test_database().await;
+
+    for post in [
+        tagged("a", &["JS", "web"]),
+        tagged("b", &["js", "javascript"]),
+        tagged("c", &["rust"]),
+    ] {
+        database
+            .create_post(post)
+            .await
+            .expect("Failed to create post");
+    }
+
+    let affected = database
+        .rewrite_tags(&["js".to_string()], Some("javascript"))
+        .await
+        .unwrap();
+    assert_eq!(
+        affected,
+        vec!["b"],
+        "大文字小文字の異なるタグは変更しないはずです"
+    );
+    assert_eq!(tags_of(&database, "b").await, vec!["javascript"]);
+
+    let mut affected = database
+        .rewrite_tags(&["JS".to_string(), "web".to_string()], Some("frontend"))
+        .await
+        .unwrap();
+    affected.sort();
+    assert_eq!(affected, vec!["a"]);
+    assert_eq!(tags_of(&database, "a").await, vec!["frontend"]);
+
+    let affected = database
+        .rewrite_tags(&["rust".to_string()], None)
+        .await
+        .unwrap();
+    assert_eq!(affected, vec!["c"]);
+    assert!(tags_of(&database, "c").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_タグとカテゴリが保存時に正規化される() {
+    let (_temp_dir, database) = test_database().await;
+    let database = database.with_tag_normalization(TagNormalization {
+        lowercase_tags: true,
+        lowercase_categories: false,
+    });
+
+    let alias = database
+        .upsert_tag_alias(" JS ", "javascript")
+        .await
+        .unwrap();
+    assert_eq!(alias.alias, "js");
+
+    let mut post = tagged("normalized", &["Js", " Web  Dev ", "javascript", ""]);
+    post.category = Some(" Tech ".to_string());
+    let post = database.create_post(post).await.unwrap();
+    assert_eq!(post.get_tags(), vec!["javascript", "web dev"]);
+    assert_eq!(post.category.as_deref(), Some("Tech"));
+
+    let updated = database
+        .update_post(
+            post.id,
+            UpdatePost {
+                title: None,
+                content: None,
+                html_content: None,
+                excerpt: None,
+                category: Some("js".to_string()),
+                tags: Some(vec!["RUST".to_string()]),
+                published: None,
+                featured: None,
+                author: None,
+                dropbox_path: None,
+            },
+        )
+        .await
+        .unwrap()
+        .expect("post exists");
+    assert_eq!(updated.get_tags(), vec!["rust"]);
+    assert_eq!(updated.category.as_deref(), Some("javascript"));
+
+    database
+        .create_post(tagged("draft", &["CLI", "Rust"]))
+        .await
+        .unwrap();
+    assert_eq!(
+        database.list_tag_names().await.unwrap(),
+        vec!["cli", "rust"]
+    );
+
+    assert!(database.delete_tag_alias("JS").await.unwrap());
+    assert!(database.list_tag_aliases().await.unwrap().is_empty());
+}
@@ -0,0 +1,91 @@
+use tobelog::models::{CreatePost, UpdatePost};
+use tobelog::services::{MarkdownService, TemplateService, VersionService};
+
+use super::{create_post, test_database};
+
+#[tokio::test]
+async fn test_保存済みバージョンと現在の記事の差分を表示する() {
+    let (_temp_dir, database) = test_database().await;
+    let versions = VersionService::new(database.clone(), MarkdownService::new());
+
+    let original = database
+        .create_post(CreatePost {
+            title: "初版".to_string(),
+            content: "共通の行\n古い行".to_string(),
+            html_content: "<p>共通の行</p>".to_string(),
+            ..create_post("versioned")
+        })
+        .await
+        .expect("Failed to create post");
+    versions
+        .create_version(&original, Some("初版を公開".to_string()))
+        .await
+        .expect("Failed to create version");
+
+    let current = database
+        .update_post(
+            original.id,
+            UpdatePost {
+                title: Some("改訂版".to_string()),
+                content: Some("共通の行\n新しい行".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let stored = versions
+        .get_version(original.id, original.version)
+        .await
+        .unwrap()
+        .expect("Stored version should exist");
+    let diff = versions.diff_against_post(&stored, &current);
+    assert_eq!(diff.version_from, original.version);
+    assert_eq!(diff.version_to, current.version);
+    assert!(diff.title_diff.is_some());
+    let lines: Vec<&str> = diff.content_diff.lines().collect();
+    assert!(lines.contains(&"  共通の行"));
+    assert!(lines.contains(&"- 古い行"));
+    assert!(lines.contains(&"+ 新しい行"));
+
+    // 履歴ページには各バージョンへのリンクと復元ボタンが並ぶ
+    let history = versions.get_version_history(original.id).await.unwrap();
+    let templates = TemplateService::new().expect("Failed to load templates");
+    let html = templates
+        .render(
+            "admin/versions.html",
+            &serde_json::json!({
+                "page_title": "History",
+                "current_version": current.version,
+                "history": history,
+            }),
+        )
+        .expect("Failed to render version history");
+    assert!(html.contains(&format!(
+        "/admin/posts/versioned/versions/{}",
+        original.version
+    )));
+    assert!(html.contains("初版を公開"));
+    assert!(html.contains("restoreVersion('versioned'"));
+
+    let html = templates
+        .render(
+            "admin/version.html",
+            &serde_json::json!({
+                "page_title": "Version",
+                "post_slug": "versioned",
+                "current_version": current.version,
+                "version": stored,
+                "diff": diff,
+                "title_lines": null,
+                "content_lines": [
+                    { "kind": "removed", "text": "古い行" },
+                    { "kind": "added", "text": "新しい行" },
+                ],
+            }),
+        )
+        .expect("Failed to render version");
+    assert!(html.contains("+ 新しい行"));
+    assert!(html.contains("- 古い行"));
+}
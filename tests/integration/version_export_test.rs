@@ -0,0 +1,166 @@
+use axum::body::to_bytes;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use tempfile::tempdir;
+use tobelog::handlers::version::{export_versions, import_versions, VersionState};
+use tobelog::models::{CreatePost, UpdatePost, VersionRetentionPolicy};
+use tobelog::services::version::parse_versions_jsonl;
+use tobelog::services::{CacheService, DatabaseService, MarkdownService, VersionService};
+
+use super::create_post;
+
+async fn instance(dir: &tempfile::TempDir, name: &str) -> VersionState {
+    let db_path = dir.path().join(name);
+    let database = DatabaseService::new(&format!("sqlite:{}", db_path.to_str().unwrap()))
+        .await
+        .expect("Failed to create database");
+    VersionState {
+        version_service: VersionService::new(database.clone(), MarkdownService::new()),
+        database,
+        cache: CacheService::new(),
+        retention: VersionRetentionPolicy::default(),
+    }
+}
+
+fn tagged_post() -> CreatePost {
+    CreatePost {
+        tags: vec!["rust".to_string()],
+        ..create_post("history")
+    }
+}
+
+#[tokio::test]
+async fn test_バージョン履歴を書き出して別のインスタンスに取り込める() {
+    let dir = tempdir().expect("Failed to create temp dir");
+    let source = instance(&dir, "source.db").await;
+
+    // 元のインスタンスで 3 つのバージョンを保存する
+    let mut post = source.database.create_post(tagged_post()).await.unwrap();
+    for content in ["第二版", "第三版"] {
+        source
+            .version_service
+            .create_version(&post, Some(format!("v{}", post.version)))
+            .await
+            .unwrap();
+        post = source
+            .database
+            .update_post(
+                post.id,
+                UpdatePost {
+                    content: Some(content.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    source
+        .version_service
+        .create_version(&post, Some("v3".to_string()))
+        .await
+        .unwrap();
+
+    let response = export_versions(Path("history".to_string()), State(source.clone()))
+        .await
+        .expect("Failed to export versions");
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/x-ndjson"
+    );
+    let jsonl = String::from_utf8(
+        to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec(),
+    )
+    .unwrap();
+    assert_eq!(jsonl.lines().count(), 3);
+    let exported = parse_versions_jsonl(&jsonl).unwrap();
+    assert_eq!(
+        exported.iter().map(|v| v.version).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+
+    // 移行先では同じスラッグの記事が別の ID で作られている
+    let target = instance(&dir, "target.db").await;
+    let migrated = target.database.create_post(tagged_post()).await.unwrap();
+    assert_ne!(migrated.id, post.id);
+
+    let report = import_versions(
+        Path("history".to_string()),
+        State(target.clone()),
+        jsonl.clone(),
+    )
+    .await
+    .expect("Failed to import versions")
+    .0
+    .data;
+    assert_eq!((report.imported, report.skipped), (3, 0));
+    // 取り込んだバージョンと番号が重ならないよう記事のバージョンが進む
+    assert_eq!(report.post_version, 4);
+
+    let history = target
+        .version_service
+        .get_version_history(migrated.id)
+        .await
+        .unwrap();
+    assert_eq!(history.total_versions, 3);
+    let imported = target
+        .version_service
+        .get_version(migrated.id, 2)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(imported.content, "第二版");
+    assert_eq!(imported.change_summary.as_deref(), Some("v2"));
+    assert_eq!(imported.created_at, exported[1].created_at);
+    assert_eq!(imported.tags, ["rust"]);
+
+    // もう一度取り込んでも重複しない
+    let report = import_versions(Path("history".to_string()), State(target.clone()), jsonl)
+        .await
+        .unwrap()
+        .0
+        .data;
+    assert_eq!((report.imported, report.skipped), (0, 3));
+
+    // 次のスナップショットは取り込んだ履歴の後に続く
+    let current = target
+        .database
+        .get_post_by_slug("history")
+        .await
+        .unwrap()
+        .unwrap();
+    target
+        .version_service
+        .create_version(&current, None)
+        .await
+        .expect("New snapshot should not collide with imported versions");
+}
+
+#[tokio::test]
+async fn test_不正な履歴ファイルは取り込まない() {
+    let dir = tempdir().expect("Failed to create temp dir");
+    let state = instance(&dir, "invalid.db").await;
+    state.database.create_post(tagged_post()).await.unwrap();
+
+    let (status, body) = import_versions(
+        Path("history".to_string()),
+        State(state.clone()),
+        "\n{\"version\": 1}\n".to_string(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.0.message.contains("line 2"), "{}", body.0.message);
+
+    let (status, _) = import_versions(
+        Path("missing".to_string()),
+        State(state.clone()),
+        String::new(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
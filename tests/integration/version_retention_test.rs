@@ -0,0 +1,86 @@
+use chrono::{Duration, Utc};
+use tobelog::models::VersionRetentionPolicy;
+use tobelog::services::{MarkdownService, VersionService};
+
+use super::{create_post, test_database};
+
+async fn remaining_versions(versions: &VersionService, post_id: uuid::Uuid) -> Vec<i32> {
+    versions
+        .get_version_history(post_id)
+        .await
+        .unwrap()
+        .versions
+        .into_iter()
+        .map(|version| version.version)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_保持ポリシーで古いバージョンを全記事から削除する() {
+    let (_temp_dir, database) = test_database().await;
+    let versions = VersionService::new(database.clone(), MarkdownService::new());
+
+    let mut long_lived = database
+        .create_post(create_post("long-lived"))
+        .await
+        .unwrap();
+    for version in 1..=5 {
+        long_lived.version = version;
+        versions.create_version(&long_lived, None).await.unwrap();
+    }
+    let mut short = database.create_post(create_post("short")).await.unwrap();
+    for version in 1..=2 {
+        short.version = version;
+        versions.create_version(&short, None).await.unwrap();
+    }
+
+    // 1〜3版は 100 日前、4・5版と short は最近保存された
+    sqlx::query("UPDATE post_versions SET created_at = ? WHERE post_id = ? AND version <= 3")
+        .bind((Utc::now() - Duration::days(100)).to_rfc3339())
+        .bind(long_lived.id.to_string())
+        .execute(database.pool())
+        .await
+        .unwrap();
+
+    // 空のポリシーは何も消さない
+    let report = versions
+        .apply_retention(&VersionRetentionPolicy::default())
+        .await
+        .unwrap();
+    assert_eq!(report.deleted_versions, 0);
+
+    // 最新1版か 30 日以内の版のどちらかに当てはまれば残る
+    let report = versions
+        .apply_retention(&VersionRetentionPolicy {
+            keep_versions: Some(1),
+            max_age_days: Some(30),
+        })
+        .await
+        .unwrap();
+    assert_eq!(report.posts_checked, 2);
+    assert_eq!(report.deleted_versions, 3);
+    assert_eq!(
+        remaining_versions(&versions, long_lived.id).await,
+        vec![5, 4]
+    );
+    assert_eq!(remaining_versions(&versions, short.id).await, vec![2, 1]);
+
+    // 件数だけのポリシーは保存日時を問わない
+    let report = versions
+        .apply_retention(&VersionRetentionPolicy {
+            keep_versions: Some(1),
+            max_age_days: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(report.deleted_versions, 2);
+    assert_eq!(remaining_versions(&versions, long_lived.id).await, vec![5]);
+    assert_eq!(remaining_versions(&versions, short.id).await, vec![2]);
+
+    assert!(VersionRetentionPolicy {
+        keep_versions: Some(0),
+        max_age_days: None,
+    }
+    .validate()
+    .is_err());
+}
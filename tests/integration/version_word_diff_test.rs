@@ -0,0 +1,186 @@
+use tobelog::models::{CreatePost, DiffChangeKind, UpdatePost};
+use tobelog::services::{DatabaseService, MarkdownService, TemplateService, VersionService};
+
+use super::{create_post, test_database};
+
+async fn setup() -> (tempfile::TempDir, DatabaseService, VersionService) {
+    let (temp_dir, database) = test_database().await;
+    let versions = VersionService::new(database.clone(), MarkdownService::new());
+    (temp_dir, database, versions)
+}
+
+fn written(content: &str) -> CreatePost {
+    CreatePost {
+        content: content.to_string(),
+        html_content: String::new(),
+        ..create_post("wording")
+    }
+}
+
+#[tokio::test]
+async fn test_言い回しの変更は単語単位の差分と統計になる() {
+    let (_temp_dir, database, versions) = setup().await;
+    let original = database
+        .create_post(written(
+            "# Title\n\nThe quick brown fox jumps over the lazy dog.\n\n```\nlet x = 1;\n```",
+        ))
+        .await
+        .unwrap();
+    versions.create_version(&original, None).await.unwrap();
+    let first = original.version;
+
+    let updated = database
+        .update_post(
+            original.id,
+            UpdatePost {
+                content: Some(
+                    "# Title\n\nThe quick red fox leaps over the lazy dog.\n\n```\nlet x = 2;\n```\nA new line"
+                        .to_string(),
+                ),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    versions.create_version(&updated, None).await.unwrap();
+
+    let diff = versions
+        .compare_versions(original.id, first, updated.version)
+        .await
+        .unwrap();
+
+    // 行単位: 段落・コード行の置き換えと末尾の追加
+    assert_eq!(diff.stats.lines_removed, 2);
+    assert_eq!(diff.stats.lines_added, 3);
+    // 単語単位: brown/jumps → red/leaps、コード行は全体 (let, x, 数字)、追加行は 3 語
+    assert_eq!(diff.stats.words_removed, 2 + 3);
+    assert_eq!(diff.stats.words_added, 2 + 3 + 3);
+
+    // コードブロック内の行は単語単位の差分にしない
+    assert_eq!(diff.word_diff.len(), 1);
+    let paragraph = &diff.word_diff[0];
+    assert_eq!(paragraph.line, 3);
+    let changed: Vec<(DiffChangeKind, &str)> = paragraph
+        .segments
+        .iter()
+        .filter(|s| s.kind != DiffChangeKind::Unchanged)
+        .map(|s| (s.kind, s.text.as_str()))
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            (DiffChangeKind::Removed, "brown"),
+            (DiffChangeKind::Added, "red"),
+            (DiffChangeKind::Removed, "jumps"),
+            (DiffChangeKind::Added, "leaps"),
+        ]
+    );
+    let rebuilt: String = paragraph
+        .segments
+        .iter()
+        .filter(|s| s.kind != DiffChangeKind::Removed)
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(rebuilt, "The quick red fox leaps over the lazy dog.");
+
+    // 管理画面では変更された単語だけを強調する
+    let stored = versions
+        .get_version(original.id, first)
+        .await
+        .unwrap()
+        .unwrap();
+    let templates = TemplateService::new().expect("Failed to load templates");
+    let html = templates
+        .render(
+            "admin/version.html",
+            &serde_json::json!({
+                "page_title": "Version",
+                "post_slug": "wording",
+                "current_version": updated.version,
+                "version": stored,
+                "diff": diff,
+                "title_lines": null,
+                "content_lines": [],
+            }),
+        )
+        .expect("Failed to render version");
+    assert!(html.contains("<del class=\"bg-red-100 text-red-800\">brown</del>"));
+    assert!(html.contains("<ins class=\"bg-green-100 text-green-800 no-underline\">leaps</ins>"));
+}
+
+#[tokio::test]
+async fn test_日本語の文は文字単位で差分を取る() {
+    let (_temp_dir, database, versions) = setup().await;
+    let original = database
+        .create_post(written("今日は良い天気です。"))
+        .await
+        .unwrap();
+    versions.create_version(&original, None).await.unwrap();
+    let current = database
+        .update_post(
+            original.id,
+            UpdatePost {
+                content: Some("今日は悪い天気です。".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let stored = versions
+        .get_version(original.id, original.version)
+        .await
+        .unwrap()
+        .unwrap();
+    let diff = versions.diff_against_post(&stored, &current);
+    assert_eq!(diff.stats.lines_removed, 1);
+    assert_eq!(diff.stats.lines_added, 1);
+    assert_eq!(diff.stats.words_removed, 1);
+    assert_eq!(diff.stats.words_added, 1);
+    assert_eq!(diff.word_diff.len(), 1);
+    let removed: Vec<&str> = diff.word_diff[0]
+        .segments
+        .iter()
+        .filter(|s| s.kind == DiffChangeKind::Removed)
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(removed, ["良"]);
+}
+
+#[tokio::test]
+async fn test_全面的な書き換えは単語差分にしない() {
+    let (_temp_dir, database, versions) = setup().await;
+    let original = database
+        .create_post(written("Completely different words"))
+        .await
+        .unwrap();
+    versions.create_version(&original, None).await.unwrap();
+    let current = database
+        .update_post(
+            original.id,
+            UpdatePost {
+                content: Some("Nothing shared here at all".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let stored = versions
+        .get_version(original.id, original.version)
+        .await
+        .unwrap()
+        .unwrap();
+    let diff = versions.diff_against_post(&stored, &current);
+    assert!(diff.word_diff.is_empty());
+    assert_eq!(diff.stats.words_removed, 3);
+    assert_eq!(diff.stats.words_added, 5);
+
+    // 同じ内容なら統計はすべて 0
+    let same = versions.diff_against_post(&stored, &original);
+    assert_eq!(same.stats, Default::default());
+    assert!(same.word_diff.is_empty());
+}